@@ -0,0 +1,137 @@
+// Quick actions that run straight from the backend without opening the main window
+// (tray menu entries and other external triggers). Results are reported through a
+// system notification since there may be no visible UI.
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::notifications::{self, NotifyAction};
+use crate::platform;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Action {
+    OpenTool {
+        tool: String,
+    },
+    KillPort {
+        port: u16,
+    },
+    PickColor,
+    StartTimer {
+        seconds: u64,
+        #[serde(default)]
+        label: Option<String>,
+    },
+    CancelTimer,
+}
+
+/// A user-configured action with its menu label, e.g. "Kill port 3000"
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuickAction {
+    pub label: String,
+    #[serde(flatten)]
+    pub action: Action,
+}
+
+// Nothing destructive by default: a stray click in the tray shouldn't kill a process (killing
+// a port can also ask for elevation); users add "Kill port" entries themselves
+pub fn default_quick_actions() -> Vec<QuickAction> {
+    vec![
+        QuickAction {
+            label: "Pick color".to_string(),
+            action: Action::PickColor,
+        },
+    ]
+}
+
+/// Show the main window if it is hidden
+pub fn show_main_window(app: &AppHandle) {
+    let visible = app
+        .get_webview_window("main")
+        .and_then(|w| w.is_visible().ok())
+        .unwrap_or(false);
+    if !visible {
+        crate::toggle_window(app);
+    }
+}
+
+/// Open the main window (if hidden) and switch to a tool
+pub fn open_tool(app: &AppHandle, tool: &str) {
+    show_main_window(app);
+    let _ = app.emit("open-tool", tool.to_string());
+}
+
+/// Run an action, returning a short message to show the user (if any)
+pub async fn run_action(app: &AppHandle, action: &Action) -> Result<Option<String>, String> {
+    match action {
+        Action::OpenTool { tool } => {
+            open_tool(app, tool);
+            Ok(None)
+        }
+        Action::KillPort { port } => {
+            let processes = platform::scan_port_impl(*port).await?;
+            if processes.is_empty() {
+                return Ok(Some(format!("Nothing is listening on port {}", port)));
+            }
+
+            let mut pids: Vec<u32> = processes.iter().map(|p| p.pid).collect();
+            pids.sort_unstable();
+            pids.dedup();
+            // There's no window to ask from, so go straight to the UAC / polkit prompt
+            for pid in &pids {
+                crate::kill_port_process(*pid, Some(true))
+                    .await
+                    .map_err(|e| e.message)?;
+            }
+
+            let names: Vec<&str> = processes.iter().map(|p| p.name.as_str()).collect();
+            Ok(Some(format!(
+                "Killed {} on port {}",
+                names.join(", "),
+                port
+            )))
+        }
+        Action::PickColor => {
+            use tauri_plugin_clipboard_manager::ClipboardExt;
+
+            let window = app
+                .get_webview_window("main")
+                .ok_or("Main window not found")?;
+            let sample_size = crate::color_loupe::sample_size_setting(app);
+            let color = crate::color_loupe::pick_color(window, sample_size).await?;
+            app.clipboard()
+                .write_text(&color)
+                .map_err(|e| format!("Failed to write to clipboard: {}", e))?;
+            Ok(Some(format!("Copied {}", color)))
+        }
+        Action::StartTimer { seconds, label } => {
+            crate::start_timer(app.clone(), *seconds, label.clone()).await?;
+            Ok(Some(format!(
+                "{} started",
+                label.as_deref().unwrap_or("Timer")
+            )))
+        }
+        Action::CancelTimer => {
+            crate::cancel_timer(app.clone())?;
+            Ok(Some("Timer cancelled".to_string()))
+        }
+    }
+}
+
+/// Run an action in the background and report the outcome as a notification
+pub fn dispatch(app: &AppHandle, action: Action) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let (title, body) = match run_action(&app, &action).await {
+            Ok(Some(message)) => ("BunchaTools", message),
+            Ok(None) => return,
+            Err(e) if e == "Cancelled" => return,
+            Err(e) => {
+                log::warn!("Action {:?} failed: {}", action, e);
+                ("Action failed", e)
+            }
+        };
+        notifications::notify(&app, title, &body, NotifyAction::None);
+    });
+}
@@ -0,0 +1,648 @@
+// Screenshot annotation
+// A region of the screen is captured and opened in a frameless overlay window, where arrows,
+// rectangles, text and blurred areas are placed on top of it. The overlay only previews them:
+// when it saves or copies, the annotations are composited onto the captured pixels here, so the
+// result has the screenshot's full resolution. Text is laid out by the overlay (the webview
+// has the fonts) and sent as a PNG sprite.
+// `annotate_image` applies the same annotations to an image file, so a secret in a saved
+// screenshot can be pixelated without another app; there text comes as `Label`s, drawn here
+// with the system's UI font.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use ab_glyph::{Font, FontVec, PxScale, ScaleFont};
+use base64::Engine;
+use image::{imageops, RgbaImage};
+use serde::{Deserialize, Serialize};
+use tauri::{
+    AppHandle, Emitter, LogicalPosition, LogicalSize, Manager, WebviewUrl, WebviewWindowBuilder,
+};
+
+use crate::tool_windows::{self, LABEL_PREFIX};
+use crate::{color_formats, platform, window_placement};
+
+const ANNOTATE_TOOL: &str = "annotate";
+const TOOLBAR_HEIGHT: f64 = 48.0; // logical pixels above the image
+const MAX_SCREEN_FRACTION: f64 = 0.9; // of the work area, larger captures are shown scaled down
+
+// The capture being annotated
+static CAPTURE: Mutex<Option<RgbaImage>> = Mutex::new(None);
+
+// Font for `Label`s, loaded the first time one is drawn
+static FONT: Mutex<Option<Arc<FontVec>>> = Mutex::new(None);
+
+/// Image pixel coordinates
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct Point {
+    pub x: f32,
+    pub y: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct Area {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32, // may be negative when dragged up or left
+    pub height: f32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Annotation {
+    Arrow {
+        from: Point,
+        to: Point,
+        color: String, // anything color_formats::parse accepts
+        width: f32,
+    },
+    Rectangle {
+        area: Area,
+        color: String,
+        width: f32,
+    },
+    Text {
+        at: Point,      // top-left corner of the sprite
+        sprite: String, // PNG, as a data URL or plain base64
+    },
+    Blur {
+        area: Area,
+        strength: f32, // Gaussian sigma in image pixels
+    },
+    Pixelate {
+        area: Area,
+        block: f32, // side of the squares in image pixels
+    },
+    Label {
+        at: Point, // top-left corner of the text
+        text: String,
+        color: String,
+        size: f32, // font size in image pixels
+        #[serde(default)]
+        background: Option<String>, // box behind the text, so it reads on any screenshot
+    },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AnnotationSession {
+    pub width: u32, // captured image, physical pixels
+    pub height: u32,
+}
+
+/// Pixel bounds of `area`, clamped to the image
+fn area_bounds(area: &Area, image: &RgbaImage) -> Option<(u32, u32, u32, u32)> {
+    let (x0, x1) = (
+        area.x.min(area.x + area.width),
+        area.x.max(area.x + area.width),
+    );
+    let (y0, y1) = (
+        area.y.min(area.y + area.height),
+        area.y.max(area.y + area.height),
+    );
+    let left = x0.max(0.0).floor() as u32;
+    let top = y0.max(0.0).floor() as u32;
+    let right = (x1.ceil().max(0.0) as u32).min(image.width());
+    let bottom = (y1.ceil().max(0.0) as u32).min(image.height());
+    (right > left && bottom > top).then_some((left, top, right - left, bottom - top))
+}
+
+/// Blend `color` over the pixel at (x, y) with `coverage` in 0..=1
+fn blend(image: &mut RgbaImage, x: i64, y: i64, color: [u8; 3], coverage: f32) {
+    if x < 0 || y < 0 || x >= image.width() as i64 || y >= image.height() as i64 {
+        return;
+    }
+    let pixel = image.get_pixel_mut(x as u32, y as u32);
+    for (channel, &c) in pixel.0.iter_mut().zip(color.iter()) {
+        *channel = (*channel as f32 + (c as f32 - *channel as f32) * coverage).round() as u8;
+    }
+}
+
+fn distance_to_segment(p: Point, a: Point, b: Point) -> f32 {
+    let (dx, dy) = (b.x - a.x, b.y - a.y);
+    let length_sq = dx * dx + dy * dy;
+    let t = if length_sq == 0.0 {
+        0.0
+    } else {
+        (((p.x - a.x) * dx + (p.y - a.y) * dy) / length_sq).clamp(0.0, 1.0)
+    };
+    let (cx, cy) = (a.x + t * dx, a.y + t * dy);
+    ((p.x - cx).powi(2) + (p.y - cy).powi(2)).sqrt()
+}
+
+/// Anti-aliased line `width` pixels thick with round ends
+fn draw_line(image: &mut RgbaImage, a: Point, b: Point, color: [u8; 3], width: f32) {
+    let radius = width.max(1.0) / 2.0;
+    let left = (a.x.min(b.x) - radius - 1.0).floor() as i64;
+    let right = (a.x.max(b.x) + radius + 1.0).ceil() as i64;
+    let top = (a.y.min(b.y) - radius - 1.0).floor() as i64;
+    let bottom = (a.y.max(b.y) + radius + 1.0).ceil() as i64;
+    for y in top.max(0)..=bottom.min(image.height() as i64 - 1) {
+        for x in left.max(0)..=right.min(image.width() as i64 - 1) {
+            let center = Point {
+                x: x as f32 + 0.5,
+                y: y as f32 + 0.5,
+            };
+            let coverage = (radius - distance_to_segment(center, a, b) + 0.5).clamp(0.0, 1.0);
+            if coverage > 0.0 {
+                blend(image, x, y, color, coverage);
+            }
+        }
+    }
+}
+
+fn draw_triangle(image: &mut RgbaImage, points: [Point; 3], color: [u8; 3]) {
+    let edge = |a: Point, b: Point, p: Point| (b.x - a.x) * (p.y - a.y) - (b.y - a.y) * (p.x - a.x);
+    let [a, b, c] = points;
+    let area = edge(a, b, c);
+    if area == 0.0 {
+        return;
+    }
+    let left = a.x.min(b.x).min(c.x).floor().max(0.0) as i64;
+    let right = a.x.max(b.x).max(c.x).ceil() as i64;
+    let top = a.y.min(b.y).min(c.y).floor().max(0.0) as i64;
+    let bottom = a.y.max(b.y).max(c.y).ceil() as i64;
+    for y in top..=bottom.min(image.height() as i64 - 1) {
+        for x in left..=right.min(image.width() as i64 - 1) {
+            let p = Point {
+                x: x as f32 + 0.5,
+                y: y as f32 + 0.5,
+            };
+            let (w0, w1, w2) = (edge(b, c, p), edge(c, a, p), edge(a, b, p));
+            let inside = if area > 0.0 {
+                w0 >= 0.0 && w1 >= 0.0 && w2 >= 0.0
+            } else {
+                w0 <= 0.0 && w1 <= 0.0 && w2 <= 0.0
+            };
+            if inside {
+                blend(image, x, y, color, 1.0);
+            }
+        }
+    }
+}
+
+fn draw_arrow(image: &mut RgbaImage, from: Point, to: Point, color: [u8; 3], width: f32) {
+    let width = width.max(1.0);
+    let (dx, dy) = (to.x - from.x, to.y - from.y);
+    let length = (dx * dx + dy * dy).sqrt();
+    if length == 0.0 {
+        return;
+    }
+    let (ux, uy) = (dx / length, dy / length);
+    // The head scales with the line, but never takes more than half the arrow
+    let head_length = (width * 4.0).max(10.0).min(length / 2.0);
+    let head_half_width = head_length * 0.6;
+    let base = Point {
+        x: to.x - ux * head_length,
+        y: to.y - uy * head_length,
+    };
+
+    draw_line(image, from, base, color, width);
+    draw_triangle(
+        image,
+        [
+            to,
+            Point {
+                x: base.x - uy * head_half_width,
+                y: base.y + ux * head_half_width,
+            },
+            Point {
+                x: base.x + uy * head_half_width,
+                y: base.y - ux * head_half_width,
+            },
+        ],
+        color,
+    );
+}
+
+fn draw_rectangle(image: &mut RgbaImage, area: &Area, color: [u8; 3], width: f32) {
+    let corners = [
+        Point {
+            x: area.x,
+            y: area.y,
+        },
+        Point {
+            x: area.x + area.width,
+            y: area.y,
+        },
+        Point {
+            x: area.x + area.width,
+            y: area.y + area.height,
+        },
+        Point {
+            x: area.x,
+            y: area.y + area.height,
+        },
+    ];
+    for i in 0..4 {
+        draw_line(image, corners[i], corners[(i + 1) % 4], color, width);
+    }
+}
+
+fn blur_area(image: &mut RgbaImage, area: &Area, strength: f32) {
+    let Some((x, y, width, height)) = area_bounds(area, image) else {
+        return;
+    };
+    let region = imageops::crop_imm(image, x, y, width, height).to_image();
+    let blurred = imageops::blur(&region, strength.max(1.0));
+    imageops::replace(image, &blurred, x as i64, y as i64);
+}
+
+/// Replace each `block` square of `area` with its average color. Unlike a blur this can't be
+/// undone, so it's the one to use on passwords and keys.
+fn pixelate_area(image: &mut RgbaImage, area: &Area, block: f32) {
+    let Some((x, y, width, height)) = area_bounds(area, image) else {
+        return;
+    };
+    let block = block.round().max(2.0) as u32;
+    for top in (y..y + height).step_by(block as usize) {
+        for left in (x..x + width).step_by(block as usize) {
+            let right = (left + block).min(x + width);
+            let bottom = (top + block).min(y + height);
+            let mut sum = [0u64; 4];
+            for py in top..bottom {
+                for px in left..right {
+                    for (total, &channel) in sum.iter_mut().zip(image.get_pixel(px, py).0.iter()) {
+                        *total += channel as u64;
+                    }
+                }
+            }
+            let count = ((right - left) * (bottom - top)) as u64;
+            let average = image::Rgba(sum.map(|total| (total / count) as u8));
+            for py in top..bottom {
+                for px in left..right {
+                    image.put_pixel(px, py, average);
+                }
+            }
+        }
+    }
+}
+
+fn ui_font() -> Result<Arc<FontVec>, String> {
+    let mut loaded = FONT.lock().unwrap();
+    if let Some(font) = loaded.as_ref() {
+        return Ok(font.clone());
+    }
+    let path = platform::ui_font_path().ok_or("No font found to draw text with")?;
+    let bytes = std::fs::read(&path).map_err(|e| format!("Failed to read font: {}", e))?;
+    let font = Arc::new(FontVec::try_from_vec(bytes).map_err(|e| format!("Invalid font: {}", e))?);
+    *loaded = Some(font.clone());
+    Ok(font)
+}
+
+/// Width of a line of text in pixels
+fn line_width<F: Font>(font: &impl ScaleFont<F>, line: &str) -> f32 {
+    let mut width = 0.0;
+    let mut previous = None;
+    for c in line.chars() {
+        let glyph = font.glyph_id(c);
+        if let Some(previous) = previous {
+            width += font.kern(previous, glyph);
+        }
+        width += font.h_advance(glyph);
+        previous = Some(glyph);
+    }
+    width
+}
+
+/// Text (lines split on `\n`) with its top-left corner at `at`, on a box of `background`
+fn draw_label(
+    image: &mut RgbaImage,
+    font: &FontVec,
+    at: Point,
+    text: &str,
+    color: [u8; 3],
+    size: f32,
+    background: Option<[u8; 3]>,
+) {
+    let font = font.as_scaled(PxScale::from(size.max(4.0)));
+    let line_height = font.height() + font.line_gap();
+    let lines: Vec<&str> = text.lines().collect();
+
+    if let Some(background) = background {
+        let padding = (size * 0.25).round();
+        let width = lines
+            .iter()
+            .map(|line| line_width(&font, line))
+            .fold(0.0, f32::max);
+        let left = (at.x - padding).floor() as i64;
+        let top = (at.y - padding).floor() as i64;
+        let right = (at.x + width + padding).ceil() as i64;
+        let bottom = (at.y + line_height * lines.len() as f32 + padding).ceil() as i64;
+        for y in top..bottom {
+            for x in left..right {
+                blend(image, x, y, background, 1.0);
+            }
+        }
+    }
+
+    for (row, line) in lines.iter().enumerate() {
+        let baseline = at.y + font.ascent() + line_height * row as f32;
+        let mut x = at.x;
+        let mut previous = None;
+        for c in line.chars() {
+            let id = font.glyph_id(c);
+            if let Some(previous) = previous {
+                x += font.kern(previous, id);
+            }
+            let glyph = id.with_scale_and_position(font.scale(), ab_glyph::point(x, baseline));
+            x += font.h_advance(id);
+            previous = Some(id);
+            let Some(outlined) = font.outline_glyph(glyph) else {
+                continue; // spaces
+            };
+            let bounds = outlined.px_bounds();
+            outlined.draw(|gx, gy, coverage| {
+                blend(
+                    image,
+                    bounds.min.x as i64 + gx as i64,
+                    bounds.min.y as i64 + gy as i64,
+                    color,
+                    coverage,
+                );
+            });
+        }
+    }
+}
+
+fn decode_sprite(sprite: &str) -> Result<RgbaImage, String> {
+    let data = sprite
+        .split_once("base64,")
+        .map_or(sprite, |(_, data)| data);
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(data.trim())
+        .map_err(|e| format!("Invalid text image: {}", e))?;
+    image::load_from_memory(&bytes)
+        .map(|image| image.to_rgba8())
+        .map_err(|e| format!("Invalid text image: {}", e))
+}
+
+fn draw_sprite(image: &mut RgbaImage, at: Point, sprite: &RgbaImage) {
+    imageops::overlay(image, sprite, at.x.round() as i64, at.y.round() as i64);
+}
+
+/// Composite `annotations` onto a copy of `image`, in order
+pub fn render(image: &RgbaImage, annotations: &[Annotation]) -> Result<RgbaImage, String> {
+    let mut out = image.clone();
+    for annotation in annotations {
+        match annotation {
+            Annotation::Arrow {
+                from,
+                to,
+                color,
+                width,
+            } => draw_arrow(&mut out, *from, *to, color_formats::parse(color)?, *width),
+            Annotation::Rectangle { area, color, width } => {
+                draw_rectangle(&mut out, area, color_formats::parse(color)?, *width)
+            }
+            Annotation::Text { at, sprite } => draw_sprite(&mut out, *at, &decode_sprite(sprite)?),
+            Annotation::Blur { area, strength } => blur_area(&mut out, area, *strength),
+            Annotation::Pixelate { area, block } => pixelate_area(&mut out, area, *block),
+            Annotation::Label {
+                at,
+                text,
+                color,
+                size,
+                background,
+            } => {
+                let background = background
+                    .as_deref()
+                    .map(color_formats::parse)
+                    .transpose()?;
+                draw_label(
+                    &mut out,
+                    &*ui_font()?,
+                    *at,
+                    text,
+                    color_formats::parse(color)?,
+                    *size,
+                    background,
+                );
+            }
+        }
+    }
+    Ok(out)
+}
+
+fn rendered(annotations: &[Annotation]) -> Result<RgbaImage, String> {
+    let capture = CAPTURE.lock().unwrap();
+    let image = capture
+        .as_ref()
+        .ok_or_else(|| "There's no screenshot to annotate".to_string())?;
+    render(image, annotations)
+}
+
+/// Open the overlay over the middle of the cursor's monitor, sized to the capture. An overlay
+/// that's still open is reused and told to load the new capture.
+fn open_overlay(app: &AppHandle, width: u32, height: u32) -> Result<(), String> {
+    let monitors = app.available_monitors().map_err(|e| e.to_string())?;
+    let work_area = platform::get_cursor_monitor_work_area();
+    let monitor = work_area
+        .and_then(|(x, y, w, h)| {
+            monitors
+                .iter()
+                .find(|m| window_placement::contains(m, x + w / 2, y + h / 2))
+        })
+        .or_else(|| monitors.first());
+    let scale = monitor.map_or(1.0, |m| m.scale_factor());
+    let (area_x, area_y, area_width, area_height) = match work_area {
+        Some((x, y, w, h)) => (x as f64, y as f64, w as f64, h as f64),
+        None => (
+            0.0,
+            0.0,
+            width as f64,
+            height as f64 + TOOLBAR_HEIGHT * scale,
+        ),
+    };
+
+    // Logical size of the image plus the toolbar, scaled down to fit the work area
+    let max_width = area_width / scale * MAX_SCREEN_FRACTION;
+    let max_height = area_height / scale * MAX_SCREEN_FRACTION - TOOLBAR_HEIGHT;
+    let fit = (max_width / (width as f64 / scale))
+        .min(max_height / (height as f64 / scale))
+        .min(1.0);
+    let window_width = (width as f64 / scale * fit).max(360.0);
+    let window_height = height as f64 / scale * fit + TOOLBAR_HEIGHT;
+    let x = area_x / scale + (area_width / scale - window_width) / 2.0;
+    let y = area_y / scale + (area_height / scale - window_height) / 2.0;
+
+    let label = format!("{}{}", LABEL_PREFIX, ANNOTATE_TOOL);
+    if let Some(window) = app.get_webview_window(&label) {
+        let _ = window.set_size(LogicalSize::new(window_width, window_height));
+        let _ = window.set_position(LogicalPosition::new(x, y));
+        let _ = app.emit_to(&label, "annotation-image-changed", ());
+        let _ = window.show();
+        let _ = window.set_focus();
+        return Ok(());
+    }
+
+    WebviewWindowBuilder::new(
+        app,
+        &label,
+        WebviewUrl::App(format!("index.html?window={}", ANNOTATE_TOOL).into()),
+    )
+    .title("BunchaTools - Annotate")
+    .inner_size(window_width, window_height)
+    .position(x, y)
+    .decorations(false)
+    .resizable(false)
+    .always_on_top(true)
+    .skip_taskbar(true)
+    .focused(true)
+    .build()
+    .map_err(|e| format!("Failed to open the annotation window: {}", e))?;
+    Ok(())
+}
+
+/// Let the user select a screen region and open it for annotation
+// Async: the capture waits for the user, and windows can't be created from sync commands
+#[tauri::command]
+pub async fn start_screen_annotation(app: AppHandle) -> Result<AnnotationSession, String> {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.hide();
+    }
+    // Let the compositor take the window off screen before capturing
+    tokio::time::sleep(std::time::Duration::from_millis(150)).await;
+
+    let image = platform::capture_screen_region_impl(&app).await?;
+    let session = AnnotationSession {
+        width: image.width(),
+        height: image.height(),
+    };
+    *CAPTURE.lock().unwrap() = Some(image);
+    open_overlay(&app, session.width, session.height)?;
+    Ok(session)
+}
+
+/// The capture as PNG, for the overlay to draw on
+#[tauri::command]
+pub fn get_annotation_image() -> Result<tauri::ipc::Response, String> {
+    let capture = CAPTURE.lock().unwrap();
+    let image = capture
+        .as_ref()
+        .ok_or_else(|| "There's no screenshot to annotate".to_string())?;
+    let mut png = Vec::new();
+    image
+        .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+        .map_err(|e| format!("Failed to encode screenshot: {}", e))?;
+    Ok(tauri::ipc::Response::new(png))
+}
+
+/// Save the annotated screenshot to `path`, or to the Pictures folder; returns the path
+#[tauri::command]
+pub async fn save_annotated_image(
+    app: AppHandle,
+    annotations: Vec<Annotation>,
+    path: Option<String>,
+) -> Result<String, String> {
+    let path = match path {
+        Some(path) => PathBuf::from(path),
+        None => {
+            let timestamp = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0);
+            app.path()
+                .picture_dir()
+                .map_err(|e| format!("Could not find the Pictures folder: {}", e))?
+                .join(format!("Screenshot {}.png", timestamp))
+        }
+    };
+    let path = crate::path_policy::validate_write_path(&app, &path.to_string_lossy())?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let image = rendered(&annotations)?;
+        image
+            .save(&path)
+            .map_err(|e| format!("Failed to save screenshot: {}", e))?;
+        Ok(path.to_string_lossy().to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Copy the annotated screenshot to the clipboard
+#[tauri::command]
+pub async fn copy_annotated_image(
+    app: AppHandle,
+    annotations: Vec<Annotation>,
+) -> Result<(), String> {
+    use tauri_plugin_clipboard_manager::ClipboardExt;
+
+    let image = tauri::async_runtime::spawn_blocking(move || rendered(&annotations))
+        .await
+        .map_err(|e| e.to_string())??;
+    let (width, height) = image.dimensions();
+    app.clipboard()
+        .write_image(&tauri::image::Image::new_owned(
+            image.into_raw(),
+            width,
+            height,
+        ))
+        .map_err(|e| format!("Failed to copy screenshot: {}", e))
+}
+
+// shot.png -> shot (annotated).png, or shot (annotated) (2).png if taken
+fn annotated_output(image: &Path) -> PathBuf {
+    let stem = image
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "image".to_string());
+    let extension = image
+        .extension()
+        .map(|e| e.to_string_lossy().to_string())
+        .unwrap_or_else(|| "png".to_string());
+    let name = format!("{} (annotated)", stem);
+    let mut candidate = image.with_file_name(format!("{}.{}", name, extension));
+    let mut n = 2;
+    while candidate.exists() {
+        candidate = image.with_file_name(format!("{} ({}).{}", name, n, extension));
+        n += 1;
+    }
+    candidate
+}
+
+/// Draw `operations` (arrows, rectangles, blurred or pixelated areas, labels) onto the image
+/// at `path`, saved next to it unless `output_path` is given; returns the annotated image's
+/// path. The original is left as it was.
+#[tauri::command]
+pub async fn annotate_image(
+    app: AppHandle,
+    path: String,
+    operations: Vec<Annotation>,
+    output_path: Option<String>,
+) -> Result<String, String> {
+    let source = PathBuf::from(&path);
+    if !source.is_file() {
+        return Err(format!("{} doesn't exist", path));
+    }
+    let output = match output_path {
+        Some(output) => PathBuf::from(output),
+        None => annotated_output(&source),
+    };
+    let output = crate::path_policy::validate_write_path(&app, &output.to_string_lossy())?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let image = image::open(&source)
+            .map_err(|e| format!("Failed to open image: {}", e))?
+            .to_rgba8();
+        let annotated = image::DynamicImage::ImageRgba8(render(&image, &operations)?);
+        // JPEG has no transparency
+        let annotated = match image::ImageFormat::from_path(&output) {
+            Ok(image::ImageFormat::Jpeg) => image::DynamicImage::ImageRgb8(annotated.to_rgb8()),
+            _ => annotated,
+        };
+        annotated
+            .save(&output)
+            .map_err(|e| format!("Failed to save image: {}", e))?;
+        Ok(output.to_string_lossy().to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Close the overlay and drop the capture
+#[tauri::command]
+pub fn close_screen_annotation(app: AppHandle) {
+    *CAPTURE.lock().unwrap() = None;
+    tool_windows::close_tool_window(&app, ANNOTATE_TOOL);
+}
@@ -0,0 +1,282 @@
+// App lock: a passcode in front of the commands that show histories and secrets (command,
+// translation, calculator, color and random history, time reports, snippets, the secrets
+// store), for shared machines. Once a passcode is set and the lock is on, BunchaTools starts
+// locked, and it locks again when asked to, when the session locks, or after `idle_minutes`
+// without input.
+// Only an Argon2id hash of the passcode (PHC string) is kept in settings. Hashes from before
+// Argon2 (PBKDF2-HMAC-SHA256) still verify and are replaced on the next unlock.
+
+use std::num::NonZeroU32;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use ring::pbkdf2;
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::session_state::SessionState;
+use crate::{secrets, AppState};
+
+// OWASP's minimum for Argon2id: 19 MiB, 2 passes, 1 lane
+const MEMORY_KIB: u32 = 19 * 1024;
+const PASSES: u32 = 2;
+const LEGACY_SCHEME: &str = "pbkdf2-sha256";
+const MIN_PASSCODE_LEN: usize = 4;
+// Slows down guessing
+const FAILED_ATTEMPT_DELAY: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AppLockSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_idle_minutes")]
+    pub idle_minutes: u64, // 0 = only on demand and when the session locks
+    // "$argon2id$v=19$m=..,t=..,p=..$<salt>$<hash>", set through `set_app_passcode` only
+    #[serde(default)]
+    pub passcode_hash: Option<String>,
+}
+
+fn default_idle_minutes() -> u64 {
+    10
+}
+
+impl Default for AppLockSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            idle_minutes: default_idle_minutes(),
+            passcode_hash: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AppLockStatus {
+    pub enabled: bool, // on, with a passcode set
+    pub locked: bool,
+}
+
+pub(crate) static LOCKED: AtomicBool = AtomicBool::new(false);
+
+/// Salted Argon2id hash of `passcode` using `memory_kib` of memory, as a PHC string
+pub fn hash_passcode(passcode: &str, memory_kib: u32) -> Result<String, String> {
+    let mut salt = [0u8; 16];
+    SystemRandom::new()
+        .fill(&mut salt)
+        .map_err(|_| "Failed to generate random bytes".to_string())?;
+    let salt = SaltString::encode_b64(&salt).map_err(|e| e.to_string())?;
+    let params = Params::new(memory_kib, PASSES, 1, None).map_err(|e| e.to_string())?;
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password(passcode.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| format!("Failed to hash the passcode: {}", e))
+}
+
+/// True if `passcode` matches a hash from `hash_passcode`, or a PBKDF2 hash from before
+/// (compared in constant time)
+pub fn verify_passcode(hash: &str, passcode: &str) -> bool {
+    if hash.starts_with(LEGACY_SCHEME) {
+        return verify_legacy(hash, passcode);
+    }
+    let Ok(parsed) = PasswordHash::new(hash) else {
+        return false;
+    };
+    // The parameters come from the hash itself
+    parsed.algorithm == Algorithm::Argon2id.ident()
+        && Argon2::default()
+            .verify_password(passcode.as_bytes(), &parsed)
+            .is_ok()
+}
+
+/// Whether a hash predates Argon2 and should be replaced once the passcode is known
+pub fn is_legacy_hash(hash: &str) -> bool {
+    !hash.starts_with("$argon2id$")
+}
+
+fn verify_legacy(hash: &str, passcode: &str) -> bool {
+    let parts: Vec<&str> = hash.split('$').collect();
+    let [scheme, iterations, salt, expected] = parts[..] else {
+        return false;
+    };
+    let (Some(iterations), Ok(salt), Ok(expected)) = (
+        iterations.parse().ok().and_then(NonZeroU32::new),
+        BASE64.decode(salt),
+        BASE64.decode(expected),
+    ) else {
+        return false;
+    };
+    scheme == LEGACY_SCHEME
+        && pbkdf2::verify(
+            pbkdf2::PBKDF2_HMAC_SHA256,
+            iterations,
+            &salt,
+            passcode.as_bytes(),
+            &expected,
+        )
+        .is_ok()
+}
+
+fn lock_settings(app: &AppHandle) -> AppLockSettings {
+    app.state::<AppState>()
+        .settings
+        .lock()
+        .unwrap()
+        .app_lock
+        .clone()
+}
+
+fn is_enabled(settings: &AppLockSettings) -> bool {
+    settings.enabled && settings.passcode_hash.is_some()
+}
+
+fn status(app: &AppHandle) -> AppLockStatus {
+    AppLockStatus {
+        enabled: is_enabled(&lock_settings(app)),
+        locked: LOCKED.load(Ordering::SeqCst),
+    }
+}
+
+/// Fail while the app is locked; called first by the commands the lock protects
+pub fn ensure_unlocked() -> Result<(), String> {
+    if LOCKED.load(Ordering::SeqCst) {
+        return Err("BunchaTools is locked: \"unlock <passcode>\" first".to_string());
+    }
+    Ok(())
+}
+
+fn lock(app: &AppHandle) {
+    // The secrets key goes too, so unlocking the app doesn't unlock secrets by itself
+    secrets::lock(app);
+    if !LOCKED.swap(true, Ordering::SeqCst) {
+        log::info!("App locked");
+        let _ = app.emit("app-lock-changed", status(app));
+    }
+}
+
+/// Start locked when the lock is on
+pub fn init(app: &AppHandle) {
+    if is_enabled(&lock_settings(app)) {
+        LOCKED.store(true, Ordering::SeqCst);
+    }
+}
+
+/// Lock on session lock or idle time; called on every session state poll
+pub fn follow_session(app: &AppHandle, state: &SessionState) {
+    let settings = lock_settings(app);
+    if !is_enabled(&settings) || LOCKED.load(Ordering::SeqCst) {
+        return;
+    }
+    let idle = settings.idle_minutes > 0 && state.idle_seconds >= settings.idle_minutes * 60;
+    if state.locked || idle {
+        lock(app);
+    }
+}
+
+#[tauri::command]
+pub fn get_app_lock_status(app: AppHandle) -> AppLockStatus {
+    status(&app)
+}
+
+#[tauri::command]
+pub fn lock_app(app: AppHandle) -> Result<AppLockStatus, String> {
+    if !is_enabled(&lock_settings(&app)) {
+        return Err("Set a passcode and turn the app lock on first".to_string());
+    }
+    lock(&app);
+    Ok(status(&app))
+}
+
+#[tauri::command]
+pub async fn unlock_app(app: AppHandle, passcode: String) -> Result<AppLockStatus, String> {
+    let Some(hash) = lock_settings(&app).passcode_hash else {
+        LOCKED.store(false, Ordering::SeqCst);
+        return Ok(status(&app));
+    };
+    let legacy = is_legacy_hash(&hash);
+    let (matches, rehashed) = tauri::async_runtime::spawn_blocking(move || {
+        let matches = verify_passcode(&hash, &passcode);
+        let rehashed = (matches && legacy)
+            .then(|| hash_passcode(&passcode, MEMORY_KIB).ok())
+            .flatten();
+        (matches, rehashed)
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+    if !matches {
+        tokio::time::sleep(FAILED_ATTEMPT_DELAY).await;
+        return Err("Wrong passcode".to_string());
+    }
+    if let Some(hash) = rehashed {
+        upgrade_hash(&app, hash);
+    }
+    if LOCKED.swap(false, Ordering::SeqCst) {
+        log::info!("App unlocked");
+    }
+    let status = status(&app);
+    let _ = app.emit("app-lock-changed", status.clone());
+    Ok(status)
+}
+
+// Replace a PBKDF2 hash with an Argon2id one of the same passcode
+fn upgrade_hash(app: &AppHandle, hash: String) {
+    let settings = {
+        let state = app.state::<AppState>();
+        let mut settings = state.settings.lock().unwrap();
+        settings.app_lock.passcode_hash = Some(hash);
+        settings.clone()
+    };
+    match crate::save_settings_to_file(app, &settings) {
+        Ok(()) => log::info!("App lock passcode hash upgraded to Argon2id"),
+        Err(e) => log::warn!("Failed to save the upgraded passcode hash: {}", e),
+    }
+}
+
+/// Set, change (`current` needed) or remove (`passcode` None, which also turns the lock off)
+/// the passcode
+#[tauri::command]
+pub async fn set_app_passcode(
+    app: AppHandle,
+    current: Option<String>,
+    passcode: Option<String>,
+) -> Result<AppLockStatus, String> {
+    let existing = lock_settings(&app).passcode_hash;
+    let passcode = passcode.filter(|p| !p.is_empty());
+    if passcode
+        .as_ref()
+        .is_some_and(|p| p.chars().count() < MIN_PASSCODE_LEN)
+    {
+        return Err(format!(
+            "Choose a passcode of at least {} characters",
+            MIN_PASSCODE_LEN
+        ));
+    }
+    let hash = tauri::async_runtime::spawn_blocking(move || {
+        if let Some(existing) = existing {
+            if !verify_passcode(&existing, current.as_deref().unwrap_or_default()) {
+                return Err("The current passcode is wrong".to_string());
+            }
+        }
+        passcode.map(|p| hash_passcode(&p, MEMORY_KIB)).transpose()
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    let settings = {
+        let state = app.state::<AppState>();
+        let mut settings = state.settings.lock().unwrap();
+        settings.app_lock.enabled = hash.is_some();
+        settings.app_lock.passcode_hash = hash;
+        settings.clone()
+    };
+    if settings.app_lock.passcode_hash.is_none() {
+        LOCKED.store(false, Ordering::SeqCst);
+    }
+    crate::save_settings_to_file(&app, &settings)?;
+    let _ = app.emit("settings-changed", &settings);
+    Ok(status(&app))
+}
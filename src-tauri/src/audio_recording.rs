@@ -0,0 +1,360 @@
+// Microphone recording to MP3, WAV or Opus with the bundled ffmpeg. ffmpeg's astats filter
+// measures the input as it records; the peaks are sent as `audio-recording-level` events
+// (about ten a second) for a level meter. `audio-recording-changed` is emitted on start and
+// stop (null once stopped). Like screen recordings, ffmpeg is stopped with `q` on stdin.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::PathBuf;
+use std::process::{Child, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::notifications::{self, NotifyAction};
+use crate::platform::{self, AudioCaptureInput, AudioInputDevice};
+
+// Time ffmpeg gets to finish writing the file after `q` before it is killed
+const STOP_TIMEOUT: Duration = Duration::from_secs(5);
+const LEVEL_INTERVAL: Duration = Duration::from_millis(100);
+
+// Quietest level the meter shows, in dBFS
+const METER_FLOOR_DB: f32 = -60.0;
+
+const PEAK_KEY: &str = "lavfi.astats.Overall.Peak_level";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AudioFormat {
+    #[default]
+    Mp3,
+    Wav,
+    Opus,
+}
+
+impl AudioFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            AudioFormat::Mp3 => "mp3",
+            AudioFormat::Wav => "wav",
+            AudioFormat::Opus => "opus",
+        }
+    }
+}
+
+/// Running recording (`audio-recording-changed`)
+#[derive(Debug, Clone, Serialize)]
+pub struct AudioRecordingStatus {
+    pub path: String,
+    pub format: AudioFormat,
+    pub device: String, // device name
+    pub elapsed_secs: u64,
+}
+
+/// Input level (`audio-recording-level`)
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct AudioLevel {
+    pub peak_db: Option<f32>, // None for digital silence
+    pub level: f32,           // 0..1 for the meter
+    pub elapsed_secs: u64,
+}
+
+struct Session {
+    generation: u64,
+    child: Child,
+    started: Instant,
+    status: AudioRecordingStatus,
+}
+
+static SESSION: Mutex<Option<Session>> = Mutex::new(None);
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+// Set while a `start` is launching ffmpeg, so a second one can't launch another
+static STARTING: AtomicBool = AtomicBool::new(false);
+
+// Gives up the reservation when `start` returns, after its session is stored or on failure
+struct Starting;
+
+impl Starting {
+    fn reserve() -> Result<Self, String> {
+        let session = SESSION.lock().unwrap();
+        if session.is_some() || STARTING.swap(true, Ordering::SeqCst) {
+            return Err("A recording is already running".to_string());
+        }
+        Ok(Starting)
+    }
+}
+
+impl Drop for Starting {
+    fn drop(&mut self) {
+        STARTING.store(false, Ordering::SeqCst);
+    }
+}
+
+/// ffmpeg arguments for recording `input` into `output`, printing peak levels on stdout
+pub fn ffmpeg_args(input: &AudioCaptureInput, format: AudioFormat, output: &str) -> Vec<String> {
+    let meter = format!(
+        "astats=metadata=1:reset=1,ametadata=mode=print:key={}:file=-",
+        PEAK_KEY
+    );
+    let mut args: Vec<&str> = vec![
+        "-hide_banner",
+        "-loglevel",
+        "error",
+        "-y",
+        "-f",
+        input.format.as_str(),
+        "-i",
+        input.device.as_str(),
+        "-af",
+        meter.as_str(),
+    ];
+    match format {
+        AudioFormat::Mp3 => args.extend(["-c:a", "libmp3lame", "-q:a", "2"]),
+        AudioFormat::Wav => args.extend(["-c:a", "pcm_s16le"]),
+        AudioFormat::Opus => args.extend(["-c:a", "libopus", "-b:a", "96k"]),
+    }
+    args.push(output);
+    args.into_iter().map(str::to_string).collect()
+}
+
+/// The peak level in a line of ffmpeg's metadata output; Some(None) for "-inf" (silence)
+pub fn parse_peak(line: &str) -> Option<Option<f32>> {
+    let value = line.trim().strip_prefix(PEAK_KEY)?.strip_prefix('=')?;
+    match value.parse::<f32>() {
+        Ok(db) if db.is_finite() => Some(Some(db)),
+        Ok(_) => Some(None),
+        Err(_) if value.ends_with("inf") => Some(None),
+        Err(_) => None,
+    }
+}
+
+/// A peak in dBFS on a 0..1 meter scale, linear in dB from `METER_FLOOR_DB`
+pub fn meter_level(peak_db: Option<f32>) -> f32 {
+    match peak_db {
+        Some(db) => ((db - METER_FLOOR_DB) / -METER_FLOOR_DB).clamp(0.0, 1.0),
+        None => 0.0,
+    }
+}
+
+fn output_path(
+    app: &AppHandle,
+    directory: Option<&str>,
+    format: AudioFormat,
+) -> Result<PathBuf, String> {
+    let directory = match directory {
+        Some(directory) => crate::path_policy::validate_output_dir(app, directory)?,
+        None => app
+            .path()
+            .audio_dir()
+            .map_err(|e| format!("Could not find the Music folder: {}", e))?,
+    };
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let path = directory.join(format!("Recording {}.{}", timestamp, format.extension()));
+    crate::path_policy::validate_write_path(app, &path.to_string_lossy())
+}
+
+fn status() -> Option<AudioRecordingStatus> {
+    SESSION.lock().unwrap().as_ref().map(|s| {
+        let mut status = s.status.clone();
+        status.elapsed_secs = s.started.elapsed().as_secs();
+        status
+    })
+}
+
+/// Ask ffmpeg to finish the file, killing it if it doesn't within `STOP_TIMEOUT`
+fn finish_child(child: &mut Child) {
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(b"q");
+    }
+    let deadline = Instant::now() + STOP_TIMEOUT;
+    while Instant::now() < deadline {
+        if let Ok(Some(_)) = child.try_wait() {
+            return;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    log::warn!("ffmpeg didn't stop in time, killing it");
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+/// Stop recording; returns the finished recording, or None if nothing was recording
+pub fn stop(app: &AppHandle) -> Option<AudioRecordingStatus> {
+    GENERATION.fetch_add(1, Ordering::SeqCst);
+    let status = status();
+    let mut session = SESSION.lock().unwrap().take()?;
+    finish_child(&mut session.child);
+    let _ = app.emit("audio-recording-changed", None::<AudioRecordingStatus>);
+    status
+}
+
+// Send the loudest peak of every `LEVEL_INTERVAL`; the end of the output means ffmpeg exited,
+// which is reported if nobody stopped it (device unplugged, disk full)
+fn meter(app: AppHandle, generation: u64, stdout: impl Read, started: Instant) {
+    let mut loudest: Option<Option<f32>> = None;
+    let mut last_sent = Instant::now();
+    for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+        let Some(peak) = parse_peak(&line) else {
+            continue;
+        };
+        loudest = Some(match (loudest.flatten(), peak) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            (a, b) => a.or(b),
+        });
+        if last_sent.elapsed() >= LEVEL_INTERVAL {
+            let peak_db = loudest.take().flatten();
+            last_sent = Instant::now();
+            let _ = app.emit(
+                "audio-recording-level",
+                AudioLevel {
+                    peak_db,
+                    level: meter_level(peak_db),
+                    elapsed_secs: started.elapsed().as_secs(),
+                },
+            );
+        }
+    }
+
+    let mut guard = SESSION.lock().unwrap();
+    if guard.as_ref().map(|s| s.generation) != Some(generation) {
+        return;
+    }
+    let mut session = guard.take().unwrap();
+    drop(guard);
+    let _ = session.child.wait();
+    let mut error = String::new();
+    if let Some(mut stderr) = session.child.stderr.take() {
+        let _ = stderr.read_to_string(&mut error);
+    }
+    let error = error.lines().last().unwrap_or("ffmpeg exited").to_string();
+    log::warn!("Audio recording stopped: {}", error);
+    let _ = app.emit("audio-recording-changed", None::<AudioRecordingStatus>);
+    notifications::notify(
+        &app,
+        "Audio recording stopped",
+        &error,
+        NotifyAction::reveal(&session.status.path),
+    );
+}
+
+fn start(
+    app: AppHandle,
+    device: Option<String>,
+    format: AudioFormat,
+    directory: Option<String>,
+) -> Result<AudioRecordingStatus, String> {
+    let _starting = Starting::reserve()?;
+
+    let ffmpeg = platform::get_ffmpeg_path()?;
+    let (input, device_name) = match device.filter(|d| !d.trim().is_empty()) {
+        Some(id) => {
+            let name = platform::list_audio_inputs(&ffmpeg)
+                .ok()
+                .and_then(|devices| devices.into_iter().find(|d| d.id == id))
+                .map(|d| d.name)
+                .unwrap_or_else(|| id.clone());
+            (platform::audio_input(&id), name)
+        }
+        None => (
+            platform::audio_capture_input(&ffmpeg)?,
+            "Default input".to_string(),
+        ),
+    };
+    let path = output_path(&app, directory.as_deref(), format)?;
+
+    let mut child = crate::hidden_command(&ffmpeg)
+        .args(ffmpeg_args(&input, format, &path.to_string_lossy()))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to start ffmpeg: {}", e))?;
+
+    // Fail fast on a missing device instead of reporting a recording that isn't running
+    std::thread::sleep(Duration::from_millis(500));
+    if let Ok(Some(_)) = child.try_wait() {
+        let mut error = String::new();
+        if let Some(mut stderr) = child.stderr.take() {
+            let _ = stderr.read_to_string(&mut error);
+        }
+        return Err(format!(
+            "Recording failed: {}",
+            error.lines().last().unwrap_or("ffmpeg exited")
+        ));
+    }
+
+    let generation = GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    let started = Instant::now();
+    let status = AudioRecordingStatus {
+        path: path.to_string_lossy().to_string(),
+        format,
+        device: device_name,
+        elapsed_secs: 0,
+    };
+    let stdout = child.stdout.take();
+    *SESSION.lock().unwrap() = Some(Session {
+        generation,
+        child,
+        started,
+        status: status.clone(),
+    });
+    let _ = app.emit("audio-recording-changed", Some(&status));
+
+    if let Some(stdout) = stdout {
+        std::thread::spawn(move || meter(app, generation, stdout, started));
+    }
+    Ok(status)
+}
+
+/// Microphones and other audio sources that can be recorded
+#[tauri::command]
+pub async fn list_audio_devices() -> Result<Vec<AudioInputDevice>, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        platform::list_audio_inputs(&platform::get_ffmpeg_path()?)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Record `device` (an id from `list_audio_devices`, or the default input) into the Music
+/// folder or `output_dir`
+#[tauri::command]
+pub async fn start_audio_recording(
+    app: AppHandle,
+    device: Option<String>,
+    format: Option<AudioFormat>,
+    output_dir: Option<String>,
+) -> Result<AudioRecordingStatus, String> {
+    let format = format.unwrap_or_default();
+    tauri::async_runtime::spawn_blocking(move || start(app, device, format, output_dir))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Stop recording and finish the file; returns the recording, None if nothing was recording
+#[tauri::command]
+pub async fn stop_audio_recording(app: AppHandle) -> Result<Option<AudioRecordingStatus>, String> {
+    let app_for_stop = app.clone();
+    let status = tauri::async_runtime::spawn_blocking(move || stop(&app_for_stop))
+        .await
+        .map_err(|e| e.to_string())?;
+    if let Some(status) = &status {
+        notifications::notify_if_hidden(
+            &app,
+            "Audio recording saved",
+            &status.path,
+            NotifyAction::reveal(&status.path),
+        );
+    }
+    Ok(status)
+}
+
+#[tauri::command]
+pub fn get_audio_recording() -> Option<AudioRecordingStatus> {
+    status()
+}
@@ -0,0 +1,207 @@
+// Auto-clicker: repeat mouse clicks at a fixed interval, at the cursor or at a fixed point,
+// until a click limit is reached, it's stopped from the launcher, or the stop hotkey is pressed.
+// The stop hotkey is a global shortcut registered only while clicking, so it works even when
+// the clicks land in a window that keeps focus.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut};
+
+use crate::notifications::{self, NotifyAction};
+use crate::platform::{self, MouseButton};
+
+// Faster than this and most apps drop clicks (and it's hard to get the stop hotkey through)
+const MIN_INTERVAL_MS: u64 = 10;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct AutoClickerConfig {
+    pub interval_ms: u64,
+    pub button: MouseButton,
+    pub position: Option<(i32, i32)>, // screen pixels; None follows the cursor
+    pub max_clicks: Option<u64>,      // None = until stopped
+    pub start_delay_ms: u64,          // time to move the cursor into place
+    pub stop_hotkey_modifiers: Vec<String>,
+    pub stop_hotkey_key: String,
+}
+
+impl Default for AutoClickerConfig {
+    fn default() -> Self {
+        Self {
+            interval_ms: 100,
+            button: MouseButton::Left,
+            position: None,
+            max_clicks: None,
+            start_delay_ms: 1000,
+            stop_hotkey_modifiers: Vec::new(),
+            stop_hotkey_key: "F6".to_string(),
+        }
+    }
+}
+
+/// Running auto-clicker (`auto-clicker-changed`, null once stopped)
+#[derive(Debug, Clone, Serialize)]
+pub struct AutoClickerStatus {
+    pub interval_ms: u64,
+    pub button: MouseButton,
+    pub position: Option<(i32, i32)>,
+    pub max_clicks: Option<u64>,
+    pub clicks: u64,
+    pub stop_hotkey: String, // e.g. "Ctrl+F6"
+}
+
+struct Session {
+    generation: u64,
+    status: AutoClickerStatus,
+    stop_shortcut: Shortcut,
+}
+
+static SESSION: Mutex<Option<Session>> = Mutex::new(None);
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+fn status() -> Option<AutoClickerStatus> {
+    SESSION.lock().unwrap().as_ref().map(|s| s.status.clone())
+}
+
+/// True if `shortcut` is the stop hotkey of the running auto-clicker
+pub fn is_stop_shortcut(shortcut: &Shortcut) -> bool {
+    SESSION
+        .lock()
+        .unwrap()
+        .as_ref()
+        .is_some_and(|s| s.stop_shortcut == *shortcut)
+}
+
+/// Stop clicking; returns the number of clicks made, or None if nothing was running
+pub fn stop(app: &AppHandle) -> Option<u64> {
+    GENERATION.fetch_add(1, Ordering::SeqCst);
+    let session = SESSION.lock().unwrap().take()?;
+    let _ = app.global_shortcut().unregister(session.stop_shortcut);
+    let _ = app.emit("auto-clicker-changed", None::<AutoClickerStatus>);
+    Some(session.status.clicks)
+}
+
+// Stop from the clicking thread, unless a newer session replaced this one
+fn finish(app: &AppHandle, generation: u64) -> Option<u64> {
+    let current = SESSION
+        .lock()
+        .unwrap()
+        .as_ref()
+        .is_some_and(|s| s.generation == generation);
+    if current {
+        stop(app)
+    } else {
+        None
+    }
+}
+
+fn run(app: AppHandle, generation: u64, config: AutoClickerConfig) {
+    std::thread::sleep(Duration::from_millis(config.start_delay_ms));
+    let interval = Duration::from_millis(config.interval_ms);
+    loop {
+        if GENERATION.load(Ordering::SeqCst) != generation {
+            return;
+        }
+        if let Err(e) = platform::click_mouse_impl(config.button, config.position) {
+            log::warn!("Auto-clicker stopped: {}", e);
+            if finish(&app, generation).is_some() {
+                notifications::notify(&app, "Auto-clicker stopped", &e, NotifyAction::None);
+            }
+            return;
+        }
+
+        let done = {
+            let mut session = SESSION.lock().unwrap();
+            match session.as_mut().filter(|s| s.generation == generation) {
+                Some(session) => {
+                    session.status.clicks += 1;
+                    config
+                        .max_clicks
+                        .is_some_and(|max| session.status.clicks >= max)
+                }
+                None => return,
+            }
+        };
+        if done {
+            if let Some(clicks) = finish(&app, generation) {
+                notifications::notify(
+                    &app,
+                    "Auto-clicker finished",
+                    &format!("Clicked {} times", clicks),
+                    NotifyAction::None,
+                );
+            }
+            return;
+        }
+        std::thread::sleep(interval);
+    }
+}
+
+/// Start clicking, replacing any running auto-clicker
+#[tauri::command]
+pub fn start_auto_clicker(
+    app: AppHandle,
+    config: Option<AutoClickerConfig>,
+) -> Result<AutoClickerStatus, String> {
+    let config = config.unwrap_or_default();
+    if config.interval_ms < MIN_INTERVAL_MS {
+        return Err(format!(
+            "The interval needs to be at least {} ms",
+            MIN_INTERVAL_MS
+        ));
+    }
+    if config.max_clicks == Some(0) {
+        return Err("Click at least once".to_string());
+    }
+    let stop_shortcut =
+        crate::parse_shortcut(&config.stop_hotkey_modifiers, &config.stop_hotkey_key)
+            .ok_or_else(|| format!("Invalid stop hotkey: {}", config.stop_hotkey_key))?;
+    let stop_hotkey = config
+        .stop_hotkey_modifiers
+        .iter()
+        .map(String::as_str)
+        .chain([config.stop_hotkey_key.as_str()])
+        .collect::<Vec<_>>()
+        .join("+");
+
+    stop(&app);
+    // Without a working stop hotkey the clicks could make the launcher hard to reach
+    app.global_shortcut()
+        .register(stop_shortcut)
+        .map_err(|e| format!("Can't use {} as the stop hotkey: {}", stop_hotkey, e))?;
+
+    let generation = GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    let status = AutoClickerStatus {
+        interval_ms: config.interval_ms,
+        button: config.button,
+        position: config.position,
+        max_clicks: config.max_clicks,
+        clicks: 0,
+        stop_hotkey,
+    };
+    *SESSION.lock().unwrap() = Some(Session {
+        generation,
+        status: status.clone(),
+        stop_shortcut,
+    });
+    let _ = app.emit("auto-clicker-changed", Some(&status));
+
+    let app = app.clone();
+    std::thread::spawn(move || run(app, generation, config));
+    Ok(status)
+}
+
+/// Stop clicking; returns the number of clicks made
+#[tauri::command]
+pub fn stop_auto_clicker(app: AppHandle) -> Option<u64> {
+    stop(&app)
+}
+
+#[tauri::command]
+pub fn get_auto_clicker() -> Option<AutoClickerStatus> {
+    status()
+}
@@ -0,0 +1,340 @@
+// ASCII art banners from embedded FIGlet fonts, for READMEs, commit messages and MOTDs
+// Fonts are standard FIGlet `.flf` files compiled into the binary. Characters are laid out
+// the way the `figlet` command does it: full width, fitted (kerned) or smushed, as requested
+// by the font's header. Only the printable ASCII range plus the seven required Deutsch
+// characters are supported; anything else renders as '?'.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+// (name, description, .flf source); the first one is the default
+#[rustfmt::skip]
+const FONTS: &[(&str, &str, &str)] = &[
+    ("standard", "The classic figlet font", include_str!("../fonts/standard.flf")),
+    ("solid", "Large full-block letters", include_str!("../fonts/solid.flf")),
+    ("tiny", "Compact half-block letters, 3 lines tall", include_str!("../fonts/tiny.flf")),
+];
+
+// Banners are meant for a terminal or a README, not for whole documents
+const MAX_BANNER_TEXT: usize = 200;
+
+// Code points of the characters every font defines after the ASCII range
+const DEUTSCH_CHARS: [u32; 7] = [196, 214, 220, 228, 246, 252, 223];
+
+// Layout bits of the header's "full layout" field
+const SMUSH_EQUAL: u32 = 1;
+const SMUSH_LOWLINE: u32 = 2;
+const SMUSH_HIERARCHY: u32 = 4;
+const SMUSH_PAIR: u32 = 8;
+const SMUSH_BIGX: u32 = 16;
+const SMUSH_HARDBLANK: u32 = 32;
+const LAYOUT_KERN: u32 = 64;
+const LAYOUT_SMUSH: u32 = 128;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BannerFont {
+    pub name: String,
+    pub description: String,
+}
+
+/// A parsed FIGfont
+struct Font {
+    hardblank: char,
+    height: usize,
+    layout: u32,
+    glyphs: HashMap<char, Vec<Vec<char>>>, // rows of each character, all the same width
+}
+
+impl Font {
+    fn parse(source: &str) -> Result<Self, String> {
+        let mut lines = source.lines();
+        let header = lines.next().ok_or("Empty font file")?;
+        let signature = header.strip_prefix("flf2a").ok_or("Not a FIGlet font")?;
+        let hardblank = signature.chars().next().ok_or("Missing hardblank")?;
+        let fields: Vec<i64> = signature[hardblank.len_utf8()..]
+            .split_whitespace()
+            .map(|f| {
+                f.parse()
+                    .map_err(|_| format!("Invalid font header: {}", header))
+            })
+            .collect::<Result<_, _>>()?;
+        if fields.len() < 5 {
+            return Err(format!("Invalid font header: {}", header));
+        }
+
+        let height = fields[0].max(1) as usize;
+        let old_layout = fields[3];
+        let comment_lines = fields[4].max(0) as usize;
+        // The full layout field is optional; derive it from the old one when missing
+        let layout = match fields.get(6) {
+            Some(&full) => full as u32,
+            None if old_layout == 0 => LAYOUT_KERN,
+            None if old_layout < 0 => 0,
+            None => (old_layout as u32 & 31) | LAYOUT_SMUSH,
+        };
+
+        let mut lines = lines.skip(comment_lines);
+        let read_glyph = |lines: &mut dyn Iterator<Item = &str>| -> Option<Vec<Vec<char>>> {
+            let rows: Vec<Vec<char>> = (0..height)
+                .map(|_| lines.next().map(strip_endmarks))
+                .collect::<Option<_>>()?;
+            let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+            Some(
+                rows.into_iter()
+                    .map(|mut row| {
+                        row.resize(width, ' ');
+                        row
+                    })
+                    .collect(),
+            )
+        };
+
+        let mut glyphs = HashMap::new();
+        let required = (32..127).chain(DEUTSCH_CHARS);
+        for code in required {
+            let glyph = read_glyph(&mut lines).ok_or("Font file is truncated")?;
+            if let Some(c) = char::from_u32(code) {
+                glyphs.insert(c, glyph);
+            }
+        }
+
+        // Code-tagged characters: a "<code> <description>" line before each glyph
+        while let Some(tag) = lines.next() {
+            let Some(glyph) = read_glyph(&mut lines) else {
+                break;
+            };
+            let code = tag.split_whitespace().next().and_then(parse_code);
+            if let Some(c) = code.and_then(char::from_u32) {
+                glyphs.insert(c, glyph);
+            }
+        }
+
+        Ok(Self {
+            hardblank,
+            height,
+            layout,
+            glyphs,
+        })
+    }
+
+    fn glyph(&self, c: char) -> Option<&Vec<Vec<char>>> {
+        self.glyphs.get(&c).or_else(|| self.glyphs.get(&'?'))
+    }
+
+    /// Character that results from overlapping `left` and `right`, if they may be smushed
+    fn smush(&self, left: char, right: char, widths: (usize, usize)) -> Option<char> {
+        if left == ' ' {
+            return Some(right);
+        }
+        if right == ' ' {
+            return Some(left);
+        }
+        // Single-column characters are never smushed into their neighbours
+        if widths.0 < 2 || widths.1 < 2 || self.layout & LAYOUT_SMUSH == 0 {
+            return None;
+        }
+
+        let rules = self.layout & 63;
+        let hardblank = self.hardblank;
+        if rules == 0 {
+            // Universal smushing: the right character wins, hardblanks give way
+            return Some(if left == hardblank {
+                right
+            } else if right == hardblank {
+                left
+            } else {
+                right
+            });
+        }
+
+        if left == hardblank || right == hardblank {
+            return (rules & SMUSH_HARDBLANK != 0 && left == right).then_some(left);
+        }
+        if rules & SMUSH_EQUAL != 0 && left == right {
+            return Some(left);
+        }
+        if rules & SMUSH_LOWLINE != 0 {
+            const REPLACES_LOWLINE: &str = "|/\\[]{}()<>";
+            if left == '_' && REPLACES_LOWLINE.contains(right) {
+                return Some(right);
+            }
+            if right == '_' && REPLACES_LOWLINE.contains(left) {
+                return Some(left);
+            }
+        }
+        if rules & SMUSH_HIERARCHY != 0 {
+            const CLASSES: [&str; 6] = ["|", "/\\", "[]", "{}", "()", "<>"];
+            let class = |c: char| CLASSES.iter().position(|class| class.contains(c));
+            if let (Some(l), Some(r)) = (class(left), class(right)) {
+                if l != r {
+                    return Some(if l > r { left } else { right });
+                }
+            }
+        }
+        if rules & SMUSH_PAIR != 0 {
+            let pair: String = [left, right].iter().collect();
+            if ["[]", "][", "{}", "}{", "()", ")("].contains(&pair.as_str()) {
+                return Some('|');
+            }
+        }
+        if rules & SMUSH_BIGX != 0 {
+            match (left, right) {
+                ('/', '\\') => return Some('|'),
+                ('\\', '/') => return Some('Y'),
+                ('>', '<') => return Some('X'),
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// How many columns `glyph` can overlap the end of `output`
+    fn overlap(&self, output: &[Vec<char>], glyph: &[Vec<char>], widths: (usize, usize)) -> usize {
+        if self.layout & (LAYOUT_KERN | LAYOUT_SMUSH) == 0 {
+            return 0;
+        }
+        let glyph_width = widths.1;
+        let mut amount = glyph_width;
+
+        for (line, row) in output.iter().zip(glyph) {
+            // Trailing blank columns of the output line and leading ones of the glyph row
+            let line_end = line.iter().rposition(|&c| c != ' ');
+            let row_start = row.iter().position(|&c| c != ' ').unwrap_or(glyph_width);
+            let mut row_amount = row_start + line.len() - line_end.map_or(0, |i| i + 1);
+
+            if let (Some(end), Some(&right)) = (line_end, row.get(row_start)) {
+                if self.smush(line[end], right, widths).is_some() {
+                    row_amount += 1;
+                }
+            }
+            amount = amount.min(row_amount);
+        }
+        amount
+    }
+
+    fn render_line(&self, text: &str) -> Vec<String> {
+        let mut output: Vec<Vec<char>> = vec![Vec::new(); self.height];
+        let mut previous_width = 0;
+
+        for c in text.chars() {
+            let Some(glyph) = self.glyph(c) else {
+                continue;
+            };
+            let width = glyph.first().map_or(0, Vec::len);
+            let widths = (previous_width, width);
+            let overlap = self.overlap(&output, glyph, widths).min(output[0].len());
+
+            for (line, row) in output.iter_mut().zip(glyph) {
+                let start = line.len() - overlap;
+                for (k, &right) in row.iter().enumerate().take(overlap) {
+                    let left = line[start + k];
+                    line[start + k] = self.smush(left, right, widths).unwrap_or(right);
+                }
+                line.extend(row.iter().skip(overlap));
+            }
+            previous_width = width;
+        }
+
+        output
+            .into_iter()
+            .map(|line| {
+                let line: String = line
+                    .into_iter()
+                    .map(|c| if c == self.hardblank { ' ' } else { c })
+                    .collect();
+                line.trim_end().to_string()
+            })
+            .collect()
+    }
+
+    /// Render `text`, one block of rows per input line
+    fn render(&self, text: &str) -> String {
+        let mut rows = Vec::new();
+        for line in text.lines() {
+            rows.extend(self.render_line(line));
+        }
+        // Drop rows left empty by glyphs without descenders, and the shared left margin
+        while rows.last().is_some_and(|row| row.is_empty()) {
+            rows.pop();
+        }
+        let margin = rows
+            .iter()
+            .filter(|row| !row.is_empty())
+            .map(|row| row.len() - row.trim_start_matches(' ').len())
+            .min()
+            .unwrap_or(0);
+        rows.iter()
+            .map(|row| row.get(margin..).unwrap_or(""))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// A glyph row without its endmark(s): the last character, repeated on the glyph's last row
+fn strip_endmarks(line: &str) -> Vec<char> {
+    let mut chars: Vec<char> = line.trim_end_matches(['\r', '\n']).chars().collect();
+    if let Some(&endmark) = chars.last() {
+        while chars.last() == Some(&endmark) {
+            chars.pop();
+        }
+    }
+    chars
+}
+
+/// Character code of a code-tagged glyph: decimal, 0x hexadecimal or 0 octal
+fn parse_code(code: &str) -> Option<u32> {
+    let (negative, code) = match code.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, code),
+    };
+    // Negative codes are font-private and can't be typed
+    if negative {
+        return None;
+    }
+    if let Some(hex) = code.strip_prefix("0x").or_else(|| code.strip_prefix("0X")) {
+        u32::from_str_radix(hex, 16).ok()
+    } else if code.len() > 1 && code.starts_with('0') {
+        u32::from_str_radix(&code[1..], 8).ok()
+    } else {
+        code.parse().ok()
+    }
+}
+
+/// Render `text` as a banner in `font` (default: "standard")
+pub fn render(text: &str, font: Option<&str>) -> Result<String, String> {
+    let text = text.trim_end();
+    if text.trim().is_empty() {
+        return Err("Nothing to render".to_string());
+    }
+    if text.chars().count() > MAX_BANNER_TEXT {
+        return Err(format!(
+            "Text is too long (max {} characters)",
+            MAX_BANNER_TEXT
+        ));
+    }
+
+    let name = font.unwrap_or(FONTS[0].0);
+    let (_, _, source) = FONTS
+        .iter()
+        .find(|(font_name, _, _)| font_name.eq_ignore_ascii_case(name))
+        .ok_or_else(|| format!("Unknown font: {}", name))?;
+    Ok(Font::parse(source)?.render(text))
+}
+
+#[tauri::command]
+pub fn generate_banner(text: String, font: Option<String>) -> Result<String, String> {
+    render(&text, font.as_deref())
+}
+
+/// Fonts available to `generate_banner`, default first
+#[tauri::command]
+pub fn list_banner_fonts() -> Vec<BannerFont> {
+    FONTS
+        .iter()
+        .map(|(name, description, _)| BannerFont {
+            name: name.to_string(),
+            description: description.to_string(),
+        })
+        .collect()
+}
@@ -0,0 +1,176 @@
+// Calculator tape and memory register
+// Results taken from the calculator are appended to a tape (oldest first, capped) and kept
+// next to the M+ / M- / MR register in calculator.json, so they survive hiding the window
+// and restarts. The tape can be exported as plain text, one `expression = result` per line.
+
+use std::fs;
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+const CALCULATOR_FILE: &str = "calculator.json";
+const MAX_TAPE_ENTRIES: usize = 200;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CalcEntry {
+    pub expression: String,
+    pub result: f64,
+    pub timestamp: u64, // seconds since the epoch
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CalcHistory {
+    pub entries: Vec<CalcEntry>, // oldest first
+    pub memory: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MemoryOp {
+    Add,      // M+
+    Subtract, // M-
+    Recall,   // MR
+    Clear,    // MC
+}
+
+/// Shortest decimal form, without float noise like 0.30000000000000004
+pub fn format_number(value: f64) -> String {
+    let rounded = (value * 1e10).round() / 1e10;
+    let text = format!("{:.10}", rounded);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text == "-0" {
+        "0".to_string()
+    } else {
+        text.to_string()
+    }
+}
+
+/// Add a calculation, unless it repeats the last one
+pub fn push_entry(history: &mut CalcHistory, entry: CalcEntry) {
+    let repeated = history
+        .entries
+        .last()
+        .is_some_and(|last| last.expression == entry.expression && last.result == entry.result);
+    if repeated {
+        return;
+    }
+    history.entries.push(entry);
+    let excess = history.entries.len().saturating_sub(MAX_TAPE_ENTRIES);
+    history.entries.drain(..excess);
+}
+
+/// Apply a memory key; Add / Subtract use `value`, or the last result on the tape
+pub fn apply_memory(
+    history: &mut CalcHistory,
+    op: MemoryOp,
+    value: Option<f64>,
+) -> Result<f64, String> {
+    let operand = || {
+        value
+            .or_else(|| history.entries.last().map(|e| e.result))
+            .ok_or_else(|| "There's no result to add to memory yet".to_string())
+    };
+    match op {
+        MemoryOp::Add => history.memory += operand()?,
+        MemoryOp::Subtract => history.memory -= operand()?,
+        MemoryOp::Recall => {}
+        MemoryOp::Clear => history.memory = 0.0,
+    }
+    Ok(history.memory)
+}
+
+/// Plain-text tape with the `=` signs lined up, and the memory register if it's set
+pub fn tape(history: &CalcHistory) -> String {
+    let width = history
+        .entries
+        .iter()
+        .map(|e| e.expression.chars().count())
+        .max()
+        .unwrap_or(0);
+    let mut out = String::new();
+    for entry in &history.entries {
+        out.push_str(&format!(
+            "{:<width$} = {}\n",
+            entry.expression,
+            format_number(entry.result),
+            width = width
+        ));
+    }
+    if history.memory != 0.0 {
+        out.push_str(&format!("\nM = {}\n", format_number(history.memory)));
+    }
+    out
+}
+
+fn load(app: &AppHandle) -> CalcHistory {
+    crate::load_json_file(app, CALCULATOR_FILE)
+}
+
+fn save(app: &AppHandle, history: &CalcHistory) -> Result<(), String> {
+    crate::save_json_file(app, CALCULATOR_FILE, history)
+}
+
+#[tauri::command]
+pub fn get_calc_history(app: AppHandle) -> Result<CalcHistory, String> {
+    crate::app_lock::ensure_unlocked()?;
+    Ok(load(&app))
+}
+
+#[tauri::command]
+pub fn record_calculation(
+    app: AppHandle,
+    expression: String,
+    result: f64,
+) -> Result<CalcHistory, String> {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    if !result.is_finite() {
+        return Err("The result isn't a number".to_string());
+    }
+    let mut history = load(&app);
+    push_entry(
+        &mut history,
+        CalcEntry {
+            expression: expression.trim().to_string(),
+            result,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+        },
+    );
+    save(&app, &history)?;
+    Ok(history)
+}
+
+/// M+ / M- / MR / MC; returns the register afterwards
+#[tauri::command]
+pub fn calc_memory(app: AppHandle, op: MemoryOp, value: Option<f64>) -> Result<f64, String> {
+    let mut history = load(&app);
+    let memory = apply_memory(&mut history, op, value)?;
+    if op != MemoryOp::Recall {
+        save(&app, &history)?;
+    }
+    Ok(memory)
+}
+
+/// Empty the tape; the memory register is kept
+#[tauri::command]
+pub fn clear_calc_history(app: AppHandle) -> Result<(), String> {
+    let mut history = load(&app);
+    history.entries.clear();
+    save(&app, &history)
+}
+
+/// Write the tape to `path` as text
+#[tauri::command]
+pub async fn export_calc_tape(app: AppHandle, path: String) -> Result<String, String> {
+    crate::app_lock::ensure_unlocked()?;
+    let history = load(&app);
+    if history.entries.is_empty() {
+        return Err("The calculator tape is empty".to_string());
+    }
+    let path = crate::path_policy::validate_write_path(&app, &path)?;
+    fs::write(&path, tape(&history)).map_err(|e| format!("Failed to write tape: {}", e))?;
+    Ok(path.to_string_lossy().to_string())
+}
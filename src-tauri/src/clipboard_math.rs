@@ -0,0 +1,158 @@
+// Quick math on numbers copied from spreadsheets, invoices, chat messages...
+// Numbers are picked out of arbitrary clipboard text. Currency symbols are ignored, "-5",
+// "−5" and accounting-style "(5.00)" count as negative, and thousands/decimal separators are
+// worked out per number: with both '.' and ',' present the last one is the decimal separator,
+// a repeated one groups thousands, and a single one followed by exactly three digits
+// ("1,234") is resolved with the user's locale.
+
+use serde::Serialize;
+use tauri::AppHandle;
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+const CURRENCY_SYMBOLS: [char; 10] = ['$', '€', '£', '¥', '₹', '₩', '₽', '¢', '₺', '₪'];
+
+// Spaces used for digit grouping ("1 234 567"); plain spaces separate numbers instead
+const GROUP_SEPARATORS: [char; 3] = ['\'', '\u{a0}', '\u{202f}'];
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NumberSummary {
+    pub count: usize,
+    pub sum: f64,
+    pub average: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl NumberSummary {
+    pub fn from_numbers(numbers: &[f64]) -> Option<Self> {
+        if numbers.is_empty() {
+            return None;
+        }
+        let sum: f64 = numbers.iter().sum();
+        Some(Self {
+            count: numbers.len(),
+            sum,
+            average: sum / numbers.len() as f64,
+            min: numbers.iter().copied().fold(f64::INFINITY, f64::min),
+            max: numbers.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        })
+    }
+}
+
+fn is_number_char(c: char) -> bool {
+    c.is_ascii_digit() || c == '.' || c == ',' || GROUP_SEPARATORS.contains(&c)
+}
+
+/// Values of one number token (digits and separators, sign already handled). A comma list
+/// without spaces ("1,2,3") yields several values; dotted sequences like versions and IP
+/// addresses yield none.
+fn parse_token(token: &str, decimal_separator: char) -> Vec<f64> {
+    let token: String = token
+        .chars()
+        .filter(|c| !GROUP_SEPARATORS.contains(c))
+        .collect();
+    let parse = |s: &str| s.parse::<f64>().ok();
+
+    let (last_dot, last_comma) = (token.rfind('.'), token.rfind(','));
+    let normalized = match (last_dot, last_comma) {
+        (None, None) => token.clone(),
+        // Both present: the last one is the decimal separator
+        (Some(dot), Some(comma)) => {
+            let (decimal, group) = if dot > comma { ('.', ',') } else { (',', '.') };
+            if token.matches(decimal).count() > 1 {
+                return Vec::new();
+            }
+            token.replace(group, "").replace(decimal, ".")
+        }
+        (Some(_), None) | (None, Some(_)) => {
+            let separator = if last_dot.is_some() { '.' } else { ',' };
+            let groups: Vec<&str> = token.split(separator).collect();
+            let grouped_thousands = groups[0].len() <= 3
+                && !groups[0].is_empty()
+                && groups[1..].iter().all(|g| g.len() == 3);
+
+            if groups.len() > 2 {
+                if grouped_thousands {
+                    token.replace(separator, "")
+                } else if separator == ',' {
+                    return groups.iter().filter_map(|g| parse(g)).collect();
+                } else {
+                    return Vec::new();
+                }
+            } else if grouped_thousands && separator != decimal_separator {
+                token.replace(separator, "")
+            } else {
+                token.replace(separator, ".")
+            }
+        }
+    };
+    parse(&normalized).into_iter().collect()
+}
+
+/// All numbers in `text`. `decimal_separator` is the user's locale separator ('.' or ','),
+/// used only when a number could be read either way.
+pub fn parse_numbers(text: &str, decimal_separator: char) -> Vec<f64> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut numbers = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        if !chars[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        while i < chars.len() && is_number_char(chars[i]) {
+            i += 1;
+        }
+        // Trailing separators belong to the sentence ("costs 5.", "1, 2")
+        let mut end = i;
+        while end > start && !chars[end - 1].is_ascii_digit() {
+            end -= 1;
+        }
+        let token: String = chars[start..end].iter().collect();
+
+        // Sign: look back past a currency symbol for '-', '−' or an opening parenthesis
+        let mut before = start;
+        if before > 0 && CURRENCY_SYMBOLS.contains(&chars[before - 1]) {
+            before -= 1;
+        }
+        let mut after = end;
+        if after < chars.len() && CURRENCY_SYMBOLS.contains(&chars[after]) {
+            after += 1;
+        }
+        let minus = before > 0
+            && matches!(chars[before - 1], '-' | '−')
+            && (before == 1 || !chars[before - 2].is_alphanumeric());
+        let parenthesized =
+            before > 0 && chars[before - 1] == '(' && chars.get(after) == Some(&')');
+        let sign = if minus || parenthesized { -1.0 } else { 1.0 };
+
+        numbers.extend(
+            parse_token(&token, decimal_separator)
+                .into_iter()
+                .map(|n| n * sign),
+        );
+    }
+
+    numbers
+}
+
+/// Sum, average, min and max of the numbers on the clipboard
+#[tauri::command]
+pub fn sum_clipboard_numbers(
+    app: AppHandle,
+    decimal_separator: Option<String>,
+) -> Result<NumberSummary, String> {
+    let text = app
+        .clipboard()
+        .read_text()
+        .map_err(|e| format!("Failed to read clipboard: {}", e))?;
+    let decimal_separator = match decimal_separator.as_deref() {
+        Some(",") => ',',
+        _ => '.',
+    };
+    NumberSummary::from_numbers(&parse_numbers(&text, decimal_separator))
+        .ok_or_else(|| "No numbers found in clipboard".to_string())
+}
@@ -0,0 +1,405 @@
+// LAN clipboard sharing: copy on one computer, paste on another, without a cloud service.
+// Instances with sharing on advertise themselves over mDNS (_bunchatools._tcp) and accept
+// clipboard text on a TCP port. Every message is sealed with AES-256-GCM under a key derived
+// from the share code, which has to be the same on all of the user's devices: others on the
+// network can see the advertisement but can neither read nor inject clipboard text.
+//
+// Wire format: a 4-byte big-endian length, then the sealed JSON `Payload`; the receiver
+// answers one byte, 1 if the text was accepted and 0 if not. The length isn't trusted: the
+// message has to arrive within a few seconds and its buffer grows only as data comes in.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use ring::digest::{digest, SHA256};
+use serde::{Deserialize, Serialize};
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::notifications::{self, NotifyAction};
+use crate::secrets::{self, Key};
+use crate::transfers::{self, Usage};
+use crate::{mdns, AppState};
+
+const SERVICE: &str = "_bunchatools._tcp.local";
+const KEY_SALT: &[u8] = b"bunchatools-clipboard-share";
+const KEY_ITERATIONS: u32 = 100_000;
+// Sealed messages are base64, so this allows about 750 KB of clipboard text
+const MAX_MESSAGE: usize = 1024 * 1024;
+// Older messages are refused, and fresher ones only accepted once (see `SeenMessages`)
+const MAX_AGE_SECS: i64 = 120;
+const BROWSE_TIME: Duration = Duration::from_millis(1500);
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+// Connections beyond this are dropped until one finishes
+const MAX_CONNECTIONS: usize = 8;
+const MIN_CODE_LEN: usize = 8;
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct ClipboardShareSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub device_name: String, // empty uses the computer name
+    #[serde(default)]
+    pub code: String, // shared secret, the same on every device
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ShareDevice {
+    pub name: String,
+    pub address: String,
+    pub port: u16,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Payload {
+    pub from: String,
+    pub sent_at: i64, // seconds since the epoch
+    pub text: String,
+}
+
+struct Sharing {
+    settings: ClipboardShareSettings,
+    instance_id: String,
+    task: JoinHandle<()>, // server and mDNS advertisement
+}
+
+static SHARING: Mutex<Option<Sharing>> = Mutex::new(None);
+static SEEN: Mutex<SeenMessages> = Mutex::new(SeenMessages::new());
+
+/// Digests of the messages accepted in the last `MAX_AGE_SECS`, so a recorded message can't be
+/// replayed while it's still fresh enough for `open_payload`
+#[derive(Default)]
+pub struct SeenMessages(Vec<(i64, Vec<u8>)>); // sent_at, SHA-256 of the sealed message
+
+impl SeenMessages {
+    pub const fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Remember a message sent at `sent_at`; false if it was already accepted
+    pub fn first_sighting(&mut self, message: &[u8], sent_at: i64, now: i64) -> bool {
+        self.0.retain(|(seen_at, _)| now - seen_at <= MAX_AGE_SECS);
+        let id = digest(&SHA256, message).as_ref().to_vec();
+        if self.0.iter().any(|(_, seen)| *seen == id) {
+            return false;
+        }
+        self.0.push((sent_at, id));
+        true
+    }
+}
+
+/// The message key for `code`
+pub fn share_key(code: &str) -> Key {
+    secrets::derive_key(code.trim(), KEY_SALT, KEY_ITERATIONS)
+}
+
+pub fn seal_payload(key: &Key, payload: &Payload) -> Result<Vec<u8>, String> {
+    let json = serde_json::to_vec(payload).map_err(|e| e.to_string())?;
+    secrets::seal(key, &json).map(String::into_bytes)
+}
+
+/// Open a message; fails for another share code, altered data or a message older than
+/// `MAX_AGE_SECS` at `now`
+pub fn open_payload(key: &Key, message: &[u8], now: i64) -> Result<Payload, String> {
+    let sealed = std::str::from_utf8(message).map_err(|_| "Malformed message".to_string())?;
+    let json = secrets::open(key, sealed).map_err(|_| "Wrong share code".to_string())?;
+    let payload: Payload =
+        serde_json::from_slice(&json).map_err(|_| "Malformed message".to_string())?;
+    if (now - payload.sent_at).abs() > MAX_AGE_SECS {
+        return Err("Message too old (check both clocks)".to_string());
+    }
+    Ok(payload)
+}
+
+fn now_secs() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+fn computer_name() -> String {
+    std::env::var("COMPUTERNAME")
+        .or_else(|_| std::env::var("HOSTNAME"))
+        .ok()
+        .or_else(|| std::fs::read_to_string("/proc/sys/kernel/hostname").ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "BunchaTools".to_string())
+}
+
+fn device_name(settings: &ClipboardShareSettings) -> String {
+    match settings.device_name.trim() {
+        "" => computer_name(),
+        name => name.to_string(),
+    }
+}
+
+fn share_settings(app: &AppHandle) -> ClipboardShareSettings {
+    app.state::<AppState>()
+        .settings
+        .lock()
+        .unwrap()
+        .clipboard_share
+        .clone()
+}
+
+// Read one length-prefixed message
+async fn read_message(stream: &mut TcpStream) -> Result<Vec<u8>, String> {
+    let len = stream.read_u32().await.map_err(|e| e.to_string())? as usize;
+    if len > MAX_MESSAGE {
+        return Err("Message too large".to_string());
+    }
+    let mut message = Vec::new();
+    stream
+        .take(len as u64)
+        .read_to_end(&mut message)
+        .await
+        .map_err(|e| e.to_string())?;
+    if message.len() < len {
+        return Err("Message cut short".to_string());
+    }
+    Ok(message)
+}
+
+// Open a message, refusing one that was already accepted
+fn accept_message(key: &Key, message: &[u8]) -> Result<Payload, String> {
+    let now = now_secs();
+    let payload = open_payload(key, message, now)?;
+    if !SEEN
+        .lock()
+        .unwrap()
+        .first_sighting(message, payload.sent_at, now)
+    {
+        return Err("Message already received".to_string());
+    }
+    Ok(payload)
+}
+
+async fn receive(app: &AppHandle, key: &Key, mut stream: TcpStream) -> Result<String, String> {
+    let message = tokio::time::timeout(READ_TIMEOUT, read_message(&mut stream))
+        .await
+        .unwrap_or_else(|_| Err("Timed out".to_string()));
+    let opened = match message {
+        Ok(message) => {
+            let usage = Usage {
+                downloaded: message.len() as u64,
+                uploaded: 0,
+            };
+            transfers::record(app, "clipboard-share", usage);
+            accept_message(key, &message)
+        }
+        Err(e) => Err(e),
+    };
+    let _ = stream.write_u8(opened.is_ok() as u8).await;
+    let payload = opened?;
+    app.clipboard()
+        .write_text(payload.text)
+        .map_err(|e| format!("Failed to write clipboard: {}", e))?;
+    Ok(payload.from)
+}
+
+async fn run_server(app: AppHandle, listener: TcpListener, key: Key) {
+    let active = Arc::new(AtomicUsize::new(0));
+    loop {
+        let (stream, peer) = match listener.accept().await {
+            Ok(connection) => connection,
+            Err(e) => {
+                log::warn!("Clipboard share accept failed: {}", e);
+                continue;
+            }
+        };
+        if active.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+            active.fetch_sub(1, Ordering::SeqCst);
+            log::warn!("Too many clipboard connections, dropped {}", peer);
+            continue;
+        }
+        let (app, active) = (app.clone(), active.clone());
+        tauri::async_runtime::spawn(async move {
+            let received = receive(&app, &key, stream).await;
+            active.fetch_sub(1, Ordering::SeqCst);
+            match received {
+                Ok(from) => {
+                    log::info!("Clipboard received from {} ({})", from, peer);
+                    let _ = app.emit("clipboard-received", &from);
+                    notifications::notify_if_hidden(
+                        &app,
+                        "Clipboard received",
+                        &format!("From {}; ready to paste", from),
+                        NotifyAction::None,
+                    );
+                }
+                Err(e) => log::warn!("Refused clipboard from {}: {}", peer, e),
+            }
+        });
+    }
+}
+
+/// Start, restart or stop sharing to match `settings`
+pub fn apply_settings(app: &AppHandle, settings: &ClipboardShareSettings) {
+    let mut sharing = SHARING.lock().unwrap();
+    let code_ok = settings.code.trim().chars().count() >= MIN_CODE_LEN;
+    if settings.enabled && !code_ok {
+        log::warn!(
+            "Clipboard sharing needs a share code of at least {} characters",
+            MIN_CODE_LEN
+        );
+    }
+    let wanted = (settings.enabled && code_ok).then_some(settings);
+    if sharing.as_ref().map(|s| &s.settings) == wanted {
+        return;
+    }
+    if let Some(previous) = sharing.take() {
+        previous.task.abort();
+    }
+    let Some(settings) = wanted.cloned() else {
+        return;
+    };
+
+    let instance_id = format!("{:016x}", rand::random::<u64>());
+    let txt = vec![format!("id={}", instance_id), "clipboard=1".to_string()];
+    let (app, task_settings) = (app.clone(), settings.clone());
+    let task = tauri::async_runtime::spawn(async move {
+        let listener = match TcpListener::bind("0.0.0.0:0").await {
+            Ok(listener) => listener,
+            Err(e) => {
+                log::warn!("Clipboard sharing unavailable: {}", e);
+                return;
+            }
+        };
+        let port = listener.local_addr().map(|a| a.port()).unwrap_or(0);
+        log::info!("Clipboard sharing on port {}", port);
+        let key = share_key(&task_settings.code);
+        let advertise = async {
+            let name = device_name(&task_settings);
+            if let Err(e) = mdns::advertise(SERVICE.to_string(), name, port, txt).await {
+                log::warn!("Clipboard share advertisement failed: {}", e);
+            }
+        };
+        futures_util::future::join(run_server(app, listener, key), advertise).await;
+    });
+    *sharing = Some(Sharing {
+        settings,
+        instance_id,
+        task,
+    });
+}
+
+async fn find_devices() -> Result<Vec<ShareDevice>, String> {
+    let own_id = SHARING
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|s| s.instance_id.clone());
+    let instances = mdns::browse(SERVICE, BROWSE_TIME).await?;
+    Ok(instances
+        .into_iter()
+        .filter(|i| i.txt.contains_key("clipboard") && i.txt.get("id") != own_id.as_ref())
+        .map(|i| ShareDevice {
+            name: i.instance,
+            address: i.address.to_string(),
+            port: i.port,
+        })
+        .collect())
+}
+
+/// Other devices on the LAN that accept clipboard text
+#[tauri::command]
+pub async fn get_clipboard_devices(app: AppHandle) -> Result<Vec<ShareDevice>, String> {
+    if !share_settings(&app).enabled {
+        return Err("Turn on clipboard sharing in Settings first".to_string());
+    }
+    find_devices().await
+}
+
+/// Send the clipboard text to the device named `device` (or the only one, when empty)
+#[tauri::command]
+pub async fn send_clipboard_to(app: AppHandle, device: String) -> Result<String, String> {
+    let settings = share_settings(&app);
+    if !settings.enabled || settings.code.trim().chars().count() < MIN_CODE_LEN {
+        return Err(format!(
+            "Turn on clipboard sharing with a share code of at least {} characters first",
+            MIN_CODE_LEN
+        ));
+    }
+    let text = app
+        .clipboard()
+        .read_text()
+        .ok()
+        .filter(|t| !t.is_empty())
+        .ok_or("The clipboard has no text")?;
+
+    let devices = find_devices().await?;
+    let wanted = device.trim();
+    let target = match (wanted, devices.as_slice()) {
+        (_, []) => return Err("No other devices found; is sharing on there too?".to_string()),
+        ("", [only]) => only,
+        ("", _) => {
+            return Err(format!(
+                "Which device? {}",
+                devices
+                    .iter()
+                    .map(|d| d.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))
+        }
+        (name, _) => devices
+            .iter()
+            .find(|d| d.name.eq_ignore_ascii_case(name))
+            .or_else(|| {
+                let name = name.to_lowercase();
+                devices
+                    .iter()
+                    .find(|d| d.name.to_lowercase().starts_with(&name))
+            })
+            .ok_or_else(|| format!("No device called \"{}\"", name))?,
+    };
+
+    let payload = Payload {
+        from: device_name(&settings),
+        sent_at: now_secs(),
+        text,
+    };
+    let key = share_key(&settings.code);
+    let message = seal_payload(&key, &payload)?;
+    if message.len() > MAX_MESSAGE {
+        return Err("The clipboard text is too large to share".to_string());
+    }
+    let address = format!("{}:{}", target.address, target.port);
+    let send = async {
+        let mut stream = TcpStream::connect(&address)
+            .await
+            .map_err(|e| format!("Failed to connect to {}: {}", target.name, e))?;
+        stream
+            .write_u32(message.len() as u32)
+            .await
+            .map_err(|e| e.to_string())?;
+        stream
+            .write_all(&message)
+            .await
+            .map_err(|e| e.to_string())?;
+        stream.read_u8().await.map_err(|e| e.to_string())
+    };
+    let accepted = tokio::time::timeout(CONNECT_TIMEOUT, send)
+        .await
+        .map_err(|_| format!("{} didn't answer", target.name))??;
+    let usage = Usage {
+        downloaded: 0,
+        uploaded: message.len() as u64,
+    };
+    transfers::record(&app, "clipboard-share", usage);
+    if accepted != 1 {
+        return Err(format!(
+            "{} refused the clipboard; are the share codes the same?",
+            target.name
+        ));
+    }
+    Ok(target.name.clone())
+}
@@ -0,0 +1,606 @@
+// Color notation parsing and conversion (hex, RGB, HSL, HSV, CMYK, OKLCH, Lab, XYZ, CSS names)
+// Picked colors come back from `pick_color` in every format at once, and `convert_color`
+// turns any supported notation into another one. The output strings match what the color
+// panel displays and copies.
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ColorFormats {
+    pub hex: String,
+    pub rgb: String,
+    pub hsl: String,
+    pub hsv: String,
+    pub cmyk: String,
+    pub oklch: String,
+    pub lab: String,
+    pub xyz: String,
+    pub name: String,     // nearest CSS named color
+    pub name_exact: bool, // `name` is exactly this color
+}
+
+/// WCAG 2.1 contrast between a text color and its background
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ContrastResult {
+    pub foreground: String,
+    pub background: String,
+    pub ratio: f64,       // 1-21, rounded to two decimals for display
+    pub aa_normal: bool,  // 4.5:1
+    pub aa_large: bool,   // 3:1, also the minimum for UI components and graphics
+    pub aaa_normal: bool, // 7:1
+    pub aaa_large: bool,  // 4.5:1
+}
+
+// CSS Color Module Level 4 named colors. Where two names share a value (aqua/cyan,
+// gray/grey, ...) the first one is what gets reported.
+const CSS_COLORS: &[(&str, [u8; 3])] = &[
+    ("aliceblue", [240, 248, 255]),
+    ("antiquewhite", [250, 235, 215]),
+    ("aqua", [0, 255, 255]),
+    ("aquamarine", [127, 255, 212]),
+    ("azure", [240, 255, 255]),
+    ("beige", [245, 245, 220]),
+    ("bisque", [255, 228, 196]),
+    ("black", [0, 0, 0]),
+    ("blanchedalmond", [255, 235, 205]),
+    ("blue", [0, 0, 255]),
+    ("blueviolet", [138, 43, 226]),
+    ("brown", [165, 42, 42]),
+    ("burlywood", [222, 184, 135]),
+    ("cadetblue", [95, 158, 160]),
+    ("chartreuse", [127, 255, 0]),
+    ("chocolate", [210, 105, 30]),
+    ("coral", [255, 127, 80]),
+    ("cornflowerblue", [100, 149, 237]),
+    ("cornsilk", [255, 248, 220]),
+    ("crimson", [220, 20, 60]),
+    ("cyan", [0, 255, 255]),
+    ("darkblue", [0, 0, 139]),
+    ("darkcyan", [0, 139, 139]),
+    ("darkgoldenrod", [184, 134, 11]),
+    ("darkgray", [169, 169, 169]),
+    ("darkgreen", [0, 100, 0]),
+    ("darkgrey", [169, 169, 169]),
+    ("darkkhaki", [189, 183, 107]),
+    ("darkmagenta", [139, 0, 139]),
+    ("darkolivegreen", [85, 107, 47]),
+    ("darkorange", [255, 140, 0]),
+    ("darkorchid", [153, 50, 204]),
+    ("darkred", [139, 0, 0]),
+    ("darksalmon", [233, 150, 122]),
+    ("darkseagreen", [143, 188, 143]),
+    ("darkslateblue", [72, 61, 139]),
+    ("darkslategray", [47, 79, 79]),
+    ("darkslategrey", [47, 79, 79]),
+    ("darkturquoise", [0, 206, 209]),
+    ("darkviolet", [148, 0, 211]),
+    ("deeppink", [255, 20, 147]),
+    ("deepskyblue", [0, 191, 255]),
+    ("dimgray", [105, 105, 105]),
+    ("dimgrey", [105, 105, 105]),
+    ("dodgerblue", [30, 144, 255]),
+    ("firebrick", [178, 34, 34]),
+    ("floralwhite", [255, 250, 240]),
+    ("forestgreen", [34, 139, 34]),
+    ("fuchsia", [255, 0, 255]),
+    ("gainsboro", [220, 220, 220]),
+    ("ghostwhite", [248, 248, 255]),
+    ("gold", [255, 215, 0]),
+    ("goldenrod", [218, 165, 32]),
+    ("gray", [128, 128, 128]),
+    ("green", [0, 128, 0]),
+    ("greenyellow", [173, 255, 47]),
+    ("grey", [128, 128, 128]),
+    ("honeydew", [240, 255, 240]),
+    ("hotpink", [255, 105, 180]),
+    ("indianred", [205, 92, 92]),
+    ("indigo", [75, 0, 130]),
+    ("ivory", [255, 255, 240]),
+    ("khaki", [240, 230, 140]),
+    ("lavender", [230, 230, 250]),
+    ("lavenderblush", [255, 240, 245]),
+    ("lawngreen", [124, 252, 0]),
+    ("lemonchiffon", [255, 250, 205]),
+    ("lightblue", [173, 216, 230]),
+    ("lightcoral", [240, 128, 128]),
+    ("lightcyan", [224, 255, 255]),
+    ("lightgoldenrodyellow", [250, 250, 210]),
+    ("lightgray", [211, 211, 211]),
+    ("lightgreen", [144, 238, 144]),
+    ("lightgrey", [211, 211, 211]),
+    ("lightpink", [255, 182, 193]),
+    ("lightsalmon", [255, 160, 122]),
+    ("lightseagreen", [32, 178, 170]),
+    ("lightskyblue", [135, 206, 250]),
+    ("lightslategray", [119, 136, 153]),
+    ("lightslategrey", [119, 136, 153]),
+    ("lightsteelblue", [176, 196, 222]),
+    ("lightyellow", [255, 255, 224]),
+    ("lime", [0, 255, 0]),
+    ("limegreen", [50, 205, 50]),
+    ("linen", [250, 240, 230]),
+    ("magenta", [255, 0, 255]),
+    ("maroon", [128, 0, 0]),
+    ("mediumaquamarine", [102, 205, 170]),
+    ("mediumblue", [0, 0, 205]),
+    ("mediumorchid", [186, 85, 211]),
+    ("mediumpurple", [147, 112, 219]),
+    ("mediumseagreen", [60, 179, 113]),
+    ("mediumslateblue", [123, 104, 238]),
+    ("mediumspringgreen", [0, 250, 154]),
+    ("mediumturquoise", [72, 209, 204]),
+    ("mediumvioletred", [199, 21, 133]),
+    ("midnightblue", [25, 25, 112]),
+    ("mintcream", [245, 255, 250]),
+    ("mistyrose", [255, 228, 225]),
+    ("moccasin", [255, 228, 181]),
+    ("navajowhite", [255, 222, 173]),
+    ("navy", [0, 0, 128]),
+    ("oldlace", [253, 245, 230]),
+    ("olive", [128, 128, 0]),
+    ("olivedrab", [107, 142, 35]),
+    ("orange", [255, 165, 0]),
+    ("orangered", [255, 69, 0]),
+    ("orchid", [218, 112, 214]),
+    ("palegoldenrod", [238, 232, 170]),
+    ("palegreen", [152, 251, 152]),
+    ("paleturquoise", [175, 238, 238]),
+    ("palevioletred", [219, 112, 147]),
+    ("papayawhip", [255, 239, 213]),
+    ("peachpuff", [255, 218, 185]),
+    ("peru", [205, 133, 63]),
+    ("pink", [255, 192, 203]),
+    ("plum", [221, 160, 221]),
+    ("powderblue", [176, 224, 230]),
+    ("purple", [128, 0, 128]),
+    ("rebeccapurple", [102, 51, 153]),
+    ("red", [255, 0, 0]),
+    ("rosybrown", [188, 143, 143]),
+    ("royalblue", [65, 105, 225]),
+    ("saddlebrown", [139, 69, 19]),
+    ("salmon", [250, 128, 114]),
+    ("sandybrown", [244, 164, 96]),
+    ("seagreen", [46, 139, 87]),
+    ("seashell", [255, 245, 238]),
+    ("sienna", [160, 82, 45]),
+    ("silver", [192, 192, 192]),
+    ("skyblue", [135, 206, 235]),
+    ("slateblue", [106, 90, 205]),
+    ("slategray", [112, 128, 144]),
+    ("slategrey", [112, 128, 144]),
+    ("snow", [255, 250, 250]),
+    ("springgreen", [0, 255, 127]),
+    ("steelblue", [70, 130, 180]),
+    ("tan", [210, 180, 140]),
+    ("teal", [0, 128, 128]),
+    ("thistle", [216, 191, 216]),
+    ("tomato", [255, 99, 71]),
+    ("turquoise", [64, 224, 208]),
+    ("violet", [238, 130, 238]),
+    ("wheat", [245, 222, 179]),
+    ("white", [255, 255, 255]),
+    ("whitesmoke", [245, 245, 245]),
+    ("yellow", [255, 255, 0]),
+    ("yellowgreen", [154, 205, 50]),
+];
+
+/// Output notations accepted by `convert_color`
+pub const FORMATS: &[&str] = &[
+    "hex", "rgb", "hsl", "hsv", "cmyk", "oklch", "lab", "xyz", "name",
+];
+
+// D65 reference white for XYZ / Lab
+const WHITE: [f64; 3] = [95.047, 100.0, 108.883];
+
+fn to_linear(channel: u8) -> f64 {
+    let c = channel as f64 / 255.0;
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn from_linear(c: f64) -> u8 {
+    let c = if c > 0.0031308 {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    } else {
+        12.92 * c
+    };
+    (c * 255.0).round().clamp(0.0, 255.0) as u8
+}
+
+fn channel(value: f64) -> u8 {
+    value.round().clamp(0.0, 255.0) as u8
+}
+
+// Hue sector shared by HSL and HSV: chroma `c`, second component `x`, offset `m`
+fn from_hue(h: f64, c: f64, x: f64, m: f64) -> [u8; 3] {
+    let (r, g, b) = match (h.rem_euclid(360.0) / 60.0) as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    [
+        channel((r + m) * 255.0),
+        channel((g + m) * 255.0),
+        channel((b + m) * 255.0),
+    ]
+}
+
+fn hsl_to_rgb(h: f64, s: f64, l: f64) -> [u8; 3] {
+    let (s, l) = (s / 100.0, l / 100.0);
+    let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+    let x = c * (1.0 - ((h.rem_euclid(360.0) / 60.0) % 2.0 - 1.0).abs());
+    from_hue(h, c, x, l - c / 2.0)
+}
+
+fn hsv_to_rgb(h: f64, s: f64, v: f64) -> [u8; 3] {
+    let (s, v) = (s / 100.0, v / 100.0);
+    let c = v * s;
+    let x = c * (1.0 - ((h.rem_euclid(360.0) / 60.0) % 2.0 - 1.0).abs());
+    from_hue(h, c, x, v - c)
+}
+
+fn cmyk_to_rgb(c: f64, m: f64, y: f64, k: f64) -> [u8; 3] {
+    let k = 1.0 - k / 100.0;
+    [
+        channel(255.0 * (1.0 - c / 100.0) * k),
+        channel(255.0 * (1.0 - m / 100.0) * k),
+        channel(255.0 * (1.0 - y / 100.0) * k),
+    ]
+}
+
+fn xyz_to_rgb(x: f64, y: f64, z: f64) -> [u8; 3] {
+    let (x, y, z) = (x / 100.0, y / 100.0, z / 100.0);
+    [
+        from_linear(x * 3.2406 + y * -1.5372 + z * -0.4986),
+        from_linear(x * -0.9689 + y * 1.8758 + z * 0.0415),
+        from_linear(x * 0.0557 + y * -0.204 + z * 1.057),
+    ]
+}
+
+fn lab_to_rgb(l: f64, a: f64, b: f64) -> [u8; 3] {
+    let fy = (l + 16.0) / 116.0;
+    let f = |t: f64| {
+        let cube = t.powi(3);
+        if cube > 0.008856 {
+            cube
+        } else {
+            (t - 16.0 / 116.0) / 7.787
+        }
+    };
+    xyz_to_rgb(
+        f(a / 500.0 + fy) * WHITE[0],
+        f(fy) * WHITE[1],
+        f(fy - b / 200.0) * WHITE[2],
+    )
+}
+
+// `l` is 0-1, `h` in degrees
+fn oklch_to_rgb(l: f64, c: f64, h: f64) -> [u8; 3] {
+    let (a, b) = (c * h.to_radians().cos(), c * h.to_radians().sin());
+    let l_ = (l + 0.3963377774 * a + 0.2158037573 * b).powi(3);
+    let m_ = (l - 0.1055613458 * a - 0.0638541728 * b).powi(3);
+    let s_ = (l - 0.0894841775 * a - 1.291485548 * b).powi(3);
+    [
+        from_linear(4.0767416621 * l_ - 3.3077115913 * m_ + 0.2309699292 * s_),
+        from_linear(-1.2684380046 * l_ + 2.6097574011 * m_ - 0.3413193965 * s_),
+        from_linear(-0.0041960863 * l_ - 0.7034186147 * m_ + 1.707614701 * s_),
+    ]
+}
+
+fn rgb_to_xyz(rgb: [u8; 3]) -> [f64; 3] {
+    let [r, g, b] = rgb.map(|c| to_linear(c) * 100.0);
+    [
+        r * 0.4124 + g * 0.3576 + b * 0.1805,
+        r * 0.2126 + g * 0.7152 + b * 0.0722,
+        r * 0.0193 + g * 0.1192 + b * 0.9505,
+    ]
+}
+
+fn rgb_to_lab(rgb: [u8; 3]) -> [f64; 3] {
+    let xyz = rgb_to_xyz(rgb);
+    let f = |t: f64| {
+        if t > 0.008856 {
+            t.cbrt()
+        } else {
+            7.787 * t + 16.0 / 116.0
+        }
+    };
+    let [x, y, z] = [0, 1, 2].map(|i| f(xyz[i] / WHITE[i]));
+    [116.0 * y - 16.0, 500.0 * (x - y), 200.0 * (y - z)]
+}
+
+fn rgb_to_oklab(rgb: [u8; 3]) -> [f64; 3] {
+    let [r, g, b] = rgb.map(to_linear);
+    let l = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+    let m = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+    let s = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+    [
+        0.2104542553 * l + 0.793617785 * m - 0.0040720468 * s,
+        1.9779984951 * l - 2.428592205 * m + 0.4505937099 * s,
+        0.0259040371 * l + 0.7827717662 * m - 0.808675766 * s,
+    ]
+}
+
+// Hue in degrees, saturation and lightness / value in percent
+fn rgb_to_hsl_hsv(rgb: [u8; 3]) -> (f64, [f64; 2], [f64; 2]) {
+    let [r, g, b] = rgb.map(|c| c as f64 / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let d = max - min;
+    let hue = if d == 0.0 {
+        0.0
+    } else if max == r {
+        ((g - b) / d).rem_euclid(6.0) * 60.0
+    } else if max == g {
+        ((b - r) / d + 2.0) * 60.0
+    } else {
+        ((r - g) / d + 4.0) * 60.0
+    };
+    let l = (max + min) / 2.0;
+    let hsl_s = if d == 0.0 {
+        0.0
+    } else {
+        d / (1.0 - (2.0 * l - 1.0).abs())
+    };
+    let hsv_s = if max == 0.0 { 0.0 } else { d / max };
+    (
+        hue,
+        [hsl_s * 100.0, l * 100.0],
+        [hsv_s * 100.0, max * 100.0],
+    )
+}
+
+fn hex(rgb: [u8; 3]) -> String {
+    format!("#{:02X}{:02X}{:02X}", rgb[0], rgb[1], rgb[2])
+}
+
+// Two decimals without trailing zeros, e.g. 0.1 rather than 0.10
+fn short(value: f64) -> String {
+    let text = format!("{:.2}", value);
+    text.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+/// Nearest CSS named color (by OKLab distance) and whether it's an exact match
+pub fn nearest_name(rgb: [u8; 3]) -> (&'static str, bool) {
+    if let Some((name, _)) = CSS_COLORS.iter().find(|(_, value)| *value == rgb) {
+        return (name, true);
+    }
+    let target = rgb_to_oklab(rgb);
+    let distance = |value: [u8; 3]| {
+        let lab = rgb_to_oklab(value);
+        (0..3).map(|i| (lab[i] - target[i]).powi(2)).sum::<f64>()
+    };
+    let (name, _) = CSS_COLORS
+        .iter()
+        .min_by(|a, b| distance(a.1).total_cmp(&distance(b.1)))
+        .unwrap();
+    (name, false)
+}
+
+/// `rgb` written in one of `FORMATS`
+pub fn format(rgb: [u8; 3], to_format: &str) -> Result<String, String> {
+    let (hue, hsl, hsv) = rgb_to_hsl_hsv(rgb);
+    let round = |v: f64| v.round() as i64;
+    Ok(match to_format.trim().to_lowercase().as_str() {
+        "hex" => hex(rgb),
+        "rgb" => format!("rgb({}, {}, {})", rgb[0], rgb[1], rgb[2]),
+        "hsl" => format!(
+            "hsl({}, {}%, {}%)",
+            round(hue) % 360,
+            round(hsl[0]),
+            round(hsl[1])
+        ),
+        "hsv" => format!(
+            "hsv({}, {}%, {}%)",
+            round(hue) % 360,
+            round(hsv[0]),
+            round(hsv[1])
+        ),
+        "cmyk" => {
+            let [r, g, b] = rgb.map(|c| c as f64 / 255.0);
+            let k = 1.0 - r.max(g).max(b);
+            if k >= 1.0 {
+                "cmyk(0%, 0%, 0%, 100%)".to_string()
+            } else {
+                let part = |c: f64| round((1.0 - c - k) / (1.0 - k) * 100.0);
+                format!(
+                    "cmyk({}%, {}%, {}%, {}%)",
+                    part(r),
+                    part(g),
+                    part(b),
+                    round(k * 100.0)
+                )
+            }
+        }
+        "oklch" => {
+            let [l, a, b] = rgb_to_oklab(rgb);
+            let chroma = (a * a + b * b).sqrt();
+            // Grays have no meaningful hue
+            let hue = if chroma < 0.0005 {
+                0.0
+            } else {
+                b.atan2(a).to_degrees().rem_euclid(360.0)
+            };
+            format!(
+                "oklch({}% {} {})",
+                round(l * 100.0),
+                short(chroma),
+                round(hue) % 360
+            )
+        }
+        "lab" => {
+            let [l, a, b] = rgb_to_lab(rgb);
+            format!("lab({}% {} {})", round(l), round(a), round(b))
+        }
+        "xyz" => {
+            let [x, y, z] = rgb_to_xyz(rgb);
+            format!("xyz({}%, {}%, {}%)", round(x), round(y), round(z))
+        }
+        "name" => nearest_name(rgb).0.to_string(),
+        other => {
+            return Err(format!(
+                "Unknown color format: {} (use one of {})",
+                other,
+                FORMATS.join(", ")
+            ))
+        }
+    })
+}
+
+/// Every format of `rgb` at once
+pub fn describe(rgb: [u8; 3]) -> ColorFormats {
+    let get = |to_format: &str| format(rgb, to_format).unwrap_or_default();
+    let (name, name_exact) = nearest_name(rgb);
+    ColorFormats {
+        hex: get("hex"),
+        rgb: get("rgb"),
+        hsl: get("hsl"),
+        hsv: get("hsv"),
+        cmyk: get("cmyk"),
+        oklch: get("oklch"),
+        lab: get("lab"),
+        xyz: get("xyz"),
+        name: name.to_string(),
+        name_exact,
+    }
+}
+
+// WCAG relative luminance, 0 for black to 1 for white
+fn relative_luminance(rgb: [u8; 3]) -> f64 {
+    0.2126 * to_linear(rgb[0]) + 0.7152 * to_linear(rgb[1]) + 0.0722 * to_linear(rgb[2])
+}
+
+/// Contrast of `foreground` text on `background`. The levels are judged on the exact ratio;
+/// WCAG doesn't allow rounding 4.47 up to 4.5.
+pub fn contrast(foreground: [u8; 3], background: [u8; 3]) -> ContrastResult {
+    let (a, b) = (
+        relative_luminance(foreground),
+        relative_luminance(background),
+    );
+    let ratio = (a.max(b) + 0.05) / (a.min(b) + 0.05);
+    ContrastResult {
+        foreground: hex(foreground),
+        background: hex(background),
+        ratio: (ratio * 100.0).round() / 100.0,
+        aa_normal: ratio >= 4.5,
+        aa_large: ratio >= 3.0,
+        aaa_normal: ratio >= 7.0,
+        aaa_large: ratio >= 4.5,
+    }
+}
+
+/// Parse `#RGB`, `#RRGGBB` (the `#` is optional), a CSS color name, or
+/// `rgb()` / `hsl()` / `hsv()` / `cmyk()` / `oklch()` / `lab()` / `xyz()` notation
+pub fn parse(input: &str) -> Result<[u8; 3], String> {
+    let text = input.trim().to_lowercase();
+    let invalid = || format!("Unrecognized color: {}", input.trim());
+
+    if let Some((_, rgb)) = CSS_COLORS.iter().find(|(name, _)| *name == text) {
+        return Ok(*rgb);
+    }
+
+    let digits = text.strip_prefix('#').unwrap_or(&text);
+    if matches!(digits.len(), 3 | 6) && digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        let digits: String = if digits.len() == 3 {
+            digits.chars().flat_map(|c| [c, c]).collect()
+        } else {
+            digits.to_string()
+        };
+        let byte = |i: usize| u8::from_str_radix(&digits[i..i + 2], 16).unwrap();
+        return Ok([byte(0), byte(2), byte(4)]);
+    }
+
+    let (function, rest) = text.split_once('(').ok_or_else(invalid)?;
+    let args = rest.trim_end().strip_suffix(')').ok_or_else(invalid)?;
+    // Commas, spaces and the "/ alpha" separator all split arguments
+    let values = args
+        .split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+        .filter(|arg| !arg.is_empty())
+        .map(|arg| {
+            let percent = arg.ends_with('%');
+            arg.trim_end_matches('%')
+                .trim_end_matches("deg")
+                .parse::<f64>()
+                .ok()
+                .filter(|v| v.is_finite())
+                .map(|v| (v, percent))
+                .ok_or_else(invalid)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let v = |i: usize| values[i].0;
+    let pct = |i: usize| values[i].0.clamp(0.0, 100.0);
+    let arity = |expected: &[usize]| {
+        if expected.contains(&values.len()) {
+            Ok(())
+        } else {
+            Err(invalid())
+        }
+    };
+
+    match function.trim() {
+        "rgb" | "rgba" => {
+            arity(&[3, 4])?;
+            let part = |i: usize| {
+                let (value, percent) = values[i];
+                channel(if percent {
+                    value * 255.0 / 100.0
+                } else {
+                    value
+                })
+            };
+            Ok([part(0), part(1), part(2)])
+        }
+        "hsl" | "hsla" => {
+            arity(&[3, 4])?;
+            Ok(hsl_to_rgb(v(0), pct(1), pct(2)))
+        }
+        "hsv" | "hsb" => {
+            arity(&[3])?;
+            Ok(hsv_to_rgb(v(0), pct(1), pct(2)))
+        }
+        "cmyk" => {
+            arity(&[4])?;
+            Ok(cmyk_to_rgb(pct(0), pct(1), pct(2), pct(3)))
+        }
+        "oklch" => {
+            arity(&[3, 4])?;
+            // Lightness as a percentage or a 0-1 number
+            let (l, percent) = values[0];
+            let l = if percent || l > 1.0 { l / 100.0 } else { l };
+            Ok(oklch_to_rgb(l.clamp(0.0, 1.0), v(1).max(0.0), v(2)))
+        }
+        "lab" => {
+            arity(&[3, 4])?;
+            Ok(lab_to_rgb(pct(0), v(1), v(2)))
+        }
+        "xyz" => {
+            arity(&[3])?;
+            Ok(xyz_to_rgb(v(0), v(1), v(2)))
+        }
+        _ => Err(invalid()),
+    }
+}
+
+/// Every format of a color written in any supported notation
+#[tauri::command]
+pub fn get_color_formats(input: String) -> Result<ColorFormats, String> {
+    parse(&input).map(describe)
+}
+
+/// Write a color given in any supported notation as `to_format` (see `FORMATS`)
+#[tauri::command]
+pub fn convert_color(input: String, to_format: String) -> Result<String, String> {
+    format(parse(&input)?, &to_format)
+}
+
+/// WCAG contrast of two colors written in any supported notation
+#[tauri::command]
+pub fn check_contrast(fg: String, bg: String) -> Result<ContrastResult, String> {
+    Ok(contrast(parse(&fg)?, parse(&bg)?))
+}
@@ -0,0 +1,91 @@
+// History of colors picked from the screen
+// Every successful pick is saved to disk with when it was taken and which monitor it came
+// from, so a color grabbed yesterday can still be found after a restart.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::{platform, window_placement};
+
+const HISTORY_FILE: &str = "color_history.json";
+
+// Oldest picks are dropped beyond this
+const HISTORY_LIMIT: usize = 500;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColorHistoryEntry {
+    pub id: u64,
+    pub hex: String,
+    pub timestamp: u64,          // seconds since the epoch
+    pub monitor: Option<String>, // name of the monitor the color was picked on
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SavedHistory {
+    colors: Vec<ColorHistoryEntry>, // newest first
+}
+
+// Name of the monitor under the cursor, which is where the pick just happened
+fn cursor_monitor_name(app: &AppHandle) -> Option<String> {
+    let (x, y, w, h) = platform::get_cursor_monitor_work_area()?;
+    let monitors = app.available_monitors().ok()?;
+    let index = monitors
+        .iter()
+        .position(|m| window_placement::contains(m, x + w / 2, y + h / 2))?;
+    Some(
+        monitors[index]
+            .name()
+            .cloned()
+            .unwrap_or_else(|| format!("Display {}", index + 1)),
+    )
+}
+
+/// Save a picked color; failures are only logged so they never fail the pick itself
+pub fn record(app: &AppHandle, hex: &str) {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let mut saved: SavedHistory = crate::load_json_file(app, HISTORY_FILE);
+    let id = saved.colors.iter().map(|c| c.id).max().unwrap_or(0) + 1;
+    saved.colors.insert(
+        0,
+        ColorHistoryEntry {
+            id,
+            hex: hex.to_uppercase(),
+            timestamp,
+            monitor: cursor_monitor_name(app),
+        },
+    );
+    saved.colors.truncate(HISTORY_LIMIT);
+    if let Err(e) = crate::save_json_file(app, HISTORY_FILE, &saved) {
+        log::warn!("Failed to save color history: {}", e);
+    }
+}
+
+/// Picked colors, newest first
+#[tauri::command]
+pub fn get_color_history(app: AppHandle) -> Result<Vec<ColorHistoryEntry>, String> {
+    crate::app_lock::ensure_unlocked()?;
+    let saved: SavedHistory = crate::load_json_file(&app, HISTORY_FILE);
+    Ok(saved.colors)
+}
+
+#[tauri::command]
+pub fn delete_color_history_item(app: AppHandle, id: u64) -> Result<(), String> {
+    crate::app_lock::ensure_unlocked()?;
+    let mut saved: SavedHistory = crate::load_json_file(&app, HISTORY_FILE);
+    let before = saved.colors.len();
+    saved.colors.retain(|c| c.id != id);
+    if saved.colors.len() == before {
+        return Err(format!("No color with id {} in the history", id));
+    }
+    crate::save_json_file(&app, HISTORY_FILE, &saved)
+}
+
+#[tauri::command]
+pub fn clear_color_history(app: AppHandle) -> Result<(), String> {
+    crate::save_json_file(&app, HISTORY_FILE, &SavedHistory::default())
+}
@@ -0,0 +1,178 @@
+// Magnifier loupe for the screen color picker
+// While a pick is running, a small frameless window follows the cursor with the pixels around
+// it enlarged, the pixel under the cursor outlined, and its hex value. The platform pickers
+// report cursor moves (see platform::LoupeCallback); this module owns the window. Wayland's
+// portal picker brings its own magnifier, so the loupe never shows there.
+
+use std::sync::Arc;
+
+use serde::Serialize;
+use tauri::{
+    AppHandle, Emitter, Manager, Monitor, PhysicalPosition, WebviewUrl, WebviewWindow,
+    WebviewWindowBuilder,
+};
+
+use crate::platform::{self, ColorPickCallback, LoupeCallback, LOUPE_SIZE};
+use crate::tool_windows::{self, LABEL_PREFIX};
+use crate::{color_formats, color_history};
+
+const LOUPE_TOOL: &str = "loupe";
+const WINDOW_SIZE: (f64, f64) = (132.0, 156.0);
+const CURSOR_OFFSET: i32 = 24; // physical pixels between the cursor and the loupe
+
+/// One loupe update (`loupe-frame`)
+#[derive(Debug, Clone, Serialize)]
+pub struct LoupeFrame {
+    pub size: i32,           // pixels per row
+    pub pixels: Vec<String>, // hex colors, row by row
+    pub hex: String,         // the pixel under the cursor
+}
+
+fn hex(pixel: &[u8; 3]) -> String {
+    format!("#{:02X}{:02X}{:02X}", pixel[0], pixel[1], pixel[2])
+}
+
+fn open(app: &AppHandle) -> Result<WebviewWindow, String> {
+    let label = format!("{}{}", LABEL_PREFIX, LOUPE_TOOL);
+    if let Some(window) = app.get_webview_window(&label) {
+        return Ok(window);
+    }
+    WebviewWindowBuilder::new(
+        app,
+        &label,
+        WebviewUrl::App(format!("index.html?window={}", LOUPE_TOOL).into()),
+    )
+    .title("BunchaTools - Loupe")
+    .inner_size(WINDOW_SIZE.0, WINDOW_SIZE.1)
+    .decorations(false)
+    .transparent(true)
+    .shadow(false)
+    .resizable(false)
+    .always_on_top(true)
+    .skip_taskbar(true)
+    .focused(false)
+    .visible(false)
+    .build()
+    .map_err(|e| format!("Failed to open the loupe: {}", e))
+}
+
+// Below-right of the cursor, flipped to the other side near the edges of its monitor
+fn position_near(monitors: &[Monitor], window: &WebviewWindow, x: i32, y: i32) {
+    let Ok(size) = window.outer_size() else {
+        return;
+    };
+    let (width, height) = (size.width as i32, size.height as i32);
+    let mut left = x + CURSOR_OFFSET;
+    let mut top = y + CURSOR_OFFSET;
+
+    let monitor = monitors.iter().find(|m| {
+        let (position, size) = (m.position(), m.size());
+        x >= position.x
+            && x < position.x + size.width as i32
+            && y >= position.y
+            && y < position.y + size.height as i32
+    });
+    if let Some(monitor) = monitor {
+        let (position, size) = (monitor.position(), monitor.size());
+        if left + width > position.x + size.width as i32 {
+            left = x - CURSOR_OFFSET - width;
+        }
+        if top + height > position.y + size.height as i32 {
+            top = y - CURSOR_OFFSET - height;
+        }
+    }
+    let _ = window.set_position(PhysicalPosition::new(left, top));
+}
+
+/// Side of the square picks average over, from the settings
+pub fn sample_size_setting(app: &AppHandle) -> i32 {
+    let state = app.state::<crate::AppState>();
+    let size = state.settings.lock().unwrap().color_sample_size;
+    size
+}
+
+fn check_sample_size(sample_size: i32) -> Result<(), String> {
+    if platform::SAMPLE_SIZES.contains(&sample_size) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Sample size must be one of {:?} pixels",
+            platform::SAMPLE_SIZES
+        ))
+    }
+}
+
+// Open the loupe and return the callback that moves and repaints it
+fn start_loupe(app: &AppHandle) -> LoupeCallback {
+    // A missing loupe shouldn't stop the pick
+    let loupe = open(app).map_err(|e| log::warn!("{}", e)).ok();
+    let monitors = app.available_monitors().unwrap_or_default();
+    let app = app.clone();
+    Arc::new(move |x, y, pixels| {
+        let Some(loupe) = &loupe else {
+            return;
+        };
+        let center = ((LOUPE_SIZE / 2) * LOUPE_SIZE + LOUPE_SIZE / 2) as usize;
+        let frame = LoupeFrame {
+            size: LOUPE_SIZE,
+            hex: pixels.get(center).map(hex).unwrap_or_default(),
+            pixels: pixels.iter().map(hex).collect(),
+        };
+        position_near(&monitors, loupe, x, y);
+        if !loupe.is_visible().unwrap_or(false) {
+            let _ = loupe.show();
+        }
+        let _ = app.emit_to(loupe.label(), "loupe-frame", frame);
+    })
+}
+
+/// Pick a color from the screen, showing the loupe while the cursor moves. The result is the
+/// average of the `sample_size`² pixels around the click (see `platform::SAMPLE_SIZES`).
+pub async fn pick_color(window: WebviewWindow, sample_size: i32) -> Result<String, String> {
+    check_sample_size(sample_size)?;
+    let app = window.app_handle().clone();
+    let on_move = start_loupe(&app);
+
+    let result = platform::pick_color_impl(window, on_move, sample_size).await;
+    tool_windows::close_tool_window(&app, LOUPE_TOOL);
+    if let Ok(hex) = &result {
+        color_history::record(&app, hex);
+    }
+    result
+}
+
+/// Pick colors until Escape or a right click. Each one is saved to the history and sent as a
+/// `color-picked` event as soon as it's clicked; the whole list is returned at the end.
+pub async fn pick_colors_continuous(
+    window: WebviewWindow,
+    sample_size: i32,
+) -> Result<Vec<String>, String> {
+    check_sample_size(sample_size)?;
+    let app = window.app_handle().clone();
+    let on_move = start_loupe(&app);
+    let on_pick: ColorPickCallback = {
+        let app = app.clone();
+        Arc::new(move |hex| {
+            color_history::record(&app, &hex);
+            let _ = app.emit("color-picked", hex);
+        })
+    };
+
+    let result = platform::pick_colors_continuous_impl(window, on_move, sample_size, on_pick).await;
+    tool_windows::close_tool_window(&app, LOUPE_TOOL);
+    result
+}
+
+/// Pick the text color, then the background, and report their WCAG contrast. Cancelling
+/// either pick cancels both.
+pub async fn pick_contrast(
+    window: WebviewWindow,
+    sample_size: i32,
+) -> Result<color_formats::ContrastResult, String> {
+    let foreground = pick_color(window.clone(), sample_size).await?;
+    let background = pick_color(window, sample_size).await?;
+    Ok(color_formats::contrast(
+        color_formats::parse(&foreground)?,
+        color_formats::parse(&background)?,
+    ))
+}
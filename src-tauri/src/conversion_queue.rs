@@ -0,0 +1,394 @@
+// Batch conversions: a queue of media files converted with shared options
+// `enqueue_conversion` adds files to the queue, which starts as many of them at a time as the
+// conversion job limit allows (`jobs::JobLimits`), each as its own conversion job (so it can be
+// cancelled and paused like any other). Items that haven't started yet can be reordered or
+// removed. Every change, including each 10% step of an item, emits `conversion-queue-changed`
+// with the whole queue and its overall percent.
+// Finished items stay listed until the next batch is added to an idle queue.
+
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::jobs::{self, JobKind, MAX_JOB_LIMIT};
+use crate::notifications::{self, NotifyAction};
+use crate::{path_policy, platform, AppState, VideoConvertOptions};
+
+pub const DEFAULT_CONCURRENCY: usize = 2;
+
+static QUEUE: Mutex<ConversionQueue> = Mutex::new(ConversionQueue::new());
+
+/// One file to convert (`enqueue_conversion`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConversionItem {
+    pub input_path: String,
+    pub output_path: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QueueStatus {
+    Queued,
+    Converting,
+    Done,
+    Failed,
+    Cancelled,
+}
+
+impl QueueStatus {
+    fn is_finished(self) -> bool {
+        !matches!(self, QueueStatus::Queued | QueueStatus::Converting)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct QueuedConversion {
+    pub id: u64,
+    pub input_path: String,
+    pub output_path: String,
+    pub status: QueueStatus,
+    pub progress: u8,
+    pub error: Option<String>,
+    #[serde(skip)]
+    pub options: Option<VideoConvertOptions>, // None converts with ffmpeg's defaults
+}
+
+/// The queue as the frontend sees it (`conversion-queue-changed`)
+#[derive(Debug, Clone, Serialize)]
+pub struct QueueState {
+    pub items: Vec<QueuedConversion>, // in the order they run
+    pub concurrency: usize,
+    pub percent: u8,    // overall, finished items counting as 100
+    pub pending: usize, // queued or converting
+    pub done: usize,
+    pub failed: usize, // failed or cancelled
+}
+
+#[derive(Debug)]
+pub struct ConversionQueue {
+    items: Vec<QueuedConversion>,
+    concurrency: usize,
+    next_id: u64,
+}
+
+impl Default for ConversionQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConversionQueue {
+    pub const fn new() -> Self {
+        ConversionQueue {
+            items: Vec::new(),
+            concurrency: DEFAULT_CONCURRENCY,
+            next_id: 1,
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        self.items.iter().all(|item| item.status.is_finished())
+    }
+
+    /// Add files to the end of the queue, returning their ids. A batch added to an idle queue
+    /// replaces the finished items of the last one.
+    pub fn add(
+        &mut self,
+        items: Vec<ConversionItem>,
+        options: Option<VideoConvertOptions>,
+    ) -> Vec<u64> {
+        if self.is_idle() {
+            self.items.clear();
+        }
+        items
+            .into_iter()
+            .map(|item| {
+                let id = self.next_id;
+                self.next_id += 1;
+                self.items.push(QueuedConversion {
+                    id,
+                    input_path: item.input_path,
+                    output_path: item.output_path,
+                    status: QueueStatus::Queued,
+                    progress: 0,
+                    error: None,
+                    options: options.clone(),
+                });
+                id
+            })
+            .collect()
+    }
+
+    /// Mark as many queued items as converting as the concurrency allows, returning them
+    pub fn start_next(&mut self) -> Vec<QueuedConversion> {
+        let running = self
+            .items
+            .iter()
+            .filter(|item| item.status == QueueStatus::Converting)
+            .count();
+        let mut free = self.concurrency.saturating_sub(running);
+        let mut started = Vec::new();
+        for item in &mut self.items {
+            if free == 0 {
+                break;
+            }
+            if item.status == QueueStatus::Queued {
+                item.status = QueueStatus::Converting;
+                started.push(item.clone());
+                free -= 1;
+            }
+        }
+        started
+    }
+
+    /// Record a converting item's percent, returns true if it changed
+    pub fn set_progress(&mut self, id: u64, percent: u8) -> bool {
+        match self.items.iter_mut().find(|item| item.id == id) {
+            Some(item) if item.progress != percent => {
+                item.progress = percent;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Record how an item ended
+    pub fn finish(&mut self, id: u64, status: QueueStatus, error: Option<String>) {
+        if let Some(item) = self.items.iter_mut().find(|item| item.id == id) {
+            item.status = status;
+            item.error = error;
+            if status == QueueStatus::Done {
+                item.progress = 100;
+            }
+        }
+    }
+
+    /// Move a queued item to `index` in the queue
+    pub fn move_item(&mut self, id: u64, index: usize) -> Result<(), String> {
+        let from = self.queued_index(id)?;
+        let item = self.items.remove(from);
+        let index = index.min(self.items.len());
+        self.items.insert(index, item);
+        Ok(())
+    }
+
+    /// Take an item out of the queue; a running one has to be cancelled instead
+    pub fn remove(&mut self, id: u64) -> Result<(), String> {
+        let index = self
+            .items
+            .iter()
+            .position(|item| item.id == id)
+            .ok_or_else(|| format!("No conversion {} in the queue", id))?;
+        if self.items[index].status == QueueStatus::Converting {
+            return Err("That file is already converting; cancel its job instead".to_string());
+        }
+        self.items.remove(index);
+        Ok(())
+    }
+
+    fn queued_index(&self, id: u64) -> Result<usize, String> {
+        match self.items.iter().position(|item| item.id == id) {
+            Some(index) if self.items[index].status == QueueStatus::Queued => Ok(index),
+            Some(_) => Err("Only files that haven't started can be moved".to_string()),
+            None => Err(format!("No conversion {} in the queue", id)),
+        }
+    }
+
+    pub fn set_concurrency(&mut self, limit: usize) {
+        self.concurrency = limit.clamp(1, MAX_JOB_LIMIT);
+    }
+
+    pub fn state(&self) -> QueueState {
+        let count = |matches: fn(QueueStatus) -> bool| {
+            self.items
+                .iter()
+                .filter(|item| matches(item.status))
+                .count()
+        };
+        let percent = if self.items.is_empty() {
+            0
+        } else {
+            let sum: usize = self
+                .items
+                .iter()
+                .map(|item| {
+                    if item.status.is_finished() {
+                        100
+                    } else {
+                        item.progress as usize
+                    }
+                })
+                .sum();
+            (sum / self.items.len()) as u8
+        };
+        QueueState {
+            items: self.items.clone(),
+            concurrency: self.concurrency,
+            percent,
+            pending: count(|status| !status.is_finished()),
+            done: count(|status| status == QueueStatus::Done),
+            failed: count(|status| matches!(status, QueueStatus::Failed | QueueStatus::Cancelled)),
+        }
+    }
+}
+
+fn emit_changed(app: &AppHandle, queue: &ConversionQueue) {
+    let _ = app.emit("conversion-queue-changed", queue.state());
+}
+
+// Start whatever the conversion limit allows, each on its own thread
+fn pump(app: &AppHandle) {
+    let limit = app
+        .state::<AppState>()
+        .settings
+        .lock()
+        .unwrap()
+        .job_limits
+        .conversions;
+    let started = {
+        let mut queue = QUEUE.lock().unwrap();
+        queue.set_concurrency(limit);
+        let started = queue.start_next();
+        if !started.is_empty() {
+            emit_changed(app, &queue);
+        }
+        started
+    };
+    for item in started {
+        let app = app.clone();
+        std::thread::spawn(move || convert(&app, item));
+    }
+}
+
+fn convert(app: &AppHandle, item: QueuedConversion) {
+    let file_name = std::path::Path::new(&item.input_path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| item.input_path.clone());
+    let job = jobs::start_job(
+        app,
+        JobKind::Conversion,
+        format!("Converting {}", file_name),
+    );
+
+    let result = platform::get_ffmpeg_path().and_then(|ffmpeg| {
+        crate::run_conversion(
+            &job,
+            &ffmpeg,
+            &item.input_path,
+            &item.output_path,
+            item.options.as_ref(),
+            |percent| {
+                let mut queue = QUEUE.lock().unwrap();
+                if queue.set_progress(item.id, percent as u8) {
+                    emit_changed(app, &queue);
+                }
+            },
+        )
+    });
+    let (status, error) = match result {
+        Ok(()) => {
+            job.complete();
+            (QueueStatus::Done, None)
+        }
+        Err(_) if job.token().is_cancelled() => (QueueStatus::Cancelled, None),
+        Err(e) => {
+            log::warn!("Queued conversion of {} failed: {}", item.input_path, e);
+            (QueueStatus::Failed, Some(e))
+        }
+    };
+    drop(job);
+
+    let finished = {
+        let mut queue = QUEUE.lock().unwrap();
+        queue.finish(item.id, status, error);
+        emit_changed(app, &queue);
+        let state = queue.state();
+        (state.pending == 0).then_some(state)
+    };
+    match finished {
+        Some(state) => notify_finished(app, &state),
+        None => pump(app),
+    }
+}
+
+fn notify_finished(app: &AppHandle, state: &QueueState) {
+    let body = if state.failed == 0 {
+        format!("{} file(s) converted", state.done)
+    } else {
+        format!(
+            "{} file(s) converted, {} failed or cancelled",
+            state.done, state.failed
+        )
+    };
+    let action = match state
+        .items
+        .iter()
+        .rev()
+        .find(|item| item.status == QueueStatus::Done)
+    {
+        Some(item) => NotifyAction::reveal(&item.output_path),
+        None => NotifyAction::ShowWindow,
+    };
+    notifications::notify_if_hidden(app, "Batch conversion finished", &body, action);
+}
+
+/// Queue files for conversion with shared options (None for ffmpeg's defaults, as with
+/// `convert_media`), returning their queue ids
+#[tauri::command]
+pub async fn enqueue_conversion(
+    app: AppHandle,
+    items: Vec<ConversionItem>,
+    options: Option<VideoConvertOptions>,
+) -> Result<Vec<u64>, String> {
+    if items.is_empty() {
+        return Err("No files to convert".to_string());
+    }
+    let items = items
+        .into_iter()
+        .map(|item| {
+            let output_path = path_policy::validate_write_path(&app, &item.output_path)?;
+            Ok(ConversionItem {
+                input_path: item.input_path,
+                output_path: output_path.to_string_lossy().to_string(),
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    let ids = {
+        let mut queue = QUEUE.lock().unwrap();
+        let ids = queue.add(items, options);
+        emit_changed(&app, &queue);
+        ids
+    };
+    pump(&app);
+    Ok(ids)
+}
+
+#[tauri::command]
+pub fn get_conversion_queue() -> QueueState {
+    QUEUE.lock().unwrap().state()
+}
+
+/// Move a file that hasn't started converting to `index` in the queue
+#[tauri::command]
+pub fn move_queued_conversion(app: AppHandle, id: u64, index: usize) -> Result<(), String> {
+    let mut queue = QUEUE.lock().unwrap();
+    queue.move_item(id, index)?;
+    emit_changed(&app, &queue);
+    Ok(())
+}
+
+/// Remove a file from the queue (one that is converting has to be cancelled as a job)
+#[tauri::command]
+pub fn remove_queued_conversion(app: AppHandle, id: u64) -> Result<(), String> {
+    let mut queue = QUEUE.lock().unwrap();
+    queue.remove(id)?;
+    emit_changed(&app, &queue);
+    Ok(())
+}
+
+/// After the job limits change: a higher conversion limit starts more queued files right away
+pub fn apply_limits(app: &AppHandle) {
+    pump(app);
+}
@@ -0,0 +1,537 @@
+// Country reference (ISO codes, calling codes, currencies, flags) and public holidays
+// The country table is embedded, so lookups work offline. Holidays come from the free
+// Nager.Date API and are kept in `holidays_cache.json`: past years for good, the current and
+// future years for a month (governments do add holidays), and a stale list is used when the
+// API can't be reached.
+
+use std::collections::HashMap;
+
+use chrono::{Datelike, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+const CACHE_FILE: &str = "holidays_cache.json";
+const HOLIDAYS_API: &str = "https://date.nager.at/api/v3/PublicHolidays";
+const CACHE_DAYS: i64 = 30;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Country {
+    pub code: String,  // ISO 3166-1 alpha-2
+    pub code3: String, // alpha-3
+    pub name: String,
+    pub calling_code: String, // "+44", "+1 876" for NANP members
+    pub currency: String,     // ISO 4217
+    pub flag: String,
+}
+
+// (alpha-2, alpha-3, name, calling code, currency)
+#[rustfmt::skip]
+const COUNTRIES: &[(&str, &str, &str, &str, &str)] = &[
+    ("AD", "AND", "Andorra", "+376", "EUR"),
+    ("AE", "ARE", "United Arab Emirates", "+971", "AED"),
+    ("AF", "AFG", "Afghanistan", "+93", "AFN"),
+    ("AG", "ATG", "Antigua and Barbuda", "+1 268", "XCD"),
+    ("AI", "AIA", "Anguilla", "+1 264", "XCD"),
+    ("AL", "ALB", "Albania", "+355", "ALL"),
+    ("AM", "ARM", "Armenia", "+374", "AMD"),
+    ("AO", "AGO", "Angola", "+244", "AOA"),
+    ("AR", "ARG", "Argentina", "+54", "ARS"),
+    ("AS", "ASM", "American Samoa", "+1 684", "USD"),
+    ("AT", "AUT", "Austria", "+43", "EUR"),
+    ("AU", "AUS", "Australia", "+61", "AUD"),
+    ("AW", "ABW", "Aruba", "+297", "AWG"),
+    ("AX", "ALA", "Åland Islands", "+358", "EUR"),
+    ("AZ", "AZE", "Azerbaijan", "+994", "AZN"),
+    ("BA", "BIH", "Bosnia and Herzegovina", "+387", "BAM"),
+    ("BB", "BRB", "Barbados", "+1 246", "BBD"),
+    ("BD", "BGD", "Bangladesh", "+880", "BDT"),
+    ("BE", "BEL", "Belgium", "+32", "EUR"),
+    ("BF", "BFA", "Burkina Faso", "+226", "XOF"),
+    ("BG", "BGR", "Bulgaria", "+359", "EUR"),
+    ("BH", "BHR", "Bahrain", "+973", "BHD"),
+    ("BI", "BDI", "Burundi", "+257", "BIF"),
+    ("BJ", "BEN", "Benin", "+229", "XOF"),
+    ("BL", "BLM", "Saint Barthélemy", "+590", "EUR"),
+    ("BM", "BMU", "Bermuda", "+1 441", "BMD"),
+    ("BN", "BRN", "Brunei", "+673", "BND"),
+    ("BO", "BOL", "Bolivia", "+591", "BOB"),
+    ("BQ", "BES", "Caribbean Netherlands", "+599", "USD"),
+    ("BR", "BRA", "Brazil", "+55", "BRL"),
+    ("BS", "BHS", "Bahamas", "+1 242", "BSD"),
+    ("BT", "BTN", "Bhutan", "+975", "BTN"),
+    ("BW", "BWA", "Botswana", "+267", "BWP"),
+    ("BY", "BLR", "Belarus", "+375", "BYN"),
+    ("BZ", "BLZ", "Belize", "+501", "BZD"),
+    ("CA", "CAN", "Canada", "+1", "CAD"),
+    ("CC", "CCK", "Cocos (Keeling) Islands", "+61", "AUD"),
+    ("CD", "COD", "DR Congo", "+243", "CDF"),
+    ("CF", "CAF", "Central African Republic", "+236", "XAF"),
+    ("CG", "COG", "Republic of the Congo", "+242", "XAF"),
+    ("CH", "CHE", "Switzerland", "+41", "CHF"),
+    ("CI", "CIV", "Côte d'Ivoire", "+225", "XOF"),
+    ("CK", "COK", "Cook Islands", "+682", "NZD"),
+    ("CL", "CHL", "Chile", "+56", "CLP"),
+    ("CM", "CMR", "Cameroon", "+237", "XAF"),
+    ("CN", "CHN", "China", "+86", "CNY"),
+    ("CO", "COL", "Colombia", "+57", "COP"),
+    ("CR", "CRI", "Costa Rica", "+506", "CRC"),
+    ("CU", "CUB", "Cuba", "+53", "CUP"),
+    ("CV", "CPV", "Cape Verde", "+238", "CVE"),
+    ("CW", "CUW", "Curaçao", "+599", "XCG"),
+    ("CX", "CXR", "Christmas Island", "+61", "AUD"),
+    ("CY", "CYP", "Cyprus", "+357", "EUR"),
+    ("CZ", "CZE", "Czechia", "+420", "CZK"),
+    ("DE", "DEU", "Germany", "+49", "EUR"),
+    ("DJ", "DJI", "Djibouti", "+253", "DJF"),
+    ("DK", "DNK", "Denmark", "+45", "DKK"),
+    ("DM", "DMA", "Dominica", "+1 767", "XCD"),
+    ("DO", "DOM", "Dominican Republic", "+1 809", "DOP"),
+    ("DZ", "DZA", "Algeria", "+213", "DZD"),
+    ("EC", "ECU", "Ecuador", "+593", "USD"),
+    ("EE", "EST", "Estonia", "+372", "EUR"),
+    ("EG", "EGY", "Egypt", "+20", "EGP"),
+    ("EH", "ESH", "Western Sahara", "+212", "MAD"),
+    ("ER", "ERI", "Eritrea", "+291", "ERN"),
+    ("ES", "ESP", "Spain", "+34", "EUR"),
+    ("ET", "ETH", "Ethiopia", "+251", "ETB"),
+    ("FI", "FIN", "Finland", "+358", "EUR"),
+    ("FJ", "FJI", "Fiji", "+679", "FJD"),
+    ("FK", "FLK", "Falkland Islands", "+500", "FKP"),
+    ("FM", "FSM", "Micronesia", "+691", "USD"),
+    ("FO", "FRO", "Faroe Islands", "+298", "DKK"),
+    ("FR", "FRA", "France", "+33", "EUR"),
+    ("GA", "GAB", "Gabon", "+241", "XAF"),
+    ("GB", "GBR", "United Kingdom", "+44", "GBP"),
+    ("GD", "GRD", "Grenada", "+1 473", "XCD"),
+    ("GE", "GEO", "Georgia", "+995", "GEL"),
+    ("GF", "GUF", "French Guiana", "+594", "EUR"),
+    ("GG", "GGY", "Guernsey", "+44", "GBP"),
+    ("GH", "GHA", "Ghana", "+233", "GHS"),
+    ("GI", "GIB", "Gibraltar", "+350", "GIP"),
+    ("GL", "GRL", "Greenland", "+299", "DKK"),
+    ("GM", "GMB", "Gambia", "+220", "GMD"),
+    ("GN", "GIN", "Guinea", "+224", "GNF"),
+    ("GP", "GLP", "Guadeloupe", "+590", "EUR"),
+    ("GQ", "GNQ", "Equatorial Guinea", "+240", "XAF"),
+    ("GR", "GRC", "Greece", "+30", "EUR"),
+    ("GT", "GTM", "Guatemala", "+502", "GTQ"),
+    ("GU", "GUM", "Guam", "+1 671", "USD"),
+    ("GW", "GNB", "Guinea-Bissau", "+245", "XOF"),
+    ("GY", "GUY", "Guyana", "+592", "GYD"),
+    ("HK", "HKG", "Hong Kong", "+852", "HKD"),
+    ("HN", "HND", "Honduras", "+504", "HNL"),
+    ("HR", "HRV", "Croatia", "+385", "EUR"),
+    ("HT", "HTI", "Haiti", "+509", "HTG"),
+    ("HU", "HUN", "Hungary", "+36", "HUF"),
+    ("ID", "IDN", "Indonesia", "+62", "IDR"),
+    ("IE", "IRL", "Ireland", "+353", "EUR"),
+    ("IL", "ISR", "Israel", "+972", "ILS"),
+    ("IM", "IMN", "Isle of Man", "+44", "GBP"),
+    ("IN", "IND", "India", "+91", "INR"),
+    ("IO", "IOT", "British Indian Ocean Territory", "+246", "USD"),
+    ("IQ", "IRQ", "Iraq", "+964", "IQD"),
+    ("IR", "IRN", "Iran", "+98", "IRR"),
+    ("IS", "ISL", "Iceland", "+354", "ISK"),
+    ("IT", "ITA", "Italy", "+39", "EUR"),
+    ("JE", "JEY", "Jersey", "+44", "GBP"),
+    ("JM", "JAM", "Jamaica", "+1 876", "JMD"),
+    ("JO", "JOR", "Jordan", "+962", "JOD"),
+    ("JP", "JPN", "Japan", "+81", "JPY"),
+    ("KE", "KEN", "Kenya", "+254", "KES"),
+    ("KG", "KGZ", "Kyrgyzstan", "+996", "KGS"),
+    ("KH", "KHM", "Cambodia", "+855", "KHR"),
+    ("KI", "KIR", "Kiribati", "+686", "AUD"),
+    ("KM", "COM", "Comoros", "+269", "KMF"),
+    ("KN", "KNA", "Saint Kitts and Nevis", "+1 869", "XCD"),
+    ("KP", "PRK", "North Korea", "+850", "KPW"),
+    ("KR", "KOR", "South Korea", "+82", "KRW"),
+    ("KW", "KWT", "Kuwait", "+965", "KWD"),
+    ("KY", "CYM", "Cayman Islands", "+1 345", "KYD"),
+    ("KZ", "KAZ", "Kazakhstan", "+7", "KZT"),
+    ("LA", "LAO", "Laos", "+856", "LAK"),
+    ("LB", "LBN", "Lebanon", "+961", "LBP"),
+    ("LC", "LCA", "Saint Lucia", "+1 758", "XCD"),
+    ("LI", "LIE", "Liechtenstein", "+423", "CHF"),
+    ("LK", "LKA", "Sri Lanka", "+94", "LKR"),
+    ("LR", "LBR", "Liberia", "+231", "LRD"),
+    ("LS", "LSO", "Lesotho", "+266", "LSL"),
+    ("LT", "LTU", "Lithuania", "+370", "EUR"),
+    ("LU", "LUX", "Luxembourg", "+352", "EUR"),
+    ("LV", "LVA", "Latvia", "+371", "EUR"),
+    ("LY", "LBY", "Libya", "+218", "LYD"),
+    ("MA", "MAR", "Morocco", "+212", "MAD"),
+    ("MC", "MCO", "Monaco", "+377", "EUR"),
+    ("MD", "MDA", "Moldova", "+373", "MDL"),
+    ("ME", "MNE", "Montenegro", "+382", "EUR"),
+    ("MF", "MAF", "Saint Martin", "+590", "EUR"),
+    ("MG", "MDG", "Madagascar", "+261", "MGA"),
+    ("MH", "MHL", "Marshall Islands", "+692", "USD"),
+    ("MK", "MKD", "North Macedonia", "+389", "MKD"),
+    ("ML", "MLI", "Mali", "+223", "XOF"),
+    ("MM", "MMR", "Myanmar", "+95", "MMK"),
+    ("MN", "MNG", "Mongolia", "+976", "MNT"),
+    ("MO", "MAC", "Macao", "+853", "MOP"),
+    ("MP", "MNP", "Northern Mariana Islands", "+1 670", "USD"),
+    ("MQ", "MTQ", "Martinique", "+596", "EUR"),
+    ("MR", "MRT", "Mauritania", "+222", "MRU"),
+    ("MS", "MSR", "Montserrat", "+1 664", "XCD"),
+    ("MT", "MLT", "Malta", "+356", "EUR"),
+    ("MU", "MUS", "Mauritius", "+230", "MUR"),
+    ("MV", "MDV", "Maldives", "+960", "MVR"),
+    ("MW", "MWI", "Malawi", "+265", "MWK"),
+    ("MX", "MEX", "Mexico", "+52", "MXN"),
+    ("MY", "MYS", "Malaysia", "+60", "MYR"),
+    ("MZ", "MOZ", "Mozambique", "+258", "MZN"),
+    ("NA", "NAM", "Namibia", "+264", "NAD"),
+    ("NC", "NCL", "New Caledonia", "+687", "XPF"),
+    ("NE", "NER", "Niger", "+227", "XOF"),
+    ("NF", "NFK", "Norfolk Island", "+672", "AUD"),
+    ("NG", "NGA", "Nigeria", "+234", "NGN"),
+    ("NI", "NIC", "Nicaragua", "+505", "NIO"),
+    ("NL", "NLD", "Netherlands", "+31", "EUR"),
+    ("NO", "NOR", "Norway", "+47", "NOK"),
+    ("NP", "NPL", "Nepal", "+977", "NPR"),
+    ("NR", "NRU", "Nauru", "+674", "AUD"),
+    ("NU", "NIU", "Niue", "+683", "NZD"),
+    ("NZ", "NZL", "New Zealand", "+64", "NZD"),
+    ("OM", "OMN", "Oman", "+968", "OMR"),
+    ("PA", "PAN", "Panama", "+507", "PAB"),
+    ("PE", "PER", "Peru", "+51", "PEN"),
+    ("PF", "PYF", "French Polynesia", "+689", "XPF"),
+    ("PG", "PNG", "Papua New Guinea", "+675", "PGK"),
+    ("PH", "PHL", "Philippines", "+63", "PHP"),
+    ("PK", "PAK", "Pakistan", "+92", "PKR"),
+    ("PL", "POL", "Poland", "+48", "PLN"),
+    ("PM", "SPM", "Saint Pierre and Miquelon", "+508", "EUR"),
+    ("PN", "PCN", "Pitcairn Islands", "+64", "NZD"),
+    ("PR", "PRI", "Puerto Rico", "+1 787", "USD"),
+    ("PS", "PSE", "Palestine", "+970", "ILS"),
+    ("PT", "PRT", "Portugal", "+351", "EUR"),
+    ("PW", "PLW", "Palau", "+680", "USD"),
+    ("PY", "PRY", "Paraguay", "+595", "PYG"),
+    ("QA", "QAT", "Qatar", "+974", "QAR"),
+    ("RE", "REU", "Réunion", "+262", "EUR"),
+    ("RO", "ROU", "Romania", "+40", "RON"),
+    ("RS", "SRB", "Serbia", "+381", "RSD"),
+    ("RU", "RUS", "Russia", "+7", "RUB"),
+    ("RW", "RWA", "Rwanda", "+250", "RWF"),
+    ("SA", "SAU", "Saudi Arabia", "+966", "SAR"),
+    ("SB", "SLB", "Solomon Islands", "+677", "SBD"),
+    ("SC", "SYC", "Seychelles", "+248", "SCR"),
+    ("SD", "SDN", "Sudan", "+249", "SDG"),
+    ("SE", "SWE", "Sweden", "+46", "SEK"),
+    ("SG", "SGP", "Singapore", "+65", "SGD"),
+    ("SH", "SHN", "Saint Helena", "+290", "SHP"),
+    ("SI", "SVN", "Slovenia", "+386", "EUR"),
+    ("SJ", "SJM", "Svalbard and Jan Mayen", "+47", "NOK"),
+    ("SK", "SVK", "Slovakia", "+421", "EUR"),
+    ("SL", "SLE", "Sierra Leone", "+232", "SLE"),
+    ("SM", "SMR", "San Marino", "+378", "EUR"),
+    ("SN", "SEN", "Senegal", "+221", "XOF"),
+    ("SO", "SOM", "Somalia", "+252", "SOS"),
+    ("SR", "SUR", "Suriname", "+597", "SRD"),
+    ("SS", "SSD", "South Sudan", "+211", "SSP"),
+    ("ST", "STP", "São Tomé and Príncipe", "+239", "STN"),
+    ("SV", "SLV", "El Salvador", "+503", "USD"),
+    ("SX", "SXM", "Sint Maarten", "+1 721", "XCG"),
+    ("SY", "SYR", "Syria", "+963", "SYP"),
+    ("SZ", "SWZ", "Eswatini", "+268", "SZL"),
+    ("TC", "TCA", "Turks and Caicos Islands", "+1 649", "USD"),
+    ("TD", "TCD", "Chad", "+235", "XAF"),
+    ("TG", "TGO", "Togo", "+228", "XOF"),
+    ("TH", "THA", "Thailand", "+66", "THB"),
+    ("TJ", "TJK", "Tajikistan", "+992", "TJS"),
+    ("TK", "TKL", "Tokelau", "+690", "NZD"),
+    ("TL", "TLS", "Timor-Leste", "+670", "USD"),
+    ("TM", "TKM", "Turkmenistan", "+993", "TMT"),
+    ("TN", "TUN", "Tunisia", "+216", "TND"),
+    ("TO", "TON", "Tonga", "+676", "TOP"),
+    ("TR", "TUR", "Türkiye", "+90", "TRY"),
+    ("TT", "TTO", "Trinidad and Tobago", "+1 868", "TTD"),
+    ("TV", "TUV", "Tuvalu", "+688", "AUD"),
+    ("TW", "TWN", "Taiwan", "+886", "TWD"),
+    ("TZ", "TZA", "Tanzania", "+255", "TZS"),
+    ("UA", "UKR", "Ukraine", "+380", "UAH"),
+    ("UG", "UGA", "Uganda", "+256", "UGX"),
+    ("US", "USA", "United States", "+1", "USD"),
+    ("UY", "URY", "Uruguay", "+598", "UYU"),
+    ("UZ", "UZB", "Uzbekistan", "+998", "UZS"),
+    ("VA", "VAT", "Vatican City", "+39", "EUR"),
+    ("VC", "VCT", "Saint Vincent and the Grenadines", "+1 784", "XCD"),
+    ("VE", "VEN", "Venezuela", "+58", "VES"),
+    ("VG", "VGB", "British Virgin Islands", "+1 284", "USD"),
+    ("VI", "VIR", "U.S. Virgin Islands", "+1 340", "USD"),
+    ("VN", "VNM", "Vietnam", "+84", "VND"),
+    ("VU", "VUT", "Vanuatu", "+678", "VUV"),
+    ("WF", "WLF", "Wallis and Futuna", "+681", "XPF"),
+    ("WS", "WSM", "Samoa", "+685", "WST"),
+    ("XK", "XKX", "Kosovo", "+383", "EUR"),
+    ("YE", "YEM", "Yemen", "+967", "YER"),
+    ("YT", "MYT", "Mayotte", "+262", "EUR"),
+    ("ZA", "ZAF", "South Africa", "+27", "ZAR"),
+    ("ZM", "ZMB", "Zambia", "+260", "ZMW"),
+    ("ZW", "ZWE", "Zimbabwe", "+263", "ZWG"),
+];
+
+// Other names people type, lowercase
+const ALIASES: &[(&str, &str)] = &[
+    ("uk", "GB"),
+    ("britain", "GB"),
+    ("great britain", "GB"),
+    ("england", "GB"),
+    ("scotland", "GB"),
+    ("wales", "GB"),
+    ("usa", "US"),
+    ("america", "US"),
+    ("united states of america", "US"),
+    ("holland", "NL"),
+    ("the netherlands", "NL"),
+    ("czech republic", "CZ"),
+    ("turkey", "TR"),
+    ("ivory coast", "CI"),
+    ("cote d'ivoire", "CI"),
+    ("swaziland", "SZ"),
+    ("burma", "MM"),
+    ("korea", "KR"),
+    ("east timor", "TL"),
+    ("cabo verde", "CV"),
+    ("macau", "MO"),
+    ("vatican", "VA"),
+    ("holy see", "VA"),
+    ("drc", "CD"),
+    ("congo-kinshasa", "CD"),
+    ("congo-brazzaville", "CG"),
+    ("uae", "AE"),
+    ("emirates", "AE"),
+];
+
+/// Flag emoji for an alpha-2 code (two regional indicator symbols)
+pub fn flag(code: &str) -> String {
+    code.chars()
+        .filter(|c| c.is_ascii_alphabetic())
+        .filter_map(|c| char::from_u32(0x1F1E6 + (c.to_ascii_uppercase() as u32 - 'A' as u32)))
+        .collect()
+}
+
+fn country(entry: &(&str, &str, &str, &str, &str)) -> Country {
+    let (code, code3, name, calling_code, currency) = *entry;
+    Country {
+        code: code.to_string(),
+        code3: code3.to_string(),
+        name: name.to_string(),
+        calling_code: calling_code.to_string(),
+        currency: currency.to_string(),
+        flag: flag(code),
+    }
+}
+
+// Matches for `query` with their rank, best first (0 = code, name or alias)
+fn ranked(query: &str) -> Vec<(u8, Country)> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let lower = query.to_lowercase();
+    let upper = query.to_uppercase();
+    let digits: String = query.chars().filter(char::is_ascii_digit).collect();
+    let is_calling_code = !digits.is_empty()
+        && query
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '+' | ' ' | '-'));
+    let alias = ALIASES
+        .iter()
+        .find(|(name, _)| *name == lower)
+        .map(|(_, code)| *code);
+
+    let rank = |(code, code3, name, calling_code, currency): &(&str, &str, &str, &str, &str)| {
+        let name = name.to_lowercase();
+        if *code == upper || *code3 == upper || name == lower || alias == Some(*code) {
+            Some(0)
+        } else if is_calling_code {
+            let calling_digits: String =
+                calling_code.chars().filter(char::is_ascii_digit).collect();
+            (calling_digits == digits).then_some(1)
+        } else if *currency == upper {
+            Some(2)
+        } else if name.starts_with(&lower) {
+            Some(3)
+        } else if lower.len() >= 3 && name.contains(&lower) {
+            Some(4)
+        } else {
+            None
+        }
+    };
+
+    let mut matches: Vec<(u8, Country)> = COUNTRIES
+        .iter()
+        .filter_map(|entry| rank(entry).map(|r| (r, country(entry))))
+        .collect();
+    // Stable sort keeps alphabetical order within a rank
+    matches.sort_by_key(|(r, _)| *r);
+    matches
+}
+
+/// Countries matching `query`, best first: code or name ("de", "DEU", "Germany", "uk"),
+/// calling code ("+44", "1 876"), currency ("EUR" lists the euro area), then names
+/// starting with or containing the query
+pub fn lookup(query: &str) -> Vec<Country> {
+    ranked(query).into_iter().map(|(_, c)| c).collect()
+}
+
+/// The one country `query` names, for commands that need a single country
+pub fn find(query: &str) -> Result<Country, String> {
+    let mut matches = ranked(query);
+    match matches.as_slice() {
+        [] => Err(format!("No country called \"{}\"", query.trim())),
+        [(0, _), ..] | [_] => Ok(matches.swap_remove(0).1),
+        _ => Err(format!(
+            "\"{}\" could be {}",
+            query.trim(),
+            matches
+                .iter()
+                .take(5)
+                .map(|(_, c)| c.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PublicHoliday {
+    pub date: NaiveDate,
+    #[serde(alias = "localName")]
+    pub local_name: String,
+    pub name: String, // English
+    #[serde(default)]
+    pub global: bool, // false when only some regions observe it
+    #[serde(alias = "counties", default)]
+    pub regions: Option<Vec<String>>, // ISO 3166-2 codes, e.g. "DE-BY"
+    #[serde(default)]
+    pub types: Vec<String>, // Public, Bank, School, Optional, ...
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HolidayCalendar {
+    pub country: Country,
+    pub year: i32,
+    pub holidays: Vec<PublicHoliday>,
+    pub cached: bool, // true when the API couldn't be reached and an old copy was used
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedHolidays {
+    fetched: NaiveDate,
+    holidays: Vec<PublicHoliday>,
+}
+
+/// Parse a Nager.Date PublicHolidays response, sorted by date
+pub fn parse_holidays(json: &str) -> Result<Vec<PublicHoliday>, String> {
+    let mut holidays: Vec<PublicHoliday> =
+        serde_json::from_str(json).map_err(|e| format!("Unexpected holiday data: {}", e))?;
+    holidays.sort_by_key(|h| h.date);
+    Ok(holidays)
+}
+
+/// Whether holidays of `year` fetched on `fetched` can still be used on `today`
+pub fn cache_is_fresh(year: i32, fetched: NaiveDate, today: NaiveDate) -> bool {
+    // Once a year is over, its list doesn't change (a list fetched during it could still
+    // lack a holiday added late, so it has to be fetched after the year ended)
+    if year < today.year() && fetched.year() > year {
+        return true;
+    }
+    (today - fetched).num_days() < CACHE_DAYS
+}
+
+async fn fetch_holidays(code: &str, year: i32) -> Result<Vec<PublicHoliday>, String> {
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let response = client
+        .get(format!("{}/{}/{}", HOLIDAYS_API, year, code))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch holidays: {}", e))?;
+    // Countries the API doesn't cover answer 404 or an empty 204
+    if response.status() == reqwest::StatusCode::NOT_FOUND
+        || response.status() == reqwest::StatusCode::NO_CONTENT
+    {
+        return Ok(Vec::new());
+    }
+    if !response.status().is_success() {
+        return Err(format!("Holiday API error: {}", response.status()));
+    }
+    let text = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read holidays: {}", e))?;
+    parse_holidays(&text)
+}
+
+/// Look up countries by code, name, calling code or currency
+#[tauri::command]
+pub fn lookup_country(query: String) -> Vec<Country> {
+    lookup(&query)
+}
+
+/// Public holidays of `country` (code or name) in `year` (default this year)
+#[tauri::command]
+pub async fn get_public_holidays(
+    app: AppHandle,
+    country: String,
+    year: Option<i32>,
+) -> Result<HolidayCalendar, String> {
+    let country = find(&country)?;
+    let today = Local::now().date_naive();
+    let year = year.unwrap_or(today.year());
+    if !(1900..=2100).contains(&year) {
+        return Err(format!("No holiday data for {}", year));
+    }
+
+    let key = format!("{}-{}", country.code, year);
+    let mut cache: HashMap<String, CachedHolidays> = crate::load_json_file(&app, CACHE_FILE);
+    if let Some(cached) = cache.get(&key) {
+        if cache_is_fresh(year, cached.fetched, today) {
+            return Ok(HolidayCalendar {
+                country,
+                year,
+                holidays: cached.holidays.clone(),
+                cached: false,
+            });
+        }
+    }
+
+    let holidays = match fetch_holidays(&country.code, year).await {
+        Ok(holidays) => holidays,
+        Err(e) => {
+            return match cache.remove(&key) {
+                Some(stale) => Ok(HolidayCalendar {
+                    country,
+                    year,
+                    holidays: stale.holidays,
+                    cached: true,
+                }),
+                None => Err(e),
+            };
+        }
+    };
+    if holidays.is_empty() {
+        return Err(format!("No public holiday data for {}", country.name));
+    }
+    cache.insert(
+        key,
+        CachedHolidays {
+            fetched: today,
+            holidays: holidays.clone(),
+        },
+    );
+    crate::save_json_file(&app, CACHE_FILE, &cache)?;
+
+    Ok(HolidayCalendar {
+        country,
+        year,
+        holidays,
+        cached: false,
+    })
+}
@@ -0,0 +1,166 @@
+// Cryptocurrency prices for the currency converter, from CoinGecko's free API (no key).
+// Frankfurter only knows fiat currencies, so any pair with a coin in it (BTC to USD, EUR to
+// ETH, SOL to BTC) is priced here instead: both sides in the fiat currency of the pair, or in
+// US dollars for coin to coin, and the rate is their ratio.
+
+use crate::currency::CurrencyResult;
+
+const SIMPLE_PRICE_API: &str = "https://api.coingecko.com/api/v3/simple/price";
+
+// Ticker symbol -> CoinGecko coin id, for the larger coins
+const COINS: &[(&str, &str)] = &[
+    ("BTC", "bitcoin"),
+    ("ETH", "ethereum"),
+    ("USDT", "tether"),
+    ("USDC", "usd-coin"),
+    ("BNB", "binancecoin"),
+    ("SOL", "solana"),
+    ("XRP", "ripple"),
+    ("DOGE", "dogecoin"),
+    ("ADA", "cardano"),
+    ("TRX", "tron"),
+    ("TON", "the-open-network"),
+    ("AVAX", "avalanche-2"),
+    ("SHIB", "shiba-inu"),
+    ("DOT", "polkadot"),
+    ("LINK", "chainlink"),
+    ("BCH", "bitcoin-cash"),
+    ("LTC", "litecoin"),
+    ("XLM", "stellar"),
+    ("XMR", "monero"),
+    ("ATOM", "cosmos"),
+    ("UNI", "uniswap"),
+    ("NEAR", "near"),
+    ("APT", "aptos"),
+    ("SUI", "sui"),
+    ("ARB", "arbitrum"),
+    ("OP", "optimism"),
+    ("POL", "polygon-ecosystem-token"),
+    ("PEPE", "pepe"),
+    ("DAI", "dai"),
+    ("ETC", "ethereum-classic"),
+];
+
+/// The CoinGecko id of a coin's ticker symbol, None for fiat currencies
+pub fn coin_id(symbol: &str) -> Option<&'static str> {
+    COINS
+        .iter()
+        .find(|(s, _)| s.eq_ignore_ascii_case(symbol))
+        .map(|(_, id)| *id)
+}
+
+/// Whether a currency code is a coin this converter knows
+pub fn is_crypto(symbol: &str) -> bool {
+    coin_id(symbol).is_some()
+}
+
+// The currency both sides are priced in: the fiat side of the pair, or dollars for coin to coin
+fn quote_currency(from: &str, to: &str) -> String {
+    [from, to]
+        .into_iter()
+        .find(|c| !is_crypto(c))
+        .unwrap_or("USD")
+        .to_lowercase()
+}
+
+/// The rate of one `from` in `to`, from a CoinGecko simple/price response priced in `quote`
+pub fn rate_from_prices(
+    prices: &serde_json::Value,
+    from: &str,
+    to: &str,
+    quote: &str,
+) -> Result<f64, String> {
+    let price = |symbol: &str| -> Result<f64, String> {
+        match coin_id(symbol) {
+            Some(id) => prices[id][quote.to_lowercase()]
+                .as_f64()
+                .filter(|p| *p > 0.0)
+                .ok_or_else(|| {
+                    format!(
+                        "No {} price for {}",
+                        quote.to_uppercase(),
+                        symbol.to_uppercase()
+                    )
+                }),
+            None if symbol.eq_ignore_ascii_case(quote) => Ok(1.0),
+            None => Err(format!("Currency '{}' not found", symbol.to_uppercase())),
+        }
+    };
+    Ok(price(from)? / price(to)?)
+}
+
+/// Convert an amount where at least one side is a coin
+pub async fn convert(amount: f64, from: &str, to: &str) -> Result<CurrencyResult, String> {
+    convert_many(amount, from, &[to.to_string()])
+        .await?
+        .pop()
+        .ok_or_else(|| "No conversion result".to_string())
+}
+
+/// Convert an amount into several currencies with one price request; each pair needs a coin
+/// on at least one side
+pub async fn convert_many(
+    amount: f64,
+    from: &str,
+    targets: &[String],
+) -> Result<Vec<CurrencyResult>, String> {
+    let from = from.to_uppercase();
+    let targets: Vec<String> = targets.iter().map(|t| t.to_uppercase()).collect();
+    let quotes: Vec<String> = targets.iter().map(|to| quote_currency(&from, to)).collect();
+    let mut ids: Vec<&str> = std::iter::once(&from)
+        .chain(&targets)
+        .filter_map(|c| coin_id(c))
+        .collect();
+    ids.sort();
+    ids.dedup();
+    let mut vs_currencies = quotes.clone();
+    vs_currencies.sort();
+    vs_currencies.dedup();
+    let url = format!(
+        "{}?ids={}&vs_currencies={}",
+        SIMPLE_PRICE_API,
+        ids.join(","),
+        vs_currencies.join(",")
+    );
+
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch prices: {}", e))?;
+    if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        return Err("Too many price requests, try again in a minute".to_string());
+    }
+    if !response.status().is_success() {
+        return Err(format!("API error: {}", response.status()));
+    }
+    let prices: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse response: {}", e))?;
+
+    let as_of = chrono::Utc::now().format("%Y-%m-%d").to_string();
+    targets
+        .into_iter()
+        .zip(quotes)
+        .map(|(to, quote)| {
+            let rate = rate_from_prices(&prices, &from, &to, &quote)?;
+            Ok(CurrencyResult {
+                amount,
+                from: from.clone(),
+                to,
+                result: amount * rate,
+                rate,
+                as_of: as_of.clone(),
+                is_stale: false,
+                change_7d: None,
+                change_30d: None,
+                change_365d: None,
+            })
+        })
+        .collect()
+}
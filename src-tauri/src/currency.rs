@@ -0,0 +1,333 @@
+// Currency conversion with frankfurter.app (ECB reference rates, free, no key). The whole rate
+// table is fetched at once and kept in `exchange_rates.json`, so conversions between any two
+// currencies come from one request an hour, and keep working offline with the last known
+// rates (flagged `is_stale`). Pairs with a cryptocurrency go to `crypto_prices` instead.
+// Daily history of a pair comes from the time-series endpoint, for charts and for the change
+// over the last week, month and year shown with each conversion.
+// `convert_currency_multi` converts one amount into several currencies (the favorites) from
+// the same table.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::{Days, NaiveDate};
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::crypto_prices;
+
+const FRANKFURTER_API: &str = "https://api.frankfurter.app";
+const CACHE_FILE: &str = "exchange_rates.json";
+
+// The ECB publishes once a working day, so an hour old table is still current
+const REFRESH_AFTER: Duration = Duration::from_secs(60 * 60);
+
+const MAX_HISTORY_DAYS: u32 = 3650;
+
+// A pair's history and the day it was fetched
+type CachedHistory = (NaiveDate, Vec<RatePoint>);
+
+// Pair histories fetched today, by "FROM:TO:days"
+static HISTORY: Mutex<Option<HashMap<String, CachedHistory>>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CurrencyResult {
+    pub amount: f64,
+    pub from: String,
+    pub to: String,
+    pub result: f64,
+    pub rate: f64,
+    pub as_of: String,  // date of the rates, e.g. "2026-10-15"
+    pub is_stale: bool, // offline, converted with the last rates that were fetched
+    // Percent change of the rate over the last 7 / 30 / 365 days, when the history loaded
+    pub change_7d: Option<f64>,
+    pub change_30d: Option<f64>,
+    pub change_365d: Option<f64>,
+}
+
+/// The rate of a pair on one day (`get_rate_history`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RatePoint {
+    pub date: String, // "2026-10-15"
+    pub rate: f64,
+}
+
+#[derive(Deserialize)]
+struct TimeSeries {
+    rates: BTreeMap<String, HashMap<String, f64>>, // by date, then currency
+}
+
+/// Rates of every currency against `base`, as frankfurter's /latest returns them
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RateTable {
+    pub base: String,
+    pub date: String,
+    pub rates: HashMap<String, f64>,
+    #[serde(default)]
+    pub fetched_at: u64, // unix seconds
+}
+
+impl RateTable {
+    /// Units of `to` for one `from`
+    pub fn rate(&self, from: &str, to: &str) -> Result<f64, String> {
+        let per_base = |code: &str| -> Result<f64, String> {
+            if code.eq_ignore_ascii_case(&self.base) {
+                return Ok(1.0);
+            }
+            self.rates
+                .get(&code.to_uppercase())
+                .copied()
+                .filter(|r| *r > 0.0)
+                .ok_or_else(|| format!("Currency '{}' not found", code.to_uppercase()))
+        };
+        Ok(per_base(to)? / per_base(from)?)
+    }
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+async fn fetch_table() -> Result<RateTable, String> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let response = client
+        .get(format!("{}/latest", FRANKFURTER_API))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch rates: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("API error: {}", response.status()));
+    }
+    let mut table: RateTable = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse response: {}", e))?;
+    table.fetched_at = now_secs();
+    Ok(table)
+}
+
+/// The current rate table and whether it is an old one kept from before (offline)
+pub async fn rate_table(app: &AppHandle) -> Result<(RateTable, bool), String> {
+    let cached: Option<RateTable> = crate::load_json_file(app, CACHE_FILE);
+    if let Some(table) = &cached {
+        if now_secs().saturating_sub(table.fetched_at) < REFRESH_AFTER.as_secs() {
+            return Ok((table.clone(), false));
+        }
+    }
+    match fetch_table().await {
+        Ok(table) => {
+            if let Err(e) = crate::save_json_file(app, CACHE_FILE, &Some(&table)) {
+                log::warn!("Failed to save exchange rates: {}", e);
+            }
+            Ok((table, false))
+        }
+        Err(e) => match cached {
+            Some(table) => {
+                log::info!("Using exchange rates from {} ({})", table.date, e);
+                Ok((table, true))
+            }
+            None => Err(e),
+        },
+    }
+}
+
+/// Daily rates of `to` per `from`, oldest first, from a frankfurter time-series response
+pub fn parse_time_series(json: &str, to: &str) -> Result<Vec<RatePoint>, String> {
+    let series: TimeSeries =
+        serde_json::from_str(json).map_err(|e| format!("Failed to parse response: {}", e))?;
+    let to = to.to_uppercase();
+    Ok(series
+        .rates
+        .into_iter()
+        .filter_map(|(date, rates)| rates.get(&to).map(|&rate| RatePoint { date, rate }))
+        .collect())
+}
+
+/// Percent change from the rate `days` before `today` (the last working day on or before it)
+/// to `current`
+pub fn percent_change(
+    history: &[RatePoint],
+    current: f64,
+    days: u64,
+    today: NaiveDate,
+) -> Option<f64> {
+    let since = today.checked_sub_days(Days::new(days))?.to_string();
+    // Dates are ISO, so they sort as strings
+    let past = history.iter().rev().find(|p| p.date <= since)?.rate;
+    (past > 0.0).then(|| (current - past) / past * 100.0)
+}
+
+async fn fetch_history(from: &str, to: &str, days: u32) -> Result<Vec<RatePoint>, String> {
+    let today = chrono::Utc::now().date_naive();
+    let start = today
+        .checked_sub_days(Days::new(days as u64))
+        .ok_or("Too many days")?;
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(15))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let response = client
+        .get(format!(
+            "{}/{}..?from={}&to={}",
+            FRANKFURTER_API, start, from, to
+        ))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch rate history: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("API error: {}", response.status()));
+    }
+    let body = response.text().await.map_err(|e| e.to_string())?;
+    parse_time_series(&body, to)
+}
+
+/// A pair's daily rates over the last `days`, fetched once a day
+pub async fn rate_history(from: &str, to: &str, days: u32) -> Result<Vec<RatePoint>, String> {
+    let (from, to) = (from.to_uppercase(), to.to_uppercase());
+    if from == to {
+        return Err("Pick two different currencies".to_string());
+    }
+    let key = format!("{}:{}:{}", from, to, days);
+    let today = chrono::Utc::now().date_naive();
+    if let Some((fetched, history)) = HISTORY
+        .lock()
+        .unwrap()
+        .get_or_insert_with(HashMap::new)
+        .get(&key)
+    {
+        if *fetched == today {
+            return Ok(history.clone());
+        }
+    }
+    let history = fetch_history(&from, &to, days).await?;
+    HISTORY
+        .lock()
+        .unwrap()
+        .get_or_insert_with(HashMap::new)
+        .insert(key, (today, history.clone()));
+    Ok(history)
+}
+
+/// Daily rates of `to` per `from` over the last `days` (at most ten years), oldest first;
+/// the ECB has no rates on weekends and holidays
+#[tauri::command]
+pub async fn get_rate_history(
+    from: String,
+    to: String,
+    days: u32,
+) -> Result<Vec<RatePoint>, String> {
+    if days == 0 || days > MAX_HISTORY_DAYS {
+        return Err(format!("History goes back 1 to {} days", MAX_HISTORY_DAYS));
+    }
+    if crypto_prices::is_crypto(&from) || crypto_prices::is_crypto(&to) {
+        return Err("Rate history is only available for fiat currencies".to_string());
+    }
+    rate_history(&from, &to, days).await
+}
+
+#[tauri::command]
+pub async fn convert_currency(
+    app: AppHandle,
+    amount: f64,
+    from: String,
+    to: String,
+) -> Result<CurrencyResult, String> {
+    // Coins are priced through CoinGecko, frankfurter only has fiat currencies
+    if crypto_prices::is_crypto(&from) || crypto_prices::is_crypto(&to) {
+        return crypto_prices::convert(amount, &from, &to).await;
+    }
+
+    let (table, is_stale) = rate_table(&app).await?;
+    let rate = table.rate(&from, &to)?;
+    // Changes are a bonus: offline or a failed history just leaves them out
+    let history = if is_stale {
+        Vec::new()
+    } else {
+        rate_history(&from, &to, 365).await.unwrap_or_else(|e| {
+            log::warn!("No rate history for {}/{}: {}", from, to, e);
+            Vec::new()
+        })
+    };
+    let today = chrono::Utc::now().date_naive();
+    let change = |days| percent_change(&history, rate, days, today);
+    Ok(CurrencyResult {
+        amount,
+        from: from.to_uppercase(),
+        to: to.to_uppercase(),
+        result: amount * rate,
+        rate,
+        as_of: table.date,
+        is_stale,
+        change_7d: change(7),
+        change_30d: change(30),
+        change_365d: change(365),
+    })
+}
+
+/// The currencies to convert `from` into: upper case, each once, without `from` itself
+pub fn multi_targets(from: &str, targets: &[String]) -> Vec<String> {
+    let mut unique: Vec<String> = Vec::new();
+    for target in targets {
+        let target = target.trim().to_uppercase();
+        if !target.is_empty() && !target.eq_ignore_ascii_case(from) && !unique.contains(&target) {
+            unique.push(target);
+        }
+    }
+    unique
+}
+
+/// Convert one amount into each of `targets`, in their order. Fiat currencies all come from
+/// the one rate table and coins from one price request, so five favorites cost no more than
+/// one conversion. Rate changes are left out.
+#[tauri::command]
+pub async fn convert_currency_multi(
+    app: AppHandle,
+    amount: f64,
+    from: String,
+    targets: Vec<String>,
+) -> Result<Vec<CurrencyResult>, String> {
+    let from = from.trim().to_uppercase();
+    let targets = multi_targets(&from, &targets);
+    if targets.is_empty() {
+        return Err("Pick at least one other currency".to_string());
+    }
+
+    let from_crypto = crypto_prices::is_crypto(&from);
+    let (coin_pairs, fiat_pairs): (Vec<String>, Vec<String>) = targets
+        .iter()
+        .cloned()
+        .partition(|to| from_crypto || crypto_prices::is_crypto(to));
+
+    let mut results = Vec::with_capacity(targets.len());
+    if !fiat_pairs.is_empty() {
+        let (table, is_stale) = rate_table(&app).await?;
+        for to in fiat_pairs {
+            let rate = table.rate(&from, &to)?;
+            results.push(CurrencyResult {
+                amount,
+                from: from.clone(),
+                to,
+                result: amount * rate,
+                rate,
+                as_of: table.date.clone(),
+                is_stale,
+                change_7d: None,
+                change_30d: None,
+                change_365d: None,
+            });
+        }
+    }
+    if !coin_pairs.is_empty() {
+        results.extend(crypto_prices::convert_many(amount, &from, &coin_pairs).await?);
+    }
+    results.sort_by_key(|r| targets.iter().position(|t| *t == r.to));
+    Ok(results)
+}
@@ -0,0 +1,86 @@
+// Desktop shortcuts to a tool or a quick action, e.g. an icon that opens the color picker
+// straight away. A shortcut starts `bunchatools --open-tool <tool>` or `--action <action>`
+// like the jump list does, so with BunchaTools running the single-instance plugin forwards
+// it (see shell_integration.rs). The .lnk / .desktop file and its icon are the platforms'.
+
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+use tauri::AppHandle;
+
+use crate::actions::Action;
+use crate::platform::{self, DesktopShortcut};
+use crate::shell_integration;
+
+/// The action `action` names with the parameters in `args`, e.g. ("kill_port", {port: 3000})
+pub fn shortcut_action(action: &str, args: BTreeMap<String, Value>) -> Result<Action, String> {
+    let mut fields: serde_json::Map<String, Value> = args.into_iter().collect();
+    fields.insert("type".to_string(), Value::String(action.to_string()));
+    serde_json::from_value(Value::Object(fields))
+        .map_err(|e| format!("Not an action for a shortcut ({}): {}", action, e))
+}
+
+// "color-picker" -> "Color Picker", "kill_port" -> "Kill Port"
+fn title_case(id: &str) -> String {
+    id.split(['-', '_'])
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The label of a shortcut nobody named
+pub fn default_shortcut_name(action: &Action) -> String {
+    match action {
+        Action::OpenTool { tool } => title_case(tool),
+        Action::KillPort { port } => format!("Kill port {}", port),
+        Action::StartTimer {
+            label: Some(label), ..
+        } => label.clone(),
+        other => serde_json::to_value(other)
+            .ok()
+            .and_then(|value| value["type"].as_str().map(title_case))
+            .unwrap_or_else(|| "BunchaTools".to_string()),
+    }
+}
+
+// The window icon as an image for the platforms that need one as a file
+fn app_icon(app: &AppHandle) -> Option<image::RgbaImage> {
+    let icon = app.default_window_icon()?;
+    image::RgbaImage::from_raw(icon.width(), icon.height(), icon.rgba().to_vec())
+}
+
+/// Put a shortcut on the desktop that runs `action` (a quick action, or `open_tool` with a
+/// `tool`) with `args`, named `name` or after the action; returns the file's path
+#[tauri::command]
+pub async fn create_desktop_shortcut(
+    app: AppHandle,
+    action: String,
+    args: Option<BTreeMap<String, Value>>,
+    name: Option<String>,
+) -> Result<String, String> {
+    let action = shortcut_action(&action, args.unwrap_or_default())?;
+    let name = name
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| default_shortcut_name(&action));
+    let shortcut = DesktopShortcut {
+        comment: format!("{} (BunchaTools)", name),
+        name,
+        args: shell_integration::launch_args(&action),
+        icon: app_icon(&app),
+    };
+    let path = tauri::async_runtime::spawn_blocking(move || {
+        platform::create_desktop_shortcut_impl(&shortcut)
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+    log::info!("Created desktop shortcut {}", path.display());
+    Ok(path.to_string_lossy().to_string())
+}
@@ -0,0 +1,216 @@
+// Dictionary lookups: definitions, parts of speech, synonyms and examples for a word, from the
+// free dictionaryapi.dev (Wiktionary data, mostly English). Words looked up before are kept in
+// `dictionary_cache.json`, so they come back instantly and work offline.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+const CACHE_FILE: &str = "dictionary_cache.json";
+const DICTIONARY_API: &str = "https://api.dictionaryapi.dev/api/v2/entries";
+
+// Oldest words are dropped beyond this
+const CACHE_LIMIT: usize = 500;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Sense {
+    pub definition: String,
+    pub example: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Meaning {
+    pub part_of_speech: String, // "noun", "verb", ...
+    pub senses: Vec<Sense>,
+    pub synonyms: Vec<String>,
+    pub antonyms: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WordDefinition {
+    pub word: String,
+    pub language: String,
+    pub phonetic: Option<String>,
+    pub meanings: Vec<Meaning>, // one per part of speech
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct DictionaryCache {
+    words: HashMap<String, (u64, WordDefinition)>, // by "lang:word", with when it was looked up
+}
+
+#[derive(Deserialize)]
+struct ApiEntry {
+    word: String,
+    phonetic: Option<String>,
+    #[serde(default)]
+    phonetics: Vec<ApiPhonetic>,
+    #[serde(default)]
+    meanings: Vec<ApiMeaning>,
+}
+
+#[derive(Deserialize)]
+struct ApiPhonetic {
+    text: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiMeaning {
+    part_of_speech: String,
+    #[serde(default)]
+    definitions: Vec<ApiDefinition>,
+    #[serde(default)]
+    synonyms: Vec<String>,
+    #[serde(default)]
+    antonyms: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct ApiDefinition {
+    definition: String,
+    example: Option<String>,
+    #[serde(default)]
+    synonyms: Vec<String>,
+    #[serde(default)]
+    antonyms: Vec<String>,
+}
+
+fn push_unique(list: &mut Vec<String>, items: Vec<String>) {
+    for item in items {
+        if !item.trim().is_empty() && !list.contains(&item) {
+            list.push(item);
+        }
+    }
+}
+
+/// The API's entries for a word (one per etymology) merged into one definition, with a
+/// meaning per part of speech
+pub fn parse_entries(json: &str, language: &str) -> Result<WordDefinition, String> {
+    let entries: Vec<ApiEntry> =
+        serde_json::from_str(json).map_err(|e| format!("Failed to parse definition: {}", e))?;
+    let first = entries.first().ok_or("No definitions found")?;
+    let mut definition = WordDefinition {
+        word: first.word.clone(),
+        language: language.to_string(),
+        phonetic: entries
+            .iter()
+            .filter_map(|e| e.phonetic.clone())
+            .chain(
+                entries
+                    .iter()
+                    .flat_map(|e| e.phonetics.iter().filter_map(|p| p.text.clone())),
+            )
+            .find(|p| !p.trim().is_empty()),
+        meanings: Vec::new(),
+    };
+    for api_meaning in entries.into_iter().flat_map(|e| e.meanings) {
+        let index = match definition
+            .meanings
+            .iter()
+            .position(|m| m.part_of_speech == api_meaning.part_of_speech)
+        {
+            Some(index) => index,
+            None => {
+                definition.meanings.push(Meaning {
+                    part_of_speech: api_meaning.part_of_speech.clone(),
+                    senses: Vec::new(),
+                    synonyms: Vec::new(),
+                    antonyms: Vec::new(),
+                });
+                definition.meanings.len() - 1
+            }
+        };
+        let meaning = &mut definition.meanings[index];
+        for api_definition in api_meaning.definitions {
+            meaning.senses.push(Sense {
+                definition: api_definition.definition,
+                example: api_definition.example.filter(|e| !e.trim().is_empty()),
+            });
+            push_unique(&mut meaning.synonyms, api_definition.synonyms);
+            push_unique(&mut meaning.antonyms, api_definition.antonyms);
+        }
+        push_unique(&mut meaning.synonyms, api_meaning.synonyms);
+        push_unique(&mut meaning.antonyms, api_meaning.antonyms);
+    }
+    if definition.meanings.iter().all(|m| m.senses.is_empty()) {
+        return Err(format!("No definitions found for \"{}\"", definition.word));
+    }
+    Ok(definition)
+}
+
+async fn fetch(word: &str, language: &str) -> Result<WordDefinition, String> {
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let url = format!(
+        "{}/{}/{}",
+        DICTIONARY_API,
+        language,
+        urlencoding::encode(word)
+    );
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to connect: {}", e))?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Err(format!("No definitions found for \"{}\"", word));
+    }
+    if !response.status().is_success() {
+        return Err(format!("Dictionary error: {}", response.status()));
+    }
+    let body = response.text().await.map_err(|e| e.to_string())?;
+    parse_entries(&body, language)
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Definitions of `word` in `lang` (an ISO 639-1 code, English by default)
+#[tauri::command]
+pub async fn define_word(
+    app: AppHandle,
+    word: String,
+    lang: Option<String>,
+) -> Result<WordDefinition, String> {
+    let word = word.trim().to_lowercase();
+    if word.is_empty() || word.split_whitespace().count() > 3 {
+        return Err("Give a word to look up".to_string());
+    }
+    let language = lang
+        .map(|l| l.trim().to_lowercase())
+        .filter(|l| !l.is_empty())
+        .unwrap_or_else(|| "en".to_string());
+    if language.len() != 2 || !language.chars().all(|c| c.is_ascii_lowercase()) {
+        return Err(format!("\"{}\" isn't a language code (e.g. en)", language));
+    }
+
+    let key = format!("{}:{}", language, word);
+    let mut cache: DictionaryCache = crate::load_json_file(&app, CACHE_FILE);
+    if let Some((_, definition)) = cache.words.get(&key) {
+        return Ok(definition.clone());
+    }
+    let definition = fetch(&word, &language).await?;
+    cache.words.insert(key, (now_secs(), definition.clone()));
+    if cache.words.len() > CACHE_LIMIT {
+        let oldest = cache
+            .words
+            .iter()
+            .min_by_key(|(_, (looked_up, _))| *looked_up)
+            .map(|(key, _)| key.clone());
+        if let Some(oldest) = oldest {
+            cache.words.remove(&oldest);
+        }
+    }
+    if let Err(e) = crate::save_json_file(&app, CACHE_FILE, &cache) {
+        log::warn!("Failed to save dictionary cache: {}", e);
+    }
+    Ok(definition)
+}
@@ -0,0 +1,55 @@
+// Elevation helper for narrow admin-only operations (hosts file edits, killing protected
+// processes, some power actions). Each call elevates exactly one program invocation
+// through UAC on Windows or pkexec/polkit on Linux.
+
+use crate::platform;
+
+pub use platform::ELEVATION_DECLINED;
+
+/// Whether the current process already has administrator/root rights
+pub fn is_elevated() -> bool {
+    platform::is_elevated_impl()
+}
+
+/// Run `program` with administrator rights, prompting the user if needed.
+/// `action` describes the operation for error messages (e.g. "kill process 1234").
+pub fn run_elevated(program: &str, args: &[&str], action: &str) -> Result<(), String> {
+    let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+
+    let exit_code = if is_elevated() {
+        // Already elevated: run directly so we don't show a pointless prompt
+        let output = crate::hidden_command(program)
+            .args(&args)
+            .output()
+            .map_err(|e| format!("Failed to {}: {}", action, e))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("Failed to {}: {}", action, stderr.trim()));
+        }
+        0
+    } else {
+        log::info!("Requesting elevation to {}", action);
+        platform::run_elevated_impl(program, &args).map_err(|e| {
+            if e == ELEVATION_DECLINED {
+                format!("Could not {}: {}", action, ELEVATION_DECLINED)
+            } else {
+                format!("Failed to {}: {}", action, e)
+            }
+        })?
+    };
+
+    if exit_code != 0 {
+        return Err(format!(
+            "Failed to {}: elevated process exited with code {}",
+            action, exit_code
+        ));
+    }
+
+    Ok(())
+}
+
+/// Let the UI know whether admin-only tools will trigger a prompt
+#[tauri::command]
+pub fn is_process_elevated() -> bool {
+    is_elevated()
+}
@@ -0,0 +1,683 @@
+// Math expressions typed into the launcher: arithmetic with ^ for powers, percentages
+// ("200 + 15%", "15% of 80"), bit operations on whole numbers (&, |, xor, ~, <<, >>), hex,
+// binary and octal literals and results ("0xFF & 0b1010 in bin"), and terms with units or
+// currencies, converted to the first term's unit ("5 km + 300 m in mi", "20 usd + 15 eur").
+// Units come from units.rs, exchange rates from currency.rs and are only fetched when the
+// expression has a currency code in it.
+
+use std::f64::consts::{E, PI, TAU};
+use std::fmt;
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::calculator::format_number;
+use crate::currency::{self, RateTable};
+use crate::units;
+
+// Between an expression and what to show it in, "... in mi"
+const SEPARATORS: &[&str] = &["in", "to", "as", "into"];
+const FUNCTIONS: &[&str] = &[
+    "sqrt", "abs", "round", "floor", "ceil", "ln", "log", "log2", "exp", "sin", "cos", "tan",
+];
+const CONSTANTS: &[&str] = &["pi", "π", "e", "tau"];
+const KEYWORDS: &[&str] = &["of", "mod", "xor"];
+
+/// A calculated expression (`evaluate_expression`)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExpressionResult {
+    pub value: f64,
+    pub unit: Option<String>, // unit symbol or currency code of the value
+    pub result: String,       // the value to copy, e.g. "8.5" or "0xFF"
+    pub display: String,      // with its unit, e.g. "8.5 km"
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Base {
+    Hex,
+    Binary,
+    Octal,
+    Decimal,
+}
+
+fn base(word: &str) -> Option<Base> {
+    match word.to_lowercase().as_str() {
+        "hex" | "hexadecimal" => Some(Base::Hex),
+        "bin" | "binary" => Some(Base::Binary),
+        "oct" | "octal" => Some(Base::Octal),
+        "dec" | "decimal" => Some(Base::Decimal),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Word(String),
+    Op(&'static str),
+    Open,
+    Close,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Number(value) => write!(f, "{}", format_number(*value)),
+            Token::Word(word) => write!(f, "{}", word),
+            Token::Op(op) => write!(f, "{}", op),
+            Token::Open => write!(f, "("),
+            Token::Close => write!(f, ")"),
+        }
+    }
+}
+
+fn is_word_char(c: char, first: bool) -> bool {
+    c.is_alphabetic() || c == '°' || (!first && (c.is_ascii_digit() || c == '²' || c == '³'))
+}
+
+// A number at `chars[*i]`: decimal with optional `_` or `,` thousands grouping and exponent,
+// or a 0x / 0b / 0o literal
+fn lex_number(chars: &[char], i: &mut usize) -> Result<f64, String> {
+    let start = *i;
+    let radix = match chars.get(start + 1).map(|c| c.to_ascii_lowercase()) {
+        Some('x') if chars[start] == '0' => 16,
+        Some('b') if chars[start] == '0' => 2,
+        Some('o') if chars[start] == '0' => 8,
+        _ => 10,
+    };
+    if radix != 10 && chars.get(start + 2).is_some_and(|c| c.is_digit(radix)) {
+        let mut end = start + 2;
+        while end < chars.len() && (chars[end].is_digit(radix) || chars[end] == '_') {
+            end += 1;
+        }
+        let digits: String = chars[start + 2..end]
+            .iter()
+            .filter(|c| **c != '_')
+            .collect();
+        let literal: String = chars[start..end].iter().collect();
+        if chars.get(end).is_some_and(|c| c.is_ascii_alphanumeric()) {
+            return Err(format!("'{}' isn't a number", literal));
+        }
+        *i = end;
+        return u64::from_str_radix(&digits, radix)
+            .map(|n| n as f64)
+            .map_err(|_| format!("'{}' is too large", literal));
+    }
+
+    let digit_at = |at: usize| chars.get(at).is_some_and(|c| c.is_ascii_digit());
+    let mut end = start;
+    let mut text = String::new();
+    while end < chars.len() {
+        let c = chars[end];
+        if c.is_ascii_digit() || c == '.' {
+            text.push(c);
+            end += 1;
+        } else if c == '_' && digit_at(end + 1) {
+            end += 1;
+        } else if c == ',' && (1..=3).all(|k| digit_at(end + k)) && !digit_at(end + 4) {
+            end += 1; // "1,000"
+        } else if (c == 'e' || c == 'E')
+            && (digit_at(end + 1)
+                || (matches!(chars.get(end + 1), Some('+' | '-')) && digit_at(end + 2)))
+        {
+            text.push('e');
+            text.push(chars[end + 1]);
+            end += 2;
+        } else {
+            break;
+        }
+    }
+    *i = end;
+    text.parse()
+        .map_err(|_| format!("'{}' isn't a number", text))
+}
+
+fn tokenize(expr: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = expr.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_ascii_digit() || (c == '.' && next.is_some_and(|n| n.is_ascii_digit())) {
+            tokens.push(Token::Number(lex_number(&chars, &mut i)?));
+        } else if is_word_char(c, true) {
+            let start = i;
+            while i < chars.len() && is_word_char(chars[i], i == start) {
+                i += 1;
+            }
+            tokens.push(Token::Word(chars[start..i].iter().collect()));
+        } else {
+            let (token, len) = match (c, next) {
+                ('*', Some('*')) => (Token::Op("^"), 2),
+                ('<', Some('<')) => (Token::Op("<<"), 2),
+                ('>', Some('>')) => (Token::Op(">>"), 2),
+                ('+', _) => (Token::Op("+"), 1),
+                ('-' | '−', _) => (Token::Op("-"), 1),
+                ('*' | '×' | '·', _) => (Token::Op("*"), 1),
+                ('/' | '÷', _) => (Token::Op("/"), 1),
+                ('^', _) => (Token::Op("^"), 1),
+                ('%', _) => (Token::Op("%"), 1),
+                ('&', _) => (Token::Op("&"), 1),
+                ('|', _) => (Token::Op("|"), 1),
+                ('~', _) => (Token::Op("~"), 1),
+                ('(', _) => (Token::Open, 1),
+                (')', _) => (Token::Close, 1),
+                _ => return Err(format!("Unexpected '{}'", c)),
+            };
+            tokens.push(token);
+            i += len;
+        }
+    }
+    Ok(tokens)
+}
+
+fn is_reserved(word: &str) -> bool {
+    let word = word.to_lowercase();
+    [SEPARATORS, FUNCTIONS, CONSTANTS, KEYWORDS]
+        .iter()
+        .any(|words| words.contains(&word.as_str()))
+        || base(&word).is_some()
+}
+
+// Three letters that aren't a unit or a word of the expression: taken for a currency code
+fn is_currency_code(word: &str) -> bool {
+    word.len() == 3
+        && word.chars().all(|c| c.is_ascii_alphabetic())
+        && !is_reserved(word)
+        && units::unit_symbol(word).is_none()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Kind {
+    Plain,
+    Percent,
+    Unit(String), // as typed, e.g. "km"
+    Currency(String),
+}
+
+impl Kind {
+    fn describe(&self) -> String {
+        match self {
+            Kind::Plain => "a number".to_string(),
+            Kind::Percent => "a percentage".to_string(),
+            Kind::Unit(unit) => unit.clone(),
+            Kind::Currency(code) => code.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Quantity {
+    value: f64,
+    kind: Kind,
+}
+
+impl Quantity {
+    fn plain(value: f64) -> Self {
+        Quantity {
+            value,
+            kind: Kind::Plain,
+        }
+    }
+
+    fn with(value: f64, kind: Kind) -> Self {
+        Quantity { value, kind }
+    }
+
+    // A percentage used as a number: 15% is 0.15
+    fn fraction(self) -> Self {
+        match self.kind {
+            Kind::Percent => Quantity::plain(self.value / 100.0),
+            _ => self,
+        }
+    }
+
+    // For bit operations, which only work on whole numbers
+    fn integer(&self) -> Result<i64, String> {
+        if self.kind != Kind::Plain {
+            return Err(format!(
+                "Bit operations don't work on {}",
+                self.kind.describe()
+            ));
+        }
+        if self.value.fract() != 0.0 || self.value.abs() >= i64::MAX as f64 {
+            return Err("Bit operations need whole numbers".to_string());
+        }
+        Ok(self.value as i64)
+    }
+}
+
+struct Parser<'a> {
+    tokens: Vec<Token>,
+    pos: usize,
+    rates: Option<&'a RateTable>,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn eat_op(&mut self, ops: &[&str]) -> Option<&'static str> {
+        match self.peek() {
+            Some(Token::Op(op)) if ops.contains(op) => {
+                let op = *op;
+                self.pos += 1;
+                Some(op)
+            }
+            _ => None,
+        }
+    }
+
+    fn eat_word(&mut self, word: &str) -> bool {
+        let found = matches!(self.peek(), Some(Token::Word(w)) if w.eq_ignore_ascii_case(word));
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    // Whether the token at `at` starts an operand, which makes a '%' before it a modulo
+    fn operand_at(&self, at: usize) -> bool {
+        match self.tokens.get(at) {
+            Some(Token::Number(_) | Token::Open) => true,
+            Some(Token::Word(word)) => !KEYWORDS.contains(&word.to_lowercase().as_str()),
+            _ => false,
+        }
+    }
+
+    fn word_kind(&self, word: &str) -> Option<Kind> {
+        if units::unit_symbol(word).is_some() {
+            Some(Kind::Unit(word.to_string()))
+        } else if is_currency_code(word) {
+            Some(Kind::Currency(word.to_uppercase()))
+        } else {
+            None
+        }
+    }
+
+    // `quantity`'s value in `kind`; a plain number is taken to be in it already
+    fn convert(&self, quantity: &Quantity, kind: &Kind) -> Result<f64, String> {
+        match (&quantity.kind, kind) {
+            (Kind::Plain, _) => Ok(quantity.value),
+            (Kind::Unit(from), Kind::Unit(to)) => {
+                Ok(units::convert(quantity.value, from, to)?.result)
+            }
+            (Kind::Currency(from), Kind::Currency(to)) => {
+                let rates = self
+                    .rates
+                    .ok_or_else(|| "Exchange rates aren't available".to_string())?;
+                Ok(quantity.value * rates.rate(from, to)?)
+            }
+            (from, to) => Err(format!(
+                "Can't combine {} with {}",
+                from.describe(),
+                to.describe()
+            )),
+        }
+    }
+
+    fn expression(&mut self) -> Result<Quantity, String> {
+        let mut left = self.bit_xor()?;
+        while self.eat_op(&["|"]).is_some() {
+            let right = self.bit_xor()?;
+            left = Quantity::plain((left.integer()? | right.integer()?) as f64);
+        }
+        Ok(left)
+    }
+
+    fn bit_xor(&mut self) -> Result<Quantity, String> {
+        let mut left = self.bit_and()?;
+        while self.eat_word("xor") {
+            let right = self.bit_and()?;
+            left = Quantity::plain((left.integer()? ^ right.integer()?) as f64);
+        }
+        Ok(left)
+    }
+
+    fn bit_and(&mut self) -> Result<Quantity, String> {
+        let mut left = self.shift()?;
+        while self.eat_op(&["&"]).is_some() {
+            let right = self.shift()?;
+            left = Quantity::plain((left.integer()? & right.integer()?) as f64);
+        }
+        Ok(left)
+    }
+
+    fn shift(&mut self) -> Result<Quantity, String> {
+        let mut left = self.additive()?;
+        while let Some(op) = self.eat_op(&["<<", ">>"]) {
+            let right = self.additive()?.integer()?;
+            let bits = u32::try_from(right)
+                .ok()
+                .filter(|bits| *bits < 64)
+                .ok_or_else(|| format!("Can't shift by {}", right))?;
+            let value = left.integer()?;
+            left = Quantity::plain(if op == "<<" {
+                value << bits
+            } else {
+                value >> bits
+            } as f64);
+        }
+        Ok(left)
+    }
+
+    fn additive(&mut self) -> Result<Quantity, String> {
+        let mut left = self.term()?;
+        while let Some(op) = self.eat_op(&["+", "-"]) {
+            let right = self.term()?;
+            let sign = if op == "+" { 1.0 } else { -1.0 };
+            left = self.add(left, right, sign)?;
+        }
+        Ok(left)
+    }
+
+    // `left + sign * right`; adding a percentage adds that much of `left`
+    fn add(&self, left: Quantity, right: Quantity, sign: f64) -> Result<Quantity, String> {
+        match (&left.kind, &right.kind) {
+            (Kind::Percent, Kind::Percent) => Ok(Quantity::with(
+                left.value + sign * right.value,
+                Kind::Percent,
+            )),
+            (_, Kind::Percent) => Ok(Quantity::with(
+                left.value * (1.0 + sign * right.value / 100.0),
+                left.kind,
+            )),
+            (Kind::Percent, _) => self.add(left.fraction(), right, sign),
+            (Kind::Plain, _) => Ok(Quantity::with(left.value + sign * right.value, right.kind)),
+            _ => {
+                let right = self.convert(&right, &left.kind)?;
+                Ok(Quantity::with(left.value + sign * right, left.kind))
+            }
+        }
+    }
+
+    fn term(&mut self) -> Result<Quantity, String> {
+        let mut left = self.unary()?;
+        loop {
+            if self.eat_op(&["*"]).is_some() {
+                let right = self.unary()?;
+                left = multiply(left, right)?;
+            } else if self.eat_op(&["/"]).is_some() {
+                let right = self.unary()?;
+                left = self.divide(left, right)?;
+            } else if self.eat_word("mod") || self.eat_op(&["%"]).is_some() {
+                let right = self.unary()?.fraction();
+                if right.kind != Kind::Plain {
+                    return Err(format!(
+                        "Can't take the remainder by {}",
+                        right.kind.describe()
+                    ));
+                }
+                if right.value == 0.0 {
+                    return Err("Division by zero".to_string());
+                }
+                let left_value = left.clone().fraction();
+                left = Quantity::with(left_value.value % right.value, left_value.kind);
+            } else if self.eat_word("of") {
+                if left.kind != Kind::Percent {
+                    return Err("'of' goes after a percentage, like 15% of 80".to_string());
+                }
+                let right = self.unary()?;
+                left = multiply(left, right)?;
+            } else {
+                return Ok(left);
+            }
+        }
+    }
+
+    fn divide(&self, left: Quantity, right: Quantity) -> Result<Quantity, String> {
+        let (left, right) = (left.fraction(), right.fraction());
+        let divisor = match (&left.kind, &right.kind) {
+            (_, Kind::Plain) => right.value,
+            (Kind::Plain, kind) => {
+                return Err(format!("Can't divide a number by {}", kind.describe()))
+            }
+            (kind, _) => self.convert(&right, kind)?,
+        };
+        if divisor == 0.0 {
+            return Err("Division by zero".to_string());
+        }
+        let kind = if right.kind == Kind::Plain {
+            left.kind
+        } else {
+            Kind::Plain // a ratio of two lengths, amounts, ...
+        };
+        Ok(Quantity::with(left.value / divisor, kind))
+    }
+
+    fn unary(&mut self) -> Result<Quantity, String> {
+        if self.eat_op(&["-"]).is_some() {
+            let operand = self.unary()?;
+            Ok(Quantity::with(-operand.value, operand.kind))
+        } else if self.eat_op(&["+"]).is_some() {
+            self.unary()
+        } else if self.eat_op(&["~"]).is_some() {
+            Ok(Quantity::plain(!self.unary()?.integer()? as f64))
+        } else {
+            self.power()
+        }
+    }
+
+    fn power(&mut self) -> Result<Quantity, String> {
+        let base = self.postfix()?;
+        if self.eat_op(&["^"]).is_none() {
+            return Ok(base);
+        }
+        let (base, exponent) = (base.fraction(), self.unary()?.fraction());
+        if base.kind != Kind::Plain || exponent.kind != Kind::Plain {
+            return Err("Only plain numbers can be raised to a power".to_string());
+        }
+        Ok(Quantity::plain(base.value.powf(exponent.value)))
+    }
+
+    // A value with its '%' or unit: "15%", "5 km", "(2 + 3) usd"
+    fn postfix(&mut self) -> Result<Quantity, String> {
+        let mut quantity = self.primary()?;
+        if matches!(self.peek(), Some(Token::Op("%"))) && !self.operand_at(self.pos + 1) {
+            self.pos += 1;
+            if quantity.kind != Kind::Plain {
+                return Err(format!(
+                    "Can't take a percentage of {}",
+                    quantity.kind.describe()
+                ));
+            }
+            quantity.kind = Kind::Percent;
+        }
+        if quantity.kind == Kind::Plain {
+            if let Some(Token::Word(word)) = self.peek() {
+                if let Some(kind) = self.word_kind(word) {
+                    self.pos += 1;
+                    quantity.kind = kind;
+                }
+            }
+        }
+        Ok(quantity)
+    }
+
+    fn primary(&mut self) -> Result<Quantity, String> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .cloned()
+            .ok_or_else(|| "The expression is incomplete".to_string())?;
+        self.pos += 1;
+        match token {
+            Token::Number(value) => Ok(Quantity::plain(value)),
+            Token::Open => {
+                let inner = self.expression()?;
+                if self.peek() != Some(&Token::Close) {
+                    return Err("Missing ')'".to_string());
+                }
+                self.pos += 1;
+                Ok(inner)
+            }
+            Token::Word(word) => {
+                let name = word.to_lowercase();
+                if FUNCTIONS.contains(&name.as_str()) && self.peek() == Some(&Token::Open) {
+                    let argument = self.primary()?;
+                    return call(&name, argument);
+                }
+                match name.as_str() {
+                    "pi" | "π" => Ok(Quantity::plain(PI)),
+                    "e" => Ok(Quantity::plain(E)),
+                    "tau" => Ok(Quantity::plain(TAU)),
+                    _ => Err(format!("Unknown word '{}'", word)),
+                }
+            }
+            token => Err(format!("Unexpected '{}'", token)),
+        }
+    }
+}
+
+fn multiply(left: Quantity, right: Quantity) -> Result<Quantity, String> {
+    let (left, right) = (left.fraction(), right.fraction());
+    match (&left.kind, &right.kind) {
+        (Kind::Plain, _) => Ok(Quantity::with(left.value * right.value, right.kind)),
+        (_, Kind::Plain) => Ok(Quantity::with(left.value * right.value, left.kind)),
+        (a, b) => Err(format!(
+            "Can't multiply {} by {}",
+            a.describe(),
+            b.describe()
+        )),
+    }
+}
+
+// Rounding keeps a unit, the rest of the functions take plain numbers; angles in radians
+fn call(function: &str, argument: Quantity) -> Result<Quantity, String> {
+    let argument = argument.fraction();
+    let keeps_unit = |value: f64| Ok(Quantity::with(value, argument.kind.clone()));
+    match function {
+        "abs" => return keeps_unit(argument.value.abs()),
+        "round" => return keeps_unit(argument.value.round()),
+        "floor" => return keeps_unit(argument.value.floor()),
+        "ceil" => return keeps_unit(argument.value.ceil()),
+        _ => {}
+    }
+    if argument.kind != Kind::Plain {
+        return Err(format!("{} needs a plain number", function));
+    }
+    let x = argument.value;
+    let value = match function {
+        "sqrt" => x.sqrt(),
+        "ln" => x.ln(),
+        "log" => x.log10(),
+        "log2" => x.log2(),
+        "exp" => x.exp(),
+        "sin" => x.sin(),
+        "cos" => x.cos(),
+        _ => x.tan(),
+    };
+    Ok(Quantity::plain(value))
+}
+
+fn in_base(value: f64, base: Base) -> Result<String, String> {
+    if value.fract() != 0.0 || value.abs() >= i64::MAX as f64 {
+        return Err("Only whole numbers can be shown in another base".to_string());
+    }
+    let n = value as i64;
+    let sign = if n < 0 { "-" } else { "" };
+    let m = n.unsigned_abs();
+    Ok(match base {
+        Base::Hex => format!("{}0x{:X}", sign, m),
+        Base::Binary => format!("{}0b{:b}", sign, m),
+        Base::Octal => format!("{}0o{:o}", sign, m),
+        Base::Decimal => n.to_string(),
+    })
+}
+
+/// Whether `expr` has a currency code in it, so `evaluate` needs the exchange rates
+pub fn uses_currency(expr: &str) -> bool {
+    tokenize(expr).is_ok_and(|tokens| {
+        tokens
+            .iter()
+            .any(|token| matches!(token, Token::Word(word) if is_currency_code(word)))
+    })
+}
+
+/// Calculate `expr`, optionally ending in "in <unit / currency / hex / bin / oct / dec>".
+/// Currencies are converted with `rates`.
+pub fn evaluate(expr: &str, rates: Option<&RateTable>) -> Result<ExpressionResult, String> {
+    let mut tokens = tokenize(expr)?;
+    if tokens.is_empty() {
+        return Err("Nothing to calculate".to_string());
+    }
+    // "5 in in cm": only the second to last word can be the separator
+    let mut target = None;
+    if let [_, .., Token::Word(separator), Token::Word(word)] = tokens.as_slice() {
+        if SEPARATORS.contains(&separator.to_lowercase().as_str()) {
+            target = Some(word.clone());
+        }
+    }
+    if target.is_some() {
+        tokens.truncate(tokens.len() - 2);
+    }
+
+    let mut parser = Parser {
+        tokens,
+        pos: 0,
+        rates,
+    };
+    let quantity = parser.expression()?.fraction();
+    if let Some(token) = parser.peek() {
+        return Err(format!("Unexpected '{}'", token));
+    }
+
+    let quantity = match target {
+        Some(word) => {
+            if let Some(base) = base(&word) {
+                let text = in_base(quantity.value, base)?;
+                return Ok(ExpressionResult {
+                    value: quantity.value,
+                    unit: None,
+                    result: text.clone(),
+                    display: text,
+                });
+            }
+            let kind = parser
+                .word_kind(&word)
+                .ok_or_else(|| format!("Unknown unit '{}'", word))?;
+            if quantity.kind == Kind::Plain {
+                return Err(format!("Nothing to convert to {}", word));
+            }
+            Quantity::with(parser.convert(&quantity, &kind)?, kind)
+        }
+        None => quantity,
+    };
+    if !quantity.value.is_finite() {
+        return Err("The result isn't a finite number".to_string());
+    }
+
+    let (result, unit) = match &quantity.kind {
+        Kind::Unit(unit) => (
+            format_number(quantity.value),
+            Some(units::unit_symbol(unit).unwrap_or(unit).to_string()),
+        ),
+        Kind::Currency(code) => (format!("{:.2}", quantity.value), Some(code.clone())),
+        _ => (format_number(quantity.value), None),
+    };
+    Ok(ExpressionResult {
+        value: quantity.value,
+        display: match &unit {
+            Some(unit) => format!("{} {}", result, unit),
+            None => result.clone(),
+        },
+        unit,
+        result,
+    })
+}
+
+/// Calculate an expression from the launcher, e.g. "0xFF & 0b1010 in bin", "200 + 15%" or
+/// "5 km + 300 m in mi"
+#[tauri::command]
+pub async fn evaluate_expression(app: AppHandle, expr: String) -> Result<ExpressionResult, String> {
+    let rates = if uses_currency(&expr) {
+        Some(currency::rate_table(&app).await?.0)
+    } else {
+        None
+    };
+    evaluate(&expr, rates.as_ref())
+}
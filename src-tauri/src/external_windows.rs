@@ -0,0 +1,48 @@
+// Other apps' windows: pin one above everything (a reference document, a calculator) or make
+// it translucent (a video to keep an eye on while working). Windows are found with
+// `list_external_windows`, by the title the launcher matches against.
+
+use crate::platform::{self, ExternalWindow};
+
+// Anything fainter is easy to lose track of
+const MIN_OPACITY: f32 = 0.1;
+
+/// `alpha` (0..1) as the byte the platforms take, refusing nearly invisible windows
+pub fn opacity_byte(alpha: f32) -> Result<u8, String> {
+    if !alpha.is_finite() || !(MIN_OPACITY..=1.0).contains(&alpha) {
+        return Err(format!(
+            "Opacity goes from {}% to 100%",
+            (MIN_OPACITY * 100.0) as u32
+        ));
+    }
+    Ok((alpha * 255.0).round() as u8)
+}
+
+/// Top-level windows of other apps, most recent first
+#[tauri::command]
+pub async fn list_external_windows() -> Result<Vec<ExternalWindow>, String> {
+    tauri::async_runtime::spawn_blocking(platform::list_external_windows)
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Keep another app's window on top of all others (`on`), or stop
+#[tauri::command]
+pub async fn set_external_window_topmost(window_id: u64, on: bool) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        platform::set_external_window_topmost(window_id, on)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Make another app's window translucent, `alpha` from 0.1 to 1 (opaque)
+#[tauri::command]
+pub async fn set_external_window_opacity(window_id: u64, alpha: f32) -> Result<(), String> {
+    let alpha = opacity_byte(alpha)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        platform::set_external_window_opacity(window_id, alpha)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
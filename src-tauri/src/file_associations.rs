@@ -0,0 +1,208 @@
+// File associations for formats the tools read
+// When enabled in settings, BunchaTools registers as the handler for download lists, workflow
+// files and palettes. Opening one launches the app (or reaches the running instance through
+// the single-instance plugin) and routes the file to its tool: lists and palettes are loaded
+// by the frontend, workflows are shown with their actions and only run once the user confirms.
+//
+// Formats:
+//   .torrentless           one URL per line, '#' starts a comment
+//   .bunchatools-workflow  JSON: {"name": "...", "actions": [<Action>, ...]}
+//   .gpl                   GIMP palette ("R G B name" lines) or plain "#RRGGBB" lines
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::actions::{self, Action};
+use crate::notifications::{self, NotifyAction};
+use crate::platform::{self, FileAssociation};
+
+/// Pseudo tool id for workflow files, which the frontend confirms instead of opening a tool
+pub const WORKFLOW_TOOL: &str = "workflow";
+
+const FILE_TYPES: [(FileAssociation, &str); 3] = [
+    (
+        FileAssociation {
+            extension: "torrentless",
+            description: "BunchaTools Download List",
+            mime_type: "application/x-bunchatools-download-list",
+            owned: true,
+        },
+        "youtube-downloader",
+    ),
+    (
+        FileAssociation {
+            extension: "bunchatools-workflow",
+            description: "BunchaTools Workflow",
+            mime_type: "application/x-bunchatools-workflow",
+            owned: true,
+        },
+        WORKFLOW_TOOL,
+    ),
+    (
+        FileAssociation {
+            extension: "gpl",
+            description: "GIMP Palette",
+            mime_type: "application/x-gimp-palette",
+            owned: false,
+        },
+        "color-picker",
+    ),
+];
+
+fn extension_of(path: &str) -> Option<String> {
+    Path::new(path)
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+}
+
+/// Tool that opens `path`, based on its extension
+pub fn tool_for_path(path: &str) -> Option<&'static str> {
+    let extension = extension_of(path)?;
+    FILE_TYPES
+        .iter()
+        .find(|(association, _)| association.extension == extension)
+        .map(|(_, tool)| *tool)
+}
+
+/// Add or remove the handler registrations
+pub fn set_file_associations(enable: bool) -> Result<(), String> {
+    let associations: Vec<FileAssociation> = FILE_TYPES.iter().map(|(a, _)| *a).collect();
+    platform::set_file_associations_impl(enable, &associations)
+}
+
+// Only files of the tool's own format can be read through the commands below
+fn read_associated_file(path: &str, tool: &str) -> Result<String, String> {
+    if tool_for_path(path) != Some(tool) {
+        return Err(format!("Unsupported file type: {}", path));
+    }
+    std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path, e))
+}
+
+// ============================================================================
+// Download lists
+// ============================================================================
+
+pub fn parse_download_list(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect()
+}
+
+/// URLs from a `.torrentless` download list
+#[tauri::command]
+pub fn read_download_list(path: String) -> Result<Vec<String>, String> {
+    let content = read_associated_file(&path, "youtube-downloader")?;
+    Ok(parse_download_list(&content))
+}
+
+// ============================================================================
+// Palettes
+// ============================================================================
+
+/// Hex colors (`#RRGGBB`) from a GIMP palette or a plain list of hex colors
+pub fn parse_palette(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter_map(|line| {
+            if let Some(hex) = line.strip_prefix('#') {
+                // Plain hex line; GIMP comments also start with '#' but aren't valid hex
+                let hex = hex.split_whitespace().next()?;
+                return (hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+                    .then(|| format!("#{}", hex.to_uppercase()));
+            }
+            let mut parts = line.split_whitespace();
+            let r: u8 = parts.next()?.parse().ok()?;
+            let g: u8 = parts.next()?.parse().ok()?;
+            let b: u8 = parts.next()?.parse().ok()?;
+            Some(format!("#{:02X}{:02X}{:02X}", r, g, b))
+        })
+        .collect()
+}
+
+/// Colors from a palette file
+#[tauri::command]
+pub fn read_palette_file(path: String) -> Result<Vec<String>, String> {
+    let content = read_associated_file(&path, "color-picker")?;
+    let colors = parse_palette(&content);
+    if colors.is_empty() {
+        return Err("No colors found in palette".to_string());
+    }
+    Ok(colors)
+}
+
+// ============================================================================
+// Workflows
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Workflow {
+    #[serde(default)]
+    pub name: Option<String>,
+    pub actions: Vec<Action>,
+}
+
+/// A workflow file, named after the file when it doesn't name itself. Shown to the user, who
+/// then runs it with `run_workflow`.
+#[tauri::command]
+pub fn read_workflow_file(path: String) -> Result<Workflow, String> {
+    let content = read_associated_file(&path, WORKFLOW_TOOL)?;
+    let mut workflow = serde_json::from_str::<Workflow>(&content)
+        .map_err(|e| format!("Invalid workflow file: {}", e))?;
+    if workflow.name.is_none() {
+        workflow.name = Path::new(&path)
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string());
+    }
+    if workflow.actions.is_empty() {
+        return Err("The workflow has no actions".to_string());
+    }
+    Ok(workflow)
+}
+
+/// Run a workflow the user confirmed
+#[tauri::command]
+pub fn run_workflow(app: AppHandle, workflow: Workflow) {
+    run_workflow_actions(&app, workflow);
+}
+
+/// Run a workflow's actions in order in the background, stopping at the first failure; the
+/// outcome is shown as a notification
+pub fn run_workflow_actions(app: &AppHandle, workflow: Workflow) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let name = workflow
+            .name
+            .clone()
+            .unwrap_or_else(|| "Workflow".to_string());
+        let result = async {
+            let mut messages = Vec::new();
+            for action in &workflow.actions {
+                let message = actions::run_action(&app, action)
+                    .await
+                    .map_err(|e| format!("{}: {}", name, e))?;
+                messages.extend(message);
+            }
+            Ok::<_, String>(messages)
+        }
+        .await;
+
+        match result {
+            Ok(messages) => notifications::notify(
+                &app,
+                &format!("{} finished", name),
+                &messages.join("\n"),
+                NotifyAction::None,
+            ),
+            Err(e) => {
+                log::warn!("Workflow {} failed: {}", name, e);
+                notifications::notify(&app, "Workflow failed", &e, NotifyAction::None);
+            }
+        }
+    });
+}
@@ -0,0 +1,445 @@
+// Translating whole files: plain text, SRT and WebVTT subtitles, and Markdown. A file is split
+// into the text to translate and the parts that stay as they are (cue numbers and timings,
+// code blocks, list markers, tags, link targets); the text goes to the translation engine in
+// chunks small enough for it, and a copy named like movie.de.srt is written next to the file.
+// Lines that belong together (a cue, a paragraph) are translated as one piece so sentences
+// aren't cut in half; the line breaks inside it are whatever the engine returns.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::offline_translation::TranslationEngine;
+use crate::AppState;
+
+// MyMemory refuses queries over 500 bytes
+const ONLINE_CHUNK_BYTES: usize = 450;
+const OFFLINE_CHUNK_BYTES: usize = 2000;
+const DETECT_SAMPLE_CHARS: usize = 4000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileFormat {
+    Text,
+    Srt,
+    Vtt,
+    Markdown,
+}
+
+impl FileFormat {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_lowercase().as_str() {
+            "txt" | "text" => Some(Self::Text),
+            "srt" => Some(Self::Srt),
+            "vtt" => Some(Self::Vtt),
+            "md" | "markdown" => Some(Self::Markdown),
+            _ => None,
+        }
+    }
+
+    // Inline markup that has to come back exactly as it was
+    fn markup(self) -> Regex {
+        let pattern = match self {
+            Self::Text => r"https?://\S+",
+            // <i>, <font color=..>, WebVTT <c.x>/<v Name>/timestamps, SSA {\an8}
+            Self::Srt | Self::Vtt => r"<[^>\n]+>|\{\\[^}\n]*\}",
+            // `code`, HTML tags, link targets and bare URLs
+            Self::Markdown => r"`[^`\n]+`|<[^>\n]+>|\]\([^)\n]*\)|https?://[^\s)\]]+",
+        };
+        Regex::new(pattern).unwrap()
+    }
+}
+
+/// A part of a file: kept byte for byte, or translated
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Piece {
+    Keep(String),
+    Translate(String),
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FileTranslationProgress {
+    pub path: String,
+    pub done: usize,
+    pub total: usize,
+}
+
+fn push_keep(pieces: &mut Vec<Piece>, text: &str) {
+    if text.is_empty() {
+        return;
+    }
+    match pieces.last_mut() {
+        Some(Piece::Keep(last)) => last.push_str(text),
+        _ => pieces.push(Piece::Keep(text.to_string())),
+    }
+}
+
+// Text to translate, with the whitespace around it kept; text without letters is kept as is
+fn push_text(pieces: &mut Vec<Piece>, text: &str) {
+    let trimmed = text.trim();
+    if !trimmed.chars().any(char::is_alphabetic) {
+        push_keep(pieces, text);
+        return;
+    }
+    let start = text.len() - text.trim_start().len();
+    push_keep(pieces, &text[..start]);
+    pieces.push(Piece::Translate(trimmed.to_string()));
+    push_keep(pieces, &text[start + trimmed.len()..]);
+}
+
+// Lines that belong together become one piece to translate, followed by the last line ending
+fn flush_lines(pieces: &mut Vec<Piece>, lines: &mut Vec<&str>) {
+    let Some(last) = lines.last() else {
+        return;
+    };
+    let ending = last.len() - last.trim_end_matches(['\r', '\n']).len();
+    let joined = lines.concat();
+    let (text, ending) = joined.split_at(joined.len() - ending);
+    push_text(pieces, text);
+    push_keep(pieces, ending);
+    lines.clear();
+}
+
+fn split_text(content: &str) -> Vec<Piece> {
+    let mut pieces = Vec::new();
+    let mut paragraph = Vec::new();
+    for line in content.split_inclusive('\n') {
+        if line.trim().is_empty() {
+            flush_lines(&mut pieces, &mut paragraph);
+            push_keep(&mut pieces, line);
+        } else {
+            paragraph.push(line);
+        }
+    }
+    flush_lines(&mut pieces, &mut paragraph);
+    pieces
+}
+
+// SRT and WebVTT: in each block, everything up to the timing line is kept (cue numbers and
+// ids, the WEBVTT header, NOTE and STYLE blocks have no timing line) and the cue text after it
+// is translated
+fn split_subtitles(content: &str) -> Vec<Piece> {
+    let mut pieces = Vec::new();
+    let mut cue = Vec::new();
+    let mut in_cue = false;
+    for line in content.split_inclusive('\n') {
+        if line.trim().is_empty() {
+            flush_lines(&mut pieces, &mut cue);
+            in_cue = false;
+            push_keep(&mut pieces, line);
+        } else if in_cue {
+            cue.push(line);
+        } else {
+            in_cue = line.contains("-->");
+            push_keep(&mut pieces, line);
+        }
+    }
+    flush_lines(&mut pieces, &mut cue);
+    pieces
+}
+
+fn split_markdown(content: &str) -> Vec<Piece> {
+    // Heading, quote, list and task markers in front of a line's text
+    let prefix =
+        Regex::new(r"^\s*(?:(?:#{1,6}|>|[-*+]|\d{1,9}[.)])\s+(?:\[[ xX]\]\s+)?)+").unwrap();
+    let mut pieces = Vec::new();
+    let mut paragraph = Vec::new();
+    let mut fence: Option<&str> = None;
+    let mut front_matter = content.starts_with("---");
+    for (index, line) in content.split_inclusive('\n').enumerate() {
+        let bare = line.trim();
+        if front_matter {
+            push_keep(&mut pieces, line);
+            front_matter = index == 0 || !(bare == "---" || bare == "...");
+            continue;
+        }
+        if let Some(marker) = fence {
+            push_keep(&mut pieces, line);
+            if bare.starts_with(marker) {
+                fence = None;
+            }
+            continue;
+        }
+        if let Some(marker) = ["```", "~~~"].into_iter().find(|m| bare.starts_with(m)) {
+            flush_lines(&mut pieces, &mut paragraph);
+            fence = Some(marker);
+            push_keep(&mut pieces, line);
+            continue;
+        }
+        // Blank lines, rules, setext underlines and table separators
+        if bare.chars().all(|c| "-*_=|: ".contains(c)) {
+            flush_lines(&mut pieces, &mut paragraph);
+            push_keep(&mut pieces, line);
+            continue;
+        }
+        if bare.starts_with('|') {
+            flush_lines(&mut pieces, &mut paragraph);
+            for (i, cell) in line.split('|').enumerate() {
+                if i > 0 {
+                    push_keep(&mut pieces, "|");
+                }
+                push_text(&mut pieces, cell);
+            }
+            continue;
+        }
+        match prefix.find(line) {
+            Some(marker) => {
+                flush_lines(&mut pieces, &mut paragraph);
+                push_keep(&mut pieces, marker.as_str());
+                paragraph.push(&line[marker.end()..]);
+                flush_lines(&mut pieces, &mut paragraph);
+            }
+            None => paragraph.push(line),
+        }
+    }
+    flush_lines(&mut pieces, &mut paragraph);
+    pieces
+}
+
+/// Split a file into the parts to keep and the text to translate; putting the pieces back
+/// together gives the file again
+pub fn split_document(content: &str, format: FileFormat) -> Vec<Piece> {
+    match format {
+        FileFormat::Text => split_text(content),
+        FileFormat::Srt | FileFormat::Vtt => split_subtitles(content),
+        FileFormat::Markdown => split_markdown(content),
+    }
+}
+
+/// Put a file back together with a translation for each `Translate` piece, in order
+pub fn join_document(pieces: &[Piece], translations: &[String]) -> String {
+    let mut translations = translations.iter();
+    pieces
+        .iter()
+        .map(|piece| match piece {
+            Piece::Keep(text) => text.as_str(),
+            Piece::Translate(text) => translations.next().map_or(text.as_str(), String::as_str),
+        })
+        .collect()
+}
+
+/// Swap inline markup for placeholders like [M0] the engines leave alone
+pub fn protect_markup(text: &str, pattern: &Regex) -> (String, Vec<String>) {
+    let mut markup = Vec::new();
+    let protected = pattern.replace_all(text, |caps: &regex::Captures| {
+        markup.push(caps[0].to_string());
+        format!("[M{}]", markup.len() - 1)
+    });
+    (protected.into_owned(), markup)
+}
+
+/// Put the markup back; engines sometimes add spaces inside the brackets
+pub fn restore_markup(text: &str, markup: &[String]) -> String {
+    if markup.is_empty() {
+        return text.to_string();
+    }
+    let pattern = Regex::new(r"\[\s*[Mm]\s*(\d+)\s*\]").unwrap();
+    pattern
+        .replace_all(text, |caps: &regex::Captures| {
+            caps[1]
+                .parse::<usize>()
+                .ok()
+                .and_then(|i| markup.get(i))
+                .cloned()
+                .unwrap_or_else(|| caps[0].to_string())
+        })
+        .into_owned()
+}
+
+/// Split `text` into pieces of at most `max_bytes`, at a line break, the end of a sentence
+/// or a space if there is one; the pieces put together give `text` again
+pub fn split_chunks(text: &str, max_bytes: usize) -> Vec<&str> {
+    let mut chunks = Vec::new();
+    let mut rest = text;
+    while rest.len() > max_bytes {
+        let mut limit = max_bytes;
+        while !rest.is_char_boundary(limit) {
+            limit -= 1;
+        }
+        let window = &rest[..limit];
+        let after = |i: usize| i + window[i..].chars().next().map_or(0, char::len_utf8);
+        let sentence_end = window
+            .char_indices()
+            .rev()
+            .find(|&(i, c)| match c {
+                '。' | '！' | '？' => true,
+                '.' | '!' | '?' => window[after(i)..].starts_with(char::is_whitespace),
+                _ => false,
+            })
+            .map(|(i, _)| after(i));
+        let cut = window
+            .rfind('\n')
+            .map(after)
+            .or(sentence_end)
+            .or_else(|| window.rfind(char::is_whitespace).map(after))
+            .filter(|&cut| cut > 0)
+            // One character over the limit is better than none at all
+            .unwrap_or_else(|| {
+                if limit > 0 {
+                    limit
+                } else {
+                    rest.chars().next().map_or(rest.len(), char::len_utf8)
+                }
+            });
+        chunks.push(&rest[..cut]);
+        rest = &rest[cut..];
+    }
+    if !rest.is_empty() {
+        chunks.push(rest);
+    }
+    chunks
+}
+
+/// Where the translation of `path` goes: movie.srt (or movie.en.srt, when English is the
+/// source) becomes movie.de.srt, or movie.de (2).srt if that exists already
+pub fn translated_path(path: &Path, source_lang: &str, target_lang: &str) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let stem = stem
+        .strip_suffix(&format!(".{}", source_lang))
+        .filter(|s| !s.is_empty())
+        .unwrap_or(&stem);
+    let extension = path
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+    let name = format!("{}.{}", stem, target_lang);
+    let mut candidate = path.with_file_name(format!("{}{}", name, extension));
+    let mut n = 2;
+    while candidate.exists() {
+        candidate = path.with_file_name(format!("{} ({}){}", name, n, extension));
+        n += 1;
+    }
+    candidate
+}
+
+/// Translate `texts` from `source_lang` to `target_lang` with the engine chosen in Settings, in
+/// chunks the engine accepts; `progress` is told how many texts are done after each one
+pub async fn translate_batch(
+    app: &AppHandle,
+    texts: &[String],
+    source_lang: &str,
+    target_lang: &str,
+    mut progress: impl FnMut(usize),
+) -> Result<Vec<String>, String> {
+    let engine = app
+        .state::<AppState>()
+        .settings
+        .lock()
+        .unwrap()
+        .translation_engine;
+    let max_bytes = match engine {
+        TranslationEngine::Online => ONLINE_CHUNK_BYTES,
+        TranslationEngine::Offline => OFFLINE_CHUNK_BYTES,
+    };
+    let mut translated = Vec::with_capacity(texts.len());
+    for (i, text) in texts.iter().enumerate() {
+        let mut result = String::new();
+        for chunk in split_chunks(text, max_bytes) {
+            let core = chunk.trim();
+            let start = chunk.len() - chunk.trim_start().len();
+            result.push_str(&chunk[..start]);
+            if core.chars().any(char::is_alphabetic) {
+                result.push_str(
+                    &crate::translate_with_engine(app, core, source_lang, target_lang).await?,
+                );
+            } else {
+                result.push_str(core);
+            }
+            result.push_str(&chunk[start + core.len()..]);
+        }
+        translated.push(result);
+        progress(i + 1);
+    }
+    Ok(translated)
+}
+
+/// Translate a .txt, .srt, .vtt or .md file into `target_lang`, writing a copy next to it;
+/// returns the copy's path. Progress is sent as "file-translation-progress" events.
+#[tauri::command]
+pub async fn translate_file(
+    app: AppHandle,
+    path: String,
+    target_lang: String,
+) -> Result<String, String> {
+    let input = PathBuf::from(&path);
+    let format = FileFormat::from_path(&input)
+        .ok_or("Only .txt, .srt, .vtt and .md files can be translated")?;
+    let bytes = fs::read(&input).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let content = String::from_utf8(bytes).map_err(|_| "The file isn't UTF-8 text".to_string())?;
+    let (bom, content) = match content.strip_prefix('\u{feff}') {
+        Some(rest) => ("\u{feff}", rest),
+        None => ("", content.as_str()),
+    };
+    let crlf = content.contains("\r\n");
+
+    let pieces = split_document(content, format);
+    let pattern = format.markup();
+    let protected: Vec<(String, Vec<String>)> = pieces
+        .iter()
+        .filter_map(|piece| match piece {
+            Piece::Translate(text) => Some(protect_markup(&text.replace('\r', ""), &pattern)),
+            Piece::Keep(_) => None,
+        })
+        .collect();
+    if protected.is_empty() {
+        return Err("There's no text to translate in this file".to_string());
+    }
+
+    let sample: String = protected
+        .iter()
+        .map(|(text, _)| text.as_str())
+        .collect::<Vec<_>>()
+        .join("\n")
+        .chars()
+        .take(DETECT_SAMPLE_CHARS)
+        .collect();
+    let source_lang = whatlang::detect_lang(&sample)
+        .and_then(crate::lang_to_code)
+        .unwrap_or("en");
+    let target_lang = target_lang.trim().to_lowercase();
+    if source_lang == target_lang {
+        return Err(format!(
+            "The file is already in {}",
+            crate::get_language_name(&target_lang)
+        ));
+    }
+    let output = translated_path(&input, source_lang, &target_lang);
+    let output = crate::path_policy::validate_write_path(&app, &output.to_string_lossy())?;
+
+    let texts: Vec<String> = protected.iter().map(|(text, _)| text.clone()).collect();
+    let total = texts.len();
+    let emit_progress = |done: usize| {
+        let _ = app.emit(
+            "file-translation-progress",
+            FileTranslationProgress {
+                path: path.clone(),
+                done,
+                total,
+            },
+        );
+    };
+    emit_progress(0);
+    let translated = translate_batch(&app, &texts, source_lang, &target_lang, emit_progress)
+        .await?
+        .into_iter()
+        .zip(&protected)
+        .map(|(text, (_, markup))| {
+            let text = restore_markup(&text, markup);
+            if crlf {
+                text.replace("\r\n", "\n").replace('\n', "\r\n")
+            } else {
+                text
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let document = format!("{}{}", bom, join_document(&pieces, &translated));
+    fs::write(&output, document).map_err(|e| format!("Failed to write translation: {}", e))?;
+    log::info!("Translated {} to {}", path, output.display());
+    Ok(output.to_string_lossy().to_string())
+}
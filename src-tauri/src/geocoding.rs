@@ -0,0 +1,166 @@
+// Place names to coordinates, for the location-based tools (sun times, night light)
+// Coordinates can be typed directly ("52.52, 13.40", "33.9S 151.2E"); anything else is looked up
+// with the free Open-Meteo geocoder and remembered in `geocode_cache.json`, together with the
+// last location used, so repeated lookups work offline.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+const CACHE_FILE: &str = "geocode_cache.json";
+const GEOCODING_API: &str = "https://geocoding-api.open-meteo.com/v1/search";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Location {
+    pub name: String, // "Berlin, Germany", or the coordinates as typed
+    pub latitude: f64,
+    pub longitude: f64,
+    pub timezone: Option<String>, // IANA name, when the geocoder knows it
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct GeocodeCache {
+    last: Option<Location>,
+    places: HashMap<String, Location>, // by lowercased query
+}
+
+// One hemisphere-suffixed or signed number: "52.5", "-33.9", "33.9S"
+fn coordinate(text: &str, positive: char, negative: char) -> Option<f64> {
+    let text = text.trim().trim_end_matches('°');
+    let upper = text.to_uppercase();
+    let (number, sign) = if let Some(n) = upper.strip_suffix(positive) {
+        (n.trim_end().trim_end_matches('°').to_string(), 1.0)
+    } else if let Some(n) = upper.strip_suffix(negative) {
+        (n.trim_end().trim_end_matches('°').to_string(), -1.0)
+    } else {
+        (upper, 1.0)
+    };
+    number.parse::<f64>().ok().map(|n| n * sign)
+}
+
+/// "lat, lon" or "lat lon" in decimal degrees, optionally with N/S and E/W
+pub fn parse_coordinates(text: &str) -> Option<(f64, f64)> {
+    let text = text.trim();
+    let (lat, lon) = text
+        .split_once(',')
+        .or_else(|| text.split_once(char::is_whitespace))?;
+    let latitude = coordinate(lat, 'N', 'S')?;
+    let longitude = coordinate(lon, 'E', 'W')?;
+    ((-90.0..=90.0).contains(&latitude) && (-180.0..=180.0).contains(&longitude))
+        .then_some((latitude, longitude))
+}
+
+#[derive(Deserialize)]
+struct GeocodingResponse {
+    #[serde(default)]
+    results: Vec<GeocodingResult>,
+}
+
+#[derive(Deserialize)]
+struct GeocodingResult {
+    name: String,
+    latitude: f64,
+    longitude: f64,
+    country: Option<String>,
+    timezone: Option<String>,
+}
+
+async fn geocode(query: &str) -> Result<Location, String> {
+    // "Paris, FR" / "Paris, France": narrow the search to the country
+    let (name, country) = match query.rsplit_once(',') {
+        Some((name, country)) => match crate::countries::find(country) {
+            Ok(country) => (name.trim(), Some(country.code)),
+            Err(_) => (query, None),
+        },
+        None => (query, None),
+    };
+    let mut url = format!(
+        "{}?name={}&count=1&language=en&format=json",
+        GEOCODING_API,
+        urlencoding::encode(name)
+    );
+    if let Some(code) = country {
+        url.push_str(&format!("&countryCode={}", code));
+    }
+
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to look up \"{}\": {}", query, e))?;
+    if !response.status().is_success() {
+        return Err(format!("Geocoding error: {}", response.status()));
+    }
+    let data: GeocodingResponse = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse geocoding response: {}", e))?;
+    let place = data
+        .results
+        .into_iter()
+        .next()
+        .ok_or_else(|| format!("Couldn't find \"{}\"", query))?;
+    Ok(Location {
+        name: match place.country {
+            Some(country) => format!("{}, {}", place.name, country),
+            None => place.name,
+        },
+        latitude: place.latitude,
+        longitude: place.longitude,
+        timezone: place.timezone,
+    })
+}
+
+// Coordinates as typed, or the place from the cache or the geocoder (then added to the cache)
+async fn locate(cache: &mut GeocodeCache, query: &str) -> Result<Location, String> {
+    if let Some((latitude, longitude)) = parse_coordinates(query) {
+        return Ok(Location {
+            name: format!("{:.4}, {:.4}", latitude, longitude),
+            latitude,
+            longitude,
+            timezone: None,
+        });
+    }
+    if let Some(location) = cache.places.get(&query.to_lowercase()) {
+        return Ok(location.clone());
+    }
+    let location = geocode(query).await?;
+    cache.places.insert(query.to_lowercase(), location.clone());
+    Ok(location)
+}
+
+/// Resolve `query` (coordinates or a place name) to a location; without a query, the last
+/// location used
+pub async fn resolve(app: &AppHandle, query: Option<&str>) -> Result<Location, String> {
+    let mut cache: GeocodeCache = crate::load_json_file(app, CACHE_FILE);
+    let known_places = cache.places.len();
+    let location = match query.map(str::trim).filter(|q| !q.is_empty()) {
+        None => cache.last.clone().ok_or_else(|| {
+            "Name a place or coordinates, e.g. Berlin or 52.52, 13.40".to_string()
+        })?,
+        Some(query) => locate(&mut cache, query).await?,
+    };
+
+    if cache.last.as_ref() != Some(&location) || cache.places.len() != known_places {
+        cache.last = Some(location.clone());
+        crate::save_json_file(app, CACHE_FILE, &cache)?;
+    }
+    Ok(location)
+}
+
+/// Resolve `query` like `resolve`, but without making it the last location used (for places
+/// configured in settings)
+pub async fn lookup(app: &AppHandle, query: &str) -> Result<Location, String> {
+    let mut cache: GeocodeCache = crate::load_json_file(app, CACHE_FILE);
+    let known_places = cache.places.len();
+    let location = locate(&mut cache, query.trim()).await?;
+    if cache.places.len() != known_places {
+        crate::save_json_file(app, CACHE_FILE, &cache)?;
+    }
+    Ok(location)
+}
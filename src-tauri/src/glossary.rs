@@ -0,0 +1,251 @@
+// Translation glossary: terms with the translation to always use for them, per language pair,
+// so product names and technical terms come out the same every time
+// Before the text goes to the translation engine, glossary terms (whole words, any case) are
+// swapped for placeholders like [G0], which engines leave alone; afterwards the placeholders
+// are replaced with the preferred translations. An entry without a source language applies to
+// text in any language; one for the detected language wins over it.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+const GLOSSARY_FILE: &str = "glossary.json";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GlossaryEntry {
+    pub id: u64,
+    #[serde(default)]
+    pub source_lang: String, // language code; empty for any language
+    pub target_lang: String,
+    pub term: String,
+    pub translation: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Glossary {
+    entries: Vec<GlossaryEntry>,
+}
+
+/// Text with glossary terms swapped for placeholders, and what each placeholder becomes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Protected {
+    pub text: String,
+    pub replacements: Vec<String>, // placeholder [G<i>] becomes replacements[i]
+}
+
+/// The entries that apply when translating from `source_lang` to `target_lang`, one per term
+pub fn entries_for<'a>(
+    entries: &'a [GlossaryEntry],
+    source_lang: &str,
+    target_lang: &str,
+) -> Vec<&'a GlossaryEntry> {
+    let mut matching: Vec<&GlossaryEntry> = entries
+        .iter()
+        .filter(|e| e.target_lang.eq_ignore_ascii_case(target_lang))
+        .filter(|e| e.source_lang.is_empty() || e.source_lang.eq_ignore_ascii_case(source_lang))
+        .collect();
+    // Entries for the exact language pair first, so they win over "any language" ones
+    matching.sort_by_key(|e| e.source_lang.is_empty());
+    let mut seen = Vec::new();
+    matching.retain(|e| {
+        let term = e.term.to_lowercase();
+        let new = !seen.contains(&term);
+        seen.push(term);
+        new
+    });
+    matching
+}
+
+fn placeholder(index: usize) -> String {
+    format!("[G{}]", index)
+}
+
+/// Swap the glossary terms in `text` for placeholders; longer terms win over ones they contain
+pub fn protect(text: &str, entries: &[&GlossaryEntry]) -> Protected {
+    let mut terms: Vec<&GlossaryEntry> = entries
+        .iter()
+        .copied()
+        .filter(|e| !e.term.trim().is_empty())
+        .collect();
+    if terms.is_empty() {
+        return Protected {
+            text: text.to_string(),
+            replacements: Vec::new(),
+        };
+    }
+    terms.sort_by_key(|e| std::cmp::Reverse(e.term.chars().count()));
+    let alternatives: Vec<String> = terms
+        .iter()
+        .map(|e| {
+            // Only ends that are word characters need a word boundary
+            let term = e.term.trim();
+            let edge = |c: Option<char>| match c {
+                Some(c) if c.is_alphanumeric() || c == '_' => r"\b",
+                _ => "",
+            };
+            format!(
+                "({}{}{})",
+                edge(term.chars().next()),
+                regex::escape(term),
+                edge(term.chars().last())
+            )
+        })
+        .collect();
+    let Ok(pattern) = Regex::new(&format!("(?i){}", alternatives.join("|"))) else {
+        return Protected {
+            text: text.to_string(),
+            replacements: Vec::new(),
+        };
+    };
+
+    let mut replacements = Vec::new();
+    let protected = pattern.replace_all(text, |caps: &regex::Captures| {
+        let index = (1..caps.len())
+            .find(|&i| caps.get(i).is_some())
+            .unwrap_or(1)
+            - 1;
+        replacements.push(terms[index].translation.clone());
+        placeholder(replacements.len() - 1)
+    });
+    Protected {
+        text: protected.into_owned(),
+        replacements,
+    }
+}
+
+/// Put the preferred translations in place of the placeholders in the engine's output;
+/// engines sometimes add spaces inside the brackets, which is tolerated
+pub fn restore(translated: &str, replacements: &[String]) -> String {
+    if replacements.is_empty() {
+        return translated.to_string();
+    }
+    let pattern = Regex::new(r"\[\s*[Gg]\s*(\d+)\s*\]").unwrap();
+    let mut used = vec![false; replacements.len()];
+    let restored = pattern.replace_all(translated, |caps: &regex::Captures| {
+        match caps[1]
+            .parse::<usize>()
+            .ok()
+            .filter(|&i| i < replacements.len())
+        {
+            Some(i) => {
+                used[i] = true;
+                replacements[i].clone()
+            }
+            None => caps[0].to_string(),
+        }
+    });
+    let missing = used.iter().filter(|used| !**used).count();
+    if missing > 0 {
+        log::warn!(
+            "Translation engine dropped {} glossary placeholders",
+            missing
+        );
+    }
+    restored.into_owned()
+}
+
+fn load(app: &AppHandle) -> Glossary {
+    crate::load_json_file(app, GLOSSARY_FILE)
+}
+
+/// The glossary entries for a translation from `source_lang` to `target_lang`
+pub fn load_for(app: &AppHandle, source_lang: &str, target_lang: &str) -> Vec<GlossaryEntry> {
+    let glossary = load(app);
+    entries_for(&glossary.entries, source_lang, target_lang)
+        .into_iter()
+        .cloned()
+        .collect()
+}
+
+fn language_code(code: &str) -> Result<String, String> {
+    let code = code.trim().to_lowercase();
+    if !(2..=3).contains(&code.len()) || !code.chars().all(|c| c.is_ascii_lowercase()) {
+        return Err(format!(
+            "\"{}\" isn't a language code (e.g. en, de, ja)",
+            code
+        ));
+    }
+    Ok(code)
+}
+
+/// All entries, or only those for translations into `target_lang`
+#[tauri::command]
+pub fn get_glossary(app: AppHandle, target_lang: Option<String>) -> Vec<GlossaryEntry> {
+    load(&app)
+        .entries
+        .into_iter()
+        .filter(|e| {
+            target_lang
+                .as_deref()
+                .map_or(true, |t| e.target_lang.eq_ignore_ascii_case(t.trim()))
+        })
+        .collect()
+}
+
+/// Add an entry, or change the translation of an existing one for the same term and languages
+#[tauri::command]
+pub fn save_glossary_entry(
+    app: AppHandle,
+    source_lang: Option<String>,
+    target_lang: String,
+    term: String,
+    translation: String,
+) -> Result<GlossaryEntry, String> {
+    let source_lang = match source_lang.as_deref().map(str::trim) {
+        None | Some("") | Some("*") => String::new(),
+        Some(code) => language_code(code)?,
+    };
+    let target_lang = language_code(&target_lang)?;
+    let (term, translation) = (term.trim().to_string(), translation.trim().to_string());
+    if term.is_empty() || translation.is_empty() {
+        return Err("Give both a term and its translation".to_string());
+    }
+
+    let mut glossary = load(&app);
+    let existing = glossary.entries.iter_mut().find(|e| {
+        e.source_lang == source_lang
+            && e.target_lang == target_lang
+            && e.term.to_lowercase() == term.to_lowercase()
+    });
+    let entry = match existing {
+        Some(entry) => {
+            entry.term = term;
+            entry.translation = translation;
+            entry.clone()
+        }
+        None => {
+            let entry = GlossaryEntry {
+                id: glossary.entries.iter().map(|e| e.id).max().unwrap_or(0) + 1,
+                source_lang,
+                target_lang,
+                term,
+                translation,
+            };
+            glossary.entries.push(entry.clone());
+            entry
+        }
+    };
+    crate::save_json_file(&app, GLOSSARY_FILE, &glossary)?;
+    Ok(entry)
+}
+
+/// Delete the entry with `id`, or every entry for `term` (any languages)
+#[tauri::command]
+pub fn delete_glossary_entry(
+    app: AppHandle,
+    id: Option<u64>,
+    term: Option<String>,
+) -> Result<usize, String> {
+    let mut glossary = load(&app);
+    let count = glossary.entries.len();
+    let term = term.map(|t| t.trim().to_lowercase());
+    glossary
+        .entries
+        .retain(|e| Some(e.id) != id && term.as_deref() != Some(e.term.to_lowercase().as_str()));
+    let removed = count - glossary.entries.len();
+    if removed == 0 {
+        return Err("No such glossary entry".to_string());
+    }
+    crate::save_json_file(&app, GLOSSARY_FILE, &glossary)?;
+    Ok(removed)
+}
@@ -0,0 +1,338 @@
+// Habit tracker
+// Habits are checked off at most once a day; the check-ins are kept per habit as local dates
+// in habits.json. A streak counts consecutive days up to today, or up to yesterday while
+// today's check-in is still open. Habits can have a daily reminder time: if the habit hasn't
+// been checked off by then, a notification is shown (once a day).
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::{Days, Local, NaiveDate, NaiveTime};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::notifications::{self, NotifyAction};
+
+const HABITS_FILE: &str = "habits.json";
+const REMINDER_POLL_INTERVAL: Duration = Duration::from_secs(30);
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Habit {
+    pub id: String, // derived from the name when created, then stable
+    pub name: String,
+    pub reminder: Option<String>, // local time, HH:MM
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HabitStore {
+    pub habits: Vec<Habit>,
+    pub check_ins: BTreeMap<String, BTreeSet<NaiveDate>>, // by habit id
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HabitStreak {
+    pub id: String,
+    pub name: String,
+    pub reminder: Option<String>,
+    pub done_today: bool,
+    pub current: u32, // days
+    pub longest: u32,
+    pub total: u32, // check-ins ever
+}
+
+/// "Drink water" -> "drink-water"
+pub fn habit_id(name: &str) -> String {
+    name.split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Find a habit by id or name, or by a word of its name when only one habit matches
+pub fn find_habit<'a>(store: &'a HabitStore, query: &str) -> Result<&'a Habit, String> {
+    let query = query.trim();
+    let id = habit_id(query);
+    if let Some(habit) = store
+        .habits
+        .iter()
+        .find(|h| h.id == id || h.name.eq_ignore_ascii_case(query))
+    {
+        return Ok(habit);
+    }
+    let matches: Vec<&Habit> = store
+        .habits
+        .iter()
+        .filter(|h| !id.is_empty() && h.id.contains(&id))
+        .collect();
+    match matches.as_slice() {
+        [habit] => Ok(habit),
+        [] => Err(format!("No habit called \"{}\"", query)),
+        _ => Err(format!(
+            "\"{}\" matches {}",
+            query,
+            matches
+                .iter()
+                .map(|h| h.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
+
+/// Parse a reminder time such as "9:00", "21:30" or "7pm"
+pub fn parse_reminder(input: &str) -> Result<String, String> {
+    let text = input.trim().to_lowercase().replace(' ', "");
+    let invalid = || format!("Invalid reminder time: {}", input.trim());
+    let (clock, pm) = if let Some(clock) = text.strip_suffix("pm") {
+        (clock, Some(true))
+    } else if let Some(clock) = text.strip_suffix("am") {
+        (clock, Some(false))
+    } else {
+        (text.as_str(), None)
+    };
+    let (hours, minutes) = clock.split_once(':').unwrap_or((clock, "0"));
+    let hours: u32 = hours.parse().map_err(|_| invalid())?;
+    let minutes: u32 = minutes.parse().map_err(|_| invalid())?;
+    let hours = match pm {
+        Some(_) if !(1..=12).contains(&hours) => return Err(invalid()),
+        Some(pm) => hours % 12 + if pm { 12 } else { 0 },
+        None => hours,
+    };
+    NaiveTime::from_hms_opt(hours, minutes, 0)
+        .map(|t| t.format("%H:%M").to_string())
+        .ok_or_else(invalid)
+}
+
+fn streak_for(habit: &Habit, dates: Option<&BTreeSet<NaiveDate>>, today: NaiveDate) -> HabitStreak {
+    let empty = BTreeSet::new();
+    let dates = dates.unwrap_or(&empty);
+    let done_today = dates.contains(&today);
+
+    // Today doesn't break the streak until it's over
+    let mut day = if done_today {
+        today
+    } else {
+        today - Days::new(1)
+    };
+    let mut current = 0;
+    while dates.contains(&day) {
+        current += 1;
+        day = day - Days::new(1);
+    }
+
+    let mut longest = 0;
+    let mut run = 0;
+    let mut previous: Option<NaiveDate> = None;
+    for &date in dates {
+        run = match previous {
+            Some(p) if p + Days::new(1) == date => run + 1,
+            _ => 1,
+        };
+        longest = longest.max(run);
+        previous = Some(date);
+    }
+
+    HabitStreak {
+        id: habit.id.clone(),
+        name: habit.name.clone(),
+        reminder: habit.reminder.clone(),
+        done_today,
+        current,
+        longest,
+        total: dates.len() as u32,
+    }
+}
+
+/// Streaks of every habit, in the order they were added
+pub fn streaks(store: &HabitStore, today: NaiveDate) -> Vec<HabitStreak> {
+    store
+        .habits
+        .iter()
+        .map(|h| streak_for(h, store.check_ins.get(&h.id), today))
+        .collect()
+}
+
+fn load(app: &AppHandle) -> HabitStore {
+    crate::load_json_file(app, HABITS_FILE)
+}
+
+fn save(app: &AppHandle, store: &HabitStore) -> Result<(), String> {
+    crate::save_json_file(app, HABITS_FILE, store)?;
+    let _ = app.emit("habits-changed", streaks(store, today()));
+    Ok(())
+}
+
+fn today() -> NaiveDate {
+    Local::now().date_naive()
+}
+
+fn parse_date(date: Option<String>) -> Result<NaiveDate, String> {
+    match date {
+        Some(date) => NaiveDate::parse_from_str(date.trim(), DATE_FORMAT)
+            .map_err(|_| format!("Invalid date: {}", date.trim())),
+        None => Ok(today()),
+    }
+}
+
+// Habits already reminded about, and on which day
+static REMINDED: Mutex<Option<HashMap<String, NaiveDate>>> = Mutex::new(None);
+
+fn send_due_reminders(app: &AppHandle) {
+    let now = Local::now();
+    let (today, time) = (now.date_naive(), now.time().format("%H:%M").to_string());
+    let store = load(app);
+    let mut reminded = REMINDED.lock().unwrap();
+    let reminded = reminded.get_or_insert_with(HashMap::new);
+
+    for habit in &store.habits {
+        let Some(reminder) = &habit.reminder else {
+            continue;
+        };
+        let done = store
+            .check_ins
+            .get(&habit.id)
+            .is_some_and(|dates| dates.contains(&today));
+        // HH:MM strings compare in time order
+        if done || *reminder > time || reminded.get(&habit.id) == Some(&today) {
+            continue;
+        }
+        reminded.insert(habit.id.clone(), today);
+        notifications::notify(
+            app,
+            &habit.name,
+            &format!(
+                "Not done yet today. Type \"habit done {}\" when it is.",
+                habit.id
+            ),
+            NotifyAction::ShowWindow,
+        );
+    }
+}
+
+/// Start the reminder loop
+pub fn init(app: &AppHandle) {
+    let app = app.clone();
+    std::thread::spawn(move || loop {
+        std::thread::sleep(REMINDER_POLL_INTERVAL);
+        send_due_reminders(&app);
+    });
+}
+
+#[tauri::command]
+pub fn get_habits(app: AppHandle) -> Vec<Habit> {
+    load(&app).habits
+}
+
+#[tauri::command]
+pub fn add_habit(app: AppHandle, name: String, reminder: Option<String>) -> Result<Habit, String> {
+    let name = name.trim().to_string();
+    let id = habit_id(&name);
+    if id.is_empty() {
+        return Err("Give the habit a name".to_string());
+    }
+    let mut store = load(&app);
+    if store.habits.iter().any(|h| h.id == id) {
+        return Err(format!("There's already a habit called \"{}\"", name));
+    }
+    let habit = Habit {
+        id,
+        name,
+        reminder: reminder
+            .filter(|r| !r.trim().is_empty())
+            .map(|r| parse_reminder(&r))
+            .transpose()?,
+    };
+    store.habits.push(habit.clone());
+    save(&app, &store)?;
+    Ok(habit)
+}
+
+/// Rename a habit and set or clear its reminder; check-ins are kept
+#[tauri::command]
+pub fn update_habit(
+    app: AppHandle,
+    id: String,
+    name: String,
+    reminder: Option<String>,
+) -> Result<Habit, String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Give the habit a name".to_string());
+    }
+    let reminder = reminder
+        .filter(|r| !r.trim().is_empty())
+        .map(|r| parse_reminder(&r))
+        .transpose()?;
+    let mut store = load(&app);
+    let habit = store
+        .habits
+        .iter_mut()
+        .find(|h| h.id == id)
+        .ok_or_else(|| format!("No habit called \"{}\"", id))?;
+    habit.name = name;
+    habit.reminder = reminder;
+    let habit = habit.clone();
+    save(&app, &store)?;
+    Ok(habit)
+}
+
+/// Delete a habit and its check-ins
+#[tauri::command]
+pub fn delete_habit(app: AppHandle, habit: String) -> Result<(), String> {
+    let mut store = load(&app);
+    let id = find_habit(&store, &habit)?.id.clone();
+    store.habits.retain(|h| h.id != id);
+    store.check_ins.remove(&id);
+    save(&app, &store)
+}
+
+/// Check off `habit` (id, name or a word of it) for `date` (YYYY-MM-DD, default today)
+#[tauri::command]
+pub fn check_in_habit(
+    app: AppHandle,
+    habit: String,
+    date: Option<String>,
+) -> Result<HabitStreak, String> {
+    let date = parse_date(date)?;
+    if date > today() {
+        return Err("Can't check in for a day that hasn't happened yet".to_string());
+    }
+    let mut store = load(&app);
+    let habit = find_habit(&store, &habit)?.clone();
+    store
+        .check_ins
+        .entry(habit.id.clone())
+        .or_default()
+        .insert(date);
+    save(&app, &store)?;
+    Ok(streak_for(&habit, store.check_ins.get(&habit.id), today()))
+}
+
+/// Take back a check-in
+#[tauri::command]
+pub fn undo_habit_check_in(
+    app: AppHandle,
+    habit: String,
+    date: Option<String>,
+) -> Result<HabitStreak, String> {
+    let date = parse_date(date)?;
+    let mut store = load(&app);
+    let habit = find_habit(&store, &habit)?.clone();
+    let removed = store
+        .check_ins
+        .get_mut(&habit.id)
+        .is_some_and(|dates| dates.remove(&date));
+    if !removed {
+        return Err(format!("\"{}\" wasn't checked off that day", habit.name));
+    }
+    save(&app, &store)?;
+    Ok(streak_for(&habit, store.check_ins.get(&habit.id), today()))
+}
+
+#[tauri::command]
+pub fn get_streaks(app: AppHandle) -> Vec<HabitStreak> {
+    streaks(&load(&app), today())
+}
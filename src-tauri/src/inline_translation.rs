@@ -0,0 +1,70 @@
+// Translate-and-replace: in this mode the quick translation hotkey doesn't open the translation
+// window. It copies the selected text, translates it into the quick translation language and
+// types the translation back into the focused app, over the selection. That gives inline
+// translation in any editor. The clipboard is left as it was; if typing isn't possible (e.g.
+// on Wayland), the translation is copied instead so it can be pasted.
+
+use std::time::Duration;
+
+use tauri::{AppHandle, Manager};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+use crate::notifications::{self, NotifyAction};
+use crate::{platform, AppState};
+
+/// Translate the selection in place; returns the translation
+async fn translate_selection(app: &AppHandle) -> Result<String, String> {
+    let target_lang = app
+        .state::<AppState>()
+        .settings
+        .lock()
+        .unwrap()
+        .quick_translation_target_language
+        .clone();
+
+    // Typing goes to the focused window, which mustn't be ours
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.hide();
+    }
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    // Emptied first, so nothing selected isn't mistaken for the old clipboard text
+    let clipboard = app.clipboard();
+    let previous = clipboard.read_text().ok();
+    let _ = clipboard.write_text(String::new());
+    let copied = platform::copy_selection_impl(app).await;
+    let selected = clipboard.read_text().unwrap_or_default();
+    if let Some(previous) = previous {
+        let _ = clipboard.write_text(previous);
+    }
+    copied?;
+    if selected.trim().is_empty() {
+        return Err("No text selected".to_string());
+    }
+
+    let translated = crate::translate_text(app.clone(), selected, target_lang)
+        .await?
+        .translated_text;
+    let text = translated.clone();
+    let typed = tauri::async_runtime::spawn_blocking(move || platform::type_text_impl(&text))
+        .await
+        .map_err(|e| format!("Failed to type: {}", e))?;
+    if let Err(e) = typed {
+        clipboard
+            .write_text(translated)
+            .map_err(|e| format!("Failed to write clipboard: {}", e))?;
+        return Err(format!("{}; the translation was copied instead", e));
+    }
+    Ok(translated)
+}
+
+/// Run the quick translation hotkey in replace mode; failures show as a notification
+pub fn trigger(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        match translate_selection(&app).await {
+            Ok(translated) => log::info!("Replaced selection with {} chars", translated.len()),
+            Err(e) if e == "Cancelled" => {}
+            Err(e) => notifications::notify(&app, "Translation failed", &e, NotifyAction::None),
+        }
+    });
+}
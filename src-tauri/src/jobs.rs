@@ -0,0 +1,702 @@
+// Job manager for long-running commands
+// Every download/conversion registers a job and receives a cancellation token. The token is
+// checked in stream loops and kills any attached child process (ffmpeg, yt-dlp) when cancelled.
+// Conversions can also be paused, which suspends their ffmpeg until resumed.
+// Heavy jobs (conversions, transcriptions) also follow the battery policy in power.rs:
+// on battery they may start and run throttled, or wait for AC power, unless told to run anyway.
+// Each category of job (conversions, downloads, scans) runs at most as many at once as the
+// user's `JobLimits` allow; `start_job` waits its turn in order, reporting the job's place in
+// the queue with `job-queue-progress` events.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::path::Path;
+use std::sync::{Arc, Condvar, Mutex, OnceLock};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::power::{self, JobPower};
+use crate::transfers::TransferMeter;
+use crate::workspaces::{self, Workspace};
+use crate::AppState;
+
+pub const MAX_JOB_LIMIT: usize = 8;
+
+// Waiting jobs also look again this often, in case a wakeup was missed
+const QUEUE_RECHECK: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobKind {
+    GitDownload,
+    YoutubeDownload,
+    Conversion,
+    Transcription,
+    NetworkScan,
+}
+
+/// What a job's limit is counted by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobCategory {
+    Conversion,
+    Download,
+    Scan,
+}
+
+impl JobKind {
+    // The tool whose transfers the job's downloads count towards
+    fn transfer_tool(self) -> Option<&'static str> {
+        match self {
+            JobKind::GitDownload => Some("git-downloader"),
+            JobKind::YoutubeDownload => Some("youtube-downloader"),
+            JobKind::Conversion | JobKind::Transcription | JobKind::NetworkScan => None,
+        }
+    }
+
+    pub fn category(self) -> JobCategory {
+        match self {
+            JobKind::GitDownload | JobKind::YoutubeDownload => JobCategory::Download,
+            // Transcribing keeps the CPU as busy as encoding
+            JobKind::Conversion | JobKind::Transcription => JobCategory::Conversion,
+            JobKind::NetworkScan => JobCategory::Scan,
+        }
+    }
+
+    /// Whether the battery policy applies to the job
+    pub fn is_heavy(self) -> bool {
+        self.category() == JobCategory::Conversion
+    }
+}
+
+/// How many jobs of each category run at once (1 to 8); the rest wait their turn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JobLimits {
+    #[serde(default = "default_conversions")]
+    pub conversions: usize,
+    #[serde(default = "default_downloads")]
+    pub downloads: usize,
+    #[serde(default = "default_scans")]
+    pub scans: usize,
+}
+
+fn default_conversions() -> usize {
+    2
+}
+
+fn default_downloads() -> usize {
+    3
+}
+
+fn default_scans() -> usize {
+    1
+}
+
+impl Default for JobLimits {
+    fn default() -> Self {
+        Self {
+            conversions: default_conversions(),
+            downloads: default_downloads(),
+            scans: default_scans(),
+        }
+    }
+}
+
+impl JobLimits {
+    pub fn limit(&self, category: JobCategory) -> usize {
+        let limit = match category {
+            JobCategory::Conversion => self.conversions,
+            JobCategory::Download => self.downloads,
+            JobCategory::Scan => self.scans,
+        };
+        limit.clamp(1, MAX_JOB_LIMIT)
+    }
+}
+
+/// Payload of `job-queue-progress`: a waiting job's place in line, None once it has started
+#[derive(Debug, Clone, Serialize)]
+pub struct QueueProgress {
+    pub id: u64,
+    pub kind: JobKind,
+    pub position: Option<usize>,
+    pub waiting_for_power: bool, // held back by the battery policy rather than the limit
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobInfo {
+    pub id: u64,
+    pub kind: JobKind,
+    pub label: String,
+    pub started_at: u64,      // timestamp in seconds
+    pub progress: Option<u8>, // percent, once the job has reported any
+    pub paused: bool,
+    pub queue_position: Option<usize>, // 1-based while waiting for a free slot
+    pub power: JobPower,               // what the battery policy does to it now
+    pub run_on_battery: bool,          // the user told it to ignore the battery policy
+}
+
+#[derive(Default)]
+struct TokenInner {
+    cancelled: AtomicBool,
+    paused: AtomicBool,    // by the user
+    held: AtomicBool,      // by the battery policy, until AC power
+    throttled: AtomicBool, // by the battery policy, resting every other slice
+    resting: AtomicBool,   // the current slice of a throttled job is a rest
+    suspended: AtomicBool, // whether the children are suspended now
+    child_pids: Mutex<Vec<u32>>,
+}
+
+impl TokenInner {
+    fn wants_suspended(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+            || self.held.load(Ordering::SeqCst)
+            || (self.throttled.load(Ordering::SeqCst) && self.resting.load(Ordering::SeqCst))
+    }
+}
+
+/// Shared cancellation flag for a job. Cancelling also kills attached child processes.
+#[derive(Clone, Default)]
+pub struct CancellationToken {
+    inner: Arc<TokenInner>,
+}
+
+impl CancellationToken {
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::SeqCst)
+    }
+
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::SeqCst);
+        let pids = std::mem::take(&mut *self.inner.child_pids.lock().unwrap());
+        for pid in pids {
+            kill_process(pid);
+        }
+    }
+
+    /// Track a spawned child so cancelling the job kills it. A child of a job that is paused
+    /// or held is suspended right away.
+    pub fn attach_child(&self, pid: u32) {
+        let mut pids = self.inner.child_pids.lock().unwrap();
+        if self.is_cancelled() {
+            drop(pids);
+            kill_process(pid);
+            return;
+        }
+        pids.push(pid);
+        if self.inner.suspended.load(Ordering::SeqCst) {
+            if let Err(e) = crate::platform::suspend_process_impl(pid, true) {
+                log::warn!("Failed to suspend process {}: {}", pid, e);
+            }
+        }
+    }
+
+    /// Stop tracking a child once it has exited
+    pub fn detach_child(&self, pid: u32) {
+        self.inner.child_pids.lock().unwrap().retain(|p| *p != pid);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.inner.paused.load(Ordering::SeqCst)
+    }
+
+    /// Suspend the attached children where they are, or let them carry on. A paused job can
+    /// still be cancelled; killing works on a suspended process.
+    pub fn set_paused(&self, paused: bool) -> Result<(), String> {
+        let pids = self.inner.child_pids.lock().unwrap();
+        if self.is_cancelled() || pids.is_empty() {
+            return Err("Nothing is running to pause".to_string());
+        }
+        let was_paused = self.inner.paused.swap(paused, Ordering::SeqCst);
+        let synced = self.sync_children(&pids);
+        if synced.is_err() {
+            self.inner.paused.store(was_paused, Ordering::SeqCst);
+        }
+        synced
+    }
+
+    /// Hold the job until AC power (see power.rs). Like a pause, but lifted separately, so a job
+    /// the user paused stays paused when the power comes back.
+    pub fn set_held(&self, held: bool) {
+        self.inner.held.store(held, Ordering::SeqCst);
+        self.sync_logged();
+    }
+
+    /// Throttle the job: its children are suspended during every resting slice
+    pub fn set_throttled(&self, throttled: bool) {
+        self.inner.throttled.store(throttled, Ordering::SeqCst);
+        self.sync_logged();
+    }
+
+    /// Start a resting or a working slice of a throttled job
+    pub fn set_resting(&self, resting: bool) {
+        self.inner.resting.store(resting, Ordering::SeqCst);
+        self.sync_logged();
+    }
+
+    /// Whether the job should be standing still now: paused, held, or resting while throttled
+    #[cfg_attr(not(feature = "test-harness"), allow(dead_code))] // children are stopped directly
+    pub fn is_suspended(&self) -> bool {
+        self.inner.wants_suspended()
+    }
+
+    // Suspend or resume the children when what the flags want differs from what they are
+    fn sync_children(&self, pids: &[u32]) -> Result<(), String> {
+        let suspend = self.inner.wants_suspended();
+        if suspend == self.inner.suspended.load(Ordering::SeqCst) {
+            return Ok(());
+        }
+        for pid in pids {
+            crate::platform::suspend_process_impl(*pid, suspend)?;
+        }
+        self.inner.suspended.store(suspend, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn sync_logged(&self) {
+        let pids = self.inner.child_pids.lock().unwrap();
+        if !self.is_cancelled() {
+            if let Err(e) = self.sync_children(&pids) {
+                log::warn!("Failed to suspend or resume a job: {}", e);
+            }
+        }
+    }
+}
+
+/// Force-kill a process (and its children on Windows)
+pub fn kill_process(pid: u32) {
+    #[cfg(target_os = "windows")]
+    {
+        let _ = crate::hidden_command("taskkill")
+            .args(["/F", "/T", "/PID", &pid.to_string()])
+            .output();
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = std::process::Command::new("kill")
+            .args(["-9", &pid.to_string()])
+            .output();
+    }
+}
+
+#[derive(Default)]
+pub struct JobManager {
+    next_id: AtomicU64,
+    jobs: Mutex<HashMap<u64, (JobInfo, CancellationToken)>>,
+    last_failure: Mutex<Option<String>>, // label of the last job that failed, until acknowledged
+    generation: Mutex<u64>, // bumped whenever a waiting job might now fit
+    changed: Condvar,
+}
+
+impl JobManager {
+    pub(crate) fn register(&self, kind: JobKind, label: String) -> (u64, CancellationToken) {
+        use std::time::{SystemTime, UNIX_EPOCH};
+
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst) + 1;
+        let token = CancellationToken::default();
+        let info = JobInfo {
+            id,
+            kind,
+            label,
+            started_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            progress: None,
+            paused: false,
+            queue_position: Some(1), // until `try_start` places it
+            power: JobPower::Normal,
+            run_on_battery: false,
+        };
+        self.jobs.lock().unwrap().insert(id, (info, token.clone()));
+        (id, token)
+    }
+
+    /// Start a waiting job if its category has a free slot once the jobs queued before it have
+    /// theirs (None), otherwise its place in line. A job deferred by `power` keeps waiting and
+    /// doesn't hold up the jobs behind it; one throttled starts throttled. With `force` it
+    /// starts regardless.
+    pub fn try_start(
+        &self,
+        id: u64,
+        limits: &JobLimits,
+        power: JobPower,
+        force: bool,
+    ) -> Option<usize> {
+        let mut jobs = self.jobs.lock().unwrap();
+        let category = jobs.get(&id)?.0.kind.category();
+        let (mut running, mut ahead) = (0, 0);
+        for (info, _) in jobs.values() {
+            if info.kind.category() != category {
+                continue;
+            }
+            match info.queue_position {
+                None => running += 1,
+                Some(_) if info.id < id && info.power != JobPower::Deferred => ahead += 1,
+                Some(_) => {}
+            }
+        }
+        let fits = power != JobPower::Deferred && running + ahead < limits.limit(category);
+        let position = if force || fits { None } else { Some(ahead + 1) };
+        let (info, token) = jobs.get_mut(&id)?;
+        let started = info.queue_position.is_some() && position.is_none();
+        info.queue_position = position;
+        info.power = power;
+        let token = token.clone();
+        drop(jobs);
+        // Starting moves the jobs behind it up a place
+        if started {
+            token.set_throttled(power == JobPower::Throttled);
+            self.notify_changed();
+        }
+        position
+    }
+
+    /// Apply the battery policy's verdict to the running heavy jobs that follow it: hold them,
+    /// throttle them or let them run. Waiting jobs are woken to look again. Returns true if
+    /// any job changed.
+    pub fn apply_power(&self, power: JobPower) -> bool {
+        let tokens: Vec<CancellationToken> = self
+            .jobs
+            .lock()
+            .unwrap()
+            .values_mut()
+            .filter(|(info, _)| {
+                info.kind.is_heavy()
+                    && !info.run_on_battery
+                    && info.queue_position.is_none()
+                    && info.power != power
+            })
+            .map(|(info, token)| {
+                info.power = power;
+                token.clone()
+            })
+            .collect();
+        for token in &tokens {
+            token.set_held(power == JobPower::Deferred);
+            token.set_throttled(power == JobPower::Throttled);
+        }
+        self.notify_changed();
+        !tokens.is_empty()
+    }
+
+    /// Tokens of the running jobs the battery policy throttles
+    pub fn throttled_tokens(&self) -> Vec<CancellationToken> {
+        self.jobs
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|(info, _)| info.queue_position.is_none() && info.power == JobPower::Throttled)
+            .map(|(_, token)| token.clone())
+            .collect()
+    }
+
+    /// Whether the user told the job to ignore the battery policy
+    pub fn runs_on_battery(&self, id: u64) -> bool {
+        self.jobs
+            .lock()
+            .unwrap()
+            .get(&id)
+            .is_some_and(|(info, _)| info.run_on_battery)
+    }
+
+    /// Let a job ignore the battery policy: a held or throttled one carries on at full speed,
+    /// and a waiting one no longer waits for AC power
+    pub fn run_on_battery(&self, id: u64) -> Result<(), String> {
+        let token = match self.jobs.lock().unwrap().get_mut(&id) {
+            Some((info, token)) => {
+                info.run_on_battery = true;
+                info.power = JobPower::Normal;
+                token.clone()
+            }
+            None => return Err(format!("Job {} is not running", id)),
+        };
+        token.set_held(false);
+        token.set_throttled(false);
+        self.notify_changed();
+        Ok(())
+    }
+
+    /// The counter `wait_for_change` compares against; read it before looking for a slot
+    pub fn generation(&self) -> u64 {
+        *self.generation.lock().unwrap()
+    }
+
+    /// Block until a job finishes, starts or is cancelled, or the limits change, since `seen`
+    pub fn wait_for_change(&self, seen: u64) {
+        let generation = self.generation.lock().unwrap();
+        let _ = self
+            .changed
+            .wait_timeout_while(generation, QUEUE_RECHECK, |generation| *generation == seen);
+    }
+
+    /// Wake the waiting jobs to look for a slot again (also after the limits change)
+    pub fn notify_changed(&self) {
+        *self.generation.lock().unwrap() += 1;
+        self.changed.notify_all();
+    }
+
+    pub(crate) fn finish(&self, id: u64, failed: bool) {
+        let removed = self.jobs.lock().unwrap().remove(&id);
+        self.notify_changed();
+        let mut last_failure = self.last_failure.lock().unwrap();
+        match removed {
+            Some((info, _)) if failed => *last_failure = Some(info.label),
+            Some(_) => *last_failure = None,
+            None => {}
+        }
+    }
+
+    /// Label of the last failed job, if it hasn't been acknowledged yet
+    pub fn last_failure(&self) -> Option<String> {
+        self.last_failure.lock().unwrap().clone()
+    }
+
+    /// Forget the last failure, returns true if there was one
+    pub fn clear_failure(&self) -> bool {
+        self.last_failure.lock().unwrap().take().is_some()
+    }
+
+    /// Cancel a single job, returns false if it is no longer running
+    pub fn cancel(&self, id: u64) -> bool {
+        let token = self.jobs.lock().unwrap().get(&id).map(|(_, t)| t.clone());
+        match token {
+            Some(token) => {
+                token.cancel();
+                self.notify_changed();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Pause or resume a running conversion's ffmpeg
+    pub fn set_paused(&self, id: u64, paused: bool) -> Result<(), String> {
+        let token = self.conversion_token(id)?;
+        if token.is_paused() != paused {
+            token.set_paused(paused)?;
+        }
+        if let Some((info, _)) = self.jobs.lock().unwrap().get_mut(&id) {
+            info.paused = paused;
+        }
+        Ok(())
+    }
+
+    fn conversion_token(&self, id: u64) -> Result<CancellationToken, String> {
+        match self.jobs.lock().unwrap().get(&id) {
+            Some((info, token)) if info.kind == JobKind::Conversion => Ok(token.clone()),
+            Some(_) => Err(format!("Job {} is not a conversion", id)),
+            None => Err(format!("Job {} is not running", id)),
+        }
+    }
+
+    /// Cancel every running job of a kind, returns how many were cancelled
+    pub fn cancel_kind(&self, kind: JobKind) -> usize {
+        let tokens: Vec<CancellationToken> = self
+            .jobs
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|(info, _)| info.kind == kind)
+            .map(|(_, t)| t.clone())
+            .collect();
+        for token in &tokens {
+            token.cancel();
+        }
+        self.notify_changed();
+        tokens.len()
+    }
+
+    /// Record a running job's progress, returns true if it changed
+    pub fn set_progress(&self, id: u64, percent: u8) -> bool {
+        match self.jobs.lock().unwrap().get_mut(&id) {
+            Some((info, _)) if info.queue_position.is_none() && info.progress != Some(percent) => {
+                info.progress = Some(percent);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn list(&self) -> Vec<JobInfo> {
+        let mut jobs: Vec<JobInfo> = self
+            .jobs
+            .lock()
+            .unwrap()
+            .values()
+            .map(|(info, _)| info.clone())
+            .collect();
+        jobs.sort_by_key(|j| j.id);
+        jobs
+    }
+}
+
+/// Handle owned by a running command; dropping it unregisters the job. A job dropped without
+/// `complete()` (an early error return) and without being cancelled counts as failed.
+pub struct JobHandle {
+    id: u64,
+    token: CancellationToken,
+    completed: AtomicBool,
+    workspace: OnceLock<Workspace>, // made on first use, removed with the job
+    meter: Option<TransferMeter>,   // for download jobs
+    app: AppHandle,
+}
+
+impl JobHandle {
+    pub fn token(&self) -> &CancellationToken {
+        &self.token
+    }
+
+    /// Mark the job as finished successfully
+    pub fn complete(&self) {
+        self.completed.store(true, Ordering::SeqCst);
+    }
+
+    /// The job's temp directory for downloads and unfinished output (see workspaces.rs)
+    pub fn workspace(&self) -> Result<&Path, String> {
+        if self.workspace.get().is_none() {
+            let workspace = workspaces::create(&self.app, &format!("job-{}", self.id))?;
+            let _ = self.workspace.set(workspace);
+        }
+        Ok(self.workspace.get().unwrap().path())
+    }
+
+    /// Record the job's percent for the jobs list and the tray (tools still send their own
+    /// progress events with the details)
+    pub fn report_progress(&self, percent: u8) {
+        let state = self.app.state::<AppState>();
+        if state.jobs.set_progress(self.id, percent.min(100)) {
+            crate::tray_status::refresh(&self.app);
+        }
+    }
+
+    /// Count bytes a download job has downloaded (see transfers.rs)
+    pub fn count_download(&self, bytes: u64) {
+        if let Some(meter) = &self.meter {
+            meter.downloaded(bytes);
+        }
+    }
+}
+
+impl Drop for JobHandle {
+    fn drop(&mut self) {
+        let failed = !self.completed.load(Ordering::SeqCst) && !self.token.is_cancelled();
+        let state = self.app.state::<AppState>();
+        state.jobs.finish(self.id, failed);
+        emit_jobs_changed(&self.app);
+    }
+}
+
+/// Register a new job and get its handle once the limit for its kind of job lets it start.
+/// Until then it waits in line; one cancelled while waiting starts right away, only to end.
+pub fn start_job(app: &AppHandle, kind: JobKind, label: impl Into<String>) -> JobHandle {
+    let state = app.state::<AppState>();
+    let (id, token) = state.jobs.register(kind, label.into());
+    let handle = JobHandle {
+        id,
+        token,
+        completed: AtomicBool::new(false),
+        workspace: OnceLock::new(),
+        meter: kind
+            .transfer_tool()
+            .map(|tool| TransferMeter::new(app, tool)),
+        app: app.clone(),
+    };
+    wait_for_slot(app, id, kind, &handle.token);
+    emit_jobs_changed(app);
+    handle
+}
+
+fn wait_for_slot(app: &AppHandle, id: u64, kind: JobKind, token: &CancellationToken) {
+    let state = app.state::<AppState>();
+    let mut reported = (None, JobPower::Normal);
+    loop {
+        let seen = state.jobs.generation();
+        let limits = state.settings.lock().unwrap().job_limits;
+        let power = if kind.is_heavy() && !state.jobs.runs_on_battery(id) {
+            power::job_power(app)
+        } else {
+            JobPower::Normal
+        };
+        let position = state
+            .jobs
+            .try_start(id, &limits, power, token.is_cancelled());
+        if (position, power) != reported {
+            reported = (position, power);
+            emit_jobs_changed(app);
+            let progress = QueueProgress {
+                id,
+                kind,
+                position,
+                waiting_for_power: position.is_some() && power == JobPower::Deferred,
+            };
+            let _ = app.emit("job-queue-progress", progress);
+        }
+        if position.is_none() {
+            return;
+        }
+        // Commands call this on the async runtime, whose other tasks move to another worker
+        tokio::task::block_in_place(|| state.jobs.wait_for_change(seen));
+    }
+}
+
+pub(crate) fn emit_jobs_changed(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    let _ = app.emit("jobs-changed", state.jobs.list());
+
+    // Tray shows progress and a "Cancel current job" entry while jobs run
+    crate::tray_menu::rebuild_tray_menu(app);
+    crate::tray_status::refresh(app);
+}
+
+#[tauri::command]
+pub fn list_jobs(app: AppHandle) -> Vec<JobInfo> {
+    let state = app.state::<AppState>();
+    state.jobs.list()
+}
+
+#[tauri::command]
+pub fn cancel_job(app: AppHandle, job_id: u64) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    if state.jobs.cancel(job_id) {
+        Ok(())
+    } else {
+        Err(format!("Job {} is not running", job_id))
+    }
+}
+
+/// Stop a conversion: kills its ffmpeg, and the unfinished output is removed with the job's
+/// workspace
+#[tauri::command]
+pub fn cancel_conversion(app: AppHandle, job_id: u64) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    state.jobs.conversion_token(job_id)?;
+    state.jobs.cancel(job_id);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn pause_conversion(app: AppHandle, job_id: u64) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    state.jobs.set_paused(job_id, true)?;
+    emit_jobs_changed(&app);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn resume_conversion(app: AppHandle, job_id: u64) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    state.jobs.set_paused(job_id, false)?;
+    emit_jobs_changed(&app);
+    Ok(())
+}
+
+/// Run a heavy job at full speed even though the battery policy would throttle or hold it
+#[tauri::command]
+pub fn run_job_on_battery(app: AppHandle, job_id: u64) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    state.jobs.run_on_battery(job_id)?;
+    emit_jobs_changed(&app);
+    Ok(())
+}
@@ -0,0 +1,42 @@
+// Windows taskbar jump list
+// Right-clicking the taskbar button offers a few common tasks. Each is a shortcut that
+// starts `bunchatools --action ...` or `--open-tool ...`; with BunchaTools already running,
+// the single-instance plugin forwards those arguments to it (see shell_integration.rs).
+
+use tauri::AppHandle;
+
+use crate::platform::{self, JumpListTask};
+use crate::shell_integration::{ACTION_ARG, OPEN_TOOL_ARG};
+
+// (title, action), for actions that run without opening the launcher
+const ACTION_TASKS: &[(&str, &str)] = &[("Pick color", "pick_color")];
+
+// (title, tool id), opened in the launcher
+const TOOL_TASKS: &[(&str, &str)] = &[
+    ("Kill port…", "port-killer"),
+    ("Convert video…", "video-converter"),
+    ("QR code…", "qr-generator"),
+    ("Running jobs", "jobs-window"),
+];
+
+fn tasks() -> Vec<JumpListTask> {
+    let actions = ACTION_TASKS.iter().map(|(title, action)| JumpListTask {
+        title: title.to_string(),
+        args: format!("{} {}", ACTION_ARG, action),
+    });
+    let tools = TOOL_TASKS.iter().map(|(title, tool)| JumpListTask {
+        title: title.to_string(),
+        args: format!("{} {}", OPEN_TOOL_ARG, tool),
+    });
+    actions.chain(tools).collect()
+}
+
+/// Publish the jump list tasks (a no-op outside Windows)
+pub fn init(_app: &AppHandle) {
+    // COM setup and the shell's list commit can be slow, so keep them off the startup path
+    std::thread::spawn(|| {
+        if let Err(e) = platform::set_jump_list_impl(&tasks()) {
+            log::warn!("Failed to set up the jump list: {}", e);
+        }
+    });
+}
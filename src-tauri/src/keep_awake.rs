@@ -0,0 +1,173 @@
+// Keep awake ("caffeinate"): stop the system from sleeping and the display from turning off,
+// until turned off again or for a set time, e.g. during long conversions and downloads.
+// While it's on, the tray icon shows a badge and the tray menu a checked "Keep Awake" item.
+// The jiggle mode also nudges the mouse now and then, for apps that go "away" after a while
+// without input no matter what the power settings say.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::notifications::{self, NotifyAction};
+use crate::{platform, tray_menu, tray_status};
+
+// Often enough for presence timeouts (usually 5 minutes or more), rare enough to go unnoticed
+const JIGGLE_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeepAwakeMode {
+    Off,
+    System, // OS sleep / display-off inhibitor
+    Jiggle, // the inhibitor plus a 1 px mouse nudge every `JIGGLE_INTERVAL`
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct KeepAwakeStatus {
+    pub mode: KeepAwakeMode,
+    pub remaining: Option<u64>, // seconds until it turns itself off; None = until turned off
+}
+
+struct Session {
+    generation: u64,
+    mode: KeepAwakeMode,
+    ends_at: Option<Instant>,
+}
+
+static SESSION: Mutex<Option<Session>> = Mutex::new(None);
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// True while sleep is being held off (for the tray)
+pub fn is_active() -> bool {
+    SESSION.lock().unwrap().is_some()
+}
+
+fn status() -> Option<KeepAwakeStatus> {
+    SESSION.lock().unwrap().as_ref().map(|s| KeepAwakeStatus {
+        mode: s.mode,
+        remaining: s
+            .ends_at
+            .map(|end| end.saturating_duration_since(Instant::now()).as_secs()),
+    })
+}
+
+fn notify_changed(app: &AppHandle) {
+    tray_status::refresh(app);
+    tray_menu::rebuild_tray_menu(app);
+    let _ = app.emit("keep-awake-changed", status());
+}
+
+/// Let the system sleep again
+async fn release(app: &AppHandle) -> Result<(), String> {
+    let was_active = SESSION.lock().unwrap().take().is_some();
+    platform::set_keep_awake_impl(false).await?;
+    if was_active {
+        notify_changed(app);
+    }
+    Ok(())
+}
+
+fn is_current(generation: u64) -> bool {
+    SESSION
+        .lock()
+        .unwrap()
+        .as_ref()
+        .is_some_and(|s| s.generation == generation)
+}
+
+/// Turn keep awake on in `mode` (replacing any running timer) or off
+pub async fn set(
+    app: &AppHandle,
+    mode: KeepAwakeMode,
+    duration: Option<u64>,
+) -> Result<(), String> {
+    if duration == Some(0) {
+        return Err("Duration needs to be at least a second".to_string());
+    }
+    let generation = GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    if mode == KeepAwakeMode::Off {
+        return release(app).await;
+    }
+    // Fail before inhibiting anything where input can't be simulated (Wayland)
+    if mode == KeepAwakeMode::Jiggle {
+        platform::nudge_mouse_impl()?;
+    }
+
+    platform::set_keep_awake_impl(true).await?;
+    *SESSION.lock().unwrap() = Some(Session {
+        generation,
+        mode,
+        ends_at: duration.map(|secs| Instant::now() + Duration::from_secs(secs)),
+    });
+    notify_changed(app);
+
+    if mode == KeepAwakeMode::Jiggle {
+        tauri::async_runtime::spawn(async move {
+            loop {
+                tokio::time::sleep(JIGGLE_INTERVAL).await;
+                if !is_current(generation) {
+                    return;
+                }
+                if let Err(e) = platform::nudge_mouse_impl() {
+                    log::warn!("Failed to nudge the mouse: {}", e);
+                }
+            }
+        });
+    }
+
+    if let Some(secs) = duration {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(Duration::from_secs(secs)).await;
+            if !is_current(generation) {
+                return; // turned off or restarted meanwhile
+            }
+            if let Err(e) = release(&app).await {
+                log::warn!("Failed to end keep awake: {}", e);
+                return;
+            }
+            notifications::notify(
+                &app,
+                "Keep awake ended",
+                "Your computer can go to sleep again",
+                NotifyAction::None,
+            );
+        });
+    }
+    Ok(())
+}
+
+/// Keep the system awake, for `duration` seconds or until turned off
+#[tauri::command]
+pub async fn set_keep_awake(
+    app: AppHandle,
+    enabled: bool,
+    duration: Option<u64>,
+) -> Result<Option<KeepAwakeStatus>, String> {
+    let mode = if enabled {
+        KeepAwakeMode::System
+    } else {
+        KeepAwakeMode::Off
+    };
+    set(&app, mode, duration).await?;
+    Ok(status())
+}
+
+/// Keep the system awake in `mode` (or turn it off), for `duration` seconds or until turned off
+#[tauri::command]
+pub async fn keep_awake(
+    app: AppHandle,
+    mode: KeepAwakeMode,
+    duration: Option<u64>,
+) -> Result<Option<KeepAwakeStatus>, String> {
+    set(&app, mode, duration).await?;
+    Ok(status())
+}
+
+#[tauri::command]
+pub fn get_keep_awake_status() -> Option<KeepAwakeStatus> {
+    status()
+}
@@ -0,0 +1,70 @@
+// Language detection on its own, so the UI can show how sure it is instead of quietly
+// assuming English. whatlang only reports its best guess; the runner-ups come from asking
+// again with the languages already found ruled out, so each alternative's confidence is how
+// sure detection would be if everything ranked above it were wrong.
+
+use serde::Serialize;
+use whatlang::{Detector, Lang};
+
+const MAX_ALTERNATIVES: usize = 3;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LanguageCandidate {
+    pub language: String,
+    pub code: String, // ISO 639-1, or ISO 639-3 for languages without a two-letter code here
+    pub confidence: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LanguageDetection {
+    pub language: String,
+    pub code: String,
+    pub confidence: f64, // 0 to 1
+    pub reliable: bool,
+    pub alternatives: Vec<LanguageCandidate>,
+}
+
+fn candidate(lang: Lang, confidence: f64) -> LanguageCandidate {
+    let language = match crate::lang_to_name(lang) {
+        "Unknown" => lang.eng_name(),
+        name => name,
+    };
+    LanguageCandidate {
+        language: language.to_string(),
+        code: crate::lang_to_code(lang).unwrap_or(lang.code()).to_string(),
+        confidence,
+    }
+}
+
+/// The most likely language of `text` and up to three runner-ups; `None` for text without
+/// anything to go on (empty, digits or punctuation only)
+pub fn detect(text: &str) -> Option<LanguageDetection> {
+    let best = whatlang::detect(text)?;
+    let mut ruled_out = vec![best.lang()];
+    let mut alternatives = Vec::new();
+    while alternatives.len() < MAX_ALTERNATIVES {
+        let Some(next) = Detector::with_denylist(ruled_out.clone()).detect(text) else {
+            break;
+        };
+        // Text in a script only one language uses has nothing else to offer
+        if ruled_out.contains(&next.lang()) || next.confidence() <= 0.0 {
+            break;
+        }
+        ruled_out.push(next.lang());
+        alternatives.push(candidate(next.lang(), next.confidence()));
+    }
+
+    let top = candidate(best.lang(), best.confidence());
+    Some(LanguageDetection {
+        language: top.language,
+        code: top.code,
+        confidence: top.confidence,
+        reliable: best.is_reliable(),
+        alternatives,
+    })
+}
+
+#[tauri::command]
+pub fn detect_language(text: String) -> Result<LanguageDetection, String> {
+    detect(&text).ok_or_else(|| "Couldn't tell what language that is".to_string())
+}
@@ -1,18 +1,218 @@
+use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::sync::Mutex;
 use serde::{Deserialize, Serialize};
 use tauri::{
     tray::{MouseButton, MouseButtonState, TrayIcon, TrayIconBuilder, TrayIconEvent},
     window::Color,
-    AppHandle, Emitter, Manager,
+    AppHandle, Emitter, Manager, Runtime,
 };
 use tauri_plugin_global_shortcut::{Code, GlobalShortcutExt, Modifiers, Shortcut, ShortcutState};
 
 // Platform-specific implementations
 mod platform;
 
+// Write destination validation
+mod path_policy;
+
+// Admin-rights helper for narrow operations
+mod elevation;
+
+// Long-running job tracking and cancellation
+mod jobs;
+use jobs::{CancellationToken, JobHandle, JobKind};
+
+// Native notifications with click actions
+mod notifications;
+use notifications::NotifyAction;
+
+// Quick actions that run without opening the window
+mod actions;
+
+// Tray menu with recent tools and quick actions
+mod tray_menu;
+
+// Tray tooltip/icon progress, icon style and busy/error states
+mod tray_status;
+
+// Stream Deck / MIDI triggers
+mod triggers;
+
+// File manager "Convert with BunchaTools" entries and launch arguments
+mod shell_integration;
+
+// Handler registration for download lists, workflows and palettes
+mod file_associations;
+
+// Next-action suggestions from the clipboard
+mod suggestions;
+
+// Sum/average of numbers copied to the clipboard
+mod clipboard_math;
+
+// Per-monitor remembered window positions
+mod window_placement;
+
+// Tools opened in their own windows
+mod tool_windows;
+
+// Offline HTTP status / MIME type / port / HTML entity reference
+mod reference;
+
+// FIGlet-style ASCII art banners
+mod banner;
+
+// Morse / NATO / ROT13 / leetspeak text transforms
+mod text_transforms;
+
+// Pomodoro focus sessions with do-not-disturb
+mod pomodoro;
+
+// Network interfaces, gateway, DNS, VPN/proxy detection and change events
+mod network;
+
+// Wake-on-LAN magic packets and saved devices
+mod wake_on_lan;
+
+// Session lock / user idle detection and events
+mod session_state;
+
+// Keep-awake (sleep/display-off inhibitor) with auto-release
+mod keep_awake;
+
+// Random picks, dice rolls and coin flips
+mod random_tools;
+
+// Lock / sleep / restart / (delayed) shutdown
+mod power_actions;
+
+// Validated Wi-Fi / contact / location / email QR payloads
+mod qr_payloads;
+
+// Windows taskbar jump list tasks
+mod jump_list;
+// Desktop shortcuts that open a tool or run a quick action
+mod desktop_shortcuts;
+
+// Magnifier window that follows the cursor during color picks
+mod color_loupe;
+
+// On-disk history of picked colors
+mod color_history;
+
+// Color notation parsing and conversion
+mod color_formats;
+
+// Repeated mouse clicks with a stop hotkey
+mod auto_clicker;
+
+// Spelled-out amounts for invoices and checks
+mod number_words;
+
+// Starter .gitignore / .editorconfig files for project folders
+mod project_files;
+
+// Palette files for design tools (ASE, GPL, CSS, SCSS, JSON tokens)
+mod palette_export;
+
+// Number / currency / date / plural rendering per locale, for i18n debugging
+mod locale_preview;
+
+// Reloads settings.json when it's edited outside the app
+mod settings_watch;
+// Startup failures (hotkeys, tray, external binaries) reported to the UI
+mod startup_health;
+// Screen recording through the bundled ffmpeg
+mod screen_recording;
+// Calculator tape and M+/M-/MR memory, persisted across restarts
+mod calculator;
+// Translate text read from a screen region (OCR)
+mod screen_translate;
+// Time tracking entries, reports and CSV export
+mod time_tracking;
+// Screenshot annotation overlay and image files (arrows, rectangles, text, blur, pixelate)
+mod annotation;
+// Habit check-ins, streaks and reminders
+mod habits;
+// QR code decoding for screen captures
+mod qr_decoder;
+// Scan QR codes from a screen region or the whole screen
+mod qr_scan;
+// Country reference (codes, calling codes, currencies) and public holidays
+mod countries;
+// Place names to coordinates (Open-Meteo geocoder, cached)
+mod geocoding;
+// Sunrise, sunset and golden hour from the NOAA solar equations
+mod sun_times;
+// Warmer screen colors from sunset to sunrise (gamma ramps)
+mod night_light;
+// Translation on this computer (translateLocally / Bergamot models)
+mod offline_translation;
+// Text snippets with computed placeholders ({uuid}, {date+3d}, {clipboard|upper})
+mod snippets;
+// Searchable history of translations
+mod translation_history;
+// Passphrase-protected key for encrypted snippets, locked again after a while unused
+mod secrets;
+// Passcode in front of histories and secrets, for shared machines
+mod app_lock;
+// Quick translation that types the translation over the selection
+mod inline_translation;
+// Multicast DNS advertising and browsing on the LAN
+mod mdns;
+// Send the clipboard to BunchaTools on another computer, encrypted with a share code
+mod clipboard_share;
+// Preferred translations for terms, applied around every translation
+mod glossary;
+// Devices on the LAN from mDNS, SSDP and the ARP cache
+mod network_discovery;
+// Signed HTTP webhooks that run notifications, downloads and workflows
+mod webhook;
+// Language detection with a confidence score and runner-up languages
+mod language_detection;
+// Translated copies of text, subtitle and Markdown files
+mod file_translation;
+// Cache and MyMemory rate limit in front of the translation engines
+mod translation_cache;
+// Subtitles from the speech in audio and video files (whisper.cpp)
+mod transcription;
+// Word definitions, synonyms and examples from a free dictionary API
+mod dictionary;
+// Push-to-talk speech into the launcher query
+mod voice_input;
+// Microphone recording to MP3 / WAV / Opus with a level meter
+mod audio_recording;
+// Cryptocurrency prices for the currency converter
+mod crypto_prices;
+// Exchange rates, cached for offline conversion
+mod currency;
+// On-demand downloads of the local ML models
+mod models;
+// Pin other apps' windows on top or make them translucent
+mod external_windows;
+// Offline unit conversion (length, mass, temperature, data size, ...)
+mod units;
+// Tools the user has tried and the tips they unlock
+mod onboarding;
+// Time zone and Unix timestamp conversion
+mod time_zones;
+// Per-job temp directories and cleanup after crashes
+mod workspaces;
+// Calculator expressions with percentages, bit operations, bases, units and currencies
+mod expressions;
+// Bytes downloaded and uploaded per tool and day, and the monthly cap
+mod transfers;
+// Queue of media files converted with shared options, a few at a time
+mod conversion_queue;
+// Power status, and throttling or holding heavy jobs on battery
+mod power;
+
+// Test support (see tests/)
+#[cfg(feature = "test-harness")]
+pub mod testing;
+
 /// Creates a Command that hides the console window on Windows.
 /// On other platforms, returns a regular Command.
 fn hidden_command<S: AsRef<std::ffi::OsStr>>(program: S) -> Command {
@@ -45,9 +245,14 @@ pub struct PathAliases {
 pub struct Settings {
     pub hotkey_modifiers: Vec<String>, // ["Alt"], ["Ctrl", "Shift"], etc.
     pub hotkey_key: String,            // "Q", "Space", etc.
+    // Tried in order when another app already holds the main hotkey
+    #[serde(default = "default_hotkey_fallbacks")]
+    pub hotkey_fallbacks: Vec<HotkeyBinding>,
     pub launch_at_startup: bool,
     #[serde(default)]
-    pub window_position: Option<(i32, i32)>, // Saved window position (x, y)
+    pub autostart_method: platform::AutostartMethod, // Linux: XDG autostart entry or systemd unit
+    #[serde(default)]
+    pub window_positions: HashMap<String, (i32, i32)>, // Saved window position (x, y) per monitor
     #[serde(default = "default_show_in_tray")]
     pub show_in_tray: bool,
     #[serde(default)]
@@ -59,6 +264,129 @@ pub struct Settings {
     pub quick_translation_hotkey_key: String, // Empty string means disabled
     #[serde(default = "default_quick_translation_target_language")]
     pub quick_translation_target_language: String,
+    // Type the translation over the selection instead of opening the translation window
+    #[serde(default)]
+    pub quick_translation_replace: bool,
+    // Translate text read from a dragged-out screen region (OCR); empty key disables it
+    #[serde(default = "default_screen_translation_modifiers")]
+    pub screen_translation_hotkey_modifiers: Vec<String>,
+    #[serde(default = "default_screen_translation_key")]
+    pub screen_translation_hotkey_key: String,
+    // Push-to-talk: hold to speak a launcher query; empty key disables it
+    #[serde(default = "default_voice_modifiers")]
+    pub voice_hotkey_modifiers: Vec<String>,
+    #[serde(default)]
+    pub voice_hotkey_key: String,
+    // Language spoken to voice input, or empty to detect it
+    #[serde(default = "default_voice_language")]
+    pub voice_language: String,
+    // Currencies an amount like "100 usd" is shown in, at most five
+    #[serde(default = "default_favorite_currencies")]
+    pub favorite_currencies: Vec<String>,
+    // Hotkey chords: main hotkey followed by a single key opens a tool directly (e.g. Alt+Q, T)
+    #[serde(default)]
+    pub chord_bindings: Vec<ChordBinding>,
+    #[serde(default = "default_chord_timeout_ms")]
+    pub chord_timeout_ms: u64,
+    // Mouse side button / desktop middle-click that toggles the window
+    #[serde(default)]
+    pub mouse_trigger: platform::MouseTrigger,
+    // Actions listed in the tray menu
+    #[serde(default = "actions::default_quick_actions")]
+    pub quick_actions: Vec<actions::QuickAction>,
+    // Stream Deck (localhost WebSocket) and MIDI bindings
+    #[serde(default)]
+    pub external_triggers: triggers::ExternalTriggerSettings,
+    // "Convert with BunchaTools" in the file manager context menu / Send To
+    #[serde(default)]
+    pub shell_integration: bool,
+    // Open download lists, workflow and palette files with BunchaTools
+    #[serde(default)]
+    pub file_associations: bool,
+    // Full-color or monochrome (theme-matched) tray icon
+    #[serde(default)]
+    pub tray_icon_style: tray_status::TrayIconStyle,
+    // Offer the matching tool for URLs/colors/paths/foreign text on the clipboard
+    #[serde(default = "default_clipboard_suggestions")]
+    pub clipboard_suggestions: bool,
+    // Pinned: stay above other windows and don't hide on focus loss
+    #[serde(default)]
+    pub always_on_top: bool,
+    // Pomodoro focus sessions
+    #[serde(default = "default_pomodoro_focus_minutes")]
+    pub pomodoro_focus_minutes: u64,
+    #[serde(default = "default_pomodoro_break_minutes")]
+    pub pomodoro_break_minutes: u64,
+    #[serde(default)]
+    pub pomodoro_dnd: bool, // turn on the OS do-not-disturb switch while focusing
+    #[serde(default = "default_pomodoro_pause_notifications")]
+    pub pomodoro_pause_notifications: bool, // hold job notifications until the break
+    // Stay in the tray on launch instead of showing the launcher (always, not just with --hidden)
+    #[serde(default)]
+    pub start_hidden: bool,
+    // Minutes without input before the user counts as idle (session-state-changed)
+    #[serde(default = "default_idle_threshold_minutes")]
+    pub idle_threshold_minutes: u64,
+    // Side of the square of pixels the color picker averages (1, 3, 5 or 9)
+    #[serde(default = "default_color_sample_size")]
+    pub color_sample_size: i32,
+    // Warmer screen colors, scheduled from sunset to sunrise or toggled from the tray
+    #[serde(default)]
+    pub night_light: night_light::NightLightSettings,
+    // MyMemory, or offline models that keep the text on this computer
+    #[serde(default)]
+    pub translation_engine: offline_translation::TranslationEngine,
+    // Minutes unused before the secrets store locks itself
+    #[serde(default = "default_secrets_lock_minutes")]
+    pub secrets_lock_minutes: u64,
+    // Passcode lock for histories and secrets
+    #[serde(default)]
+    pub app_lock: app_lock::AppLockSettings,
+    // Clipboard sending between the user's computers on the LAN
+    #[serde(default)]
+    pub clipboard_share: clipboard_share::ClipboardShareSettings,
+    // Listener for signed webhooks from CI, home automation and scripts
+    #[serde(default)]
+    pub webhook: webhook::WebhookSettings,
+    // How many conversions, downloads and scans run at once
+    #[serde(default)]
+    pub job_limits: jobs::JobLimits,
+    // What heavy jobs do on battery power
+    #[serde(default)]
+    pub battery: power::BatterySettings,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HotkeyBinding {
+    pub modifiers: Vec<String>, // ["Ctrl", "Alt"], etc.
+    pub key: String,            // "Space", "Q", etc.
+}
+
+impl HotkeyBinding {
+    fn new(modifiers: &[&str], key: &str) -> Self {
+        Self {
+            modifiers: modifiers.iter().map(|m| m.to_string()).collect(),
+            key: key.to_string(),
+        }
+    }
+
+    /// Display form, e.g. "Alt+Q"
+    fn label(&self) -> String {
+        hotkey_label(&self.modifiers, &self.key)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChordBinding {
+    pub key: String,  // second-step key, e.g. "T"
+    pub tool: String, // tool id, e.g. "quick-translation"
+}
+
+fn default_hotkey_fallbacks() -> Vec<HotkeyBinding> {
+    vec![
+        HotkeyBinding::new(&["Ctrl", "Alt"], "Space"),
+        HotkeyBinding::new(&["Alt", "Shift"], "Q"),
+    ]
 }
 
 fn default_show_in_tray() -> bool {
@@ -73,18 +401,107 @@ fn default_quick_translation_target_language() -> String {
     "en".to_string()
 }
 
+fn default_screen_translation_modifiers() -> Vec<String> {
+    vec!["Alt".to_string(), "Shift".to_string()]
+}
+
+fn default_screen_translation_key() -> String {
+    "O".to_string()
+}
+
+fn default_voice_modifiers() -> Vec<String> {
+    vec!["Ctrl".to_string(), "Alt".to_string()]
+}
+
+fn default_voice_language() -> String {
+    "en".to_string()
+}
+
+fn default_favorite_currencies() -> Vec<String> {
+    ["USD", "EUR", "GBP", "JPY", "BTC"]
+        .into_iter()
+        .map(String::from)
+        .collect()
+}
+
+fn default_chord_timeout_ms() -> u64 {
+    1500
+}
+
+fn default_clipboard_suggestions() -> bool {
+    true
+}
+
+fn default_pomodoro_focus_minutes() -> u64 {
+    25
+}
+
+fn default_pomodoro_break_minutes() -> u64 {
+    5
+}
+
+fn default_pomodoro_pause_notifications() -> bool {
+    true
+}
+
+fn default_idle_threshold_minutes() -> u64 {
+    5
+}
+
+fn default_color_sample_size() -> i32 {
+    1
+}
+
+fn default_secrets_lock_minutes() -> u64 {
+    5
+}
+
 impl Default for Settings {
     fn default() -> Self {
         Self {
             hotkey_modifiers: vec!["Alt".to_string()],
             hotkey_key: "Q".to_string(),
+            hotkey_fallbacks: default_hotkey_fallbacks(),
             launch_at_startup: false,
-            window_position: None,
+            autostart_method: platform::AutostartMethod::DesktopEntry,
+            window_positions: HashMap::new(),
             show_in_tray: true,
             command_only_mode: false,
             quick_translation_hotkey_modifiers: default_quick_translation_modifiers(),
             quick_translation_hotkey_key: String::new(), // Disabled by default
             quick_translation_target_language: default_quick_translation_target_language(),
+            quick_translation_replace: false,
+            screen_translation_hotkey_modifiers: default_screen_translation_modifiers(),
+            screen_translation_hotkey_key: default_screen_translation_key(),
+            voice_hotkey_modifiers: default_voice_modifiers(),
+            voice_hotkey_key: String::new(), // Disabled by default
+            voice_language: default_voice_language(),
+            favorite_currencies: default_favorite_currencies(),
+            chord_bindings: Vec::new(),
+            chord_timeout_ms: default_chord_timeout_ms(),
+            mouse_trigger: platform::MouseTrigger::None,
+            quick_actions: actions::default_quick_actions(),
+            external_triggers: triggers::ExternalTriggerSettings::default(),
+            shell_integration: false,
+            file_associations: false,
+            tray_icon_style: tray_status::TrayIconStyle::Color,
+            clipboard_suggestions: default_clipboard_suggestions(),
+            always_on_top: false,
+            pomodoro_focus_minutes: default_pomodoro_focus_minutes(),
+            pomodoro_break_minutes: default_pomodoro_break_minutes(),
+            pomodoro_dnd: false,
+            pomodoro_pause_notifications: default_pomodoro_pause_notifications(),
+            start_hidden: false,
+            idle_threshold_minutes: default_idle_threshold_minutes(),
+            color_sample_size: default_color_sample_size(),
+            night_light: night_light::NightLightSettings::default(),
+            translation_engine: offline_translation::TranslationEngine::Online,
+            secrets_lock_minutes: default_secrets_lock_minutes(),
+            app_lock: app_lock::AppLockSettings::default(),
+            clipboard_share: clipboard_share::ClipboardShareSettings::default(),
+            webhook: webhook::WebhookSettings::default(),
+            job_limits: jobs::JobLimits::default(),
+            battery: power::BatterySettings::default(),
         }
     }
 }
@@ -97,17 +514,29 @@ struct TimerState {
     label: String,
 }
 
+// Payload of `chord-started`: the launcher takes the second key of a chord from its own
+// key events, so nothing is registered globally and typing elsewhere is never swallowed
+#[derive(Debug, Clone, Serialize)]
+struct ChordStart {
+    bindings: Vec<ChordBinding>,
+    modifiers: Vec<String>, // main hotkey modifiers, which may still be held for the second key
+    timeout_ms: u64,
+}
+
 struct AppState {
     current_shortcut: Mutex<Option<Shortcut>>,
+    active_hotkey: Mutex<Option<HotkeyBinding>>, // configured hotkey or the fallback in use
     quick_translation_shortcut: Mutex<Option<Shortcut>>,
+    screen_translation_shortcut: Mutex<Option<Shortcut>>,
+    voice_shortcut: Mutex<Option<Shortcut>>,
     settings: Mutex<Settings>,
     auto_hide_enabled: Mutex<bool>,
     is_dragging: Mutex<bool>,
     tray_handle: Mutex<Option<TrayIcon>>,
     app_ready: Mutex<bool>,
-    git_download_cancelled: Mutex<bool>,
-    youtube_download_cancelled: Mutex<bool>,
-    youtube_download_process: Mutex<Option<u32>>, // PID of yt-dlp process
+    jobs: jobs::JobManager,
+    triggers: Mutex<triggers::TriggerRuntime>,
+    launch_request: Mutex<Option<shell_integration::OpenFilesRequest>>,
     timer_state: Mutex<TimerState>,
 }
 
@@ -190,6 +619,38 @@ fn save_command_history_to_file(app: &AppHandle, history: &CommandHistory) -> Re
     fs::write(path, content).map_err(|e| e.to_string())
 }
 
+// Generic JSON stores in the app data directory (used by the tool modules)
+fn get_app_data_file<R: Runtime>(app: &AppHandle<R>, file_name: &str) -> PathBuf {
+    let app_data = app.path().app_data_dir().unwrap();
+    fs::create_dir_all(&app_data).unwrap_or_default();
+    app_data.join(file_name)
+}
+
+fn load_json_file<T: serde::de::DeserializeOwned + Default>(
+    app: &AppHandle<impl Runtime>,
+    file_name: &str,
+) -> T {
+    let path = get_app_data_file(app, file_name);
+    if path.exists() {
+        if let Ok(content) = fs::read_to_string(&path) {
+            if let Ok(value) = serde_json::from_str(&content) {
+                return value;
+            }
+        }
+    }
+    T::default()
+}
+
+fn save_json_file<T: Serialize>(
+    app: &AppHandle<impl Runtime>,
+    file_name: &str,
+    value: &T,
+) -> Result<(), String> {
+    let path = get_app_data_file(app, file_name);
+    let content = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
+    fs::write(path, content).map_err(|e| e.to_string())
+}
+
 fn parse_shortcut(modifiers: &[String], key: &str) -> Option<Shortcut> {
     let mut mods = Modifiers::empty();
     for m in modifiers {
@@ -262,6 +723,16 @@ fn parse_shortcut(modifiers: &[String], key: &str) -> Option<Shortcut> {
     Some(Shortcut::new(mods_option, code))
 }
 
+/// Display form of a hotkey, e.g. "Alt+Q"
+fn hotkey_label(modifiers: &[String], key: &str) -> String {
+    modifiers
+        .iter()
+        .map(String::as_str)
+        .chain([key])
+        .collect::<Vec<_>>()
+        .join("+")
+}
+
 #[tauri::command]
 fn hide_window(window: tauri::Window) {
     let _ = window.hide();
@@ -290,25 +761,74 @@ fn get_settings(app: AppHandle) -> Settings {
 }
 
 #[tauri::command]
-fn save_settings(app: AppHandle, settings: Settings) -> Result<(), String> {
+fn save_settings(app: AppHandle, mut settings: Settings) -> Result<(), String> {
+    // Otherwise the lock could just be turned off
+    app_lock::ensure_unlocked()?;
+
+    // Window positions are recorded by the backend; the frontend's copy may be stale
+    // The passcode only changes through set_app_passcode
+    {
+        let state = app.state::<AppState>();
+        let current = state.settings.lock().unwrap();
+        settings.window_positions = current.window_positions.clone();
+        settings.app_lock.passcode_hash = current.app_lock.passcode_hash.clone();
+    }
+
     // Save to file
     save_settings_to_file(&app, &settings)?;
 
+    apply_settings(&app, settings)
+}
+
+/// Make `settings` current: update the state, hotkeys, triggers, tray and OS integration.
+/// Used by `save_settings` and when settings.json is changed on disk (see `settings_watch`).
+fn apply_settings(app: &AppHandle, settings: Settings) -> Result<(), String> {
     // Update state
     let state = app.state::<AppState>();
     *state.settings.lock().unwrap() = settings.clone();
 
     // Update hotkey
-    update_global_shortcut(&app, &settings)?;
+    update_global_shortcut(app, &settings)?;
+
+    // Update mouse trigger
+    update_mouse_trigger(app, &settings)?;
 
     // Update startup setting (platform-specific)
-    platform::set_launch_at_startup_impl(settings.launch_at_startup)?;
+    platform::set_launch_at_startup_impl(settings.launch_at_startup, settings.autostart_method)?;
+
+    // Update file manager context menu entries
+    shell_integration::set_shell_integration(settings.shell_integration)?;
+    file_associations::set_file_associations(settings.file_associations)?;
 
     // Update tray visibility
     if let Some(tray) = state.tray_handle.lock().unwrap().as_ref() {
         let _ = tray.set_visible(settings.show_in_tray);
     }
 
+    apply_always_on_top(app, settings.always_on_top);
+
+    // Hotkey label and quick actions may have changed
+    tray_menu::rebuild_tray_menu(app);
+    tray_status::refresh(app);
+
+    // Restart Stream Deck / MIDI listeners
+    triggers::apply_trigger_settings(app, &settings.external_triggers);
+
+    // Restart the night light schedule
+    night_light::apply_settings(app, &settings.night_light);
+
+    // Start, restart or stop clipboard sharing
+    clipboard_share::apply_settings(app, &settings.clipboard_share);
+
+    // Start, restart or stop the webhook listener
+    webhook::apply_settings(app, &settings.webhook);
+
+    // Jobs waiting in line may fit under new limits
+    state.jobs.notify_changed();
+    conversion_queue::apply_limits(app);
+    // A new battery policy applies to running jobs too
+    power::apply(app);
+
     Ok(())
 }
 
@@ -320,6 +840,9 @@ fn get_launch_at_startup() -> Result<bool, String> {
 fn update_global_shortcut(app: &AppHandle, settings: &Settings) -> Result<(), String> {
     let state = app.state::<AppState>();
 
+    // Drop any half-finished chord before the bindings change
+    cancel_chord(app);
+
     // Unregister old main shortcut
     if let Some(old_shortcut) = state.current_shortcut.lock().unwrap().take() {
         let _ = app.global_shortcut().unregister(old_shortcut);
@@ -330,14 +853,19 @@ fn update_global_shortcut(app: &AppHandle, settings: &Settings) -> Result<(), St
         let _ = app.global_shortcut().unregister(old_shortcut);
     }
 
-    // Register new main shortcut
-    if let Some(new_shortcut) = parse_shortcut(&settings.hotkey_modifiers, &settings.hotkey_key) {
-        app.global_shortcut()
-            .register(new_shortcut.clone())
-            .map_err(|e| e.to_string())?;
-        *state.current_shortcut.lock().unwrap() = Some(new_shortcut);
+    // Unregister old screen translation shortcut
+    if let Some(old_shortcut) = state.screen_translation_shortcut.lock().unwrap().take() {
+        let _ = app.global_shortcut().unregister(old_shortcut);
     }
 
+    // Unregister old voice input shortcut
+    if let Some(old_shortcut) = state.voice_shortcut.lock().unwrap().take() {
+        let _ = app.global_shortcut().unregister(old_shortcut);
+    }
+
+    // Register new main shortcut
+    register_main_shortcut(app, settings)?;
+
     // Register new quick translation shortcut (only if key is set)
     if !settings.quick_translation_hotkey_key.is_empty() {
         if let Some(new_shortcut) = parse_shortcut(
@@ -351,15 +879,182 @@ fn update_global_shortcut(app: &AppHandle, settings: &Settings) -> Result<(), St
         }
     }
 
+    // Register new screen translation shortcut (only if key is set)
+    if !settings.screen_translation_hotkey_key.is_empty() {
+        if let Some(new_shortcut) = parse_shortcut(
+            &settings.screen_translation_hotkey_modifiers,
+            &settings.screen_translation_hotkey_key,
+        ) {
+            // Another app holding it mustn't stop the rest of the settings from applying
+            match app.global_shortcut().register(new_shortcut) {
+                Ok(()) => *state.screen_translation_shortcut.lock().unwrap() = Some(new_shortcut),
+                Err(e) => log::warn!(
+                    "Failed to register the screen translation hotkey {}: {}",
+                    hotkey_label(
+                        &settings.screen_translation_hotkey_modifiers,
+                        &settings.screen_translation_hotkey_key,
+                    ),
+                    e
+                ),
+            }
+        }
+    }
+
+    // Register new voice input shortcut (only if key is set)
+    if !settings.voice_hotkey_key.is_empty() {
+        if let Some(new_shortcut) =
+            parse_shortcut(&settings.voice_hotkey_modifiers, &settings.voice_hotkey_key)
+        {
+            match app.global_shortcut().register(new_shortcut) {
+                Ok(()) => *state.voice_shortcut.lock().unwrap() = Some(new_shortcut),
+                Err(e) => log::warn!(
+                    "Failed to register the voice input hotkey {}: {}",
+                    hotkey_label(&settings.voice_hotkey_modifiers, &settings.voice_hotkey_key),
+                    e
+                ),
+            }
+        }
+    }
+
     Ok(())
 }
 
+// Last hotkey that opened the launcher, for tools that read it outside the app
+const ACTIVE_HOTKEY_FILE: &str = "active_hotkey.json";
+
+/// Register the main hotkey, falling back to `settings.hotkey_fallbacks` in order when another
+/// app already holds it. The binding that worked is stored in the state and in
+/// active_hotkey.json; the error lists every hotkey that was tried.
+fn register_main_shortcut(app: &AppHandle, settings: &Settings) -> Result<HotkeyBinding, String> {
+    let state = app.state::<AppState>();
+    let configured = HotkeyBinding {
+        modifiers: settings.hotkey_modifiers.clone(),
+        key: settings.hotkey_key.clone(),
+    };
+
+    let mut tried = Vec::new();
+    let mut result = Err(format!("Invalid hotkey: {}", configured.label()));
+    for binding in std::iter::once(&configured).chain(&settings.hotkey_fallbacks) {
+        if tried.contains(&binding.label()) {
+            continue;
+        }
+        tried.push(binding.label());
+        let Some(shortcut) = parse_shortcut(&binding.modifiers, &binding.key) else {
+            log::warn!("Skipping invalid fallback hotkey {}", binding.label());
+            continue;
+        };
+        match app.global_shortcut().register(shortcut) {
+            Ok(()) => {
+                if binding != &configured {
+                    log::warn!(
+                        "{} is taken, using the fallback {}",
+                        configured.label(),
+                        binding.label()
+                    );
+                }
+                *state.current_shortcut.lock().unwrap() = Some(shortcut);
+                result = Ok(binding.clone());
+                break;
+            }
+            Err(e) => {
+                log::warn!("Couldn't register {}: {}", binding.label(), e);
+                result = Err(if tried.len() == 1 {
+                    format!("{} is taken by another app", configured.label())
+                } else {
+                    format!(
+                        "{} is taken by another app, and so are the fallbacks ({})",
+                        configured.label(),
+                        tried[1..].join(", ")
+                    )
+                });
+            }
+        }
+    }
+
+    let active = result.as_ref().ok().cloned();
+    *state.active_hotkey.lock().unwrap() = active.clone();
+    if let Err(e) = save_json_file(app, ACTIVE_HOTKEY_FILE, &active) {
+        log::warn!("Failed to save the active hotkey: {}", e);
+    }
+    result
+}
+
+/// The hotkey that currently opens the launcher, None if none could be registered
+#[tauri::command]
+fn get_active_hotkey(app: AppHandle) -> Option<HotkeyBinding> {
+    let state = app.state::<AppState>();
+    let active = state.active_hotkey.lock().unwrap().clone();
+    active
+}
+
+fn update_mouse_trigger(app: &AppHandle, settings: &Settings) -> Result<(), String> {
+    let app_handle = app.clone();
+    platform::set_mouse_trigger_impl(
+        settings.mouse_trigger,
+        std::sync::Arc::new(move || {
+            // Called from the hook/listener thread, so hand off to the async runtime
+            let app_handle_clone = app_handle.clone();
+            tauri::async_runtime::spawn(async move {
+                toggle_window(&app_handle_clone);
+            });
+        }),
+    )
+}
+
+/// Start waiting for the second key of a chord after the main hotkey opened the window. The
+/// launcher catches the key itself while it has focus, until the chord completes or times out.
+fn start_chord(app: &AppHandle) {
+    let settings = app.state::<AppState>().settings.lock().unwrap().clone();
+    if settings.chord_bindings.is_empty() {
+        return;
+    }
+    let chord = ChordStart {
+        bindings: settings.chord_bindings,
+        modifiers: settings.hotkey_modifiers,
+        timeout_ms: settings.chord_timeout_ms,
+    };
+    let _ = app.emit("chord-started", chord);
+}
+
+/// Stop waiting for the second key of a chord
+fn cancel_chord(app: &AppHandle) {
+    let _ = app.emit("chord-cancelled", ());
+}
+
 #[tauri::command]
 fn set_auto_hide(app: AppHandle, enabled: bool) {
     let state = app.state::<AppState>();
     *state.auto_hide_enabled.lock().unwrap() = enabled;
 }
 
+// The launcher is configured always-on-top already; pinning re-asserts it, since some window
+// managers drop the flag when the window is hidden and shown again
+fn apply_always_on_top(app: &AppHandle, pinned: bool) {
+    if !pinned {
+        return;
+    }
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.set_always_on_top(true);
+    }
+}
+
+/// Pin or unpin the window, persisting the choice
+#[tauri::command]
+fn set_always_on_top(app: AppHandle, pinned: bool) -> Result<(), String> {
+    let settings = {
+        let state = app.state::<AppState>();
+        let mut settings = state.settings.lock().unwrap();
+        settings.always_on_top = pinned;
+        settings.clone()
+    };
+    save_settings_to_file(&app, &settings)?;
+
+    apply_always_on_top(&app, pinned);
+    tray_menu::rebuild_tray_menu(&app);
+    let _ = app.emit("always-on-top-changed", pinned);
+    Ok(())
+}
+
 #[tauri::command]
 fn set_dragging(app: AppHandle, dragging: bool) {
     let state = app.state::<AppState>();
@@ -369,16 +1064,26 @@ fn set_dragging(app: AppHandle, dragging: bool) {
 #[tauri::command]
 fn mark_app_ready(app: AppHandle) {
     let state = app.state::<AppState>();
-    *state.app_ready.lock().unwrap() = true;
+    let was_ready = std::mem::replace(&mut *state.app_ready.lock().unwrap(), true);
 
     // Show tray icon now that app is ready (if enabled in settings)
-    let settings = state.settings.lock().unwrap();
-    if settings.show_in_tray {
+    let (show_in_tray, start_hidden) = {
+        let settings = state.settings.lock().unwrap();
+        (settings.show_in_tray, settings.start_hidden)
+    };
+    if show_in_tray {
         if let Some(tray) = state.tray_handle.lock().unwrap().as_ref() {
             let _ = tray.set_visible(true);
         }
     }
 
+    // The window is created hidden, so a login launch never flashes it; a manual launch
+    // brings the launcher up once (not again when the frontend reloads)
+    let args: Vec<String> = std::env::args().collect();
+    if !was_ready && !start_hidden && !shell_integration::launched_hidden(&args) {
+        actions::show_main_window(&app);
+    }
+
     log::info!("App marked as ready");
 }
 
@@ -431,75 +1136,170 @@ fn parse_time_from_progress(line: &str) -> Option<f64> {
     None
 }
 
-#[tauri::command]
-async fn convert_media(
-    app: AppHandle,
-    input_path: String,
-    output_path: String,
+/// The file name of `output_path`, for ffmpeg to write it in the job's workspace first
+fn output_file_name(output_path: &str) -> std::ffi::OsString {
+    Path::new(output_path)
+        .file_name()
+        .map(|name| name.to_os_string())
+        .unwrap_or_else(|| "output".into())
+}
+
+/// Forward ffmpeg `-progress pipe:1` output to `on_progress` (in steps of 10%)
+fn relay_ffmpeg_progress(
+    reader: impl std::io::BufRead,
+    total_duration: f64,
+    mut on_progress: impl FnMut(i32),
+) {
+    let mut last_progress = 0;
+
+    for line in reader.lines().map_while(Result::ok) {
+        if let Some(current_time) = parse_time_from_progress(&line) {
+            if total_duration > 0.0 {
+                let progress = ((current_time / total_duration) * 100.0).min(99.0) as i32;
+                // Only emit in increments of 10
+                let progress_rounded = (progress / 10) * 10;
+                if progress_rounded > last_progress {
+                    last_progress = progress_rounded;
+                    on_progress(progress_rounded);
+                }
+            }
+        }
+    }
+}
+
+/// Run ffmpeg for one conversion as `job`: with `options` for a video, otherwise with ffmpeg's
+/// defaults for the output's extension. Renders into the job's workspace and only moves the
+/// result to `output_path` once ffmpeg has succeeded. Shared by `convert_media`, `convert_video`
+/// and the conversion queue.
+pub(crate) fn run_conversion(
+    job: &JobHandle,
+    ffmpeg: &Path,
+    input_path: &str,
+    output_path: &str,
+    options: Option<&VideoConvertOptions>,
+    on_progress: impl FnMut(i32),
 ) -> Result<(), String> {
-    use std::io::{BufRead, BufReader};
+    use std::io::BufReader;
     use std::process::Stdio;
 
-    // Get bundled ffmpeg path using platform-specific resolution
-    let ffmpeg = platform::get_ffmpeg_path()?;
+    // Get total duration for progress calculation
+    let total_duration = get_media_duration(ffmpeg, input_path).unwrap_or(0.0);
 
-    // Get total duration
-    let total_duration = get_media_duration(&ffmpeg, &input_path).unwrap_or(0.0);
+    let mut args = match options {
+        Some(options) => video_args(input_path, output_path, options),
+        None => vec!["-i".to_string(), input_path.to_string(), "-y".to_string()],
+    };
 
-    // Emit initial progress
-    let _ = app.emit("conversion-progress", 0);
+    // Progress output
+    args.push("-progress".to_string());
+    args.push("pipe:1".to_string());
+    args.push("-nostats".to_string());
 
-    // Run ffmpeg with progress output
-    let mut child = hidden_command(&ffmpeg)
-        .args([
-            "-i", &input_path,
-            "-y",
-            "-progress", "pipe:1",
-            "-nostats",
-            &output_path
-        ])
+    // Output path, in the job's workspace until ffmpeg has finished
+    let partial_output = job.workspace()?.join(output_file_name(output_path));
+    args.push(partial_output.to_string_lossy().to_string());
+
+    // Run ffmpeg
+    let mut child = hidden_command(ffmpeg)
+        .args(&args)
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .spawn()
         .map_err(|e| e.to_string())?;
+    job.token().attach_child(child.id());
 
     // Read progress from stdout
     if let Some(stdout) = child.stdout.take() {
-        let reader = BufReader::new(stdout);
-        let mut last_progress = 0;
-
-        for line in reader.lines() {
-            if let Ok(line) = line {
-                if let Some(current_time) = parse_time_from_progress(&line) {
-                    if total_duration > 0.0 {
-                        let progress = ((current_time / total_duration) * 100.0).min(99.0) as i32;
-                        // Only emit in increments of 10
-                        let progress_rounded = (progress / 10) * 10;
-                        if progress_rounded > last_progress {
-                            last_progress = progress_rounded;
-                            let _ = app.emit("conversion-progress", progress_rounded);
-                        }
-                    }
-                }
-            }
-        }
+        let mut on_progress = on_progress;
+        relay_ffmpeg_progress(BufReader::new(stdout), total_duration, |percent| {
+            job.report_progress(percent.clamp(0, 100) as u8);
+            on_progress(percent);
+        });
     }
 
     // Wait for process to complete
     let status = child.wait().map_err(|e| e.to_string())?;
+    job.token().detach_child(child.id());
+
+    if job.token().is_cancelled() {
+        return Err("Conversion cancelled".to_string());
+    }
 
     if !status.success() {
-        return Err("Conversion failed".to_string());
+        return Err(match options {
+            Some(_) => "Video conversion failed".to_string(),
+            None => "Conversion failed".to_string(),
+        });
     }
+    workspaces::persist(&partial_output, Path::new(output_path))
+}
+
+#[tauri::command]
+async fn convert_media(
+    app: AppHandle,
+    input_path: String,
+    output_path: String,
+) -> Result<(), String> {
+    let output_path = path_policy::validate_write_path(&app, &output_path)?
+        .to_string_lossy()
+        .to_string();
+
+    // Get bundled ffmpeg path using platform-specific resolution
+    let ffmpeg = platform::get_ffmpeg_path()?;
+
+    let job = jobs::start_job(&app, JobKind::Conversion, format!("Converting {}", input_path));
+
+    // Emit initial progress
+    let _ = app.emit("conversion-progress", 0);
+
+    run_conversion(&job, &ffmpeg, &input_path, &output_path, None, |percent| {
+        let _ = app.emit("conversion-progress", percent);
+    })?;
 
     // Emit completion
+    job.complete();
     let _ = app.emit("conversion-progress", 100);
+    notifications::notify_if_hidden(
+        &app,
+        "Conversion complete",
+        &output_path,
+        NotifyAction::reveal(&output_path),
+    );
     Ok(())
 }
 
 #[tauri::command]
-async fn pick_color(window: tauri::WebviewWindow) -> Result<String, String> {
-    platform::pick_color_impl(window).await
+async fn pick_color(
+    window: tauri::WebviewWindow,
+    sample_size: Option<i32>,
+) -> Result<color_formats::ColorFormats, String> {
+    // Averaging area from the settings unless the caller asks for one
+    let sample_size =
+        sample_size.unwrap_or_else(|| color_loupe::sample_size_setting(window.app_handle()));
+    let hex = color_loupe::pick_color(window, sample_size).await?;
+    color_formats::parse(&hex).map(color_formats::describe)
+}
+
+/// Pick colors until Escape or a right click (see `color_loupe::pick_colors_continuous`)
+#[tauri::command]
+async fn pick_colors_continuous(
+    window: tauri::WebviewWindow,
+    sample_size: Option<i32>,
+) -> Result<Vec<String>, String> {
+    let sample_size =
+        sample_size.unwrap_or_else(|| color_loupe::sample_size_setting(window.app_handle()));
+    color_loupe::pick_colors_continuous(window, sample_size).await
+}
+
+/// Pick a text color and then its background from the screen, returning their WCAG contrast
+#[tauri::command]
+async fn pick_contrast(
+    window: tauri::WebviewWindow,
+    sample_size: Option<i32>,
+) -> Result<color_formats::ContrastResult, String> {
+    let sample_size =
+        sample_size.unwrap_or_else(|| color_loupe::sample_size_setting(window.app_handle()));
+    color_loupe::pick_contrast(window, sample_size).await
 }
 
 // Re-export PortProcess from platform module for the command handler
@@ -510,19 +1310,61 @@ async fn scan_port(port: u16) -> Result<Vec<PortProcess>, String> {
     platform::scan_port_impl(port).await
 }
 
+/// Error from `kill_port_process`. `requires_elevation` means the process belongs to another
+/// user or SYSTEM, so the UI can offer to retry with `elevate` (a UAC / polkit prompt).
+#[derive(Debug, Clone, Serialize)]
+struct KillProcessError {
+    message: String,
+    requires_elevation: bool,
+}
+
+impl From<String> for KillProcessError {
+    fn from(message: String) -> Self {
+        Self {
+            message,
+            requires_elevation: false,
+        }
+    }
+}
+
 #[tauri::command]
-async fn kill_port_process(pid: u32) -> Result<(), String> {
-    platform::kill_port_process_impl(pid).await
+async fn kill_port_process(pid: u32, elevate: Option<bool>) -> Result<(), KillProcessError> {
+    let error = match platform::kill_port_process_impl(pid).await {
+        Ok(()) => return Ok(()),
+        Err(e) if is_access_denied(&e) && !elevation::is_elevated() => e,
+        Err(e) => return Err(e.into()),
+    };
+    if !elevate.unwrap_or(false) {
+        return Err(KillProcessError {
+            message: format!(
+                "Process {} belongs to another user or a system service; administrator rights are needed to kill it",
+                pid
+            ),
+            requires_elevation: true,
+        });
+    }
+
+    log::info!("Retrying kill of process {} with elevation ({})", pid, error);
+    tauri::async_runtime::spawn_blocking(move || {
+        let pid_str = pid.to_string();
+        #[cfg(target_os = "windows")]
+        let (program, args) = ("taskkill", ["/F", "/PID", pid_str.as_str()]);
+        #[cfg(not(target_os = "windows"))]
+        let (program, args) = ("kill", ["-9", pid_str.as_str()]);
+
+        elevation::run_elevated(program, &args, &format!("kill process {}", pid))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??;
+    Ok(())
 }
 
-// Currency conversion response
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct CurrencyResult {
-    pub amount: f64,
-    pub from: String,
-    pub to: String,
-    pub result: f64,
-    pub rate: f64,
+// Permission failures reported by kill/taskkill
+fn is_access_denied(message: &str) -> bool {
+    let lower = message.to_lowercase();
+    lower.contains(&platform::ACCESS_DENIED.to_lowercase())
+        || lower.contains("operation not permitted")
+        || lower.contains("permission denied")
 }
 
 // Video metadata response
@@ -546,46 +1388,6 @@ pub struct VideoConvertOptions {
     pub bitrate: u32, // kbps, 0 for original
 }
 
-#[tauri::command]
-async fn convert_currency(amount: f64, from: String, to: String) -> Result<CurrencyResult, String> {
-    // Use frankfurter.app - free, no API key required
-    let url = format!(
-        "https://api.frankfurter.app/latest?amount={}&from={}&to={}",
-        amount,
-        from.to_uppercase(),
-        to.to_uppercase()
-    );
-
-    let response = reqwest::get(&url)
-        .await
-        .map_err(|e| format!("Failed to fetch rates: {}", e))?;
-
-    if !response.status().is_success() {
-        return Err(format!("API error: {}", response.status()));
-    }
-
-    let data: serde_json::Value = response
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse response: {}", e))?;
-
-    let to_upper = to.to_uppercase();
-    let result_value = data["rates"][&to_upper]
-        .as_f64()
-        .ok_or_else(|| format!("Currency '{}' not found", to_upper))?;
-
-    // Calculate the rate (1 unit of source currency)
-    let rate = result_value / amount;
-
-    Ok(CurrencyResult {
-        amount,
-        from: from.to_uppercase(),
-        to: to_upper,
-        result: result_value,
-        rate,
-    })
-}
-
 #[tauri::command]
 async fn start_text_selection(window: tauri::WebviewWindow) -> Result<(), String> {
     platform::start_text_selection_impl(window).await
@@ -623,41 +1425,41 @@ struct MyMemoryResponseData {
     translatedText: Option<String>,
 }
 
-// Convert whatlang Lang to ISO 639-1 code
-fn lang_to_code(lang: whatlang::Lang) -> &'static str {
+// Convert whatlang Lang to ISO 639-1 code, for the languages the translation engines take
+fn lang_to_code(lang: whatlang::Lang) -> Option<&'static str> {
     use whatlang::Lang::*;
     match lang {
-        Eng => "en",
-        Fra => "fr",
-        Deu => "de",
-        Spa => "es",
-        Por => "pt",
-        Ita => "it",
-        Nld => "nl",
-        Rus => "ru",
-        Ukr => "uk",
-        Pol => "pl",
-        Jpn => "ja",
-        Cmn => "zh",
-        Kor => "ko",
-        Ara => "ar",
-        Hin => "hi",
-        Tur => "tr",
-        Vie => "vi",
-        Tha => "th",
-        Ind => "id",
-        Ces => "cs",
-        Ell => "el",
-        Heb => "he",
-        Swe => "sv",
-        Dan => "da",
-        Fin => "fi",
-        Nob => "no",
-        Hun => "hu",
-        Ron => "ro",
-        Slk => "sk",
-        Bul => "bg",
-        _ => "en", // Default fallback
+        Eng => Some("en"),
+        Fra => Some("fr"),
+        Deu => Some("de"),
+        Spa => Some("es"),
+        Por => Some("pt"),
+        Ita => Some("it"),
+        Nld => Some("nl"),
+        Rus => Some("ru"),
+        Ukr => Some("uk"),
+        Pol => Some("pl"),
+        Jpn => Some("ja"),
+        Cmn => Some("zh"),
+        Kor => Some("ko"),
+        Ara => Some("ar"),
+        Hin => Some("hi"),
+        Tur => Some("tr"),
+        Vie => Some("vi"),
+        Tha => Some("th"),
+        Ind => Some("id"),
+        Ces => Some("cs"),
+        Ell => Some("el"),
+        Heb => Some("he"),
+        Swe => Some("sv"),
+        Dan => Some("da"),
+        Fin => Some("fi"),
+        Nob => Some("no"),
+        Hun => Some("hu"),
+        Ron => Some("ro"),
+        Slk => Some("sk"),
+        Bul => Some("bg"),
+        _ => None,
     }
 }
 
@@ -739,35 +1541,92 @@ fn get_language_name(code: &str) -> String {
 }
 
 #[tauri::command]
-async fn translate_text(text: String, target_lang: String) -> Result<TranslationResult, String> {
+async fn translate_text(
+    app: AppHandle,
+    text: String,
+    target_lang: String,
+) -> Result<TranslationResult, String> {
     // Detect language locally using whatlang
     let detected = whatlang::detect(&text);
 
     let (source_code, detected_name) = match detected {
         Some(info) => {
-            let code = lang_to_code(info.lang());
+            let code = lang_to_code(info.lang()).unwrap_or("en");
             let name = lang_to_name(info.lang());
             (code, name.to_string())
         }
-        None => ("en", "Unknown".to_string()), // Default to English if detection fails
+        None => ("en", "Unknown".to_string()), // Default to English if detection fails
+    };
+
+    // If source and target are the same, just return the original text
+    if source_code == target_lang {
+        return Ok(TranslationResult {
+            translated_text: text,
+            detected_language: detected_name,
+            target_language: get_language_name(&target_lang),
+        });
+    }
+
+    let translated_text = translate_with_engine(&app, &text, source_code, &target_lang).await?;
+
+    let result = TranslationResult {
+        translated_text,
+        detected_language: detected_name,
+        target_language: get_language_name(&target_lang),
+    };
+    translation_history::record(&app, &text, &result);
+    Ok(result)
+}
+
+// Translate with the engine chosen in Settings, keeping glossary terms
+async fn translate_with_engine(
+    app: &AppHandle,
+    text: &str,
+    source_code: &str,
+    target_lang: &str,
+) -> Result<String, String> {
+    // Glossary terms go to the engine as placeholders and come back as the preferred translation
+    let glossary = glossary::load_for(app, source_code, target_lang);
+    let protected = glossary::protect(text, &glossary.iter().collect::<Vec<_>>());
+    let input = &protected.text;
+    if let Some(cached) = translation_cache::get(app, input, source_code, target_lang) {
+        return Ok(glossary::restore(&cached, &protected.replacements));
+    }
+
+    let engine = app.state::<AppState>().settings.lock().unwrap().translation_engine;
+    let translated_text = match engine {
+        // The text never leaves the computer; models are downloaded on first use
+        offline_translation::TranslationEngine::Offline => {
+            offline_translation::translate(input, source_code, target_lang, true).await?
+        }
+        // Without a connection, fall back to the models already installed
+        offline_translation::TranslationEngine::Online => {
+            translation_cache::wait_for_online_slot().await;
+            match translate_online(input, source_code, target_lang).await {
+                Ok(translated) => translated,
+                Err(e) => offline_translation::translate(input, source_code, target_lang, false)
+                    .await
+                    .map_err(|_| e)?,
+            }
+        }
     };
+    translation_cache::store(app, input, source_code, target_lang, &translated_text);
+    Ok(glossary::restore(&translated_text, &protected.replacements))
+}
 
-    // If source and target are the same, just return the original text
-    if source_code == target_lang {
-        return Ok(TranslationResult {
-            translated_text: text,
-            detected_language: detected_name,
-            target_language: get_language_name(&target_lang),
-        });
-    }
-
+// Translate with the MyMemory API
+async fn translate_online(
+    text: &str,
+    source_code: &str,
+    target_lang: &str,
+) -> Result<String, String> {
     let client = reqwest::Client::builder()
         .timeout(std::time::Duration::from_secs(10))
         .build()
         .map_err(|e| e.to_string())?;
 
     // URL encode the text
-    let encoded_text = urlencoding::encode(&text);
+    let encoded_text = urlencoding::encode(text);
 
     // MyMemory API endpoint
     let url = format!(
@@ -790,25 +1649,20 @@ async fn translate_text(text: String, target_lang: String) -> Result<Translation
         .await
         .map_err(|e| format!("Failed to parse response: {}", e))?;
 
-    let translated_text = data
-        .responseData
+    data.responseData
         .and_then(|r| r.translatedText)
-        .ok_or_else(|| "No translation received".to_string())?;
-
-    Ok(TranslationResult {
-        translated_text,
-        detected_language: detected_name,
-        target_language: get_language_name(&target_lang),
-    })
+        .ok_or_else(|| "No translation received".to_string())
 }
 
 #[tauri::command]
-async fn save_binary_file(path: String, data: Vec<u8>) -> Result<(), String> {
+async fn save_binary_file(app: AppHandle, path: String, data: Vec<u8>) -> Result<(), String> {
+    let path = path_policy::validate_write_path(&app, &path)?;
     fs::write(&path, &data).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-async fn save_text_file(path: String, content: String) -> Result<(), String> {
+async fn save_text_file(app: AppHandle, path: String, content: String) -> Result<(), String> {
+    let path = path_policy::validate_write_path(&app, &path)?;
     fs::write(&path, content).map_err(|e| e.to_string())
 }
 
@@ -929,24 +1783,8 @@ async fn get_video_metadata(path: String) -> Result<VideoMetadata, String> {
     })
 }
 
-#[tauri::command]
-async fn convert_video(
-    app: AppHandle,
-    input_path: String,
-    output_path: String,
-    options: VideoConvertOptions,
-) -> Result<(), String> {
-    use std::io::{BufRead, BufReader};
-    use std::process::Stdio;
-
-    let ffmpeg = platform::get_ffmpeg_path()?;
-
-    // Get total duration for progress calculation
-    let total_duration = get_media_duration(&ffmpeg, &input_path).unwrap_or(0.0);
-
-    // Emit initial progress
-    let _ = app.emit("conversion-progress", 0);
-
+/// ffmpeg arguments (before the progress and output ones) to convert a video with `options`
+fn video_args(input_path: &str, output_path: &str, options: &VideoConvertOptions) -> Vec<String> {
     // Detect output format from extension
     let output_ext = output_path
         .rsplit('.')
@@ -960,7 +1798,7 @@ async fn convert_video(
     // Build ffmpeg arguments
     let mut args: Vec<String> = vec![
         "-i".to_string(),
-        input_path.clone(),
+        input_path.to_string(),
         "-y".to_string(), // Overwrite output
     ];
 
@@ -1093,52 +1931,39 @@ async fn convert_video(
         }
     }
 
-    // Progress output
-    args.push("-progress".to_string());
-    args.push("pipe:1".to_string());
-    args.push("-nostats".to_string());
-
-    // Output path
-    args.push(output_path.clone());
+    args
+}
 
-    // Run ffmpeg
-    let mut child = hidden_command(&ffmpeg)
-        .args(&args)
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()
-        .map_err(|e| e.to_string())?;
+#[tauri::command]
+async fn convert_video(
+    app: AppHandle,
+    input_path: String,
+    output_path: String,
+    options: VideoConvertOptions,
+) -> Result<(), String> {
+    let output_path = path_policy::validate_write_path(&app, &output_path)?
+        .to_string_lossy()
+        .to_string();
 
-    // Read progress from stdout
-    if let Some(stdout) = child.stdout.take() {
-        let reader = BufReader::new(stdout);
-        let mut last_progress = 0;
-
-        for line in reader.lines() {
-            if let Ok(line) = line {
-                if let Some(current_time) = parse_time_from_progress(&line) {
-                    if total_duration > 0.0 {
-                        let progress = ((current_time / total_duration) * 100.0).min(99.0) as i32;
-                        let progress_rounded = (progress / 10) * 10;
-                        if progress_rounded > last_progress {
-                            last_progress = progress_rounded;
-                            let _ = app.emit("conversion-progress", progress_rounded);
-                        }
-                    }
-                }
-            }
-        }
-    }
+    let ffmpeg = platform::get_ffmpeg_path()?;
 
-    // Wait for process to complete
-    let status = child.wait().map_err(|e| e.to_string())?;
+    // Emit initial progress
+    let _ = app.emit("conversion-progress", 0);
 
-    if !status.success() {
-        return Err("Video conversion failed".to_string());
-    }
+    let job = jobs::start_job(&app, JobKind::Conversion, format!("Converting {}", input_path));
+    run_conversion(&job, &ffmpeg, &input_path, &output_path, Some(&options), |percent| {
+        let _ = app.emit("conversion-progress", percent);
+    })?;
 
     // Emit completion
+    job.complete();
     let _ = app.emit("conversion-progress", 100);
+    notifications::notify_if_hidden(
+        &app,
+        "Conversion complete",
+        &output_path,
+        NotifyAction::reveal(&output_path),
+    );
     Ok(())
 }
 
@@ -1167,6 +1992,12 @@ pub struct GitDownloadProgress {
     pub processed_files: Option<u32>,
 }
 
+// Send a git download's progress, and record it on the job for the tray
+fn emit_git_progress(app: &AppHandle, job: &JobHandle, progress: GitDownloadProgress) {
+    job.report_progress(progress.percent.min(100) as u8);
+    let _ = app.emit("git-download-progress", progress);
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GitDownloadResult {
     pub success: bool,
@@ -1312,6 +2143,7 @@ async fn download_files_parallel(
     output_dir: &PathBuf,
     options: &GitDownloadOptions,
     app: &AppHandle,
+    job: &JobHandle,
 ) -> Result<(u32, u64), String> {
     use futures_util::stream::{self, StreamExt};
     use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
@@ -1332,15 +2164,13 @@ async fn download_files_parallel(
             let downloaded_count = downloaded_count.clone();
             let total_size = total_size.clone();
             let app = app.clone();
+            let token = job.token().clone();
             let flatten = options.flatten_structure;
 
             async move {
                 // Check for cancellation
-                {
-                    let state = app.state::<AppState>();
-                    if *state.git_download_cancelled.lock().unwrap() {
-                        return Err("Download cancelled".to_string());
-                    }
+                if token.is_cancelled() {
+                    return Err("Download cancelled".to_string());
                 }
 
                 // Calculate output path
@@ -1387,6 +2217,7 @@ async fn download_files_parallel(
                     .map_err(|e| format!("Failed to read {}: {}", relative_path, e))?;
 
                 let size = bytes.len() as u64;
+                job.count_download(size);
 
                 // Write to file
                 fs::write(&output_file_path, &bytes)
@@ -1398,8 +2229,9 @@ async fn download_files_parallel(
 
                 // Emit progress (10-95%)
                 let percent = 10 + ((count as f64 / total_files as f64) * 85.0) as u32;
-                let _ = app.emit(
-                    "git-download-progress",
+                emit_git_progress(
+                    &app,
+                    job,
                     GitDownloadProgress {
                         stage: "downloading".to_string(),
                         percent: percent.min(95),
@@ -1440,7 +2272,10 @@ async fn download_via_zipball(
     url_info: &GitHubUrlInfo,
     output_path: &str,
     options: &GitDownloadOptions,
+    job: &JobHandle,
 ) -> Result<GitDownloadResult, String> {
+    let token = job.token();
+
     // Build the archive URL
     let archive_url = format!(
         "https://api.github.com/repos/{}/{}/zipball/{}",
@@ -1448,8 +2283,9 @@ async fn download_via_zipball(
     );
 
     // Download the ZIP archive
-    let _ = app.emit(
-        "git-download-progress",
+    emit_git_progress(
+        app,
+        job,
         GitDownloadProgress {
             stage: "downloading".to_string(),
             percent: 10,
@@ -1475,10 +2311,8 @@ async fn download_via_zipball(
         return Err(format!("GitHub API error: {}", response.status()));
     }
 
-    // Create temp file for the ZIP
-    let temp_dir =
-        tempfile::tempdir().map_err(|e| format!("Failed to create temp directory: {}", e))?;
-    let temp_path = temp_dir.path().join("download.zip");
+    // The ZIP goes in the job's workspace
+    let temp_path = job.workspace()?.join("download.zip");
 
     // Stream download directly to file (memory efficient)
     use futures_util::StreamExt;
@@ -1493,23 +2327,22 @@ async fn download_via_zipball(
 
     while let Some(chunk_result) = stream.next().await {
         // Check for cancellation
-        {
-            let state = app.state::<AppState>();
-            if *state.git_download_cancelled.lock().unwrap() {
-                return Err("Download cancelled".to_string());
-            }
+        if token.is_cancelled() {
+            return Err("Download cancelled".to_string());
         }
 
         let chunk = chunk_result.map_err(|e| format!("Download error: {}", e))?;
         file.write_all(&chunk)
             .map_err(|e| format!("Write error: {}", e))?;
         downloaded += chunk.len() as u64;
+        job.count_download(chunk.len() as u64);
 
         // Emit progress every 500ms
         if last_progress_update.elapsed().as_millis() > 500 {
             let estimated_progress = (15.0 + (downloaded as f64 / 1_000_000.0).min(35.0)) as u32;
-            let _ = app.emit(
-                "git-download-progress",
+            emit_git_progress(
+                app,
+                job,
                 GitDownloadProgress {
                     stage: "downloading".to_string(),
                     percent: estimated_progress.min(50),
@@ -1525,8 +2358,9 @@ async fn download_via_zipball(
     drop(file);
 
     // Emit download complete
-    let _ = app.emit(
-        "git-download-progress",
+    emit_git_progress(
+        app,
+        job,
         GitDownloadProgress {
             stage: "downloading".to_string(),
             percent: 50,
@@ -1537,8 +2371,9 @@ async fn download_via_zipball(
     );
 
     // Extract the ZIP
-    let _ = app.emit(
-        "git-download-progress",
+    emit_git_progress(
+        app,
+        job,
         GitDownloadProgress {
             stage: "extracting".to_string(),
             percent: 55,
@@ -1608,8 +2443,9 @@ async fn download_via_zipball(
         ));
     }
 
-    let _ = app.emit(
-        "git-download-progress",
+    emit_git_progress(
+        app,
+        job,
         GitDownloadProgress {
             stage: "extracting".to_string(),
             percent: 60,
@@ -1625,11 +2461,8 @@ async fn download_via_zipball(
 
     for i in 0..archive.len() {
         // Check for cancellation
-        {
-            let state = app.state::<AppState>();
-            if *state.git_download_cancelled.lock().unwrap() {
-                return Err("Download cancelled".to_string());
-            }
+        if token.is_cancelled() {
+            return Err("Download cancelled".to_string());
         }
 
         let mut entry = archive.by_index(i).map_err(|e| e.to_string())?;
@@ -1668,8 +2501,9 @@ async fn download_via_zipball(
         extracted_count += 1;
 
         let progress = 60 + ((extracted_count as f64 / matching_files.max(1) as f64) * 35.0) as u32;
-        let _ = app.emit(
-            "git-download-progress",
+        emit_git_progress(
+            app,
+            job,
             GitDownloadProgress {
                 stage: "extracting".to_string(),
                 percent: progress.min(95),
@@ -1681,8 +2515,9 @@ async fn download_via_zipball(
     }
 
     // Emit completion
-    let _ = app.emit(
-        "git-download-progress",
+    emit_git_progress(
+        app,
+        job,
         GitDownloadProgress {
             stage: "complete".to_string(),
             percent: 100,
@@ -1707,15 +2542,21 @@ async fn download_github_folder(
     output_path: String,
     options: GitDownloadOptions,
 ) -> Result<GitDownloadResult, String> {
-    // Reset cancellation flag
-    {
-        let state = app.state::<AppState>();
-        *state.git_download_cancelled.lock().unwrap() = false;
-    }
+    let output_path = path_policy::validate_output_dir(&app, &output_path)?
+        .to_string_lossy()
+        .to_string();
+
+    transfers::check_cap(&app)?;
+    let job = jobs::start_job(
+        &app,
+        JobKind::GitDownload,
+        format!("Downloading {}/{}", url_info.owner, url_info.repo),
+    );
 
     // Emit initial progress
-    let _ = app.emit(
-        "git-download-progress",
+    emit_git_progress(
+        &app,
+        &job,
         GitDownloadProgress {
             stage: "fetching".to_string(),
             percent: 0,
@@ -1733,10 +2574,11 @@ async fn download_github_folder(
 
     // If a specific path is provided, use the efficient Contents API approach
     // Otherwise, use zipball for full repository downloads (more efficient for full repos)
-    if !url_info.path.is_empty() {
+    let result = if !url_info.path.is_empty() {
         // Use Contents API for folder-specific downloads
-        let _ = app.emit(
-            "git-download-progress",
+        emit_git_progress(
+            &app,
+            &job,
             GitDownloadProgress {
                 stage: "listing".to_string(),
                 percent: 5,
@@ -1767,8 +2609,9 @@ async fn download_github_folder(
                 }
 
                 let total_files = files.len() as u32;
-                let _ = app.emit(
-                    "git-download-progress",
+                emit_git_progress(
+                    &app,
+                    &job,
                     GitDownloadProgress {
                         stage: "downloading".to_string(),
                         percent: 10,
@@ -1804,12 +2647,14 @@ async fn download_github_folder(
                     &final_output,
                     &options,
                     &app,
+                    &job,
                 )
                 .await?;
 
                 // Emit completion
-                let _ = app.emit(
-                    "git-download-progress",
+                emit_git_progress(
+                    &app,
+                    &job,
                     GitDownloadProgress {
                         stage: "complete".to_string(),
                         percent: 100,
@@ -1836,19 +2681,46 @@ async fn download_github_folder(
                 }
 
                 // Fall back to zipball method
-                download_via_zipball(&app, &client, &url_info, &output_path, &options).await
+                download_via_zipball(
+                    &app,
+                    &client,
+                    &url_info,
+                    &output_path,
+                    &options,
+                    &job,
+                )
+                .await
             }
         }
     } else {
         // Use zipball for full repository downloads
-        download_via_zipball(&app, &client, &url_info, &output_path, &options).await
+        download_via_zipball(
+            &app,
+            &client,
+            &url_info,
+            &output_path,
+            &options,
+            &job,
+        )
+        .await
+    };
+
+    if let Ok(download) = &result {
+        job.complete();
+        notifications::notify_if_hidden(
+            &app,
+            "Download complete",
+            &format!("{} files from {}/{}", download.files_count, url_info.owner, url_info.repo),
+            NotifyAction::reveal(&download.output_path),
+        );
     }
+    result
 }
 
 #[tauri::command]
 async fn cancel_git_download(app: AppHandle) -> Result<(), String> {
     let state = app.state::<AppState>();
-    *state.git_download_cancelled.lock().unwrap() = true;
+    state.jobs.cancel_kind(JobKind::GitDownload);
     Ok(())
 }
 
@@ -1944,8 +2816,9 @@ fn is_valid_path(path: String) -> bool {
 
 /// Load command history from disk
 #[tauri::command]
-fn load_command_history(app: AppHandle) -> Vec<String> {
-    load_command_history_from_file(&app).commands
+fn load_command_history(app: AppHandle) -> Result<Vec<String>, String> {
+    app_lock::ensure_unlocked()?;
+    Ok(load_command_history_from_file(&app).commands)
 }
 
 /// Save command history to disk
@@ -2117,8 +2990,6 @@ struct TimerTick {
 
 #[tauri::command]
 async fn start_timer(app: AppHandle, seconds: u64, label: Option<String>) -> Result<(), String> {
-    use tauri_plugin_notification::NotificationExt;
-
     let state = app.state::<AppState>();
 
     // Check if timer is already active
@@ -2174,11 +3045,12 @@ async fn start_timer(app: AppHandle, seconds: u64, label: Option<String>) -> Res
         });
 
         // Send notification
-        let _ = app_handle.notification()
-            .builder()
-            .title("Timer Complete")
-            .body(&format!("{} - Time's up!", label_clone))
-            .show();
+        notifications::notify(
+            &app_handle,
+            "Timer Complete",
+            &format!("{} - Time's up!", label_clone),
+            NotifyAction::ShowWindow,
+        );
 
         // Reset timer state
         {
@@ -2236,24 +3108,29 @@ fn get_timer_remaining(app: AppHandle) -> Result<Option<u64>, String> {
 
 #[tauri::command]
 async fn open_folder_in_explorer(path: String) -> Result<(), String> {
+    open_in_file_manager(&path)
+}
+
+/// Open a folder in the platform file manager
+fn open_in_file_manager(path: &str) -> Result<(), String> {
     #[cfg(target_os = "windows")]
     {
         std::process::Command::new("explorer")
-            .arg(&path)
+            .arg(path)
             .spawn()
             .map_err(|e| format!("Failed to open folder: {}", e))?;
     }
     #[cfg(target_os = "macos")]
     {
         std::process::Command::new("open")
-            .arg(&path)
+            .arg(path)
             .spawn()
             .map_err(|e| format!("Failed to open folder: {}", e))?;
     }
     #[cfg(target_os = "linux")]
     {
         std::process::Command::new("xdg-open")
-            .arg(&path)
+            .arg(path)
             .spawn()
             .map_err(|e| format!("Failed to open folder: {}", e))?;
     }
@@ -2355,21 +3232,15 @@ async fn download_youtube_video(
     output_path: String,
     options: YouTubeDownloadOptions,
 ) -> Result<String, String> {
-    // Reset cancellation flag
-    {
-        let state = app.state::<AppState>();
-        *state.youtube_download_cancelled.lock().unwrap() = false;
-        *state.youtube_download_process.lock().unwrap() = None;
-    }
+    let output_path = path_policy::validate_output_dir(&app, &output_path)?
+        .to_string_lossy()
+        .to_string();
 
     let ytdlp_path = platform::get_ytdlp_path()?;
     let format_selector = build_format_selector(&options.quality, &options.mode);
 
-    // Build output template
-    let output_template = PathBuf::from(&output_path)
-        .join("%(title)s.%(ext)s")
-        .to_string_lossy()
-        .to_string();
+    // Build output template, relative to the output directory given with --paths below
+    let output_template = "%(title)s.%(ext)s".to_string();
 
     // Emit initial progress
     let _ = app.emit(
@@ -2409,6 +3280,17 @@ async fn download_youtube_video(
         args.push("mp3".to_string());
     }
 
+    transfers::check_cap(&app)?;
+    let job = jobs::start_job(&app, JobKind::YoutubeDownload, format!("Downloading {}", url));
+
+    // Fragments and .part files go in the job's workspace, only the finished file in the output
+    // directory; yt-dlp moves it there when it's done
+    args.push("--paths".to_string());
+    args.push(format!("home:{}", output_path));
+    args.push("--paths".to_string());
+    let workspace = job.workspace()?;
+    args.push(format!("temp:{}", workspace.display()));
+
     args.push(url);
 
     // Spawn the yt-dlp process
@@ -2419,77 +3301,36 @@ async fn download_youtube_video(
         .spawn()
         .map_err(|e| format!("Failed to start yt-dlp: {}", e))?;
 
-    // Store the process ID for cancellation
-    {
-        let state = app.state::<AppState>();
-        *state.youtube_download_process.lock().unwrap() = Some(child.id());
-    }
+    // Cancelling the job kills the process
+    job.token().attach_child(child.id());
 
     let stdout = child.stdout.take()
         .ok_or("Failed to capture stdout")?;
 
     // Read and parse progress from stdout
-    use std::io::{BufRead, BufReader};
-    let reader = BufReader::new(stdout);
-    let mut final_output_path: Option<String> = None;
-
-    for line in reader.lines() {
-        // Check for cancellation
-        {
-            let state = app.state::<AppState>();
-            if *state.youtube_download_cancelled.lock().unwrap() {
-                let _ = child.kill();
-                return Err("Download cancelled".to_string());
-            }
-        }
-
-        if let Ok(line) = line {
-            // Parse progress line
-            // Format: [download]  45.2% of 245.60MiB at 5.23MiB/s ETA 02:15
-            if line.contains("[download]") && line.contains("%") {
-                let progress = parse_ytdlp_progress(&line);
-                let _ = app.emit("youtube-download-progress", progress);
-            }
-            // Check for destination line
-            // Format: [download] Destination: /path/to/file.mp4
-            else if line.contains("[download] Destination:") {
-                if let Some(path) = line.split("Destination:").nth(1) {
-                    final_output_path = Some(path.trim().to_string());
-                }
-            }
-            // Check for merge line which indicates final file
-            // Format: [Merger] Merging formats into "/path/to/file.mp4"
-            else if line.contains("[Merger] Merging formats into") {
-                if let Some(start) = line.find('"') {
-                    if let Some(end) = line.rfind('"') {
-                        if start < end {
-                            final_output_path = Some(line[start+1..end].to_string());
-                        }
-                    }
-                }
-            }
-            // Check for already downloaded
-            else if line.contains("has already been downloaded") {
-                if let Some(_start) = line.find('[') {
-                    if let Some(path_start) = line.find("] ") {
-                        let path_part = &line[path_start+2..];
-                        if let Some(end) = path_part.find(" has already") {
-                            final_output_path = Some(path_part[..end].to_string());
-                        }
-                    }
-                }
-            }
+    let reader = std::io::BufReader::new(stdout);
+    let final_output_path = match relay_ytdlp_output(&app, reader, job.token(), |percent| {
+        job.report_progress(percent)
+    }) {
+        Ok(path) => path,
+        Err(e) => {
+            let _ = child.kill();
+            job.count_download(workspaces::size_of(workspace));
+            return Err(e);
         }
-    }
+    };
 
     // Wait for process to complete
     let status = child.wait()
         .map_err(|e| format!("Failed to wait for yt-dlp: {}", e))?;
+    // A download counts for what it used even if it doesn't finish: the partial files left in
+    // the workspace now, plus the finished file once it's in the output directory
+    job.count_download(workspaces::size_of(workspace));
 
-    // Clear the process ID
-    {
-        let state = app.state::<AppState>();
-        *state.youtube_download_process.lock().unwrap() = None;
+    job.token().detach_child(child.id());
+
+    if job.token().is_cancelled() {
+        return Err("Download cancelled".to_string());
     }
 
     if !status.success() {
@@ -2497,7 +3338,14 @@ async fn download_youtube_video(
     }
 
     // Emit completion
-    let result_path = final_output_path.clone().unwrap_or_else(|| output_path.clone());
+    job.complete();
+    // yt-dlp reports the file in the workspace; it has been moved to the output directory since
+    let result_path = final_output_path
+        .as_deref()
+        .and_then(|path| Path::new(path).file_name())
+        .map(|name| Path::new(&output_path).join(name).to_string_lossy().to_string())
+        .unwrap_or_else(|| output_path.clone());
+    job.count_download(fs::metadata(&result_path).map_or(0, |m| m.len()));
     let _ = app.emit(
         "youtube-download-progress",
         YouTubeDownloadProgress {
@@ -2510,10 +3358,83 @@ async fn download_youtube_video(
             output_path: Some(result_path.clone()),
         },
     );
+    notifications::notify_if_hidden(
+        &app,
+        "Download complete",
+        &result_path,
+        NotifyAction::reveal(&result_path),
+    );
 
     Ok(result_path)
 }
 
+/// Forward yt-dlp output as `youtube-download-progress` events (and each percent to
+/// `on_percent`) until it exits or the job is cancelled. Returns the final file path if yt-dlp
+/// reported one.
+fn relay_ytdlp_output<R: Runtime>(
+    app: &AppHandle<R>,
+    reader: impl std::io::BufRead,
+    token: &CancellationToken,
+    mut on_percent: impl FnMut(u8),
+) -> Result<Option<String>, String> {
+    let mut final_output_path: Option<String> = None;
+
+    for line in reader.lines() {
+        // Check for cancellation
+        if token.is_cancelled() {
+            return Err("Download cancelled".to_string());
+        }
+
+        if let Ok(line) = line {
+            // Parse progress line
+            // Format: [download]  45.2% of 245.60MiB at 5.23MiB/s ETA 02:15
+            if line.contains("[download]") && line.contains("%") {
+                let progress = parse_ytdlp_progress(&line);
+                on_percent(progress.percent.clamp(0.0, 100.0) as u8);
+                let _ = app.emit("youtube-download-progress", progress);
+            } else if let Some(path) = parse_ytdlp_output_path(&line) {
+                final_output_path = Some(path);
+            }
+        }
+    }
+
+    Ok(final_output_path)
+}
+
+/// Extract the output file from yt-dlp's destination / merger / already-downloaded lines
+fn parse_ytdlp_output_path(line: &str) -> Option<String> {
+    // Check for destination line
+    // Format: [download] Destination: /path/to/file.mp4
+    if line.contains("[download] Destination:") {
+        if let Some(path) = line.split("Destination:").nth(1) {
+            return Some(path.trim().to_string());
+        }
+    }
+    // Check for merge line which indicates final file
+    // Format: [Merger] Merging formats into "/path/to/file.mp4"
+    else if line.contains("[Merger] Merging formats into") {
+        if let Some(start) = line.find('"') {
+            if let Some(end) = line.rfind('"') {
+                if start < end {
+                    return Some(line[start+1..end].to_string());
+                }
+            }
+        }
+    }
+    // Check for already downloaded
+    else if line.contains("has already been downloaded") {
+        if let Some(_start) = line.find('[') {
+            if let Some(path_start) = line.find("] ") {
+                let path_part = &line[path_start+2..];
+                if let Some(end) = path_part.find(" has already") {
+                    return Some(path_part[..end].to_string());
+                }
+            }
+        }
+    }
+    None
+}
+
 fn parse_ytdlp_progress(line: &str) -> YouTubeDownloadProgress {
     // Parse: [download]  45.2% of 245.60MiB at 5.23MiB/s ETA 02:15
     let mut percent: f32 = 0.0;
@@ -2569,25 +3490,9 @@ fn parse_ytdlp_progress(line: &str) -> YouTubeDownloadProgress {
 
 #[tauri::command]
 async fn cancel_youtube_download(app: AppHandle) -> Result<(), String> {
+    // Cancelling the job also kills the yt-dlp process
     let state = app.state::<AppState>();
-    *state.youtube_download_cancelled.lock().unwrap() = true;
-
-    // Try to kill the process if it exists
-    if let Some(pid) = *state.youtube_download_process.lock().unwrap() {
-        #[cfg(target_os = "windows")]
-        {
-            let _ = std::process::Command::new("taskkill")
-                .args(["/F", "/PID", &pid.to_string()])
-                .output();
-        }
-        #[cfg(not(target_os = "windows"))]
-        {
-            let _ = std::process::Command::new("kill")
-                .args(["-9", &pid.to_string()])
-                .output();
-        }
-    }
-
+    state.jobs.cancel_kind(JobKind::YoutubeDownload);
     Ok(())
 }
 
@@ -2603,29 +3508,10 @@ fn toggle_window(app: &AppHandle) {
             let _ = window.hide();
         } else {
             // Position window on the monitor where the cursor is located
-            #[cfg(target_os = "windows")]
-            {
-                // Get current window size
-                if let Ok(size) = window.outer_size() {
-                    if let Some((x, y)) = platform::get_centered_position_on_cursor_monitor(
-                        size.width as i32,
-                        size.height as i32,
-                    ) {
-                        use tauri::PhysicalPosition;
-                        let _ = window.set_position(PhysicalPosition::new(x, y));
-                    } else {
-                        let _ = window.center();
-                    }
-                } else {
-                    let _ = window.center();
-                }
-            }
-            #[cfg(not(target_os = "windows"))]
-            {
-                let _ = window.center();
-            }
+            window_placement::place_main_window(app, &window);
             let _ = window.show();
             let _ = window.set_focus();
+            apply_always_on_top(app, state.settings.lock().unwrap().always_on_top);
 
             // Force foreground focus on Windows to ensure immediate keyboard capture
             // This is critical for spotlight/command-palette UX where users start typing immediately
@@ -2637,6 +3523,7 @@ fn toggle_window(app: &AppHandle) {
             }
 
             let _ = app.emit("focus-search", ());
+            suggestions::emit_clipboard_suggestion(app);
         }
     } else {
         log::warn!("Main window not found");
@@ -2646,20 +3533,27 @@ fn toggle_window(app: &AppHandle) {
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
+        // Must be registered first so a second launch exits before setting anything up
+        .plugin(tauri_plugin_single_instance::init(
+            shell_integration::handle_second_instance,
+        ))
         .plugin(tauri_plugin_clipboard_manager::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_notification::init())
         .manage(AppState {
             current_shortcut: Mutex::new(None),
+            active_hotkey: Mutex::new(None),
             quick_translation_shortcut: Mutex::new(None),
+            screen_translation_shortcut: Mutex::new(None),
+            voice_shortcut: Mutex::new(None),
             settings: Mutex::new(Settings::default()),
             auto_hide_enabled: Mutex::new(true),
             is_dragging: Mutex::new(false),
             tray_handle: Mutex::new(None),
             app_ready: Mutex::new(false),
-            git_download_cancelled: Mutex::new(false),
-            youtube_download_cancelled: Mutex::new(false),
-            youtube_download_process: Mutex::new(None),
+            jobs: jobs::JobManager::default(),
+            triggers: Mutex::new(triggers::TriggerRuntime::default()),
+            launch_request: Mutex::new(None),
             timer_state: Mutex::new(TimerState {
                 active: false,
                 end_time: None,
@@ -2683,36 +3577,30 @@ pub fn run() {
                 *state.settings.lock().unwrap() = settings.clone();
             }
 
+            // Rewrite the autostart entry, so entries from older versions gain --hidden and
+            // it follows the executable if the app was moved
+            if settings.launch_at_startup {
+                if let Err(e) =
+                    platform::set_launch_at_startup_impl(true, settings.autostart_method)
+                {
+                    log::warn!("Failed to update autostart entry: {}", e);
+                }
+            }
+
             // Create system tray
-            let hotkey_display = format!(
-                "{}+{}",
-                settings.hotkey_modifiers.join("+"),
-                settings.hotkey_key
-            );
-            let quit_item =
-                tauri::menu::MenuItemBuilder::with_id("quit", "Quit BunchaTools").build(app)?;
-            let show_item = tauri::menu::MenuItemBuilder::with_id(
-                "show",
-                format!("Show ({})", hotkey_display),
-            )
-            .build(app)?;
-            let menu = tauri::menu::MenuBuilder::new(app)
-                .item(&show_item)
-                .separator()
-                .item(&quit_item)
-                .build()?;
+            let menu = tray_menu::build_tray_menu(app.handle())?;
 
             let tray = TrayIconBuilder::with_id("main-tray")
                 .tooltip("BunchaTools")
                 .icon(app.default_window_icon().unwrap().clone())
                 .menu(&menu)
                 .show_menu_on_left_click(false)
-                .on_menu_event(|app, event| match event.id.as_ref() {
-                    "quit" => app.exit(0),
-                    "show" => toggle_window(app),
-                    _ => {}
-                })
+                .on_menu_event(|app, event| tray_menu::handle_menu_event(app, event.id.as_ref()))
                 .on_tray_icon_event(|tray, event| {
+                    // Any click counts as having seen a failed job
+                    if let TrayIconEvent::Click { .. } = event {
+                        tray_status::acknowledge_failure(tray.app_handle());
+                    }
                     if let TrayIconEvent::Click {
                         button: MouseButton::Left,
                         button_state: MouseButtonState::Up,
@@ -2722,23 +3610,57 @@ pub fn run() {
                         toggle_window(tray.app_handle());
                     }
                 })
-                .build(app)?;
+                .build(app);
 
-            // Store tray handle - initially hidden until app is ready
-            {
-                let state = app.state::<AppState>();
-                let _ = tray.set_visible(false);
-                *state.tray_handle.lock().unwrap() = Some(tray);
+            // Store tray handle - initially hidden until app is ready. Without a tray the
+            // app still works through the hotkey, so a failure is only reported.
+            match tray {
+                Ok(tray) => {
+                    let state = app.state::<AppState>();
+                    let _ = tray.set_visible(false);
+                    *state.tray_handle.lock().unwrap() = Some(tray);
+                }
+                Err(e) => startup_health::record(
+                    "tray",
+                    "The tray icon couldn't be created".to_string(),
+                    e.to_string(),
+                ),
             }
+            tray_status::init(app.handle());
+            pomodoro::init(app.handle());
+            network::init(app.handle());
+            power::init(app.handle());
+            app_lock::init(app.handle());
+            session_state::init(app.handle());
+            time_tracking::init(app.handle());
+            habits::init(app.handle());
+            night_light::init(app.handle());
+            jump_list::init(app.handle());
 
             // Register global shortcut with handler
             let app_handle = app.handle().clone();
             app.handle().plugin(
                 tauri_plugin_global_shortcut::Builder::new()
                     .with_handler(move |_app, shortcut, event| {
+                        // Push-to-talk listens while the voice hotkey is held down
+                        let voice_shortcut = *_app.state::<AppState>().voice_shortcut.lock().unwrap();
+                        if voice_shortcut.as_ref() == Some(shortcut) {
+                            voice_input::on_hotkey(_app, event.state() == ShortcutState::Pressed);
+                            return;
+                        }
+
                         if event.state() == ShortcutState::Pressed {
                             let state = _app.state::<AppState>();
 
+                            // Stop hotkey of a running auto-clicker
+                            if auto_clicker::is_stop_shortcut(shortcut) {
+                                let app_handle_clone = app_handle.clone();
+                                tauri::async_runtime::spawn(async move {
+                                    auto_clicker::stop(&app_handle_clone);
+                                });
+                                return;
+                            }
+
                             // Check for main window toggle shortcut
                             let current_shortcut = state.current_shortcut.lock().unwrap().clone();
                             if let Some(current) = current_shortcut {
@@ -2748,6 +3670,17 @@ pub fn run() {
                                     let app_handle_clone = app_handle.clone();
                                     tauri::async_runtime::spawn(async move {
                                         toggle_window(&app_handle_clone);
+
+                                        // Opening the window starts waiting for a chord key
+                                        let shown = app_handle_clone
+                                            .get_webview_window("main")
+                                            .and_then(|w| w.is_visible().ok())
+                                            .unwrap_or(false);
+                                        if shown {
+                                            start_chord(&app_handle_clone);
+                                        } else {
+                                            cancel_chord(&app_handle_clone);
+                                        }
                                     });
                                     return;
                                 }
@@ -2757,9 +3690,24 @@ pub fn run() {
                             let quick_translation_shortcut = state.quick_translation_shortcut.lock().unwrap().clone();
                             if let Some(qt_shortcut) = quick_translation_shortcut {
                                 if shortcut == &qt_shortcut {
+                                    let app_handle_clone = app_handle.clone();
+                                    if state.settings.lock().unwrap().quick_translation_replace {
+                                        inline_translation::trigger(app_handle_clone);
+                                    } else {
+                                        tauri::async_runtime::spawn(async move {
+                                            let _ = app_handle_clone.emit("trigger-quick-translation", ());
+                                        });
+                                    }
+                                }
+                            }
+
+                            // Check for screen translation shortcut
+                            let screen_translation_shortcut = *state.screen_translation_shortcut.lock().unwrap();
+                            if let Some(st_shortcut) = screen_translation_shortcut {
+                                if shortcut == &st_shortcut {
                                     let app_handle_clone = app_handle.clone();
                                     tauri::async_runtime::spawn(async move {
-                                        let _ = app_handle_clone.emit("trigger-quick-translation", ());
+                                        let _ = app_handle_clone.emit("trigger-screen-translation", ());
                                     });
                                 }
                             }
@@ -2768,13 +3716,28 @@ pub fn run() {
                     .build(),
             )?;
 
-            // Register the initial main shortcut
-            if let Some(shortcut) =
-                parse_shortcut(&settings.hotkey_modifiers, &settings.hotkey_key)
-            {
-                app.global_shortcut().register(shortcut.clone())?;
-                let state = app.state::<AppState>();
-                *state.current_shortcut.lock().unwrap() = Some(shortcut);
+            // Register the initial main shortcut, or a fallback if another app holds it.
+            // That shouldn't stop startup; the UI explains it from the startup health report.
+            match register_main_shortcut(app.handle(), &settings) {
+                Ok(active) if active.key != settings.hotkey_key
+                    || active.modifiers != settings.hotkey_modifiers =>
+                {
+                    startup_health::record(
+                        "hotkey",
+                        format!(
+                            "{} is taken by another app, so {} opens BunchaTools instead",
+                            hotkey_label(&settings.hotkey_modifiers, &settings.hotkey_key),
+                            active.label()
+                        ),
+                        "Registered a fallback hotkey".to_string(),
+                    );
+                }
+                Ok(_) => {}
+                Err(e) => startup_health::record(
+                    "hotkey",
+                    format!("{}, so the launcher hotkey doesn't work. Pick a different one in Settings.", e),
+                    e,
+                ),
             }
 
             // Register the initial quick translation shortcut (if set)
@@ -2783,12 +3746,91 @@ pub fn run() {
                     &settings.quick_translation_hotkey_modifiers,
                     &settings.quick_translation_hotkey_key,
                 ) {
-                    app.global_shortcut().register(shortcut.clone())?;
-                    let state = app.state::<AppState>();
-                    *state.quick_translation_shortcut.lock().unwrap() = Some(shortcut);
+                    match app.global_shortcut().register(shortcut) {
+                        Ok(()) => {
+                            let state = app.state::<AppState>();
+                            *state.quick_translation_shortcut.lock().unwrap() = Some(shortcut);
+                        }
+                        Err(e) => startup_health::record_hotkey(
+                            "quick translation",
+                            &hotkey_label(
+                                &settings.quick_translation_hotkey_modifiers,
+                                &settings.quick_translation_hotkey_key,
+                            ),
+                            e.to_string(),
+                        ),
+                    }
+                }
+            }
+
+            // Register the initial screen translation shortcut (if set)
+            if !settings.screen_translation_hotkey_key.is_empty() {
+                if let Some(shortcut) = parse_shortcut(
+                    &settings.screen_translation_hotkey_modifiers,
+                    &settings.screen_translation_hotkey_key,
+                ) {
+                    match app.global_shortcut().register(shortcut) {
+                        Ok(()) => {
+                            let state = app.state::<AppState>();
+                            *state.screen_translation_shortcut.lock().unwrap() = Some(shortcut);
+                        }
+                        Err(e) => startup_health::record_hotkey(
+                            "screen translation",
+                            &hotkey_label(
+                                &settings.screen_translation_hotkey_modifiers,
+                                &settings.screen_translation_hotkey_key,
+                            ),
+                            e.to_string(),
+                        ),
+                    }
                 }
             }
 
+            // Register the initial voice input shortcut (if set)
+            if !settings.voice_hotkey_key.is_empty() {
+                if let Some(shortcut) =
+                    parse_shortcut(&settings.voice_hotkey_modifiers, &settings.voice_hotkey_key)
+                {
+                    match app.global_shortcut().register(shortcut) {
+                        Ok(()) => {
+                            let state = app.state::<AppState>();
+                            *state.voice_shortcut.lock().unwrap() = Some(shortcut);
+                        }
+                        Err(e) => startup_health::record_hotkey(
+                            "voice input",
+                            &hotkey_label(&settings.voice_hotkey_modifiers, &settings.voice_hotkey_key),
+                            e.to_string(),
+                        ),
+                    }
+                }
+            }
+
+            // The tray menu was built before the hotkey was known
+            tray_menu::rebuild_tray_menu(app.handle());
+
+            // Install the mouse trigger (if set); failure here shouldn't block startup
+            if let Err(e) = update_mouse_trigger(app.handle(), &settings) {
+                log::warn!("Failed to set up mouse trigger: {}", e);
+            }
+
+            // Start Stream Deck / MIDI listeners (if enabled)
+            triggers::apply_trigger_settings(app.handle(), &settings.external_triggers);
+
+            // Advertise and accept shared clipboards (if enabled)
+            clipboard_share::apply_settings(app.handle(), &settings.clipboard_share);
+
+            // Listen for webhooks (if enabled)
+            webhook::apply_settings(app.handle(), &settings.webhook);
+
+            // Pick up hand edits and synced copies of settings.json
+            settings_watch::init(app.handle());
+
+            // Check ffmpeg / yt-dlp and tell the UI about anything that failed above
+            startup_health::init(app.handle());
+
+            // Files passed by the shell are picked up by the frontend once it has loaded
+            shell_integration::store_launch_request(app.handle());
+
             // Handle window events - use if let to avoid panic if window isn't ready
             if let Some(window) = app.get_webview_window("main") {
                 let _ = window.set_background_color(Some(Color(0, 0, 0, 0)));
@@ -2796,12 +3838,16 @@ pub fn run() {
                 let window_clone = window.clone();
                 let app_handle_for_blur = app.handle().clone();
                 window.on_window_event(move |event| {
+                    if let tauri::WindowEvent::Moved(position) = event {
+                        window_placement::record_move(&app_handle_for_blur, &window_clone, *position);
+                    }
                     if let tauri::WindowEvent::Focused(false) = event {
                         let state = app_handle_for_blur.state::<AppState>();
                         let auto_hide = *state.auto_hide_enabled.lock().unwrap();
                         let is_dragging = *state.is_dragging.lock().unwrap();
-                        // Don't hide if dragging or auto_hide is disabled
-                        if auto_hide && !is_dragging {
+                        let pinned = state.settings.lock().unwrap().always_on_top;
+                        // Don't hide if dragging, pinned or auto_hide is disabled
+                        if auto_hide && !is_dragging && !pinned {
                             let _ = window_clone.hide();
                         }
                     }
@@ -2816,16 +3862,171 @@ pub fn run() {
             hide_window,
             show_window,
             pick_color,
+            pick_colors_continuous,
+            pick_contrast,
+            color_formats::get_color_formats,
+            color_formats::convert_color,
+            color_formats::check_contrast,
+            palette_export::export_palette,
+            color_history::get_color_history,
+            color_history::delete_color_history_item,
+            color_history::clear_color_history,
             get_settings,
             save_settings,
             get_launch_at_startup,
             set_auto_hide,
+            set_always_on_top,
+            window_placement::reset_window_positions,
+            tool_windows::open_tool_window,
+            tool_windows::show_overlay_timer,
+            tool_windows::get_overlay_timer,
+            tool_windows::close_overlay_timer,
+            tool_windows::list_displays,
+            tool_windows::open_calibration_window,
+            tool_windows::close_calibration_window,
+            reference::lookup_reference,
+            banner::generate_banner,
+            banner::list_banner_fonts,
+            text_transforms::transform_text,
+            text_transforms::list_text_transforms,
+            text_transforms::transform_clipboard,
+            pomodoro::start_pomodoro,
+            pomodoro::stop_pomodoro,
+            pomodoro::get_pomodoro_status,
+            network::get_network_status,
+            power::get_power_status,
+            tray_status::get_system_theme,
+            wake_on_lan::send_wol,
+            wake_on_lan::list_wol_devices,
+            wake_on_lan::save_wol_device,
+            wake_on_lan::remove_wol_device,
+            wake_on_lan::wake_device,
+            session_state::get_session_state,
+            keep_awake::set_keep_awake,
+            keep_awake::keep_awake,
+            keep_awake::get_keep_awake_status,
+            auto_clicker::start_auto_clicker,
+            auto_clicker::stop_auto_clicker,
+            auto_clicker::get_auto_clicker,
+            random_tools::random_choice,
+            random_tools::roll_dice,
+            random_tools::flip_coin,
+            random_tools::get_random_history,
+            random_tools::clear_random_history,
+            power_actions::lock_screen,
+            power_actions::sleep_system,
+            power_actions::restart_system,
+            power_actions::shutdown_system,
+            power_actions::cancel_shutdown,
+            power_actions::get_scheduled_shutdown,
+            qr_payloads::build_qr_payload,
+            number_words::number_to_words,
+            locale_preview::preview_locale_formatting,
+            startup_health::get_startup_health,
+            get_active_hotkey,
+            screen_recording::start_screen_recording,
+            screen_recording::stop_screen_recording,
+            screen_recording::get_screen_recording,
+            calculator::get_calc_history,
+            calculator::record_calculation,
+            calculator::calc_memory,
+            calculator::clear_calc_history,
+            calculator::export_calc_tape,
+            screen_translate::translate_screen_region,
+            time_tracking::start_time_entry,
+            time_tracking::stop_time_entry,
+            time_tracking::get_running_time_entry,
+            time_tracking::get_time_report,
+            time_tracking::export_time_entries,
+            annotation::start_screen_annotation,
+            annotation::get_annotation_image,
+            annotation::save_annotated_image,
+            annotation::copy_annotated_image,
+            annotation::annotate_image,
+            annotation::close_screen_annotation,
+            habits::get_habits,
+            habits::add_habit,
+            habits::update_habit,
+            habits::delete_habit,
+            habits::check_in_habit,
+            habits::undo_habit_check_in,
+            habits::get_streaks,
+            qr_scan::scan_qr_from_screen,
+            countries::lookup_country,
+            countries::get_public_holidays,
+            sun_times::get_sun_times,
+            night_light::get_night_light_status,
+            night_light::set_night_light,
+            offline_translation::get_offline_translation_models,
+            offline_translation::download_translation_models,
+            snippets::get_snippets,
+            snippets::save_snippet,
+            snippets::set_snippet_encrypted,
+            snippets::delete_snippet,
+            snippets::save_snippet_transform,
+            snippets::delete_snippet_transform,
+            snippets::expand_snippet,
+            snippets::expand_placeholders,
+            translation_history::get_translation_history,
+            translation_history::clear_translation_history,
+            secrets::get_secrets_status,
+            secrets::unlock_secrets,
+            secrets::lock_secrets,
+            app_lock::get_app_lock_status,
+            app_lock::lock_app,
+            app_lock::unlock_app,
+            app_lock::set_app_passcode,
+            clipboard_share::get_clipboard_devices,
+            clipboard_share::send_clipboard_to,
+            glossary::get_glossary,
+            glossary::save_glossary_entry,
+            glossary::delete_glossary_entry,
+            network_discovery::discover_network_devices,
+            webhook::get_webhook_status,
+            webhook::start_webhook_listener,
+            webhook::stop_webhook_listener,
+            language_detection::detect_language,
+            file_translation::translate_file,
+            translation_cache::clear_translation_cache,
+            transcription::transcribe_media,
+            dictionary::define_word,
+            voice_input::start_voice_capture,
+            voice_input::stop_voice_capture,
+            audio_recording::list_audio_devices,
+            audio_recording::start_audio_recording,
+            audio_recording::stop_audio_recording,
+            audio_recording::get_audio_recording,
+            external_windows::list_external_windows,
+            external_windows::set_external_window_topmost,
+            external_windows::set_external_window_opacity,
+            units::convert_unit,
+            units::convert_unit_query,
+            onboarding::get_tried_tools,
+            onboarding::get_tool_tips,
+            onboarding::dismiss_tip,
+            desktop_shortcuts::create_desktop_shortcut,
+            time_zones::convert_time,
+            time_zones::now_in,
+            time_zones::parse_epoch,
+            time_zones::convert_time_query,
+            expressions::evaluate_expression,
+            transfers::get_transfer_stats,
+            transfers::set_transfer_cap,
+            conversion_queue::enqueue_conversion,
+            conversion_queue::get_conversion_queue,
+            conversion_queue::move_queued_conversion,
+            conversion_queue::remove_queued_conversion,
+            project_files::list_gitignore_templates,
+            project_files::generate_gitignore,
+            project_files::generate_editorconfig,
             set_dragging,
             mark_app_ready,
             convert_media,
             scan_port,
             kill_port_process,
-            convert_currency,
+            currency::convert_currency,
+            currency::get_rate_history,
+            currency::convert_currency_multi,
             start_text_selection,
             start_text_selection_from_hotkey,
             translate_text,
@@ -2848,9 +4049,28 @@ pub fn run() {
             launch_app,
             read_clipboard,
             write_clipboard,
+            clipboard_math::sum_clipboard_numbers,
             start_timer,
             cancel_timer,
-            get_timer_remaining
+            get_timer_remaining,
+            path_policy::pick_output_directory,
+            path_policy::pick_save_path,
+            path_policy::get_allowed_paths,
+            path_policy::remove_allowed_path,
+            elevation::is_process_elevated,
+            jobs::list_jobs,
+            jobs::cancel_job,
+            jobs::cancel_conversion,
+            jobs::pause_conversion,
+            jobs::resume_conversion,
+            jobs::run_job_on_battery,
+            tray_menu::record_tool_use,
+            triggers::list_midi_devices,
+            shell_integration::take_launch_request,
+            file_associations::read_download_list,
+            file_associations::read_palette_file,
+            file_associations::read_workflow_file,
+            file_associations::run_workflow
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,1070 @@
+// Locale formatting preview for i18n debugging: how numbers, currency amounts, dates and
+// plural categories come out in a given locale. Separators, grouping, currency and date
+// patterns, month names and plural rules are taken from CLDR (the data ICU ships), trimmed
+// to the locales below. A locale that isn't listed falls back to one with the same language.
+//
+// Samples are strings so that visible fraction digits survive ("1" is `one` in English,
+// "1.0" is `other`): plain decimals ("1234.5", "-0.75") or dates ("2026-03-09",
+// "2026-03-09T14:05").
+
+use serde::Serialize;
+
+// CLDR plural operands: `i` integer digits, `v` number of visible fraction digits,
+// `f` the visible fraction digits as an integer
+#[derive(Debug, Clone, Copy)]
+struct Operands {
+    i: u64,
+    v: usize,
+    f: u64,
+}
+
+impl Operands {
+    // `n = k` in CLDR rule syntax: the value itself, whatever the visible digits
+    fn n_is(&self, k: u64) -> bool {
+        self.f == 0 && self.i == k
+    }
+}
+
+type PluralRule = fn(&Operands) -> &'static str;
+
+struct LocaleData {
+    id: &'static str,
+    name: &'static str,
+    decimal: &'static str,
+    group: &'static str,
+    minus: &'static str,
+    indian_grouping: bool, // 12,34,567 instead of 1,234,567
+    min_grouping: usize,   // 2 leaves four-digit numbers ungrouped
+    currency: &'static str,
+    currency_digits: usize,
+    currency_pattern: &'static str, // `¤` symbol, `#` the number
+    short_date: &'static str,
+    long_date: &'static str,
+    time: &'static str,
+    am_pm: [&'static str; 2],
+    months: [&'static str; 12], // as used in long dates (genitive where the language has one)
+    cardinal: PluralRule,
+    ordinal: PluralRule,
+}
+
+// ============================================================================
+// Plural rules (CLDR)
+// ============================================================================
+
+// "many" for exact millions in Romance languages ("un millón de euros")
+fn is_million_multiple(o: &Operands) -> bool {
+    o.v == 0 && o.i != 0 && o.i % 1_000_000 == 0
+}
+
+fn other_only(_: &Operands) -> &'static str {
+    "other"
+}
+
+fn one_if_i1_v0(o: &Operands) -> &'static str {
+    if o.i == 1 && o.v == 0 {
+        "one"
+    } else {
+        "other"
+    }
+}
+
+fn italian(o: &Operands) -> &'static str {
+    match o {
+        o if o.i == 1 && o.v == 0 => "one",
+        o if is_million_multiple(o) => "many",
+        _ => "other",
+    }
+}
+
+fn spanish(o: &Operands) -> &'static str {
+    match o {
+        o if o.n_is(1) => "one",
+        o if is_million_multiple(o) => "many",
+        _ => "other",
+    }
+}
+
+// French and Brazilian Portuguese: 0 and 1 (and 1.5) are singular
+fn french(o: &Operands) -> &'static str {
+    match o {
+        o if o.i <= 1 => "one",
+        o if is_million_multiple(o) => "many",
+        _ => "other",
+    }
+}
+
+fn polish(o: &Operands) -> &'static str {
+    let (i10, i100) = (o.i % 10, o.i % 100);
+    match o {
+        o if o.v != 0 => "other",
+        o if o.i == 1 => "one",
+        _ if (2..=4).contains(&i10) && !(12..=14).contains(&i100) => "few",
+        _ => "many",
+    }
+}
+
+fn east_slavic(o: &Operands) -> &'static str {
+    let (i10, i100) = (o.i % 10, o.i % 100);
+    match o {
+        o if o.v != 0 => "other",
+        _ if i10 == 1 && i100 != 11 => "one",
+        _ if (2..=4).contains(&i10) && !(12..=14).contains(&i100) => "few",
+        _ => "many",
+    }
+}
+
+fn czech(o: &Operands) -> &'static str {
+    match o {
+        o if o.v != 0 => "many",
+        o if o.i == 1 => "one",
+        o if (2..=4).contains(&o.i) => "few",
+        _ => "other",
+    }
+}
+
+fn one_if_n1(o: &Operands) -> &'static str {
+    if o.n_is(1) {
+        "one"
+    } else {
+        "other"
+    }
+}
+
+fn hindi(o: &Operands) -> &'static str {
+    if o.i == 0 || o.n_is(1) {
+        "one"
+    } else {
+        "other"
+    }
+}
+
+fn english_ordinal(o: &Operands) -> &'static str {
+    let (n10, n100) = (o.i % 10, o.i % 100);
+    match (n10, n100) {
+        (1, n) if n != 11 => "one",
+        (2, n) if n != 12 => "two",
+        (3, n) if n != 13 => "few",
+        _ => "other",
+    }
+}
+
+fn swedish_ordinal(o: &Operands) -> &'static str {
+    if matches!(o.i % 10, 1 | 2) && !matches!(o.i % 100, 11 | 12) {
+        "one"
+    } else {
+        "other"
+    }
+}
+
+fn italian_ordinal(o: &Operands) -> &'static str {
+    if matches!(o.i, 8 | 11 | 80 | 800) {
+        "many"
+    } else {
+        "other"
+    }
+}
+
+fn hindi_ordinal(o: &Operands) -> &'static str {
+    match o.i {
+        1 => "one",
+        2 | 3 => "two",
+        4 => "few",
+        6 => "many",
+        _ => "other",
+    }
+}
+
+// ============================================================================
+// Locale data (CLDR)
+// ============================================================================
+
+const EN_MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+// ja / zh / ko only use numeric months
+const NUMERIC_MONTHS: [&str; 12] = [
+    "1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "12",
+];
+
+const NBSP: &str = "\u{a0}";
+const NARROW_NBSP: &str = "\u{202f}";
+
+const LOCALES: &[LocaleData] = &[
+    LocaleData {
+        id: "en-US",
+        name: "English (United States)",
+        decimal: ".",
+        group: ",",
+        minus: "-",
+        indian_grouping: false,
+        min_grouping: 1,
+        currency: "$",
+        currency_digits: 2,
+        currency_pattern: "¤#",
+        short_date: "M/d/yy",
+        long_date: "MMMM d, y",
+        time: "h:mm\u{202f}a",
+        am_pm: ["AM", "PM"],
+        months: EN_MONTHS,
+        cardinal: one_if_i1_v0,
+        ordinal: english_ordinal,
+    },
+    LocaleData {
+        id: "en-GB",
+        name: "English (United Kingdom)",
+        decimal: ".",
+        group: ",",
+        minus: "-",
+        indian_grouping: false,
+        min_grouping: 1,
+        currency: "£",
+        currency_digits: 2,
+        currency_pattern: "¤#",
+        short_date: "dd/MM/y",
+        long_date: "d MMMM y",
+        time: "HH:mm",
+        am_pm: ["am", "pm"],
+        months: EN_MONTHS,
+        cardinal: one_if_i1_v0,
+        ordinal: english_ordinal,
+    },
+    LocaleData {
+        id: "en-IN",
+        name: "English (India)",
+        decimal: ".",
+        group: ",",
+        minus: "-",
+        indian_grouping: true,
+        min_grouping: 1,
+        currency: "₹",
+        currency_digits: 2,
+        currency_pattern: "¤#",
+        short_date: "dd/MM/yy",
+        long_date: "d MMMM y",
+        time: "h:mm\u{202f}a",
+        am_pm: ["am", "pm"],
+        months: EN_MONTHS,
+        cardinal: one_if_i1_v0,
+        ordinal: english_ordinal,
+    },
+    LocaleData {
+        id: "hi-IN",
+        name: "Hindi (India)",
+        decimal: ".",
+        group: ",",
+        minus: "-",
+        indian_grouping: true,
+        min_grouping: 1,
+        currency: "₹",
+        currency_digits: 2,
+        currency_pattern: "¤#",
+        short_date: "d/M/yy",
+        long_date: "d MMMM y",
+        time: "h:mm a",
+        am_pm: ["am", "pm"],
+        months: [
+            "जनवरी",
+            "फ़रवरी",
+            "मार्च",
+            "अप्रैल",
+            "मई",
+            "जून",
+            "जुलाई",
+            "अगस्त",
+            "सितंबर",
+            "अक्तूबर",
+            "नवंबर",
+            "दिसंबर",
+        ],
+        cardinal: hindi,
+        ordinal: hindi_ordinal,
+    },
+    LocaleData {
+        id: "de-DE",
+        name: "German (Germany)",
+        decimal: ",",
+        group: ".",
+        minus: "-",
+        indian_grouping: false,
+        min_grouping: 1,
+        currency: "€",
+        currency_digits: 2,
+        currency_pattern: "#\u{a0}¤",
+        short_date: "dd.MM.yy",
+        long_date: "d. MMMM y",
+        time: "HH:mm",
+        am_pm: ["AM", "PM"],
+        months: [
+            "Januar",
+            "Februar",
+            "März",
+            "April",
+            "Mai",
+            "Juni",
+            "Juli",
+            "August",
+            "September",
+            "Oktober",
+            "November",
+            "Dezember",
+        ],
+        cardinal: one_if_i1_v0,
+        ordinal: other_only,
+    },
+    LocaleData {
+        id: "fr-FR",
+        name: "French (France)",
+        decimal: ",",
+        group: NARROW_NBSP,
+        minus: "-",
+        indian_grouping: false,
+        min_grouping: 1,
+        currency: "€",
+        currency_digits: 2,
+        currency_pattern: "#\u{a0}¤",
+        short_date: "dd/MM/y",
+        long_date: "d MMMM y",
+        time: "HH:mm",
+        am_pm: ["AM", "PM"],
+        months: [
+            "janvier",
+            "février",
+            "mars",
+            "avril",
+            "mai",
+            "juin",
+            "juillet",
+            "août",
+            "septembre",
+            "octobre",
+            "novembre",
+            "décembre",
+        ],
+        cardinal: french,
+        ordinal: one_if_n1,
+    },
+    LocaleData {
+        id: "es-ES",
+        name: "Spanish (Spain)",
+        decimal: ",",
+        group: ".",
+        minus: "-",
+        indian_grouping: false,
+        min_grouping: 2,
+        currency: "€",
+        currency_digits: 2,
+        currency_pattern: "#\u{a0}¤",
+        short_date: "d/M/yy",
+        long_date: "d 'de' MMMM 'de' y",
+        time: "H:mm",
+        am_pm: ["a.\u{a0}m.", "p.\u{a0}m."],
+        months: [
+            "enero",
+            "febrero",
+            "marzo",
+            "abril",
+            "mayo",
+            "junio",
+            "julio",
+            "agosto",
+            "septiembre",
+            "octubre",
+            "noviembre",
+            "diciembre",
+        ],
+        cardinal: spanish,
+        ordinal: other_only,
+    },
+    LocaleData {
+        id: "it-IT",
+        name: "Italian (Italy)",
+        decimal: ",",
+        group: ".",
+        minus: "-",
+        indian_grouping: false,
+        min_grouping: 1,
+        currency: "€",
+        currency_digits: 2,
+        currency_pattern: "#\u{a0}¤",
+        short_date: "dd/MM/yy",
+        long_date: "d MMMM y",
+        time: "HH:mm",
+        am_pm: ["AM", "PM"],
+        months: [
+            "gennaio",
+            "febbraio",
+            "marzo",
+            "aprile",
+            "maggio",
+            "giugno",
+            "luglio",
+            "agosto",
+            "settembre",
+            "ottobre",
+            "novembre",
+            "dicembre",
+        ],
+        cardinal: italian,
+        ordinal: italian_ordinal,
+    },
+    LocaleData {
+        id: "pt-BR",
+        name: "Portuguese (Brazil)",
+        decimal: ",",
+        group: ".",
+        minus: "-",
+        indian_grouping: false,
+        min_grouping: 1,
+        currency: "R$",
+        currency_digits: 2,
+        currency_pattern: "¤\u{a0}#",
+        short_date: "dd/MM/y",
+        long_date: "d 'de' MMMM 'de' y",
+        time: "HH:mm",
+        am_pm: ["AM", "PM"],
+        months: [
+            "janeiro",
+            "fevereiro",
+            "março",
+            "abril",
+            "maio",
+            "junho",
+            "julho",
+            "agosto",
+            "setembro",
+            "outubro",
+            "novembro",
+            "dezembro",
+        ],
+        cardinal: french,
+        ordinal: other_only,
+    },
+    LocaleData {
+        id: "nl-NL",
+        name: "Dutch (Netherlands)",
+        decimal: ",",
+        group: ".",
+        minus: "-",
+        indian_grouping: false,
+        min_grouping: 1,
+        currency: "€",
+        currency_digits: 2,
+        currency_pattern: "¤\u{a0}#",
+        short_date: "dd-MM-y",
+        long_date: "d MMMM y",
+        time: "HH:mm",
+        am_pm: ["a.m.", "p.m."],
+        months: [
+            "januari",
+            "februari",
+            "maart",
+            "april",
+            "mei",
+            "juni",
+            "juli",
+            "augustus",
+            "september",
+            "oktober",
+            "november",
+            "december",
+        ],
+        cardinal: one_if_i1_v0,
+        ordinal: other_only,
+    },
+    LocaleData {
+        id: "sv-SE",
+        name: "Swedish (Sweden)",
+        decimal: ",",
+        group: NBSP,
+        minus: "\u{2212}",
+        indian_grouping: false,
+        min_grouping: 1,
+        currency: "kr",
+        currency_digits: 2,
+        currency_pattern: "#\u{a0}¤",
+        short_date: "y-MM-dd",
+        long_date: "d MMMM y",
+        time: "HH:mm",
+        am_pm: ["fm", "em"],
+        months: [
+            "januari",
+            "februari",
+            "mars",
+            "april",
+            "maj",
+            "juni",
+            "juli",
+            "augusti",
+            "september",
+            "oktober",
+            "november",
+            "december",
+        ],
+        cardinal: one_if_i1_v0,
+        ordinal: swedish_ordinal,
+    },
+    LocaleData {
+        id: "pl-PL",
+        name: "Polish (Poland)",
+        decimal: ",",
+        group: NBSP,
+        minus: "-",
+        indian_grouping: false,
+        min_grouping: 2,
+        currency: "zł",
+        currency_digits: 2,
+        currency_pattern: "#\u{a0}¤",
+        short_date: "d.MM.y",
+        long_date: "d MMMM y",
+        time: "HH:mm",
+        am_pm: ["AM", "PM"],
+        months: [
+            "stycznia",
+            "lutego",
+            "marca",
+            "kwietnia",
+            "maja",
+            "czerwca",
+            "lipca",
+            "sierpnia",
+            "września",
+            "października",
+            "listopada",
+            "grudnia",
+        ],
+        cardinal: polish,
+        ordinal: other_only,
+    },
+    LocaleData {
+        id: "ru-RU",
+        name: "Russian (Russia)",
+        decimal: ",",
+        group: NBSP,
+        minus: "-",
+        indian_grouping: false,
+        min_grouping: 1,
+        currency: "₽",
+        currency_digits: 2,
+        currency_pattern: "#\u{a0}¤",
+        short_date: "dd.MM.y",
+        long_date: "d MMMM y 'г'.",
+        time: "HH:mm",
+        am_pm: ["AM", "PM"],
+        months: [
+            "января",
+            "февраля",
+            "марта",
+            "апреля",
+            "мая",
+            "июня",
+            "июля",
+            "августа",
+            "сентября",
+            "октября",
+            "ноября",
+            "декабря",
+        ],
+        cardinal: east_slavic,
+        ordinal: other_only,
+    },
+    LocaleData {
+        id: "uk-UA",
+        name: "Ukrainian (Ukraine)",
+        decimal: ",",
+        group: NBSP,
+        minus: "-",
+        indian_grouping: false,
+        min_grouping: 1,
+        currency: "₴",
+        currency_digits: 2,
+        currency_pattern: "#\u{a0}¤",
+        short_date: "dd.MM.yy",
+        long_date: "d MMMM y 'р'.",
+        time: "HH:mm",
+        am_pm: ["дп", "пп"],
+        months: [
+            "січня",
+            "лютого",
+            "березня",
+            "квітня",
+            "травня",
+            "червня",
+            "липня",
+            "серпня",
+            "вересня",
+            "жовтня",
+            "листопада",
+            "грудня",
+        ],
+        cardinal: east_slavic,
+        ordinal: other_only,
+    },
+    LocaleData {
+        id: "cs-CZ",
+        name: "Czech (Czechia)",
+        decimal: ",",
+        group: NBSP,
+        minus: "-",
+        indian_grouping: false,
+        min_grouping: 1,
+        currency: "Kč",
+        currency_digits: 2,
+        currency_pattern: "#\u{a0}¤",
+        short_date: "dd.MM.yy",
+        long_date: "d. MMMM y",
+        time: "H:mm",
+        am_pm: ["dop.", "odp."],
+        months: [
+            "ledna",
+            "února",
+            "března",
+            "dubna",
+            "května",
+            "června",
+            "července",
+            "srpna",
+            "září",
+            "října",
+            "listopadu",
+            "prosince",
+        ],
+        cardinal: czech,
+        ordinal: other_only,
+    },
+    LocaleData {
+        id: "tr-TR",
+        name: "Turkish (Türkiye)",
+        decimal: ",",
+        group: ".",
+        minus: "-",
+        indian_grouping: false,
+        min_grouping: 1,
+        currency: "₺",
+        currency_digits: 2,
+        currency_pattern: "¤#",
+        short_date: "d.MM.y",
+        long_date: "d MMMM y",
+        time: "HH:mm",
+        am_pm: ["ÖÖ", "ÖS"],
+        months: [
+            "Ocak", "Şubat", "Mart", "Nisan", "Mayıs", "Haziran", "Temmuz", "Ağustos", "Eylül",
+            "Ekim", "Kasım", "Aralık",
+        ],
+        cardinal: one_if_n1,
+        ordinal: other_only,
+    },
+    LocaleData {
+        id: "ja-JP",
+        name: "Japanese (Japan)",
+        decimal: ".",
+        group: ",",
+        minus: "-",
+        indian_grouping: false,
+        min_grouping: 1,
+        currency: "￥",
+        currency_digits: 0,
+        currency_pattern: "¤#",
+        short_date: "y/MM/dd",
+        long_date: "y年M月d日",
+        time: "H:mm",
+        am_pm: ["午前", "午後"],
+        months: NUMERIC_MONTHS,
+        cardinal: other_only,
+        ordinal: other_only,
+    },
+    LocaleData {
+        id: "zh-CN",
+        name: "Chinese (China)",
+        decimal: ".",
+        group: ",",
+        minus: "-",
+        indian_grouping: false,
+        min_grouping: 1,
+        currency: "¥",
+        currency_digits: 2,
+        currency_pattern: "¤#",
+        short_date: "y/M/d",
+        long_date: "y年M月d日",
+        time: "HH:mm",
+        am_pm: ["上午", "下午"],
+        months: NUMERIC_MONTHS,
+        cardinal: other_only,
+        ordinal: other_only,
+    },
+    LocaleData {
+        id: "ko-KR",
+        name: "Korean (South Korea)",
+        decimal: ".",
+        group: ",",
+        minus: "-",
+        indian_grouping: false,
+        min_grouping: 1,
+        currency: "₩",
+        currency_digits: 0,
+        currency_pattern: "¤#",
+        short_date: "yy. M. d.",
+        long_date: "y년 M월 d일",
+        time: "a h:mm",
+        am_pm: ["오전", "오후"],
+        months: NUMERIC_MONTHS,
+        cardinal: other_only,
+        ordinal: other_only,
+    },
+];
+
+// ============================================================================
+// Formatting
+// ============================================================================
+
+/// A sample number as written: sign, integer digits and visible fraction digits
+#[derive(Debug, Clone, PartialEq)]
+struct Decimal {
+    negative: bool,
+    int: String,
+    frac: String,
+}
+
+fn parse_decimal(text: &str) -> Option<Decimal> {
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text),
+    };
+    let (int, frac) = digits.split_once('.').unwrap_or((digits, ""));
+    let all_digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+    if int.is_empty() || int.len() > 18 || !all_digits(int) || !all_digits(frac) {
+        return None;
+    }
+    let int = int.trim_start_matches('0');
+    Some(Decimal {
+        negative,
+        int: if int.is_empty() { "0" } else { int }.to_string(),
+        frac: frac.to_string(),
+    })
+}
+
+impl Decimal {
+    fn operands(&self) -> Operands {
+        let frac = &self.frac[..self.frac.len().min(18)];
+        Operands {
+            i: self.int.parse().unwrap_or(0),
+            v: self.frac.len(),
+            f: frac.parse().unwrap_or(0),
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.int == "0" && self.frac.chars().all(|c| c == '0')
+    }
+
+    // Exactly `digits` fraction digits, rounding half away from zero like `Intl.NumberFormat`
+    fn round_to(&self, digits: usize) -> Decimal {
+        if self.frac.len() <= digits {
+            return Decimal {
+                frac: format!("{:0<width$}", self.frac, width = digits),
+                ..self.clone()
+            };
+        }
+        let mut all: Vec<u8> = format!("{}{}", self.int, &self.frac[..digits]).into_bytes();
+        if self.frac.as_bytes()[digits] >= b'5' {
+            let mut carry = true;
+            for digit in all.iter_mut().rev() {
+                if *digit == b'9' {
+                    *digit = b'0';
+                } else {
+                    *digit += 1;
+                    carry = false;
+                    break;
+                }
+            }
+            if carry {
+                all.insert(0, b'1');
+            }
+        }
+        let all = String::from_utf8(all).unwrap_or_default();
+        let (int, frac) = all.split_at(all.len() - digits);
+        Decimal {
+            negative: self.negative,
+            int: int.to_string(),
+            frac: frac.to_string(),
+        }
+    }
+}
+
+fn group_digits(int: &str, locale: &LocaleData) -> String {
+    if int.len() < 4 || int.len() < 3 + locale.min_grouping {
+        return int.to_string();
+    }
+    let (head, last) = int.split_at(int.len() - 3);
+    let size = if locale.indian_grouping { 2 } else { 3 };
+    let mut groups: Vec<&str> = Vec::new();
+    let mut end = head.len();
+    while end > 0 {
+        let start = end.saturating_sub(size);
+        groups.push(&head[start..end]);
+        end = start;
+    }
+    groups.reverse();
+    groups.push(last);
+    groups.join(locale.group)
+}
+
+fn format_decimal(value: &Decimal, locale: &LocaleData) -> String {
+    let mut out = String::new();
+    if value.negative && !value.is_zero() {
+        out.push_str(locale.minus);
+    }
+    out.push_str(&group_digits(&value.int, locale));
+    if !value.frac.is_empty() {
+        out.push_str(locale.decimal);
+        out.push_str(&value.frac);
+    }
+    out
+}
+
+fn format_currency(value: &Decimal, locale: &LocaleData) -> String {
+    let rounded = value.round_to(locale.currency_digits);
+    let number = format_decimal(
+        &Decimal {
+            negative: false,
+            ..rounded.clone()
+        },
+        locale,
+    );
+    let amount = locale
+        .currency_pattern
+        .replace('#', &number)
+        .replace('¤', locale.currency);
+    if rounded.negative && !rounded.is_zero() {
+        format!("{}{}", locale.minus, amount)
+    } else {
+        amount
+    }
+}
+
+/// A sample date (and optionally time) in the proleptic Gregorian calendar
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct DateTime {
+    year: i32,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+}
+
+fn parse_date(text: &str) -> Option<DateTime> {
+    let (date, time) = match text.split_once(['T', ' ']) {
+        Some((date, time)) => (date, Some(time)),
+        None => (text, None),
+    };
+    let mut parts = date.splitn(3, '-');
+    let year: i32 = parts.next()?.parse().ok()?;
+    let month: u32 = parts.next()?.parse().ok()?;
+    let day: u32 = parts.next()?.parse().ok()?;
+    let leap = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+    let days_in_month = match month {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        1..=12 => 31,
+        _ => return None,
+    };
+    if !(1..=9999).contains(&year) || day == 0 || day > days_in_month {
+        return None;
+    }
+    let (hour, minute) = match time {
+        Some(time) => {
+            let (hour, minute) = time.split_once(':')?;
+            let minute = minute.get(..2).unwrap_or(minute);
+            (hour.parse().ok()?, minute.parse().ok()?)
+        }
+        None => (0, 0),
+    };
+    if hour > 23 || minute > 59 {
+        return None;
+    }
+    Some(DateTime {
+        year,
+        month,
+        day,
+        hour,
+        minute,
+    })
+}
+
+// The subset of CLDR date pattern syntax the patterns above use
+fn format_date(value: &DateTime, pattern: &str, locale: &LocaleData) -> String {
+    let mut out = String::new();
+    let chars: Vec<char> = pattern.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '\'' {
+            let end = chars[i + 1..]
+                .iter()
+                .position(|&c| c == '\'')
+                .map_or(chars.len(), |p| i + 1 + p);
+            out.extend(&chars[i + 1..end]);
+            i = end + 1;
+            continue;
+        }
+        let run = chars[i..].iter().take_while(|&&next| next == c).count();
+        i += run;
+        let hour12 = match value.hour % 12 {
+            0 => 12,
+            h => h,
+        };
+        match (c, run) {
+            ('y', 2) => out.push_str(&format!("{:02}", value.year % 100)),
+            ('y', _) => out.push_str(&value.year.to_string()),
+            ('M', 1) => out.push_str(&value.month.to_string()),
+            ('M', 2) => out.push_str(&format!("{:02}", value.month)),
+            ('M', _) => out.push_str(locale.months[value.month as usize - 1]),
+            ('d', 1) => out.push_str(&value.day.to_string()),
+            ('d', _) => out.push_str(&format!("{:02}", value.day)),
+            ('H', 1) => out.push_str(&value.hour.to_string()),
+            ('H', _) => out.push_str(&format!("{:02}", value.hour)),
+            ('h', 1) => out.push_str(&hour12.to_string()),
+            ('h', _) => out.push_str(&format!("{:02}", hour12)),
+            ('m', _) => out.push_str(&format!("{:02}", value.minute)),
+            ('a', _) => out.push_str(locale.am_pm[(value.hour >= 12) as usize]),
+            _ => out.push_str(&c.to_string().repeat(run)),
+        }
+    }
+    out
+}
+
+// ============================================================================
+// Preview
+// ============================================================================
+
+pub const DEFAULT_SAMPLES: &[&str] = &[
+    "0",
+    "1",
+    "1.0",
+    "2",
+    "3",
+    "5",
+    "11",
+    "22",
+    "1234.5",
+    "1000000",
+    "-0.755",
+    "2026-03-09T14:05",
+];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NumberPreview {
+    pub input: String,
+    pub formatted: String,
+    pub currency: String,
+    pub cardinal: String, // CLDR plural category: zero/one/two/few/many/other
+    pub ordinal: Option<String>, // only for whole numbers
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DatePreview {
+    pub input: String,
+    pub short: String,
+    pub long: String,
+    pub time: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LocalePreview {
+    pub requested: String,
+    pub locale: String, // the CLDR locale actually used
+    pub name: String,
+    pub numbers: Vec<NumberPreview>,
+    pub dates: Vec<DatePreview>,
+    pub invalid: Vec<String>, // samples that are neither a number nor a date
+}
+
+// "de_at" -> "de-AT"; exact match first, then the first locale with the same language
+fn resolve(locale: &str) -> Result<&'static LocaleData, String> {
+    let requested = locale.trim().replace('_', "-");
+    let language = requested
+        .split('-')
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    LOCALES
+        .iter()
+        .find(|l| l.id.eq_ignore_ascii_case(&requested))
+        .or_else(|| {
+            LOCALES
+                .iter()
+                .find(|l| l.id.split('-').next() == Some(language.as_str()))
+        })
+        .ok_or_else(|| {
+            format!(
+                "No formatting data for {} (available: {})",
+                if requested.is_empty() {
+                    "an empty locale"
+                } else {
+                    &requested
+                },
+                LOCALES.iter().map(|l| l.id).collect::<Vec<_>>().join(", ")
+            )
+        })
+}
+
+/// Render `samples` (or `DEFAULT_SAMPLES`) in `locale`
+pub fn preview(locale: &str, samples: &[String]) -> Result<LocalePreview, String> {
+    let data = resolve(locale)?;
+    let samples: Vec<String> = if samples.is_empty() {
+        DEFAULT_SAMPLES.iter().map(|s| s.to_string()).collect()
+    } else {
+        samples.iter().map(|s| s.trim().to_string()).collect()
+    };
+
+    let mut result = LocalePreview {
+        requested: locale.trim().to_string(),
+        locale: data.id.to_string(),
+        name: data.name.to_string(),
+        numbers: Vec::new(),
+        dates: Vec::new(),
+        invalid: Vec::new(),
+    };
+    for sample in samples {
+        if let Some(value) = parse_decimal(&sample) {
+            let operands = value.operands();
+            result.numbers.push(NumberPreview {
+                formatted: format_decimal(&value, data),
+                currency: format_currency(&value, data),
+                cardinal: (data.cardinal)(&operands).to_string(),
+                ordinal: (operands.v == 0 && !value.negative)
+                    .then(|| (data.ordinal)(&operands).to_string()),
+                input: sample,
+            });
+        } else if let Some(value) = parse_date(&sample) {
+            result.dates.push(DatePreview {
+                short: format_date(&value, data.short_date, data),
+                long: format_date(&value, data.long_date, data),
+                time: format_date(&value, data.time, data),
+                input: sample,
+            });
+        } else if !sample.is_empty() {
+            result.invalid.push(sample);
+        }
+    }
+    Ok(result)
+}
+
+#[tauri::command]
+pub fn preview_locale_formatting(
+    locale: String,
+    samples: Option<Vec<String>>,
+) -> Result<LocalePreview, String> {
+    preview(&locale, &samples.unwrap_or_default())
+}
@@ -0,0 +1,419 @@
+// Minimal multicast DNS (RFC 6762 / DNS-SD, RFC 6763): enough to advertise a service on the
+// LAN and browse for it. Only A, PTR, SRV and TXT records are understood; names are written
+// without compression but compressed names are read. Queries are sent from an ephemeral port
+// ("legacy unicast"), so responders answer straight back to us.
+
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, SocketAddrV4, UdpSocket as StdUdpSocket};
+use std::time::Duration;
+
+use socket2::{Domain, Protocol, Socket, Type};
+use tokio::net::UdpSocket;
+
+pub const MDNS_ADDR: Ipv4Addr = Ipv4Addr::new(224, 0, 0, 251);
+pub const MDNS_PORT: u16 = 5353;
+
+pub const TYPE_A: u16 = 1;
+pub const TYPE_PTR: u16 = 12;
+pub const TYPE_TXT: u16 = 16;
+pub const TYPE_SRV: u16 = 33;
+const TYPE_ANY: u16 = 255;
+const CLASS_IN: u16 = 1;
+// Top bit of a question's class: answer by unicast; of a record's class: replace cached data
+const CLASS_FLAG: u16 = 0x8000;
+const FLAG_RESPONSE: u16 = 0x8400; // response, authoritative
+const TTL: u32 = 120;
+const MAX_PACKET: usize = 9000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecordData {
+    A(Ipv4Addr),
+    Ptr(String),
+    Srv { port: u16, target: String },
+    Txt(Vec<String>),
+    Other(u16), // record type
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub name: String,
+    pub ttl: u32,
+    pub data: RecordData,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Message {
+    pub id: u16,
+    pub is_response: bool,
+    pub questions: Vec<(String, u16)>, // name, type
+    pub records: Vec<Record>,          // answers, authority and additional records together
+}
+
+/// An instance of a service found by `browse`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServiceInstance {
+    pub instance: String, // e.g. "Laptop" of "Laptop._bunchatools._tcp.local"
+    pub address: IpAddr,
+    pub port: u16,
+    pub txt: HashMap<String, String>,
+}
+
+fn write_name(buf: &mut Vec<u8>, name: &str) {
+    for label in name
+        .trim_end_matches('.')
+        .split('.')
+        .filter(|l| !l.is_empty())
+    {
+        let label = &label.as_bytes()[..label.len().min(63)];
+        buf.push(label.len() as u8);
+        buf.extend_from_slice(label);
+    }
+    buf.push(0);
+}
+
+fn read_u16(packet: &[u8], pos: usize) -> Option<u16> {
+    Some(u16::from_be_bytes([
+        *packet.get(pos)?,
+        *packet.get(pos + 1)?,
+    ]))
+}
+
+fn read_u32(packet: &[u8], pos: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(
+        packet.get(pos..pos + 4)?.try_into().ok()?,
+    ))
+}
+
+// The name at `pos`, following compression pointers; returns it and the position after it
+fn read_name(packet: &[u8], mut pos: usize) -> Option<(String, usize)> {
+    let mut labels = Vec::new();
+    let mut end = None;
+    // Pointers only point backwards in valid packets; the limit stops loops in invalid ones
+    for _ in 0..128 {
+        let len = *packet.get(pos)? as usize;
+        match len {
+            0 => {
+                return Some((labels.join("."), end.unwrap_or(pos + 1)));
+            }
+            l if l & 0xc0 == 0xc0 => {
+                let target = (read_u16(packet, pos)? & 0x3fff) as usize;
+                end.get_or_insert(pos + 2);
+                pos = target;
+            }
+            l => {
+                let label = packet.get(pos + 1..pos + 1 + l)?;
+                labels.push(String::from_utf8_lossy(label).to_string());
+                pos += 1 + l;
+            }
+        }
+    }
+    None
+}
+
+fn write_header(buf: &mut Vec<u8>, flags: u16, questions: u16, answers: u16) {
+    for value in [0, flags, questions, answers, 0, 0] {
+        buf.extend_from_slice(&u16::to_be_bytes(value));
+    }
+}
+
+/// A query for `name` records of `record_type`, asking for a unicast answer
+pub fn encode_query(name: &str, record_type: u16) -> Vec<u8> {
+    let mut buf = Vec::new();
+    write_header(&mut buf, 0, 1, 0);
+    write_name(&mut buf, name);
+    buf.extend_from_slice(&record_type.to_be_bytes());
+    buf.extend_from_slice(&(CLASS_IN | CLASS_FLAG).to_be_bytes());
+    buf
+}
+
+/// A response carrying `records` as answers
+pub fn encode_response(records: &[Record]) -> Vec<u8> {
+    let mut buf = Vec::new();
+    write_header(&mut buf, FLAG_RESPONSE, 0, records.len() as u16);
+    for record in records {
+        write_name(&mut buf, &record.name);
+        let (record_type, class) = match &record.data {
+            RecordData::A(_) => (TYPE_A, CLASS_IN | CLASS_FLAG),
+            RecordData::Ptr(_) => (TYPE_PTR, CLASS_IN), // shared: other instances have one too
+            RecordData::Srv { .. } => (TYPE_SRV, CLASS_IN | CLASS_FLAG),
+            RecordData::Txt(_) => (TYPE_TXT, CLASS_IN | CLASS_FLAG),
+            RecordData::Other(t) => (*t, CLASS_IN),
+        };
+        buf.extend_from_slice(&record_type.to_be_bytes());
+        buf.extend_from_slice(&class.to_be_bytes());
+        buf.extend_from_slice(&record.ttl.to_be_bytes());
+        let mut data = Vec::new();
+        match &record.data {
+            RecordData::A(ip) => data.extend_from_slice(&ip.octets()),
+            RecordData::Ptr(target) => write_name(&mut data, target),
+            RecordData::Srv { port, target } => {
+                data.extend_from_slice(&[0, 0, 0, 0]); // priority, weight
+                data.extend_from_slice(&port.to_be_bytes());
+                write_name(&mut data, target);
+            }
+            RecordData::Txt(entries) => {
+                for entry in entries {
+                    let entry = &entry.as_bytes()[..entry.len().min(255)];
+                    data.push(entry.len() as u8);
+                    data.extend_from_slice(entry);
+                }
+                if entries.is_empty() {
+                    data.push(0);
+                }
+            }
+            RecordData::Other(_) => {}
+        }
+        buf.extend_from_slice(&(data.len() as u16).to_be_bytes());
+        buf.extend_from_slice(&data);
+    }
+    buf
+}
+
+/// Parse a DNS message; None if it's malformed
+pub fn parse(packet: &[u8]) -> Option<Message> {
+    let id = read_u16(packet, 0)?;
+    let flags = read_u16(packet, 2)?;
+    let question_count = read_u16(packet, 4)?;
+    let record_count: u32 = (6..12)
+        .step_by(2)
+        .map(|pos| read_u16(packet, pos).map(u32::from))
+        .sum::<Option<u32>>()?;
+
+    let mut pos = 12;
+    let mut questions = Vec::new();
+    for _ in 0..question_count {
+        let (name, next) = read_name(packet, pos)?;
+        questions.push((name, read_u16(packet, next)?));
+        pos = next + 4;
+    }
+
+    let mut records = Vec::new();
+    for _ in 0..record_count {
+        let (name, next) = read_name(packet, pos)?;
+        let record_type = read_u16(packet, next)?;
+        let ttl = read_u32(packet, next + 4)?;
+        let len = read_u16(packet, next + 8)? as usize;
+        let start = next + 10;
+        let rdata = packet.get(start..start + len)?;
+        let data = match record_type {
+            TYPE_A if len == 4 => {
+                RecordData::A(Ipv4Addr::new(rdata[0], rdata[1], rdata[2], rdata[3]))
+            }
+            TYPE_PTR => RecordData::Ptr(read_name(packet, start)?.0),
+            TYPE_SRV if len >= 6 => RecordData::Srv {
+                port: read_u16(packet, start + 4)?,
+                target: read_name(packet, start + 6)?.0,
+            },
+            TYPE_TXT => {
+                let mut entries = Vec::new();
+                let mut i = 0;
+                while i < rdata.len() {
+                    let l = rdata[i] as usize;
+                    let entry = rdata.get(i + 1..i + 1 + l)?;
+                    if !entry.is_empty() {
+                        entries.push(String::from_utf8_lossy(entry).to_string());
+                    }
+                    i += 1 + l;
+                }
+                RecordData::Txt(entries)
+            }
+            other => RecordData::Other(other),
+        };
+        records.push(Record { name, ttl, data });
+        pos = start + len;
+    }
+
+    Some(Message {
+        id,
+        is_response: flags & 0x8000 != 0,
+        questions,
+        records,
+    })
+}
+
+/// Instances of `service` (e.g. "_bunchatools._tcp.local") described by `message`, which came
+/// from `source`. The address is the A record for the SRV target, or else the sender's.
+pub fn service_instances(message: &Message, service: &str, source: IpAddr) -> Vec<ServiceInstance> {
+    let find = |name: &str, pick: &dyn Fn(&RecordData) -> bool| {
+        message
+            .records
+            .iter()
+            .find(|r| r.name.eq_ignore_ascii_case(name) && pick(&r.data))
+            .map(|r| r.data.clone())
+    };
+    message
+        .records
+        .iter()
+        .filter(|r| r.name.eq_ignore_ascii_case(service) && r.ttl > 0)
+        .filter_map(|r| match &r.data {
+            RecordData::Ptr(full_name) => Some(full_name.clone()),
+            _ => None,
+        })
+        .filter_map(|full_name| {
+            let instance = full_name
+                .strip_suffix(service)
+                .and_then(|n| n.strip_suffix('.'))?
+                .to_string();
+            let Some(RecordData::Srv { port, target }) =
+                find(&full_name, &|d| matches!(d, RecordData::Srv { .. }))
+            else {
+                return None;
+            };
+            let address = match find(&target, &|d| matches!(d, RecordData::A(_))) {
+                Some(RecordData::A(ip)) => IpAddr::V4(ip),
+                _ => source,
+            };
+            let txt = match find(&full_name, &|d| matches!(d, RecordData::Txt(_))) {
+                Some(RecordData::Txt(entries)) => entries
+                    .iter()
+                    .map(|e| match e.split_once('=') {
+                        Some((key, value)) => (key.to_lowercase(), value.to_string()),
+                        None => (e.to_lowercase(), String::new()),
+                    })
+                    .collect(),
+                _ => HashMap::new(),
+            };
+            Some(ServiceInstance {
+                instance,
+                address,
+                port,
+                txt,
+            })
+        })
+        .collect()
+}
+
+/// Ask the LAN for instances of `service`, collecting answers for `wait`
+pub async fn browse(service: &str, wait: Duration) -> Result<Vec<ServiceInstance>, String> {
+    let socket = UdpSocket::bind("0.0.0.0:0")
+        .await
+        .map_err(|e| format!("Failed to open a UDP socket: {}", e))?;
+    socket
+        .send_to(
+            &encode_query(service, TYPE_PTR),
+            SocketAddrV4::new(MDNS_ADDR, MDNS_PORT),
+        )
+        .await
+        .map_err(|e| format!("Failed to send the mDNS query: {}", e))?;
+
+    let mut found: Vec<ServiceInstance> = Vec::new();
+    let mut buf = vec![0u8; MAX_PACKET];
+    let deadline = tokio::time::Instant::now() + wait;
+    while let Ok(Ok((len, source))) =
+        tokio::time::timeout_at(deadline, socket.recv_from(&mut buf)).await
+    {
+        let Some(message) = parse(&buf[..len]).filter(|m| m.is_response) else {
+            continue;
+        };
+        for instance in service_instances(&message, service, source.ip()) {
+            if !found
+                .iter()
+                .any(|f| f.instance == instance.instance && f.address == instance.address)
+            {
+                found.push(instance);
+            }
+        }
+    }
+    Ok(found)
+}
+
+// The local address used to reach `peer`, to put in the A record sent to it
+fn local_address_for(peer: IpAddr) -> Option<Ipv4Addr> {
+    let socket = StdUdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect(SocketAddr::new(peer, MDNS_PORT)).ok()?;
+    match socket.local_addr().ok()?.ip() {
+        IpAddr::V4(ip) => Some(ip),
+        IpAddr::V6(_) => None,
+    }
+}
+
+// Port 5353, shared with the system's own responder (Avahi, Bonjour, Windows' DNS client)
+fn bind_mdns_socket() -> Result<UdpSocket, String> {
+    let bind_error = |e: std::io::Error| format!("Failed to listen for mDNS: {}", e);
+    let socket = Socket::new(Domain::IPV4, Type::DGRAM, Some(Protocol::UDP)).map_err(bind_error)?;
+    socket.set_reuse_address(true).map_err(bind_error)?;
+    #[cfg(unix)]
+    socket.set_reuse_port(true).map_err(bind_error)?;
+    socket
+        .bind(&SocketAddr::from((Ipv4Addr::UNSPECIFIED, MDNS_PORT)).into())
+        .map_err(bind_error)?;
+    socket
+        .join_multicast_v4(&MDNS_ADDR, &Ipv4Addr::UNSPECIFIED)
+        .map_err(bind_error)?;
+    socket.set_nonblocking(true).map_err(bind_error)?;
+    UdpSocket::from_std(socket.into()).map_err(bind_error)
+}
+
+/// Answer queries for `service` with one instance of it on `port`, until the task is aborted
+pub async fn advertise(
+    service: String,
+    instance: String,
+    port: u16,
+    txt: Vec<String>,
+) -> Result<(), String> {
+    let socket = bind_mdns_socket()?;
+    // Dots would split the instance label
+    let instance = instance.replace('.', "-");
+    let full_name = format!("{}.{}", instance, service);
+    let host = format!("{}.local", instance.replace(' ', "-"));
+
+    let mut buf = vec![0u8; MAX_PACKET];
+    loop {
+        let (len, source) = match socket.recv_from(&mut buf).await {
+            Ok(received) => received,
+            Err(e) => {
+                log::warn!("mDNS receive failed: {}", e);
+                continue;
+            }
+        };
+        let Some(query) = parse(&buf[..len]).filter(|m| !m.is_response) else {
+            continue;
+        };
+        let asked = query.questions.iter().any(|(name, record_type)| {
+            (name.eq_ignore_ascii_case(&service) || name.eq_ignore_ascii_case(&full_name))
+                && matches!(*record_type, TYPE_PTR | TYPE_SRV | TYPE_TXT | TYPE_ANY)
+        });
+        if !asked {
+            continue;
+        }
+
+        let mut records = vec![
+            Record {
+                name: service.clone(),
+                ttl: TTL,
+                data: RecordData::Ptr(full_name.clone()),
+            },
+            Record {
+                name: full_name.clone(),
+                ttl: TTL,
+                data: RecordData::Srv {
+                    port,
+                    target: host.clone(),
+                },
+            },
+            Record {
+                name: full_name.clone(),
+                ttl: TTL,
+                data: RecordData::Txt(txt.clone()),
+            },
+        ];
+        if let Some(ip) = local_address_for(source.ip()) {
+            records.push(Record {
+                name: host.clone(),
+                ttl: TTL,
+                data: RecordData::A(ip),
+            });
+        }
+        let mut response = encode_response(&records);
+        // Legacy unicast queries (not from port 5353) get the answer directly, with their id
+        let destination = if source.port() == MDNS_PORT {
+            SocketAddr::from((MDNS_ADDR, MDNS_PORT))
+        } else {
+            response[..2].copy_from_slice(&query.id.to_be_bytes());
+            source
+        };
+        if let Err(e) = socket.send_to(&response, destination).await {
+            log::warn!("mDNS response to {} failed: {}", destination, e);
+        }
+    }
+}
@@ -0,0 +1,172 @@
+// Local ML models (speech recognition, ...) are too big to ship with the app, so each is
+// downloaded into the app data folder the first time a tool needs it. The download goes to a
+// `.part` file first, so an interrupted one is never mistaken for a model, and only takes the
+// model's name once its SHA-256 matches the pinned one, so a URL that follows a branch can't
+// swap the file underneath. A model already on disk is hashed again the first time it's used
+// in a session, and downloaded anew if it no longer matches.
+
+use std::collections::HashSet;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use futures_util::StreamExt;
+use ring::digest::{Context, SHA256};
+use tauri::{AppHandle, Manager};
+
+use crate::transfers::TransferMeter;
+
+/// A model file and where it comes from
+pub struct ModelFile {
+    pub folder: &'static str, // under the app data folder
+    pub name: &'static str,
+    pub url: &'static str,
+    pub sha256: &'static str, // lowercase hex of the file's SHA-256
+    pub label: &'static str,  // for messages, e.g. "speech model"
+    pub tool: &'static str,   // whose transfers the download counts towards
+}
+
+// Models whose file on disk has matched its checksum this session
+static VERIFIED: Mutex<Option<HashSet<PathBuf>>> = Mutex::new(None);
+
+fn hex(digest: &[u8]) -> String {
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn file_sha256(path: &Path) -> std::io::Result<String> {
+    let mut file = std::fs::File::open(path)?;
+    let mut digest = Context::new(&SHA256);
+    let mut buffer = vec![0u8; 1 << 16];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        digest.update(&buffer[..read]);
+    }
+    Ok(hex(digest.finish().as_ref()))
+}
+
+fn is_verified(path: &Path) -> bool {
+    VERIFIED
+        .lock()
+        .unwrap()
+        .as_ref()
+        .is_some_and(|verified| verified.contains(path))
+}
+
+fn mark_verified(path: PathBuf) {
+    VERIFIED
+        .lock()
+        .unwrap()
+        .get_or_insert_with(HashSet::new)
+        .insert(path);
+}
+
+/// Whether the model on disk still matches its checksum. A mismatching file (older
+/// downloads came from unpinned URLs) is deleted so it gets downloaded again.
+async fn existing_matches(model: &ModelFile, path: &Path) -> bool {
+    if is_verified(path) {
+        return true;
+    }
+    let owned = path.to_path_buf();
+    let sha256 = tauri::async_runtime::spawn_blocking(move || file_sha256(&owned))
+        .await
+        .map_err(|e| e.to_string())
+        .and_then(|r| r.map_err(|e| e.to_string()));
+    match sha256 {
+        Ok(sha256) if sha256 == model.sha256 => {
+            mark_verified(path.to_path_buf());
+            true
+        }
+        Ok(sha256) => {
+            log::warn!(
+                "{} on disk doesn't match its checksum (expected {}, got {}), downloading it again",
+                model.name,
+                model.sha256,
+                sha256
+            );
+            let _ = std::fs::remove_file(path);
+            false
+        }
+        Err(e) => {
+            log::warn!("Failed to check {}: {}", model.name, e);
+            let _ = std::fs::remove_file(path);
+            false
+        }
+    }
+}
+
+/// The model's path, downloaded first if needed. `on_progress` gets the percentage and
+/// `cancelled` is checked between chunks.
+pub async fn ensure(
+    app: &AppHandle,
+    model: &ModelFile,
+    mut on_progress: impl FnMut(u8),
+    cancelled: impl Fn() -> bool,
+) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?
+        .join(model.folder);
+    let path = dir.join(model.name);
+    if path.is_file() && existing_matches(model, &path).await {
+        return Ok(path);
+    }
+    crate::transfers::check_cap(app)?;
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    log::info!("Downloading {} {}", model.label, model.name);
+    on_progress(0);
+
+    let response = reqwest::get(model.url)
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| format!("Failed to download the {}: {}", model.label, e))?;
+    let total = response.content_length().unwrap_or(0);
+    let partial = dir.join(format!("{}.part", model.name));
+    let mut file = std::fs::File::create(&partial).map_err(|e| e.to_string())?;
+    let meter = TransferMeter::new(app, model.tool);
+    let mut stream = response.bytes_stream();
+    let mut digest = Context::new(&SHA256);
+    let (mut downloaded, mut last_percent) = (0u64, 0u8);
+    while let Some(chunk) = stream.next().await {
+        if cancelled() {
+            drop(file);
+            let _ = std::fs::remove_file(&partial);
+            return Err("Download cancelled".to_string());
+        }
+        let chunk = chunk.map_err(|e| format!("The {} download failed: {}", model.label, e))?;
+        file.write_all(&chunk).map_err(|e| e.to_string())?;
+        digest.update(&chunk);
+        downloaded += chunk.len() as u64;
+        meter.downloaded(chunk.len() as u64);
+        let percent = (downloaded * 100).checked_div(total).unwrap_or(0).min(100) as u8;
+        if percent != last_percent {
+            last_percent = percent;
+            on_progress(percent);
+        }
+    }
+    drop(file);
+    if total > 0 && downloaded != total {
+        let _ = std::fs::remove_file(&partial);
+        return Err(format!("The {} download was cut off", model.label));
+    }
+    let sha256 = hex(digest.finish().as_ref());
+    if sha256 != model.sha256 {
+        let _ = std::fs::remove_file(&partial);
+        log::warn!(
+            "{} checksum mismatch: expected {}, got {}",
+            model.name,
+            model.sha256,
+            sha256
+        );
+        return Err(format!(
+            "The downloaded {} doesn't match the expected checksum",
+            model.label
+        ));
+    }
+    std::fs::rename(&partial, &path).map_err(|e| e.to_string())?;
+    mark_verified(path.clone());
+    Ok(path)
+}
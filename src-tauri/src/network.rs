@@ -0,0 +1,110 @@
+// Network status: interfaces, default gateway, DNS servers, VPN and proxy detection
+// The configuration is polled in the background and `network-changed` is emitted with the
+// new status whenever it differs, so tools that need the internet can react to going
+// offline or connecting to a VPN without polling themselves.
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use crate::platform::{self, NetworkInfo, NetworkInterface};
+
+// Reading the configuration spawns `ip` on Linux, so don't do it too often
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+// Interface name prefixes used by VPN clients and tunnel drivers
+const VPN_PREFIXES: &[&str] = &["tun", "tap", "wg", "ppp", "utun", "zt"];
+
+// Substrings of the names/descriptions that VPN clients give their adapters
+const VPN_KEYWORDS: &[&str] = &[
+    "vpn",
+    "wireguard",
+    "tailscale",
+    "zerotier",
+    "nordlynx",
+    "wintun",
+    "openvpn",
+    "anyconnect",
+    "fortinet",
+    "globalprotect",
+    "mullvad",
+    "proton",
+];
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NetworkStatus {
+    #[serde(flatten)]
+    pub info: NetworkInfo,
+    pub vpn_interfaces: Vec<String>, // names of connected VPN adapters
+    pub online: bool,                // has a default route through a configured interface
+}
+
+impl NetworkStatus {
+    pub fn from_info(info: NetworkInfo) -> Self {
+        let vpn_interfaces = info
+            .interfaces
+            .iter()
+            .filter(|i| i.is_up && is_vpn_interface(i))
+            .map(|i| i.name.clone())
+            .collect();
+        let online = info.gateway.is_some()
+            && info
+                .interfaces
+                .iter()
+                .any(|i| i.is_up && !i.is_loopback && !i.addresses.is_empty());
+        Self {
+            info,
+            vpn_interfaces,
+            online,
+        }
+    }
+}
+
+/// Whether the adapter looks like it belongs to a VPN
+pub fn is_vpn_interface(interface: &NetworkInterface) -> bool {
+    if interface.is_loopback {
+        return false;
+    }
+    let name = interface.name.to_lowercase();
+    let description = interface.description.to_lowercase();
+    interface.is_tunnel
+        || VPN_PREFIXES.iter().any(|prefix| name.starts_with(prefix))
+        || VPN_KEYWORDS
+            .iter()
+            .any(|keyword| name.contains(keyword) || description.contains(keyword))
+}
+
+fn read_status() -> Result<NetworkStatus, String> {
+    platform::get_network_info_impl().map(NetworkStatus::from_info)
+}
+
+static LAST_STATUS: Mutex<Option<NetworkStatus>> = Mutex::new(None);
+
+/// Start watching for network changes
+pub fn init(app: &AppHandle) {
+    let app = app.clone();
+    std::thread::spawn(move || loop {
+        match read_status() {
+            Ok(status) => {
+                let mut last = LAST_STATUS.lock().unwrap();
+                // The first reading is the baseline, not a change
+                let changed = last.as_ref().is_some_and(|last| *last != status);
+                if changed {
+                    let _ = app.emit("network-changed", &status);
+                }
+                *last = Some(status);
+            }
+            Err(e) => log::warn!("Failed to read network status: {}", e),
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    });
+}
+
+#[tauri::command]
+pub async fn get_network_status() -> Result<NetworkStatus, String> {
+    tauri::async_runtime::spawn_blocking(read_status)
+        .await
+        .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,382 @@
+// LAN device discovery: what's on the local network, with names, IPs and MAC vendors
+// Three sources are combined per IP address:
+// - mDNS / DNS-SD: a list of common service types is browsed (see `mdns`); instance names
+//   make good device names ("Living Room TV"), and _workstation._tcp also gives the MAC
+// - SSDP (UPnP): an M-SEARCH for everything; the friendly name comes from the device
+//   description the answer points to
+// - the OS ARP cache, which the answers above fill, for MAC addresses
+// MAC vendors come from the IEEE OUI list when the system has one (hwdata, ieee-data, nmap,
+// Wireshark), otherwise from a few common prefixes built in. Randomized (locally
+// administered) MACs, as phones use for privacy, have no vendor.
+
+use std::collections::{HashMap, HashSet};
+use std::net::{IpAddr, Ipv4Addr, SocketAddrV4};
+use std::time::Duration;
+
+use futures_util::future::join_all;
+use regex::Regex;
+use serde::Serialize;
+use tauri::AppHandle;
+use tokio::net::UdpSocket;
+
+use crate::jobs::{self, JobKind};
+use crate::{mdns, platform};
+
+const SSDP_ADDR: Ipv4Addr = Ipv4Addr::new(239, 255, 255, 250);
+const SSDP_PORT: u16 = 1900;
+const BROWSE_TIME: Duration = Duration::from_millis(2000);
+const DESCRIPTION_TIMEOUT: Duration = Duration::from_secs(2);
+
+// Service types most home and office devices advertise
+const SERVICE_TYPES: &[&str] = &[
+    "_workstation._tcp.local",
+    "_device-info._tcp.local",
+    "_http._tcp.local",
+    "_ssh._tcp.local",
+    "_smb._tcp.local",
+    "_afpovertcp._tcp.local",
+    "_ipp._tcp.local",
+    "_printer._tcp.local",
+    "_scanner._tcp.local",
+    "_airplay._tcp.local",
+    "_raop._tcp.local",
+    "_googlecast._tcp.local",
+    "_spotify-connect._tcp.local",
+    "_sonos._tcp.local",
+    "_hap._tcp.local",
+    "_homekit._tcp.local",
+    "_matter._tcp.local",
+    "_hue._tcp.local",
+    "_bunchatools._tcp.local",
+];
+
+// System copies of the IEEE OUI list, in the formats `parse_oui_line` reads
+const OUI_FILES: &[&str] = &[
+    "/usr/share/hwdata/oui.txt",
+    "/usr/share/ieee-data/oui.txt",
+    "/usr/share/misc/oui.txt",
+    "/usr/share/nmap/nmap-mac-prefixes",
+    "/usr/share/wireshark/manuf",
+];
+
+// Used when there's no OUI list (Windows, minimal Linux installs)
+const BUILTIN_VENDORS: &[(&str, &str)] = &[
+    ("000393", "Apple"),
+    ("001B63", "Apple"),
+    ("F01898", "Apple"),
+    ("B827EB", "Raspberry Pi Foundation"),
+    ("DCA632", "Raspberry Pi Trading"),
+    ("E45F01", "Raspberry Pi Trading"),
+    ("28CDC1", "Raspberry Pi Trading"),
+    ("D83ADD", "Raspberry Pi Trading"),
+    ("001A11", "Google"),
+    ("3C5AB4", "Google"),
+    ("18B430", "Nest Labs"),
+    ("44650D", "Amazon Technologies"),
+    ("001788", "Philips Lighting (Hue)"),
+    ("000E58", "Sonos"),
+    ("001132", "Synology"),
+    ("00044B", "NVIDIA"),
+    ("0050F2", "Microsoft"),
+    ("00155D", "Microsoft (Hyper-V)"),
+    ("000C29", "VMware"),
+    ("005056", "VMware"),
+    ("000569", "VMware"),
+    ("080027", "Oracle (VirtualBox)"),
+    ("001C42", "Parallels"),
+    ("00163E", "Xen"),
+    ("525400", "QEMU / KVM"),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DiscoveredDevice {
+    pub ip: String,
+    pub name: Option<String>,
+    pub mac: Option<String>, // AA:BB:CC:DD:EE:FF
+    pub vendor: Option<String>,
+    pub services: Vec<String>, // e.g. "googlecast", "ipp", "upnp"
+}
+
+/// One source's view of a device, combined per IP by `merge`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Sighting {
+    pub ip: String,
+    pub name: Option<String>,
+    pub mac: Option<String>,
+    pub service: Option<String>,
+}
+
+/// "googlecast" for "_googlecast._tcp.local"
+pub fn service_label(service: &str) -> String {
+    service
+        .split('.')
+        .next()
+        .unwrap_or(service)
+        .trim_start_matches('_')
+        .to_string()
+}
+
+/// Split a _workstation._tcp instance, "host [aa:bb:cc:dd:ee:ff]", into its name and MAC
+pub fn split_workstation_name(instance: &str) -> (String, Option<String>) {
+    let instance = instance.trim();
+    if let Some((name, rest)) = instance.rsplit_once(" [") {
+        if let Some(mac) = rest.strip_suffix(']') {
+            if crate::wake_on_lan::parse_mac(mac).is_ok() {
+                return (name.trim().to_string(), Some(mac.to_uppercase()));
+            }
+        }
+    }
+    (instance.to_string(), None)
+}
+
+/// Headers of an SSDP answer ("HTTP/1.1 200 OK" or a NOTIFY), names in lower case
+pub fn parse_ssdp_response(response: &str) -> Option<HashMap<String, String>> {
+    let mut lines = response.lines();
+    let status = lines.next()?;
+    if !status.starts_with("HTTP/1.1 200") && !status.starts_with("NOTIFY ") {
+        return None;
+    }
+    Some(
+        lines
+            .filter_map(|line| line.split_once(':'))
+            .map(|(name, value)| (name.trim().to_lowercase(), value.trim().to_string()))
+            .collect(),
+    )
+}
+
+/// Combine sightings into one device per IP, sorted by address; earlier names win
+pub fn merge(sightings: Vec<Sighting>) -> Vec<DiscoveredDevice> {
+    let mut devices: Vec<DiscoveredDevice> = Vec::new();
+    for sighting in sightings {
+        let index = match devices.iter().position(|d| d.ip == sighting.ip) {
+            Some(index) => index,
+            None => {
+                devices.push(DiscoveredDevice {
+                    ip: sighting.ip.clone(),
+                    name: None,
+                    mac: None,
+                    vendor: None,
+                    services: Vec::new(),
+                });
+                devices.len() - 1
+            }
+        };
+        let device = &mut devices[index];
+        if device.name.is_none() {
+            device.name = sighting.name.filter(|n| !n.trim().is_empty());
+        }
+        if device.mac.is_none() {
+            device.mac = sighting.mac.map(|m| m.to_uppercase());
+        }
+        if let Some(service) = sighting.service {
+            if !device.services.contains(&service) {
+                device.services.push(service);
+            }
+        }
+    }
+    devices.sort_by_key(|d| {
+        d.ip.parse::<IpAddr>()
+            .unwrap_or(IpAddr::V4(Ipv4Addr::UNSPECIFIED))
+    });
+    devices
+}
+
+/// The first 3 bytes of a MAC address as 6 upper-case hex digits
+pub fn oui_prefix(mac: &str) -> Option<String> {
+    let bytes = crate::wake_on_lan::parse_mac(mac).ok()?;
+    Some(format!("{:02X}{:02X}{:02X}", bytes[0], bytes[1], bytes[2]))
+}
+
+/// Prefix and vendor from a line of an OUI list: IEEE oui.txt ("00-00-0C   (hex)  Cisco"),
+/// nmap-mac-prefixes ("00000C Cisco") or Wireshark's manuf ("00:00:0C\tCisco\tCisco Systems")
+pub fn parse_oui_line(line: &str) -> Option<(String, String)> {
+    let line = line.trim();
+    if line.starts_with('#') {
+        return None;
+    }
+    let (prefix, rest) = line.split_once(char::is_whitespace)?;
+    let prefix: String = prefix.chars().filter(|c| !matches!(c, ':' | '-')).collect();
+    if prefix.len() != 6 || !prefix.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let rest = rest.trim_start();
+    let rest = rest.strip_prefix("(hex)").unwrap_or(rest);
+    let vendor = rest.split('\t').map(str::trim).rfind(|f| !f.is_empty())?;
+    Some((prefix.to_uppercase(), vendor.to_string()))
+}
+
+/// Vendors for the MACs' prefixes, from `lists` (OUI list contents) over the built-in ones
+pub fn vendors_for(macs: &[&str], lists: &[String]) -> HashMap<String, String> {
+    let wanted: HashSet<String> = macs.iter().filter_map(|mac| oui_prefix(mac)).collect();
+    let mut vendors: HashMap<String, String> = BUILTIN_VENDORS
+        .iter()
+        .filter(|(prefix, _)| wanted.contains(*prefix))
+        .map(|(prefix, vendor)| (prefix.to_string(), vendor.to_string()))
+        .collect();
+    for list in lists {
+        for (prefix, vendor) in list.lines().filter_map(parse_oui_line) {
+            if wanted.contains(&prefix) {
+                vendors.insert(prefix, vendor);
+            }
+        }
+    }
+    vendors
+}
+
+/// The vendor of `mac` in `vendors`; randomized MACs have none
+pub fn vendor_of(mac: &str, vendors: &HashMap<String, String>) -> Option<String> {
+    let prefix = oui_prefix(mac)?;
+    if let Some(vendor) = vendors.get(&prefix) {
+        return Some(vendor.clone());
+    }
+    let first = u8::from_str_radix(&prefix[..2], 16).ok()?;
+    // The locally administered bit
+    (first & 0x02 != 0).then(|| "Private address".to_string())
+}
+
+async fn browse_mdns() -> Vec<Sighting> {
+    let browses = SERVICE_TYPES
+        .iter()
+        .map(|service| async move { (service, mdns::browse(service, BROWSE_TIME).await) });
+    let mut sightings = Vec::new();
+    for (service, result) in join_all(browses).await {
+        let instances = match result {
+            Ok(instances) => instances,
+            Err(e) => {
+                log::warn!("mDNS browse for {} failed: {}", service, e);
+                continue;
+            }
+        };
+        for instance in instances {
+            let (name, mac) = if *service == "_workstation._tcp.local" {
+                split_workstation_name(&instance.instance)
+            } else {
+                (instance.instance, None)
+            };
+            sightings.push(Sighting {
+                ip: instance.address.to_string(),
+                name: Some(name),
+                mac,
+                service: Some(service_label(service)),
+            });
+        }
+    }
+    sightings
+}
+
+// The UPnP friendly name from a device description URL
+async fn friendly_name(client: &reqwest::Client, location: &str) -> Option<String> {
+    let description = client.get(location).send().await.ok()?.text().await.ok()?;
+    let pattern = Regex::new(r"<friendlyName>\s*([^<]+?)\s*</friendlyName>").unwrap();
+    let name = pattern.captures(&description)?[1].to_string();
+    Some(
+        name.replace("&amp;", "&")
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&apos;", "'")
+            .replace("&quot;", "\""),
+    )
+}
+
+async fn search_ssdp() -> Result<Vec<Sighting>, String> {
+    let socket = UdpSocket::bind("0.0.0.0:0")
+        .await
+        .map_err(|e| format!("Failed to open a UDP socket: {}", e))?;
+    let search = format!(
+        "M-SEARCH * HTTP/1.1\r\nHOST: {}:{}\r\nMAN: \"ssdp:discover\"\r\nMX: 1\r\nST: ssdp:all\r\n\r\n",
+        SSDP_ADDR, SSDP_PORT
+    );
+    socket
+        .send_to(search.as_bytes(), SocketAddrV4::new(SSDP_ADDR, SSDP_PORT))
+        .await
+        .map_err(|e| format!("Failed to send the SSDP search: {}", e))?;
+
+    // Devices answer once per service they have; the first location per IP is enough
+    let mut locations: Vec<(IpAddr, Option<String>)> = Vec::new();
+    let mut buf = vec![0u8; 4096];
+    let deadline = tokio::time::Instant::now() + BROWSE_TIME;
+    while let Ok(Ok((len, source))) =
+        tokio::time::timeout_at(deadline, socket.recv_from(&mut buf)).await
+    {
+        let Some(headers) = parse_ssdp_response(&String::from_utf8_lossy(&buf[..len])) else {
+            continue;
+        };
+        if !locations.iter().any(|(ip, _)| *ip == source.ip()) {
+            locations.push((source.ip(), headers.get("location").cloned()));
+        }
+    }
+
+    let client = reqwest::Client::builder()
+        .timeout(DESCRIPTION_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let sightings = locations.into_iter().map(|(ip, location)| {
+        let client = &client;
+        async move {
+            // Only descriptions served by the device itself
+            let location = location.filter(|l| {
+                reqwest::Url::parse(l)
+                    .ok()
+                    .and_then(|url| url.host_str().map(|h| h == ip.to_string()))
+                    .unwrap_or(false)
+            });
+            let name = match location {
+                Some(location) => friendly_name(client, &location).await,
+                None => None,
+            };
+            Sighting {
+                ip: ip.to_string(),
+                name,
+                mac: None,
+                service: Some("upnp".to_string()),
+            }
+        }
+    });
+    Ok(join_all(sightings).await)
+}
+
+/// List the devices on the LAN; takes a few seconds while answers come in. Runs as a scan job,
+/// so it waits while the scan limit is reached.
+#[tauri::command]
+pub async fn discover_network_devices(app: AppHandle) -> Result<Vec<DiscoveredDevice>, String> {
+    let job = jobs::start_job(&app, JobKind::NetworkScan, "Scanning the network");
+    let (mdns, ssdp) = futures_util::future::join(browse_mdns(), search_ssdp()).await;
+    if job.token().is_cancelled() {
+        return Err("Network scan cancelled".to_string());
+    }
+    let mut sightings = mdns;
+    match ssdp {
+        Ok(found) => sightings.extend(found),
+        Err(e) => log::warn!("SSDP search failed: {}", e),
+    }
+
+    // Read last, when the answers above have filled the cache
+    let neighbors = tauri::async_runtime::spawn_blocking(platform::get_neighbors_impl)
+        .await
+        .map_err(|e| e.to_string())?;
+    match neighbors {
+        Ok(neighbors) => sightings.extend(neighbors.into_iter().map(|n| Sighting {
+            ip: n.ip,
+            mac: Some(n.mac),
+            ..Default::default()
+        })),
+        Err(e) => log::warn!("Failed to read the ARP cache: {}", e),
+    }
+
+    let mut devices = merge(sightings);
+    let macs: Vec<&str> = devices.iter().filter_map(|d| d.mac.as_deref()).collect();
+    let lists: Vec<String> = OUI_FILES
+        .iter()
+        .filter_map(|path| std::fs::read_to_string(path).ok())
+        .collect();
+    let vendors = vendors_for(&macs, &lists);
+    for device in &mut devices {
+        device.vendor = device
+            .mac
+            .as_deref()
+            .and_then(|mac| vendor_of(mac, &vendors));
+    }
+    log::info!("Found {} devices on the network", devices.len());
+    job.complete();
+    Ok(devices)
+}
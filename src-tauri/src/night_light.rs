@@ -0,0 +1,306 @@
+// Night light: warmer screen colors in the evening, through the display gamma ramps
+// With the schedule on it turns itself on at sunset and off at sunrise, at the place from the
+// settings (or the last one the sun times tool used). Toggling it by hand, from the tray or the
+// launcher, holds until the next sunrise or sunset.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::geocoding::{self, Location};
+use crate::sun_times::{sun_times, Polar};
+use crate::{platform, tray_menu, AppState};
+
+const STATE_FILE: &str = "night_light_state.json";
+// Often enough to notice waking from sleep or a clock change, and to restore the ramp after a
+// fullscreen game or a display change resets it
+const RECHECK_INTERVAL: Duration = Duration::from_secs(5 * 60);
+const MIN_TEMPERATURE: u32 = 1000;
+const NEUTRAL_TEMPERATURE: u32 = 6500;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NightLightSettings {
+    #[serde(default)]
+    pub schedule: bool, // on at sunset, off at sunrise
+    #[serde(default = "default_temperature")]
+    pub temperature: u32, // kelvin
+    #[serde(default)]
+    pub location: String, // place or coordinates; empty uses the last one sun times used
+}
+
+fn default_temperature() -> u32 {
+    3400
+}
+
+impl Default for NightLightSettings {
+    fn default() -> Self {
+        Self {
+            schedule: false,
+            temperature: default_temperature(),
+            location: String::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NightLightStatus {
+    pub enabled: bool,
+    pub scheduled: bool,
+    pub next_change: Option<i64>, // Unix time of the next sunrise or sunset switch
+}
+
+// Whether we left the gamma changed, to undo it after a crash
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct SavedState {
+    enabled: bool,
+}
+
+struct Runtime {
+    enabled: bool,
+    settings: Option<NightLightSettings>, // what the schedule was started with
+    next_change: Option<i64>,
+}
+
+static RUNTIME: Mutex<Runtime> = Mutex::new(Runtime {
+    enabled: false,
+    settings: None,
+    next_change: None,
+});
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// True while the screen is warmed (for the tray)
+pub fn is_active() -> bool {
+    RUNTIME.lock().unwrap().enabled
+}
+
+fn status() -> NightLightStatus {
+    let runtime = RUNTIME.lock().unwrap();
+    NightLightStatus {
+        enabled: runtime.enabled,
+        scheduled: runtime.settings.as_ref().is_some_and(|s| s.schedule),
+        next_change: runtime.next_change,
+    }
+}
+
+// Tanner Helland's fit of the black-body color, 0-255 per channel
+fn blackbody(kelvin: f64) -> (f64, f64, f64) {
+    let t = kelvin / 100.0;
+    let red = if t <= 66.0 {
+        255.0
+    } else {
+        329.698727446 * (t - 60.0).powf(-0.1332047592)
+    };
+    let green = if t <= 66.0 {
+        99.4708025861 * t.ln() - 161.1195681661
+    } else {
+        288.1221695283 * (t - 60.0).powf(-0.0755148492)
+    };
+    let blue = if t >= 66.0 {
+        255.0
+    } else if t <= 19.0 {
+        0.0
+    } else {
+        138.5177312231 * (t - 10.0).ln() - 305.0447927307
+    };
+    (red, green, blue)
+}
+
+/// Gamma multipliers for a color temperature, relative to daylight (6500 K is 1, 1, 1)
+pub fn whitepoint(kelvin: u32) -> (f64, f64, f64) {
+    let kelvin = kelvin.clamp(MIN_TEMPERATURE, NEUTRAL_TEMPERATURE) as f64;
+    let (red, green, blue) = blackbody(kelvin);
+    let (white_red, white_green, white_blue) = blackbody(NEUTRAL_TEMPERATURE as f64);
+    let scale = |value: f64, white: f64| (value / white).clamp(0.0, 1.0);
+    (
+        scale(red, white_red),
+        scale(green, white_green),
+        scale(blue, white_blue),
+    )
+}
+
+/// Whether it's night (between sunset and sunrise) at `now` at the given coordinates, and when
+/// that changes next (None while the sun neither rises nor sets for days)
+pub fn schedule_at(latitude: f64, longitude: f64, now: i64) -> (bool, Option<i64>) {
+    let today = DateTime::from_timestamp(now, 0)
+        .unwrap_or_default()
+        .date_naive();
+    // A solar day can start on the previous UTC day (far east) or end on the next (far west)
+    let mut events = Vec::new(); // (time, night starts)
+    let mut polar = None;
+    for offset in -1..=2 {
+        let times = sun_times(latitude, longitude, today + chrono::Duration::days(offset));
+        if offset == 0 {
+            polar = times.polar;
+        }
+        events.extend(times.sunrise.map(|time| (time, false)));
+        events.extend(times.sunset.map(|time| (time, true)));
+    }
+    events.sort_unstable();
+
+    let night = match polar {
+        Some(polar) => polar == Polar::Night,
+        None => events
+            .iter()
+            .rev()
+            .find(|(time, _)| *time <= now)
+            .is_some_and(|&(_, night)| night),
+    };
+    let next_change = events
+        .iter()
+        .find(|&&(time, starts_night)| time > now && starts_night != night)
+        .map(|&(time, _)| time);
+    (night, next_change)
+}
+
+fn save_state(app: &AppHandle, enabled: bool) {
+    if let Err(e) = crate::save_json_file(app, STATE_FILE, &SavedState { enabled }) {
+        log::warn!("Failed to save night light state: {}", e);
+    }
+}
+
+// Warm the screen to the configured temperature, or restore it
+fn apply(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    let temperature = app
+        .state::<AppState>()
+        .settings
+        .lock()
+        .unwrap()
+        .night_light
+        .temperature;
+    let (red, green, blue) = if enabled {
+        whitepoint(temperature)
+    } else {
+        (1.0, 1.0, 1.0)
+    };
+    platform::set_gamma_impl(red, green, blue)?;
+
+    let changed = std::mem::replace(&mut RUNTIME.lock().unwrap().enabled, enabled) != enabled;
+    if changed {
+        save_state(app, enabled);
+        tray_menu::rebuild_tray_menu(app);
+        let _ = app.emit("night-light-changed", status());
+    }
+    Ok(())
+}
+
+/// Turn the night light on or off by hand; a schedule takes over again at the next sunrise or
+/// sunset
+pub fn set(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    apply(app, enabled)
+}
+
+/// Put the screen colors back before quitting
+pub fn shutdown(app: &AppHandle) {
+    if is_active() {
+        if let Err(e) = apply(app, false) {
+            log::warn!("Failed to turn off the night light: {}", e);
+        }
+    }
+}
+
+// The place from the settings, or the last one sun times used
+async fn schedule_location(app: &AppHandle, location: &str) -> Result<Location, String> {
+    if location.trim().is_empty() {
+        geocoding::resolve(app, None).await
+    } else {
+        geocoding::lookup(app, location).await
+    }
+}
+
+async fn run_schedule(app: AppHandle, location: String, generation: u64) {
+    let mut scheduled = None; // night or day, as last switched by the schedule
+    loop {
+        let place = schedule_location(&app, &location).await;
+        if GENERATION.load(Ordering::SeqCst) != generation {
+            return; // settings changed meanwhile
+        }
+        let wait = match place {
+            Ok(place) => {
+                let now = Utc::now().timestamp();
+                let (night, next_change) = schedule_at(place.latitude, place.longitude, now);
+                RUNTIME.lock().unwrap().next_change = next_change;
+                // Only switch at sunrise and sunset, so a manual toggle holds until then
+                let result = if scheduled != Some(night) {
+                    scheduled = Some(night);
+                    apply(&app, night)
+                } else if is_active() {
+                    apply(&app, true)
+                } else {
+                    Ok(())
+                };
+                if let Err(e) = result {
+                    log::warn!("Failed to switch the night light: {}", e);
+                }
+                next_change
+                    .map(|time| Duration::from_secs((time - now).max(0) as u64 + 1))
+                    .map_or(RECHECK_INTERVAL, |until| until.min(RECHECK_INTERVAL))
+            }
+            Err(e) => {
+                log::warn!("Night light schedule: {}", e);
+                RECHECK_INTERVAL
+            }
+        };
+        tokio::time::sleep(wait).await;
+    }
+}
+
+/// Restart the schedule when its settings changed, and re-apply a changed temperature
+pub fn apply_settings(app: &AppHandle, settings: &NightLightSettings) {
+    let (previous, enabled) = {
+        let mut runtime = RUNTIME.lock().unwrap();
+        (runtime.settings.replace(settings.clone()), runtime.enabled)
+    };
+    if previous.as_ref() == Some(settings) {
+        return;
+    }
+    if enabled && previous.map(|p| p.temperature) != Some(settings.temperature) {
+        if let Err(e) = apply(app, true) {
+            log::warn!("Failed to change the night light temperature: {}", e);
+        }
+    }
+
+    let generation = GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    RUNTIME.lock().unwrap().next_change = None;
+    if settings.schedule {
+        tauri::async_runtime::spawn(run_schedule(
+            app.clone(),
+            settings.location.clone(),
+            generation,
+        ));
+    }
+}
+
+pub fn init(app: &AppHandle) {
+    let settings = app
+        .state::<AppState>()
+        .settings
+        .lock()
+        .unwrap()
+        .night_light
+        .clone();
+    // Left on by a run that didn't get to restore it; the schedule decides for itself
+    let saved: SavedState = crate::load_json_file(app, STATE_FILE);
+    if saved.enabled && !settings.schedule {
+        match platform::set_gamma_impl(1.0, 1.0, 1.0) {
+            Ok(()) => save_state(app, false),
+            Err(e) => log::warn!("Failed to restore the screen colors: {}", e),
+        }
+    }
+    apply_settings(app, &settings);
+}
+
+#[tauri::command]
+pub fn get_night_light_status() -> NightLightStatus {
+    status()
+}
+
+/// Turn the night light on or off (until the next scheduled switch)
+#[tauri::command]
+pub fn set_night_light(app: AppHandle, enabled: bool) -> Result<NightLightStatus, String> {
+    set(&app, enabled)?;
+    Ok(status())
+}
@@ -0,0 +1,179 @@
+// Native notifications: toasts on Windows, the freedesktop notification service (D-Bus) on Linux.
+// Used for results that may arrive while the window is hidden (finished downloads/conversions,
+// timers, quick actions). Clicking a notification can run a follow-up action such as opening
+// the folder that a download was saved to.
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use tauri::{AppHandle, Manager};
+
+// Job results held back while notifications are paused (during a focus session); None when not paused
+static HELD: Mutex<Option<Vec<(String, String, NotifyAction)>>> = Mutex::new(None);
+
+/// What happens when the user clicks the notification
+#[derive(Debug, Clone, Default)]
+pub enum NotifyAction {
+    #[default]
+    None,
+    OpenFolder(PathBuf),
+    ShowWindow,
+}
+
+impl NotifyAction {
+    /// Open the folder containing `path` (or `path` itself if it is a folder)
+    pub fn reveal(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref();
+        let folder = if path.is_dir() {
+            path
+        } else {
+            path.parent().unwrap_or(path)
+        };
+        NotifyAction::OpenFolder(folder.to_path_buf())
+    }
+
+    fn label(&self) -> Option<&'static str> {
+        match self {
+            NotifyAction::None => None,
+            NotifyAction::OpenFolder(_) => Some("Open folder"),
+            NotifyAction::ShowWindow => Some("Open BunchaTools"),
+        }
+    }
+
+    fn run(&self, app: &AppHandle) {
+        match self {
+            NotifyAction::None => {}
+            NotifyAction::OpenFolder(path) => {
+                if let Err(e) = crate::open_in_file_manager(&path.to_string_lossy()) {
+                    log::warn!("Notification action failed: {}", e);
+                }
+            }
+            NotifyAction::ShowWindow => crate::actions::show_main_window(app),
+        }
+    }
+}
+
+/// Show a notification; failures are logged since there is nowhere else to report them
+pub fn notify(app: &AppHandle, title: &str, body: &str, action: NotifyAction) {
+    if let Err(e) = show_notification(app, title, body, action) {
+        log::warn!("Failed to show notification '{}': {}", title, e);
+    }
+}
+
+/// Notify only while the main window is hidden; otherwise the UI already shows the result.
+/// Used for job results, which are held back while notifications are paused.
+pub fn notify_if_hidden(app: &AppHandle, title: &str, body: &str, action: NotifyAction) {
+    let visible = app
+        .get_webview_window("main")
+        .and_then(|w| w.is_visible().ok())
+        .unwrap_or(false);
+    if visible {
+        return;
+    }
+    if let Some(held) = HELD.lock().unwrap().as_mut() {
+        held.push((title.to_string(), body.to_string(), action));
+        return;
+    }
+    notify(app, title, body, action);
+}
+
+/// Hold back job result notifications until `resume_notifications`
+pub fn pause_notifications() {
+    HELD.lock().unwrap().get_or_insert_with(Vec::new);
+}
+
+/// Deliver the notifications held while paused and show new ones again
+pub fn resume_notifications(app: &AppHandle) {
+    let held = HELD.lock().unwrap().take().unwrap_or_default();
+    for (title, body, action) in held {
+        notify(app, &title, &body, action);
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn show_notification(
+    app: &AppHandle,
+    title: &str,
+    body: &str,
+    action: NotifyAction,
+) -> Result<(), String> {
+    use tauri_winrt_notification::Toast;
+
+    // Toasts need a registered AppUserModelID, which only the installed app has
+    let exe = std::env::current_exe().map_err(|e| e.to_string())?;
+    let installed = exe
+        .parent()
+        .map(|dir| !dir.ends_with("target\\debug") && !dir.ends_with("target\\release"))
+        .unwrap_or(false);
+    let identifier = app.config().identifier.clone();
+    let app_id = if installed {
+        identifier.as_str()
+    } else {
+        Toast::POWERSHELL_APP_ID
+    };
+
+    let mut toast = Toast::new(app_id).title(title).text1(body);
+    if let Some(label) = action.label() {
+        let app = app.clone();
+        toast = toast.add_button(label, "action").on_activated(move |_| {
+            action.run(&app);
+            Ok(())
+        });
+    }
+    toast
+        .show()
+        .map_err(|e| format!("Failed to show toast: {}", e))
+}
+
+#[cfg(target_os = "linux")]
+fn show_notification(
+    app: &AppHandle,
+    title: &str,
+    body: &str,
+    action: NotifyAction,
+) -> Result<(), String> {
+    let mut notification = notify_rust::Notification::new();
+    notification
+        .appname("BunchaTools")
+        .summary(title)
+        .body(body)
+        .auto_icon();
+    if let Some(label) = action.label() {
+        // "default" is what the server reports when the notification body itself is clicked
+        notification.action("default", label);
+    }
+
+    let handle = notification
+        .show()
+        .map_err(|e| format!("Failed to show notification: {}", e))?;
+
+    if action.label().is_some() {
+        // Blocks until the notification is clicked or closed
+        let app = app.clone();
+        std::thread::spawn(move || {
+            handle.wait_for_action(|id| {
+                if id == "default" {
+                    action.run(&app);
+                }
+            });
+        });
+    }
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+fn show_notification(
+    app: &AppHandle,
+    title: &str,
+    body: &str,
+    _action: NotifyAction,
+) -> Result<(), String> {
+    use tauri_plugin_notification::NotificationExt;
+
+    app.notification()
+        .builder()
+        .title(title)
+        .body(body)
+        .show()
+        .map_err(|e| e.to_string())
+}
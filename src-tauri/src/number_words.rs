@@ -0,0 +1,678 @@
+// Spelled-out amounts for invoices and checks ("one thousand two hundred thirty-four euros
+// and fifty cents"). Languages use the same ISO 639-1 codes as translation, and each
+// language follows its own grammar: gendered "un/una" before Spanish nouns, French "et un"
+// and "quatre-vingts", German numbers written as one word below a million.
+
+// Also the index into each currency's names
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Lang {
+    En,
+    Es,
+    Fr,
+    De,
+}
+
+const LANGUAGES: &[(&str, Lang)] = &[
+    ("en", Lang::En),
+    ("es", Lang::Es),
+    ("fr", Lang::Fr),
+    ("de", Lang::De),
+];
+
+// Amounts at or above this don't fit the scale words below (trillion / billón / Billion)
+const MAX_AMOUNT: f64 = 1e15;
+
+#[derive(Debug, Clone, Copy)]
+struct Noun {
+    one: &'static str,
+    many: &'static str,
+    feminine: bool,
+}
+
+const fn m(one: &'static str, many: &'static str) -> Noun {
+    Noun {
+        one,
+        many,
+        feminine: false,
+    }
+}
+
+const fn f(one: &'static str, many: &'static str) -> Noun {
+    Noun {
+        one,
+        many,
+        feminine: true,
+    }
+}
+
+struct Currency {
+    code: &'static str,
+    decimals: u32,
+    // Indexed like `LANGUAGES`
+    major: [Noun; 4],
+    minor: [Noun; 4],
+}
+
+const CURRENCIES: &[Currency] = &[
+    Currency {
+        code: "EUR",
+        decimals: 2,
+        major: [
+            m("euro", "euros"),
+            m("euro", "euros"),
+            m("euro", "euros"),
+            m("Euro", "Euro"),
+        ],
+        minor: [
+            m("cent", "cents"),
+            m("céntimo", "céntimos"),
+            m("centime", "centimes"),
+            m("Cent", "Cent"),
+        ],
+    },
+    Currency {
+        code: "USD",
+        decimals: 2,
+        major: [
+            m("dollar", "dollars"),
+            m("dólar", "dólares"),
+            m("dollar", "dollars"),
+            m("Dollar", "Dollar"),
+        ],
+        minor: [
+            m("cent", "cents"),
+            m("centavo", "centavos"),
+            m("cent", "cents"),
+            m("Cent", "Cent"),
+        ],
+    },
+    Currency {
+        code: "GBP",
+        decimals: 2,
+        major: [
+            m("pound", "pounds"),
+            f("libra", "libras"),
+            f("livre", "livres"),
+            m("Pfund", "Pfund"),
+        ],
+        minor: [
+            m("penny", "pence"),
+            m("penique", "peniques"),
+            m("penny", "pence"),
+            m("Penny", "Pence"),
+        ],
+    },
+    Currency {
+        code: "CHF",
+        decimals: 2,
+        major: [
+            m("franc", "francs"),
+            m("franco", "francos"),
+            m("franc", "francs"),
+            m("Franken", "Franken"),
+        ],
+        minor: [
+            m("centime", "centimes"),
+            m("céntimo", "céntimos"),
+            m("centime", "centimes"),
+            m("Rappen", "Rappen"),
+        ],
+    },
+    Currency {
+        code: "CAD",
+        decimals: 2,
+        major: [
+            m("Canadian dollar", "Canadian dollars"),
+            m("dólar canadiense", "dólares canadienses"),
+            m("dollar canadien", "dollars canadiens"),
+            m("kanadischer Dollar", "kanadische Dollar"),
+        ],
+        minor: [
+            m("cent", "cents"),
+            m("centavo", "centavos"),
+            m("cent", "cents"),
+            m("Cent", "Cent"),
+        ],
+    },
+    Currency {
+        code: "AUD",
+        decimals: 2,
+        major: [
+            m("Australian dollar", "Australian dollars"),
+            m("dólar australiano", "dólares australianos"),
+            m("dollar australien", "dollars australiens"),
+            m("australischer Dollar", "australische Dollar"),
+        ],
+        minor: [
+            m("cent", "cents"),
+            m("centavo", "centavos"),
+            m("cent", "cents"),
+            m("Cent", "Cent"),
+        ],
+    },
+    Currency {
+        code: "MXN",
+        decimals: 2,
+        major: [
+            m("peso", "pesos"),
+            m("peso", "pesos"),
+            m("peso", "pesos"),
+            m("Peso", "Pesos"),
+        ],
+        minor: [
+            m("centavo", "centavos"),
+            m("centavo", "centavos"),
+            m("centavo", "centavos"),
+            m("Centavo", "Centavos"),
+        ],
+    },
+    Currency {
+        code: "JPY",
+        decimals: 0,
+        major: [
+            m("yen", "yen"),
+            m("yen", "yenes"),
+            m("yen", "yens"),
+            m("Yen", "Yen"),
+        ],
+        minor: [m("", ""), m("", ""), m("", ""), m("", "")],
+    },
+];
+
+fn join(parts: Vec<String>) -> String {
+    parts
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// ============================================================================
+// English
+// ============================================================================
+
+const EN_ONES: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+const EN_TENS: [&str; 10] = [
+    "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+const EN_SCALES: [&str; 5] = ["", "thousand", "million", "billion", "trillion"];
+
+fn en_below_thousand(n: u64) -> String {
+    let (hundreds, rest) = (n / 100, n % 100);
+    let mut parts = Vec::new();
+    if hundreds > 0 {
+        parts.push(format!("{} hundred", EN_ONES[hundreds as usize]));
+    }
+    if rest >= 20 {
+        let (tens, ones) = (rest / 10, rest % 10);
+        parts.push(if ones == 0 {
+            EN_TENS[tens as usize].to_string()
+        } else {
+            format!("{}-{}", EN_TENS[tens as usize], EN_ONES[ones as usize])
+        });
+    } else if rest > 0 {
+        parts.push(EN_ONES[rest as usize].to_string());
+    }
+    join(parts)
+}
+
+fn en_number(n: u64) -> String {
+    if n == 0 {
+        return EN_ONES[0].to_string();
+    }
+    let mut parts = Vec::new();
+    for (scale, word) in EN_SCALES.iter().enumerate().rev() {
+        let group = (n / 1000u64.pow(scale as u32)) % 1000;
+        if group > 0 {
+            parts.push(en_below_thousand(group));
+            parts.push(word.to_string());
+        }
+    }
+    join(parts)
+}
+
+// ============================================================================
+// Spanish
+// ============================================================================
+
+const ES_ONES: [&str; 30] = [
+    "cero",
+    "uno",
+    "dos",
+    "tres",
+    "cuatro",
+    "cinco",
+    "seis",
+    "siete",
+    "ocho",
+    "nueve",
+    "diez",
+    "once",
+    "doce",
+    "trece",
+    "catorce",
+    "quince",
+    "dieciséis",
+    "diecisiete",
+    "dieciocho",
+    "diecinueve",
+    "veinte",
+    "veintiuno",
+    "veintidós",
+    "veintitrés",
+    "veinticuatro",
+    "veinticinco",
+    "veintiséis",
+    "veintisiete",
+    "veintiocho",
+    "veintinueve",
+];
+const ES_TENS: [&str; 10] = [
+    "",
+    "",
+    "",
+    "treinta",
+    "cuarenta",
+    "cincuenta",
+    "sesenta",
+    "setenta",
+    "ochenta",
+    "noventa",
+];
+const ES_HUNDREDS: [&str; 10] = [
+    "",
+    "ciento",
+    "doscientos",
+    "trescientos",
+    "cuatrocientos",
+    "quinientos",
+    "seiscientos",
+    "setecientos",
+    "ochocientos",
+    "novecientos",
+];
+
+// "uno" agrees with the noun ("una libra") and shortens right before one ("un euro")
+fn es_one(word: &str, feminine: bool, apocope: bool) -> String {
+    match (feminine, apocope) {
+        (true, _) => format!("{}a", &word[..word.len() - 1]),
+        (false, true) if word == "uno" => "un".to_string(),
+        (false, true) => "veintiún".to_string(),
+        (false, false) => word.to_string(),
+    }
+}
+
+fn es_below_thousand(n: u64, feminine: bool, apocope: bool) -> String {
+    let (hundreds, rest) = (n / 100, n % 100);
+    let mut parts = Vec::new();
+    if n == 100 {
+        return "cien".to_string();
+    }
+    if hundreds > 0 {
+        let word = ES_HUNDREDS[hundreds as usize];
+        parts.push(if feminine && hundreds > 1 {
+            format!("{}as", &word[..word.len() - 2])
+        } else {
+            word.to_string()
+        });
+    }
+    if rest > 0 {
+        let word = |n: u64| {
+            let word = ES_ONES[n as usize];
+            if n == 1 || n == 21 {
+                es_one(word, feminine, apocope)
+            } else {
+                word.to_string()
+            }
+        };
+        parts.push(if rest < 30 {
+            word(rest)
+        } else if rest % 10 == 0 {
+            ES_TENS[(rest / 10) as usize].to_string()
+        } else {
+            format!("{} y {}", ES_TENS[(rest / 10) as usize], word(rest % 10))
+        });
+    }
+    join(parts)
+}
+
+fn es_below_million(n: u64, feminine: bool, apocope: bool) -> String {
+    let (thousands, rest) = (n / 1000, n % 1000);
+    let thousands = match thousands {
+        0 => String::new(),
+        1 => "mil".to_string(),
+        _ => format!("{} mil", es_below_thousand(thousands, feminine, true)),
+    };
+    join(vec![thousands, es_below_thousand(rest, feminine, apocope)])
+}
+
+fn es_number(n: u64, feminine: bool, apocope: bool) -> String {
+    if n == 0 {
+        return ES_ONES[0].to_string();
+    }
+    // Spanish counts in long scale: mil millones, then billón
+    let scale = |count: u64, one: &str, many: &str| match count {
+        0 => String::new(),
+        1 => format!("un {}", one),
+        _ => format!("{} {}", es_below_million(count, false, true), many),
+    };
+    join(vec![
+        scale(n / 1_000_000_000_000, "billón", "billones"),
+        scale((n / 1_000_000) % 1_000_000, "millón", "millones"),
+        es_below_million(n % 1_000_000, feminine, apocope),
+    ])
+}
+
+// ============================================================================
+// French
+// ============================================================================
+
+const FR_ONES: [&str; 20] = [
+    "zéro", "un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf", "dix", "onze",
+    "douze", "treize", "quatorze", "quinze", "seize", "dix-sept", "dix-huit", "dix-neuf",
+];
+const FR_TENS: [&str; 7] = [
+    "",
+    "",
+    "vingt",
+    "trente",
+    "quarante",
+    "cinquante",
+    "soixante",
+];
+
+// `last` is whether nothing multiplies this number: "quatre-vingts" and "deux cents" only
+// take their -s at the very end ("quatre-vingt mille", but "quatre-vingts millions")
+fn fr_below_hundred(n: u64, feminine: bool, last: bool) -> String {
+    let one = |n: u64| {
+        if n == 1 && feminine {
+            "une".to_string()
+        } else {
+            FR_ONES[n as usize].to_string()
+        }
+    };
+    let (tens, ones) = (n / 10, n % 10);
+    match tens {
+        0 | 1 => one(n),
+        2..=6 => match ones {
+            0 => FR_TENS[tens as usize].to_string(),
+            1 => format!("{} et {}", FR_TENS[tens as usize], one(1)),
+            _ => format!("{}-{}", FR_TENS[tens as usize], one(ones)),
+        },
+        7 if ones == 1 => "soixante et onze".to_string(),
+        7 => format!("soixante-{}", FR_ONES[(10 + ones) as usize]),
+        8 if ones == 0 => if last {
+            "quatre-vingts"
+        } else {
+            "quatre-vingt"
+        }
+        .to_string(),
+        8 => format!("quatre-vingt-{}", one(ones)),
+        _ => format!("quatre-vingt-{}", FR_ONES[(10 + ones) as usize]),
+    }
+}
+
+fn fr_below_thousand(n: u64, feminine: bool, last: bool) -> String {
+    let (hundreds, rest) = (n / 100, n % 100);
+    let hundreds = match hundreds {
+        0 => String::new(),
+        1 => "cent".to_string(),
+        _ if rest == 0 && last => format!("{} cents", FR_ONES[hundreds as usize]),
+        _ => format!("{} cent", FR_ONES[hundreds as usize]),
+    };
+    let rest = if rest > 0 {
+        fr_below_hundred(rest, feminine, last)
+    } else {
+        String::new()
+    };
+    join(vec![hundreds, rest])
+}
+
+fn fr_number(n: u64, feminine: bool) -> String {
+    if n == 0 {
+        return FR_ONES[0].to_string();
+    }
+    let scale = |count: u64, word: &str| match count {
+        0 => String::new(),
+        1 => format!("un {}", word),
+        _ => format!("{} {}s", fr_below_thousand(count, false, true), word),
+    };
+    let thousands = match (n / 1000) % 1000 {
+        0 => String::new(),
+        1 => "mille".to_string(),
+        count => format!("{} mille", fr_below_thousand(count, false, false)),
+    };
+    join(vec![
+        scale(n / 1_000_000_000_000, "billion"),
+        scale((n / 1_000_000_000) % 1000, "milliard"),
+        scale((n / 1_000_000) % 1000, "million"),
+        thousands,
+        fr_below_thousand(n % 1000, feminine, true),
+    ])
+}
+
+// ============================================================================
+// German
+// ============================================================================
+
+const DE_ONES: [&str; 20] = [
+    "null",
+    "eins",
+    "zwei",
+    "drei",
+    "vier",
+    "fünf",
+    "sechs",
+    "sieben",
+    "acht",
+    "neun",
+    "zehn",
+    "elf",
+    "zwölf",
+    "dreizehn",
+    "vierzehn",
+    "fünfzehn",
+    "sechzehn",
+    "siebzehn",
+    "achtzehn",
+    "neunzehn",
+];
+const DE_TENS: [&str; 10] = [
+    "", "", "zwanzig", "dreißig", "vierzig", "fünfzig", "sechzig", "siebzig", "achtzig", "neunzig",
+];
+
+fn de_below_thousand(n: u64) -> String {
+    let (hundreds, rest) = (n / 100, n % 100);
+    let mut word = String::new();
+    if hundreds > 0 {
+        word.push_str(if hundreds == 1 {
+            "ein"
+        } else {
+            DE_ONES[hundreds as usize]
+        });
+        word.push_str("hundert");
+    }
+    if rest >= 20 {
+        let (tens, ones) = (rest / 10, rest % 10);
+        if ones > 0 {
+            word.push_str(if ones == 1 {
+                "ein"
+            } else {
+                DE_ONES[ones as usize]
+            });
+            word.push_str("und");
+        }
+        word.push_str(DE_TENS[tens as usize]);
+    } else if rest > 0 {
+        word.push_str(DE_ONES[rest as usize]);
+    }
+    word
+}
+
+// "eins" only stands alone; in front of anything it's "ein" ("einundzwanzig", "eintausend")
+fn de_prefix(n: u64) -> String {
+    let word = de_below_thousand(n);
+    match word.strip_suffix("eins") {
+        Some(stem) => format!("{}ein", stem),
+        None => word,
+    }
+}
+
+fn de_number(n: u64, before_noun: bool) -> String {
+    if n == 0 {
+        return DE_ONES[0].to_string();
+    }
+    if n == 1 && before_noun {
+        return "ein".to_string();
+    }
+    let scale = |count: u64, one: &str, many: &str| match count {
+        0 => String::new(),
+        1 => format!("eine {}", one),
+        _ => format!("{} {}", de_prefix(count), many),
+    };
+    let thousands = match (n / 1000) % 1000 {
+        0 => String::new(),
+        count => format!("{}tausend", de_prefix(count)),
+    };
+    join(vec![
+        scale(n / 1_000_000_000_000, "Billion", "Billionen"),
+        scale((n / 1_000_000_000) % 1000, "Milliarde", "Milliarden"),
+        scale((n / 1_000_000) % 1000, "Million", "Millionen"),
+        format!("{}{}", thousands, de_below_thousand(n % 1000)),
+    ])
+}
+
+// ============================================================================
+// Amounts
+// ============================================================================
+
+fn number(lang: Lang, n: u64, noun: Option<Noun>) -> String {
+    let feminine = noun.is_some_and(|noun| noun.feminine);
+    match lang {
+        Lang::En => en_number(n),
+        Lang::Es => es_number(n, feminine, noun.is_some()),
+        Lang::Fr => fr_number(n, feminine),
+        Lang::De => de_number(n, noun.is_some()),
+    }
+}
+
+// "fifty cents", "un euro", "zéro euro", "un millón de euros"
+fn counted(lang: Lang, n: u64, noun: Noun) -> String {
+    let singular = match lang {
+        Lang::Fr => n < 2,
+        _ => n == 1,
+    };
+    let name = if singular { noun.one } else { noun.many };
+    let words = number(lang, n, Some(noun));
+    let round_millions = n >= 1_000_000 && n % 1_000_000 == 0;
+    match lang {
+        Lang::Es if round_millions => format!("{} de {}", words, name),
+        Lang::Fr if round_millions && name.starts_with(['a', 'e', 'i', 'o', 'u', 'y']) => {
+            format!("{} d'{}", words, name)
+        }
+        Lang::Fr if round_millions => format!("{} de {}", words, name),
+        _ => format!("{} {}", words, name),
+    }
+}
+
+fn parse_lang(lang: &str) -> Result<Lang, String> {
+    let code = lang
+        .trim()
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    LANGUAGES
+        .iter()
+        .find(|(known, _)| *known == code)
+        .map(|(_, lang)| *lang)
+        .ok_or_else(|| {
+            format!(
+                "Number words aren't available in {}",
+                crate::get_language_name(&code)
+            )
+        })
+}
+
+/// Spell out `amount` in `lang` (an ISO 639-1 code), as a currency amount when `currency`
+/// (an ISO 4217 code) is given and as a check-style "… and 50/100" otherwise
+pub fn spell(amount: f64, lang: &str, currency: Option<&str>) -> Result<String, String> {
+    let lang = parse_lang(lang)?;
+    let index = lang as usize;
+    let currency = match currency.map(str::trim).filter(|code| !code.is_empty()) {
+        Some(code) => Some(
+            CURRENCIES
+                .iter()
+                .find(|c| c.code.eq_ignore_ascii_case(code))
+                .ok_or_else(|| format!("Unsupported currency: {}", code.to_uppercase()))?,
+        ),
+        None => None,
+    };
+    if !amount.is_finite() || amount.abs() >= MAX_AMOUNT {
+        return Err("Amount is too large to spell out".to_string());
+    }
+
+    let decimals = currency.map_or(2, |c| c.decimals);
+    let factor = 10u64.pow(decimals);
+    let total = (amount.abs() * factor as f64).round() as u64;
+    let (major, minor) = (total / factor, total % factor);
+
+    let and = match lang {
+        Lang::En => "and",
+        Lang::Es => "con",
+        Lang::Fr => "et",
+        Lang::De => "und",
+    };
+    let text = match currency {
+        Some(currency) if major == 0 && minor > 0 => counted(lang, minor, currency.minor[index]),
+        Some(currency) if minor > 0 => format!(
+            "{} {} {}",
+            counted(lang, major, currency.major[index]),
+            and,
+            counted(lang, minor, currency.minor[index])
+        ),
+        Some(currency) => counted(lang, major, currency.major[index]),
+        None if minor > 0 => format!("{} {} {:02}/100", number(lang, major, None), and, minor),
+        None => number(lang, major, None),
+    };
+
+    if total > 0 && amount < 0.0 {
+        let minus = match lang {
+            Lang::Es => "menos",
+            Lang::Fr => "moins",
+            Lang::En | Lang::De => "minus",
+        };
+        return Ok(format!("{} {}", minus, text));
+    }
+    Ok(text)
+}
+
+#[tauri::command]
+pub fn number_to_words(
+    amount: f64,
+    lang: String,
+    currency: Option<String>,
+) -> Result<String, String> {
+    spell(amount, &lang, currency.as_deref())
+}
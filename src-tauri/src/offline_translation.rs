@@ -0,0 +1,205 @@
+// Offline translation through translateLocally (Bergamot / Marian models, run on this computer)
+// Models are downloaded per language pair the first time the pair is needed; pairs without a
+// direct model go through English (e.g. de -> en -> fr). Apart from those downloads nothing
+// leaves the computer, so the offline engine is safe for sensitive text.
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::Stdio;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TranslationEngine {
+    #[default]
+    Online, // MyMemory, falling back to installed offline models when it can't be reached
+    Offline, // translateLocally only
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Model {
+    pub name: String, // short name, e.g. "en-de-tiny"
+    pub source: String,
+    pub target: String,
+    pub kind: String, // "base", "tiny", ...
+}
+
+/// Models listed in translateLocally's `--list-models` or `--available-models` output
+pub fn parse_model_list(output: &str) -> Vec<Model> {
+    // "Czech-English type: base version: 1; To invoke do -m cs-en-base" (--list-models), or
+    // "... To download do -d cs-en-base" (--available-models)
+    let line_pattern = Regex::new(r"-[md]\s+([a-z]{2,3})-([a-z]{2,3})-(\S+)\s*$").unwrap();
+    output
+        .lines()
+        .filter_map(|line| line_pattern.captures(line.trim()))
+        .map(|c| Model {
+            name: format!("{}-{}-{}", &c[1], &c[2], &c[3]),
+            source: c[1].to_string(),
+            target: c[2].to_string(),
+            kind: c[3].to_string(),
+        })
+        .collect()
+}
+
+// Larger models translate better; tiny ones are the fallback
+fn quality_rank(model: &Model) -> u8 {
+    match model.kind.as_str() {
+        "base" => 0,
+        "tiny" => 1,
+        _ => 2,
+    }
+}
+
+/// The models that take `source` to `target`: a direct one, or two through English
+pub fn route<'a>(models: &'a [Model], source: &str, target: &str) -> Option<Vec<&'a Model>> {
+    let best = |from: &str, to: &str| {
+        models
+            .iter()
+            .filter(|m| m.source == from && m.target == to)
+            .min_by_key(|m| quality_rank(m))
+    };
+    if let Some(model) = best(source, target) {
+        return Some(vec![model]);
+    }
+    if source == "en" || target == "en" {
+        return None;
+    }
+    Some(vec![best(source, "en")?, best("en", target)?])
+}
+
+// translateLocally's language codes are plain ISO 639-1 ("zh", not "zh-cn")
+fn base_language(code: &str) -> String {
+    code.split(['-', '_']).next().unwrap_or(code).to_lowercase()
+}
+
+fn engine_path() -> PathBuf {
+    // The Windows installer doesn't add itself to PATH
+    #[cfg(target_os = "windows")]
+    if let Some(program_files) = std::env::var_os("ProgramFiles") {
+        let installed = PathBuf::from(program_files)
+            .join("translateLocally")
+            .join("translateLocally.exe");
+        if installed.exists() {
+            return installed;
+        }
+    }
+    PathBuf::from("translateLocally")
+}
+
+// Run translateLocally with `args`, feeding it `input`; returns its output
+fn run_engine(args: &[&str], input: Option<&str>) -> Result<String, String> {
+    let not_installed = |e: std::io::Error| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            "Offline translation needs translateLocally (translatelocally.com)".to_string()
+        } else {
+            format!("Failed to run translateLocally: {}", e)
+        }
+    };
+    let mut child = crate::hidden_command(engine_path())
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(not_installed)?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(input.unwrap_or_default().as_bytes())
+            .map_err(|e| format!("Failed to send text to translateLocally: {}", e))?;
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("Failed to run translateLocally: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Offline translation failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+fn installed_models() -> Result<Vec<Model>, String> {
+    run_engine(&["--list-models"], None).map(|out| parse_model_list(&out))
+}
+
+// The models for the pair, downloading missing ones when `download` is set
+fn models_for(source: &str, target: &str, download: bool) -> Result<Vec<Model>, String> {
+    let installed = installed_models()?;
+    if let Some(models) = route(&installed, source, target) {
+        return Ok(models.into_iter().cloned().collect());
+    }
+    let no_model = || {
+        format!(
+            "No offline model for {} to {}",
+            crate::get_language_name(source),
+            crate::get_language_name(target)
+        )
+    };
+    if !download {
+        return Err(no_model());
+    }
+
+    let available = parse_model_list(&run_engine(&["--available-models"], None)?);
+    let models: Vec<Model> = route(&available, source, target)
+        .ok_or_else(no_model)?
+        .into_iter()
+        .cloned()
+        .collect();
+    for model in models.iter().filter(|m| !installed.contains(m)) {
+        log::info!("Downloading translation model {}", model.name);
+        run_engine(&["--download-model", &model.name], None)
+            .map_err(|e| format!("Failed to download {}: {}", model.name, e))?;
+    }
+    Ok(models)
+}
+
+/// Translate `text` on this computer; with `download`, missing models are fetched first
+pub async fn translate(
+    text: &str,
+    source: &str,
+    target: &str,
+    download: bool,
+) -> Result<String, String> {
+    let (text, source, target) = (
+        text.to_string(),
+        base_language(source),
+        base_language(target),
+    );
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut translated = text;
+        for model in models_for(&source, &target, download)? {
+            translated = run_engine(&["--model", &model.name], Some(&translated))?;
+        }
+        Ok(translated.trim_end().to_string())
+    })
+    .await
+    .map_err(|e| format!("Offline translation failed: {}", e))?
+}
+
+/// Installed offline models, e.g. ["de-en-base", "en-de-tiny"]
+#[tauri::command]
+pub async fn get_offline_translation_models() -> Result<Vec<String>, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        installed_models().map(|models| models.into_iter().map(|m| m.name).collect())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Download the models for a language pair ahead of time, e.g. before going offline
+#[tauri::command]
+pub async fn download_translation_models(
+    source: String,
+    target: String,
+) -> Result<Vec<String>, String> {
+    let (source, target) = (base_language(&source), base_language(&target));
+    tauri::async_runtime::spawn_blocking(move || {
+        models_for(&source, &target, true)
+            .map(|models| models.into_iter().map(|m| m.name).collect())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
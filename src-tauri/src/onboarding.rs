@@ -0,0 +1,159 @@
+// Which tools the user has tried, and tips revealed as they use them: a tool's first tips
+// show once it has been opened, the more advanced ones after a few more uses, and each is
+// gone for good once dismissed. Uses are counted by `record_tool_use`, kept with the
+// dismissed tips in `onboarding.json`, so the launcher only asks for the tips to show.
+
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+const ONBOARDING_FILE: &str = "onboarding.json";
+
+struct Tip {
+    id: &'static str,
+    tool: &'static str,
+    after_uses: u32, // shown once the tool has been used this many times
+    text: &'static str,
+}
+
+const TIPS: &[Tip] = &[
+    Tip {
+        id: "calculator-enter-copies",
+        tool: "calculator-tape",
+        after_uses: 1,
+        text: "Type a sum like 12*7 in the launcher and press Enter to copy the result",
+    },
+    Tip {
+        id: "calculator-conversions",
+        tool: "calculator-tape",
+        after_uses: 3,
+        text: "Conversions work the same way: \"5 mi to km\" or \"100 usd in eur\"",
+    },
+    Tip {
+        id: "color-history",
+        tool: "color-picker",
+        after_uses: 1,
+        text: "Picked colors are kept in the color history, even after a restart",
+    },
+    Tip {
+        id: "color-from-clipboard",
+        tool: "color-picker",
+        after_uses: 3,
+        text: "Copy a color like #1e90ff and the launcher offers to open it here",
+    },
+    Tip {
+        id: "annotate-tool-keys",
+        tool: "screenshot-annotate",
+        after_uses: 1,
+        text: "Press A, R, T, B or P for arrow, rectangle, text, blur and pixelate",
+    },
+    Tip {
+        id: "annotate-shortcuts",
+        tool: "screenshot-annotate",
+        after_uses: 2,
+        text: "Ctrl+C copies the annotated screenshot, Ctrl+S saves it, Ctrl+Z undoes",
+    },
+    Tip {
+        id: "translation-history",
+        tool: "quick-translation",
+        after_uses: 3,
+        text: "Earlier translations are under Translation History",
+    },
+    Tip {
+        id: "jobs-tray-cancel",
+        tool: "jobs-window",
+        after_uses: 1,
+        text: "The newest job can also be cancelled from the tray menu",
+    },
+];
+
+/// A tip for the launcher to show (`get_tool_tips`)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ToolTip {
+    pub id: String,
+    pub tool: String,
+    pub text: String,
+}
+
+/// How often a tool has been used
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ToolUsage {
+    pub uses: u32,
+    pub first_used: u64, // unix seconds
+    pub last_used: u64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SavedOnboarding {
+    tools: HashMap<String, ToolUsage>, // by tool id
+    dismissed: Vec<String>,            // tip ids
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// The tips of `tool` its uses have unlocked and that weren't dismissed, first unlocked first
+pub fn unlocked_tips(tool: &str, uses: u32, dismissed: &[String]) -> Vec<ToolTip> {
+    TIPS.iter()
+        .filter(|tip| tip.tool == tool && uses >= tip.after_uses)
+        .filter(|tip| !dismissed.iter().any(|id| id == tip.id))
+        .map(|tip| ToolTip {
+            id: tip.id.to_string(),
+            tool: tip.tool.to_string(),
+            text: tip.text.to_string(),
+        })
+        .collect()
+}
+
+/// Count a use of a tool; failures are only logged so they never fail opening it
+pub fn record_use(app: &AppHandle, tool_id: &str) {
+    let mut saved: SavedOnboarding = crate::load_json_file(app, ONBOARDING_FILE);
+    let now = now_secs();
+    let usage = saved
+        .tools
+        .entry(tool_id.to_string())
+        .or_insert_with(|| ToolUsage {
+            first_used: now,
+            ..Default::default()
+        });
+    usage.uses += 1;
+    usage.last_used = now;
+    if let Err(e) = crate::save_json_file(app, ONBOARDING_FILE, &saved) {
+        log::warn!("Failed to save tool usage: {}", e);
+    }
+}
+
+/// Tools that have been used, by id
+#[tauri::command]
+pub fn get_tried_tools(app: AppHandle) -> HashMap<String, ToolUsage> {
+    let saved: SavedOnboarding = crate::load_json_file(&app, ONBOARDING_FILE);
+    saved.tools
+}
+
+/// Tips to show for a tool now, the first one first
+#[tauri::command]
+pub fn get_tool_tips(app: AppHandle, tool: String) -> Vec<ToolTip> {
+    let saved: SavedOnboarding = crate::load_json_file(&app, ONBOARDING_FILE);
+    let uses = saved.tools.get(&tool).map_or(0, |usage| usage.uses);
+    unlocked_tips(&tool, uses, &saved.dismissed)
+}
+
+/// Never show a tip again
+#[tauri::command]
+pub fn dismiss_tip(app: AppHandle, id: String) -> Result<(), String> {
+    if !TIPS.iter().any(|tip| tip.id == id) {
+        return Err(format!("No tip '{}'", id));
+    }
+    let mut saved: SavedOnboarding = crate::load_json_file(&app, ONBOARDING_FILE);
+    if !saved.dismissed.contains(&id) {
+        saved.dismissed.push(id);
+        crate::save_json_file(&app, ONBOARDING_FILE, &saved)?;
+    }
+    Ok(())
+}
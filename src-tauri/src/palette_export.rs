@@ -0,0 +1,157 @@
+// Palette export for design tools: Adobe ASE, GIMP GPL, CSS custom properties, SCSS
+// variables and JSON design tokens (W3C Design Tokens format). Colors come in as any
+// notation `color_formats::parse` understands; swatches are named color-1, color-2, ...
+// in the text formats and by hex value in ASE / GPL, where the name is shown next to the swatch.
+
+use std::fs;
+
+use serde::Deserialize;
+use tauri::AppHandle;
+
+use crate::color_formats;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PaletteFormat {
+    Ase,
+    Gpl,
+    Css,
+    Scss,
+    Json,
+}
+
+fn hex(rgb: [u8; 3]) -> String {
+    format!("#{:02X}{:02X}{:02X}", rgb[0], rgb[1], rgb[2])
+}
+
+// ASE strings: u16 length in UTF-16 units including the terminator, then UTF-16BE + NUL
+fn ase_string(out: &mut Vec<u8>, text: &str) {
+    let units: Vec<u16> = text.encode_utf16().chain(std::iter::once(0)).collect();
+    out.extend_from_slice(&(units.len() as u16).to_be_bytes());
+    for unit in units {
+        out.extend_from_slice(&unit.to_be_bytes());
+    }
+}
+
+fn ase_block(out: &mut Vec<u8>, kind: u16, body: &[u8]) {
+    out.extend_from_slice(&kind.to_be_bytes());
+    out.extend_from_slice(&(body.len() as u32).to_be_bytes());
+    out.extend_from_slice(body);
+}
+
+// One group named after the palette, holding a global RGB swatch per color
+fn ase(colors: &[[u8; 3]], name: &str) -> Vec<u8> {
+    const GROUP_START: u16 = 0xC001;
+    const GROUP_END: u16 = 0xC002;
+    const COLOR_ENTRY: u16 = 0x0001;
+    const GLOBAL: u16 = 0;
+
+    let mut out = Vec::new();
+    out.extend_from_slice(b"ASEF");
+    out.extend_from_slice(&1u16.to_be_bytes()); // version 1.0
+    out.extend_from_slice(&0u16.to_be_bytes());
+    out.extend_from_slice(&(colors.len() as u32 + 2).to_be_bytes());
+
+    let mut group = Vec::new();
+    ase_string(&mut group, name);
+    ase_block(&mut out, GROUP_START, &group);
+    for &rgb in colors {
+        let mut entry = Vec::new();
+        ase_string(&mut entry, &hex(rgb));
+        entry.extend_from_slice(b"RGB ");
+        for channel in rgb {
+            entry.extend_from_slice(&(channel as f32 / 255.0).to_be_bytes());
+        }
+        entry.extend_from_slice(&GLOBAL.to_be_bytes());
+        ase_block(&mut out, COLOR_ENTRY, &entry);
+    }
+    ase_block(&mut out, GROUP_END, &[]);
+    out
+}
+
+fn gpl(colors: &[[u8; 3]], name: &str) -> String {
+    let mut out = format!("GIMP Palette\nName: {}\nColumns: 8\n#\n", name);
+    for &rgb in colors {
+        out.push_str(&format!(
+            "{:3} {:3} {:3}\t{}\n",
+            rgb[0],
+            rgb[1],
+            rgb[2],
+            hex(rgb)
+        ));
+    }
+    out
+}
+
+fn css(colors: &[[u8; 3]]) -> String {
+    let mut out = String::from(":root {\n");
+    for (i, &rgb) in colors.iter().enumerate() {
+        out.push_str(&format!("  --color-{}: {};\n", i + 1, hex(rgb)));
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn scss(colors: &[[u8; 3]]) -> String {
+    colors
+        .iter()
+        .enumerate()
+        .map(|(i, &rgb)| format!("$color-{}: {};\n", i + 1, hex(rgb)))
+        .collect()
+}
+
+fn json_tokens(colors: &[[u8; 3]], name: &str) -> String {
+    let tokens: serde_json::Map<String, serde_json::Value> = colors
+        .iter()
+        .enumerate()
+        .map(|(i, &rgb)| {
+            (
+                format!("color-{}", i + 1),
+                serde_json::json!({ "$type": "color", "$value": hex(rgb) }),
+            )
+        })
+        .collect();
+    let mut document = serde_json::Map::new();
+    document.insert(name.to_string(), tokens.into());
+    let mut out = serde_json::to_string_pretty(&document).unwrap_or_default();
+    out.push('\n');
+    out
+}
+
+/// File contents for `colors` in `format`; `name` titles the palette where the format has one
+pub fn render(colors: &[String], format: PaletteFormat, name: &str) -> Result<Vec<u8>, String> {
+    if colors.is_empty() {
+        return Err("The palette has no colors".to_string());
+    }
+    let colors = colors
+        .iter()
+        .map(|color| color_formats::parse(color))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(match format {
+        PaletteFormat::Ase => ase(&colors, name),
+        PaletteFormat::Gpl => gpl(&colors, name).into_bytes(),
+        PaletteFormat::Css => css(&colors).into_bytes(),
+        PaletteFormat::Scss => scss(&colors).into_bytes(),
+        PaletteFormat::Json => json_tokens(&colors, name).into_bytes(),
+    })
+}
+
+/// Write `colors` to `path` as `format`. The palette is named after the file.
+#[tauri::command]
+pub async fn export_palette(
+    app: AppHandle,
+    colors: Vec<String>,
+    format: PaletteFormat,
+    path: String,
+) -> Result<String, String> {
+    let path = crate::path_policy::validate_write_path(&app, &path)?;
+    let name = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .filter(|stem| !stem.is_empty())
+        .unwrap_or("palette")
+        .to_string();
+    let content = render(&colors, format, &name)?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write palette: {}", e))?;
+    Ok(path.to_string_lossy().to_string())
+}
@@ -0,0 +1,324 @@
+// Path policy for commands that write to disk
+// Destinations must resolve inside an allowed root (standard user folders, folders picked
+// through a dialog, or folders the user explicitly approved). System locations are always refused.
+// A folder outside the roots is approved through a blocking dialog, so the validate functions
+// must only be called from async commands or background threads: a sync `#[tauri::command]`
+// runs on the main thread, which the dialog needs, and would hang the app.
+
+use std::path::{Component, Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
+
+const POLICY_FILE: &str = "path_policy.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct PathPolicy {
+    pub allowed_roots: Vec<String>, // user-approved or dialog-picked folders
+}
+
+/// A file type choice in the save dialog
+#[derive(Debug, Clone, Deserialize)]
+pub struct FileFilter {
+    pub name: String,
+    pub extensions: Vec<String>,
+}
+
+/// Strip the `\\?\` prefix that `canonicalize` adds on Windows so paths stay readable
+/// and usable by external tools like ffmpeg.
+fn simplify_path(path: PathBuf) -> PathBuf {
+    let s = path.to_string_lossy();
+    if let Some(rest) = s.strip_prefix(r"\\?\") {
+        if !rest.starts_with("UNC\\") {
+            return PathBuf::from(rest);
+        }
+    }
+    path
+}
+
+/// Resolve a destination path to its canonical form.
+/// The path does not need to exist yet: the deepest existing ancestor is canonicalized
+/// and the remaining components are appended, with any `..` rejected.
+pub fn canonicalize_destination(path: &str) -> Result<PathBuf, String> {
+    let requested = PathBuf::from(path);
+    if !requested.is_absolute() {
+        return Err(format!("Path must be absolute: {}", path));
+    }
+
+    let mut existing = requested.as_path();
+    let mut remainder: Vec<&std::ffi::OsStr> = Vec::new();
+    while !existing.exists() {
+        match (existing.parent(), existing.file_name()) {
+            (Some(parent), Some(name)) => {
+                remainder.push(name);
+                existing = parent;
+            }
+            _ => return Err(format!("Invalid path: {}", path)),
+        }
+    }
+
+    // file_name() skips `..`, so check the unresolved tail explicitly
+    let tail = requested.strip_prefix(existing).unwrap_or(Path::new(""));
+    if tail.components().any(|c| matches!(c, Component::ParentDir)) {
+        return Err("Path traversal is not allowed".to_string());
+    }
+
+    let mut resolved = existing
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve path '{}': {}", path, e))?;
+    for name in remainder.iter().rev() {
+        resolved.push(name);
+    }
+
+    Ok(simplify_path(resolved))
+}
+
+/// Locations that are never writable, regardless of user approval
+fn protected_roots() -> Vec<PathBuf> {
+    let mut roots: Vec<PathBuf> = Vec::new();
+
+    #[cfg(target_os = "windows")]
+    {
+        let env_or =
+            |key: &str, fallback: &str| std::env::var(key).unwrap_or_else(|_| fallback.to_string());
+        roots.push(PathBuf::from(env_or("SystemRoot", "C:\\Windows")));
+        roots.push(PathBuf::from(env_or("ProgramFiles", "C:\\Program Files")));
+        roots.push(PathBuf::from(env_or(
+            "ProgramFiles(x86)",
+            "C:\\Program Files (x86)",
+        )));
+        roots.push(PathBuf::from(env_or("ProgramData", "C:\\ProgramData")));
+    }
+
+    // Only the system parts of /var and /run: Fedora Silverblue and other image-based systems
+    // keep home folders in /var/home, and removable media is mounted under /run/media/$USER
+    #[cfg(not(target_os = "windows"))]
+    {
+        for dir in [
+            "/bin",
+            "/boot",
+            "/dev",
+            "/etc",
+            "/lib",
+            "/lib32",
+            "/lib64",
+            "/libx32",
+            "/opt",
+            "/proc",
+            "/run/dbus",
+            "/run/initramfs",
+            "/run/lock",
+            "/run/log",
+            "/run/mount",
+            "/run/systemd",
+            "/run/udev",
+            "/sbin",
+            "/snap",
+            "/sys",
+            "/usr",
+            "/var/cache",
+            "/var/db",
+            "/var/empty",
+            "/var/lib",
+            "/var/log",
+            "/var/mail",
+            "/var/spool",
+        ] {
+            roots.push(PathBuf::from(dir));
+        }
+    }
+
+    // Never let the renderer overwrite our own binaries or sidecars
+    if let Some(exe_dir) = std::env::current_exe()
+        .ok()
+        .and_then(|p| p.parent().map(|p| p.to_path_buf()))
+    {
+        roots.push(exe_dir);
+    }
+
+    roots
+        .into_iter()
+        .map(|p| p.canonicalize().map(simplify_path).unwrap_or(p))
+        .collect()
+}
+
+fn is_protected(path: &Path) -> bool {
+    // The filesystem root itself is never a valid destination
+    if path.parent().is_none() {
+        return true;
+    }
+    protected_roots().iter().any(|root| path.starts_with(root))
+}
+
+/// Standard user folders that tools write to by default
+fn default_roots(app: &AppHandle) -> Vec<PathBuf> {
+    let resolver = app.path();
+    [
+        resolver.download_dir(),
+        resolver.desktop_dir(),
+        resolver.document_dir(),
+        resolver.picture_dir(),
+        resolver.video_dir(),
+        resolver.audio_dir(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+fn allowed_roots(app: &AppHandle) -> Vec<PathBuf> {
+    let policy: PathPolicy = crate::load_json_file(app, POLICY_FILE);
+    default_roots(app)
+        .into_iter()
+        .chain(policy.allowed_roots.into_iter().map(PathBuf::from))
+        .map(|p| p.canonicalize().map(simplify_path).unwrap_or(p))
+        .collect()
+}
+
+fn remember_root(app: &AppHandle, root: &Path) -> Result<(), String> {
+    let mut policy: PathPolicy = crate::load_json_file(app, POLICY_FILE);
+    let root = root.to_string_lossy().to_string();
+    if !policy.allowed_roots.contains(&root) {
+        policy.allowed_roots.push(root);
+        crate::save_json_file(app, POLICY_FILE, &policy)?;
+    }
+    Ok(())
+}
+
+/// Ask the user through a native dialog whether a folder outside the allowed roots may be written to.
+/// Blocks until answered, so it must not be called from the main thread (sync commands run there).
+fn confirm_override(app: &AppHandle, folder: &Path) -> bool {
+    app.dialog()
+        .message(format!(
+            "BunchaTools wants to save files in:\n\n{}\n\nThis folder is outside your usual locations. Allow it?",
+            folder.display()
+        ))
+        .title("Allow folder access")
+        .kind(MessageDialogKind::Warning)
+        .buttons(MessageDialogButtons::OkCancel)
+        .blocking_show()
+}
+
+fn validate_destination(app: &AppHandle, path: &str, is_dir: bool) -> Result<PathBuf, String> {
+    let resolved = canonicalize_destination(path)?;
+
+    if is_protected(&resolved) {
+        return Err(format!(
+            "Refusing to write to protected system location: {}",
+            resolved.display()
+        ));
+    }
+
+    if allowed_roots(app)
+        .iter()
+        .any(|root| resolved.starts_with(root))
+    {
+        return Ok(resolved);
+    }
+
+    // Explicit override: the user approves the containing folder once
+    let folder = if is_dir {
+        resolved.clone()
+    } else {
+        resolved
+            .parent()
+            .map(|p| p.to_path_buf())
+            .unwrap_or(resolved.clone())
+    };
+    if confirm_override(app, &folder) {
+        remember_root(app, &folder)?;
+        log::info!("User approved writes to {:?}", folder);
+        return Ok(resolved);
+    }
+
+    Err(format!("Writing to '{}' was not allowed", folder.display()))
+}
+
+/// Validate a file destination, returning its canonical path. May ask the user, so only call
+/// it from async commands or background threads.
+pub fn validate_write_path(app: &AppHandle, path: &str) -> Result<PathBuf, String> {
+    validate_destination(app, path, false)
+}
+
+/// Validate an output directory, returning its canonical path. May ask the user, so only call
+/// it from async commands or background threads.
+pub fn validate_output_dir(app: &AppHandle, path: &str) -> Result<PathBuf, String> {
+    validate_destination(app, path, true)
+}
+
+/// Open a native folder picker and allow writes to the selected folder
+#[tauri::command]
+pub async fn pick_output_directory(
+    app: AppHandle,
+    title: Option<String>,
+) -> Result<Option<String>, String> {
+    let mut dialog = app.dialog().file();
+    if let Some(title) = title {
+        dialog = dialog.set_title(title);
+    }
+
+    let Some(selected) = dialog.blocking_pick_folder() else {
+        return Ok(None);
+    };
+    let folder = selected.into_path().map_err(|e| e.to_string())?;
+    let resolved = canonicalize_destination(&folder.to_string_lossy())?;
+    if is_protected(&resolved) {
+        return Err(format!(
+            "Refusing to write to protected system location: {}",
+            resolved.display()
+        ));
+    }
+
+    remember_root(&app, &resolved)?;
+    Ok(Some(resolved.to_string_lossy().to_string()))
+}
+
+/// Open a native save dialog and allow writes to the selected file's folder
+#[tauri::command]
+pub async fn pick_save_path(
+    app: AppHandle,
+    default_name: Option<String>,
+    filters: Option<Vec<FileFilter>>,
+) -> Result<Option<String>, String> {
+    let mut dialog = app.dialog().file();
+    if let Some(name) = default_name {
+        dialog = dialog.set_file_name(name);
+    }
+    for filter in filters.unwrap_or_default() {
+        let exts: Vec<&str> = filter.extensions.iter().map(|e| e.as_str()).collect();
+        dialog = dialog.add_filter(filter.name, &exts);
+    }
+
+    let Some(selected) = dialog.blocking_save_file() else {
+        return Ok(None);
+    };
+    let file = selected.into_path().map_err(|e| e.to_string())?;
+    let resolved = canonicalize_destination(&file.to_string_lossy())?;
+    if is_protected(&resolved) {
+        return Err(format!(
+            "Refusing to write to protected system location: {}",
+            resolved.display()
+        ));
+    }
+
+    if let Some(parent) = resolved.parent() {
+        remember_root(&app, parent)?;
+    }
+    Ok(Some(resolved.to_string_lossy().to_string()))
+}
+
+/// List the user-approved write locations (standard folders are always allowed)
+#[tauri::command]
+pub fn get_allowed_paths(app: AppHandle) -> Vec<String> {
+    let policy: PathPolicy = crate::load_json_file(&app, POLICY_FILE);
+    policy.allowed_roots
+}
+
+/// Revoke a previously approved write location
+#[tauri::command]
+pub fn remove_allowed_path(app: AppHandle, path: String) -> Result<(), String> {
+    let mut policy: PathPolicy = crate::load_json_file(&app, POLICY_FILE);
+    policy.allowed_roots.retain(|p| p != &path);
+    crate::save_json_file(&app, POLICY_FILE, &policy)
+}
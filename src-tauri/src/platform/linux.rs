@@ -1,4 +1,4 @@
-// Linux-specific implementations using X11 (via x11rb crate)
+// Linux-specific implementations using X11 (via x11rb crate), with XDG portals on Wayland
 
 use super::PortProcess;
 use std::collections::HashSet;
@@ -14,22 +14,146 @@ use x11rb::rust_connection::RustConnection;
 // Note: EventMask, GrabMode, GrabStatus are still used by pick_color_impl
 
 // ============================================================================
-// Color Picker (X11)
+// Color Picker (XDG Screenshot portal on Wayland, X11 otherwise)
 // ============================================================================
 
-pub async fn pick_color_impl(window: tauri::WebviewWindow) -> Result<String, String> {
+/// Wayland session: X11 grabs only see XWayland windows there, so portals are needed
+fn is_wayland_session() -> bool {
+    std::env::var("XDG_SESSION_TYPE")
+        .map(|t| t.eq_ignore_ascii_case("wayland"))
+        .unwrap_or(false)
+        || std::env::var_os("WAYLAND_DISPLAY").is_some()
+}
+
+/// Pick a color from the screen, averaging the `sample_size`² pixels around the click
+pub async fn pick_color_impl(
+    window: tauri::WebviewWindow,
+    on_move: super::LoupeCallback,
+    sample_size: i32,
+) -> Result<String, String> {
+    let colors = pick_colors(window, on_move, sample_size, None).await?;
+    colors
+        .into_iter()
+        .next()
+        .ok_or_else(|| "Cancelled".to_string())
+}
+
+/// Pick colors until Escape or a right click, reporting each one to `on_pick`
+pub async fn pick_colors_continuous_impl(
+    window: tauri::WebviewWindow,
+    on_move: super::LoupeCallback,
+    sample_size: i32,
+    on_pick: super::ColorPickCallback,
+) -> Result<Vec<String>, String> {
+    pick_colors(window, on_move, sample_size, Some(on_pick)).await
+}
+
+// One pick, or with `on_pick` as many as the user clicks
+async fn pick_colors(
+    window: tauri::WebviewWindow,
+    on_move: super::LoupeCallback,
+    sample_size: i32,
+    on_pick: Option<super::ColorPickCallback>,
+) -> Result<Vec<String>, String> {
     let _ = window.hide();
     std::thread::sleep(std::time::Duration::from_millis(100));
 
+    // The portal's own picker has a magnifier, so the loupe is X11 only. It also returns a
+    // single pixel, so there's no area averaging either.
+    if is_wayland_session() {
+        let mut colors = Vec::new();
+        loop {
+            match pick_color_portal().await {
+                Ok(color) => {
+                    colors.push(color.clone());
+                    match &on_pick {
+                        // The portal picks once per request, so keep asking until cancelled
+                        Some(on_pick) => on_pick(color),
+                        None => return Ok(colors),
+                    }
+                }
+                Err(e) if e == "Cancelled" => return Ok(colors),
+                // No portal backend with PickColor (e.g. a bare compositor): try XWayland
+                Err(e) if colors.is_empty() => {
+                    log::warn!("Portal color picker unavailable, using X11: {}", e);
+                    break;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
     // Run in a blocking thread since X11 operations are synchronous
-    let result = tokio::task::spawn_blocking(|| pick_color_x11())
-        .await
-        .map_err(|e| format!("Task join error: {}", e))?;
+    let result =
+        tokio::task::spawn_blocking(move || pick_colors_x11(on_move, sample_size, on_pick))
+            .await
+            .map_err(|e| format!("Task join error: {}", e))?;
 
     result
 }
 
-fn pick_color_x11() -> Result<String, String> {
+/// Pick a color with org.freedesktop.portal.Screenshot.PickColor; the desktop shows its own picker
+async fn pick_color_portal() -> Result<String, String> {
+    use ashpd::desktop::{Color, ResponseError};
+
+    let request = Color::pick()
+        .send()
+        .await
+        .map_err(|e| format!("Color picker portal failed: {}", e))?;
+    let color = request.response().map_err(|e| match e {
+        ashpd::Error::Response(ResponseError::Cancelled) => "Cancelled".to_string(),
+        e => format!("Color picker portal failed: {}", e),
+    })?;
+
+    let channel = |value: f64| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+    Ok(format!(
+        "#{:02X}{:02X}{:02X}",
+        channel(color.red()),
+        channel(color.green()),
+        channel(color.blue())
+    ))
+}
+
+/// The `size`² pixels of the root window around (x, y) as RGB
+fn capture_pixels(
+    conn: &RustConnection,
+    root: u32,
+    x: i16,
+    y: i16,
+    size: u16,
+) -> Option<Vec<[u8; 3]>> {
+    let half = (size / 2) as i16;
+    let image = conn
+        .get_image(
+            ImageFormat::Z_PIXMAP,
+            root,
+            x - half,
+            y - half,
+            size,
+            size,
+            !0,
+        )
+        .ok()?
+        .reply()
+        .ok()?;
+    // 24/32-bit visuals use 4 bytes per pixel, BGRX
+    if image.data.len() < usize::from(size) * usize::from(size) * 4 {
+        return None;
+    }
+    Some(
+        image
+            .data
+            .chunks(4)
+            .map(|px| [px[2], px[1], px[0]])
+            .collect(),
+    )
+}
+
+fn pick_colors_x11(
+    on_move: super::LoupeCallback,
+    sample_size: i32,
+    on_pick: Option<super::ColorPickCallback>,
+) -> Result<Vec<String>, String> {
     let (conn, screen_num) = RustConnection::connect(None).map_err(|e| format!("X11 connection failed: {}. Note: This feature requires X11 (not Wayland).", e))?;
 
     let screen = &conn.setup().roots[screen_num];
@@ -67,7 +191,10 @@ fn pick_color_x11() -> Result<String, String> {
         .grab_pointer(
             true,
             root,
-            (EventMask::BUTTON_PRESS | EventMask::BUTTON_RELEASE | EventMask::KEY_PRESS).into(),
+            EventMask::BUTTON_PRESS
+                | EventMask::BUTTON_RELEASE
+                | EventMask::POINTER_MOTION
+                | EventMask::KEY_PRESS,
             GrabMode::ASYNC,
             GrabMode::ASYNC,
             root,
@@ -91,29 +218,56 @@ fn pick_color_x11() -> Result<String, String> {
         GrabMode::ASYNC,
     );
 
-    // Wait for click or escape
-    let mut click_x = 0i16;
-    let mut click_y = 0i16;
-    let mut cancelled = false;
+    // Wait for clicks until done or escape
+    let mut colors = Vec::new();
+    let mut result = Ok(());
 
     loop {
-        let event = conn
-            .wait_for_event()
-            .map_err(|e| format!("Event error: {}", e))?;
+        let event = match conn.wait_for_event() {
+            Ok(event) => event,
+            Err(e) => {
+                result = Err(format!("Event error: {}", e));
+                break;
+            }
+        };
 
         match event {
             x11rb::protocol::Event::ButtonPress(bp) => {
                 if bp.detail == 1 {
                     // Left click
-                    click_x = bp.root_x;
-                    click_y = bp.root_y;
+                    let color = read_color_x11(&conn, screen, bp.root_x, bp.root_y, sample_size);
+                    match color {
+                        Ok(color) => {
+                            colors.push(color.clone());
+                            match &on_pick {
+                                Some(on_pick) => on_pick(color),
+                                None => break,
+                            }
+                        }
+                        Err(e) => {
+                            result = Err(e);
+                            break;
+                        }
+                    }
+                } else if bp.detail == 3 && on_pick.is_some() {
+                    // Right click ends a continuous pick
                     break;
                 }
             }
+            x11rb::protocol::Event::MotionNotify(motion) => {
+                if let Some(pixels) = capture_pixels(
+                    &conn,
+                    root,
+                    motion.root_x,
+                    motion.root_y,
+                    super::LOUPE_SIZE as u16,
+                ) {
+                    on_move(motion.root_x.into(), motion.root_y.into(), pixels);
+                }
+            }
             x11rb::protocol::Event::KeyPress(kp) => {
                 // Escape key is keycode 9 on most systems
                 if kp.detail == 9 {
-                    cancelled = true;
                     break;
                 }
             }
@@ -128,13 +282,32 @@ fn pick_color_x11() -> Result<String, String> {
     let _ = conn.close_font(cursor_font);
     let _ = conn.flush();
 
-    if cancelled {
-        return Err("Cancelled".to_string());
+    result.map(|_| colors)
+}
+
+/// Color at (x, y), averaged over `sample_size`² pixels
+fn read_color_x11(
+    conn: &RustConnection,
+    screen: &x11rb::protocol::xproto::Screen,
+    x: i16,
+    y: i16,
+    sample_size: i32,
+) -> Result<String, String> {
+    let root = screen.root;
+    if sample_size > 1 {
+        // GetImage fails for areas reaching past the screen, so keep the square on it
+        let half = (sample_size / 2) as i16;
+        let x = x.clamp(half, screen.width_in_pixels as i16 - 1 - half);
+        let y = y.clamp(half, screen.height_in_pixels as i16 - 1 - half);
+        let pixels = capture_pixels(conn, root, x, y, sample_size as u16);
+        if let Some(hex) = pixels.as_deref().and_then(super::average_hex) {
+            return Ok(hex);
+        }
     }
 
     // Get pixel color using GetImage
     let image = conn
-        .get_image(ImageFormat::Z_PIXMAP, root, click_x, click_y, 1, 1, !0)
+        .get_image(ImageFormat::Z_PIXMAP, root, x, y, 1, 1, !0)
         .map_err(|e| format!("GetImage request failed: {}", e))?
         .reply()
         .map_err(|e| format!("GetImage reply failed: {}", e))?;
@@ -153,24 +326,143 @@ fn pick_color_x11() -> Result<String, String> {
 }
 
 // ============================================================================
-// Text Selection (X11 + XTest)
+// Text Selection (primary selection / RemoteDesktop portal on Wayland, X11 + XTest)
 // ============================================================================
 
+// Keysyms for the synthetic Ctrl+C sent through the RemoteDesktop portal
+const XK_CONTROL_L: i32 = 0xffe3;
+const XK_C: i32 = 0x0063;
+
+// Restore token from the last RemoteDesktop session, so the permission dialog is only shown once
+const PORTAL_TOKENS_FILE: &str = "portal_tokens.json";
+
 pub async fn start_text_selection_impl(window: tauri::WebviewWindow) -> Result<(), String> {
+    use tauri::Manager;
+
     let _ = window.hide();
     std::thread::sleep(std::time::Duration::from_millis(100));
 
-    // Copy the already-selected text using Ctrl+C
-    let result = tokio::task::spawn_blocking(|| copy_selected_text_x11())
-        .await
-        .map_err(|e| format!("Task join error: {}", e))?;
+    let result = copy_selection_impl(window.app_handle()).await;
+
+    let _ = window.show();
+    let _ = window.set_focus();
+
+    result
+}
+
+/// Put the text selected in the focused app on the clipboard
+pub async fn copy_selection_impl(app: &tauri::AppHandle) -> Result<(), String> {
+    let result = if is_wayland_session() {
+        copy_selected_text_wayland(app).await
+    } else {
+        // Copy the already-selected text using Ctrl+C
+        tokio::task::spawn_blocking(copy_selected_text_x11)
+            .await
+            .map_err(|e| format!("Task join error: {}", e))?
+    };
 
     // Wait for clipboard to be populated
     std::thread::sleep(std::time::Duration::from_millis(100));
 
-    let _ = window.show();
-    let _ = window.set_focus();
+    result
+}
+
+/// Put the selected text on the clipboard on Wayland, where XTest can't reach native windows.
+/// Selecting text already sets the primary selection, so that is read first (wl-clipboard);
+/// otherwise Ctrl+C is sent through the RemoteDesktop portal, and XWayland is the last resort.
+async fn copy_selected_text_wayland(app: &tauri::AppHandle) -> Result<(), String> {
+    use tauri_plugin_clipboard_manager::ClipboardExt;
+
+    match read_primary_selection() {
+        Some(text) => {
+            return app
+                .clipboard()
+                .write_text(text)
+                .map_err(|e| format!("Failed to write clipboard: {}", e));
+        }
+        None => log::info!("Primary selection unavailable, sending Ctrl+C through the portal"),
+    }
+
+    match send_copy_shortcut_portal(app).await {
+        Ok(()) => Ok(()),
+        Err(e) if e == "Cancelled" => Err(e),
+        Err(e) => {
+            log::warn!("RemoteDesktop portal unavailable, using X11: {}", e);
+            tokio::task::spawn_blocking(copy_selected_text_x11)
+                .await
+                .map_err(|e| format!("Task join error: {}", e))?
+        }
+    }
+}
+
+/// Current primary selection (the last text selected with the mouse), via `wl-paste`
+fn read_primary_selection() -> Option<String> {
+    let output = Command::new("wl-paste")
+        .args(["--primary", "--no-newline", "--type", "text"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let text = String::from_utf8_lossy(&output.stdout).to_string();
+    (!text.trim().is_empty()).then_some(text)
+}
+
+/// Press Ctrl+C in the focused window through org.freedesktop.portal.RemoteDesktop
+async fn send_copy_shortcut_portal(app: &tauri::AppHandle) -> Result<(), String> {
+    use ashpd::desktop::remote_desktop::{DeviceType, KeyState, RemoteDesktop};
+    use ashpd::desktop::{PersistMode, ResponseError};
+
+    let portal_error = |e: ashpd::Error| match e {
+        ashpd::Error::Response(ResponseError::Cancelled) => "Cancelled".to_string(),
+        e => format!("RemoteDesktop portal failed: {}", e),
+    };
+
+    let mut tokens: std::collections::HashMap<String, String> =
+        crate::load_json_file(app, PORTAL_TOKENS_FILE);
+    let proxy = RemoteDesktop::new().await.map_err(portal_error)?;
+    let session = proxy.create_session().await.map_err(portal_error)?;
+    proxy
+        .select_devices(
+            &session,
+            DeviceType::Keyboard.into(),
+            tokens.get("remote_desktop").map(String::as_str),
+            PersistMode::ExplicitlyRevoked,
+        )
+        .await
+        .map_err(portal_error)?;
+    let devices = proxy
+        .start(&session, None)
+        .await
+        .map_err(portal_error)?
+        .response()
+        .map_err(portal_error)?;
+
+    if let Some(token) = devices.restore_token() {
+        tokens.insert("remote_desktop".to_string(), token.to_string());
+        if let Err(e) = crate::save_json_file(app, PORTAL_TOKENS_FILE, &tokens) {
+            log::warn!("Failed to save portal restore token: {}", e);
+        }
+    }
+
+    let result = async {
+        for (keysym, state) in [
+            (XK_CONTROL_L, KeyState::Pressed),
+            (XK_C, KeyState::Pressed),
+            (XK_C, KeyState::Released),
+            (XK_CONTROL_L, KeyState::Released),
+        ] {
+            proxy
+                .notify_keyboard_keysym(&session, keysym, state)
+                .await
+                .map_err(portal_error)?;
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        Ok(())
+    }
+    .await;
 
+    let _ = session.close().await;
     result
 }
 
@@ -267,7 +559,7 @@ pub async fn scan_port_impl(port: u16) -> Result<Vec<PortProcess>, String> {
     Ok(processes)
 }
 
-fn parse_ss_line(line: &str, target_port: u16) -> Option<PortProcess> {
+pub fn parse_ss_line(line: &str, target_port: u16) -> Option<PortProcess> {
     // Example: LISTEN 0 128 0.0.0.0:3000 0.0.0.0:* users:(("node",pid=12345,fd=3))
     // Or:      LISTEN 0 128 [::]:3000 [::]:* users:(("node",pid=12345,fd=3))
     let parts: Vec<&str> = line.split_whitespace().collect();
@@ -426,9 +718,81 @@ pub async fn kill_port_process_impl(pid: u32) -> Result<(), String> {
 }
 
 // ============================================================================
-// Auto-Startup (XDG Autostart)
+// Pausing Processes (SIGSTOP / SIGCONT)
+// ============================================================================
+
+/// Stop a process where it is (`suspend`) or let it carry on
+pub fn suspend_process_impl(pid: u32, suspend: bool) -> Result<(), String> {
+    let signal = if suspend { "-STOP" } else { "-CONT" };
+    let output = Command::new("kill")
+        .args([signal, &pid.to_string()])
+        .output()
+        .map_err(|e| e.to_string())?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Failed to signal process: {}", stderr.trim()));
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// Power Status (/sys/class/power_supply)
+// ============================================================================
+
+/// Read the AC adapter and system battery from sysfs. Batteries of wireless mice and the
+/// like (scope "Device") are ignored.
+pub fn get_power_status_impl() -> Result<super::PowerStatus, String> {
+    let read = |path: &std::path::Path, name: &str| {
+        fs::read_to_string(path.join(name))
+            .map(|value| value.trim().to_string())
+            .ok()
+    };
+    let entries = match fs::read_dir("/sys/class/power_supply") {
+        Ok(entries) => entries,
+        // No power supply class at all, e.g. in a container: treat as mains powered
+        Err(_) => return Ok(super::PowerStatus::default()),
+    };
+
+    let mut mains: Option<bool> = None;
+    let mut discharging = false;
+    let mut percents = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        match read(&path, "type").as_deref() {
+            Some("Mains") => {
+                let online = read(&path, "online").as_deref() == Some("1");
+                mains = Some(mains.unwrap_or(false) || online);
+            }
+            Some("Battery") if read(&path, "scope").as_deref() != Some("Device") => {
+                if let Some(percent) = read(&path, "capacity").and_then(|c| c.parse::<u8>().ok()) {
+                    percents.push(percent.min(100));
+                }
+                discharging |= read(&path, "status").as_deref() == Some("Discharging");
+            }
+            _ => {}
+        }
+    }
+
+    let has_battery = !percents.is_empty();
+    let battery_percent = has_battery
+        .then(|| (percents.iter().map(|p| *p as u32).sum::<u32>() / percents.len() as u32) as u8);
+    // Some laptops report no mains supply; the battery discharging says the same
+    let on_battery = has_battery && mains.map_or(discharging, |online| !online);
+    Ok(super::PowerStatus {
+        on_battery,
+        battery_percent,
+    })
+}
+
+// ============================================================================
+// Auto-Startup (XDG Autostart or systemd user service)
 // ============================================================================
 
+const SYSTEMD_UNIT_NAME: &str = "bunchatools.service";
+const SYSTEMD_TARGET: &str = "graphical-session.target";
+
 fn get_autostart_dir() -> Result<PathBuf, String> {
     let config_dir = dirs::config_dir().ok_or("Could not find config directory")?;
     let autostart_dir = config_dir.join("autostart");
@@ -443,12 +807,50 @@ fn get_desktop_file_path() -> Result<PathBuf, String> {
     Ok(get_autostart_dir()?.join("bunchatools.desktop"))
 }
 
+fn get_systemd_user_dir() -> Result<PathBuf, String> {
+    let config_dir = dirs::config_dir().ok_or("Could not find config directory")?;
+    Ok(config_dir.join("systemd").join("user"))
+}
+
+/// Symlink created by `systemctl --user enable`
+fn get_systemd_wants_link() -> Result<PathBuf, String> {
+    Ok(get_systemd_user_dir()?
+        .join(format!("{}.wants", SYSTEMD_TARGET))
+        .join(SYSTEMD_UNIT_NAME))
+}
+
+fn systemctl_user(args: &[&str]) -> Result<(), String> {
+    let output = Command::new("systemctl")
+        .arg("--user")
+        .args(args)
+        .output()
+        .map_err(|e| format!("systemctl not available: {}", e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "systemctl --user {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
 pub fn get_launch_at_startup_impl() -> Result<bool, String> {
     let desktop_file = get_desktop_file_path()?;
-    Ok(desktop_file.exists())
+    Ok(desktop_file.exists() || get_systemd_wants_link()?.exists())
 }
 
-pub fn set_launch_at_startup_impl(enable: bool) -> Result<(), String> {
+pub fn set_launch_at_startup_impl(
+    enable: bool,
+    method: super::AutostartMethod,
+) -> Result<(), String> {
+    let use_systemd = enable && method == super::AutostartMethod::Systemd;
+    set_desktop_entry_autostart(enable && !use_systemd)?;
+    set_systemd_autostart(use_systemd)
+}
+
+fn set_desktop_entry_autostart(enable: bool) -> Result<(), String> {
     let desktop_file = get_desktop_file_path()?;
 
     if enable {
@@ -458,12 +860,13 @@ pub fn set_launch_at_startup_impl(enable: bool) -> Result<(), String> {
 Type=Application
 Name=BunchaTools
 Comment=A lightweight launcher for creative developers
-Exec={}
+Exec="{}" {}
 Terminal=false
 StartupNotify=false
 Categories=Utility;
 "#,
-            exe_path.display()
+            exe_path.display(),
+            crate::shell_integration::HIDDEN_ARG
         );
 
         fs::write(&desktop_file, desktop_content).map_err(|e| e.to_string())?;
@@ -476,115 +879,1299 @@ Categories=Utility;
     Ok(())
 }
 
+/// Install (or remove) a user unit started with the graphical session. Settings are saved
+/// often, so systemctl only runs when the unit file or its enabled state actually changes.
+fn set_systemd_autostart(enable: bool) -> Result<(), String> {
+    let unit_dir = get_systemd_user_dir()?;
+    let unit_file = unit_dir.join(SYSTEMD_UNIT_NAME);
+    let enabled = get_systemd_wants_link()?.exists();
+
+    if enable {
+        let exe_path = std::env::current_exe().map_err(|e| e.to_string())?;
+        let unit_content = format!(
+            r#"[Unit]
+Description=BunchaTools launcher
+PartOf={target}
+After={target}
+
+[Service]
+Type=simple
+ExecStart="{exe}" {hidden}
+Restart=on-failure
+RestartSec=5
+
+[Install]
+WantedBy={target}
+"#,
+            target = SYSTEMD_TARGET,
+            exe = exe_path.display(),
+            hidden = crate::shell_integration::HIDDEN_ARG
+        );
+
+        let unchanged =
+            fs::read_to_string(&unit_file).ok().as_deref() == Some(unit_content.as_str());
+        if unchanged && enabled {
+            return Ok(());
+        }
+        fs::create_dir_all(&unit_dir).map_err(|e| e.to_string())?;
+        fs::write(&unit_file, unit_content).map_err(|e| e.to_string())?;
+        systemctl_user(&["daemon-reload"])?;
+        systemctl_user(&["enable", SYSTEMD_UNIT_NAME])?;
+    } else if unit_file.exists() {
+        if enabled {
+            systemctl_user(&["disable", SYSTEMD_UNIT_NAME])?;
+        }
+        fs::remove_file(&unit_file).map_err(|e| e.to_string())?;
+        let _ = systemctl_user(&["daemon-reload"]);
+    }
+
+    Ok(())
+}
+
 // ============================================================================
-// FFmpeg Path Resolution
+// Shell Integration (.desktop handler for video files)
 // ============================================================================
 
-pub fn get_ffmpeg_path() -> Result<PathBuf, String> {
-    // Get executable directory
-    let exe_dir = std::env::current_exe()
-        .map_err(|e| e.to_string())?
-        .parent()
-        .ok_or("Failed to get exe directory")?
-        .to_path_buf();
+const SHELL_VIDEO_MIME_TYPES: [&str; 9] = [
+    "video/mp4",
+    "video/x-matroska",
+    "video/quicktime",
+    "video/x-msvideo",
+    "video/webm",
+    "video/x-ms-wmv",
+    "video/x-flv",
+    "video/x-m4v",
+    "video/mpeg",
+];
+
+fn get_shell_desktop_file_path() -> Result<PathBuf, String> {
+    let data_dir = dirs::data_dir().ok_or("Could not find data directory")?;
+    let applications_dir = data_dir.join("applications");
+    fs::create_dir_all(&applications_dir).map_err(|e| e.to_string())?;
+    Ok(applications_dir.join("bunchatools-convert.desktop"))
+}
 
-    // Get current working directory
-    let cwd = std::env::current_dir().unwrap_or_default();
+/// Register (or remove) a hidden .desktop entry that file managers offer as
+/// "Open With > Convert with BunchaTools" for video files. `args` go before the file paths.
+pub fn set_shell_integration_impl(enable: bool, args: &str) -> Result<(), String> {
+    let desktop_file = get_shell_desktop_file_path()?;
 
-    let possible_paths = vec![
-        // Production paths (Tauri sidecar)
-        exe_dir.join("ffmpeg"),
-        exe_dir.join("binaries").join("ffmpeg"),
-        // Development paths
-        cwd.join("src-tauri/binaries/ffmpeg-x86_64-unknown-linux-gnu"),
-        cwd.join("binaries/ffmpeg-x86_64-unknown-linux-gnu"),
-        // System ffmpeg as fallback
-        PathBuf::from("/usr/bin/ffmpeg"),
-        PathBuf::from("/usr/local/bin/ffmpeg"),
-    ];
+    if enable {
+        let exe_path = std::env::current_exe().map_err(|e| e.to_string())?;
+        let desktop_content = format!(
+            r#"[Desktop Entry]
+Type=Application
+Name=Convert with BunchaTools
+Comment=Convert videos with quality presets
+Exec="{}" {} %F
+Icon=bunchatools
+Terminal=false
+NoDisplay=true
+MimeType={};
+"#,
+            exe_path.display(),
+            args,
+            SHELL_VIDEO_MIME_TYPES.join(";")
+        );
 
-    for path in &possible_paths {
-        if path.exists() {
-            log::info!("Found FFmpeg at: {:?}", path);
-            return Ok(path.clone());
-        }
+        fs::write(&desktop_file, desktop_content).map_err(|e| e.to_string())?;
+    } else if desktop_file.exists() {
+        fs::remove_file(&desktop_file).map_err(|e| e.to_string())?;
     }
 
-    // Try to find ffmpeg in PATH using which
-    if let Ok(output) = Command::new("which").arg("ffmpeg").output() {
-        if output.status.success() {
-            let path_str = String::from_utf8_lossy(&output.stdout).trim().to_string();
-            if !path_str.is_empty() {
-                return Ok(PathBuf::from(path_str));
-            }
-        }
+    // Refresh the MIME cache so file managers pick up the change; not every desktop ships it
+    if let Some(dir) = desktop_file.parent() {
+        let _ = Command::new("update-desktop-database").arg(dir).output();
     }
 
-    Err(format!(
-        "FFmpeg not found. CWD: {:?}, Searched in: {:?}",
-        cwd, possible_paths
-    ))
+    Ok(())
 }
 
-pub fn get_ffprobe_path() -> Result<PathBuf, String> {
-    // Get executable directory
-    let exe_dir = std::env::current_exe()
-        .map_err(|e| e.to_string())?
-        .parent()
-        .ok_or("Failed to get exe directory")?
-        .to_path_buf();
+// ============================================================================
+// File Associations (shared-mime-info + .desktop MimeType)
+// ============================================================================
 
-    // Get current working directory
-    let cwd = std::env::current_dir().unwrap_or_default();
+const FILE_ASSOCIATION_DESKTOP_FILE: &str = "bunchatools-open.desktop";
+
+/// Register (or remove) BunchaTools as the handler for `associations`: MIME types for our own
+/// formats go into ~/.local/share/mime, and a hidden .desktop entry lists every type so file
+/// managers offer it under "Open With". Owned formats also become the default handler.
+pub fn set_file_associations_impl(
+    enable: bool,
+    associations: &[super::FileAssociation],
+) -> Result<(), String> {
+    let data_dir = dirs::data_dir().ok_or("Could not find data directory")?;
+    let mime_dir = data_dir.join("mime");
+    let mime_packages_dir = mime_dir.join("packages");
+    let applications_dir = data_dir.join("applications");
+    let mime_file = mime_packages_dir.join("bunchatools.xml");
+    let desktop_file = applications_dir.join(FILE_ASSOCIATION_DESKTOP_FILE);
 
-    let possible_paths = vec![
-        // Production paths (Tauri sidecar)
-        exe_dir.join("ffprobe"),
-        exe_dir.join("binaries").join("ffprobe"),
-        // Development paths
-        cwd.join("src-tauri/binaries/ffprobe-x86_64-unknown-linux-gnu"),
-        cwd.join("binaries/ffprobe-x86_64-unknown-linux-gnu"),
-        // System ffprobe as fallback
-        PathBuf::from("/usr/bin/ffprobe"),
-        PathBuf::from("/usr/local/bin/ffprobe"),
-    ];
+    if enable {
+        fs::create_dir_all(&mime_packages_dir).map_err(|e| e.to_string())?;
+        fs::create_dir_all(&applications_dir).map_err(|e| e.to_string())?;
+
+        let mime_types: String = associations
+            .iter()
+            .filter(|a| a.owned)
+            .map(|a| {
+                format!(
+                    "  <mime-type type=\"{}\">\n    <comment>{}</comment>\n    <glob pattern=\"*.{}\"/>\n  </mime-type>\n",
+                    a.mime_type, a.description, a.extension
+                )
+            })
+            .collect();
+        let mime_content = format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<mime-info xmlns=\"http://www.freedesktop.org/standards/shared-mime-info\">\n{}</mime-info>\n",
+            mime_types
+        );
+        fs::write(&mime_file, mime_content).map_err(|e| e.to_string())?;
 
-    for path in &possible_paths {
-        if path.exists() {
-            log::info!("Found FFprobe at: {:?}", path);
-            return Ok(path.clone());
+        let exe_path = std::env::current_exe().map_err(|e| e.to_string())?;
+        let mime_list: Vec<&str> = associations.iter().map(|a| a.mime_type).collect();
+        let desktop_content = format!(
+            r#"[Desktop Entry]
+Type=Application
+Name=BunchaTools
+Comment=A lightweight launcher for creative developers
+Exec="{}" %F
+Icon=bunchatools
+Terminal=false
+NoDisplay=true
+MimeType={};
+"#,
+            exe_path.display(),
+            mime_list.join(";")
+        );
+        fs::write(&desktop_file, desktop_content).map_err(|e| e.to_string())?;
+    } else {
+        if mime_file.exists() {
+            fs::remove_file(&mime_file).map_err(|e| e.to_string())?;
+        }
+        if desktop_file.exists() {
+            fs::remove_file(&desktop_file).map_err(|e| e.to_string())?;
         }
     }
 
-    // Try to find ffprobe in PATH using which
-    if let Ok(output) = Command::new("which").arg("ffprobe").output() {
-        if output.status.success() {
-            let path_str = String::from_utf8_lossy(&output.stdout).trim().to_string();
-            if !path_str.is_empty() {
-                return Ok(PathBuf::from(path_str));
-            }
+    // Rebuild the caches; missing tools just mean the desktop refreshes on its own schedule
+    let _ = Command::new("update-mime-database").arg(&mime_dir).output();
+    let _ = Command::new("update-desktop-database")
+        .arg(&applications_dir)
+        .output();
+
+    if enable {
+        for association in associations.iter().filter(|a| a.owned) {
+            let _ = Command::new("xdg-mime")
+                .args(["default", FILE_ASSOCIATION_DESKTOP_FILE, association.mime_type])
+                .output();
         }
     }
 
-    Err(format!(
-        "FFprobe not found. CWD: {:?}, Searched in: {:?}",
-        cwd, possible_paths
-    ))
+    Ok(())
 }
 
-pub fn get_ytdlp_path() -> Result<PathBuf, String> {
-    // Get executable directory
-    let exe_dir = std::env::current_exe()
-        .map_err(|e| e.to_string())?
-        .parent()
-        .ok_or("Failed to get exe directory")?
-        .to_path_buf();
+// ============================================================================
+// System Theme (XDG portal / gsettings)
+// ============================================================================
 
-    // Get current working directory
-    let cwd = std::env::current_dir().unwrap_or_default();
+/// Desktop color scheme: the XDG settings portal first, then GNOME's gsettings keys
+pub fn get_system_theme_impl() -> super::SystemTheme {
+    // org.freedesktop.appearance color-scheme: 0 = no preference, 1 = dark, 2 = light
+    let portal = Command::new("gdbus")
+        .args([
+            "call",
+            "--session",
+            "--dest",
+            "org.freedesktop.portal.Desktop",
+            "--object-path",
+            "/org/freedesktop/portal/desktop",
+            "--method",
+            "org.freedesktop.portal.Settings.Read",
+            "org.freedesktop.appearance",
+            "color-scheme",
+        ])
+        .output();
+    if let Ok(output) = portal {
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        if stdout.contains("uint32 1") {
+            return super::SystemTheme::Dark;
+        }
+        if stdout.contains("uint32 2") {
+            return super::SystemTheme::Light;
+        }
+    }
 
-    let possible_paths = vec![
-        // Production paths
+    for key in ["color-scheme", "gtk-theme"] {
+        let output = Command::new("gsettings")
+            .args(["get", "org.gnome.desktop.interface", key])
+            .output();
+        if let Ok(output) = output {
+            if String::from_utf8_lossy(&output.stdout)
+                .to_lowercase()
+                .contains("dark")
+            {
+                return super::SystemTheme::Dark;
+            }
+        }
+    }
+
+    super::SystemTheme::Light
+}
+
+// Without the settings portal there's no change signal to wait for
+const THEME_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Call `on_change` with the theme when the portal's color-scheme setting changes, or
+/// poll for changes when no settings portal is running
+pub fn watch_system_theme_impl(on_change: super::SystemThemeCallback) {
+    std::thread::spawn(move || {
+        if let Err(e) = tauri::async_runtime::block_on(watch_color_scheme_portal(&on_change)) {
+            log::info!("Settings portal unavailable ({}), polling the theme", e);
+        }
+        let mut last = get_system_theme_impl();
+        loop {
+            std::thread::sleep(THEME_POLL_INTERVAL);
+            let theme = get_system_theme_impl();
+            if theme != last {
+                last = theme;
+                on_change(theme);
+            }
+        }
+    });
+}
+
+/// Follow the portal's SettingChanged signal for org.freedesktop.appearance color-scheme
+async fn watch_color_scheme_portal(
+    on_change: &super::SystemThemeCallback,
+) -> Result<(), ashpd::Error> {
+    use ashpd::desktop::settings::{ColorScheme, Settings};
+    use futures_util::StreamExt;
+
+    let settings = Settings::new().await?;
+    // Fails if the portal backend doesn't implement Settings
+    settings.color_scheme().await?;
+    let mut changes = settings.receive_color_scheme_changed().await?;
+    while let Some(scheme) = changes.next().await {
+        on_change(match scheme {
+            ColorScheme::PreferDark => super::SystemTheme::Dark,
+            ColorScheme::PreferLight => super::SystemTheme::Light,
+            // "No preference" leaves it to the GTK theme
+            ColorScheme::NoPreference => get_system_theme_impl(),
+        });
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Multi-Monitor Support (XRandR, Wayland compositor IPC)
+// ============================================================================
+
+/// Rectangle (x, y, width, height) of the focused output from the compositor's IPC.
+/// Under Wayland, X11 only sees the pointer while it's over an XWayland window, so the
+/// compositor is asked first (Hyprland, Sway); other compositors fall back to X11.
+fn get_focused_wayland_output() -> Option<(i32, i32, i32, i32)> {
+    std::env::var_os("WAYLAND_DISPLAY")?;
+
+    let rect = |v: &serde_json::Value| -> Option<(i32, i32, i32, i32)> {
+        Some((
+            v.get("x")?.as_i64()? as i32,
+            v.get("y")?.as_i64()? as i32,
+            v.get("width")?.as_i64()? as i32,
+            v.get("height")?.as_i64()? as i32,
+        ))
+    };
+    let query = |program: &str, args: &[&str]| -> Option<Vec<serde_json::Value>> {
+        let output = Command::new(program).args(args).output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        serde_json::from_slice(&output.stdout).ok()
+    };
+
+    if let Some(monitors) = query("hyprctl", &["monitors", "-j"]) {
+        let focused = monitors
+            .iter()
+            .find(|m| m.get("focused").and_then(|f| f.as_bool()) == Some(true))?;
+        // Hyprland reports the mode size; the layout uses scaled (logical) coordinates
+        let scale = focused.get("scale").and_then(|s| s.as_f64()).unwrap_or(1.0);
+        let (x, y, width, height) = rect(focused)?;
+        return Some((
+            x,
+            y,
+            (width as f64 / scale) as i32,
+            (height as f64 / scale) as i32,
+        ));
+    }
+
+    if let Some(outputs) = query("swaymsg", &["-t", "get_outputs", "-r"]) {
+        let focused = outputs
+            .iter()
+            .find(|o| o.get("focused").and_then(|f| f.as_bool()) == Some(true))?;
+        return rect(focused.get("rect")?);
+    }
+
+    None
+}
+
+/// Rectangle of the monitor under the pointer from XRandR, clipped to _NET_WORKAREA
+/// (panels/docks) when the window manager publishes it
+fn get_x11_cursor_monitor_work_area() -> Option<(i32, i32, i32, i32)> {
+    use x11rb::protocol::randr::ConnectionExt as RandrConnectionExt;
+    use x11rb::protocol::xproto::AtomEnum;
+
+    let (conn, screen_num) = RustConnection::connect(None).ok()?;
+    let screen = &conn.setup().roots[screen_num];
+    let root = screen.root;
+
+    let pointer = conn.query_pointer(root).ok()?.reply().ok()?;
+    let (px, py) = (pointer.root_x as i32, pointer.root_y as i32);
+
+    let monitors = conn.randr_get_monitors(root, true).ok()?.reply().ok()?.monitors;
+    let monitor = monitors
+        .iter()
+        .find(|m| {
+            let (x, y) = (m.x as i32, m.y as i32);
+            px >= x && px < x + m.width as i32 && py >= y && py < y + m.height as i32
+        })
+        .or_else(|| monitors.iter().find(|m| m.primary))
+        .or(monitors.first());
+    let (mut left, mut top, mut right, mut bottom) = match monitor {
+        Some(m) => (
+            m.x as i32,
+            m.y as i32,
+            m.x as i32 + m.width as i32,
+            m.y as i32 + m.height as i32,
+        ),
+        None => (
+            0,
+            0,
+            screen.width_in_pixels as i32,
+            screen.height_in_pixels as i32,
+        ),
+    };
+
+    // _NET_WORKAREA spans all monitors, so it can only trim the edges it shares with this one
+    let workarea_atom = conn
+        .intern_atom(false, b"_NET_WORKAREA")
+        .ok()?
+        .reply()
+        .ok()?
+        .atom;
+    let workarea = conn
+        .get_property(false, root, workarea_atom, AtomEnum::CARDINAL, 0, 4)
+        .ok()
+        .and_then(|cookie| cookie.reply().ok())
+        .and_then(|reply| reply.value32().map(|v| v.collect::<Vec<u32>>()));
+    if let Some([x, y, width, height]) = workarea.as_deref() {
+        let (wx, wy) = (*x as i32, *y as i32);
+        let (wr, wb) = (wx + *width as i32, wy + *height as i32);
+        if wx < right && wr > left && wy < bottom && wb > top {
+            left = left.max(wx);
+            top = top.max(wy);
+            right = right.min(wr);
+            bottom = bottom.min(wb);
+        }
+    }
+
+    Some((left, top, right - left, bottom - top))
+}
+
+/// Get the work area of the monitor where the cursor is (or the focused output on Wayland).
+/// Returns (x, y, width, height) of the work area.
+pub fn get_cursor_monitor_work_area() -> Option<(i32, i32, i32, i32)> {
+    get_focused_wayland_output().or_else(get_x11_cursor_monitor_work_area)
+}
+
+/// Calculate the centered position for a window on the cursor's monitor.
+/// Returns (x, y) for the top-left corner of the window.
+pub fn get_centered_position_on_cursor_monitor(
+    window_width: i32,
+    window_height: i32,
+) -> Option<(i32, i32)> {
+    let (work_x, work_y, work_width, work_height) = get_cursor_monitor_work_area()?;
+
+    let x = work_x + (work_width - window_width) / 2;
+    let y = work_y + (work_height - window_height) / 2;
+
+    Some((x, y))
+}
+
+// ============================================================================
+// Do Not Disturb (GNOME / Xfce / dunst)
+// ============================================================================
+
+/// A notification daemon's do-not-disturb switch, read and written through its CLI
+#[derive(Clone, Copy)]
+struct DndBackend {
+    program: &'static str,
+    get_args: &'static [&'static str],
+    set_args: &'static [&'static str], // followed by "true" / "false"
+    inverted: bool, // the setting is "show notifications" rather than "do not disturb"
+}
+
+const DND_BACKENDS: [DndBackend; 3] = [
+    DndBackend {
+        program: "gsettings",
+        get_args: &["get", "org.gnome.desktop.notifications", "show-banners"],
+        set_args: &["set", "org.gnome.desktop.notifications", "show-banners"],
+        inverted: true,
+    },
+    DndBackend {
+        program: "xfconf-query",
+        get_args: &["-c", "xfce4-notifyd", "-p", "/do-not-disturb"],
+        set_args: &["-c", "xfce4-notifyd", "-p", "/do-not-disturb", "--create", "-t", "bool", "-s"],
+        inverted: false,
+    },
+    DndBackend {
+        program: "dunstctl",
+        get_args: &["is-paused"],
+        set_args: &["set-paused"],
+        inverted: false,
+    },
+];
+
+/// Backend for the running desktop: its own settings first, then a standalone dunst
+fn dnd_backend() -> DndBackend {
+    let desktop = std::env::var("XDG_CURRENT_DESKTOP")
+        .unwrap_or_default()
+        .to_uppercase();
+    let program = if desktop.contains("XFCE") {
+        "xfconf-query"
+    } else if ["GNOME", "UNITY", "BUDGIE", "PANTHEON"]
+        .iter()
+        .any(|d| desktop.contains(d))
+    {
+        "gsettings"
+    } else {
+        "dunstctl"
+    };
+    *DND_BACKENDS.iter().find(|b| b.program == program).unwrap_or(&DND_BACKENDS[2])
+}
+
+/// Whether notifications are currently silenced; None if the desktop has no supported switch
+pub fn get_do_not_disturb_impl() -> Option<bool> {
+    let backend = dnd_backend();
+    let output = Command::new(backend.program)
+        .args(backend.get_args)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let value = String::from_utf8_lossy(&output.stdout).trim() == "true";
+    Some(value != backend.inverted)
+}
+
+pub fn set_do_not_disturb_impl(enabled: bool) -> Result<(), String> {
+    let backend = dnd_backend();
+    let value = if enabled != backend.inverted { "true" } else { "false" };
+    let output = Command::new(backend.program)
+        .args(backend.set_args)
+        .arg(value)
+        .output()
+        .map_err(|e| format!("Do not disturb isn't supported on this desktop: {}", e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Failed to change do not disturb: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+// ============================================================================
+// Network Status (iproute2, /proc/net/route, resolv.conf)
+// ============================================================================
+
+// systemd-resolved's local stub; the real upstream servers are in its own resolv.conf
+const RESOLVED_STUB: &str = "127.0.0.53";
+const RESOLVED_UPSTREAM_CONF: &str = "/run/systemd/resolve/resolv.conf";
+
+pub fn get_network_info_impl() -> Result<super::NetworkInfo, String> {
+    let output = Command::new("ip")
+        .args(["-details", "-json", "address", "show"])
+        .output()
+        .map_err(|e| format!("Failed to run ip: {}", e))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    let interfaces = parse_ip_addr_json(&String::from_utf8_lossy(&output.stdout))?;
+
+    let route = fs::read_to_string("/proc/net/route").unwrap_or_default();
+    let (gateway_interface, gateway) = parse_proc_net_route(&route).unzip();
+
+    let mut dns_servers =
+        parse_resolv_conf(&fs::read_to_string("/etc/resolv.conf").unwrap_or_default());
+    if dns_servers.iter().any(|s| s == RESOLVED_STUB) {
+        if let Ok(upstream) = fs::read_to_string(RESOLVED_UPSTREAM_CONF) {
+            dns_servers = parse_resolv_conf(&upstream);
+        }
+    }
+
+    Ok(super::NetworkInfo {
+        interfaces,
+        gateway,
+        gateway_interface,
+        dns_servers,
+        proxy: get_system_proxy(),
+    })
+}
+
+/// Interfaces from `ip -details -json address show`
+pub fn parse_ip_addr_json(json: &str) -> Result<Vec<super::NetworkInterface>, String> {
+    let links: Vec<serde_json::Value> =
+        serde_json::from_str(json).map_err(|e| format!("Unexpected ip output: {}", e))?;
+
+    Ok(links
+        .iter()
+        .filter_map(|link| {
+            let name = link.get("ifname")?.as_str()?.to_string();
+            let flags: Vec<&str> = link
+                .get("flags")
+                .and_then(|f| f.as_array())
+                .map(|f| f.iter().filter_map(|f| f.as_str()).collect())
+                .unwrap_or_default();
+            let link_type = link.get("link_type").and_then(|t| t.as_str()).unwrap_or("");
+            let kind = link
+                .pointer("/linkinfo/info_kind")
+                .and_then(|k| k.as_str())
+                .unwrap_or(link_type);
+            // Tunnels (WireGuard, tun) report an UNKNOWN operstate while they work
+            let is_up = flags.contains(&"UP")
+                && link.get("operstate").and_then(|s| s.as_str()) != Some("DOWN");
+            let mac_address = link
+                .get("address")
+                .and_then(|a| a.as_str())
+                .filter(|a| link_type == "ether" && *a != "00:00:00:00:00:00")
+                .map(|a| a.to_uppercase());
+            let addresses = link
+                .get("addr_info")
+                .and_then(|a| a.as_array())
+                .map(|addrs| {
+                    addrs
+                        .iter()
+                        .filter_map(|a| a.get("local")?.as_str().map(String::from))
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            Some(super::NetworkInterface {
+                name,
+                description: kind.to_string(),
+                mac_address,
+                addresses,
+                is_up,
+                is_loopback: flags.contains(&"LOOPBACK"),
+                is_tunnel: matches!(link_type, "none" | "ppp")
+                    || matches!(kind, "tun" | "wireguard"),
+            })
+        })
+        .collect())
+}
+
+/// Default route from /proc/net/route as (interface, gateway); the lowest metric wins
+pub fn parse_proc_net_route(content: &str) -> Option<(String, String)> {
+    const RTF_GATEWAY: u32 = 0x2;
+
+    content
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let (iface, destination, gateway, flags, metric) = (
+                fields.first()?,
+                fields.get(1)?,
+                fields.get(2)?,
+                fields.get(3)?,
+                fields.get(6)?,
+            );
+            let flags = u32::from_str_radix(flags, 16).ok()?;
+            if *destination != "00000000" || flags & RTF_GATEWAY == 0 {
+                return None;
+            }
+            // Network byte order, printed as a little-endian hex number
+            let gateway = u32::from_str_radix(gateway, 16).ok()?;
+            let gateway = std::net::Ipv4Addr::from(gateway.to_le_bytes());
+            Some((
+                metric.parse::<u32>().ok()?,
+                iface.to_string(),
+                gateway.to_string(),
+            ))
+        })
+        .min_by_key(|(metric, ..)| *metric)
+        .map(|(_, iface, gateway)| (iface, gateway))
+}
+
+/// `nameserver` entries of a resolv.conf
+pub fn parse_resolv_conf(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            match parts.next()? {
+                "nameserver" => parts.next().map(String::from),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Proxy from the environment, or GNOME's proxy settings
+fn get_system_proxy() -> Option<String> {
+    for var in [
+        "https_proxy",
+        "HTTPS_PROXY",
+        "http_proxy",
+        "HTTP_PROXY",
+        "all_proxy",
+        "ALL_PROXY",
+    ] {
+        if let Ok(value) = std::env::var(var) {
+            if !value.trim().is_empty() {
+                return Some(value);
+            }
+        }
+    }
+
+    let gsettings = |schema: &str, key: &str| -> Option<String> {
+        let output = Command::new("gsettings")
+            .args(["get", schema, key])
+            .output()
+            .ok()?;
+        output.status.success().then(|| {
+            String::from_utf8_lossy(&output.stdout)
+                .trim()
+                .trim_matches('\'')
+                .to_string()
+        })
+    };
+    match gsettings("org.gnome.system.proxy", "mode")?.as_str() {
+        "manual" => {
+            let host =
+                gsettings("org.gnome.system.proxy.http", "host").filter(|h| !h.is_empty())?;
+            let port = gsettings("org.gnome.system.proxy.http", "port").unwrap_or_default();
+            Some(format!("{}:{}", host, port))
+        }
+        "auto" => gsettings("org.gnome.system.proxy", "autoconfig-url").filter(|u| !u.is_empty()),
+        _ => None,
+    }
+}
+
+// ============================================================================
+// LAN Neighbors (/proc/net/arp)
+// ============================================================================
+
+pub fn get_neighbors_impl() -> Result<Vec<super::Neighbor>, String> {
+    let content = fs::read_to_string("/proc/net/arp")
+        .map_err(|e| format!("Failed to read the ARP cache: {}", e))?;
+    Ok(parse_proc_net_arp(&content))
+}
+
+/// Resolved entries of /proc/net/arp
+pub fn parse_proc_net_arp(content: &str) -> Vec<super::Neighbor> {
+    const ATF_COM: u32 = 0x2; // completed entry
+
+    content
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let (ip, flags, mac) = (fields.first()?, fields.get(2)?, fields.get(3)?);
+            let flags = u32::from_str_radix(flags.trim_start_matches("0x"), 16).ok()?;
+            if flags & ATF_COM == 0 || *mac == "00:00:00:00:00:00" {
+                return None;
+            }
+            Some(super::Neighbor {
+                ip: ip.to_string(),
+                mac: mac.to_uppercase(),
+            })
+        })
+        .collect()
+}
+
+// ============================================================================
+// Idle Time & Session Lock (Mutter IdleMonitor / XScreenSaver, logind)
+// ============================================================================
+
+/// Seconds since the last keyboard/mouse input, if the desktop reports it
+pub fn get_idle_seconds_impl() -> Option<u64> {
+    // GNOME (X11 and Wayland)
+    let output = Command::new("gdbus")
+        .args([
+            "call",
+            "--session",
+            "--dest",
+            "org.gnome.Mutter.IdleMonitor",
+            "--object-path",
+            "/org/gnome/Mutter/IdleMonitor/Core",
+            "--method",
+            "org.gnome.Mutter.IdleMonitor.GetIdletime",
+        ])
+        .output();
+    if let Ok(output) = output {
+        // "(uint64 12345,)", in milliseconds
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        if let Some(ms) = stdout
+            .trim()
+            .strip_prefix("(uint64 ")
+            .and_then(|rest| rest.split([',', ')']).next())
+            .and_then(|ms| ms.parse::<u64>().ok())
+        {
+            return Some(ms / 1000);
+        }
+    }
+
+    // Other X11 desktops; under Wayland this only sees input to X clients, so don't trust it
+    if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        return None;
+    }
+    use x11rb::protocol::screensaver::ConnectionExt as ScreenSaverConnectionExt;
+    let (conn, screen_num) = RustConnection::connect(None).ok()?;
+    let root = conn.setup().roots[screen_num].root;
+    let info = conn.screensaver_query_info(root).ok()?.reply().ok()?;
+    Some(u64::from(info.ms_since_user_input) / 1000)
+}
+
+/// Whether the session is locked: logind's LockedHint, then the screensaver's state
+pub fn is_session_locked_impl() -> Option<bool> {
+    let session = std::env::var("XDG_SESSION_ID").unwrap_or_else(|_| "auto".to_string());
+    let output = Command::new("loginctl")
+        .args(["show-session", &session, "--property=LockedHint", "--value"])
+        .output();
+    let logind = output
+        .ok()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string());
+    if logind.as_deref() == Some("yes") {
+        return Some(true);
+    }
+    let logind_unlocked = logind.as_deref() == Some("no");
+
+    // Lockers that don't update logind still show up as an active screensaver
+    let screensaver = Command::new("gdbus")
+        .args([
+            "call",
+            "--session",
+            "--dest",
+            "org.freedesktop.ScreenSaver",
+            "--object-path",
+            "/org/freedesktop/ScreenSaver",
+            "--method",
+            "org.freedesktop.ScreenSaver.GetActive",
+        ])
+        .output()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string());
+    match screensaver.as_deref() {
+        Ok("(true,)") => Some(true),
+        Ok("(false,)") => Some(false),
+        _ => logind_unlocked.then_some(false),
+    }
+}
+
+// ============================================================================
+// Keep Awake (Inhibit portal / systemd-inhibit)
+// ============================================================================
+
+enum KeepAwakeInhibitor {
+    Portal(Box<ashpd::desktop::Request<()>>), // held until the request is closed
+    Process(std::process::Child),             // systemd-inhibit, held until it exits
+}
+
+static KEEP_AWAKE: Mutex<Option<KeepAwakeInhibitor>> = Mutex::new(None);
+
+const KEEP_AWAKE_REASON: &str = "Keep awake is on in BunchaTools";
+
+/// Keep the system from suspending and the screen from blanking until called again with
+/// `false`. The Inhibit portal covers both on GNOME and KDE; elsewhere logind's idle/sleep
+/// inhibitor is used, which not every screen locker honors.
+pub async fn set_keep_awake_impl(enabled: bool) -> Result<(), String> {
+    let previous = KEEP_AWAKE.lock().unwrap().take();
+    match previous {
+        Some(KeepAwakeInhibitor::Portal(request)) => {
+            if let Err(e) = request.close().await {
+                log::warn!("Failed to release the inhibitor: {}", e);
+            }
+        }
+        Some(KeepAwakeInhibitor::Process(mut child)) => {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+        None => {}
+    }
+    if !enabled {
+        return Ok(());
+    }
+
+    let inhibitor = match inhibit_portal().await {
+        Ok(request) => KeepAwakeInhibitor::Portal(Box::new(request)),
+        Err(e) => {
+            log::info!("Inhibit portal unavailable ({}), using systemd-inhibit", e);
+            let child = Command::new("systemd-inhibit")
+                .args([
+                    "--what=idle:sleep",
+                    "--who=BunchaTools",
+                    &format!("--why={}", KEEP_AWAKE_REASON),
+                    "--mode=block",
+                    // Exits with us, so the inhibitor can't outlive the app
+                    "tail",
+                    &format!("--pid={}", std::process::id()),
+                    "-f",
+                    "/dev/null",
+                ])
+                .stdin(std::process::Stdio::null())
+                .stdout(std::process::Stdio::null())
+                .stderr(std::process::Stdio::null())
+                .spawn()
+                .map_err(|e| format!("Failed to run systemd-inhibit: {}", e))?;
+            KeepAwakeInhibitor::Process(child)
+        }
+    };
+    *KEEP_AWAKE.lock().unwrap() = Some(inhibitor);
+    Ok(())
+}
+
+async fn inhibit_portal() -> Result<ashpd::desktop::Request<()>, ashpd::Error> {
+    use ashpd::desktop::inhibit::{InhibitFlags, InhibitProxy};
+
+    let proxy = InhibitProxy::new().await?;
+    proxy
+        .inhibit(
+            None,
+            InhibitFlags::Idle | InhibitFlags::Suspend,
+            KEEP_AWAKE_REASON,
+        )
+        .await
+}
+
+// ============================================================================
+// Power Actions (logind via loginctl / systemctl)
+// ============================================================================
+
+/// Lock the screen, suspend, reboot or power off right away
+pub fn power_action_impl(action: super::PowerAction) -> Result<(), String> {
+    use super::PowerAction;
+
+    let (program, args): (&str, &[&str]) = match action {
+        PowerAction::Lock => ("loginctl", &["lock-session"]),
+        PowerAction::Sleep => ("systemctl", &["suspend"]),
+        PowerAction::Restart => ("systemctl", &["reboot"]),
+        PowerAction::Shutdown => ("systemctl", &["poweroff"]),
+    };
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+
+    // Lockers that don't listen to logind (outside GNOME/KDE) usually implement ScreenSaver
+    if action == PowerAction::Lock {
+        let locked = Command::new("xdg-screensaver")
+            .arg("lock")
+            .status()
+            .is_ok_and(|status| status.success());
+        if locked {
+            return Ok(());
+        }
+    }
+    Err(if stderr.is_empty() {
+        format!("{} {} failed", program, args.join(" "))
+    } else {
+        stderr
+    })
+}
+
+// ============================================================================
+// Jump List
+// ============================================================================
+
+/// Taskbar jump lists are Windows-only; docks read actions from the .desktop file instead
+pub fn set_jump_list_impl(_tasks: &[super::JumpListTask]) -> Result<(), String> {
+    Ok(())
+}
+
+// ============================================================================
+// Desktop Shortcuts (.desktop launchers)
+// ============================================================================
+
+// An Exec= argument: quoted when needed, with the characters the spec reserves escaped.
+// Backslashes are escaped again for the string value the whole line also is.
+fn desktop_exec_arg(arg: &str) -> String {
+    let arg = arg.replace('%', "%%");
+    let plain = |c: char| c.is_ascii_alphanumeric() || "-_=./:,+@".contains(c);
+    if !arg.is_empty() && arg.chars().all(plain) {
+        return arg;
+    }
+    let mut quoted = String::from("\"");
+    for c in arg.chars() {
+        if matches!(c, '"' | '`' | '$' | '\\') {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted.replace('\\', "\\\\")
+}
+
+// The app icon saved as a file for Icon= (kept current with the running version), or the
+// themed icon name when there is none
+fn shortcut_icon(icon: Option<&image::RgbaImage>) -> String {
+    let saved = icon.and_then(|icon| {
+        let path = dirs::data_dir()?.join("bunchatools").join("bunchatools.png");
+        fs::create_dir_all(path.parent()?).ok()?;
+        if let Err(e) = icon.save(&path) {
+            log::warn!("Failed to save the shortcut icon: {}", e);
+            return None;
+        }
+        Some(path)
+    });
+    saved.map_or_else(|| "bunchatools".to_string(), |path| path.display().to_string())
+}
+
+/// Write a launcher for this executable to the desktop folder, executable and (on GNOME)
+/// trusted, so the file manager runs it instead of asking
+pub fn create_desktop_shortcut_impl(shortcut: &super::DesktopShortcut) -> Result<PathBuf, String> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let desktop_dir = dirs::desktop_dir().ok_or("Could not find the desktop folder")?;
+    fs::create_dir_all(&desktop_dir).map_err(|e| e.to_string())?;
+    let exe_path = std::env::current_exe().map_err(|e| e.to_string())?;
+    let exec: Vec<String> = std::iter::once(exe_path.to_string_lossy().to_string())
+        .chain(shortcut.args.iter().cloned())
+        .map(|arg| desktop_exec_arg(&arg))
+        .collect();
+    let content = format!(
+        r#"[Desktop Entry]
+Type=Application
+Name={}
+Comment={}
+Exec={}
+Icon={}
+Terminal=false
+StartupNotify=false
+"#,
+        shortcut.name.replace('\n', " "),
+        shortcut.comment.replace('\n', " "),
+        exec.join(" "),
+        shortcut_icon(shortcut.icon.as_ref())
+    );
+
+    let path = desktop_dir.join(format!(
+        "{}.desktop",
+        super::shortcut_file_stem(&shortcut.name)
+    ));
+    fs::write(&path, content).map_err(|e| e.to_string())?;
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).map_err(|e| e.to_string())?;
+    // Not every desktop has gio, or cares
+    let _ = Command::new("gio")
+        .arg("set")
+        .arg(&path)
+        .args(["metadata::trusted", "true"])
+        .output();
+    Ok(path)
+}
+
+// ============================================================================
+// Mouse Input (X11 + XTest)
+// ============================================================================
+
+/// Click `button`, first moving the cursor to `position` (screen pixels) if given
+pub fn click_mouse_impl(
+    button: super::MouseButton,
+    position: Option<(i32, i32)>,
+) -> Result<(), String> {
+    // Wayland only allows input injection through a RemoteDesktop portal session, which
+    // asks for permission every time it starts
+    if is_wayland_session() {
+        return Err("Simulated clicks need an X11 session".to_string());
+    }
+    let (conn, screen_num) =
+        RustConnection::connect(None).map_err(|e| format!("X11 connection failed: {}", e))?;
+    let root = conn.setup().roots[screen_num].root;
+
+    if let Some((x, y)) = position {
+        conn.xtest_fake_input(
+            x11rb::protocol::xproto::MOTION_NOTIFY_EVENT,
+            0,
+            x11rb::CURRENT_TIME,
+            root,
+            x as i16,
+            y as i16,
+            0,
+        )
+        .map_err(|e| format!("Failed to move the cursor: {}", e))?;
+    }
+    let detail = match button {
+        super::MouseButton::Left => 1,
+        super::MouseButton::Middle => 2,
+        super::MouseButton::Right => 3,
+    };
+    for event in [
+        x11rb::protocol::xproto::BUTTON_PRESS_EVENT,
+        x11rb::protocol::xproto::BUTTON_RELEASE_EVENT,
+    ] {
+        conn.xtest_fake_input(event, detail, x11rb::CURRENT_TIME, root, 0, 0, 0)
+            .map_err(|e| format!("Failed to click: {}", e))?;
+    }
+    conn.flush().map_err(|e| format!("Failed to click: {}", e))
+}
+
+// Keysym that types `c`: Latin-1 keysyms are the code points, others are 0x01000000 + it
+fn keysym_for(c: char) -> Option<u32> {
+    match c {
+        '\n' => Some(0xff0d), // Return
+        '\t' => Some(0xff09), // Tab
+        '\r' => None,
+        ' '..='~' | '\u{a0}'..='\u{ff}' => Some(c as u32),
+        c if c.is_control() => None,
+        c => Some(0x0100_0000 + c as u32),
+    }
+}
+
+/// Type `text` into the focused window. Each character goes through a spare keycode remapped
+/// to its keysym (as xdotool does), so any text types whatever the keyboard layout.
+pub fn type_text_impl(text: &str) -> Result<(), String> {
+    // Same as for clicks: Wayland needs a RemoteDesktop portal session
+    if is_wayland_session() {
+        return Err("Typing text needs an X11 session".to_string());
+    }
+    let (conn, screen_num) =
+        RustConnection::connect(None).map_err(|e| format!("X11 connection failed: {}", e))?;
+    let setup = conn.setup();
+    let root = setup.roots[screen_num].root;
+    let (min_keycode, max_keycode) = (setup.min_keycode, setup.max_keycode);
+
+    let mapping = conn
+        .get_keyboard_mapping(min_keycode, max_keycode - min_keycode + 1)
+        .map_err(|e| format!("Failed to read the keyboard mapping: {}", e))?
+        .reply()
+        .map_err(|e| format!("Failed to read the keyboard mapping: {}", e))?;
+    let per_keycode = mapping.keysyms_per_keycode as usize;
+    // A keycode without keysyms, so remapping it doesn't disturb any real key
+    let spare = (min_keycode..=max_keycode)
+        .rev()
+        .find(|&keycode| {
+            let start = (keycode - min_keycode) as usize * per_keycode;
+            mapping.keysyms[start..start + per_keycode]
+                .iter()
+                .all(|&keysym| keysym == 0)
+        })
+        .ok_or("No free keycode to type with")?;
+
+    let type_error = |e: &dyn std::fmt::Display| format!("Failed to type: {}", e);
+    let typed = || -> Result<(), String> {
+        let mut mapped = 0;
+        for keysym in text.chars().filter_map(keysym_for) {
+            if keysym != mapped {
+                conn.change_keyboard_mapping(1, spare, 1, &[keysym])
+                    .map_err(|e| type_error(&e))?;
+                // Round trip, then give clients a moment for the MappingNotify
+                conn.get_input_focus()
+                    .map_err(|e| type_error(&e))?
+                    .reply()
+                    .map_err(|e| type_error(&e))?;
+                std::thread::sleep(std::time::Duration::from_millis(10));
+                mapped = keysym;
+            }
+            for event in [
+                x11rb::protocol::xproto::KEY_PRESS_EVENT,
+                x11rb::protocol::xproto::KEY_RELEASE_EVENT,
+            ] {
+                conn.xtest_fake_input(event, spare, x11rb::CURRENT_TIME, root, 0, 0, 0)
+                    .map_err(|e| type_error(&e))?;
+            }
+            conn.flush().map_err(|e| type_error(&e))?;
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+        Ok(())
+    };
+    let result = typed();
+
+    // Leave the keycode unmapped again
+    let _ = conn.change_keyboard_mapping(1, spare, 1, &[0]);
+    let _ = conn.flush();
+    result
+}
+
+/// Move the pointer 1 px and back, which counts as user input without visibly moving it
+pub fn nudge_mouse_impl() -> Result<(), String> {
+    if is_wayland_session() {
+        return Err("Simulated mouse movement needs an X11 session".to_string());
+    }
+    let (conn, screen_num) =
+        RustConnection::connect(None).map_err(|e| format!("X11 connection failed: {}", e))?;
+    let root = conn.setup().roots[screen_num].root;
+    // Detail 1 makes the motion relative
+    for dx in [1, -1] {
+        conn.xtest_fake_input(
+            x11rb::protocol::xproto::MOTION_NOTIFY_EVENT,
+            1,
+            x11rb::CURRENT_TIME,
+            root,
+            dx,
+            0,
+            0,
+        )
+        .map_err(|e| format!("Failed to move the cursor: {}", e))?;
+    }
+    conn.flush()
+        .map_err(|e| format!("Failed to move the cursor: {}", e))
+}
+
+// ============================================================================
+// Night Light (XRandR CRTC gamma)
+// ============================================================================
+
+/// Scale each channel's gamma ramp on every output (1.0 = unchanged); (1, 1, 1) restores the
+/// linear ramp. The ramps belong to the X server, so they stay after the connection closes.
+pub fn set_gamma_impl(red: f64, green: f64, blue: f64) -> Result<(), String> {
+    use x11rb::protocol::randr::ConnectionExt as RandrConnectionExt;
+
+    // Wayland compositors only change gamma through their own night light settings
+    if is_wayland_session() {
+        return Err("The night light needs an X11 session".to_string());
+    }
+    let gamma_error = |e: &dyn std::fmt::Display| format!("Failed to set the gamma: {}", e);
+    let (conn, screen_num) =
+        RustConnection::connect(None).map_err(|e| format!("X11 connection failed: {}", e))?;
+    let root = conn.setup().roots[screen_num].root;
+    let resources = conn
+        .randr_get_screen_resources_current(root)
+        .map_err(|e| gamma_error(&e))?
+        .reply()
+        .map_err(|e| gamma_error(&e))?;
+    for crtc in resources.crtcs {
+        let size = conn
+            .randr_get_crtc_gamma_size(crtc)
+            .map_err(|e| gamma_error(&e))?
+            .reply()
+            .map_err(|e| gamma_error(&e))?
+            .size;
+        if size < 2 {
+            continue; // disabled output
+        }
+        let ramp = |scale: f64| -> Vec<u16> {
+            (0..size)
+                .map(|i| (i as f64 / (size - 1) as f64 * scale * 65535.0).round() as u16)
+                .collect()
+        };
+        conn.randr_set_crtc_gamma(crtc, &ramp(red), &ramp(green), &ramp(blue))
+            .map_err(|e| gamma_error(&e))?;
+    }
+    conn.flush().map_err(|e| gamma_error(&e))
+}
+
+// ============================================================================
+// FFmpeg Path Resolution
+// ============================================================================
+
+pub fn get_ffmpeg_path() -> Result<PathBuf, String> {
+    // Get executable directory
+    let exe_dir = std::env::current_exe()
+        .map_err(|e| e.to_string())?
+        .parent()
+        .ok_or("Failed to get exe directory")?
+        .to_path_buf();
+
+    // Get current working directory
+    let cwd = std::env::current_dir().unwrap_or_default();
+
+    let possible_paths = vec![
+        // Production paths (Tauri sidecar)
+        exe_dir.join("ffmpeg"),
+        exe_dir.join("binaries").join("ffmpeg"),
+        // Development paths
+        cwd.join("src-tauri/binaries/ffmpeg-x86_64-unknown-linux-gnu"),
+        cwd.join("binaries/ffmpeg-x86_64-unknown-linux-gnu"),
+        // System ffmpeg as fallback
+        PathBuf::from("/usr/bin/ffmpeg"),
+        PathBuf::from("/usr/local/bin/ffmpeg"),
+    ];
+
+    for path in &possible_paths {
+        if path.exists() {
+            log::info!("Found FFmpeg at: {:?}", path);
+            return Ok(path.clone());
+        }
+    }
+
+    // Try to find ffmpeg in PATH using which
+    if let Ok(output) = Command::new("which").arg("ffmpeg").output() {
+        if output.status.success() {
+            let path_str = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            if !path_str.is_empty() {
+                return Ok(PathBuf::from(path_str));
+            }
+        }
+    }
+
+    Err(format!(
+        "FFmpeg not found. CWD: {:?}, Searched in: {:?}",
+        cwd, possible_paths
+    ))
+}
+
+pub fn get_ffprobe_path() -> Result<PathBuf, String> {
+    // Get executable directory
+    let exe_dir = std::env::current_exe()
+        .map_err(|e| e.to_string())?
+        .parent()
+        .ok_or("Failed to get exe directory")?
+        .to_path_buf();
+
+    // Get current working directory
+    let cwd = std::env::current_dir().unwrap_or_default();
+
+    let possible_paths = vec![
+        // Production paths (Tauri sidecar)
+        exe_dir.join("ffprobe"),
+        exe_dir.join("binaries").join("ffprobe"),
+        // Development paths
+        cwd.join("src-tauri/binaries/ffprobe-x86_64-unknown-linux-gnu"),
+        cwd.join("binaries/ffprobe-x86_64-unknown-linux-gnu"),
+        // System ffprobe as fallback
+        PathBuf::from("/usr/bin/ffprobe"),
+        PathBuf::from("/usr/local/bin/ffprobe"),
+    ];
+
+    for path in &possible_paths {
+        if path.exists() {
+            log::info!("Found FFprobe at: {:?}", path);
+            return Ok(path.clone());
+        }
+    }
+
+    // Try to find ffprobe in PATH using which
+    if let Ok(output) = Command::new("which").arg("ffprobe").output() {
+        if output.status.success() {
+            let path_str = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            if !path_str.is_empty() {
+                return Ok(PathBuf::from(path_str));
+            }
+        }
+    }
+
+    Err(format!(
+        "FFprobe not found. CWD: {:?}, Searched in: {:?}",
+        cwd, possible_paths
+    ))
+}
+
+pub fn get_ytdlp_path() -> Result<PathBuf, String> {
+    // Get executable directory
+    let exe_dir = std::env::current_exe()
+        .map_err(|e| e.to_string())?
+        .parent()
+        .ok_or("Failed to get exe directory")?
+        .to_path_buf();
+
+    // Get current working directory
+    let cwd = std::env::current_dir().unwrap_or_default();
+
+    let possible_paths = vec![
+        // Production paths
         exe_dir.join("yt-dlp"),
         exe_dir.join("binaries").join("yt-dlp"),
         // Development paths - Tauri externalBin naming convention
@@ -604,3 +2191,629 @@ pub fn get_ytdlp_path() -> Result<PathBuf, String> {
         cwd, possible_paths
     ))
 }
+
+
+// ============================================================================
+// Screen Recording (x11grab / PipeWire)
+// ============================================================================
+
+/// x11grab on X11; on Wayland X11 grabs only see XWayland windows, so the portal is needed
+pub fn screen_capture_backend() -> super::ScreenCaptureBackend {
+    if is_wayland_session() {
+        super::ScreenCaptureBackend::Pipewire
+    } else {
+        super::ScreenCaptureBackend::X11grab {
+            display: std::env::var("DISPLAY").unwrap_or_else(|_| ":0".to_string()),
+        }
+    }
+}
+
+/// The default PulseAudio source, which PipeWire also serves through pipewire-pulse
+pub fn audio_capture_input(_ffmpeg: &std::path::Path) -> Result<super::AudioCaptureInput, String> {
+    Ok(audio_input("default"))
+}
+
+/// A PulseAudio source by name
+pub fn audio_input(id: &str) -> super::AudioCaptureInput {
+    super::AudioCaptureInput {
+        format: "pulse".to_string(),
+        device: id.to_string(),
+    }
+}
+
+/// The default source, then every source `pactl` knows (microphones and output monitors)
+pub fn list_audio_inputs(
+    _ffmpeg: &std::path::Path,
+) -> Result<Vec<super::AudioInputDevice>, String> {
+    let mut devices = vec![super::AudioInputDevice {
+        id: "default".to_string(),
+        name: "Default input".to_string(),
+    }];
+    let output = match Command::new("pactl")
+        .args(["list", "sources"])
+        .env("LC_ALL", "C")
+        .output()
+    {
+        Ok(output) if output.status.success() => output,
+        // Without pactl only the default source can be offered
+        _ => return Ok(devices),
+    };
+
+    // Each source is a block with `Name: alsa_input...` followed by `Description: ...`
+    let mut name = None;
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let line = line.trim();
+        if let Some(id) = line.strip_prefix("Name: ") {
+            name = Some(id.to_string());
+        } else if let Some(description) = line.strip_prefix("Description: ") {
+            if let Some(id) = name.take() {
+                devices.push(super::AudioInputDevice {
+                    id,
+                    name: description.to_string(),
+                });
+            }
+        }
+    }
+    Ok(devices)
+}
+
+/// A regular sans-serif font file for drawing text into images, as fontconfig picks it
+pub fn ui_font_path() -> Option<PathBuf> {
+    let from_fontconfig = Command::new("fc-match")
+        .args(["-f", "%{file}", "sans-serif:style=Regular"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| PathBuf::from(String::from_utf8_lossy(&output.stdout).trim()))
+        .filter(|path| path.is_file());
+    from_fontconfig.or_else(|| {
+        [
+            "/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf",
+            "/usr/share/fonts/dejavu/DejaVuSans.ttf",
+            "/usr/share/fonts/TTF/DejaVuSans.ttf",
+            "/usr/share/fonts/noto/NotoSans-Regular.ttf",
+            "/usr/share/fonts/truetype/liberation/LiberationSans-Regular.ttf",
+        ]
+        .into_iter()
+        .map(PathBuf::from)
+        .find(|path| path.is_file())
+    })
+}
+
+// ============================================================================
+// Other Apps' Windows (pinned on top, translucent), through the window manager's EWMH hints
+// ============================================================================
+
+struct WindowAtoms {
+    client_list: u32,
+    name: u32,
+    utf8_string: u32,
+    pid: u32,
+    window_type: u32,
+    type_normal: u32,
+    state: u32,
+    state_above: u32,
+    opacity: u32,
+}
+
+fn window_atoms(conn: &RustConnection) -> Result<WindowAtoms, String> {
+    let intern = |name: &[u8]| -> Result<u32, String> {
+        conn.intern_atom(false, name)
+            .ok()
+            .and_then(|cookie| cookie.reply().ok())
+            .map(|reply| reply.atom)
+            .ok_or_else(|| "The window manager can't be reached".to_string())
+    };
+    Ok(WindowAtoms {
+        client_list: intern(b"_NET_CLIENT_LIST")?,
+        name: intern(b"_NET_WM_NAME")?,
+        utf8_string: intern(b"UTF8_STRING")?,
+        pid: intern(b"_NET_WM_PID")?,
+        window_type: intern(b"_NET_WM_WINDOW_TYPE")?,
+        type_normal: intern(b"_NET_WM_WINDOW_TYPE_NORMAL")?,
+        state: intern(b"_NET_WM_STATE")?,
+        state_above: intern(b"_NET_WM_STATE_ABOVE")?,
+        opacity: intern(b"_NET_WM_WINDOW_OPACITY")?,
+    })
+}
+
+fn external_windows_connection() -> Result<(RustConnection, u32, WindowAtoms), String> {
+    if is_wayland_session() {
+        return Err("Changing other apps' windows needs an X11 session".to_string());
+    }
+    let (conn, screen_num) =
+        RustConnection::connect(None).map_err(|e| format!("X11 connection failed: {}", e))?;
+    let root = conn.setup().roots[screen_num].root;
+    let atoms = window_atoms(&conn)?;
+    Ok((conn, root, atoms))
+}
+
+fn property32(conn: &RustConnection, window: u32, property: u32, kind: u32) -> Option<Vec<u32>> {
+    conn.get_property(false, window, property, kind, 0, 1024)
+        .ok()?
+        .reply()
+        .ok()?
+        .value32()
+        .map(|values| values.collect())
+}
+
+/// Application windows the window manager lists, except this app's
+pub fn list_external_windows() -> Result<Vec<super::ExternalWindow>, String> {
+    use x11rb::protocol::xproto::AtomEnum;
+
+    let (conn, root, atoms) = external_windows_connection()?;
+    let clients = property32(&conn, root, atoms.client_list, AtomEnum::WINDOW.into())
+        .ok_or("The window manager doesn't list its windows")?;
+    let own_pid = std::process::id();
+
+    let mut windows = Vec::new();
+    // _NET_CLIENT_LIST is oldest first, list the newest first
+    for window in clients.into_iter().rev() {
+        let pid = property32(&conn, window, atoms.pid, AtomEnum::CARDINAL.into());
+        if pid.as_deref().and_then(|p| p.first()) == Some(&own_pid) {
+            continue;
+        }
+        // Docks, panels and desktops aren't for pinning
+        let types = property32(&conn, window, atoms.window_type, AtomEnum::ATOM.into())
+            .unwrap_or_default();
+        if !types.is_empty() && !types.contains(&atoms.type_normal) {
+            continue;
+        }
+        let title = [
+            (atoms.name, atoms.utf8_string),
+            (AtomEnum::WM_NAME.into(), AtomEnum::STRING.into()),
+        ]
+        .into_iter()
+        .find_map(|(property, kind)| {
+            let reply = conn
+                .get_property(false, window, property, kind, 0, 1024)
+                .ok()?
+                .reply()
+                .ok()?;
+            let title = String::from_utf8_lossy(&reply.value).trim().to_string();
+            (!title.is_empty()).then_some(title)
+        });
+        let Some(title) = title else {
+            continue;
+        };
+        let states = property32(&conn, window, atoms.state, AtomEnum::ATOM.into())
+            .unwrap_or_default();
+        let opacity = property32(&conn, window, atoms.opacity, AtomEnum::CARDINAL.into())
+            .and_then(|values| values.first().copied())
+            .map_or(1.0, |value| value as f32 / u32::MAX as f32);
+        windows.push(super::ExternalWindow {
+            id: window as u64,
+            title,
+            topmost: states.contains(&atoms.state_above),
+            opacity,
+        });
+    }
+    Ok(windows)
+}
+
+// The window, if the window manager still lists it
+fn external_window(
+    conn: &RustConnection,
+    root: u32,
+    atoms: &WindowAtoms,
+    id: u64,
+) -> Result<u32, String> {
+    use x11rb::protocol::xproto::AtomEnum;
+
+    let window = u32::try_from(id).map_err(|_| "That window was closed".to_string())?;
+    property32(conn, root, atoms.client_list, AtomEnum::WINDOW.into())
+        .filter(|clients| clients.contains(&window))
+        .map(|_| window)
+        .ok_or_else(|| "That window was closed".to_string())
+}
+
+/// Keep a window above all others, or let it go behind them again (`_NET_WM_STATE_ABOVE`)
+pub fn set_external_window_topmost(id: u64, on: bool) -> Result<(), String> {
+    use x11rb::protocol::xproto::ClientMessageEvent;
+
+    let (conn, root, atoms) = external_windows_connection()?;
+    let window = external_window(&conn, root, &atoms, id)?;
+    // Action 1 adds the state and 0 removes it; source 2 says a pager asked, not the app
+    let event = ClientMessageEvent::new(
+        32,
+        window,
+        atoms.state,
+        [u32::from(on), atoms.state_above, 0, 2, 0],
+    );
+    conn.send_event(
+        false,
+        root,
+        EventMask::SUBSTRUCTURE_REDIRECT | EventMask::SUBSTRUCTURE_NOTIFY,
+        event,
+    )
+    .map_err(|e| format!("Failed to pin the window: {}", e))?;
+    conn.flush()
+        .map_err(|e| format!("Failed to pin the window: {}", e))
+}
+
+/// Make a window translucent, `alpha` 255 is opaque. Compositors read
+/// `_NET_WM_WINDOW_OPACITY`; without one nothing changes.
+pub fn set_external_window_opacity(id: u64, alpha: u8) -> Result<(), String> {
+    use x11rb::protocol::xproto::{AtomEnum, PropMode};
+    use x11rb::wrapper::ConnectionExt as _;
+
+    let (conn, root, atoms) = external_windows_connection()?;
+    let window = external_window(&conn, root, &atoms, id)?;
+    let result = if alpha == u8::MAX {
+        conn.delete_property(window, atoms.opacity).map(|_| ())
+    } else {
+        // 0..255 spread over the full CARDINAL range
+        let value = alpha as u32 * (u32::MAX / 255);
+        conn.change_property32(
+            PropMode::REPLACE,
+            window,
+            atoms.opacity,
+            AtomEnum::CARDINAL,
+            &[value],
+        )
+        .map(|_| ())
+    };
+    result
+        .and_then(|_| conn.flush())
+        .map_err(|e| format!("Failed to change the window's opacity: {}", e))
+}
+
+// ============================================================================
+// Screen Text (Screenshot portal / screenshot tools, tesseract)
+// ============================================================================
+
+// Region screenshot tools tried on X11 without a portal, with their arguments before the output path
+const REGION_SCREENSHOT_TOOLS: &[(&str, &[&str])] = &[
+    ("maim", &["-s"]),
+    ("gnome-screenshot", &["-a", "-f"]),
+    ("spectacle", &["-r", "-b", "-n", "-o"]),
+    ("scrot", &["-s", "-o"]),
+];
+
+/// Let the user drag out a region of the screen and return it. The Screenshot portal covers
+/// Wayland and desktops running xdg-desktop-portal; bare X11 falls back to screenshot tools.
+pub async fn capture_screen_region_impl(
+    _app: &tauri::AppHandle,
+) -> Result<image::RgbaImage, String> {
+    match screenshot_portal(true).await {
+        Ok(path) => {
+            let image = image::open(&path).map_err(|e| format!("Failed to read screenshot: {}", e));
+            // The portal saves into the user's Pictures folder; the file was only a means here
+            let _ = fs::remove_file(&path);
+            return image.map(|i| i.to_rgba8());
+        }
+        Err(e) if e == "Cancelled" || is_wayland_session() => return Err(e),
+        Err(e) => log::warn!("Screenshot portal unavailable, trying screenshot tools: {}", e),
+    }
+
+    tokio::task::spawn_blocking(capture_region_with_tool)
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+}
+
+/// The whole screen, without a selection step. Uses the Screenshot portal where there is
+/// one (it may still ask for permission once) and the X11 root window otherwise.
+pub async fn capture_full_screen_impl(
+    _app: &tauri::AppHandle,
+) -> Result<image::RgbaImage, String> {
+    match screenshot_portal(false).await {
+        Ok(path) => {
+            let image = image::open(&path).map_err(|e| format!("Failed to read screenshot: {}", e));
+            let _ = fs::remove_file(&path);
+            return image.map(|i| i.to_rgba8());
+        }
+        Err(e) if e == "Cancelled" || is_wayland_session() => return Err(e),
+        Err(e) => log::warn!("Screenshot portal unavailable, reading the X11 screen: {}", e),
+    }
+
+    tokio::task::spawn_blocking(capture_root_window)
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+}
+
+fn capture_root_window() -> Result<image::RgbaImage, String> {
+    let (conn, screen_num) =
+        RustConnection::connect(None).map_err(|e| format!("X11 connection failed: {}", e))?;
+    let screen = &conn.setup().roots[screen_num];
+    let (width, height) = (screen.width_in_pixels, screen.height_in_pixels);
+    let image = conn
+        .get_image(ImageFormat::Z_PIXMAP, screen.root, 0, 0, width, height, !0)
+        .map_err(|e| format!("Failed to capture the screen: {}", e))?
+        .reply()
+        .map_err(|e| format!("Failed to capture the screen: {}", e))?;
+    // 24/32-bit visuals use 4 bytes per pixel, BGRX
+    let rgba: Vec<u8> = image
+        .data
+        .chunks_exact(4)
+        .flat_map(|px| [px[2], px[1], px[0], 255])
+        .collect();
+    image::RgbaImage::from_raw(width as u32, height as u32, rgba)
+        .ok_or_else(|| "Unsupported screen pixel format".to_string())
+}
+
+/// Screenshot through org.freedesktop.portal.Screenshot; returns the saved file
+async fn screenshot_portal(interactive: bool) -> Result<PathBuf, String> {
+    use ashpd::desktop::screenshot::Screenshot;
+    use ashpd::desktop::ResponseError;
+
+    let portal_error = |e: ashpd::Error| match e {
+        ashpd::Error::Response(ResponseError::Cancelled) => "Cancelled".to_string(),
+        e => format!("Screenshot portal failed: {}", e),
+    };
+    let response = Screenshot::request()
+        .interactive(interactive)
+        .modal(true)
+        .send()
+        .await
+        .map_err(portal_error)?
+        .response()
+        .map_err(portal_error)?;
+    response
+        .uri()
+        .to_file_path()
+        .map_err(|_| format!("Unexpected screenshot location: {}", response.uri()))
+}
+
+fn capture_region_with_tool() -> Result<image::RgbaImage, String> {
+    let file = tempfile::Builder::new()
+        .suffix(".png")
+        .tempfile()
+        .map_err(|e| format!("Failed to create temporary file: {}", e))?;
+    for (tool, args) in REGION_SCREENSHOT_TOOLS {
+        let status = match Command::new(tool).args(*args).arg(file.path()).status() {
+            Ok(status) => status,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(format!("Failed to run {}: {}", tool, e)),
+        };
+        // Escape / right click ends the selection with an error status or an empty file
+        let empty = fs::metadata(file.path()).map(|m| m.len() == 0).unwrap_or(true);
+        if !status.success() || empty {
+            return Err("Cancelled".to_string());
+        }
+        return image::open(file.path())
+            .map(|i| i.to_rgba8())
+            .map_err(|e| format!("Failed to read screenshot: {}", e));
+    }
+    Err("Selecting a screen region needs xdg-desktop-portal or one of maim, gnome-screenshot, spectacle or scrot".to_string())
+}
+
+/// Recognize text with the tesseract CLI, in every language it has data for
+pub fn recognize_text_impl(image: &image::RgbaImage) -> Result<String, String> {
+    let file = tempfile::Builder::new()
+        .suffix(".png")
+        .tempfile()
+        .map_err(|e| format!("Failed to create temporary file: {}", e))?;
+    image
+        .save_with_format(file.path(), image::ImageFormat::Png)
+        .map_err(|e| format!("Failed to save screenshot: {}", e))?;
+
+    let not_installed = |e: std::io::Error| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            "Text recognition needs tesseract (the tesseract-ocr package)".to_string()
+        } else {
+            format!("Failed to run tesseract: {}", e)
+        }
+    };
+    // First line is a header ("List of available languages ..."); osd is layout data, not a language
+    let listing = Command::new("tesseract")
+        .arg("--list-langs")
+        .output()
+        .map_err(not_installed)?;
+    let languages: Vec<String> = String::from_utf8_lossy(&listing.stdout)
+        .lines()
+        .skip(1)
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty() && l != "osd")
+        .collect();
+
+    let mut command = Command::new("tesseract");
+    command.arg(file.path()).arg("-");
+    if !languages.is_empty() {
+        command.args(["-l", &languages.join("+")]);
+    }
+    let output = command.output().map_err(not_installed)?;
+    if !output.status.success() {
+        return Err(format!(
+            "Text recognition failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+// ============================================================================
+// Elevation (pkexec / polkit)
+// ============================================================================
+
+pub fn is_elevated_impl() -> bool {
+    use std::os::unix::fs::MetadataExt;
+    // /proc/self is owned by the effective uid of the current process
+    fs::metadata("/proc/self").map(|m| m.uid() == 0).unwrap_or(false)
+}
+
+/// Run a single program as root through pkexec, which shows the polkit authentication dialog.
+/// Returns the program's exit code.
+pub fn run_elevated_impl(program: &str, args: &[String]) -> Result<i32, String> {
+    let output = Command::new("pkexec")
+        .arg(program)
+        .args(args)
+        .output()
+        .map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                "pkexec is not available. Install polkit to run this operation with administrator rights.".to_string()
+            } else {
+                format!("Failed to run pkexec: {}", e)
+            }
+        })?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    match output.status.code() {
+        // 126: the authentication dialog was dismissed
+        Some(126) => Err(super::ELEVATION_DECLINED.to_string()),
+        // 127: authorization failed or no agent could ask
+        Some(127) if stderr.contains("No authentication agent") => {
+            Err("No polkit authentication agent is running to ask for administrator permission".to_string())
+        }
+        Some(127) if stderr.contains("Not authorized") => Err(super::ELEVATION_DECLINED.to_string()),
+        Some(code) => Ok(code),
+        None => Err("Elevated process was terminated by a signal".to_string()),
+    }
+}
+
+// ============================================================================
+// Mouse Triggers (X11 button grab)
+// ============================================================================
+
+use super::{MouseTrigger, MouseTriggerCallback};
+use std::sync::{Arc, Mutex};
+use x11rb::protocol::xproto::{Allow, AtomEnum, ButtonIndex, ModMask, Window};
+
+static MOUSE_TRIGGER: Mutex<MouseTrigger> = Mutex::new(MouseTrigger::None);
+static MOUSE_TRIGGER_CALLBACK: Mutex<Option<MouseTriggerCallback>> = Mutex::new(None);
+// Connection shared with the listener thread, opened the first time a trigger is enabled
+static MOUSE_TRIGGER_CONN: Mutex<Option<(Arc<RustConnection>, Window)>> = Mutex::new(None);
+
+const BUTTON_MIDDLE: u8 = 2;
+const BUTTON_BACK: u8 = 8; // XButton1
+const BUTTON_FORWARD: u8 = 9; // XButton2
+
+/// Configure the mouse trigger by replacing our passive button grabs on the root window
+pub fn set_mouse_trigger_impl(
+    trigger: MouseTrigger,
+    on_trigger: MouseTriggerCallback,
+) -> Result<(), String> {
+    *MOUSE_TRIGGER_CALLBACK.lock().unwrap() = Some(on_trigger);
+    *MOUSE_TRIGGER.lock().unwrap() = trigger;
+
+    let mut guard = MOUSE_TRIGGER_CONN.lock().unwrap();
+    if guard.is_none() {
+        if trigger == MouseTrigger::None {
+            return Ok(());
+        }
+        let (conn, screen_num) = RustConnection::connect(None).map_err(|e| {
+            format!(
+                "X11 connection failed: {}. Note: Mouse triggers require X11 (not Wayland).",
+                e
+            )
+        })?;
+        let root = conn.setup().roots[screen_num].root;
+        let conn = Arc::new(conn);
+
+        let listener_conn = conn.clone();
+        std::thread::spawn(move || run_mouse_trigger_listener(listener_conn));
+        *guard = Some((conn, root));
+    }
+    let (conn, root) = guard.as_ref().unwrap();
+
+    // Only removes grabs held by this connection
+    let _ = conn.ungrab_button(ButtonIndex::ANY, *root, ModMask::ANY);
+
+    let grab = match trigger {
+        MouseTrigger::None => None,
+        MouseTrigger::XButton1 => Some((BUTTON_BACK, GrabMode::ASYNC)),
+        MouseTrigger::XButton2 => Some((BUTTON_FORWARD, GrabMode::ASYNC)),
+        // Synchronous so clicks outside the desktop can be replayed to the real window
+        MouseTrigger::MiddleClickDesktop => Some((BUTTON_MIDDLE, GrabMode::SYNC)),
+    };
+
+    if let Some((button, pointer_mode)) = grab {
+        conn.grab_button(
+            false,
+            *root,
+            EventMask::BUTTON_PRESS | EventMask::BUTTON_RELEASE,
+            pointer_mode,
+            GrabMode::ASYNC,
+            x11rb::NONE,
+            x11rb::NONE,
+            ButtonIndex::from(button),
+            ModMask::ANY,
+        )
+        .map_err(|e| format!("Grab button request failed: {}", e))?
+        .check()
+        .map_err(|e| {
+            format!(
+                "Failed to grab mouse button (another application may already use it): {}",
+                e
+            )
+        })?;
+    }
+
+    conn.flush().map_err(|e| format!("X11 flush failed: {}", e))?;
+    Ok(())
+}
+
+fn run_mouse_trigger_listener(conn: Arc<RustConnection>) {
+    loop {
+        let event = match conn.wait_for_event() {
+            Ok(event) => event,
+            Err(e) => {
+                log::error!("Mouse trigger listener stopped: {}", e);
+                return;
+            }
+        };
+
+        let x11rb::protocol::Event::ButtonPress(bp) = event else {
+            continue;
+        };
+        let trigger = *MOUSE_TRIGGER.lock().unwrap();
+
+        match bp.detail {
+            BUTTON_BACK if trigger == MouseTrigger::XButton1 => fire_mouse_trigger(),
+            BUTTON_FORWARD if trigger == MouseTrigger::XButton2 => fire_mouse_trigger(),
+            BUTTON_MIDDLE => {
+                // The pointer is frozen until we either consume or replay the click
+                let on_desktop = bp.child == x11rb::NONE || is_desktop_window(&conn, bp.child);
+                if trigger == MouseTrigger::MiddleClickDesktop && on_desktop {
+                    let _ = conn.allow_events(Allow::ASYNC_POINTER, bp.time);
+                    fire_mouse_trigger();
+                } else {
+                    let _ = conn.allow_events(Allow::REPLAY_POINTER, bp.time);
+                }
+                let _ = conn.flush();
+            }
+            _ => {}
+        }
+    }
+}
+
+fn fire_mouse_trigger() {
+    let callback = MOUSE_TRIGGER_CALLBACK.lock().unwrap().clone();
+    if let Some(callback) = callback {
+        callback();
+    }
+}
+
+/// Whether a top-level window (or the client inside a WM frame) is a desktop window
+fn is_desktop_window(conn: &RustConnection, window: Window) -> bool {
+    let intern = |name: &[u8]| {
+        conn.intern_atom(false, name)
+            .ok()
+            .and_then(|cookie| cookie.reply().ok())
+            .map(|reply| reply.atom)
+    };
+    let (Some(wm_type), Some(desktop_type)) = (
+        intern(b"_NET_WM_WINDOW_TYPE"),
+        intern(b"_NET_WM_WINDOW_TYPE_DESKTOP"),
+    ) else {
+        return false;
+    };
+
+    let has_desktop_type = |w: Window| {
+        conn.get_property(false, w, wm_type, AtomEnum::ATOM, 0, 32)
+            .ok()
+            .and_then(|cookie| cookie.reply().ok())
+            .and_then(|reply| reply.value32().map(|mut atoms| atoms.any(|a| a == desktop_type)))
+            .unwrap_or(false)
+    };
+
+    if has_desktop_type(window) {
+        return true;
+    }
+
+    // Reparenting window managers put the client one level below the frame
+    conn.query_tree(window)
+        .ok()
+        .and_then(|cookie| cookie.reply().ok())
+        .map(|tree| tree.children.into_iter().any(has_desktop_type))
+        .unwrap_or(false)
+}
@@ -24,3 +24,204 @@ pub struct PortProcess {
     pub port: u16,
     pub protocol: String,
 }
+
+/// Error returned when the user dismisses an elevation prompt (UAC / polkit)
+pub const ELEVATION_DECLINED: &str = "Administrator permission was declined";
+
+/// Reason given when an operation needs rights we don't have, whatever the OS language
+pub const ACCESS_DENIED: &str = "Access is denied";
+
+/// Mouse gesture that toggles the main window, as an alternative to the hotkey
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MouseTrigger {
+    #[default]
+    None,
+    #[serde(rename = "xbutton1")]
+    XButton1, // "Back" side button
+    #[serde(rename = "xbutton2")]
+    XButton2, // "Forward" side button
+    MiddleClickDesktop, // middle click on the desktop background
+}
+
+/// Called (off the UI thread) whenever the configured mouse trigger fires
+pub type MouseTriggerCallback = std::sync::Arc<dyn Fn() + Send + Sync>;
+
+/// A file type the app can register itself as a handler for
+#[derive(Debug, Clone, Copy)]
+pub struct FileAssociation {
+    pub extension: &'static str, // without the dot
+    pub description: &'static str,
+    pub mime_type: &'static str,
+    pub owned: bool, // our own format: become the default handler, not just an "Open with" entry
+}
+
+/// A top-level window of another app (`list_external_windows`)
+#[derive(Debug, Clone, Serialize)]
+pub struct ExternalWindow {
+    pub id: u64, // HWND on Windows, X11 window id on Linux
+    pub title: String,
+    pub topmost: bool,
+    pub opacity: f32, // 0..1
+}
+
+/// Side of the square of pixels shown in the color picker's loupe
+pub const LOUPE_SIZE: i32 = 8;
+
+/// Called (off the UI thread) while picking a color whenever the cursor moves, with its
+/// physical position and the `LOUPE_SIZE`² pixels around it, row by row. The pixel under the
+/// cursor is at (LOUPE_SIZE / 2, LOUPE_SIZE / 2).
+pub type LoupeCallback = std::sync::Arc<dyn Fn(i32, i32, Vec<[u8; 3]>) + Send + Sync>;
+
+/// Called (off the UI thread) with each color of a continuous pick as it's clicked
+pub type ColorPickCallback = std::sync::Arc<dyn Fn(String) + Send + Sync>;
+
+/// Squares `pick_color` can average over; 1 reads just the pixel under the cursor. Averaging
+/// gives the intended color of anti-aliased text and noisy gradients.
+pub const SAMPLE_SIZES: [i32; 4] = [1, 3, 5, 9];
+
+/// Mean of `pixels` as `#RRGGBB`
+pub fn average_hex(pixels: &[[u8; 3]]) -> Option<String> {
+    if pixels.is_empty() {
+        return None;
+    }
+    let mean = |i: usize| {
+        let sum: u32 = pixels.iter().map(|p| u32::from(p[i])).sum();
+        (f64::from(sum) / pixels.len() as f64).round() as u8
+    };
+    Some(format!("#{:02X}{:02X}{:02X}", mean(0), mean(1), mean(2)))
+}
+
+/// Light or dark desktop theme, as reported by the OS
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SystemTheme {
+    #[default]
+    Light,
+    Dark,
+}
+
+/// Called (off the UI thread) when the desktop switches between light and dark
+pub type SystemThemeCallback = std::sync::Arc<dyn Fn(SystemTheme) + Send + Sync>;
+
+/// How the app registers itself to start at login. Only Linux offers a choice; Windows
+/// always uses the registry Run key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AutostartMethod {
+    #[default]
+    DesktopEntry, // XDG autostart .desktop file
+    Systemd, // systemd user service, restarted if it crashes
+}
+
+/// A network adapter as reported by the OS
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NetworkInterface {
+    pub name: String,
+    pub description: String, // adapter description on Windows, link kind (e.g. "wireguard") on Linux
+    pub mac_address: Option<String>,
+    pub addresses: Vec<String>, // IPv4 and IPv6, without prefix length
+    pub is_up: bool,
+    pub is_loopback: bool,
+    pub is_tunnel: bool, // point-to-point / tunnel link, as VPN adapters are
+}
+
+/// Network configuration read from the OS
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NetworkInfo {
+    pub interfaces: Vec<NetworkInterface>,
+    pub gateway: Option<String>,           // default IPv4 gateway
+    pub gateway_interface: Option<String>, // interface the default route goes through
+    pub dns_servers: Vec<String>,
+    pub proxy: Option<String>, // system proxy server or PAC URL
+}
+
+/// Whether the computer is running on battery, and how full that is
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PowerStatus {
+    pub on_battery: bool,
+    pub battery_percent: Option<u8>, // None without a battery, or when the OS can't tell
+}
+
+/// A LAN neighbor from the OS ARP cache
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Neighbor {
+    pub ip: String,
+    pub mac: String, // AA:BB:CC:DD:EE:FF
+}
+
+/// System power action (lock, sleep, restart, shut down)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PowerAction {
+    Lock,
+    Sleep,
+    Restart,
+    Shutdown,
+}
+
+/// Mouse button for simulated clicks
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MouseButton {
+    #[default]
+    Left,
+    Right,
+    Middle,
+}
+
+/// A jump list task: a shortcut back into this executable with `args`
+#[derive(Debug, Clone)]
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))] // only Windows has jump lists
+pub struct JumpListTask {
+    pub title: String,
+    pub args: String,
+}
+
+/// A shortcut on the user's desktop that starts this executable with `args`
+#[derive(Debug, Clone)]
+pub struct DesktopShortcut {
+    pub name: String, // under the icon, and the file name
+    pub comment: String,
+    pub args: Vec<String>,
+    // The app icon, for desktops that need it as a file (Windows shows the executable's own)
+    #[cfg_attr(target_os = "windows", allow(dead_code))]
+    pub icon: Option<image::RgbaImage>,
+}
+
+/// `name` without the characters file names can't have
+pub fn shortcut_file_stem(name: &str) -> String {
+    let stem: String = name
+        .chars()
+        .filter(|c| {
+            !c.is_control() && !matches!(c, '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*')
+        })
+        .collect();
+    match stem.trim().trim_end_matches('.') {
+        "" => "BunchaTools".to_string(),
+        stem => stem.to_string(),
+    }
+}
+
+/// Screen grabber ffmpeg records from
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[allow(dead_code)] // each platform only constructs its own grabber
+pub enum ScreenCaptureBackend {
+    Gdigrab,                     // Windows GDI desktop grab
+    X11grab { display: String }, // X11 display, e.g. ":0"
+    Pipewire,                    // Wayland: the screencast portal through ffmpeg's pipewiregrab
+}
+
+/// Audio input ffmpeg records from, as `-f <format> -i <device>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AudioCaptureInput {
+    pub format: String, // "dshow", "pulse"
+    pub device: String,
+}
+
+/// A microphone (or other audio source) to pick from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AudioInputDevice {
+    pub id: String,   // passed back when recording, see `audio_input`
+    pub name: String, // for display
+}
@@ -126,7 +126,117 @@ use winreg::RegKey;
 // Color Picker
 // ============================================================================
 
-pub async fn pick_color_impl(window: tauri::WebviewWindow) -> Result<String, String> {
+/// Copy the `size`² screen pixels around (x, y) as RGB
+fn capture_pixels(x: i32, y: i32, size: i32) -> Option<Vec<[u8; 3]>> {
+    use windows::Win32::Graphics::Gdi::{
+        BitBlt, CreateCompatibleBitmap, CreateCompatibleDC, DeleteDC, DeleteObject, GetDIBits,
+        SelectObject, BITMAPINFO, BITMAPINFOHEADER, BI_RGB, DIB_RGB_COLORS, SRCCOPY,
+    };
+
+    let mut data = vec![0u8; (size * size * 4) as usize];
+    let copied = unsafe {
+        let screen = GetDC(None);
+        let memory = CreateCompatibleDC(Some(screen));
+        let bitmap = CreateCompatibleBitmap(screen, size, size);
+        let previous = SelectObject(memory, bitmap.into());
+        let copied = BitBlt(
+            memory,
+            0,
+            0,
+            size,
+            size,
+            Some(screen),
+            x - size / 2,
+            y - size / 2,
+            SRCCOPY,
+        )
+        .is_ok();
+        SelectObject(memory, previous);
+
+        let mut info = BITMAPINFO {
+            bmiHeader: BITMAPINFOHEADER {
+                biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+                biWidth: size,
+                biHeight: -size, // top-down rows
+                biPlanes: 1,
+                biBitCount: 32,
+                biCompression: BI_RGB.0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let lines = GetDIBits(
+            memory,
+            bitmap,
+            0,
+            size as u32,
+            Some(data.as_mut_ptr() as *mut _),
+            &mut info,
+            DIB_RGB_COLORS,
+        );
+        let _ = DeleteObject(bitmap.into());
+        let _ = DeleteDC(memory);
+        ReleaseDC(None, screen);
+        copied && lines == size
+    };
+
+    // BGRA
+    copied.then(|| data.chunks(4).map(|px| [px[2], px[1], px[0]]).collect())
+}
+
+/// Pick a color from the screen, averaging the `sample_size`² pixels around the click
+pub async fn pick_color_impl(
+    window: tauri::WebviewWindow,
+    on_move: super::LoupeCallback,
+    sample_size: i32,
+) -> Result<String, String> {
+    let colors = pick_colors(window, on_move, sample_size, None)?;
+    colors
+        .into_iter()
+        .next()
+        .ok_or_else(|| "Cancelled".to_string())
+}
+
+/// Pick colors until Escape or a right click, reporting each one to `on_pick`
+pub async fn pick_colors_continuous_impl(
+    window: tauri::WebviewWindow,
+    on_move: super::LoupeCallback,
+    sample_size: i32,
+    on_pick: super::ColorPickCallback,
+) -> Result<Vec<String>, String> {
+    pick_colors(window, on_move, sample_size, Some(on_pick))
+}
+
+// Color at (x, y), averaged over `sample_size`² pixels
+fn read_color(x: i32, y: i32, sample_size: i32) -> String {
+    if sample_size > 1 {
+        let pixels = capture_pixels(x, y, sample_size);
+        if let Some(hex) = pixels.as_deref().and_then(super::average_hex) {
+            return hex;
+        }
+    }
+
+    let color = unsafe {
+        let hdc = GetDC(None);
+        let pixel = GetPixel(hdc, x, y);
+        let _ = ReleaseDC(None, hdc);
+        pixel
+    };
+
+    let r = (color.0 & 0xFF) as u8;
+    let g = ((color.0 >> 8) & 0xFF) as u8;
+    let b = ((color.0 >> 16) & 0xFF) as u8;
+
+    format!("#{:02X}{:02X}{:02X}", r, g, b)
+}
+
+// One pick, or with `on_pick` as many as the user clicks
+fn pick_colors(
+    window: tauri::WebviewWindow,
+    on_move: super::LoupeCallback,
+    sample_size: i32,
+    on_pick: Option<super::ColorPickCallback>,
+) -> Result<Vec<String>, String> {
     let _ = window.hide();
     std::thread::sleep(std::time::Duration::from_millis(100));
 
@@ -146,49 +256,51 @@ pub async fn pick_color_impl(window: tauri::WebviewWindow) -> Result<String, Str
     };
 
     const VK_LBUTTON: i32 = 0x01;
+    const VK_RBUTTON: i32 = 0x02;
     const VK_ESCAPE: i32 = 0x1B;
+    let is_down = |key: i32| unsafe { GetAsyncKeyState(key) } < 0;
+    let wait_for_release = || {
+        while is_down(VK_LBUTTON) {
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+    };
 
+    wait_for_release();
+
+    let mut colors = Vec::new();
+    let mut last_position = None;
     loop {
-        let state = unsafe { GetAsyncKeyState(VK_LBUTTON) };
-        if state >= 0 {
+        // Escape cancels a single pick; it (or a right click) ends a continuous one
+        if is_down(VK_ESCAPE) || (on_pick.is_some() && is_down(VK_RBUTTON)) {
             break;
         }
-        std::thread::sleep(std::time::Duration::from_millis(10));
-    }
 
-    loop {
-        let escape_state = unsafe { GetAsyncKeyState(VK_ESCAPE) };
-        if escape_state < 0 {
-            restore_cursors();
-            return Err("Cancelled".to_string());
+        let mut point = POINT { x: 0, y: 0 };
+        let has_point = unsafe { GetCursorPos(&mut point) }.is_ok();
+
+        if is_down(VK_LBUTTON) && has_point {
+            let color = read_color(point.x, point.y, sample_size);
+            colors.push(color.clone());
+            match &on_pick {
+                Some(on_pick) => on_pick(color),
+                None => break,
+            }
+            wait_for_release();
+            continue;
         }
 
-        let state = unsafe { GetAsyncKeyState(VK_LBUTTON) };
-        if state < 0 {
-            break;
+        // Feed the loupe whenever the cursor moves
+        if has_point && last_position != Some((point.x, point.y)) {
+            last_position = Some((point.x, point.y));
+            if let Some(pixels) = capture_pixels(point.x, point.y, super::LOUPE_SIZE) {
+                on_move(point.x, point.y, pixels);
+            }
         }
         std::thread::sleep(std::time::Duration::from_millis(10));
     }
 
     restore_cursors();
-
-    let mut point = POINT { x: 0, y: 0 };
-    unsafe {
-        let _ = GetCursorPos(&mut point);
-    }
-
-    let color = unsafe {
-        let hdc = GetDC(None);
-        let pixel = GetPixel(hdc, point.x, point.y);
-        let _ = ReleaseDC(None, hdc);
-        pixel
-    };
-
-    let r = (color.0 & 0xFF) as u8;
-    let g = ((color.0 >> 8) & 0xFF) as u8;
-    let b = ((color.0 >> 16) & 0xFF) as u8;
-
-    Ok(format!("#{:02X}{:02X}{:02X}", r, g, b))
+    Ok(colors)
 }
 
 // ============================================================================
@@ -196,20 +308,29 @@ pub async fn pick_color_impl(window: tauri::WebviewWindow) -> Result<String, Str
 // ============================================================================
 
 pub async fn start_text_selection_impl(window: tauri::WebviewWindow) -> Result<(), String> {
+    use tauri::Manager;
+
     // Hide the window first
     let _ = window.hide();
     std::thread::sleep(std::time::Duration::from_millis(100));
 
+    copy_selection_impl(window.app_handle()).await?;
+
+    // Show the window
+    let _ = window.show();
+    let _ = window.set_focus();
+
+    Ok(())
+}
+
+/// Put the text selected in the focused app on the clipboard
+pub async fn copy_selection_impl(_app: &tauri::AppHandle) -> Result<(), String> {
     // Simulate Ctrl+C to copy the already-selected text
     copy_selected_text();
 
     // Wait for clipboard to be populated
     std::thread::sleep(std::time::Duration::from_millis(100));
 
-    // Show the window
-    let _ = window.show();
-    let _ = window.set_focus();
-
     Ok(())
 }
 
@@ -350,64 +471,62 @@ pub async fn scan_port_impl(port: u16) -> Result<Vec<PortProcess>, String> {
     let mut seen_pids: HashSet<u32> = HashSet::new();
 
     for line in stdout.lines() {
-        // Parse lines like: TCP    0.0.0.0:3000    0.0.0.0:0    LISTENING    12345
-        let parts: Vec<&str> = line.split_whitespace().collect();
-        if parts.len() >= 5 {
-            let protocol = parts[0];
-            let local_addr = parts[1];
-
-            // Check if this is TCP or UDP
-            if protocol != "TCP" && protocol != "UDP" {
+        if let Some((protocol, local_port, pid)) = parse_netstat_line(line, port) {
+            if pid == 0 || seen_pids.contains(&pid) {
                 continue;
             }
+            seen_pids.insert(pid);
 
-            // Parse the port from local address (format: IP:PORT or [IPv6]:PORT)
-            let port_str = if local_addr.contains('[') {
-                // IPv6: [::]:port
-                local_addr.rsplit(':').next()
-            } else {
-                // IPv4: 0.0.0.0:port
-                local_addr.rsplit(':').next()
-            };
+            // Get process name using tasklist
+            let process_name = get_process_name_impl(pid).unwrap_or_else(|| "Unknown".to_string());
 
-            if let Some(port_str) = port_str {
-                if let Ok(local_port) = port_str.parse::<u16>() {
-                    if local_port == port {
-                        // Get PID (last column for TCP, different for UDP)
-                        let pid_str = if protocol == "TCP" && parts.len() >= 5 {
-                            parts[4]
-                        } else if protocol == "UDP" && parts.len() >= 4 {
-                            parts[3]
-                        } else {
-                            continue;
-                        };
-
-                        if let Ok(pid) = pid_str.parse::<u32>() {
-                            if pid == 0 || seen_pids.contains(&pid) {
-                                continue;
-                            }
-                            seen_pids.insert(pid);
-
-                            // Get process name using tasklist
-                            let process_name =
-                                get_process_name_impl(pid).unwrap_or_else(|| "Unknown".to_string());
-
-                            processes.push(PortProcess {
-                                pid,
-                                name: process_name,
-                                port: local_port,
-                                protocol: protocol.to_string(),
-                            });
-                        }
-                    }
-                }
-            }
+            processes.push(PortProcess {
+                pid,
+                name: process_name,
+                port: local_port,
+                protocol,
+            });
         }
     }
 
     Ok(processes)
 }
 
+/// Parse one `netstat -ano` line, returning (protocol, port, pid) if it is bound to `port`
+pub fn parse_netstat_line(line: &str, port: u16) -> Option<(String, u16, u32)> {
+    // Parse lines like: TCP    0.0.0.0:3000    0.0.0.0:0    LISTENING    12345
+    let parts: Vec<&str> = line.split_whitespace().collect();
+    if parts.len() < 5 {
+        return None;
+    }
+
+    let protocol = parts[0];
+    let local_addr = parts[1];
+
+    // Check if this is TCP or UDP
+    if protocol != "TCP" && protocol != "UDP" {
+        return None;
+    }
+
+    // Parse the port from local address (format: IP:PORT or [IPv6]:PORT)
+    let local_port = local_addr.rsplit(':').next()?.parse::<u16>().ok()?;
+    if local_port != port {
+        return None;
+    }
+
+    // Get PID (last column for TCP, different for UDP)
+    let pid_str = if protocol == "TCP" && parts.len() >= 5 {
+        parts[4]
+    } else if protocol == "UDP" && parts.len() >= 4 {
+        parts[3]
+    } else {
+        return None;
+    };
+
+    let pid = pid_str.parse::<u32>().ok()?;
+    Some((protocol.to_string(), local_port, pid))
+}
+
 pub fn get_process_name_impl(pid: u32) -> Option<String> {
     let output = Command::new("tasklist")
         .args(["/FI", &format!("PID eq {}", pid), "/FO", "CSV", "/NH"])
@@ -436,6 +555,9 @@ pub async fn kill_port_process_impl(pid: u32) -> Result<(), String> {
         .map_err(|e| e.to_string())?;
 
     if !output.status.success() {
+        if is_terminate_denied(pid) {
+            return Err(format!("Failed to kill process: {}", super::ACCESS_DENIED));
+        }
         let stderr = String::from_utf8_lossy(&output.stderr);
         return Err(format!("Failed to kill process: {}", stderr));
     }
@@ -443,6 +565,93 @@ pub async fn kill_port_process_impl(pid: u32) -> Result<(), String> {
     Ok(())
 }
 
+/// Whether we lack the rights to terminate `pid` (services running as SYSTEM, other
+/// users' processes). taskkill's own message is localized, so ask the OS directly.
+fn is_terminate_denied(pid: u32) -> bool {
+    use windows::Win32::Foundation::{CloseHandle, E_ACCESSDENIED};
+    use windows::Win32::System::Threading::{OpenProcess, PROCESS_TERMINATE};
+
+    match unsafe { OpenProcess(PROCESS_TERMINATE, false, pid) } {
+        Ok(handle) => {
+            unsafe {
+                let _ = CloseHandle(handle);
+            }
+            false
+        }
+        Err(e) => e.code() == E_ACCESSDENIED,
+    }
+}
+
+// ============================================================================
+// Pausing Processes (SuspendThread / ResumeThread)
+// ============================================================================
+
+/// Suspend every thread of a process (`suspend`) or resume them. Windows has no documented
+/// call for a whole process, so its threads are found in a Toolhelp snapshot.
+pub fn suspend_process_impl(pid: u32, suspend: bool) -> Result<(), String> {
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Diagnostics::ToolHelp::{
+        CreateToolhelp32Snapshot, Thread32First, Thread32Next, TH32CS_SNAPTHREAD, THREADENTRY32,
+    };
+    use windows::Win32::System::Threading::{
+        OpenThread, ResumeThread, SuspendThread, THREAD_SUSPEND_RESUME,
+    };
+
+    let snapshot = unsafe { CreateToolhelp32Snapshot(TH32CS_SNAPTHREAD, 0) }
+        .map_err(|e| format!("Failed to list threads: {}", e))?;
+    let mut entry = THREADENTRY32 {
+        dwSize: std::mem::size_of::<THREADENTRY32>() as u32,
+        ..Default::default()
+    };
+    let mut changed = 0;
+    unsafe {
+        let mut more = Thread32First(snapshot, &mut entry).is_ok();
+        while more {
+            if entry.th32OwnerProcessID == pid {
+                if let Ok(thread) = OpenThread(THREAD_SUSPEND_RESUME, false, entry.th32ThreadID) {
+                    let previous = if suspend {
+                        SuspendThread(thread)
+                    } else {
+                        ResumeThread(thread)
+                    };
+                    if previous != u32::MAX {
+                        changed += 1;
+                    }
+                    let _ = CloseHandle(thread);
+                }
+            }
+            more = Thread32Next(snapshot, &mut entry).is_ok();
+        }
+        let _ = CloseHandle(snapshot);
+    }
+
+    if changed == 0 {
+        return Err(format!("Process {} has no threads we can suspend", pid));
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Power Status (GetSystemPowerStatus)
+// ============================================================================
+
+pub fn get_power_status_impl() -> Result<super::PowerStatus, String> {
+    use windows::Win32::System::Power::{GetSystemPowerStatus, SYSTEM_POWER_STATUS};
+
+    let mut status = SYSTEM_POWER_STATUS::default();
+    unsafe { GetSystemPowerStatus(&mut status) }
+        .map_err(|e| format!("Failed to read power status: {}", e))?;
+
+    // BatteryFlag 128 is "no system battery", 255 unknown; 255 percent is unknown too
+    let has_battery = status.BatteryFlag != 128 && status.BatteryFlag != 255;
+    let battery_percent = (has_battery && status.BatteryLifePercent <= 100)
+        .then_some(status.BatteryLifePercent);
+    Ok(super::PowerStatus {
+        on_battery: has_battery && status.ACLineStatus == 0,
+        battery_percent,
+    })
+}
+
 // ============================================================================
 // Auto-Startup (Registry)
 // ============================================================================
@@ -455,7 +664,10 @@ pub fn get_launch_at_startup_impl() -> Result<bool, String> {
     Ok(run_key.get_value::<String, _>("BunchaTools").is_ok())
 }
 
-pub fn set_launch_at_startup_impl(enable: bool) -> Result<(), String> {
+pub fn set_launch_at_startup_impl(
+    enable: bool,
+    _method: super::AutostartMethod,
+) -> Result<(), String> {
     let hkcu = RegKey::predef(HKEY_CURRENT_USER);
     let (run_key, _) = hkcu
         .create_subkey("Software\\Microsoft\\Windows\\CurrentVersion\\Run")
@@ -463,8 +675,13 @@ pub fn set_launch_at_startup_impl(enable: bool) -> Result<(), String> {
 
     if enable {
         let exe_path = std::env::current_exe().map_err(|e| e.to_string())?;
+        let command = format!(
+            "\"{}\" {}",
+            exe_path.display(),
+            crate::shell_integration::HIDDEN_ARG
+        );
         run_key
-            .set_value("BunchaTools", &exe_path.to_string_lossy().to_string())
+            .set_value("BunchaTools", &command)
             .map_err(|e| e.to_string())?;
     } else {
         let _ = run_key.delete_value("BunchaTools");
@@ -473,71 +690,1476 @@ pub fn set_launch_at_startup_impl(enable: bool) -> Result<(), String> {
 }
 
 // ============================================================================
-// FFmpeg Path Resolution
+// Shell Integration (Explorer context menu + Send To)
 // ============================================================================
 
-pub fn get_ffmpeg_path() -> Result<std::path::PathBuf, String> {
-    // Get executable directory
-    let exe_dir = std::env::current_exe()
+const SHELL_VIDEO_EXTENSIONS: [&str; 9] = [
+    "mp4", "mkv", "mov", "avi", "webm", "wmv", "flv", "m4v", "mpg",
+];
+const SHELL_VERB: &str = "BunchaTools.Convert";
+const SHELL_LABEL: &str = "Convert with BunchaTools";
+
+/// Add (or remove) "Convert with BunchaTools" to the context menu of video files and a
+/// BunchaTools shortcut to the Send To menu. `args` go before the selected file paths.
+pub fn set_shell_integration_impl(enable: bool, args: &str) -> Result<(), String> {
+    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+    let exe_path = std::env::current_exe()
         .map_err(|e| e.to_string())?
-        .parent()
-        .ok_or("Failed to get exe directory")?
-        .to_path_buf();
+        .to_string_lossy()
+        .to_string();
+
+    // SystemFileAssociations adds the verb without taking over the extension's default handler
+    for ext in SHELL_VIDEO_EXTENSIONS {
+        let verb_key = format!(
+            "Software\\Classes\\SystemFileAssociations\\.{}\\shell\\{}",
+            ext, SHELL_VERB
+        );
+        if enable {
+            let (verb, _) = hkcu.create_subkey(&verb_key).map_err(|e| e.to_string())?;
+            verb.set_value("", &SHELL_LABEL).map_err(|e| e.to_string())?;
+            verb.set_value("Icon", &exe_path).map_err(|e| e.to_string())?;
+            let (command, _) = hkcu
+                .create_subkey(format!("{}\\command", verb_key))
+                .map_err(|e| e.to_string())?;
+            command
+                .set_value("", &format!("\"{}\" {} \"%1\"", exe_path, args))
+                .map_err(|e| e.to_string())?;
+        } else {
+            let _ = hkcu.delete_subkey_all(&verb_key);
+        }
+    }
 
-    // Get current working directory
-    let cwd = std::env::current_dir().unwrap_or_default();
+    let send_to = std::env::var("APPDATA")
+        .map(|appdata| {
+            std::path::PathBuf::from(appdata)
+                .join("Microsoft\\Windows\\SendTo")
+                .join(format!("{}.lnk", SHELL_LABEL))
+        })
+        .map_err(|_| "Could not find the Send To folder")?;
+    if enable {
+        create_shortcut(&send_to, &exe_path, args, SHELL_LABEL)?;
+    } else if send_to.exists() {
+        std::fs::remove_file(&send_to).map_err(|e| e.to_string())?;
+    }
 
-    let possible_paths = vec![
-        // Production paths
-        exe_dir.join("ffmpeg.exe"),
-        exe_dir.join("binaries").join("ffmpeg.exe"),
-        // Development paths (relative to cwd)
-        cwd.join("src-tauri/binaries/ffmpeg-x86_64-pc-windows-msvc.exe"),
-        cwd.join("binaries/ffmpeg-x86_64-pc-windows-msvc.exe"),
-    ];
+    Ok(())
+}
 
-    for path in &possible_paths {
-        if path.exists() {
-            log::info!("Found FFmpeg at: {:?}", path);
-            return Ok(path.clone());
+fn create_shortcut(
+    path: &std::path::Path,
+    target: &str,
+    args: &str,
+    description: &str,
+) -> Result<(), String> {
+    use windows::core::{Interface, HSTRING};
+    use windows::Win32::System::Com::{
+        CoCreateInstance, CoInitializeEx, IPersistFile, CLSCTX_INPROC_SERVER,
+        COINIT_APARTMENTTHREADED,
+    };
+    use windows::Win32::UI::Shell::{IShellLinkW, ShellLink};
+
+    unsafe {
+        // Already-initialized (S_FALSE / RPC_E_CHANGED_MODE) is fine, COM is usable either way
+        let _ = CoInitializeEx(None, COINIT_APARTMENTTHREADED);
+
+        let link: IShellLinkW = CoCreateInstance(&ShellLink, None, CLSCTX_INPROC_SERVER)
+            .map_err(|e| format!("Failed to create shortcut: {}", e))?;
+        link.SetPath(&HSTRING::from(target))
+            .and_then(|_| link.SetArguments(&HSTRING::from(args)))
+            .and_then(|_| link.SetDescription(&HSTRING::from(description)))
+            .and_then(|_| link.SetIconLocation(&HSTRING::from(target), 0))
+            .map_err(|e| format!("Failed to create shortcut: {}", e))?;
+
+        let file: IPersistFile = link
+            .cast()
+            .map_err(|e| format!("Failed to create shortcut: {}", e))?;
+        file.Save(&HSTRING::from(path.as_os_str()), true)
+            .map_err(|e| format!("Failed to save shortcut: {}", e))
+    }
+}
+
+// ============================================================================
+// File Associations (ProgIDs under HKCU\Software\Classes)
+// ============================================================================
+
+/// Register (or remove) BunchaTools as the handler for `associations`. Formats we don't own
+/// are only added to "Open with" so the user's default app is left alone.
+pub fn set_file_associations_impl(
+    enable: bool,
+    associations: &[super::FileAssociation],
+) -> Result<(), String> {
+    use windows::Win32::UI::Shell::{SHChangeNotify, SHCNE_ASSOCCHANGED, SHCNF_IDLIST};
+
+    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+    let exe_path = std::env::current_exe()
+        .map_err(|e| e.to_string())?
+        .to_string_lossy()
+        .to_string();
+
+    for association in associations {
+        let prog_id = format!("BunchaTools.{}", association.extension);
+        let prog_key = format!("Software\\Classes\\{}", prog_id);
+        let ext_key = format!("Software\\Classes\\.{}", association.extension);
+
+        if enable {
+            let (prog, _) = hkcu.create_subkey(&prog_key).map_err(|e| e.to_string())?;
+            prog.set_value("", &association.description)
+                .map_err(|e| e.to_string())?;
+            let (icon, _) = hkcu
+                .create_subkey(format!("{}\\DefaultIcon", prog_key))
+                .map_err(|e| e.to_string())?;
+            icon.set_value("", &format!("\"{}\",0", exe_path))
+                .map_err(|e| e.to_string())?;
+            let (command, _) = hkcu
+                .create_subkey(format!("{}\\shell\\open\\command", prog_key))
+                .map_err(|e| e.to_string())?;
+            command
+                .set_value("", &format!("\"{}\" \"%1\"", exe_path))
+                .map_err(|e| e.to_string())?;
+
+            let (ext, _) = hkcu.create_subkey(&ext_key).map_err(|e| e.to_string())?;
+            if association.owned {
+                ext.set_value("", &prog_id).map_err(|e| e.to_string())?;
+                ext.set_value("Content Type", &association.mime_type)
+                    .map_err(|e| e.to_string())?;
+            }
+            let (open_with, _) = hkcu
+                .create_subkey(format!("{}\\OpenWithProgids", ext_key))
+                .map_err(|e| e.to_string())?;
+            open_with
+                .set_value(&prog_id, &"")
+                .map_err(|e| e.to_string())?;
+        } else {
+            let _ = hkcu.delete_subkey_all(&prog_key);
+            if let Ok(ext) = hkcu.open_subkey_with_flags(&ext_key, KEY_ALL_ACCESS) {
+                // Only clear the default if it still points at us
+                if ext.get_value::<String, _>("").ok().as_deref() == Some(prog_id.as_str()) {
+                    let _ = ext.delete_value("");
+                }
+                if let Ok(open_with) = ext.open_subkey_with_flags("OpenWithProgids", KEY_ALL_ACCESS)
+                {
+                    let _ = open_with.delete_value(&prog_id);
+                }
+            }
         }
     }
 
-    Err(format!(
-        "FFmpeg not found. CWD: {:?}, Searched in: {:?}",
-        cwd, possible_paths
-    ))
+    // Let Explorer refresh icons and "Open with" lists
+    unsafe { SHChangeNotify(SHCNE_ASSOCCHANGED, SHCNF_IDLIST, None, None) };
+
+    Ok(())
 }
 
-pub fn get_ytdlp_path() -> Result<std::path::PathBuf, String> {
-    // Get executable directory
-    let exe_dir = std::env::current_exe()
-        .map_err(|e| e.to_string())?
-        .parent()
-        .ok_or("Failed to get exe directory")?
-        .to_path_buf();
+// ============================================================================
+// System Theme (Registry)
+// ============================================================================
 
-    // Get current working directory
-    let cwd = std::env::current_dir().unwrap_or_default();
+const PERSONALIZE_KEY: &str = "Software\\Microsoft\\Windows\\CurrentVersion\\Themes\\Personalize";
 
-    let possible_paths = vec![
-        // Production paths
-        exe_dir.join("yt-dlp.exe"),
-        exe_dir.join("binaries").join("yt-dlp.exe"),
-        // Development paths (relative to cwd) - Tauri externalBin naming convention
-        cwd.join("src-tauri/binaries/yt-dlp-x86_64-pc-windows-msvc.exe"),
-        cwd.join("binaries/yt-dlp-x86_64-pc-windows-msvc.exe"),
-    ];
+/// Theme of the taskbar / notification area (which can differ from the app theme)
+pub fn get_system_theme_impl() -> super::SystemTheme {
+    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+    let light = hkcu
+        .open_subkey(PERSONALIZE_KEY)
+        .and_then(|key| key.get_value::<u32, _>("SystemUsesLightTheme"))
+        .map(|value| value != 0)
+        .unwrap_or(false); // Windows 10/11 default to a dark taskbar
+
+    if light {
+        super::SystemTheme::Light
+    } else {
+        super::SystemTheme::Dark
+    }
+}
 
-    for path in &possible_paths {
-        if path.exists() {
-            log::info!("Found yt-dlp at: {:?}", path);
-            return Ok(path.clone());
+/// Call `on_change` with the theme whenever a value under the Personalize key is written
+pub fn watch_system_theme_impl(on_change: super::SystemThemeCallback) {
+    use windows::core::HSTRING;
+    use windows::Win32::Foundation::ERROR_SUCCESS;
+    use windows::Win32::System::Registry::{
+        RegCloseKey, RegNotifyChangeKeyValue, RegOpenKeyExW, HKEY, HKEY_CURRENT_USER,
+        KEY_NOTIFY, REG_NOTIFY_CHANGE_LAST_SET,
+    };
+
+    std::thread::spawn(move || {
+        let mut key = HKEY::default();
+        let subkey = HSTRING::from(PERSONALIZE_KEY);
+        let opened =
+            unsafe { RegOpenKeyExW(HKEY_CURRENT_USER, &subkey, None, KEY_NOTIFY, &mut key) };
+        if opened != ERROR_SUCCESS {
+            log::warn!("Cannot watch the system theme: error {}", opened.0);
+            return;
+        }
+
+        // Synchronous notifications block until the next change, and have to be re-armed
+        while unsafe {
+            RegNotifyChangeKeyValue(key, false, REG_NOTIFY_CHANGE_LAST_SET, None, false)
+        } == ERROR_SUCCESS
+        {
+            on_change(get_system_theme_impl());
+        }
+        unsafe {
+            let _ = RegCloseKey(key);
+        }
+    });
+}
+
+// ============================================================================
+// Do Not Disturb (notification toasts switch)
+// ============================================================================
+
+// Focus Assist has no public API; this is the "Notifications" switch in Settings > System,
+// which stops toasts from all apps while it is off
+const NOTIFICATION_SETTINGS_KEY: &str =
+    "Software\\Microsoft\\Windows\\CurrentVersion\\Notifications\\Settings";
+const TOASTS_ENABLED_VALUE: &str = "NOC_GLOBAL_SETTING_TOASTS_ENABLED";
+
+/// Whether notifications are currently silenced; None if the state can't be read
+pub fn get_do_not_disturb_impl() -> Option<bool> {
+    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+    let key = hkcu.open_subkey(NOTIFICATION_SETTINGS_KEY).ok()?;
+    // The value only exists once the switch has been toggled; missing means enabled
+    Some(key.get_value::<u32, _>(TOASTS_ENABLED_VALUE).is_ok_and(|v| v == 0))
+}
+
+pub fn set_do_not_disturb_impl(enabled: bool) -> Result<(), String> {
+    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+    let (key, _) = hkcu
+        .create_subkey(NOTIFICATION_SETTINGS_KEY)
+        .map_err(|e| e.to_string())?;
+    key.set_value(TOASTS_ENABLED_VALUE, &u32::from(!enabled))
+        .map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// Network Status (IP Helper)
+// ============================================================================
+
+pub fn get_network_info_impl() -> Result<super::NetworkInfo, String> {
+    use windows::Win32::Foundation::{ERROR_BUFFER_OVERFLOW, NO_ERROR};
+    use windows::Win32::NetworkManagement::IpHelper::{
+        GetAdaptersAddresses, GAA_FLAG_INCLUDE_GATEWAYS, GAA_FLAG_SKIP_ANYCAST,
+        GAA_FLAG_SKIP_MULTICAST, IF_TYPE_PPP, IF_TYPE_PROP_VIRTUAL, IF_TYPE_SOFTWARE_LOOPBACK,
+        IF_TYPE_TUNNEL, IP_ADAPTER_ADDRESSES_LH,
+    };
+    use windows::Win32::NetworkManagement::Ndis::IfOperStatusUp;
+    use windows::Win32::Networking::WinSock::AF_UNSPEC;
+
+    let flags = GAA_FLAG_INCLUDE_GATEWAYS | GAA_FLAG_SKIP_ANYCAST | GAA_FLAG_SKIP_MULTICAST;
+    // u64 elements keep the buffer aligned for the structs written into it
+    let mut buffer: Vec<u64> = vec![0; 2048];
+    let mut result = ERROR_BUFFER_OVERFLOW.0;
+    // The adapter list can grow between the size query and the call, so retry a few times
+    for _ in 0..3 {
+        let mut size = (buffer.len() * 8) as u32;
+        result = unsafe {
+            GetAdaptersAddresses(
+                AF_UNSPEC.0 as u32,
+                flags,
+                None,
+                Some(buffer.as_mut_ptr() as *mut IP_ADAPTER_ADDRESSES_LH),
+                &mut size,
+            )
+        };
+        if result != ERROR_BUFFER_OVERFLOW.0 {
+            break;
         }
+        buffer.resize((size as usize).div_ceil(8), 0);
+    }
+    if result != NO_ERROR.0 {
+        return Err(format!("GetAdaptersAddresses failed: error {}", result));
     }
 
-    Err(format!(
-        "yt-dlp not found. CWD: {:?}, Searched in: {:?}",
-        cwd, possible_paths
-    ))
+    let mut info = super::NetworkInfo::default();
+    let mut gateway_metric = u32::MAX;
+    let mut adapter = buffer.as_ptr() as *const IP_ADAPTER_ADDRESSES_LH;
+    while let Some(a) = unsafe { adapter.as_ref() } {
+        adapter = a.Next;
+        let name = unsafe { a.FriendlyName.to_string() }.unwrap_or_default();
+        let is_up = a.OperStatus == IfOperStatusUp;
+
+        let mut addresses = Vec::new();
+        let mut unicast = a.FirstUnicastAddress;
+        while let Some(u) = unsafe { unicast.as_ref() } {
+            addresses.extend(socket_address_to_string(&u.Address));
+            unicast = u.Next;
+        }
+
+        if is_up {
+            let mut dns = a.FirstDnsServerAddress;
+            while let Some(d) = unsafe { dns.as_ref() } {
+                if let Some(server) = socket_address_to_string(&d.Address) {
+                    if !info.dns_servers.contains(&server) {
+                        info.dns_servers.push(server);
+                    }
+                }
+                dns = d.Next;
+            }
+
+            // The default route is the IPv4 gateway of the adapter with the lowest metric
+            let mut gateway = a.FirstGatewayAddress;
+            while let Some(g) = unsafe { gateway.as_ref() } {
+                if let Some(address) = socket_address_to_string(&g.Address) {
+                    if !address.contains(':') && a.Ipv4Metric < gateway_metric {
+                        gateway_metric = a.Ipv4Metric;
+                        info.gateway = Some(address);
+                        info.gateway_interface = Some(name.clone());
+                    }
+                }
+                gateway = g.Next;
+            }
+        }
+
+        let mac_len = (a.PhysicalAddressLength as usize).min(a.PhysicalAddress.len());
+        let mac_address = (mac_len > 0).then(|| {
+            a.PhysicalAddress[..mac_len]
+                .iter()
+                .map(|b| format!("{:02X}", b))
+                .collect::<Vec<_>>()
+                .join(":")
+        });
+
+        info.interfaces.push(super::NetworkInterface {
+            description: unsafe { a.Description.to_string() }.unwrap_or_default(),
+            name,
+            mac_address,
+            addresses,
+            is_up,
+            is_loopback: a.IfType == IF_TYPE_SOFTWARE_LOOPBACK,
+            is_tunnel: matches!(
+                a.IfType,
+                IF_TYPE_PPP | IF_TYPE_TUNNEL | IF_TYPE_PROP_VIRTUAL
+            ),
+        });
+    }
+
+    info.proxy = get_system_proxy();
+    Ok(info)
+}
+
+fn socket_address_to_string(
+    address: &windows::Win32::Networking::WinSock::SOCKET_ADDRESS,
+) -> Option<String> {
+    use windows::Win32::Networking::WinSock::{AF_INET, AF_INET6, SOCKADDR_IN, SOCKADDR_IN6};
+
+    let sockaddr = unsafe { address.lpSockaddr.as_ref() }?;
+    if sockaddr.sa_family == AF_INET {
+        let v4 = unsafe { &*(address.lpSockaddr as *const SOCKADDR_IN) };
+        let ip = u32::from_be(unsafe { v4.sin_addr.S_un.S_addr });
+        Some(std::net::Ipv4Addr::from(ip).to_string())
+    } else if sockaddr.sa_family == AF_INET6 {
+        let v6 = unsafe { &*(address.lpSockaddr as *const SOCKADDR_IN6) };
+        Some(std::net::Ipv6Addr::from(unsafe { v6.sin6_addr.u.Byte }).to_string())
+    } else {
+        None
+    }
+}
+
+/// Proxy server or PAC script from the WinINet (Internet Options) settings
+fn get_system_proxy() -> Option<String> {
+    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+    let key = hkcu
+        .open_subkey("Software\\Microsoft\\Windows\\CurrentVersion\\Internet Settings")
+        .ok()?;
+    if key.get_value::<u32, _>("ProxyEnable").is_ok_and(|v| v != 0) {
+        if let Ok(server) = key.get_value::<String, _>("ProxyServer") {
+            if !server.is_empty() {
+                return Some(server);
+            }
+        }
+    }
+    key.get_value::<String, _>("AutoConfigURL")
+        .ok()
+        .filter(|url| !url.is_empty())
+}
+
+// ============================================================================
+// LAN Neighbors (IP Helper)
+// ============================================================================
+
+pub fn get_neighbors_impl() -> Result<Vec<super::Neighbor>, String> {
+    use windows::Win32::Foundation::NO_ERROR;
+    use windows::Win32::NetworkManagement::IpHelper::{
+        FreeMibTable, GetIpNetTable2, MIB_IPNET_TABLE2,
+    };
+    use windows::Win32::Networking::WinSock::{AF_INET, NlnsIncomplete, NlnsUnreachable};
+
+    let mut table: *mut MIB_IPNET_TABLE2 = std::ptr::null_mut();
+    let result = unsafe { GetIpNetTable2(AF_INET, &mut table) };
+    if result != NO_ERROR {
+        return Err(format!("GetIpNetTable2 failed: error {}", result.0));
+    }
+    let mut neighbors = Vec::new();
+    if let Some(t) = unsafe { table.as_ref() } {
+        let rows = unsafe { std::slice::from_raw_parts(t.Table.as_ptr(), t.NumEntries as usize) };
+        for row in rows {
+            let mac_len = (row.PhysicalAddressLength as usize).min(row.PhysicalAddress.len());
+            let mac = &row.PhysicalAddress[..mac_len];
+            if row.State == NlnsUnreachable
+                || row.State == NlnsIncomplete
+                || mac.len() != 6
+                || mac.iter().all(|&b| b == 0)
+                // Broadcast and multicast entries
+                || mac.iter().all(|&b| b == 0xFF)
+                || mac[0] & 0x01 != 0
+            {
+                continue;
+            }
+            let ip = u32::from_be(unsafe { row.Address.Ipv4.sin_addr.S_un.S_addr });
+            neighbors.push(super::Neighbor {
+                ip: std::net::Ipv4Addr::from(ip).to_string(),
+                mac: mac
+                    .iter()
+                    .map(|b| format!("{:02X}", b))
+                    .collect::<Vec<_>>()
+                    .join(":"),
+            });
+        }
+        unsafe { FreeMibTable(table as *const _) };
+    }
+    Ok(neighbors)
+}
+
+// ============================================================================
+// Idle Time & Session Lock
+// ============================================================================
+
+/// Seconds since the last keyboard/mouse input in this session
+pub fn get_idle_seconds_impl() -> Option<u64> {
+    use windows::Win32::System::SystemInformation::GetTickCount;
+    use windows::Win32::UI::Input::KeyboardAndMouse::{GetLastInputInfo, LASTINPUTINFO};
+
+    let mut info = LASTINPUTINFO {
+        cbSize: std::mem::size_of::<LASTINPUTINFO>() as u32,
+        dwTime: 0,
+    };
+    if !unsafe { GetLastInputInfo(&mut info) }.as_bool() {
+        return None;
+    }
+    // Both are 32-bit tick counts, which wrap after 49.7 days
+    let idle_ms = unsafe { GetTickCount() }.wrapping_sub(info.dwTime);
+    Some(u64::from(idle_ms) / 1000)
+}
+
+/// Whether the workstation is locked. While it is, the input desktop is the secure
+/// Winlogon desktop, which this process isn't allowed to open.
+pub fn is_session_locked_impl() -> Option<bool> {
+    use windows::Win32::System::StationsAndDesktops::{
+        CloseDesktop, OpenInputDesktop, DESKTOP_CONTROL_FLAGS, DESKTOP_READOBJECTS,
+    };
+
+    match unsafe { OpenInputDesktop(DESKTOP_CONTROL_FLAGS(0), false, DESKTOP_READOBJECTS) } {
+        Ok(desktop) => {
+            unsafe {
+                let _ = CloseDesktop(desktop);
+            }
+            Some(false)
+        }
+        Err(_) => Some(true),
+    }
+}
+
+// ============================================================================
+// Keep Awake (execution state)
+// ============================================================================
+
+// The execution state belongs to the thread that set it, so a dedicated thread holds it
+// until its sender is dropped
+static KEEP_AWAKE_THREAD: std::sync::Mutex<Option<std::sync::mpsc::Sender<()>>> =
+    std::sync::Mutex::new(None);
+
+/// Keep the system and display from sleeping until called again with `false`
+pub async fn set_keep_awake_impl(enabled: bool) -> Result<(), String> {
+    use windows::Win32::System::Power::{
+        SetThreadExecutionState, ES_CONTINUOUS, ES_DISPLAY_REQUIRED, ES_SYSTEM_REQUIRED,
+    };
+
+    let mut holder = KEEP_AWAKE_THREAD.lock().unwrap();
+    // Dropping the sender ends the holding thread, which clears its execution state
+    holder.take();
+    if !enabled {
+        return Ok(());
+    }
+
+    let (release_tx, release_rx) = std::sync::mpsc::channel::<()>();
+    let (started_tx, started_rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let previous = unsafe {
+            SetThreadExecutionState(ES_CONTINUOUS | ES_SYSTEM_REQUIRED | ES_DISPLAY_REQUIRED)
+        };
+        let _ = started_tx.send(previous.0 != 0);
+        if previous.0 == 0 {
+            return;
+        }
+        let _ = release_rx.recv();
+        unsafe {
+            SetThreadExecutionState(ES_CONTINUOUS);
+        }
+    });
+
+    if !started_rx.recv().unwrap_or(false) {
+        return Err("Failed to set the thread execution state".to_string());
+    }
+    *holder = Some(release_tx);
+    Ok(())
+}
+
+// ============================================================================
+// Power Actions
+// ============================================================================
+
+/// Lock the workstation, suspend, restart or shut down right away
+pub fn power_action_impl(action: super::PowerAction) -> Result<(), String> {
+    use super::PowerAction;
+    use windows::Win32::System::Power::SetSuspendState;
+    use windows::Win32::System::Shutdown::LockWorkStation;
+
+    let shutdown_flag = match action {
+        PowerAction::Lock => {
+            return unsafe { LockWorkStation() }
+                .map_err(|e| format!("Failed to lock the workstation: {}", e));
+        }
+        PowerAction::Sleep => {
+            // Sleep rather than hibernate; whether wake timers apply is left to the power plan
+            return if unsafe { SetSuspendState(false, false, false) } {
+                Ok(())
+            } else {
+                Err("Failed to put the system to sleep".to_string())
+            };
+        }
+        PowerAction::Restart => "/r",
+        PowerAction::Shutdown => "/s",
+    };
+
+    // shutdown.exe enables the shutdown privilege itself, which ExitWindowsEx would need us to do
+    let output = Command::new("shutdown")
+        .args([shutdown_flag, "/t", "0"])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .map_err(|e| format!("Failed to run shutdown: {}", e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+// ============================================================================
+// Jump List (ICustomDestinationList)
+// ============================================================================
+
+/// Replace the taskbar jump list's Tasks section with shortcuts back into this executable
+pub fn set_jump_list_impl(tasks: &[super::JumpListTask]) -> Result<(), String> {
+    use windows::core::{Interface, HSTRING};
+    use windows::Win32::Storage::EnhancedStorage::PKEY_Title;
+    use windows::Win32::System::Com::StructuredStorage::PROPVARIANT;
+    use windows::Win32::System::Com::{
+        CoCreateInstance, CoInitializeEx, CLSCTX_INPROC_SERVER, COINIT_APARTMENTTHREADED,
+    };
+    use windows::Win32::UI::Shell::Common::{IObjectArray, IObjectCollection};
+    use windows::Win32::UI::Shell::PropertiesSystem::IPropertyStore;
+    use windows::Win32::UI::Shell::{
+        DestinationList, EnumerableObjectCollection, ICustomDestinationList, IShellLinkW, ShellLink,
+    };
+
+    let exe_path = std::env::current_exe()
+        .map_err(|e| e.to_string())?
+        .to_string_lossy()
+        .to_string();
+    let exe = HSTRING::from(exe_path.as_str());
+
+    unsafe {
+        let _ = CoInitializeEx(None, COINIT_APARTMENTTHREADED);
+
+        let list: ICustomDestinationList =
+            CoCreateInstance(&DestinationList, None, CLSCTX_INPROC_SERVER)
+                .map_err(|e| format!("Failed to create the jump list: {}", e))?;
+        let mut max_slots = 0u32;
+        let _removed: IObjectArray = list
+            .BeginList(&mut max_slots)
+            .map_err(|e| format!("Failed to begin the jump list: {}", e))?;
+
+        let collection: IObjectCollection =
+            CoCreateInstance(&EnumerableObjectCollection, None, CLSCTX_INPROC_SERVER)
+                .map_err(|e| format!("Failed to create the jump list: {}", e))?;
+        for task in tasks {
+            let link: IShellLinkW = CoCreateInstance(&ShellLink, None, CLSCTX_INPROC_SERVER)
+                .map_err(|e| format!("Failed to create jump list task: {}", e))?;
+            link.SetPath(&exe)
+                .and_then(|_| link.SetArguments(&HSTRING::from(task.args.as_str())))
+                .and_then(|_| link.SetIconLocation(&exe, 0))
+                .map_err(|e| format!("Failed to create jump list task: {}", e))?;
+
+            // Tasks show the link's title property, not its description
+            let store: IPropertyStore = link
+                .cast()
+                .map_err(|e| format!("Failed to create jump list task: {}", e))?;
+            store
+                .SetValue(&PKEY_Title, &PROPVARIANT::from(task.title.as_str()))
+                .and_then(|_| store.Commit())
+                .map_err(|e| format!("Failed to name jump list task: {}", e))?;
+
+            collection
+                .AddObject(&link)
+                .map_err(|e| format!("Failed to add jump list task: {}", e))?;
+        }
+
+        let tasks: IObjectArray = collection
+            .cast()
+            .map_err(|e| format!("Failed to add jump list tasks: {}", e))?;
+        if let Err(e) = list.AddUserTasks(&tasks) {
+            let _ = list.AbortList();
+            return Err(format!("Failed to add jump list tasks: {}", e));
+        }
+        list.CommitList()
+            .map_err(|e| format!("Failed to commit the jump list: {}", e))
+    }
+}
+
+/// Save a .lnk to this executable on the user's desktop, with the executable's own icon
+pub fn create_desktop_shortcut_impl(
+    shortcut: &super::DesktopShortcut,
+) -> Result<std::path::PathBuf, String> {
+    use windows::core::{Interface, HSTRING};
+    use windows::Win32::System::Com::{
+        CoCreateInstance, CoInitializeEx, CoTaskMemFree, IPersistFile, CLSCTX_INPROC_SERVER,
+        COINIT_APARTMENTTHREADED,
+    };
+    use windows::Win32::UI::Shell::{
+        FOLDERID_Desktop, IShellLinkW, SHGetKnownFolderPath, ShellLink, KF_FLAG_DEFAULT,
+    };
+
+    let exe_path = std::env::current_exe()
+        .map_err(|e| e.to_string())?
+        .to_string_lossy()
+        .to_string();
+    let exe = HSTRING::from(exe_path.as_str());
+    let args: Vec<String> = shortcut.args.iter().map(|a| quote_windows_arg(a)).collect();
+
+    unsafe {
+        let _ = CoInitializeEx(None, COINIT_APARTMENTTHREADED);
+
+        let desktop = SHGetKnownFolderPath(&FOLDERID_Desktop, KF_FLAG_DEFAULT, None)
+            .map_err(|e| format!("Could not find the desktop folder: {}", e))?;
+        let desktop_dir = desktop.to_string();
+        CoTaskMemFree(Some(desktop.0 as *const _));
+        let path = std::path::PathBuf::from(desktop_dir.map_err(|e| e.to_string())?)
+            .join(format!("{}.lnk", super::shortcut_file_stem(&shortcut.name)));
+
+        let link: IShellLinkW = CoCreateInstance(&ShellLink, None, CLSCTX_INPROC_SERVER)
+            .map_err(|e| format!("Failed to create the shortcut: {}", e))?;
+        link.SetPath(&exe)
+            .and_then(|_| link.SetArguments(&HSTRING::from(args.join(" ").as_str())))
+            .and_then(|_| link.SetDescription(&HSTRING::from(shortcut.comment.as_str())))
+            .and_then(|_| link.SetIconLocation(&exe, 0))
+            .map_err(|e| format!("Failed to create the shortcut: {}", e))?;
+        let file: IPersistFile = link
+            .cast()
+            .map_err(|e| format!("Failed to save the shortcut: {}", e))?;
+        file.Save(&HSTRING::from(path.to_string_lossy().as_ref()), true)
+            .map_err(|e| format!("Failed to save the shortcut: {}", e))?;
+        Ok(path)
+    }
+}
+
+// ============================================================================
+// Mouse Input
+// ============================================================================
+
+/// Click `button`, first moving the cursor to `position` (physical pixels) if given
+pub fn click_mouse_impl(
+    button: super::MouseButton,
+    position: Option<(i32, i32)>,
+) -> Result<(), String> {
+    use windows::Win32::UI::Input::KeyboardAndMouse::{
+        INPUT_MOUSE, MOUSEEVENTF_LEFTDOWN, MOUSEEVENTF_LEFTUP, MOUSEEVENTF_MIDDLEDOWN,
+        MOUSEEVENTF_MIDDLEUP, MOUSEEVENTF_RIGHTDOWN, MOUSEEVENTF_RIGHTUP, MOUSEINPUT,
+    };
+    use windows::Win32::UI::WindowsAndMessaging::SetCursorPos;
+
+    let (down, up) = match button {
+        super::MouseButton::Left => (MOUSEEVENTF_LEFTDOWN, MOUSEEVENTF_LEFTUP),
+        super::MouseButton::Right => (MOUSEEVENTF_RIGHTDOWN, MOUSEEVENTF_RIGHTUP),
+        super::MouseButton::Middle => (MOUSEEVENTF_MIDDLEDOWN, MOUSEEVENTF_MIDDLEUP),
+    };
+    unsafe {
+        if let Some((x, y)) = position {
+            SetCursorPos(x, y).map_err(|e| format!("Failed to move the cursor: {}", e))?;
+        }
+
+        let mut inputs: [INPUT; 2] = std::mem::zeroed();
+        for (input, flags) in inputs.iter_mut().zip([down, up]) {
+            input.r#type = INPUT_MOUSE;
+            input.Anonymous.mi = MOUSEINPUT {
+                dx: 0,
+                dy: 0,
+                mouseData: 0,
+                dwFlags: flags,
+                time: 0,
+                dwExtraInfo: 0,
+            };
+        }
+        // Input into elevated windows is blocked for a non-elevated process (UIPI)
+        if SendInput(&inputs, std::mem::size_of::<INPUT>() as i32) != inputs.len() as u32 {
+            return Err("The click was blocked by another program".to_string());
+        }
+    }
+    Ok(())
+}
+
+/// Type `text` into the focused window as Unicode key events, whatever the keyboard layout
+pub fn type_text_impl(text: &str) -> Result<(), String> {
+    use windows::Win32::UI::Input::KeyboardAndMouse::{
+        KEYBD_EVENT_FLAGS, KEYEVENTF_UNICODE, VIRTUAL_KEY, VK_RETURN,
+    };
+
+    let key = |vk: VIRTUAL_KEY, scan: u16, flags: KEYBD_EVENT_FLAGS| {
+        let mut input: INPUT = unsafe { std::mem::zeroed() };
+        input.r#type = INPUT_KEYBOARD;
+        input.Anonymous.ki = KEYBDINPUT {
+            wVk: vk,
+            wScan: scan,
+            dwFlags: flags,
+            time: 0,
+            dwExtraInfo: 0,
+        };
+        input
+    };
+    let mut inputs = Vec::new();
+    for c in text.chars() {
+        match c {
+            '\r' => {}
+            // Unicode events for line breaks don't reach every app
+            '\n' => {
+                inputs.push(key(VK_RETURN, 0, KEYBD_EVENT_FLAGS(0)));
+                inputs.push(key(VK_RETURN, 0, KEYEVENTF_KEYUP));
+            }
+            c => {
+                for &unit in c.encode_utf16(&mut [0; 2]).iter() {
+                    inputs.push(key(VIRTUAL_KEY(0), unit, KEYEVENTF_UNICODE));
+                    inputs.push(key(VIRTUAL_KEY(0), unit, KEYEVENTF_UNICODE | KEYEVENTF_KEYUP));
+                }
+            }
+        }
+    }
+    // Input into elevated windows is blocked for a non-elevated process (UIPI)
+    if unsafe { SendInput(&inputs, std::mem::size_of::<INPUT>() as i32) } != inputs.len() as u32 {
+        return Err("Typing was blocked by another program".to_string());
+    }
+    Ok(())
+}
+
+/// Move the pointer 1 px and back, which counts as user input without visibly moving it
+pub fn nudge_mouse_impl() -> Result<(), String> {
+    use windows::Win32::UI::Input::KeyboardAndMouse::{INPUT_MOUSE, MOUSEEVENTF_MOVE, MOUSEINPUT};
+
+    unsafe {
+        let mut inputs: [INPUT; 2] = std::mem::zeroed();
+        for (input, dx) in inputs.iter_mut().zip([1, -1]) {
+            input.r#type = INPUT_MOUSE;
+            input.Anonymous.mi = MOUSEINPUT {
+                dx,
+                dy: 0,
+                mouseData: 0,
+                dwFlags: MOUSEEVENTF_MOVE,
+                time: 0,
+                dwExtraInfo: 0,
+            };
+        }
+        if SendInput(&inputs, std::mem::size_of::<INPUT>() as i32) != inputs.len() as u32 {
+            return Err("The mouse movement was blocked by another program".to_string());
+        }
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Night Light (GDI gamma ramps)
+// ============================================================================
+
+/// Scale each channel's gamma ramp on every display (1.0 = unchanged); (1, 1, 1) restores the
+/// linear ramp. Windows refuses ramps that stray too far from linear (below roughly 3000 K)
+/// unless GdiIcmGammaRange is raised in the registry.
+pub fn set_gamma_impl(red: f64, green: f64, blue: f64) -> Result<(), String> {
+    use windows::core::PCWSTR;
+    use windows::Win32::Graphics::Gdi::{
+        CreateDCW, DeleteDC, EnumDisplayDevicesW, DISPLAY_DEVICEW,
+        DISPLAY_DEVICE_ATTACHED_TO_DESKTOP,
+    };
+    use windows::Win32::UI::ColorSystem::SetDeviceGammaRamp;
+
+    let mut ramp = [[0u16; 256]; 3];
+    for (channel, scale) in ramp.iter_mut().zip([red, green, blue]) {
+        for (i, value) in channel.iter_mut().enumerate() {
+            *value = (i as f64 / 255.0 * scale * 65535.0).round() as u16;
+        }
+    }
+
+    let mut applied = 0;
+    unsafe {
+        let mut device = DISPLAY_DEVICEW {
+            cb: std::mem::size_of::<DISPLAY_DEVICEW>() as u32,
+            ..Default::default()
+        };
+        let mut index = 0;
+        while EnumDisplayDevicesW(PCWSTR::null(), index, &mut device, 0).as_bool() {
+            index += 1;
+            if !device.StateFlags.contains(DISPLAY_DEVICE_ATTACHED_TO_DESKTOP) {
+                continue;
+            }
+            let hdc = CreateDCW(
+                PCWSTR::null(),
+                PCWSTR(device.DeviceName.as_ptr()),
+                PCWSTR::null(),
+                None,
+            );
+            if hdc.is_invalid() {
+                continue;
+            }
+            if SetDeviceGammaRamp(hdc, ramp.as_ptr() as *const _).as_bool() {
+                applied += 1;
+            }
+            let _ = DeleteDC(hdc);
+        }
+    }
+    if applied == 0 {
+        return Err("The display driver refused the color temperature".to_string());
+    }
+    Ok(())
+}
+
+// ============================================================================
+// FFmpeg Path Resolution
+// ============================================================================
+
+pub fn get_ffmpeg_path() -> Result<std::path::PathBuf, String> {
+    // Get executable directory
+    let exe_dir = std::env::current_exe()
+        .map_err(|e| e.to_string())?
+        .parent()
+        .ok_or("Failed to get exe directory")?
+        .to_path_buf();
+
+    // Get current working directory
+    let cwd = std::env::current_dir().unwrap_or_default();
+
+    let possible_paths = vec![
+        // Production paths
+        exe_dir.join("ffmpeg.exe"),
+        exe_dir.join("binaries").join("ffmpeg.exe"),
+        // Development paths (relative to cwd)
+        cwd.join("src-tauri/binaries/ffmpeg-x86_64-pc-windows-msvc.exe"),
+        cwd.join("binaries/ffmpeg-x86_64-pc-windows-msvc.exe"),
+    ];
+
+    for path in &possible_paths {
+        if path.exists() {
+            log::info!("Found FFmpeg at: {:?}", path);
+            return Ok(path.clone());
+        }
+    }
+
+    Err(format!(
+        "FFmpeg not found. CWD: {:?}, Searched in: {:?}",
+        cwd, possible_paths
+    ))
+}
+
+pub fn get_ytdlp_path() -> Result<std::path::PathBuf, String> {
+    // Get executable directory
+    let exe_dir = std::env::current_exe()
+        .map_err(|e| e.to_string())?
+        .parent()
+        .ok_or("Failed to get exe directory")?
+        .to_path_buf();
+
+    // Get current working directory
+    let cwd = std::env::current_dir().unwrap_or_default();
+
+    let possible_paths = vec![
+        // Production paths
+        exe_dir.join("yt-dlp.exe"),
+        exe_dir.join("binaries").join("yt-dlp.exe"),
+        // Development paths (relative to cwd) - Tauri externalBin naming convention
+        cwd.join("src-tauri/binaries/yt-dlp-x86_64-pc-windows-msvc.exe"),
+        cwd.join("binaries/yt-dlp-x86_64-pc-windows-msvc.exe"),
+    ];
+
+    for path in &possible_paths {
+        if path.exists() {
+            log::info!("Found yt-dlp at: {:?}", path);
+            return Ok(path.clone());
+        }
+    }
+
+    Err(format!(
+        "yt-dlp not found. CWD: {:?}, Searched in: {:?}",
+        cwd, possible_paths
+    ))
+}
+
+
+// ============================================================================
+// Screen Recording (gdigrab / DirectShow)
+// ============================================================================
+
+pub fn screen_capture_backend() -> super::ScreenCaptureBackend {
+    super::ScreenCaptureBackend::Gdigrab
+}
+
+/// First DirectShow audio device ffmpeg lists (a microphone, or Stereo Mix if enabled)
+pub fn audio_capture_input(ffmpeg: &std::path::Path) -> Result<super::AudioCaptureInput, String> {
+    list_audio_inputs(ffmpeg)?
+        .first()
+        .map(|device| audio_input(&device.id))
+        .ok_or_else(|| "No audio input device found".to_string())
+}
+
+/// A DirectShow audio device by name
+pub fn audio_input(id: &str) -> super::AudioCaptureInput {
+    super::AudioCaptureInput {
+        format: "dshow".to_string(),
+        device: format!("audio={}", id),
+    }
+}
+
+/// DirectShow audio devices as ffmpeg lists them, the default one first
+pub fn list_audio_inputs(ffmpeg: &std::path::Path) -> Result<Vec<super::AudioInputDevice>, String> {
+    let output = Command::new(ffmpeg)
+        .args(["-hide_banner", "-list_devices", "true", "-f", "dshow", "-i", "dummy"])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .map_err(|e| format!("Failed to list audio devices: {}", e))?;
+
+    // The listing goes to stderr, one `[dshow @ ...] "Microphone (Realtek Audio)" (audio)` per device
+    Ok(String::from_utf8_lossy(&output.stderr)
+        .lines()
+        .filter(|line| line.trim_end().ends_with("(audio)"))
+        .filter_map(|line| {
+            let start = line.find('"')?;
+            let end = line.rfind('"')?;
+            (end > start + 1).then(|| line[start + 1..end].to_string())
+        })
+        .map(|name| super::AudioInputDevice {
+            id: name.clone(),
+            name,
+        })
+        .collect())
+}
+
+/// A regular sans-serif font file for drawing text into images (Segoe UI)
+pub fn ui_font_path() -> Option<std::path::PathBuf> {
+    let fonts = std::path::PathBuf::from(
+        std::env::var("WINDIR").unwrap_or_else(|_| "C:\\Windows".to_string()),
+    )
+    .join("Fonts");
+    ["segoeui.ttf", "arial.ttf"]
+        .into_iter()
+        .map(|name| fonts.join(name))
+        .find(|path| path.is_file())
+}
+
+// ============================================================================
+// Other Apps' Windows (pinned on top, translucent)
+// ============================================================================
+
+unsafe extern "system" fn collect_window(
+    hwnd: HWND,
+    lparam: windows::Win32::Foundation::LPARAM,
+) -> windows::core::BOOL {
+    let handles = &mut *(lparam.0 as *mut Vec<HWND>);
+    handles.push(hwnd);
+    true.into()
+}
+
+fn external_hwnd(id: u64) -> Result<HWND, String> {
+    use windows::Win32::UI::WindowsAndMessaging::IsWindow;
+
+    let hwnd = HWND(id as isize as *mut std::ffi::c_void);
+    if unsafe { IsWindow(Some(hwnd)) }.as_bool() {
+        Ok(hwnd)
+    } else {
+        Err("That window was closed".to_string())
+    }
+}
+
+/// Visible, titled top-level windows of other apps, front to back (what Alt+Tab shows)
+pub fn list_external_windows() -> Result<Vec<super::ExternalWindow>, String> {
+    use windows::Win32::Foundation::{COLORREF, LPARAM};
+    use windows::Win32::UI::WindowsAndMessaging::{
+        EnumWindows, GetLayeredWindowAttributes, GetWindow, GetWindowLongPtrW, GetWindowTextW,
+        GetWindowThreadProcessId, IsWindowVisible, GWL_EXSTYLE, GW_OWNER,
+        LAYERED_WINDOW_ATTRIBUTES_FLAGS, LWA_ALPHA, WS_EX_LAYERED, WS_EX_TOOLWINDOW,
+        WS_EX_TOPMOST,
+    };
+
+    let mut handles: Vec<HWND> = Vec::new();
+    unsafe { EnumWindows(Some(collect_window), LPARAM(&mut handles as *mut _ as isize)) }
+        .map_err(|e| format!("Failed to list windows: {}", e))?;
+
+    let own_pid = std::process::id();
+    let mut windows = Vec::new();
+    for hwnd in handles {
+        unsafe {
+            let mut pid = 0u32;
+            GetWindowThreadProcessId(hwnd, Some(&mut pid));
+            let ex_style = GetWindowLongPtrW(hwnd, GWL_EXSTYLE) as u32;
+            if pid == own_pid
+                || !IsWindowVisible(hwnd).as_bool()
+                || GetWindow(hwnd, GW_OWNER).is_ok()
+                || ex_style & WS_EX_TOOLWINDOW.0 != 0
+            {
+                continue;
+            }
+            let mut title = [0u16; 512];
+            let len = GetWindowTextW(hwnd, &mut title);
+            let title = String::from_utf16_lossy(&title[..len.max(0) as usize]);
+            if title.trim().is_empty() {
+                continue;
+            }
+
+            let mut opacity = 1.0;
+            if ex_style & WS_EX_LAYERED.0 != 0 {
+                let (mut key, mut alpha, mut flags) =
+                    (COLORREF(0), 255u8, LAYERED_WINDOW_ATTRIBUTES_FLAGS(0));
+                let read = GetLayeredWindowAttributes(
+                    hwnd,
+                    Some(&mut key),
+                    Some(&mut alpha),
+                    Some(&mut flags),
+                );
+                if read.is_ok() && flags.0 & LWA_ALPHA.0 != 0
+                {
+                    opacity = alpha as f32 / 255.0;
+                }
+            }
+            windows.push(super::ExternalWindow {
+                id: hwnd.0 as isize as u64,
+                title,
+                topmost: ex_style & WS_EX_TOPMOST.0 != 0,
+                opacity,
+            });
+        }
+    }
+    Ok(windows)
+}
+
+/// Keep a window above all others, or let it go behind them again
+pub fn set_external_window_topmost(id: u64, on: bool) -> Result<(), String> {
+    use windows::Win32::UI::WindowsAndMessaging::{
+        SetWindowPos, HWND_NOTOPMOST, HWND_TOPMOST, SWP_NOACTIVATE, SWP_NOMOVE, SWP_NOSIZE,
+    };
+
+    let hwnd = external_hwnd(id)?;
+    unsafe {
+        SetWindowPos(
+            hwnd,
+            Some(if on { HWND_TOPMOST } else { HWND_NOTOPMOST }),
+            0,
+            0,
+            0,
+            0,
+            SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE,
+        )
+    }
+    // Windows of elevated apps refuse
+    .map_err(|_| super::ACCESS_DENIED.to_string())
+}
+
+/// Make a window translucent, `alpha` 255 is opaque
+pub fn set_external_window_opacity(id: u64, alpha: u8) -> Result<(), String> {
+    use windows::Win32::Foundation::COLORREF;
+    use windows::Win32::UI::WindowsAndMessaging::{
+        GetWindowLongPtrW, SetLayeredWindowAttributes, SetWindowLongPtrW, GWL_EXSTYLE, LWA_ALPHA,
+        WS_EX_LAYERED,
+    };
+
+    let hwnd = external_hwnd(id)?;
+    unsafe {
+        let ex_style = GetWindowLongPtrW(hwnd, GWL_EXSTYLE);
+        if ex_style as u32 & WS_EX_LAYERED.0 == 0 {
+            SetWindowLongPtrW(hwnd, GWL_EXSTYLE, ex_style | WS_EX_LAYERED.0 as isize);
+        }
+        SetLayeredWindowAttributes(hwnd, COLORREF(0), alpha, LWA_ALPHA)
+    }
+    .map_err(|e| format!("Failed to change the window's opacity: {}", e))
+}
+
+// ============================================================================
+// Screen Text (Snip & Sketch, Windows.Media.Ocr)
+// ============================================================================
+
+/// The whole virtual screen (every monitor), as the desktop is drawn right now
+pub async fn capture_full_screen_impl(
+    _app: &tauri::AppHandle,
+) -> Result<image::RgbaImage, String> {
+    tokio::task::spawn_blocking(capture_virtual_screen)
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+}
+
+fn capture_virtual_screen() -> Result<image::RgbaImage, String> {
+    use windows::Win32::Graphics::Gdi::{
+        BitBlt, CreateCompatibleBitmap, CreateCompatibleDC, DeleteDC, DeleteObject, GetDIBits,
+        SelectObject, BITMAPINFO, BITMAPINFOHEADER, BI_RGB, DIB_RGB_COLORS, SRCCOPY,
+    };
+    use windows::Win32::UI::WindowsAndMessaging::{
+        GetSystemMetrics, SM_CXVIRTUALSCREEN, SM_CYVIRTUALSCREEN, SM_XVIRTUALSCREEN,
+        SM_YVIRTUALSCREEN,
+    };
+
+    let (x, y, width, height) = unsafe {
+        (
+            GetSystemMetrics(SM_XVIRTUALSCREEN),
+            GetSystemMetrics(SM_YVIRTUALSCREEN),
+            GetSystemMetrics(SM_CXVIRTUALSCREEN),
+            GetSystemMetrics(SM_CYVIRTUALSCREEN),
+        )
+    };
+    if width <= 0 || height <= 0 {
+        return Err("Failed to capture the screen".to_string());
+    }
+
+    let mut data = vec![0u8; (width * height * 4) as usize];
+    let copied = unsafe {
+        let screen = GetDC(None);
+        let memory = CreateCompatibleDC(Some(screen));
+        let bitmap = CreateCompatibleBitmap(screen, width, height);
+        let previous = SelectObject(memory, bitmap.into());
+        let copied = BitBlt(memory, 0, 0, width, height, Some(screen), x, y, SRCCOPY).is_ok();
+        SelectObject(memory, previous);
+
+        let mut info = BITMAPINFO {
+            bmiHeader: BITMAPINFOHEADER {
+                biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+                biWidth: width,
+                biHeight: -height, // top-down rows
+                biPlanes: 1,
+                biBitCount: 32,
+                biCompression: BI_RGB.0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let lines = GetDIBits(
+            memory,
+            bitmap,
+            0,
+            height as u32,
+            Some(data.as_mut_ptr() as *mut _),
+            &mut info,
+            DIB_RGB_COLORS,
+        );
+        let _ = DeleteObject(bitmap.into());
+        let _ = DeleteDC(memory);
+        ReleaseDC(None, screen);
+        copied && lines == height
+    };
+    if !copied {
+        return Err("Failed to capture the screen".to_string());
+    }
+
+    // BGRA, with an undefined alpha channel
+    for px in data.chunks_exact_mut(4) {
+        px.swap(0, 2);
+        px[3] = 255;
+    }
+    image::RgbaImage::from_raw(width as u32, height as u32, data)
+        .ok_or_else(|| "Failed to capture the screen".to_string())
+}
+
+// How long the snipping overlay may stay open before giving up
+const SNIP_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Let the user drag out a region with the system snipping overlay (Win+Shift+S), which puts
+/// the snip on the clipboard. The clipboard is watched until a new image shows up there.
+pub async fn capture_screen_region_impl(app: &tauri::AppHandle) -> Result<image::RgbaImage, String> {
+    use std::hash::{Hash, Hasher};
+    use tauri_plugin_clipboard_manager::ClipboardExt;
+
+    let clipboard_image = |app: &tauri::AppHandle| {
+        let image = app.clipboard().read_image().ok()?;
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        image.rgba().hash(&mut hasher);
+        Some((hasher.finish(), image.width(), image.height(), image.rgba().to_vec()))
+    };
+    let before = clipboard_image(app).map(|(hash, ..)| hash);
+
+    Command::new("explorer")
+        .arg("ms-screenclip:")
+        .creation_flags(CREATE_NO_WINDOW)
+        .spawn()
+        .map_err(|e| format!("Failed to open the snipping overlay: {}", e))?;
+
+    let deadline = std::time::Instant::now() + SNIP_TIMEOUT;
+    while std::time::Instant::now() < deadline {
+        tokio::time::sleep(std::time::Duration::from_millis(250)).await;
+        if let Some((hash, width, height, rgba)) = clipboard_image(app) {
+            if Some(hash) != before {
+                return image::RgbaImage::from_raw(width, height, rgba)
+                    .ok_or_else(|| "The snip couldn't be read".to_string());
+            }
+        }
+    }
+    Err("Cancelled".to_string())
+}
+
+/// Recognize text with the Windows OCR engine in the user's profile languages
+pub fn recognize_text_impl(image: &image::RgbaImage) -> Result<String, String> {
+    use windows::Graphics::Imaging::{BitmapPixelFormat, SoftwareBitmap};
+    use windows::Media::Ocr::OcrEngine;
+    use windows::Storage::Streams::DataWriter;
+    use windows::Win32::System::Com::{CoInitializeEx, COINIT_MULTITHREADED};
+
+    let ocr_error = |e: windows::core::Error| format!("Text recognition failed: {}", e);
+
+    // Already-initialized (S_FALSE / RPC_E_CHANGED_MODE) is fine, WinRT is usable either way
+    unsafe {
+        let _ = CoInitializeEx(None, COINIT_MULTITHREADED);
+    }
+
+    let engine = OcrEngine::TryCreateFromUserProfileLanguages().map_err(|_| {
+        "No text recognition language is installed (Settings > Time & language > Language)"
+            .to_string()
+    })?;
+
+    // Larger images are rejected by the engine, so scale them down first
+    let max = OcrEngine::MaxImageDimension().map_err(ocr_error)?;
+    let image = if image.width() > max || image.height() > max {
+        let scale = max as f64 / image.width().max(image.height()) as f64;
+        image::imageops::resize(
+            image,
+            (image.width() as f64 * scale) as u32,
+            (image.height() as f64 * scale) as u32,
+            image::imageops::FilterType::Triangle,
+        )
+    } else {
+        image.clone()
+    };
+
+    // SoftwareBitmap wants BGRA
+    let mut bgra = image.as_raw().clone();
+    for pixel in bgra.chunks_exact_mut(4) {
+        pixel.swap(0, 2);
+    }
+    let writer = DataWriter::new().map_err(ocr_error)?;
+    writer.WriteBytes(&bgra).map_err(ocr_error)?;
+    let buffer = writer.DetachBuffer().map_err(ocr_error)?;
+    let bitmap = SoftwareBitmap::CreateCopyFromBuffer(
+        &buffer,
+        BitmapPixelFormat::Bgra8,
+        image.width() as i32,
+        image.height() as i32,
+    )
+    .map_err(ocr_error)?;
+
+    let result = engine
+        .RecognizeAsync(&bitmap)
+        .map_err(ocr_error)?
+        .get()
+        .map_err(ocr_error)?;
+    let mut lines = Vec::new();
+    for line in result.Lines().map_err(ocr_error)? {
+        lines.push(line.Text().map_err(ocr_error)?.to_string());
+    }
+    Ok(lines.join("\n"))
+}
+
+// ============================================================================
+// Elevation (UAC)
+// ============================================================================
+
+pub fn is_elevated_impl() -> bool {
+    unsafe { windows::Win32::UI::Shell::IsUserAnAdmin().as_bool() }
+}
+
+/// Quote a single argument following the MSVCRT command-line parsing rules
+fn quote_windows_arg(arg: &str) -> String {
+    if !arg.is_empty() && !arg.contains([' ', '\t', '"']) {
+        return arg.to_string();
+    }
+
+    let mut quoted = String::from("\"");
+    let mut backslashes = 0;
+    for c in arg.chars() {
+        match c {
+            '\\' => backslashes += 1,
+            '"' => {
+                quoted.push_str(&"\\".repeat(backslashes * 2 + 1));
+                quoted.push('"');
+                backslashes = 0;
+            }
+            _ => {
+                quoted.push_str(&"\\".repeat(backslashes));
+                quoted.push(c);
+                backslashes = 0;
+            }
+        }
+    }
+    quoted.push_str(&"\\".repeat(backslashes * 2));
+    quoted.push('"');
+    quoted
+}
+
+/// Run a single program with the "runas" verb, which shows the UAC prompt.
+/// Waits for the elevated process and returns its exit code.
+pub fn run_elevated_impl(program: &str, args: &[String]) -> Result<i32, String> {
+    use windows::core::{HSTRING, PCWSTR};
+    use windows::Win32::Foundation::{CloseHandle, ERROR_CANCELLED};
+    use windows::Win32::System::Threading::{GetExitCodeProcess, WaitForSingleObject, INFINITE};
+    use windows::Win32::UI::Shell::{
+        ShellExecuteExW, SEE_MASK_FLAG_NO_UI, SEE_MASK_NOCLOSEPROCESS, SHELLEXECUTEINFOW,
+    };
+    use windows::Win32::UI::WindowsAndMessaging::SW_HIDE;
+
+    let verb = HSTRING::from("runas");
+    let file = HSTRING::from(program);
+    let params = HSTRING::from(
+        args.iter()
+            .map(|a| quote_windows_arg(a))
+            .collect::<Vec<_>>()
+            .join(" "),
+    );
+
+    let mut info = SHELLEXECUTEINFOW {
+        cbSize: std::mem::size_of::<SHELLEXECUTEINFOW>() as u32,
+        fMask: SEE_MASK_NOCLOSEPROCESS | SEE_MASK_FLAG_NO_UI,
+        lpVerb: PCWSTR(verb.as_ptr()),
+        lpFile: PCWSTR(file.as_ptr()),
+        lpParameters: PCWSTR(params.as_ptr()),
+        nShow: SW_HIDE.0,
+        ..Default::default()
+    };
+
+    unsafe {
+        if let Err(e) = ShellExecuteExW(&mut info) {
+            if e.code() == ERROR_CANCELLED.to_hresult() {
+                return Err(super::ELEVATION_DECLINED.to_string());
+            }
+            return Err(format!("Failed to start elevated process: {}", e));
+        }
+
+        if info.hProcess.is_invalid() {
+            return Err("Elevated process did not start".to_string());
+        }
+
+        WaitForSingleObject(info.hProcess, INFINITE);
+        let mut exit_code = 0u32;
+        let result = GetExitCodeProcess(info.hProcess, &mut exit_code);
+        let _ = CloseHandle(info.hProcess);
+        result.map_err(|e| format!("Failed to read exit code: {}", e))?;
+
+        Ok(exit_code as i32)
+    }
+}
+
+// ============================================================================
+// Mouse Triggers (low-level mouse hook)
+// ============================================================================
+
+use super::{MouseTrigger, MouseTriggerCallback};
+use std::sync::Mutex;
+
+static MOUSE_TRIGGER: Mutex<MouseTrigger> = Mutex::new(MouseTrigger::None);
+static MOUSE_TRIGGER_CALLBACK: Mutex<Option<MouseTriggerCallback>> = Mutex::new(None);
+static MOUSE_HOOK_STARTED: std::sync::Once = std::sync::Once::new();
+
+/// Configure the mouse trigger. The hook thread is installed the first time a
+/// trigger is enabled and simply passes events through while set to `None`.
+pub fn set_mouse_trigger_impl(
+    trigger: MouseTrigger,
+    on_trigger: MouseTriggerCallback,
+) -> Result<(), String> {
+    *MOUSE_TRIGGER_CALLBACK.lock().unwrap() = Some(on_trigger);
+    *MOUSE_TRIGGER.lock().unwrap() = trigger;
+
+    if trigger != MouseTrigger::None {
+        MOUSE_HOOK_STARTED.call_once(|| {
+            std::thread::spawn(run_mouse_hook);
+        });
+    }
+
+    Ok(())
+}
+
+fn run_mouse_hook() {
+    use windows::Win32::UI::WindowsAndMessaging::{GetMessageW, SetWindowsHookExW, MSG, WH_MOUSE_LL};
+
+    unsafe {
+        // Low-level hooks are called on the installing thread, which must pump messages
+        if let Err(e) = SetWindowsHookExW(WH_MOUSE_LL, Some(mouse_hook_proc), None, 0) {
+            log::error!("Failed to install mouse hook: {}", e);
+            return;
+        }
+
+        let mut msg = MSG::default();
+        while GetMessageW(&mut msg, None, 0, 0).as_bool() {}
+    }
+}
+
+unsafe extern "system" fn mouse_hook_proc(
+    code: i32,
+    wparam: windows::Win32::Foundation::WPARAM,
+    lparam: windows::Win32::Foundation::LPARAM,
+) -> windows::Win32::Foundation::LRESULT {
+    use windows::Win32::Foundation::LRESULT;
+    use windows::Win32::UI::WindowsAndMessaging::{
+        CallNextHookEx, HC_ACTION, MSLLHOOKSTRUCT, WM_MBUTTONDOWN, WM_XBUTTONDOWN, WM_XBUTTONUP,
+        XBUTTON1, XBUTTON2,
+    };
+
+    if code == HC_ACTION as i32 {
+        let info = &*(lparam.0 as *const MSLLHOOKSTRUCT);
+        let message = wparam.0 as u32;
+        let trigger = *MOUSE_TRIGGER.lock().unwrap();
+
+        match trigger {
+            MouseTrigger::XButton1 | MouseTrigger::XButton2
+                if message == WM_XBUTTONDOWN || message == WM_XBUTTONUP =>
+            {
+                let wanted = if trigger == MouseTrigger::XButton1 { XBUTTON1 } else { XBUTTON2 };
+                if (info.mouseData >> 16) as u16 == wanted {
+                    if message == WM_XBUTTONDOWN {
+                        fire_mouse_trigger();
+                    }
+                    // Swallow both down and up so browsers don't also navigate back/forward
+                    return LRESULT(1);
+                }
+            }
+            MouseTrigger::MiddleClickDesktop
+                if message == WM_MBUTTONDOWN && is_desktop_at(info.pt) =>
+            {
+                fire_mouse_trigger();
+            }
+            _ => {}
+        }
+    }
+
+    CallNextHookEx(None, code, wparam, lparam)
+}
+
+fn fire_mouse_trigger() {
+    // Clone out of the lock; the callback only schedules work so the hook returns quickly
+    let callback = MOUSE_TRIGGER_CALLBACK.lock().unwrap().clone();
+    if let Some(callback) = callback {
+        callback();
+    }
+}
+
+/// Whether the point is over the desktop background (Progman / WorkerW shell windows)
+fn is_desktop_at(point: POINT) -> bool {
+    use windows::Win32::UI::WindowsAndMessaging::{GetAncestor, GetClassNameW, WindowFromPoint, GA_ROOT};
+
+    unsafe {
+        let hwnd = WindowFromPoint(point);
+        if hwnd.is_invalid() {
+            return false;
+        }
+        let root = GetAncestor(hwnd, GA_ROOT);
+
+        let mut class_name = [0u16; 64];
+        let len = GetClassNameW(root, &mut class_name);
+        let class_name = String::from_utf16_lossy(&class_name[..len.max(0) as usize]);
+        class_name == "Progman" || class_name == "WorkerW"
+    }
 }
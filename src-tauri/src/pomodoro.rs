@@ -0,0 +1,235 @@
+// Pomodoro focus sessions
+// Alternates focus and break periods until stopped, ticking `pomodoro-tick` every second.
+// While focusing the tray icon shows a focus badge and, depending on the settings, the OS
+// do-not-disturb switch is turned on and job notifications are held back; everything is
+// restored when the break begins. Focus periods are paused while the screen is locked. If the
+// app exits mid-session, the DND switch is turned back off on the next start.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::notifications::{self, NotifyAction};
+use crate::{platform, session_state, tray_status, AppState};
+
+// Remembers that we turned DND on, in case the app doesn't get to turn it off again
+const STATE_FILE: &str = "pomodoro.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PomodoroPhase {
+    Focus,
+    Break,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PomodoroStatus {
+    pub phase: PomodoroPhase,
+    pub remaining: u64, // seconds left in the current phase
+    pub round: u32,     // focus periods started so far, from 1
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SavedState {
+    dnd_enabled_by_us: bool,
+}
+
+struct Session {
+    generation: u64,
+    status: PomodoroStatus,
+    dnd_enabled_by_us: bool,
+    notifications_paused: bool,
+}
+
+static SESSION: Mutex<Option<Session>> = Mutex::new(None);
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// True while a focus period is running (for the tray icon)
+pub fn is_focusing() -> bool {
+    SESSION
+        .lock()
+        .unwrap()
+        .as_ref()
+        .is_some_and(|s| s.status.phase == PomodoroPhase::Focus)
+}
+
+/// Turn DND back off if a previous run exited during a focus period
+pub fn init(app: &AppHandle) {
+    let saved: SavedState = crate::load_json_file(app, STATE_FILE);
+    if saved.dnd_enabled_by_us {
+        set_dnd(app, false);
+    }
+}
+
+fn set_dnd(app: &AppHandle, enabled: bool) {
+    if let Err(e) = platform::set_do_not_disturb_impl(enabled) {
+        log::warn!("Failed to change do not disturb: {}", e);
+    }
+    let saved = SavedState {
+        dnd_enabled_by_us: enabled,
+    };
+    if let Err(e) = crate::save_json_file(app, STATE_FILE, &saved) {
+        log::warn!("Failed to save pomodoro state: {}", e);
+    }
+}
+
+/// Apply the focus-time settings and switch the session to `phase`
+fn enter_phase(app: &AppHandle, generation: u64, phase: PomodoroPhase, seconds: u64) -> bool {
+    let (use_dnd, pause_notifications) = {
+        let state = app.state::<AppState>();
+        let settings = state.settings.lock().unwrap();
+        (settings.pomodoro_dnd, settings.pomodoro_pause_notifications)
+    };
+
+    {
+        let mut session = SESSION.lock().unwrap();
+        let Some(session) = session.as_mut().filter(|s| s.generation == generation) else {
+            return false; // stopped or restarted
+        };
+        if phase == PomodoroPhase::Focus {
+            session.status.round += 1;
+            // Only take over the switch if the user hasn't turned DND on themselves
+            if use_dnd && platform::get_do_not_disturb_impl() == Some(false) {
+                set_dnd(app, true);
+                session.dnd_enabled_by_us = true;
+            }
+            if pause_notifications {
+                notifications::pause_notifications();
+                session.notifications_paused = true;
+            }
+        } else {
+            restore(app, session);
+        }
+        session.status.phase = phase;
+        session.status.remaining = seconds;
+    }
+
+    tray_status::refresh(app);
+    true
+}
+
+/// Undo what the focus period changed
+fn restore(app: &AppHandle, session: &mut Session) {
+    if std::mem::take(&mut session.dnd_enabled_by_us) {
+        set_dnd(app, false);
+    }
+    if std::mem::take(&mut session.notifications_paused) {
+        notifications::resume_notifications(app);
+    }
+}
+
+/// Count down the current phase; false if the session was stopped meanwhile
+async fn run_phase(app: &AppHandle, generation: u64) -> bool {
+    loop {
+        let status = {
+            let mut session = SESSION.lock().unwrap();
+            let Some(session) = session.as_mut().filter(|s| s.generation == generation) else {
+                return false;
+            };
+            let status = session.status.clone();
+            // Focus time doesn't count down while the screen is locked
+            if status.phase == PomodoroPhase::Break || !session_state::is_locked() {
+                session.status.remaining = status.remaining.saturating_sub(1);
+            }
+            status
+        };
+        let _ = app.emit("pomodoro-tick", Some(&status));
+        if status.remaining == 0 {
+            return true;
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+}
+
+/// Start focusing; a running session is replaced. Durations default to the settings.
+#[tauri::command]
+pub fn start_pomodoro(
+    app: AppHandle,
+    focus_minutes: Option<u64>,
+    break_minutes: Option<u64>,
+) -> Result<(), String> {
+    let (default_focus, default_break) = {
+        let state = app.state::<AppState>();
+        let settings = state.settings.lock().unwrap();
+        (
+            settings.pomodoro_focus_minutes,
+            settings.pomodoro_break_minutes,
+        )
+    };
+    let focus = focus_minutes.unwrap_or(default_focus);
+    let pause = break_minutes.unwrap_or(default_break);
+    if focus == 0 || pause == 0 {
+        return Err("Focus and break need to be at least a minute".to_string());
+    }
+
+    stop_session(&app);
+    let generation = GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    *SESSION.lock().unwrap() = Some(Session {
+        generation,
+        status: PomodoroStatus {
+            phase: PomodoroPhase::Break,
+            remaining: 0,
+            round: 0,
+        },
+        dnd_enabled_by_us: false,
+        notifications_paused: false,
+    });
+
+    tauri::async_runtime::spawn(async move {
+        loop {
+            if !enter_phase(&app, generation, PomodoroPhase::Focus, focus * 60)
+                || !run_phase(&app, generation).await
+            {
+                return;
+            }
+            if !enter_phase(&app, generation, PomodoroPhase::Break, pause * 60) {
+                return;
+            }
+            notifications::notify(
+                &app,
+                "Break time",
+                &format!("Focus session done - take {} minutes off", pause),
+                NotifyAction::None,
+            );
+            if !run_phase(&app, generation).await {
+                return;
+            }
+            notifications::notify(
+                &app,
+                "Back to focus",
+                &format!("Break is over - next {} minute focus session", focus),
+                NotifyAction::ShowWindow,
+            );
+        }
+    });
+
+    Ok(())
+}
+
+/// End the session and restore DND / notifications
+pub fn stop_session(app: &AppHandle) -> bool {
+    let Some(mut session) = SESSION.lock().unwrap().take() else {
+        return false;
+    };
+    restore(app, &mut session);
+    tray_status::refresh(app);
+    let _ = app.emit("pomodoro-tick", None::<PomodoroStatus>);
+    true
+}
+
+#[tauri::command]
+pub fn stop_pomodoro(app: AppHandle) -> Result<(), String> {
+    if stop_session(&app) {
+        Ok(())
+    } else {
+        Err("No focus session is running".to_string())
+    }
+}
+
+#[tauri::command]
+pub fn get_pomodoro_status() -> Option<PomodoroStatus> {
+    SESSION.lock().unwrap().as_ref().map(|s| s.status.clone())
+}
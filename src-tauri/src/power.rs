@@ -0,0 +1,157 @@
+// Power status and the battery policy for heavy jobs
+// The AC adapter and battery are polled in the background and `power-changed` is emitted with
+// the new status whenever it differs. On battery at or below the user's threshold, heavy jobs
+// (conversions, transcriptions) follow the `BatteryPolicy`: they run as usual, run
+// throttled (suspended every other slice, so they use about half the CPU), or wait for AC
+// power (queued ones don't start, running ones are suspended). Back on AC, or above the
+// threshold, everything carries on at full speed. A single job can ignore the policy
+// (`run_job_on_battery` in jobs.rs).
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::jobs;
+use crate::platform::{self, PowerStatus};
+use crate::AppState;
+
+// Reading the power supply is cheap, and unplugging should take effect soon
+const POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+// Throttled jobs alternate working and resting slices of this length
+const THROTTLE_SLICE: Duration = Duration::from_secs(1);
+
+static LAST_STATUS: Mutex<Option<PowerStatus>> = Mutex::new(None);
+
+/// What heavy jobs do on battery below the threshold; nothing changes unless the user opts in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatteryPolicy {
+    #[default]
+    Run,
+    Throttle,
+    Defer, // wait for AC power
+}
+
+/// The user's battery policy (`Settings.battery`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatterySettings {
+    #[serde(default)]
+    pub policy: BatteryPolicy,
+    #[serde(default = "default_below_percent")]
+    pub below_percent: u8, // applies at or below this charge; 100 for whenever on battery
+}
+
+fn default_below_percent() -> u8 {
+    30
+}
+
+impl Default for BatterySettings {
+    fn default() -> Self {
+        Self {
+            policy: BatteryPolicy::default(),
+            below_percent: default_below_percent(),
+        }
+    }
+}
+
+/// What the battery policy does to a heavy job right now
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobPower {
+    #[default]
+    Normal,
+    Throttled,
+    Deferred,
+}
+
+/// Payload of `power-changed` and `get_power_status`
+#[derive(Debug, Clone, Serialize)]
+pub struct PowerState {
+    #[serde(flatten)]
+    pub status: PowerStatus,
+    pub job_power: JobPower,
+}
+
+/// The policy's verdict for heavy jobs under `status`. A battery that can't tell its charge
+/// counts as low.
+pub fn policy_for(status: &PowerStatus, battery: &BatterySettings) -> JobPower {
+    let low = status
+        .battery_percent
+        .map_or(true, |percent| percent <= battery.below_percent);
+    if !status.on_battery || !low {
+        return JobPower::Normal;
+    }
+    match battery.policy {
+        BatteryPolicy::Run => JobPower::Normal,
+        BatteryPolicy::Throttle => JobPower::Throttled,
+        BatteryPolicy::Defer => JobPower::Deferred,
+    }
+}
+
+/// The verdict for heavy jobs from the last power reading (Normal before the first)
+pub fn job_power(app: &AppHandle) -> JobPower {
+    let status = LAST_STATUS.lock().unwrap().unwrap_or_default();
+    let battery = app.state::<AppState>().settings.lock().unwrap().battery;
+    policy_for(&status, &battery)
+}
+
+/// Throttle, hold or release the running heavy jobs after the power or the policy changed
+pub fn apply(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    if state.jobs.apply_power(job_power(app)) {
+        jobs::emit_jobs_changed(app);
+    }
+}
+
+/// Start watching the power supply and throttling jobs
+pub fn init(app: &AppHandle) {
+    let watcher = app.clone();
+    std::thread::spawn(move || loop {
+        match platform::get_power_status_impl() {
+            Ok(status) => {
+                let previous = LAST_STATUS.lock().unwrap().replace(status);
+                if previous != Some(status) {
+                    // The first reading is the baseline, not a change, but still applies
+                    if previous.is_some() {
+                        let power = PowerState {
+                            status,
+                            job_power: job_power(&watcher),
+                        };
+                        let _ = watcher.emit("power-changed", power);
+                    }
+                    apply(&watcher);
+                }
+            }
+            Err(e) => log::warn!("Failed to read power status: {}", e),
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    });
+
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let mut resting = false;
+        loop {
+            std::thread::sleep(THROTTLE_SLICE);
+            resting = !resting;
+            let tokens = app.state::<AppState>().jobs.throttled_tokens();
+            for token in tokens {
+                token.set_resting(resting);
+            }
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn get_power_status(app: AppHandle) -> Result<PowerState, String> {
+    let status = tauri::async_runtime::spawn_blocking(platform::get_power_status_impl)
+        .await
+        .map_err(|e| e.to_string())??;
+    let battery = app.state::<AppState>().settings.lock().unwrap().battery;
+    Ok(PowerState {
+        status,
+        job_power: policy_for(&status, &battery),
+    })
+}
@@ -0,0 +1,138 @@
+// Power actions: lock, sleep, restart and shut down from the launcher
+// A shutdown can be delayed; while it's pending a small always-on-top window counts down
+// with Cancel / Shut Down Now buttons, and `shutdown-scheduled` follows its state.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use crate::platform::{self, PowerAction};
+use crate::tool_windows::{self, ToolWindowOptions};
+
+const CONFIRM_TOOL: &str = "shutdown";
+const MAX_DELAY_DAYS: u64 = 7;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduledShutdown {
+    pub remaining: u64, // seconds until shutdown
+}
+
+struct Pending {
+    generation: u64,
+    at: Instant,
+}
+
+static PENDING: Mutex<Option<Pending>> = Mutex::new(None);
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+fn scheduled() -> Option<ScheduledShutdown> {
+    PENDING.lock().unwrap().as_ref().map(|p| ScheduledShutdown {
+        remaining: p.at.saturating_duration_since(Instant::now()).as_secs(),
+    })
+}
+
+fn run(action: PowerAction) -> Result<(), String> {
+    log::info!("Power action: {:?}", action);
+    platform::power_action_impl(action)
+}
+
+#[tauri::command]
+pub fn lock_screen() -> Result<(), String> {
+    run(PowerAction::Lock)
+}
+
+#[tauri::command]
+pub fn sleep_system() -> Result<(), String> {
+    run(PowerAction::Sleep)
+}
+
+#[tauri::command]
+pub fn restart_system() -> Result<(), String> {
+    run(PowerAction::Restart)
+}
+
+/// Shut down now, or after `delay` seconds with a window to cancel it. Scheduling again
+/// replaces the pending shutdown.
+#[tauri::command]
+pub async fn shutdown_system(app: AppHandle, delay: Option<u64>) -> Result<(), String> {
+    let generation = GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    let delay = delay.unwrap_or(0);
+    if delay == 0 {
+        *PENDING.lock().unwrap() = None;
+        tool_windows::close_tool_window(&app, CONFIRM_TOOL);
+        return run(PowerAction::Shutdown);
+    }
+    if delay > MAX_DELAY_DAYS * 86_400 {
+        return Err(format!(
+            "A shutdown can be delayed by up to {} days",
+            MAX_DELAY_DAYS
+        ));
+    }
+
+    *PENDING.lock().unwrap() = Some(Pending {
+        generation,
+        at: Instant::now() + Duration::from_secs(delay),
+    });
+    let _ = app.emit("shutdown-scheduled", scheduled());
+    let opened = tool_windows::open_tool_window(
+        app.clone(),
+        CONFIRM_TOOL.to_string(),
+        Some(ToolWindowOptions {
+            title: Some("Shutdown".to_string()),
+            width: Some(320.0),
+            height: Some(200.0),
+            always_on_top: true,
+        }),
+    )
+    .await;
+    if let Err(e) = opened {
+        // The timer never starts, so don't report a shutdown that won't happen
+        let mut pending = PENDING.lock().unwrap();
+        if pending.as_ref().is_some_and(|p| p.generation == generation) {
+            *pending = None;
+            drop(pending);
+            let _ = app.emit("shutdown-scheduled", None::<ScheduledShutdown>);
+        }
+        return Err(e);
+    }
+
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(Duration::from_secs(delay)).await;
+        let due = {
+            let mut pending = PENDING.lock().unwrap();
+            let due = pending.as_ref().is_some_and(|p| p.generation == generation);
+            if due {
+                *pending = None;
+            }
+            due
+        };
+        if !due {
+            return; // cancelled or rescheduled
+        }
+        tool_windows::close_tool_window(&app, CONFIRM_TOOL);
+        if let Err(e) = run(PowerAction::Shutdown) {
+            log::warn!("Scheduled shutdown failed: {}", e);
+            let _ = app.emit("shutdown-scheduled", None::<ScheduledShutdown>);
+        }
+    });
+    Ok(())
+}
+
+/// Cancel a delayed shutdown
+#[tauri::command]
+pub fn cancel_shutdown(app: AppHandle) {
+    GENERATION.fetch_add(1, Ordering::SeqCst);
+    if PENDING.lock().unwrap().take().is_some() {
+        log::info!("Scheduled shutdown cancelled");
+    }
+    tool_windows::close_tool_window(&app, CONFIRM_TOOL);
+    let _ = app.emit("shutdown-scheduled", None::<ScheduledShutdown>);
+}
+
+#[tauri::command]
+pub fn get_scheduled_shutdown() -> Option<ScheduledShutdown> {
+    scheduled()
+}
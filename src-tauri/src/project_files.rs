@@ -0,0 +1,561 @@
+// Starter .gitignore and .editorconfig files for a project folder
+// Common stacks are embedded so generation works offline; anything else is fetched from
+// gitignore.io and kept in `gitignore_cache.json`, so it is available offline afterwards.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+const CACHE_FILE: &str = "gitignore_cache.json";
+const GITIGNORE_API: &str = "https://www.toptal.com/developers/gitignore/api";
+
+pub struct GitignoreTemplate {
+    pub id: &'static str,
+    pub name: &'static str,
+    body: &'static str,
+}
+
+pub const GITIGNORE_TEMPLATES: &[GitignoreTemplate] = &[
+    GitignoreTemplate {
+        id: "rust",
+        name: "Rust",
+        body: "# Build output\n\
+               debug/\n\
+               target/\n\
+               \n\
+               # Backup files generated by rustfmt\n\
+               **/*.rs.bk\n\
+               \n\
+               # Debugging information generated by MSVC\n\
+               *.pdb",
+    },
+    GitignoreTemplate {
+        id: "node",
+        name: "Node",
+        body: "# Dependencies\n\
+               node_modules/\n\
+               jspm_packages/\n\
+               \n\
+               # Logs\n\
+               logs\n\
+               *.log\n\
+               npm-debug.log*\n\
+               yarn-debug.log*\n\
+               yarn-error.log*\n\
+               pnpm-debug.log*\n\
+               \n\
+               # Build output and caches\n\
+               dist/\n\
+               build/\n\
+               coverage/\n\
+               .cache/\n\
+               .parcel-cache/\n\
+               .next/\n\
+               .nuxt/\n\
+               .vite/\n\
+               *.tsbuildinfo\n\
+               .eslintcache\n\
+               \n\
+               # Environment files\n\
+               .env\n\
+               .env.local\n\
+               .env.*.local",
+    },
+    GitignoreTemplate {
+        id: "python",
+        name: "Python",
+        body: "# Byte-compiled files\n\
+               __pycache__/\n\
+               *.py[cod]\n\
+               *$py.class\n\
+               \n\
+               # Packaging\n\
+               build/\n\
+               dist/\n\
+               *.egg-info/\n\
+               .eggs/\n\
+               wheels/\n\
+               \n\
+               # Virtual environments\n\
+               .venv/\n\
+               venv/\n\
+               env/\n\
+               .env\n\
+               \n\
+               # Test and type-checker caches\n\
+               .pytest_cache/\n\
+               .mypy_cache/\n\
+               .ruff_cache/\n\
+               .tox/\n\
+               .coverage\n\
+               htmlcov/\n\
+               \n\
+               # Jupyter\n\
+               .ipynb_checkpoints",
+    },
+    GitignoreTemplate {
+        id: "go",
+        name: "Go",
+        body: "# Binaries\n\
+               *.exe\n\
+               *.exe~\n\
+               *.dll\n\
+               *.so\n\
+               *.dylib\n\
+               \n\
+               # Test binaries and coverage\n\
+               *.test\n\
+               *.out\n\
+               \n\
+               # Dependency directories\n\
+               vendor/\n\
+               \n\
+               # Workspace file\n\
+               go.work",
+    },
+    GitignoreTemplate {
+        id: "java",
+        name: "Java",
+        body: "# Compiled classes and packages\n\
+               *.class\n\
+               *.jar\n\
+               *.war\n\
+               *.ear\n\
+               \n\
+               # Build tools\n\
+               target/\n\
+               build/\n\
+               .gradle/\n\
+               out/\n\
+               \n\
+               # Crash logs\n\
+               hs_err_pid*\n\
+               replay_pid*",
+    },
+    GitignoreTemplate {
+        id: "dotnet",
+        name: ".NET",
+        body: "# Build output\n\
+               [Bb]in/\n\
+               [Oo]bj/\n\
+               [Dd]ebug/\n\
+               [Rr]elease/\n\
+               \n\
+               # User-specific files\n\
+               *.user\n\
+               *.suo\n\
+               *.userprefs\n\
+               .vs/\n\
+               \n\
+               # NuGet\n\
+               *.nupkg\n\
+               packages/\n\
+               \n\
+               # Test results\n\
+               TestResults/",
+    },
+    GitignoreTemplate {
+        id: "c++",
+        name: "C++",
+        body: "# Object files\n\
+               *.o\n\
+               *.obj\n\
+               \n\
+               # Libraries\n\
+               *.a\n\
+               *.lib\n\
+               *.so\n\
+               *.dylib\n\
+               *.dll\n\
+               \n\
+               # Executables\n\
+               *.exe\n\
+               *.out\n\
+               \n\
+               # CMake\n\
+               build/\n\
+               CMakeCache.txt\n\
+               CMakeFiles/\n\
+               cmake-build-*/",
+    },
+    GitignoreTemplate {
+        id: "unity",
+        name: "Unity",
+        body: "/[Ll]ibrary/\n\
+               /[Tt]emp/\n\
+               /[Oo]bj/\n\
+               /[Bb]uild/\n\
+               /[Bb]uilds/\n\
+               /[Ll]ogs/\n\
+               /[Uu]ser[Ss]ettings/\n\
+               \n\
+               # Generated project files\n\
+               *.csproj\n\
+               *.sln\n\
+               *.pidb.meta\n\
+               \n\
+               # Crash reports\n\
+               sysinfo.txt",
+    },
+    GitignoreTemplate {
+        id: "macos",
+        name: "macOS",
+        body: ".DS_Store\n\
+               .AppleDouble\n\
+               .LSOverride\n\
+               ._*\n\
+               .Spotlight-V100\n\
+               .Trashes",
+    },
+    GitignoreTemplate {
+        id: "windows",
+        name: "Windows",
+        body: "Thumbs.db\n\
+               ehthumbs.db\n\
+               Desktop.ini\n\
+               $RECYCLE.BIN/\n\
+               *.lnk",
+    },
+    GitignoreTemplate {
+        id: "linux",
+        name: "Linux",
+        body: "*~\n\
+               .fuse_hidden*\n\
+               .directory\n\
+               .Trash-*\n\
+               .nfs*",
+    },
+    GitignoreTemplate {
+        id: "visualstudiocode",
+        name: "VS Code",
+        body: ".vscode/*\n\
+               !.vscode/settings.json\n\
+               !.vscode/tasks.json\n\
+               !.vscode/launch.json\n\
+               !.vscode/extensions.json\n\
+               .history/",
+    },
+    GitignoreTemplate {
+        id: "jetbrains",
+        name: "JetBrains",
+        body: ".idea/\n\
+               *.iml\n\
+               *.iws\n\
+               out/",
+    },
+    GitignoreTemplate {
+        id: "vim",
+        name: "Vim",
+        body: "[._]*.s[a-v][a-z]\n\
+               [._]*.sw[a-p]\n\
+               Session.vim\n\
+               tags",
+    },
+];
+
+// Names people type for the embedded templates
+const ALIASES: &[(&str, &str)] = &[
+    ("rs", "rust"),
+    ("cargo", "rust"),
+    ("nodejs", "node"),
+    ("npm", "node"),
+    ("js", "node"),
+    ("javascript", "node"),
+    ("ts", "node"),
+    ("typescript", "node"),
+    ("react", "node"),
+    ("vue", "node"),
+    ("py", "python"),
+    ("golang", "go"),
+    ("kotlin", "java"),
+    ("gradle", "java"),
+    ("maven", "java"),
+    ("csharp", "dotnet"),
+    ("c#", "dotnet"),
+    ("cpp", "c++"),
+    ("c", "c++"),
+    ("cmake", "c++"),
+    ("mac", "macos"),
+    ("osx", "macos"),
+    ("win", "windows"),
+    ("vscode", "visualstudiocode"),
+    ("code", "visualstudiocode"),
+    ("idea", "jetbrains"),
+    ("intellij", "jetbrains"),
+];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TemplateInfo {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GeneratedFile {
+    pub content: String,
+    pub path: Option<String>, // where it was written, if a directory was given
+    pub fetched: Vec<String>, // templates that came from gitignore.io or its cache
+}
+
+fn normalize(technology: &str) -> String {
+    let id = technology.trim().to_lowercase();
+    ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == id)
+        .map(|(_, target)| target.to_string())
+        .unwrap_or(id)
+}
+
+fn embedded(id: &str) -> Option<&'static GitignoreTemplate> {
+    GITIGNORE_TEMPLATES.iter().find(|t| t.id == id)
+}
+
+/// Join template sections gitignore.io-style ("### Rust ###"), leaving out patterns an
+/// earlier section already ignores. Comments and blank lines are kept as they are.
+pub fn combine(sections: &[(String, String)]) -> String {
+    let mut seen = HashSet::new();
+    let mut out = String::from("# Created by BunchaTools\n");
+    for (name, body) in sections {
+        out.push_str(&format!("\n### {} ###\n", name));
+        let mut previous_blank = false;
+        for line in body.lines() {
+            let pattern = line.trim();
+            if !pattern.is_empty() && !pattern.starts_with('#') && !seen.insert(pattern.to_string())
+            {
+                continue;
+            }
+            // Skipping duplicates can leave two blank lines in a row
+            if pattern.is_empty() && previous_blank {
+                continue;
+            }
+            previous_blank = pattern.is_empty();
+            out.push_str(line.trim_end());
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Embedded sections for `technologies`, plus the ids that aren't embedded
+pub fn embedded_sections(technologies: &[String]) -> (Vec<(String, String)>, Vec<String>) {
+    let mut sections = Vec::new();
+    let mut missing = Vec::new();
+    let mut requested = HashSet::new();
+    for technology in technologies {
+        let id = normalize(technology);
+        if id.is_empty() || !requested.insert(id.clone()) {
+            continue;
+        }
+        match embedded(&id) {
+            Some(template) => sections.push((template.name.to_string(), template.body.to_string())),
+            None => missing.push(id),
+        }
+    }
+    (sections, missing)
+}
+
+// Split a gitignore.io response back into per-template bodies, keyed by id
+fn parse_api_response(text: &str) -> HashMap<String, String> {
+    let mut templates = HashMap::new();
+    let mut current: Option<(String, Vec<&str>)> = None;
+    for line in text.lines() {
+        if let Some(name) = line
+            .strip_prefix("### ")
+            .and_then(|rest| rest.strip_suffix(" ###"))
+        {
+            if let Some((id, body)) = current.take() {
+                templates.insert(id, body.join("\n").trim().to_string());
+            }
+            current = Some((name.to_lowercase(), Vec::new()));
+        } else if line.starts_with("# End of ") {
+            break;
+        } else if let Some((_, body)) = current.as_mut() {
+            body.push(line);
+        }
+    }
+    if let Some((id, body)) = current {
+        templates.insert(id, body.join("\n").trim().to_string());
+    }
+    templates
+}
+
+async fn fetch_templates(ids: &[String]) -> Result<HashMap<String, String>, String> {
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let list: Vec<String> = ids
+        .iter()
+        .map(|id| urlencoding::encode(id).into_owned())
+        .collect();
+    let url = format!("{}/{}", GITIGNORE_API, list.join(","));
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach gitignore.io: {}", e))?;
+    // Unknown names come back as a 404 with an error comment
+    let text = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read gitignore.io response: {}", e))?;
+    if let Some(line) = text.lines().find(|line| line.starts_with("#!! ERROR:")) {
+        return Err(line.trim_start_matches("#!! ERROR:").trim().to_string());
+    }
+    Ok(parse_api_response(&text))
+}
+
+/// Write `content` as `file_name` in `directory`, which must pass the path policy
+fn write_into(
+    app: &AppHandle,
+    directory: &str,
+    file_name: &str,
+    content: &str,
+    overwrite: bool,
+) -> Result<String, String> {
+    let dir = crate::path_policy::validate_output_dir(app, directory)?;
+    if !dir.is_dir() {
+        return Err(format!("Not a folder: {}", dir.display()));
+    }
+    let path = dir.join(file_name);
+    if path.exists() && !overwrite {
+        return Err(format!("{} already has a {}", dir.display(), file_name));
+    }
+    fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", file_name, e))?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+#[tauri::command]
+pub fn list_gitignore_templates() -> Vec<TemplateInfo> {
+    GITIGNORE_TEMPLATES
+        .iter()
+        .map(|t| TemplateInfo {
+            id: t.id.to_string(),
+            name: t.name.to_string(),
+        })
+        .collect()
+}
+
+#[tauri::command]
+pub async fn generate_gitignore(
+    app: AppHandle,
+    technologies: Vec<String>,
+    directory: Option<String>,
+    overwrite: Option<bool>,
+) -> Result<GeneratedFile, String> {
+    let (mut sections, missing) = embedded_sections(&technologies);
+    if sections.is_empty() && missing.is_empty() {
+        return Err("Name at least one language, framework or editor".to_string());
+    }
+
+    let mut fetched = Vec::new();
+    if !missing.is_empty() {
+        let mut cache: HashMap<String, String> = crate::load_json_file(&app, CACHE_FILE);
+        let uncached: Vec<String> = missing
+            .iter()
+            .filter(|id| !cache.contains_key(*id))
+            .cloned()
+            .collect();
+        if !uncached.is_empty() {
+            let downloaded = fetch_templates(&uncached)
+                .await
+                .map_err(|e| format!("No offline template for {} ({})", uncached.join(", "), e))?;
+            cache.extend(downloaded);
+            if let Err(e) = crate::save_json_file(&app, CACHE_FILE, &cache) {
+                log::warn!("Failed to cache gitignore templates: {}", e);
+            }
+        }
+        for id in missing {
+            let body = cache
+                .get(&id)
+                .ok_or_else(|| format!("gitignore.io has no template for {}", id))?;
+            sections.push((id.clone(), body.clone()));
+            fetched.push(id);
+        }
+    }
+
+    let content = combine(&sections);
+    let path = match directory {
+        Some(dir) => Some(write_into(
+            &app,
+            &dir,
+            ".gitignore",
+            &content,
+            overwrite.unwrap_or(false),
+        )?),
+        None => None,
+    };
+    Ok(GeneratedFile {
+        content,
+        path,
+        fetched,
+    })
+}
+
+/// Starter .editorconfig: UTF-8, LF, 2-space indents, plus the usual per-language overrides
+pub fn editorconfig(technologies: &[String]) -> String {
+    let ids: HashSet<String> = technologies.iter().map(|t| normalize(t)).collect();
+    let mut out = String::from(
+        "# https://editorconfig.org\n\
+         root = true\n\
+         \n\
+         [*]\n\
+         charset = utf-8\n\
+         end_of_line = lf\n\
+         indent_style = space\n\
+         indent_size = 2\n\
+         insert_final_newline = true\n\
+         trim_trailing_whitespace = true\n",
+    );
+    let overrides: &[(&str, &str, &str)] = &[
+        ("rust", "*.rs", "indent_size = 4"),
+        ("python", "*.py", "indent_size = 4"),
+        ("java", "*.{java,kt,kts}", "indent_size = 4"),
+        ("dotnet", "*.{cs,csx,vb}", "indent_size = 4"),
+        ("c++", "*.{c,cc,cpp,h,hpp}", "indent_size = 4"),
+        ("go", "*.go", "indent_style = tab"),
+    ];
+    for (id, glob, rule) in overrides {
+        if ids.contains(*id) {
+            out.push_str(&format!("\n[{}]\n{}\n", glob, rule));
+        }
+    }
+    out.push_str(
+        "\n[*.md]\n\
+         trim_trailing_whitespace = false\n\
+         \n\
+         [Makefile]\n\
+         indent_style = tab\n",
+    );
+    if ids.contains("dotnet") || ids.contains("windows") {
+        out.push_str("\n[*.{bat,cmd,sln}]\nend_of_line = crlf\n");
+    }
+    out
+}
+
+#[tauri::command]
+pub async fn generate_editorconfig(
+    app: AppHandle,
+    technologies: Vec<String>,
+    directory: Option<String>,
+    overwrite: Option<bool>,
+) -> Result<GeneratedFile, String> {
+    let content = editorconfig(&technologies);
+    let path = match directory {
+        Some(dir) => Some(write_into(
+            &app,
+            &dir,
+            ".editorconfig",
+            &content,
+            overwrite.unwrap_or(false),
+        )?),
+        None => None,
+    };
+    Ok(GeneratedFile {
+        content,
+        path,
+        fetched: Vec::new(),
+    })
+}
@@ -0,0 +1,954 @@
+// QR code decoder for screen captures
+// Codes on screen are flat and axis-aligned or close to it, so this skips what camera decoders
+// need (perspective correction, alignment pattern search) and works in three steps:
+//   1. binarize the image and find the finder patterns (the three big squares) by scanning
+//      rows for the 1:1:3:1:1 dark/light run ratio, cross-checked vertically,
+//   2. for every plausible triple of finders, sample the module grid with an affine mapping,
+//   3. read the format/version info, unmask, de-interleave the blocks, fix errors with
+//      Reed-Solomon and decode the numeric / alphanumeric / byte / ECI segments.
+// Light-on-dark and mirrored codes are handled. Kanji segments decode to U+FFFD.
+
+use image::{GrayImage, RgbaImage};
+
+// ============================================================================
+// Tables (ISO/IEC 18004), indexed by [error correction level][version]
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EcLevel {
+    L,
+    M,
+    Q,
+    H,
+}
+
+impl EcLevel {
+    fn from_format_bits(bits: u32) -> EcLevel {
+        match bits & 3 {
+            1 => EcLevel::L,
+            0 => EcLevel::M,
+            3 => EcLevel::Q,
+            _ => EcLevel::H,
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+#[rustfmt::skip]
+const ECC_CODEWORDS_PER_BLOCK: [[u8; 41]; 4] = [
+    [0, 7, 10, 15, 20, 26, 18, 20, 24, 30, 18, 20, 24, 26, 30, 22, 24, 28, 30, 28, 28, 28, 28, 30, 30, 26, 28, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30],
+    [0, 10, 16, 26, 18, 24, 16, 18, 22, 22, 26, 30, 22, 22, 24, 24, 28, 28, 26, 26, 26, 26, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28],
+    [0, 13, 22, 18, 26, 18, 24, 18, 22, 20, 24, 28, 26, 24, 20, 30, 24, 28, 28, 26, 30, 28, 30, 30, 30, 30, 28, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30],
+    [0, 17, 28, 22, 16, 22, 28, 26, 26, 24, 28, 24, 28, 22, 24, 24, 30, 28, 28, 26, 28, 30, 24, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30],
+];
+
+#[rustfmt::skip]
+const ERROR_CORRECTION_BLOCKS: [[u8; 41]; 4] = [
+    [0, 1, 1, 1, 1, 1, 2, 2, 2, 2, 4, 4, 4, 4, 4, 6, 6, 6, 6, 7, 8, 8, 9, 9, 10, 12, 12, 12, 13, 14, 15, 16, 17, 18, 19, 19, 20, 21, 22, 24, 25],
+    [0, 1, 1, 1, 2, 2, 4, 4, 4, 5, 5, 5, 8, 9, 9, 10, 10, 11, 13, 14, 16, 17, 17, 18, 20, 21, 23, 25, 26, 28, 29, 31, 33, 35, 37, 38, 40, 43, 45, 47, 49],
+    [0, 1, 1, 2, 2, 4, 4, 6, 6, 8, 8, 8, 10, 12, 16, 12, 17, 16, 18, 21, 20, 23, 23, 25, 27, 29, 34, 34, 35, 38, 40, 43, 45, 48, 51, 53, 56, 59, 62, 65, 68],
+    [0, 1, 1, 2, 4, 4, 4, 5, 6, 8, 8, 11, 11, 16, 16, 18, 16, 19, 21, 25, 25, 25, 34, 30, 32, 35, 37, 40, 42, 45, 48, 51, 54, 57, 60, 63, 66, 70, 74, 77, 81],
+];
+
+const ALPHANUMERIC: &[u8; 45] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:";
+
+/// Codeword count of a version (data and error correction)
+fn raw_codewords(version: usize) -> usize {
+    let mut modules = (16 * version + 128) * version + 64;
+    if version >= 2 {
+        let aligns = version / 7 + 2;
+        modules -= (25 * aligns - 10) * aligns - 55;
+        if version >= 7 {
+            modules -= 36;
+        }
+    }
+    modules / 8
+}
+
+fn alignment_positions(version: usize) -> Vec<usize> {
+    if version == 1 {
+        return Vec::new();
+    }
+    let count = version / 7 + 2;
+    let size = version * 4 + 17;
+    let step = if version == 32 {
+        26
+    } else {
+        (version * 4 + count * 2 + 1) / (count * 2 - 2) * 2
+    };
+    let mut positions = vec![6; count];
+    for (i, position) in positions.iter_mut().enumerate().skip(1) {
+        *position = size - 7 - (count - 1 - i) * step;
+    }
+    positions
+}
+
+fn format_codeword(data: u32) -> u32 {
+    let mut rem = data;
+    for _ in 0..10 {
+        rem = (rem << 1) ^ ((rem >> 9) * 0x537);
+    }
+    ((data << 10) | rem) ^ 0x5412
+}
+
+fn version_codeword(version: u32) -> u32 {
+    let mut rem = version;
+    for _ in 0..12 {
+        rem = (rem << 1) ^ ((rem >> 11) * 0x1F25);
+    }
+    (version << 12) | rem
+}
+
+/// The valid codeword closest to `bits`, if within the correctable distance
+fn closest<I: Iterator<Item = (u32, u32)>>(bits: u32, codes: I, max_distance: u32) -> Option<u32> {
+    codes
+        .map(|(value, code)| (value, (code ^ bits).count_ones()))
+        .min_by_key(|&(_, distance)| distance)
+        .filter(|&(_, distance)| distance <= max_distance)
+        .map(|(value, _)| value)
+}
+
+// ============================================================================
+// Reed-Solomon over GF(256), primitive polynomial 0x11D, first root α^0
+// ============================================================================
+
+struct Galois {
+    exp: [u8; 512],
+    log: [u8; 256],
+}
+
+impl Galois {
+    fn new() -> Galois {
+        let mut exp = [0u8; 512];
+        let mut log = [0u8; 256];
+        let mut x: u16 = 1;
+        for (i, value) in exp.iter_mut().enumerate().take(255) {
+            *value = x as u8;
+            log[x as usize] = i as u8;
+            x <<= 1;
+            if x & 0x100 != 0 {
+                x ^= 0x11D;
+            }
+        }
+        for i in 255..512 {
+            exp[i] = exp[i - 255];
+        }
+        Galois { exp, log }
+    }
+
+    fn mul(&self, a: u8, b: u8) -> u8 {
+        if a == 0 || b == 0 {
+            0
+        } else {
+            self.exp[self.log[a as usize] as usize + self.log[b as usize] as usize]
+        }
+    }
+
+    fn div(&self, a: u8, b: u8) -> u8 {
+        if a == 0 {
+            0
+        } else {
+            self.exp[self.log[a as usize] as usize + 255 - self.log[b as usize] as usize]
+        }
+    }
+
+    fn pow(&self, exponent: usize) -> u8 {
+        self.exp[exponent % 255]
+    }
+
+    /// Evaluate a polynomial with coefficients from the lowest degree up
+    fn eval(&self, poly: &[u8], x: u8) -> u8 {
+        poly.iter()
+            .rev()
+            .fold(0, |acc, &coefficient| self.mul(acc, x) ^ coefficient)
+    }
+}
+
+/// Correct `block` (data then error correction codewords) in place; returns the number of
+/// codewords fixed, or None if there are too many errors
+pub fn correct_errors(block: &mut [u8], ecc_len: usize) -> Option<usize> {
+    let gf = Galois::new();
+    let n = block.len();
+    // The first codeword is the highest power of x
+    let syndromes: Vec<u8> = (0..ecc_len)
+        .map(|i| {
+            let x = gf.pow(i);
+            block.iter().fold(0, |acc, &c| gf.mul(acc, x) ^ c)
+        })
+        .collect();
+    if syndromes.iter().all(|&s| s == 0) {
+        return Some(0);
+    }
+
+    // Berlekamp-Massey: error locator polynomial, lowest degree first
+    let mut locator = vec![1u8];
+    let mut previous = vec![1u8];
+    let (mut errors, mut shift, mut last_discrepancy) = (0usize, 1usize, 1u8);
+    for step in 0..ecc_len {
+        let mut discrepancy = syndromes[step];
+        for i in 1..=errors.min(locator.len() - 1) {
+            discrepancy ^= gf.mul(locator[i], syndromes[step - i]);
+        }
+        if discrepancy == 0 {
+            shift += 1;
+            continue;
+        }
+        let scale = gf.div(discrepancy, last_discrepancy);
+        let mut next = locator.clone();
+        if next.len() < previous.len() + shift {
+            next.resize(previous.len() + shift, 0);
+        }
+        for (i, &coefficient) in previous.iter().enumerate() {
+            next[i + shift] ^= gf.mul(scale, coefficient);
+        }
+        if 2 * errors <= step {
+            errors = step + 1 - errors;
+            previous = std::mem::replace(&mut locator, next);
+            last_discrepancy = discrepancy;
+            shift = 1;
+        } else {
+            locator = next;
+            shift += 1;
+        }
+    }
+    while locator.len() > 1 && locator[locator.len() - 1] == 0 {
+        locator.pop();
+    }
+    if errors * 2 > ecc_len || locator.len() - 1 != errors {
+        return None;
+    }
+
+    // Chien search: codeword k sits at x^(n-1-k); it's wrong if Λ(X^-1) = 0
+    let positions: Vec<usize> = (0..n)
+        .filter(|&k| gf.eval(&locator, gf.pow(255 - (n - 1 - k) % 255)) == 0)
+        .collect();
+    if positions.len() != errors {
+        return None;
+    }
+
+    // Forney: e = X * Ω(X^-1) / Λ'(X^-1), with Ω = S * Λ mod x^ecc_len
+    let mut evaluator = vec![0u8; ecc_len];
+    for (i, &s) in syndromes.iter().enumerate() {
+        for (j, &l) in locator.iter().enumerate() {
+            if i + j < ecc_len {
+                evaluator[i + j] ^= gf.mul(s, l);
+            }
+        }
+    }
+    // Formal derivative: odd powers survive in characteristic 2
+    let derivative: Vec<u8> = locator
+        .iter()
+        .enumerate()
+        .skip(1)
+        .map(|(i, &c)| if i % 2 == 1 { c } else { 0 })
+        .collect();
+    for &k in &positions {
+        let x = gf.pow(n - 1 - k);
+        let x_inverse = gf.pow(255 - (n - 1 - k) % 255);
+        let denominator = gf.eval(&derivative, x_inverse);
+        if denominator == 0 {
+            return None;
+        }
+        let magnitude = gf.mul(x, gf.div(gf.eval(&evaluator, x_inverse), denominator));
+        block[k] ^= magnitude;
+    }
+    Some(errors)
+}
+
+// ============================================================================
+// Module grid -> text
+// ============================================================================
+
+/// A square grid of modules, `true` for dark
+pub type Matrix = Vec<Vec<bool>>;
+
+fn function_modules(version: usize) -> Matrix {
+    let size = version * 4 + 17;
+    let mut function = vec![vec![false; size]; size];
+    let mut fill = |x0: usize, y0: usize, width: usize, height: usize| {
+        for row in function.iter_mut().skip(y0).take(height) {
+            for cell in row.iter_mut().skip(x0).take(width) {
+                *cell = true;
+            }
+        }
+    };
+    // Finders with separators and format info
+    fill(0, 0, 9, 9);
+    fill(size - 8, 0, 8, 9);
+    fill(0, size - 8, 9, 8);
+    // Timing patterns
+    fill(6, 0, 1, size);
+    fill(0, 6, size, 1);
+    // Alignment patterns, except where they'd overlap the finders
+    let aligns = alignment_positions(version);
+    let last = aligns.len().saturating_sub(1);
+    for (i, &ay) in aligns.iter().enumerate() {
+        for (j, &ax) in aligns.iter().enumerate() {
+            let corner = (i == 0 || i == last) && (j == 0 || j == last);
+            if corner && !(i == last && j == last) {
+                continue;
+            }
+            fill(ax - 2, ay - 2, 5, 5);
+        }
+    }
+    // Version info
+    if version >= 7 {
+        fill(size - 11, 0, 3, 6);
+        fill(0, size - 11, 6, 3);
+    }
+    function
+}
+
+fn mask_bit(mask: u32, x: usize, y: usize) -> bool {
+    match mask {
+        0 => (x + y) % 2 == 0,
+        1 => y % 2 == 0,
+        2 => x % 3 == 0,
+        3 => (x + y) % 3 == 0,
+        4 => (x / 3 + y / 2) % 2 == 0,
+        5 => x * y % 2 + x * y % 3 == 0,
+        6 => (x * y % 2 + x * y % 3) % 2 == 0,
+        _ => ((x + y) % 2 + x * y % 3) % 2 == 0,
+    }
+}
+
+/// Error correction level and mask, from whichever copy of the format info reads best
+fn read_format(matrix: &Matrix) -> Option<(EcLevel, u32)> {
+    let size = matrix.len();
+    let bit = |x: usize, y: usize| matrix[y][x] as u32;
+
+    let mut first = 0;
+    for i in 0..6 {
+        first |= bit(8, i) << i;
+    }
+    first |= bit(8, 7) << 6 | bit(8, 8) << 7 | bit(7, 8) << 8;
+    for i in 9..15 {
+        first |= bit(14 - i, 8) << i;
+    }
+    let mut second = 0;
+    for i in 0..8 {
+        second |= bit(size - 1 - i, 8) << i;
+    }
+    for i in 8..15 {
+        second |= bit(8, size - 15 + i) << i;
+    }
+
+    let codes = || (0..32).map(|data| (data, format_codeword(data)));
+    let data = closest(first, codes(), 3).or_else(|| closest(second, codes(), 3))?;
+    Some((EcLevel::from_format_bits(data >> 3), data & 7))
+}
+
+/// Version from the version info blocks (versions 7 and up), checked against the size
+fn read_version(matrix: &Matrix) -> Option<usize> {
+    let size = matrix.len();
+    if !(21..=177).contains(&size) || (size - 17) % 4 != 0 {
+        return None;
+    }
+    let from_size = (size - 17) / 4;
+    if from_size < 7 {
+        return Some(from_size);
+    }
+
+    let (mut top_right, mut bottom_left) = (0, 0);
+    for i in 0..18 {
+        let (a, b) = (size - 11 + i % 3, i / 3);
+        top_right |= (matrix[b][a] as u32) << i;
+        bottom_left |= (matrix[a][b] as u32) << i;
+    }
+    let codes = || (7..=40).map(|version| (version, version_codeword(version)));
+    closest(top_right, codes(), 3)
+        .or_else(|| closest(bottom_left, codes(), 3))
+        .map(|version| version as usize)
+        .filter(|&version| version == from_size)
+}
+
+/// Codewords in placement order, with the mask removed
+fn read_codewords(matrix: &Matrix, version: usize, mask: u32) -> Vec<u8> {
+    let size = matrix.len();
+    let function = function_modules(version);
+    let total = raw_codewords(version);
+    let mut codewords = vec![0u8; total];
+    let mut bit = 0;
+
+    let mut right = size as isize - 1;
+    while right >= 1 {
+        if right == 6 {
+            right = 5;
+        }
+        let upward = (right + 1) & 2 == 0;
+        for vertical in 0..size {
+            let y = if upward {
+                size - 1 - vertical
+            } else {
+                vertical
+            };
+            for j in 0..2 {
+                let x = (right - j) as usize;
+                if function[y][x] || bit >= total * 8 {
+                    continue;
+                }
+                if matrix[y][x] ^ mask_bit(mask, x, y) {
+                    codewords[bit / 8] |= 0x80 >> (bit % 8);
+                }
+                bit += 1;
+            }
+        }
+        right -= 2;
+    }
+    codewords
+}
+
+/// Split interleaved codewords into blocks, correct them and join the data codewords
+fn correct_blocks(codewords: &[u8], version: usize, level: EcLevel) -> Option<Vec<u8>> {
+    let blocks = ERROR_CORRECTION_BLOCKS[level.index()][version] as usize;
+    let ecc_len = ECC_CODEWORDS_PER_BLOCK[level.index()][version] as usize;
+    let total = codewords.len();
+    let short_blocks = blocks - total % blocks;
+    let short_len = total / blocks;
+    let short_data = short_len - ecc_len;
+
+    let mut split: Vec<Vec<u8>> = (0..blocks)
+        .map(|j| Vec::with_capacity(short_len + (j >= short_blocks) as usize))
+        .collect();
+    let mut next = codewords.iter();
+    for i in 0..=short_len {
+        for (j, block) in split.iter_mut().enumerate() {
+            // Short blocks have one data codeword less
+            if i == short_data && j < short_blocks {
+                continue;
+            }
+            block.push(*next.next()?);
+        }
+    }
+
+    let mut data = Vec::new();
+    for block in &mut split {
+        correct_errors(block, ecc_len)?;
+        data.extend_from_slice(&block[..block.len() - ecc_len]);
+    }
+    Some(data)
+}
+
+struct BitReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl BitReader<'_> {
+    fn remaining(&self) -> usize {
+        self.data.len() * 8 - self.position
+    }
+
+    fn read(&mut self, count: usize) -> Option<u32> {
+        if count > self.remaining() {
+            return None;
+        }
+        let mut value = 0;
+        for _ in 0..count {
+            let byte = self.data[self.position / 8];
+            value = (value << 1) | ((byte >> (7 - self.position % 8)) & 1) as u32;
+            self.position += 1;
+        }
+        Some(value)
+    }
+}
+
+fn decode_bytes(bytes: &[u8], eci: Option<u32>) -> String {
+    match eci {
+        // ISO-8859-1
+        Some(1) | Some(3) => bytes.iter().map(|&b| b as char).collect(),
+        // UTF-8 is what generators use in practice, whatever the spec's Latin-1 default says
+        _ => match std::str::from_utf8(bytes) {
+            Ok(text) => text.to_string(),
+            Err(_) => bytes.iter().map(|&b| b as char).collect(),
+        },
+    }
+}
+
+/// Decode the segments of the corrected data codewords
+fn decode_segments(data: &[u8], version: usize) -> Option<String> {
+    let group = match version {
+        1..=9 => 0,
+        10..=26 => 1,
+        _ => 2,
+    };
+    let mut reader = BitReader { data, position: 0 };
+    let mut text = String::new();
+    let mut bytes = Vec::new();
+    let mut eci = None;
+
+    while reader.remaining() >= 4 {
+        let mode = reader.read(4)?;
+        // Byte segments can split multi-byte characters; join consecutive ones first
+        if mode != 4 && !bytes.is_empty() {
+            text.push_str(&decode_bytes(&bytes, eci));
+            bytes.clear();
+        }
+        match mode {
+            0 => break, // terminator
+            1 => {
+                let mut count = reader.read([10, 12, 14][group])? as usize;
+                while count > 0 {
+                    let digits = count.min(3);
+                    let value = reader.read([0, 4, 7, 10][digits])?;
+                    if value >= 10u32.pow(digits as u32) {
+                        return None;
+                    }
+                    text.push_str(&format!("{:0width$}", value, width = digits));
+                    count -= digits;
+                }
+            }
+            2 => {
+                let mut count = reader.read([9, 11, 13][group])? as usize;
+                while count >= 2 {
+                    let value = reader.read(11)? as usize;
+                    let (a, b) = (value / 45, value % 45);
+                    if a >= 45 {
+                        return None;
+                    }
+                    text.push(ALPHANUMERIC[a] as char);
+                    text.push(ALPHANUMERIC[b] as char);
+                    count -= 2;
+                }
+                if count == 1 {
+                    let value = reader.read(6)? as usize;
+                    text.push(*ALPHANUMERIC.get(value)? as char);
+                }
+            }
+            4 => {
+                let count = reader.read([8, 16, 16][group])?;
+                for _ in 0..count {
+                    bytes.push(reader.read(8)? as u8);
+                }
+            }
+            7 => {
+                let first = reader.read(8)?;
+                eci = Some(if first & 0x80 == 0 {
+                    first
+                } else if first & 0xC0 == 0x80 {
+                    ((first & 0x3F) << 8) | reader.read(8)?
+                } else {
+                    ((first & 0x1F) << 16) | reader.read(16)?
+                });
+            }
+            8 => {
+                let count = reader.read([8, 10, 12][group])?;
+                reader.read(13 * count as usize)?;
+                text.extend(std::iter::repeat('\u{FFFD}').take(count as usize));
+            }
+            3 => {
+                reader.read(16)?; // structured append header
+            }
+            5 => {}
+            9 => {
+                reader.read(8)?; // FNC1 application indicator
+            }
+            _ => return None,
+        }
+    }
+    if !bytes.is_empty() {
+        text.push_str(&decode_bytes(&bytes, eci));
+    }
+    Some(text)
+}
+
+/// Decode a sampled module grid (without the quiet zone)
+pub fn decode_matrix(matrix: &Matrix) -> Option<String> {
+    let version = read_version(matrix)?;
+    let (level, mask) = read_format(matrix)?;
+    let codewords = read_codewords(matrix, version, mask);
+    let data = correct_blocks(&codewords, version, level)?;
+    decode_segments(&data, version)
+}
+
+// ============================================================================
+// Image -> module grid
+// ============================================================================
+
+/// Dark / light pixels
+struct Bitmap {
+    width: usize,
+    height: usize,
+    dark: Vec<bool>,
+}
+
+impl Bitmap {
+    fn get(&self, x: isize, y: isize) -> Option<bool> {
+        if x < 0 || y < 0 || x as usize >= self.width || y as usize >= self.height {
+            return None;
+        }
+        Some(self.dark[y as usize * self.width + x as usize])
+    }
+
+    fn inverted(&self) -> Bitmap {
+        Bitmap {
+            width: self.width,
+            height: self.height,
+            dark: self.dark.iter().map(|d| !d).collect(),
+        }
+    }
+}
+
+/// Threshold between the two luminance peaks
+fn otsu_threshold(gray: &GrayImage) -> u8 {
+    let mut histogram = [0u64; 256];
+    for pixel in gray.pixels() {
+        histogram[pixel.0[0] as usize] += 1;
+    }
+    let total: u64 = histogram.iter().sum();
+    let sum: f64 = histogram
+        .iter()
+        .enumerate()
+        .map(|(i, &count)| i as f64 * count as f64)
+        .sum();
+
+    let (mut best, mut best_variance) = (128u8, 0.0);
+    let (mut background_weight, mut background_sum) = (0u64, 0.0);
+    for (threshold, &count) in histogram.iter().enumerate() {
+        background_weight += count;
+        if background_weight == 0 {
+            continue;
+        }
+        let foreground_weight = total - background_weight;
+        if foreground_weight == 0 {
+            break;
+        }
+        background_sum += threshold as f64 * count as f64;
+        let background_mean = background_sum / background_weight as f64;
+        let foreground_mean = (sum - background_sum) / foreground_weight as f64;
+        let variance = background_weight as f64
+            * foreground_weight as f64
+            * (background_mean - foreground_mean).powi(2);
+        if variance > best_variance {
+            best_variance = variance;
+            best = threshold as u8;
+        }
+    }
+    best
+}
+
+fn binarize_global(gray: &GrayImage) -> Bitmap {
+    let threshold = otsu_threshold(gray);
+    Bitmap {
+        width: gray.width() as usize,
+        height: gray.height() as usize,
+        dark: gray.pixels().map(|p| p.0[0] <= threshold).collect(),
+    }
+}
+
+/// Dark where darker than the neighbourhood, for codes on busy or uneven backgrounds
+fn binarize_local(gray: &GrayImage) -> Bitmap {
+    let (width, height) = (gray.width() as usize, gray.height() as usize);
+    let radius = (width.min(height) / 16).max(8);
+    // Summed-area table, one row and column larger
+    let mut integral = vec![0u64; (width + 1) * (height + 1)];
+    for y in 0..height {
+        let mut row = 0u64;
+        for x in 0..width {
+            row += gray.get_pixel(x as u32, y as u32).0[0] as u64;
+            integral[(y + 1) * (width + 1) + x + 1] = integral[y * (width + 1) + x + 1] + row;
+        }
+    }
+    let mut dark = vec![false; width * height];
+    for y in 0..height {
+        let (y0, y1) = (y.saturating_sub(radius), (y + radius + 1).min(height));
+        for x in 0..width {
+            let (x0, x1) = (x.saturating_sub(radius), (x + radius + 1).min(width));
+            let sum = integral[y1 * (width + 1) + x1] + integral[y0 * (width + 1) + x0]
+                - integral[y0 * (width + 1) + x1]
+                - integral[y1 * (width + 1) + x0];
+            let mean = sum / ((x1 - x0) * (y1 - y0)) as u64;
+            let value = gray.get_pixel(x as u32, y as u32).0[0] as u64;
+            dark[y * width + x] = value + 8 < mean;
+        }
+    }
+    Bitmap {
+        width,
+        height,
+        dark,
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Finder {
+    x: f64,
+    y: f64,
+    module: f64, // estimated module size in pixels
+    hits: u32,   // rows that confirmed it
+}
+
+/// Run lengths look like dark 1 : light 1 : dark 3 : light 1 : dark 1
+fn is_finder_ratio(runs: &[usize; 5]) -> bool {
+    let total: usize = runs.iter().sum();
+    if total < 7 || runs.contains(&0) {
+        return false;
+    }
+    let module = total as f64 / 7.0;
+    let tolerance = module / 2.0;
+    [0, 1, 3, 4]
+        .iter()
+        .all(|&i| (runs[i] as f64 - module).abs() < tolerance)
+        && (runs[2] as f64 - 3.0 * module).abs() < tolerance * 3.0
+}
+
+/// Measure the pattern along a line through (x, y) stepping by (dx, dy); returns the center
+/// offset along the line and the pattern's total length
+fn cross_check(
+    bitmap: &Bitmap,
+    x: f64,
+    y: f64,
+    dx: isize,
+    dy: isize,
+    max: usize,
+) -> Option<(f64, usize)> {
+    let (x, y) = (x.round() as isize, y.round() as isize);
+    let at = |i: isize| bitmap.get(x + dx * i, y + dy * i);
+    if at(0) != Some(true) {
+        return None;
+    }
+    let mut runs = [0usize; 5];
+
+    // Back from the center: dark, light, dark
+    let mut i = 0;
+    for (state, expected) in [(2usize, true), (1, false), (0, true)] {
+        while at(i) == Some(expected) && runs[state] <= max {
+            runs[state] += 1;
+            i -= 1;
+        }
+        if runs[state] == 0 || runs[state] > max {
+            return None;
+        }
+    }
+    let start = i + 1;
+    // Forward from the center: dark (rest of the center), light, dark
+    let mut i = 1;
+    for (state, expected) in [(2usize, true), (3, false), (4, true)] {
+        let mut count = 0;
+        while at(i) == Some(expected) && count <= max {
+            count += 1;
+            i += 1;
+        }
+        runs[state] += count;
+        if (state != 2 && count == 0) || runs[state] > max {
+            return None;
+        }
+    }
+
+    if !is_finder_ratio(&runs) {
+        return None;
+    }
+    let total: usize = runs.iter().sum();
+    let center = start as f64 + runs[0] as f64 + runs[1] as f64 + runs[2] as f64 / 2.0;
+    Some((center, total))
+}
+
+fn find_finders(bitmap: &Bitmap) -> Vec<Finder> {
+    let mut finders: Vec<Finder> = Vec::new();
+    let mut add = |finder: Finder| {
+        let existing = finders.iter_mut().find(|f| {
+            (f.x - finder.x).abs() < f.module * 2.0 && (f.y - finder.y).abs() < f.module * 2.0
+        });
+        match existing {
+            Some(f) => {
+                let n = f.hits as f64;
+                f.x = (f.x * n + finder.x) / (n + 1.0);
+                f.y = (f.y * n + finder.y) / (n + 1.0);
+                f.module = (f.module * n + finder.module) / (n + 1.0);
+                f.hits += 1;
+            }
+            None => finders.push(finder),
+        }
+    };
+
+    for y in 0..bitmap.height {
+        // Runs alternate dark (even states) and light (odd states)
+        let mut runs = [0usize; 5];
+        let mut state = 0;
+        for x in 0..=bitmap.width {
+            let dark = x < bitmap.width && bitmap.dark[y * bitmap.width + x];
+            if dark == (state % 2 == 0) {
+                runs[state] += 1;
+            } else if state < 4 {
+                state += 1;
+                runs[state] = 1;
+            } else {
+                // The last dark run ended at x
+                if is_finder_ratio(&runs) {
+                    let total: usize = runs.iter().sum();
+                    let center = (x - runs[4] - runs[3]) as f64 - runs[2] as f64 / 2.0;
+                    if let Some(finder) = confirm(bitmap, center, y as f64, total) {
+                        add(finder);
+                    }
+                }
+                runs = [runs[2], runs[3], runs[4], 1, 0];
+                state = 3;
+            }
+        }
+    }
+    finders
+}
+
+/// Cross-check a row hit vertically, then horizontally again through the refined center
+fn confirm(bitmap: &Bitmap, x: f64, y: f64, row_total: usize) -> Option<Finder> {
+    let max = row_total * 2;
+    let (center_y, column_total) = cross_check(bitmap, x, y, 0, 1, max)?;
+    let y = y.round() + center_y;
+    if (column_total as f64) < row_total as f64 * 0.6
+        || (column_total as f64) > row_total as f64 * 1.6
+    {
+        return None;
+    }
+    let (center_x, row_total) = cross_check(bitmap, x, y, 1, 0, max)?;
+    let x = x.round() + center_x;
+    Some(Finder {
+        x,
+        y,
+        module: (row_total + column_total) as f64 / 14.0,
+        hits: 1,
+    })
+}
+
+fn distance(a: &Finder, b: &Finder) -> f64 {
+    ((a.x - b.x).powi(2) + (a.y - b.y).powi(2)).sqrt()
+}
+
+/// Top-left, top-right and bottom-left finder of a plausible code, or None
+fn arrange(a: Finder, b: Finder, c: Finder) -> Option<(Finder, Finder, Finder)> {
+    let modules = [a.module, b.module, c.module];
+    let (smallest, largest) = modules
+        .iter()
+        .fold((f64::MAX, 0.0f64), |(lo, hi), &m| (lo.min(m), hi.max(m)));
+    if largest > smallest * 1.5 {
+        return None;
+    }
+    // The corner finder is opposite the longest side
+    let (ab, bc, ca) = (distance(&a, &b), distance(&b, &c), distance(&c, &a));
+    let (corner, mut right, mut bottom) = if bc >= ab && bc >= ca {
+        (a, b, c)
+    } else if ca >= ab && ca >= bc {
+        (b, c, a)
+    } else {
+        (c, a, b)
+    };
+    let (leg1, leg2) = (distance(&corner, &right), distance(&corner, &bottom));
+    let hypotenuse = distance(&right, &bottom);
+    if (leg1 - leg2).abs() > leg1.max(leg2) * 0.2
+        || (hypotenuse / (leg1 * leg1 + leg2 * leg2).sqrt() - 1.0).abs() > 0.15
+        || leg1 < corner.module * 10.0
+    {
+        return None;
+    }
+    // In image coordinates (y down), top-right is clockwise from bottom-left
+    let cross =
+        (right.x - corner.x) * (bottom.y - corner.y) - (right.y - corner.y) * (bottom.x - corner.x);
+    if cross < 0.0 {
+        std::mem::swap(&mut right, &mut bottom);
+    }
+    Some((corner, right, bottom))
+}
+
+/// Sample the module grid of a `size`-module code from its finder centers
+fn sample(
+    bitmap: &Bitmap,
+    top_left: &Finder,
+    top_right: &Finder,
+    bottom_left: &Finder,
+    size: usize,
+) -> Matrix {
+    let span = (size - 7) as f64;
+    let (ux, uy) = (
+        (top_right.x - top_left.x) / span,
+        (top_right.y - top_left.y) / span,
+    );
+    let (vx, vy) = (
+        (bottom_left.x - top_left.x) / span,
+        (bottom_left.y - top_left.y) / span,
+    );
+    (0..size)
+        .map(|row| {
+            (0..size)
+                .map(|column| {
+                    let (u, v) = (column as f64 + 0.5 - 3.5, row as f64 + 0.5 - 3.5);
+                    let x = top_left.x + u * ux + v * vx;
+                    let y = top_left.y + u * uy + v * vy;
+                    bitmap
+                        .get(x.floor() as isize, y.floor() as isize)
+                        .unwrap_or(false)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn transpose(matrix: &Matrix) -> Matrix {
+    let size = matrix.len();
+    (0..size)
+        .map(|y| (0..size).map(|x| matrix[x][y]).collect())
+        .collect()
+}
+
+fn decode_bitmap(bitmap: &Bitmap, results: &mut Vec<String>) {
+    let mut finders = find_finders(bitmap);
+    finders.sort_by_key(|f| std::cmp::Reverse(f.hits));
+    finders.truncate(24);
+
+    for i in 0..finders.len() {
+        for j in i + 1..finders.len() {
+            for k in j + 1..finders.len() {
+                let Some((top_left, top_right, bottom_left)) =
+                    arrange(finders[i], finders[j], finders[k])
+                else {
+                    continue;
+                };
+                let module = (top_left.module + top_right.module + bottom_left.module) / 3.0;
+                let across =
+                    (distance(&top_left, &top_right) + distance(&top_left, &bottom_left)) / 2.0;
+                let estimate = (across / module).round() as usize + 7;
+                // Sizes are 4v + 17; try the nearest ones
+                let nearest = (estimate.saturating_sub(17) + 2) / 4;
+                for version in [nearest, nearest + 1, nearest.saturating_sub(1)] {
+                    if !(1..=40).contains(&version) {
+                        continue;
+                    }
+                    let matrix = sample(
+                        bitmap,
+                        &top_left,
+                        &top_right,
+                        &bottom_left,
+                        version * 4 + 17,
+                    );
+                    let decoded =
+                        decode_matrix(&matrix).or_else(|| decode_matrix(&transpose(&matrix)));
+                    if let Some(text) = decoded {
+                        if !results.contains(&text) {
+                            results.push(text);
+                        }
+                        break;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Decode every QR code found in `image`
+pub fn decode_image(image: &RgbaImage) -> Vec<String> {
+    let gray = image::DynamicImage::ImageRgba8(image.clone()).to_luma8();
+    let mut results = Vec::new();
+    let global = binarize_global(&gray);
+    decode_bitmap(&global, &mut results);
+    if results.is_empty() {
+        decode_bitmap(&global.inverted(), &mut results);
+    }
+    if results.is_empty() {
+        let local = binarize_local(&gray);
+        decode_bitmap(&local, &mut results);
+        if results.is_empty() {
+            decode_bitmap(&local.inverted(), &mut results);
+        }
+    }
+    results
+}
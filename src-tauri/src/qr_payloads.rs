@@ -0,0 +1,260 @@
+// Structured QR code payloads: Wi-Fi credentials, contacts, locations and email links
+// The generator sends the form fields here and encodes the returned text, so anything that
+// would make a code unscannable (bad WPA key length, unescaped separators, out-of-range
+// coordinates) is reported instead of silently producing a broken code.
+
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum WifiSecurity {
+    #[serde(rename = "WPA")]
+    Wpa,
+    #[serde(rename = "WEP")]
+    Wep,
+    #[serde(rename = "nopass")]
+    None,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct VcardFields {
+    pub first_name: String,
+    pub last_name: String,
+    pub organization: String,
+    pub phone: String,
+    pub email: String,
+    pub url: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum QrPayload {
+    Wifi {
+        ssid: String,
+        password: String,
+        security: WifiSecurity,
+        #[serde(default)]
+        hidden: bool,
+    },
+    Vcard(VcardFields),
+    Geo {
+        latitude: String,
+        longitude: String,
+    },
+    Mailto {
+        email: String,
+        #[serde(default)]
+        subject: String,
+        #[serde(default)]
+        body: String,
+    },
+}
+
+// Backslash-escape the characters that delimit fields in a WIFI: payload
+fn escape_wifi(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '\\' | ';' | ',' | ':' | '"') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+// vCard 3.0 text value escaping (RFC 2426 section 4)
+fn escape_vcard(value: &str) -> String {
+    value
+        .trim()
+        .replace('\\', "\\\\")
+        .replace(',', "\\,")
+        .replace(';', "\\;")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+fn is_valid_email(email: &str) -> bool {
+    let Some((local, domain)) = email.split_once('@') else {
+        return false;
+    };
+    !local.is_empty()
+        && domain.contains('.')
+        && !domain.starts_with('.')
+        && !domain.ends_with('.')
+        && !email.chars().any(|c| c.is_whitespace() || c == ',')
+}
+
+fn build_wifi(
+    ssid: &str,
+    password: &str,
+    security: WifiSecurity,
+    hidden: bool,
+) -> Result<String, String> {
+    if ssid.is_empty() {
+        return Err("Network name is empty".to_string());
+    }
+    if ssid.len() > 32 {
+        return Err("Network names are at most 32 bytes".to_string());
+    }
+
+    let is_hex = |key: &str| key.chars().all(|c| c.is_ascii_hexdigit());
+    match security {
+        WifiSecurity::Wpa => {
+            let valid =
+                (8..=63).contains(&password.len()) || (password.len() == 64 && is_hex(password));
+            if !valid {
+                return Err("WPA passwords are 8-63 characters (or 64 hex digits)".to_string());
+            }
+        }
+        WifiSecurity::Wep => {
+            let valid = matches!(password.len(), 5 | 13)
+                || (matches!(password.len(), 10 | 26) && is_hex(password));
+            if !valid {
+                return Err("WEP keys are 5 or 13 characters, or 10 or 26 hex digits".to_string());
+            }
+        }
+        WifiSecurity::None => {}
+    }
+
+    let mut payload = match security {
+        WifiSecurity::Wpa => format!(
+            "WIFI:T:WPA;S:{};P:{};",
+            escape_wifi(ssid),
+            escape_wifi(password)
+        ),
+        WifiSecurity::Wep => format!(
+            "WIFI:T:WEP;S:{};P:{};",
+            escape_wifi(ssid),
+            escape_wifi(password)
+        ),
+        WifiSecurity::None => format!("WIFI:T:nopass;S:{};", escape_wifi(ssid)),
+    };
+    if hidden {
+        payload.push_str("H:true;");
+    }
+    payload.push(';');
+    Ok(payload)
+}
+
+fn build_vcard(fields: &VcardFields) -> Result<String, String> {
+    let (first, last) = (fields.first_name.trim(), fields.last_name.trim());
+    let full_name = [first, last]
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    if full_name.is_empty() && fields.organization.trim().is_empty() {
+        return Err("Contact needs a name or an organization".to_string());
+    }
+    let email = fields.email.trim();
+    if !email.is_empty() && !is_valid_email(email) {
+        return Err(format!("Invalid email address: {}", email));
+    }
+    let phone = fields.phone.trim();
+    if !phone.is_empty()
+        && !phone
+            .chars()
+            .all(|c| c.is_ascii_digit() || " +-().".contains(c))
+    {
+        return Err(format!("Invalid phone number: {}", phone));
+    }
+
+    let mut lines = vec![
+        "BEGIN:VCARD".to_string(),
+        "VERSION:3.0".to_string(),
+        format!("N:{};{};;;", escape_vcard(last), escape_vcard(first)),
+        format!(
+            "FN:{}",
+            escape_vcard(if full_name.is_empty() {
+                &fields.organization
+            } else {
+                &full_name
+            })
+        ),
+    ];
+    if !fields.organization.trim().is_empty() {
+        lines.push(format!("ORG:{}", escape_vcard(&fields.organization)));
+    }
+    if !phone.is_empty() {
+        lines.push(format!("TEL;TYPE=CELL:{}", phone));
+    }
+    if !email.is_empty() {
+        lines.push(format!("EMAIL:{}", email));
+    }
+    if !fields.url.trim().is_empty() {
+        lines.push(format!("URL:{}", escape_vcard(&fields.url)));
+    }
+    lines.push("END:VCARD".to_string());
+    // vCard lines end with CRLF
+    Ok(lines.join("\r\n"))
+}
+
+fn build_geo(latitude: &str, longitude: &str) -> Result<String, String> {
+    let parse = |value: &str, name: &str, limit: f64| -> Result<f64, String> {
+        let number: f64 = value
+            .trim()
+            .parse()
+            .map_err(|_| format!("Invalid {}: {}", name, value.trim()))?;
+        if !number.is_finite() || number.abs() > limit {
+            return Err(format!("{} must be between -{} and {}", name, limit, limit));
+        }
+        Ok(number)
+    };
+    let latitude = parse(latitude, "Latitude", 90.0)?;
+    let longitude = parse(longitude, "Longitude", 180.0)?;
+    // Six decimals is about 10 cm; more only makes the code denser
+    let format = |n: f64| {
+        let text = format!("{:.6}", n);
+        let text = text.trim_end_matches('0').trim_end_matches('.');
+        if text == "-0" {
+            "0".to_string()
+        } else {
+            text.to_string()
+        }
+    };
+    Ok(format!("geo:{},{}", format(latitude), format(longitude)))
+}
+
+fn build_mailto(email: &str, subject: &str, body: &str) -> Result<String, String> {
+    let email = email.trim();
+    if !is_valid_email(email) {
+        return Err(format!("Invalid email address: {}", email));
+    }
+    let params: Vec<String> = [("subject", subject), ("body", body)]
+        .into_iter()
+        .filter(|(_, value)| !value.is_empty())
+        .map(|(key, value)| format!("{}={}", key, urlencoding::encode(value)))
+        .collect();
+    Ok(if params.is_empty() {
+        format!("mailto:{}", email)
+    } else {
+        format!("mailto:{}?{}", email, params.join("&"))
+    })
+}
+
+/// Text to encode for a structured payload, or why it can't be encoded
+pub fn build(payload: &QrPayload) -> Result<String, String> {
+    match payload {
+        QrPayload::Wifi {
+            ssid,
+            password,
+            security,
+            hidden,
+        } => build_wifi(ssid, password, *security, *hidden),
+        QrPayload::Vcard(fields) => build_vcard(fields),
+        QrPayload::Geo {
+            latitude,
+            longitude,
+        } => build_geo(latitude, longitude),
+        QrPayload::Mailto {
+            email,
+            subject,
+            body,
+        } => build_mailto(email, subject, body),
+    }
+}
+
+#[tauri::command]
+pub fn build_qr_payload(payload: QrPayload) -> Result<String, String> {
+    build(&payload)
+}
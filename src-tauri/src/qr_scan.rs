@@ -0,0 +1,147 @@
+// QR code scanner: read QR codes shown on screen
+// The user drags out a region (or the whole screen is captured) and every QR code in it is
+// decoded with `qr_decoder`. Results are classified so the UI can offer the obvious action:
+// open a link, or show Wi-Fi credentials with the password ready to copy. Linear (1D)
+// barcodes aren't read.
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use crate::{platform, qr_decoder};
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ScannedCode {
+    Url {
+        text: String,
+    },
+    Wifi {
+        text: String,
+        ssid: String,
+        password: String,
+        security: String, // WPA, WEP or nopass, as written in the code
+        hidden: bool,
+    },
+    Text {
+        text: String,
+    },
+}
+
+/// Split a WIFI: payload into its fields, undoing the backslash escapes (see `qr_payloads`)
+fn parse_wifi(payload: &str) -> Option<ScannedCode> {
+    if !payload.get(..5)?.eq_ignore_ascii_case("WIFI:") {
+        return None;
+    }
+    let body = &payload[5..];
+    let mut fields = Vec::new();
+    let mut current = String::new();
+    let mut chars = body.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => current.extend(chars.next()),
+            ';' => fields.push(std::mem::take(&mut current)),
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        fields.push(current);
+    }
+
+    let (mut ssid, mut password, mut security, mut hidden) = (None, String::new(), None, false);
+    for field in &fields {
+        let Some((key, value)) = field.split_once(':') else {
+            continue;
+        };
+        match key.to_ascii_uppercase().as_str() {
+            "S" => ssid = Some(value.to_string()),
+            "P" => password = value.to_string(),
+            "T" => security = Some(value.to_string()),
+            "H" => hidden = value.eq_ignore_ascii_case("true"),
+            _ => {}
+        }
+    }
+    Some(ScannedCode::Wifi {
+        text: payload.to_string(),
+        ssid: ssid?,
+        security: security
+            .filter(|s| !s.is_empty())
+            .unwrap_or_else(|| if password.is_empty() { "nopass" } else { "WPA" }.to_string()),
+        password,
+        hidden,
+    })
+}
+
+/// Classify decoded text as a link, Wi-Fi credentials or plain text
+pub fn classify(text: &str) -> ScannedCode {
+    if let Some(wifi) = parse_wifi(text) {
+        return wifi;
+    }
+    let lower = text.to_ascii_lowercase();
+    if (lower.starts_with("https://") || lower.starts_with("http://"))
+        && !text.chars().any(char::is_whitespace)
+    {
+        return ScannedCode::Url {
+            text: text.to_string(),
+        };
+    }
+    ScannedCode::Text {
+        text: text.to_string(),
+    }
+}
+
+/// Decode and classify every QR code in `image`
+pub fn scan_image(image: &image::RgbaImage) -> Vec<ScannedCode> {
+    qr_decoder::decode_image(image)
+        .iter()
+        .map(|text| classify(text))
+        .collect()
+}
+
+/// Let the user select a screen region (or capture every screen with `full_screen`) and read
+/// the QR codes in it
+#[tauri::command]
+pub async fn scan_qr_from_screen(
+    app: AppHandle,
+    full_screen: Option<bool>,
+) -> Result<Vec<ScannedCode>, String> {
+    // Keep the window out of the screenshot; give the compositor a moment to hide it
+    let window = app.get_webview_window("main");
+    let was_visible = window
+        .as_ref()
+        .and_then(|w| w.is_visible().ok())
+        .unwrap_or(false);
+    if let Some(window) = &window {
+        let _ = window.hide();
+    }
+    tokio::time::sleep(std::time::Duration::from_millis(150)).await;
+
+    let captured = if full_screen.unwrap_or(false) {
+        platform::capture_full_screen_impl(&app).await
+    } else {
+        platform::capture_screen_region_impl(&app).await
+    };
+
+    if let Some(window) = &window {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+    let image = match captured {
+        Ok(image) => image,
+        Err(e) => {
+            if !was_visible {
+                if let Some(window) = &window {
+                    let _ = window.hide();
+                }
+            }
+            return Err(e);
+        }
+    };
+
+    let codes = tauri::async_runtime::spawn_blocking(move || scan_image(&image))
+        .await
+        .map_err(|e| format!("QR code scan failed: {}", e))?;
+    if codes.is_empty() {
+        return Err("No QR code found".to_string());
+    }
+    Ok(codes)
+}
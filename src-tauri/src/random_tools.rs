@@ -0,0 +1,240 @@
+// Random picks, dice rolls and coin flips for quick decisions and tabletop games
+// All randomness comes from the OS generator. Results are kept in a short in-memory history
+// so the launcher can show what was rolled before.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rand::rngs::OsRng;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::Serialize;
+
+const HISTORY_LIMIT: usize = 50;
+
+// Keeps a typo like "1000d1000" from building a huge result
+const MAX_DICE: u32 = 100;
+const MAX_SIDES: u32 = 1000;
+
+/// One `NdM` group of a dice expression, or a flat modifier when `sides` is 0
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiceTerm {
+    pub count: u32,
+    pub sides: u32,
+    pub negative: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DiceGroup {
+    pub count: u32,
+    pub sides: u32,
+    pub negative: bool,
+    pub rolls: Vec<u32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DiceRoll {
+    pub notation: String,
+    pub groups: Vec<DiceGroup>,
+    pub modifier: i64,
+    pub total: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RandomKind {
+    Choice,
+    Dice,
+    Coin,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RandomHistoryEntry {
+    pub kind: RandomKind,
+    pub input: String,  // the items, dice notation, or empty for coin flips
+    pub result: String, // human-readable outcome
+    pub timestamp: u64, // seconds since the epoch
+}
+
+static HISTORY: Mutex<VecDeque<RandomHistoryEntry>> = Mutex::new(VecDeque::new());
+
+fn record(kind: RandomKind, input: String, result: String) {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let mut history = HISTORY.lock().unwrap();
+    history.push_front(RandomHistoryEntry {
+        kind,
+        input,
+        result,
+        timestamp,
+    });
+    history.truncate(HISTORY_LIMIT);
+}
+
+/// Parse dice notation like `3d6+2`, `d20`, `2d8 + 1d4 - 1` or `d%`
+pub fn parse_dice(notation: &str) -> Result<Vec<DiceTerm>, String> {
+    let compact: String = notation
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .to_lowercase();
+    if compact.is_empty() {
+        return Err("Dice notation is empty".to_string());
+    }
+    let invalid = || format!("Invalid dice notation: {}", notation.trim());
+
+    let mut terms = Vec::new();
+    let mut rest = compact.as_str();
+    let mut negative = false;
+    if let Some(stripped) = rest.strip_prefix('-') {
+        negative = true;
+        rest = stripped;
+    }
+    loop {
+        let end = rest.find(['+', '-']).unwrap_or(rest.len());
+        let term = &rest[..end];
+        if term.is_empty() {
+            return Err(invalid());
+        }
+
+        match term.split_once('d') {
+            Some((count, sides)) => {
+                let count = if count.is_empty() {
+                    1
+                } else {
+                    count.parse::<u32>().map_err(|_| invalid())?
+                };
+                let sides = if sides == "%" {
+                    100
+                } else {
+                    sides.parse::<u32>().map_err(|_| invalid())?
+                };
+                if count == 0 || count > MAX_DICE {
+                    return Err(format!("Roll between 1 and {} dice", MAX_DICE));
+                }
+                if !(2..=MAX_SIDES).contains(&sides) {
+                    return Err(format!("Dice need between 2 and {} sides", MAX_SIDES));
+                }
+                terms.push(DiceTerm {
+                    count,
+                    sides,
+                    negative,
+                });
+            }
+            None => terms.push(DiceTerm {
+                count: term.parse::<u32>().map_err(|_| invalid())?,
+                sides: 0,
+                negative,
+            }),
+        }
+
+        if end == rest.len() {
+            break;
+        }
+        negative = rest.as_bytes()[end] == b'-';
+        rest = &rest[end + 1..];
+    }
+
+    if terms.iter().all(|t| t.sides == 0) {
+        return Err(invalid());
+    }
+    Ok(terms)
+}
+
+/// Roll parsed dice with `roll(sides)` returning 1..=sides
+pub fn roll_terms(
+    notation: &str,
+    terms: &[DiceTerm],
+    mut roll: impl FnMut(u32) -> u32,
+) -> DiceRoll {
+    let mut groups = Vec::new();
+    let mut modifier = 0i64;
+    let mut total = 0i64;
+    for term in terms {
+        let sign = if term.negative { -1 } else { 1 };
+        if term.sides == 0 {
+            modifier += sign * term.count as i64;
+            continue;
+        }
+        let rolls: Vec<u32> = (0..term.count).map(|_| roll(term.sides)).collect();
+        total += sign * rolls.iter().map(|&r| r as i64).sum::<i64>();
+        groups.push(DiceGroup {
+            count: term.count,
+            sides: term.sides,
+            negative: term.negative,
+            rolls,
+        });
+    }
+    DiceRoll {
+        notation: notation.trim().to_string(),
+        groups,
+        modifier,
+        total: total + modifier,
+    }
+}
+
+/// Pick `count` distinct items (1 by default), in random order
+#[tauri::command]
+pub fn random_choice(items: Vec<String>, count: Option<usize>) -> Result<Vec<String>, String> {
+    let items: Vec<String> = items
+        .into_iter()
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect();
+    if items.is_empty() {
+        return Err("Nothing to choose from".to_string());
+    }
+    let count = count.unwrap_or(1);
+    if count == 0 || count > items.len() {
+        return Err(format!("Pick between 1 and {} items", items.len()));
+    }
+
+    let picked: Vec<String> = items.choose_multiple(&mut OsRng, count).cloned().collect();
+    record(RandomKind::Choice, items.join(", "), picked.join(", "));
+    Ok(picked)
+}
+
+#[tauri::command]
+pub fn roll_dice(notation: String) -> Result<DiceRoll, String> {
+    let terms = parse_dice(&notation)?;
+    let result = roll_terms(&notation, &terms, |sides| OsRng.gen_range(1..=sides));
+
+    let rolls: Vec<String> = result
+        .groups
+        .iter()
+        .flat_map(|g| g.rolls.iter().map(|r| r.to_string()))
+        .collect();
+    record(
+        RandomKind::Dice,
+        result.notation.clone(),
+        format!("{} ({})", result.total, rolls.join(", ")),
+    );
+    Ok(result)
+}
+
+/// "heads" or "tails"
+#[tauri::command]
+pub fn flip_coin() -> String {
+    let side = if OsRng.gen_bool(0.5) {
+        "heads"
+    } else {
+        "tails"
+    };
+    record(RandomKind::Coin, String::new(), side.to_string());
+    side.to_string()
+}
+
+/// Recent results, newest first
+#[tauri::command]
+pub fn get_random_history() -> Result<Vec<RandomHistoryEntry>, String> {
+    crate::app_lock::ensure_unlocked()?;
+    Ok(HISTORY.lock().unwrap().iter().cloned().collect())
+}
+
+#[tauri::command]
+pub fn clear_random_history() {
+    HISTORY.lock().unwrap().clear();
+}
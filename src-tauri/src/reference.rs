@@ -0,0 +1,361 @@
+// Offline developer reference: HTTP status codes, MIME types, default ports and HTML entities
+// The tables are embedded, so lookups are instant and work without a network connection.
+
+use serde::{Deserialize, Serialize};
+
+const MAX_RESULTS: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReferenceKind {
+    HttpStatus,
+    MimeType,
+    Port,
+    HtmlEntity,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReferenceEntry {
+    pub kind: ReferenceKind,
+    pub key: String,  // status code, file extension, port number or entity name
+    pub name: String, // reason phrase, MIME type, service or character
+    pub description: String,
+}
+
+// (code, reason phrase, description)
+#[rustfmt::skip]
+const HTTP_STATUSES: &[(&str, &str, &str)] = &[
+    ("100", "Continue", "The client should continue sending the request body"),
+    ("101", "Switching Protocols", "The server is switching protocols (e.g. to WebSocket)"),
+    ("102", "Processing", "WebDAV: request received, still being processed"),
+    ("103", "Early Hints", "Preload hints sent before the final response"),
+    ("200", "OK", "The request succeeded"),
+    ("201", "Created", "The request succeeded and a new resource was created"),
+    ("202", "Accepted", "The request was accepted but not processed yet"),
+    ("203", "Non-Authoritative Information", "Response modified by a transforming proxy"),
+    ("204", "No Content", "Success with no response body"),
+    ("205", "Reset Content", "Success; the client should reset the document view"),
+    ("206", "Partial Content", "Response to a Range request"),
+    ("207", "Multi-Status", "WebDAV: status for multiple resources"),
+    ("208", "Already Reported", "WebDAV: members already listed earlier in the response"),
+    ("226", "IM Used", "Delta encoding applied to the response"),
+    ("300", "Multiple Choices", "Several representations are available"),
+    ("301", "Moved Permanently", "The resource has a new permanent URL"),
+    ("302", "Found", "Temporary redirect (method may change to GET)"),
+    ("303", "See Other", "Redirect to another resource with GET"),
+    ("304", "Not Modified", "Cached version is still valid"),
+    ("307", "Temporary Redirect", "Temporary redirect, method and body preserved"),
+    ("308", "Permanent Redirect", "Permanent redirect, method and body preserved"),
+    ("400", "Bad Request", "The request is malformed"),
+    ("401", "Unauthorized", "Authentication is required or failed"),
+    ("402", "Payment Required", "Reserved; used by some APIs for billing limits"),
+    ("403", "Forbidden", "Authenticated but not allowed to access the resource"),
+    ("404", "Not Found", "The resource does not exist"),
+    ("405", "Method Not Allowed", "The HTTP method isn't supported for this resource"),
+    ("406", "Not Acceptable", "No representation matches the Accept headers"),
+    ("407", "Proxy Authentication Required", "Authenticate with the proxy first"),
+    ("408", "Request Timeout", "The server timed out waiting for the request"),
+    ("409", "Conflict", "The request conflicts with the current state of the resource"),
+    ("410", "Gone", "The resource was removed permanently"),
+    ("411", "Length Required", "Content-Length header is required"),
+    ("412", "Precondition Failed", "A conditional header (If-Match, ...) didn't match"),
+    ("413", "Content Too Large", "The request body exceeds the server's limit"),
+    ("414", "URI Too Long", "The request URI is longer than the server accepts"),
+    ("415", "Unsupported Media Type", "The request body's Content-Type isn't supported"),
+    ("416", "Range Not Satisfiable", "The requested range is outside the resource"),
+    ("417", "Expectation Failed", "The Expect header can't be met"),
+    ("418", "I'm a teapot", "April Fools' joke from RFC 2324"),
+    ("421", "Misdirected Request", "The request reached a server that can't answer it"),
+    ("422", "Unprocessable Content", "Well-formed request with semantic errors (validation)"),
+    ("423", "Locked", "WebDAV: the resource is locked"),
+    ("424", "Failed Dependency", "WebDAV: a previous request failed"),
+    ("425", "Too Early", "The server won't process a possibly replayed request"),
+    ("426", "Upgrade Required", "Switch to another protocol (see Upgrade header)"),
+    ("428", "Precondition Required", "The request must be conditional"),
+    ("429", "Too Many Requests", "Rate limited; see Retry-After"),
+    ("431", "Request Header Fields Too Large", "Headers are too large"),
+    ("451", "Unavailable For Legal Reasons", "Blocked for legal reasons"),
+    ("500", "Internal Server Error", "Generic server-side failure"),
+    ("501", "Not Implemented", "The server doesn't support the functionality"),
+    ("502", "Bad Gateway", "Invalid response from an upstream server"),
+    ("503", "Service Unavailable", "Overloaded or down for maintenance"),
+    ("504", "Gateway Timeout", "An upstream server didn't respond in time"),
+    ("505", "HTTP Version Not Supported", "The HTTP version isn't supported"),
+    ("506", "Variant Also Negotiates", "Content negotiation configuration error"),
+    ("507", "Insufficient Storage", "WebDAV: not enough storage to complete the request"),
+    ("508", "Loop Detected", "WebDAV: infinite loop while processing"),
+    ("510", "Not Extended", "Further extensions to the request are required"),
+    ("511", "Network Authentication Required", "Log in to the network (captive portal)"),
+];
+
+// (extension, MIME type, description)
+#[rustfmt::skip]
+const MIME_TYPES: &[(&str, &str, &str)] = &[
+    ("html", "text/html", "HTML document"),
+    ("htm", "text/html", "HTML document"),
+    ("css", "text/css", "Stylesheet"),
+    ("js", "text/javascript", "JavaScript"),
+    ("mjs", "text/javascript", "JavaScript module"),
+    ("json", "application/json", "JSON"),
+    ("jsonld", "application/ld+json", "JSON-LD"),
+    ("xml", "application/xml", "XML"),
+    ("txt", "text/plain", "Plain text"),
+    ("csv", "text/csv", "Comma-separated values"),
+    ("md", "text/markdown", "Markdown"),
+    ("ics", "text/calendar", "iCalendar"),
+    ("yaml", "application/yaml", "YAML"),
+    ("yml", "application/yaml", "YAML"),
+    ("toml", "application/toml", "TOML"),
+    ("wasm", "application/wasm", "WebAssembly"),
+    ("pdf", "application/pdf", "PDF document"),
+    ("zip", "application/zip", "ZIP archive"),
+    ("gz", "application/gzip", "Gzip archive"),
+    ("tar", "application/x-tar", "Tar archive"),
+    ("7z", "application/x-7z-compressed", "7-Zip archive"),
+    ("rar", "application/vnd.rar", "RAR archive"),
+    ("bin", "application/octet-stream", "Arbitrary binary data"),
+    ("exe", "application/vnd.microsoft.portable-executable", "Windows executable"),
+    ("doc", "application/msword", "Word document (legacy)"),
+    ("docx", "application/vnd.openxmlformats-officedocument.wordprocessingml.document", "Word document"),
+    ("xls", "application/vnd.ms-excel", "Excel spreadsheet (legacy)"),
+    ("xlsx", "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet", "Excel spreadsheet"),
+    ("ppt", "application/vnd.ms-powerpoint", "PowerPoint presentation (legacy)"),
+    ("pptx", "application/vnd.openxmlformats-officedocument.presentationml.presentation", "PowerPoint presentation"),
+    ("odt", "application/vnd.oasis.opendocument.text", "OpenDocument text"),
+    ("rtf", "application/rtf", "Rich Text Format"),
+    ("epub", "application/epub+zip", "EPUB e-book"),
+    ("png", "image/png", "PNG image"),
+    ("jpg", "image/jpeg", "JPEG image"),
+    ("jpeg", "image/jpeg", "JPEG image"),
+    ("gif", "image/gif", "GIF image"),
+    ("webp", "image/webp", "WebP image"),
+    ("avif", "image/avif", "AVIF image"),
+    ("svg", "image/svg+xml", "SVG image"),
+    ("ico", "image/vnd.microsoft.icon", "Icon"),
+    ("bmp", "image/bmp", "Bitmap image"),
+    ("tif", "image/tiff", "TIFF image"),
+    ("tiff", "image/tiff", "TIFF image"),
+    ("heic", "image/heic", "HEIC image"),
+    ("mp3", "audio/mpeg", "MP3 audio"),
+    ("wav", "audio/wav", "WAV audio"),
+    ("ogg", "audio/ogg", "Ogg audio"),
+    ("oga", "audio/ogg", "Ogg audio"),
+    ("flac", "audio/flac", "FLAC audio"),
+    ("m4a", "audio/mp4", "AAC/ALAC audio in MP4"),
+    ("aac", "audio/aac", "AAC audio"),
+    ("opus", "audio/opus", "Opus audio"),
+    ("mp4", "video/mp4", "MP4 video"),
+    ("webm", "video/webm", "WebM video"),
+    ("mkv", "video/x-matroska", "Matroska video"),
+    ("mov", "video/quicktime", "QuickTime video"),
+    ("avi", "video/x-msvideo", "AVI video"),
+    ("ts", "video/mp2t", "MPEG transport stream"),
+    ("woff", "font/woff", "WOFF font"),
+    ("woff2", "font/woff2", "WOFF2 font"),
+    ("ttf", "font/ttf", "TrueType font"),
+    ("otf", "font/otf", "OpenType font"),
+    ("form", "application/x-www-form-urlencoded", "URL-encoded form body"),
+    ("multipart", "multipart/form-data", "Form body with file uploads"),
+    ("sse", "text/event-stream", "Server-sent events"),
+];
+
+// (port, service, description)
+#[rustfmt::skip]
+const PORTS: &[(&str, &str, &str)] = &[
+    ("20", "FTP data", "TCP"),
+    ("21", "FTP", "TCP, control connection"),
+    ("22", "SSH", "TCP, also SFTP/SCP"),
+    ("23", "Telnet", "TCP"),
+    ("25", "SMTP", "TCP, mail relay"),
+    ("53", "DNS", "UDP/TCP"),
+    ("67", "DHCP server", "UDP"),
+    ("68", "DHCP client", "UDP"),
+    ("69", "TFTP", "UDP"),
+    ("80", "HTTP", "TCP"),
+    ("110", "POP3", "TCP"),
+    ("123", "NTP", "UDP"),
+    ("137", "NetBIOS Name Service", "UDP"),
+    ("139", "NetBIOS Session", "TCP"),
+    ("143", "IMAP", "TCP"),
+    ("161", "SNMP", "UDP"),
+    ("389", "LDAP", "TCP"),
+    ("443", "HTTPS", "TCP, also HTTP/3 over UDP"),
+    ("445", "SMB", "TCP, Windows file sharing"),
+    ("465", "SMTPS", "TCP, SMTP over TLS"),
+    ("514", "Syslog", "UDP"),
+    ("587", "SMTP submission", "TCP, mail submission with STARTTLS"),
+    ("636", "LDAPS", "TCP, LDAP over TLS"),
+    ("853", "DNS over TLS", "TCP"),
+    ("873", "rsync", "TCP"),
+    ("993", "IMAPS", "TCP, IMAP over TLS"),
+    ("995", "POP3S", "TCP, POP3 over TLS"),
+    ("1080", "SOCKS proxy", "TCP"),
+    ("1194", "OpenVPN", "UDP"),
+    ("1433", "Microsoft SQL Server", "TCP"),
+    ("1521", "Oracle Database", "TCP"),
+    ("1883", "MQTT", "TCP"),
+    ("2049", "NFS", "TCP/UDP"),
+    ("2375", "Docker daemon", "TCP, unencrypted"),
+    ("2376", "Docker daemon (TLS)", "TCP"),
+    ("3000", "Dev server", "Node/React/Rails development servers"),
+    ("3306", "MySQL / MariaDB", "TCP"),
+    ("3389", "RDP", "TCP/UDP, Remote Desktop"),
+    ("4200", "Angular dev server", "TCP"),
+    ("5000", "Dev server", "Flask, ASP.NET; AirPlay on macOS"),
+    ("5173", "Vite dev server", "TCP"),
+    ("5222", "XMPP", "TCP"),
+    ("5353", "mDNS", "UDP"),
+    ("5432", "PostgreSQL", "TCP"),
+    ("5672", "AMQP (RabbitMQ)", "TCP"),
+    ("5900", "VNC", "TCP"),
+    ("6379", "Redis", "TCP"),
+    ("6443", "Kubernetes API server", "TCP"),
+    ("8000", "Dev server", "Django and other HTTP development servers"),
+    ("8080", "HTTP alternate", "Proxies, Tomcat, dev servers"),
+    ("8443", "HTTPS alternate", "TCP"),
+    ("8888", "Jupyter Notebook", "TCP"),
+    ("9000", "PHP-FPM / SonarQube", "TCP"),
+    ("9090", "Prometheus", "TCP"),
+    ("9092", "Kafka", "TCP"),
+    ("9200", "Elasticsearch", "TCP, HTTP API"),
+    ("9418", "Git protocol", "TCP"),
+    ("11211", "Memcached", "TCP/UDP"),
+    ("25565", "Minecraft server", "TCP"),
+    ("27017", "MongoDB", "TCP"),
+    ("51820", "WireGuard", "UDP"),
+];
+
+// (entity name, character, description)
+#[rustfmt::skip]
+const HTML_ENTITIES: &[(&str, &str, &str)] = &[
+    ("amp", "&", "Ampersand"),
+    ("lt", "<", "Less-than sign"),
+    ("gt", ">", "Greater-than sign"),
+    ("quot", "\"", "Double quotation mark"),
+    ("apos", "'", "Apostrophe"),
+    ("nbsp", "\u{a0}", "Non-breaking space"),
+    ("ensp", "\u{2002}", "En space"),
+    ("emsp", "\u{2003}", "Em space"),
+    ("thinsp", "\u{2009}", "Thin space"),
+    ("zwj", "\u{200d}", "Zero-width joiner"),
+    ("zwnj", "\u{200c}", "Zero-width non-joiner"),
+    ("shy", "\u{ad}", "Soft hyphen"),
+    ("ndash", "–", "En dash"),
+    ("mdash", "—", "Em dash"),
+    ("hellip", "…", "Horizontal ellipsis"),
+    ("lsquo", "‘", "Left single quotation mark"),
+    ("rsquo", "’", "Right single quotation mark"),
+    ("ldquo", "“", "Left double quotation mark"),
+    ("rdquo", "”", "Right double quotation mark"),
+    ("laquo", "«", "Left-pointing double angle quotation mark"),
+    ("raquo", "»", "Right-pointing double angle quotation mark"),
+    ("bull", "•", "Bullet"),
+    ("middot", "·", "Middle dot"),
+    ("copy", "©", "Copyright sign"),
+    ("reg", "®", "Registered sign"),
+    ("trade", "™", "Trade mark sign"),
+    ("sect", "§", "Section sign"),
+    ("para", "¶", "Pilcrow (paragraph) sign"),
+    ("deg", "°", "Degree sign"),
+    ("plusmn", "±", "Plus-minus sign"),
+    ("times", "×", "Multiplication sign"),
+    ("divide", "÷", "Division sign"),
+    ("minus", "−", "Minus sign"),
+    ("ne", "≠", "Not equal to"),
+    ("le", "≤", "Less-than or equal to"),
+    ("ge", "≥", "Greater-than or equal to"),
+    ("asymp", "≈", "Almost equal to"),
+    ("infin", "∞", "Infinity"),
+    ("sum", "∑", "N-ary summation"),
+    ("radic", "√", "Square root"),
+    ("frac12", "½", "Vulgar fraction one half"),
+    ("frac14", "¼", "Vulgar fraction one quarter"),
+    ("frac34", "¾", "Vulgar fraction three quarters"),
+    ("sup2", "²", "Superscript two"),
+    ("sup3", "³", "Superscript three"),
+    ("micro", "µ", "Micro sign"),
+    ("alpha", "α", "Greek small letter alpha"),
+    ("beta", "β", "Greek small letter beta"),
+    ("pi", "π", "Greek small letter pi"),
+    ("Delta", "Δ", "Greek capital letter delta"),
+    ("Omega", "Ω", "Greek capital letter omega"),
+    ("euro", "€", "Euro sign"),
+    ("pound", "£", "Pound sign"),
+    ("yen", "¥", "Yen sign"),
+    ("cent", "¢", "Cent sign"),
+    ("larr", "←", "Leftwards arrow"),
+    ("rarr", "→", "Rightwards arrow"),
+    ("uarr", "↑", "Upwards arrow"),
+    ("darr", "↓", "Downwards arrow"),
+    ("harr", "↔", "Left right arrow"),
+    ("rArr", "⇒", "Rightwards double arrow"),
+    ("check", "✓", "Check mark"),
+    ("hearts", "♥", "Black heart suit"),
+    ("iexcl", "¡", "Inverted exclamation mark"),
+    ("iquest", "¿", "Inverted question mark"),
+];
+
+fn table(kind: ReferenceKind) -> &'static [(&'static str, &'static str, &'static str)] {
+    match kind {
+        ReferenceKind::HttpStatus => HTTP_STATUSES,
+        ReferenceKind::MimeType => MIME_TYPES,
+        ReferenceKind::Port => PORTS,
+        ReferenceKind::HtmlEntity => HTML_ENTITIES,
+    }
+}
+
+/// Entries matching `query`, best first: exact key, exact name, key prefix, then any field
+/// containing the query. Entity names are case-sensitive (`&Delta;` vs `&delta;`), so they
+/// prefer an exact-case match.
+pub fn lookup(kind: ReferenceKind, query: &str) -> Vec<ReferenceEntry> {
+    let query = query.trim();
+    let query = match kind {
+        ReferenceKind::HtmlEntity => query.trim_start_matches('&').trim_end_matches(';'),
+        ReferenceKind::MimeType => query.trim_start_matches('.'),
+        _ => query,
+    };
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let lower = query.to_lowercase();
+
+    let rank = |(key, name, description): &(&str, &str, &str)| -> Option<u8> {
+        if *key == query || *name == query {
+            Some(0)
+        } else if key.to_lowercase() == lower || name.to_lowercase() == lower {
+            Some(1)
+        } else if key.to_lowercase().starts_with(&lower) {
+            Some(2)
+        } else if name.to_lowercase().contains(&lower)
+            || description.to_lowercase().contains(&lower)
+        {
+            Some(3)
+        } else {
+            None
+        }
+    };
+
+    let mut matches: Vec<(u8, &(&str, &str, &str))> = table(kind)
+        .iter()
+        .filter_map(|entry| rank(entry).map(|r| (r, entry)))
+        .collect();
+    // Stable sort keeps table order within a rank
+    matches.sort_by_key(|(r, _)| *r);
+
+    matches
+        .into_iter()
+        .take(MAX_RESULTS)
+        .map(|(_, (key, name, description))| ReferenceEntry {
+            kind,
+            key: key.to_string(),
+            name: name.to_string(),
+            description: description.to_string(),
+        })
+        .collect()
+}
+
+/// Look up HTTP status codes, MIME types, default ports or HTML entities
+#[tauri::command]
+pub fn lookup_reference(kind: ReferenceKind, query: String) -> Vec<ReferenceEntry> {
+    lookup(kind, &query)
+}
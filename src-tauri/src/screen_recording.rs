@@ -0,0 +1,423 @@
+// Screen recording with the bundled ffmpeg: gdigrab on Windows, x11grab or the PipeWire
+// screencast portal on Linux, optionally with the default audio input. ffmpeg runs as a
+// hidden child and is stopped by sending `q` on stdin, so the file is finalized properly.
+// `screen-recording-tick` carries the elapsed time every second while recording and
+// `screen-recording-changed` is emitted on start and stop (null once stopped).
+
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::process::{Child, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::notifications::{self, NotifyAction};
+use crate::platform::{self, AudioCaptureInput, ScreenCaptureBackend};
+
+const MAX_FPS: u32 = 60;
+
+// Time ffmpeg gets to finish writing the file after `q` before it is killed
+const STOP_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RecordingFormat {
+    #[default]
+    Mp4,
+    Mkv,
+    Webm,
+    Gif, // no audio
+}
+
+impl RecordingFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            RecordingFormat::Mp4 => "mp4",
+            RecordingFormat::Mkv => "mkv",
+            RecordingFormat::Webm => "webm",
+            RecordingFormat::Gif => "gif",
+        }
+    }
+}
+
+/// Part of the screen to record, in physical pixels of the virtual desktop
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Region {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct RecordingOptions {
+    pub region: Option<Region>, // None records the whole desktop
+    pub fps: u32,
+    pub audio: bool, // record the default audio input as well
+    pub format: RecordingFormat,
+    pub output_path: Option<String>, // None saves to the Videos folder
+}
+
+impl Default for RecordingOptions {
+    fn default() -> Self {
+        Self {
+            region: None,
+            fps: 30,
+            audio: false,
+            format: RecordingFormat::Mp4,
+            output_path: None,
+        }
+    }
+}
+
+/// Running recording (`screen-recording-tick` / `screen-recording-changed`)
+#[derive(Debug, Clone, Serialize)]
+pub struct RecordingStatus {
+    pub path: String,
+    pub format: RecordingFormat,
+    pub audio: bool,
+    pub elapsed_secs: u64,
+}
+
+struct Session {
+    generation: u64,
+    child: Child,
+    started: Instant,
+    status: RecordingStatus,
+}
+
+static SESSION: Mutex<Option<Session>> = Mutex::new(None);
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+// Set while a `start` is launching ffmpeg, so a second one can't launch another
+static STARTING: AtomicBool = AtomicBool::new(false);
+
+// Gives up the reservation when `start` returns, after its session is stored or on failure
+struct Starting;
+
+impl Starting {
+    fn reserve() -> Result<Self, String> {
+        let session = SESSION.lock().unwrap();
+        if session.is_some() || STARTING.swap(true, Ordering::SeqCst) {
+            return Err("A recording is already running".to_string());
+        }
+        Ok(Starting)
+    }
+}
+
+impl Drop for Starting {
+    fn drop(&mut self) {
+        STARTING.store(false, Ordering::SeqCst);
+    }
+}
+
+/// ffmpeg arguments for recording `options` from `backend` into `output`
+pub fn ffmpeg_args(
+    backend: &ScreenCaptureBackend,
+    options: &RecordingOptions,
+    audio: Option<&AudioCaptureInput>,
+    output: &str,
+) -> Vec<String> {
+    let fps = options.fps.to_string();
+    let mut args: Vec<String> = ["-hide_banner", "-loglevel", "error", "-y"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    let mut push = |items: &[&str]| args.extend(items.iter().map(|s| s.to_string()));
+    let mut filters = Vec::new();
+
+    match backend {
+        ScreenCaptureBackend::Gdigrab => {
+            push(&["-f", "gdigrab", "-framerate", &fps, "-draw_mouse", "1"]);
+            if let Some(r) = options.region {
+                push(&[
+                    "-offset_x",
+                    &r.x.to_string(),
+                    "-offset_y",
+                    &r.y.to_string(),
+                    "-video_size",
+                    &format!("{}x{}", r.width, r.height),
+                ]);
+            }
+            push(&["-i", "desktop"]);
+        }
+        ScreenCaptureBackend::X11grab { display } => {
+            push(&["-f", "x11grab", "-framerate", &fps, "-draw_mouse", "1"]);
+            let input = match options.region {
+                Some(r) => {
+                    push(&["-video_size", &format!("{}x{}", r.width, r.height)]);
+                    format!("{}+{},{}", display, r.x, r.y)
+                }
+                None => display.clone(),
+            };
+            push(&["-i", &input]);
+        }
+        ScreenCaptureBackend::Pipewire => {
+            push(&[
+                "-f",
+                "lavfi",
+                "-i",
+                &format!("pipewiregrab=framerate={}", fps),
+            ]);
+            // The portal shares whole screens, so the region is cut out afterwards
+            if let Some(r) = options.region {
+                filters.push(format!("crop={}:{}:{}:{}", r.width, r.height, r.x, r.y));
+            }
+        }
+    }
+
+    let audio = audio.filter(|_| options.audio && options.format != RecordingFormat::Gif);
+    if let Some(audio) = audio {
+        push(&["-f", &audio.format, "-i", &audio.device]);
+    }
+
+    match options.format {
+        RecordingFormat::Mp4 | RecordingFormat::Mkv => {
+            // yuv420p needs even dimensions
+            filters.push("scale=trunc(iw/2)*2:trunc(ih/2)*2".to_string());
+            push(&["-vf", &filters.join(",")]);
+            push(&[
+                "-c:v",
+                "libx264",
+                "-preset",
+                "ultrafast",
+                "-crf",
+                "23",
+                "-pix_fmt",
+                "yuv420p",
+            ]);
+        }
+        RecordingFormat::Webm => {
+            filters.push("scale=trunc(iw/2)*2:trunc(ih/2)*2".to_string());
+            push(&["-vf", &filters.join(",")]);
+            push(&[
+                "-c:v",
+                "libvpx-vp9",
+                "-deadline",
+                "realtime",
+                "-cpu-used",
+                "8",
+                "-b:v",
+                "0",
+                "-crf",
+                "32",
+                "-pix_fmt",
+                "yuv420p",
+            ]);
+        }
+        RecordingFormat::Gif => {
+            if !filters.is_empty() {
+                push(&["-vf", &filters.join(",")]);
+            }
+            push(&["-loop", "0"]);
+        }
+    }
+
+    if audio.is_some() {
+        match options.format {
+            RecordingFormat::Webm => push(&["-c:a", "libopus"]),
+            _ => push(&["-c:a", "aac", "-b:a", "160k"]),
+        }
+    }
+    if options.format == RecordingFormat::Mp4 {
+        push(&["-movflags", "+faststart"]);
+    }
+    push(&[output]);
+    args
+}
+
+fn output_path(app: &AppHandle, options: &RecordingOptions) -> Result<PathBuf, String> {
+    let extension = options.format.extension();
+    let path = match &options.output_path {
+        Some(path) => {
+            let mut path = PathBuf::from(path);
+            if path.extension().is_none() {
+                path.set_extension(extension);
+            }
+            path
+        }
+        None => {
+            let timestamp = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0);
+            app.path()
+                .video_dir()
+                .map_err(|e| format!("Could not find the Videos folder: {}", e))?
+                .join(format!("Screen Recording {}.{}", timestamp, extension))
+        }
+    };
+    crate::path_policy::validate_write_path(app, &path.to_string_lossy())
+}
+
+fn status() -> Option<RecordingStatus> {
+    SESSION.lock().unwrap().as_ref().map(|s| {
+        let mut status = s.status.clone();
+        status.elapsed_secs = s.started.elapsed().as_secs();
+        status
+    })
+}
+
+/// Ask ffmpeg to finish the file, killing it if it doesn't within `STOP_TIMEOUT`
+fn finish_child(child: &mut Child) {
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(b"q");
+    }
+    let deadline = Instant::now() + STOP_TIMEOUT;
+    while Instant::now() < deadline {
+        if let Ok(Some(_)) = child.try_wait() {
+            return;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    log::warn!("ffmpeg didn't stop in time, killing it");
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+/// Stop recording; returns the finished recording, or None if nothing was recording
+pub fn stop(app: &AppHandle) -> Option<RecordingStatus> {
+    GENERATION.fetch_add(1, Ordering::SeqCst);
+    let status = status();
+    let mut session = SESSION.lock().unwrap().take()?;
+    finish_child(&mut session.child);
+    let _ = app.emit("screen-recording-changed", None::<RecordingStatus>);
+    status
+}
+
+// Tick every second and notice ffmpeg exiting on its own (portal cancelled, device lost)
+fn watch(app: AppHandle, generation: u64) {
+    loop {
+        std::thread::sleep(Duration::from_secs(1));
+        let mut guard = SESSION.lock().unwrap();
+        let Some(session) = guard.as_mut().filter(|s| s.generation == generation) else {
+            return;
+        };
+        match session.child.try_wait() {
+            Ok(None) => {
+                let mut status = session.status.clone();
+                status.elapsed_secs = session.started.elapsed().as_secs();
+                drop(guard);
+                let _ = app.emit("screen-recording-tick", &status);
+            }
+            _ => {
+                let mut session = guard.take().unwrap();
+                drop(guard);
+                let mut error = String::new();
+                if let Some(mut stderr) = session.child.stderr.take() {
+                    let _ = stderr.read_to_string(&mut error);
+                }
+                let error = error.lines().last().unwrap_or("ffmpeg exited").to_string();
+                log::warn!("Screen recording stopped: {}", error);
+                let _ = app.emit("screen-recording-changed", None::<RecordingStatus>);
+                notifications::notify(&app, "Screen recording stopped", &error, NotifyAction::None);
+                return;
+            }
+        }
+    }
+}
+
+fn start(app: AppHandle, options: RecordingOptions) -> Result<RecordingStatus, String> {
+    if options.fps == 0 || options.fps > MAX_FPS {
+        return Err(format!(
+            "The frame rate needs to be between 1 and {}",
+            MAX_FPS
+        ));
+    }
+    if let Some(r) = options.region {
+        if r.width < 2 || r.height < 2 {
+            return Err("The region is too small to record".to_string());
+        }
+    }
+    let _starting = Starting::reserve()?;
+
+    let ffmpeg = platform::get_ffmpeg_path()?;
+    let path = output_path(&app, &options)?;
+    let audio = if options.audio && options.format != RecordingFormat::Gif {
+        Some(platform::audio_capture_input(&ffmpeg)?)
+    } else {
+        None
+    };
+    let args = ffmpeg_args(
+        &platform::screen_capture_backend(),
+        &options,
+        audio.as_ref(),
+        &path.to_string_lossy(),
+    );
+
+    let mut child = crate::hidden_command(&ffmpeg)
+        .args(&args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to start ffmpeg: {}", e))?;
+
+    // Fail fast on bad devices or options instead of reporting a recording that isn't running
+    std::thread::sleep(Duration::from_millis(500));
+    if let Ok(Some(_)) = child.try_wait() {
+        let mut error = String::new();
+        if let Some(mut stderr) = child.stderr.take() {
+            let _ = stderr.read_to_string(&mut error);
+        }
+        return Err(format!(
+            "Recording failed: {}",
+            error.lines().last().unwrap_or("ffmpeg exited")
+        ));
+    }
+
+    let generation = GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    let status = RecordingStatus {
+        path: path.to_string_lossy().to_string(),
+        format: options.format,
+        audio: audio.is_some(),
+        elapsed_secs: 0,
+    };
+    *SESSION.lock().unwrap() = Some(Session {
+        generation,
+        child,
+        started: Instant::now(),
+        status: status.clone(),
+    });
+    let _ = app.emit("screen-recording-changed", Some(&status));
+
+    std::thread::spawn(move || watch(app, generation));
+    Ok(status)
+}
+
+#[tauri::command]
+pub async fn start_screen_recording(
+    app: AppHandle,
+    options: Option<RecordingOptions>,
+) -> Result<RecordingStatus, String> {
+    let options = options.unwrap_or_default();
+    tauri::async_runtime::spawn_blocking(move || start(app, options))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Stop recording and finish the file; returns the recording, None if nothing was recording
+#[tauri::command]
+pub async fn stop_screen_recording(app: AppHandle) -> Result<Option<RecordingStatus>, String> {
+    let app_for_stop = app.clone();
+    let status = tauri::async_runtime::spawn_blocking(move || stop(&app_for_stop))
+        .await
+        .map_err(|e| e.to_string())?;
+    if let Some(status) = &status {
+        notifications::notify_if_hidden(
+            &app,
+            "Screen recording saved",
+            &status.path,
+            NotifyAction::reveal(&status.path),
+        );
+    }
+    Ok(status)
+}
+
+#[tauri::command]
+pub fn get_screen_recording() -> Option<RecordingStatus> {
+    status()
+}
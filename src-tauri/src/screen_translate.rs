@@ -0,0 +1,111 @@
+// Screen translation: screenshot + OCR + translate in one step
+// The user drags out a region of the screen (the window is hidden meanwhile), the text in it
+// is recognized by the platform OCR engine (Windows.Media.Ocr, or tesseract on Linux) and
+// passed to the regular translator. This covers text that can't be selected, such as in
+// images, videos and games.
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use crate::{platform, AppState, TranslationResult};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ScreenTranslation {
+    pub text: String, // recognized text, after clean_recognized_text
+    #[serde(flatten)]
+    pub translation: TranslationResult,
+}
+
+/// Turn OCR output into translatable text: lines of a paragraph are joined, words split by a
+/// hyphen at the end of a line are rejoined and runs of whitespace collapse to one space.
+/// Blank lines are kept as paragraph breaks.
+pub fn clean_recognized_text(raw: &str) -> String {
+    let mut paragraphs: Vec<String> = Vec::new();
+    let mut current = String::new();
+
+    for line in raw.lines() {
+        let line = line.split_whitespace().collect::<Vec<_>>().join(" ");
+        if line.is_empty() {
+            if !current.is_empty() {
+                paragraphs.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        if current.is_empty() {
+            current = line;
+        } else if current.ends_with('-')
+            && line.starts_with(|c: char| c.is_lowercase())
+            && current[..current.len() - 1].ends_with(|c: char| c.is_alphabetic())
+        {
+            current.pop();
+            current.push_str(&line);
+        } else {
+            current.push(' ');
+            current.push_str(&line);
+        }
+    }
+    if !current.is_empty() {
+        paragraphs.push(current);
+    }
+
+    paragraphs.join("\n\n")
+}
+
+/// Let the user select a screen region, read the text in it and translate it to `target_lang`
+/// (the quick translation target language when omitted)
+#[tauri::command]
+pub async fn translate_screen_region(
+    app: AppHandle,
+    target_lang: Option<String>,
+) -> Result<ScreenTranslation, String> {
+    let target_lang = match target_lang {
+        Some(lang) if !lang.is_empty() => lang,
+        _ => app
+            .state::<AppState>()
+            .settings
+            .lock()
+            .unwrap()
+            .quick_translation_target_language
+            .clone(),
+    };
+
+    // Keep the window out of the screenshot; give the compositor a moment to hide it
+    let window = app.get_webview_window("main");
+    let was_visible = window
+        .as_ref()
+        .and_then(|w| w.is_visible().ok())
+        .unwrap_or(false);
+    if let Some(window) = &window {
+        let _ = window.hide();
+    }
+    tokio::time::sleep(std::time::Duration::from_millis(150)).await;
+
+    let captured = platform::capture_screen_region_impl(&app).await;
+
+    if let Some(window) = &window {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+    let image = match captured {
+        Ok(image) => image,
+        Err(e) => {
+            if !was_visible {
+                if let Some(window) = &window {
+                    let _ = window.hide();
+                }
+            }
+            return Err(e);
+        }
+    };
+
+    let raw = tauri::async_runtime::spawn_blocking(move || platform::recognize_text_impl(&image))
+        .await
+        .map_err(|e| format!("Text recognition failed: {}", e))??;
+    let text = clean_recognized_text(&raw);
+    if text.is_empty() {
+        return Err("No text found in the selected region".to_string());
+    }
+
+    let translation = crate::translate_text(app.clone(), text.clone(), target_lang).await?;
+    Ok(ScreenTranslation { text, translation })
+}
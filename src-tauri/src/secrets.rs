@@ -0,0 +1,265 @@
+// Secrets store: a passphrase-protected key for encrypting semi-sensitive text (license keys,
+// recovery codes) kept in snippets. The key is random and saved wrapped by a key derived from
+// the passphrase (PBKDF2-HMAC-SHA256), so only someone who knows the passphrase can read the
+// encrypted items. Once unlocked, the key stays in memory until locked again, by hand or after
+// `Settings.secrets_lock_minutes` without being used.
+
+use std::num::NonZeroU32;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::pbkdf2;
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::AppState;
+
+const VAULT_FILE: &str = "secrets.json";
+pub const KEY_LEN: usize = 32;
+const SALT_LEN: usize = 16;
+// OWASP's recommendation for PBKDF2-HMAC-SHA256
+const PBKDF2_ITERATIONS: u32 = 600_000;
+const MIN_PASSPHRASE_LEN: usize = 8;
+
+pub type Key = [u8; KEY_LEN];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Vault {
+    salt: String, // base64
+    iterations: u32,
+    key: String, // the store key, sealed with the passphrase key
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SecretsStatus {
+    pub configured: bool, // a passphrase has been set
+    pub unlocked: bool,
+    pub remaining: Option<u64>, // seconds until it locks itself
+}
+
+struct Unlocked {
+    key: Key,
+    generation: u64,
+    locks_at: Instant,
+}
+
+static UNLOCKED: Mutex<Option<Unlocked>> = Mutex::new(None);
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// The key for `passphrase`
+pub fn derive_key(passphrase: &str, salt: &[u8], iterations: u32) -> Key {
+    let mut key = [0u8; KEY_LEN];
+    pbkdf2::derive(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        NonZeroU32::new(iterations.max(1)).unwrap(),
+        salt,
+        passphrase.as_bytes(),
+        &mut key,
+    );
+    key
+}
+
+fn random_bytes<const N: usize>() -> Result<[u8; N], String> {
+    let mut bytes = [0u8; N];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .map_err(|_| "Failed to generate random bytes".to_string())?;
+    Ok(bytes)
+}
+
+fn aead_key(key: &Key) -> LessSafeKey {
+    LessSafeKey::new(UnboundKey::new(&AES_256_GCM, key).unwrap())
+}
+
+/// Encrypt `plaintext` with AES-256-GCM; base64 of the random nonce followed by the ciphertext
+pub fn seal(key: &Key, plaintext: &[u8]) -> Result<String, String> {
+    let nonce = random_bytes::<NONCE_LEN>()?;
+    let mut data = plaintext.to_vec();
+    aead_key(key)
+        .seal_in_place_append_tag(Nonce::assume_unique_for_key(nonce), Aad::empty(), &mut data)
+        .map_err(|_| "Failed to encrypt".to_string())?;
+    let mut sealed = nonce.to_vec();
+    sealed.extend_from_slice(&data);
+    Ok(BASE64.encode(sealed))
+}
+
+/// Decrypt what `seal` produced; fails for another key or altered data
+pub fn open(key: &Key, sealed: &str) -> Result<Vec<u8>, String> {
+    let undecryptable = || "Can't decrypt: wrong key or damaged data".to_string();
+    let data = BASE64.decode(sealed.trim()).map_err(|_| undecryptable())?;
+    if data.len() < NONCE_LEN {
+        return Err(undecryptable());
+    }
+    let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+    let nonce = Nonce::try_assume_unique_for_key(nonce).map_err(|_| undecryptable())?;
+    let mut ciphertext = ciphertext.to_vec();
+    let plaintext = aead_key(key)
+        .open_in_place(nonce, Aad::empty(), &mut ciphertext)
+        .map_err(|_| undecryptable())?;
+    Ok(plaintext.to_vec())
+}
+
+fn load_vault(app: &AppHandle) -> Option<Vault> {
+    crate::load_json_file::<Option<Vault>>(app, VAULT_FILE)
+}
+
+fn lock_timeout(app: &AppHandle) -> Duration {
+    let minutes = app
+        .state::<AppState>()
+        .settings
+        .lock()
+        .unwrap()
+        .secrets_lock_minutes;
+    Duration::from_secs(minutes.max(1) * 60)
+}
+
+fn status(app: &AppHandle) -> SecretsStatus {
+    let configured = load_vault(app).is_some();
+    let unlocked = UNLOCKED.lock().unwrap();
+    SecretsStatus {
+        configured,
+        unlocked: unlocked.is_some(),
+        remaining: unlocked.as_ref().map(|u| {
+            u.locks_at
+                .saturating_duration_since(Instant::now())
+                .as_secs()
+        }),
+    }
+}
+
+/// Forget the key
+pub fn lock(app: &AppHandle) {
+    let was_unlocked = match UNLOCKED.lock().unwrap().take() {
+        Some(mut unlocked) => {
+            unlocked.key.fill(0);
+            true
+        }
+        None => false,
+    };
+    if was_unlocked {
+        log::info!("Secrets locked");
+        let _ = app.emit("secrets-changed", status(app));
+    }
+}
+
+// Lock once the key has gone unused for the timeout; a newer unlock replaces this timer
+fn spawn_lock_timer(app: AppHandle, generation: u64) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let locks_at = match UNLOCKED.lock().unwrap().as_ref() {
+                Some(u) if u.generation == generation => u.locks_at,
+                _ => return,
+            };
+            let now = Instant::now();
+            if locks_at <= now {
+                break;
+            }
+            tokio::time::sleep(locks_at - now).await;
+        }
+        lock(&app);
+    });
+}
+
+/// The key, if unlocked; each use pushes the automatic lock back
+fn key(app: &AppHandle) -> Result<Key, String> {
+    let timeout = lock_timeout(app);
+    let mut unlocked = UNLOCKED.lock().unwrap();
+    let unlocked = unlocked
+        .as_mut()
+        .ok_or_else(|| "Secrets are locked; unlock them first".to_string())?;
+    unlocked.locks_at = Instant::now() + timeout;
+    Ok(unlocked.key)
+}
+
+/// Encrypt text with the store key (must be unlocked)
+pub fn encrypt(app: &AppHandle, text: &str) -> Result<String, String> {
+    seal(&key(app)?, text.as_bytes())
+}
+
+/// Decrypt text encrypted by `encrypt` (must be unlocked)
+pub fn decrypt(app: &AppHandle, sealed: &str) -> Result<String, String> {
+    let plaintext = open(&key(app)?, sealed)?;
+    String::from_utf8(plaintext).map_err(|_| "Decrypted text isn't valid UTF-8".to_string())
+}
+
+// The store key for `passphrase`, setting the passphrase up the first time
+fn unwrap_or_create(app: &AppHandle, passphrase: &str) -> Result<Key, String> {
+    if let Some(vault) = load_vault(app) {
+        let salt = BASE64
+            .decode(&vault.salt)
+            .map_err(|_| "The secrets store is damaged".to_string())?;
+        let mut wrapping_key = derive_key(passphrase, &salt, vault.iterations);
+        let opened = open(&wrapping_key, &vault.key);
+        wrapping_key.fill(0);
+        let mut plaintext = opened.map_err(|_| "Wrong passphrase".to_string())?;
+        let key: Result<Key, String> = plaintext
+            .as_slice()
+            .try_into()
+            .map_err(|_| "The secrets store is damaged".to_string());
+        plaintext.fill(0);
+        return key;
+    }
+
+    if passphrase.chars().count() < MIN_PASSPHRASE_LEN {
+        return Err(format!(
+            "Choose a passphrase of at least {} characters",
+            MIN_PASSPHRASE_LEN
+        ));
+    }
+    let salt = random_bytes::<SALT_LEN>()?;
+    let key = random_bytes::<KEY_LEN>()?;
+    let mut wrapping_key = derive_key(passphrase, &salt, PBKDF2_ITERATIONS);
+    let sealed = seal(&wrapping_key, &key);
+    wrapping_key.fill(0);
+    let vault = Vault {
+        salt: BASE64.encode(salt),
+        iterations: PBKDF2_ITERATIONS,
+        key: sealed?,
+    };
+    crate::save_json_file(app, VAULT_FILE, &Some(vault))?;
+    log::info!("Secrets store created");
+    Ok(key)
+}
+
+#[tauri::command]
+pub fn get_secrets_status(app: AppHandle) -> SecretsStatus {
+    status(&app)
+}
+
+/// Unlock with the passphrase; the first unlock sets it
+#[tauri::command]
+pub async fn unlock_secrets(app: AppHandle, passphrase: String) -> Result<SecretsStatus, String> {
+    crate::app_lock::ensure_unlocked()?;
+    let handle = app.clone();
+    // Key derivation is deliberately slow
+    let key = tauri::async_runtime::spawn_blocking(move || unwrap_or_create(&handle, &passphrase))
+        .await
+        .map_err(|e| e.to_string())??;
+
+    let generation = GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    let locks_at = Instant::now() + lock_timeout(&app);
+    if let Some(mut previous) = UNLOCKED.lock().unwrap().replace(Unlocked {
+        key,
+        generation,
+        locks_at,
+    }) {
+        previous.key.fill(0);
+    }
+    spawn_lock_timer(app.clone(), generation);
+    log::info!("Secrets unlocked");
+
+    let status = status(&app);
+    let _ = app.emit("secrets-changed", status.clone());
+    Ok(status)
+}
+
+#[tauri::command]
+pub fn lock_secrets(app: AppHandle) -> SecretsStatus {
+    lock(&app);
+    status(&app)
+}
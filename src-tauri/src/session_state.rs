@@ -0,0 +1,74 @@
+// Session lock and user idle tracking
+// Polls the OS for the lock state and the time since the last input, and emits
+// `session-state-changed` when the session is locked/unlocked or the user goes idle / comes
+// back, so long-running work can react (timers pausing, keep-awake releasing its inhibitor).
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{app_lock, platform, AppState};
+
+// Lock and idle queries spawn processes on Linux
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct SessionState {
+    pub locked: bool,
+    pub idle: bool,        // no input for `idle_threshold_minutes`
+    pub idle_seconds: u64, // 0 if the desktop doesn't report idle time
+}
+
+static STATE: Mutex<SessionState> = Mutex::new(SessionState {
+    locked: false,
+    idle: false,
+    idle_seconds: 0,
+});
+
+/// True while the session is locked (as of the last poll)
+pub fn is_locked() -> bool {
+    STATE.lock().unwrap().locked
+}
+
+fn read_state(idle_threshold: Duration) -> SessionState {
+    let idle_seconds = platform::get_idle_seconds_impl();
+    SessionState {
+        locked: platform::is_session_locked_impl().unwrap_or(false),
+        idle: idle_seconds.is_some_and(|s| s >= idle_threshold.as_secs()),
+        idle_seconds: idle_seconds.unwrap_or(0),
+    }
+}
+
+/// Start following the lock state and idle time
+pub fn init(app: &AppHandle) {
+    let app = app.clone();
+    std::thread::spawn(move || loop {
+        let minutes = app
+            .state::<AppState>()
+            .settings
+            .lock()
+            .unwrap()
+            .idle_threshold_minutes;
+        let state = read_state(Duration::from_secs(minutes.max(1) * 60));
+
+        let previous = std::mem::replace(&mut *STATE.lock().unwrap(), state);
+        if (previous.locked, previous.idle) != (state.locked, state.idle) {
+            log::info!(
+                "Session state: locked={}, idle={} ({}s)",
+                state.locked,
+                state.idle,
+                state.idle_seconds
+            );
+            let _ = app.emit("session-state-changed", state);
+        }
+        app_lock::follow_session(&app, &state);
+        std::thread::sleep(POLL_INTERVAL);
+    });
+}
+
+#[tauri::command]
+pub fn get_session_state() -> SessionState {
+    *STATE.lock().unwrap()
+}
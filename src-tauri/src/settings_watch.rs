@@ -0,0 +1,89 @@
+// Reload settings.json when it changes on disk
+// Hand edits and sync tools rewrite the file behind the app's back. Its directory is watched
+// (editors and sync tools often save by renaming a new file over the old one), and once the
+// events settle, contents that differ from the settings in memory are applied like a save
+// from the settings panel (hotkeys re-registered, tray and triggers updated) and
+// `settings-changed` is emitted with the new settings. The app's own saves already match the
+// state, so they don't trigger a reload.
+
+use std::fs;
+use std::path::Path;
+use std::sync::mpsc;
+use std::time::Duration;
+
+use notify::{Event, RecursiveMode, Watcher};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{AppState, Settings};
+
+// A save is often several events (truncate, writes, rename); reload once they stop
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+fn concerns(event: &notify::Result<Event>, path: &Path) -> bool {
+    event.as_ref().is_ok_and(|event| {
+        event
+            .paths
+            .iter()
+            .any(|changed| changed.file_name() == path.file_name())
+    })
+}
+
+fn reload(app: &AppHandle, path: &Path) {
+    // Missing (being replaced) or half-written files are skipped; the final write is another
+    // event
+    let Ok(content) = fs::read_to_string(path) else {
+        return;
+    };
+    let settings: Settings = match serde_json::from_str(&content) {
+        Ok(settings) => settings,
+        Err(e) => {
+            log::warn!("Ignoring invalid settings.json: {}", e);
+            return;
+        }
+    };
+
+    let current = app.state::<AppState>().settings.lock().unwrap().clone();
+    if serde_json::to_value(&settings).ok() == serde_json::to_value(&current).ok() {
+        return;
+    }
+
+    log::info!("settings.json changed on disk, reloading");
+    if let Err(e) = crate::apply_settings(app, settings.clone()) {
+        log::warn!("Failed to apply reloaded settings: {}", e);
+    }
+    let _ = app.emit("settings-changed", &settings);
+}
+
+/// Start watching settings.json
+pub fn init(app: &AppHandle) {
+    let app = app.clone();
+    let path = crate::get_settings_path(&app);
+    let Some(dir) = path.parent().map(Path::to_path_buf) else {
+        return;
+    };
+    let _ = fs::create_dir_all(&dir);
+
+    let (sender, events) = mpsc::channel();
+    let mut watcher = match notify::recommended_watcher(sender) {
+        Ok(watcher) => watcher,
+        Err(e) => {
+            log::warn!("Failed to watch settings.json: {}", e);
+            return;
+        }
+    };
+    if let Err(e) = watcher.watch(&dir, RecursiveMode::NonRecursive) {
+        log::warn!("Failed to watch {}: {}", dir.display(), e);
+        return;
+    }
+
+    std::thread::spawn(move || {
+        let _watcher = watcher; // watching stops when it's dropped
+        while let Ok(event) = events.recv() {
+            if !concerns(&event, &path) {
+                continue;
+            }
+            while events.recv_timeout(DEBOUNCE).is_ok() {}
+            reload(&app, &path);
+        }
+    });
+}
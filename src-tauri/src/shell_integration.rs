@@ -0,0 +1,155 @@
+// "Convert with BunchaTools" shell integration
+// Registers the app as a context-menu / Send To target for video files (registry on Windows,
+// a .desktop MimeType handler on Linux). The shell launches `bunchatools --open-tool <tool> <files>`;
+// when BunchaTools is already running the single-instance plugin hands those arguments to the
+// running instance instead, which opens the tool with the files preselected. Files of an
+// associated type (see file_associations.rs) arrive as plain `bunchatools <file>` launches;
+// workflow files go to the frontend too, which lists their actions and asks before running.
+// Jump list tasks (see jump_list.rs) and desktop shortcuts (desktop_shortcuts.rs) launch
+// `bunchatools --action <action>` to run a quick action in the running instance.
+
+use std::path::Path;
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::actions::{self, Action};
+use crate::{file_associations, platform, AppState};
+
+pub const OPEN_TOOL_ARG: &str = "--open-tool";
+pub const ACTION_ARG: &str = "--action";
+// Start in the tray without showing the launcher; used by the autostart entries
+pub const HIDDEN_ARG: &str = "--hidden";
+const CONVERT_TOOL: &str = "video-converter";
+
+/// A tool to open with files from the shell, sent to the frontend as `open-files`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OpenFilesRequest {
+    pub tool: String,
+    pub paths: Vec<String>,
+}
+
+/// Parse `<exe> --open-tool <tool> [files...]` or `<exe> <files...>` for associated file types,
+/// resolving relative paths against `cwd`
+pub fn parse_launch_args(args: &[String], cwd: &Path) -> Option<OpenFilesRequest> {
+    let resolve = |a: &String| cwd.join(a).to_string_lossy().to_string();
+
+    if let Some(index) = args.iter().position(|a| a == OPEN_TOOL_ARG) {
+        let tool = args.get(index + 1)?.clone();
+        let paths = args[index + 2..]
+            .iter()
+            .filter(|a| !a.starts_with("--"))
+            .map(resolve)
+            .collect();
+        return Some(OpenFilesRequest { tool, paths });
+    }
+
+    // Opened files (after the executable): the first recognized one picks the tool
+    let files: Vec<&String> = args
+        .iter()
+        .skip(1)
+        .filter(|a| !a.starts_with("--"))
+        .collect();
+    let tool = files
+        .iter()
+        .find_map(|a| file_associations::tool_for_path(a))?;
+    let paths = files
+        .into_iter()
+        .filter(|a| file_associations::tool_for_path(a) == Some(tool))
+        .map(resolve)
+        .collect();
+    Some(OpenFilesRequest {
+        tool: tool.to_string(),
+        paths,
+    })
+}
+
+/// Parse `<exe> --action <action> [name=value...]`, e.g. `--action pick_color` or
+/// `--action kill_port port=3000`; values are JSON when they parse as JSON, text otherwise
+pub fn parse_launch_action(args: &[String]) -> Option<Action> {
+    let index = args.iter().position(|a| a == ACTION_ARG)?;
+    let name = args.get(index + 1)?;
+    let mut fields = serde_json::Map::new();
+    fields.insert("type".to_string(), Value::String(name.clone()));
+    for arg in args[index + 2..].iter().take_while(|a| !a.starts_with("--")) {
+        let (key, value) = arg.split_once('=')?;
+        let value = serde_json::from_str(value).unwrap_or_else(|_| Value::String(value.into()));
+        fields.insert(key.to_string(), value);
+    }
+    serde_json::from_value(Value::Object(fields)).ok()
+}
+
+/// The arguments that run `action` at launch, as `parse_launch_action` / `parse_launch_args`
+/// read them back: `--open-tool <tool>` for tools, `--action <action> [name=value...]` else
+pub fn launch_args(action: &Action) -> Vec<String> {
+    if let Action::OpenTool { tool } = action {
+        return vec![OPEN_TOOL_ARG.to_string(), tool.clone()];
+    }
+    let mut args = vec![ACTION_ARG.to_string()];
+    let Ok(Value::Object(fields)) = serde_json::to_value(action) else {
+        return args;
+    };
+    args.extend(fields["type"].as_str().map(String::from));
+    for (key, value) in fields.iter().filter(|(key, _)| *key != "type") {
+        let value = match value {
+            Value::Null => continue,
+            // Quoted only when the text would read back as something else ("3000", "true")
+            Value::String(text) if serde_json::from_str::<Value>(text).is_err() => text.clone(),
+            other => other.to_string(),
+        };
+        args.push(format!("{}={}", key, value));
+    }
+    args
+}
+
+/// Whether the command line asks to start in the tray
+pub fn launched_hidden(args: &[String]) -> bool {
+    args.iter().skip(1).any(|a| a == HIDDEN_ARG)
+}
+
+/// Keep the request from our own command line until the frontend is ready to take it
+pub fn store_launch_request(app: &AppHandle) {
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(action) = parse_launch_action(&args) {
+        log::info!("Launched to run {:?}", action);
+        actions::dispatch(app, action);
+        return;
+    }
+    let cwd = std::env::current_dir().unwrap_or_default();
+    if let Some(request) = parse_launch_args(&args, &cwd) {
+        log::info!("Launched to open {} with {:?}", request.tool, request.paths);
+        *app.state::<AppState>().launch_request.lock().unwrap() = Some(request);
+    }
+}
+
+/// Called by the single-instance plugin with the arguments of a second launch
+pub fn handle_second_instance(app: &AppHandle, args: Vec<String>, cwd: String) {
+    if let Some(action) = parse_launch_action(&args) {
+        actions::dispatch(app, action);
+        return;
+    }
+    match parse_launch_args(&args, Path::new(&cwd)) {
+        Some(request) => {
+            let _ = app.emit("open-files", request);
+        }
+        // Launching the app again without a file just brings it up
+        None if !launched_hidden(&args) => actions::show_main_window(app),
+        None => {}
+    }
+}
+
+/// Launch request from the command line, if any (returned once)
+#[tauri::command]
+pub fn take_launch_request(app: AppHandle) -> Option<OpenFilesRequest> {
+    app.state::<AppState>()
+        .launch_request
+        .lock()
+        .unwrap()
+        .take()
+}
+
+/// Add or remove the "Convert with BunchaTools" shell entries
+pub fn set_shell_integration(enable: bool) -> Result<(), String> {
+    platform::set_shell_integration_impl(enable, &format!("{} {}", OPEN_TOOL_ARG, CONVERT_TOOL))
+}
@@ -0,0 +1,489 @@
+// Text snippets with computed placeholders
+// A snippet's text can hold placeholders that are filled in when it's expanded:
+//   {uuid}                  random UUID (v4)
+//   {random:8}              8 random letters and digits
+//   {date}, {date+3d:%d.%m} today (offset by s/m/h/d/w), with an optional strftime format
+//   {time}, {time-2h}       the same, formatted as 14:05 by default
+//   {clipboard}             the clipboard text
+// Each can be piped through transforms: {clipboard|trim|upper}, {date|replace:-:/}. Besides the
+// built-ins below, every text transform (morse, rot13, ...) works, and users can name their own
+// pipelines ({clipboard|shout} with shout = "trim|upper|replace:$:!"). "{{" and "}}" are
+// literal braces.
+// Snippets holding semi-sensitive text (license keys, ...) can be encrypted with the secrets
+// store key; they can only be expanded while the store is unlocked.
+
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Local};
+use rand::distributions::Alphanumeric;
+use rand::rngs::OsRng;
+use rand::Rng;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+use crate::{secrets, text_transforms};
+
+const SNIPPETS_FILE: &str = "snippets.json";
+const MAX_RANDOM_LENGTH: usize = 256;
+// User pipelines may use each other, but not forever
+const MAX_PIPELINE_DEPTH: usize = 8;
+
+const BUILTIN_TRANSFORMS: &[&str] = &["upper", "lower", "title", "trim", "slug", "replace"];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Snippet {
+    pub name: String,
+    pub text: String, // sealed by the secrets store when `encrypted`
+    #[serde(default)]
+    pub encrypted: bool,
+}
+
+/// A user-defined transform: a named pipeline of other transforms
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomTransform {
+    pub name: String,
+    pub pipeline: String, // e.g. "trim|upper"
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SnippetStore {
+    #[serde(default)]
+    pub snippets: Vec<Snippet>,
+    #[serde(default)]
+    pub transforms: Vec<CustomTransform>,
+}
+
+/// What placeholders are evaluated against
+pub struct Context<'a> {
+    pub now: DateTime<Local>,
+    pub clipboard: Option<String>,
+    pub transforms: &'a [CustomTransform],
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Text(String),
+    Placeholder(String),
+}
+
+fn parse(template: &str) -> Result<Vec<Segment>, String> {
+    let mut segments = Vec::new();
+    let mut text = String::new();
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                text.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                text.push('}');
+            }
+            '{' => {
+                let mut placeholder = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => placeholder.push(c),
+                        None => return Err(format!("Unclosed placeholder: {{{}", placeholder)),
+                    }
+                }
+                if !text.is_empty() {
+                    segments.push(Segment::Text(std::mem::take(&mut text)));
+                }
+                segments.push(Segment::Placeholder(placeholder));
+            }
+            '}' => return Err("Unmatched \"}\" (write \"}}\" for a literal one)".to_string()),
+            c => text.push(c),
+        }
+    }
+    if !text.is_empty() {
+        segments.push(Segment::Text(text));
+    }
+    Ok(segments)
+}
+
+fn uuid_v4() -> String {
+    let mut bytes: [u8; 16] = OsRng.gen();
+    bytes[6] = (bytes[6] & 0x0f) | 0x40; // version 4
+    bytes[8] = (bytes[8] & 0x3f) | 0x80; // RFC 4122 variant
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+// "+3d", "-2w", "" -> the offset from now
+fn time_offset(spec: &str) -> Result<chrono::Duration, String> {
+    if spec.is_empty() {
+        return Ok(chrono::Duration::zero());
+    }
+    let invalid = || format!("Invalid offset \"{}\", e.g. date+3d or time-2h", spec);
+    let (negative, rest) = match spec.as_bytes()[0] {
+        b'+' => (false, &spec[1..]),
+        b'-' => (true, &spec[1..]),
+        _ => return Err(invalid()),
+    };
+    let digits = rest.chars().take_while(char::is_ascii_digit).count();
+    let amount: i64 = rest[..digits].parse().map_err(|_| invalid())?;
+    let seconds = match &rest[digits..] {
+        "s" => 1,
+        "m" => 60,
+        "h" => 3600,
+        "d" => 86_400,
+        "w" => 7 * 86_400,
+        _ => return Err(invalid()),
+    };
+    let offset = amount
+        .checked_mul(seconds)
+        .and_then(chrono::TimeDelta::try_seconds)
+        .ok_or_else(invalid)?;
+    Ok(if negative { -offset } else { offset })
+}
+
+fn format_time(time: DateTime<Local>, format: &str) -> Result<String, String> {
+    let items: Vec<Item> = StrftimeItems::new(format).collect();
+    if items.iter().any(|item| matches!(item, Item::Error)) {
+        return Err(format!("Invalid date format: {}", format));
+    }
+    Ok(time.format_with_items(items.into_iter()).to_string())
+}
+
+// The value a placeholder's source ("uuid", "random:8", "date+1d:%A", ...) stands for
+fn evaluate_source(source: &str, context: &Context) -> Result<String, String> {
+    let (name, argument) = match source.split_once(':') {
+        Some((name, argument)) => (name.trim(), Some(argument)),
+        None => (source.trim(), None),
+    };
+    let lower = name.to_lowercase();
+    match lower.as_str() {
+        "uuid" => Ok(uuid_v4()),
+        "random" => {
+            let length = match argument {
+                Some(length) => length
+                    .trim()
+                    .parse::<usize>()
+                    .map_err(|_| format!("Invalid length: {}", length))?,
+                None => 8,
+            };
+            if !(1..=MAX_RANDOM_LENGTH).contains(&length) {
+                return Err(format!(
+                    "Random text can be 1 to {} characters",
+                    MAX_RANDOM_LENGTH
+                ));
+            }
+            Ok((0..length)
+                .map(|_| OsRng.sample(Alphanumeric) as char)
+                .collect())
+        }
+        "clipboard" => context
+            .clipboard
+            .clone()
+            .ok_or_else(|| "The clipboard has no text".to_string()),
+        _ if lower.starts_with("date") || lower.starts_with("time") => {
+            let (base, offset) = lower.split_at(4);
+            let default_format = if base == "date" { "%Y-%m-%d" } else { "%H:%M" };
+            let time = context
+                .now
+                .checked_add_signed(time_offset(offset)?)
+                .ok_or_else(|| {
+                    format!("Invalid offset \"{}\", the date is out of range", offset)
+                })?;
+            format_time(time, argument.unwrap_or(default_format))
+        }
+        _ => Err(format!("Unknown placeholder: {{{}}}", source)),
+    }
+}
+
+fn title_case(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut word_start = true;
+    for c in text.chars() {
+        if word_start {
+            result.extend(c.to_uppercase());
+        } else {
+            result.extend(c.to_lowercase());
+        }
+        word_start = c.is_whitespace();
+    }
+    result
+}
+
+fn slug(text: &str) -> String {
+    let mut result = String::new();
+    for c in text.trim().chars().flat_map(char::to_lowercase) {
+        if c.is_alphanumeric() {
+            result.push(c);
+        } else if !result.ends_with('-') && !result.is_empty() {
+            result.push('-');
+        }
+    }
+    result.trim_end_matches('-').to_string()
+}
+
+fn apply_transform(
+    step: &str,
+    text: &str,
+    context: &Context,
+    depth: usize,
+) -> Result<String, String> {
+    let (name, argument) = match step.split_once(':') {
+        Some((name, argument)) => (name.trim(), Some(argument)),
+        None => (step.trim(), None),
+    };
+    match name.to_lowercase().as_str() {
+        "upper" => Ok(text.to_uppercase()),
+        "lower" => Ok(text.to_lowercase()),
+        "title" => Ok(title_case(text)),
+        "trim" => Ok(text.trim().to_string()),
+        "slug" => Ok(slug(text)),
+        // replace:<regex>:<replacement>, where the replacement can use $1...
+        "replace" => {
+            let (pattern, replacement) = argument
+                .and_then(|a| a.split_once(':'))
+                .ok_or_else(|| "Use replace:<pattern>:<replacement>".to_string())?;
+            let regex =
+                Regex::new(pattern).map_err(|e| format!("Invalid pattern {}: {}", pattern, e))?;
+            Ok(regex.replace_all(text, replacement).into_owned())
+        }
+        _ => match context
+            .transforms
+            .iter()
+            .find(|t| t.name.eq_ignore_ascii_case(name))
+        {
+            Some(custom) => {
+                if depth >= MAX_PIPELINE_DEPTH {
+                    return Err(format!("Transform \"{}\" uses itself", custom.name));
+                }
+                run_pipeline(&custom.pipeline, text, context, depth + 1)
+            }
+            None => text_transforms::apply(name, text),
+        },
+    }
+}
+
+fn run_pipeline(
+    pipeline: &str,
+    text: &str,
+    context: &Context,
+    depth: usize,
+) -> Result<String, String> {
+    pipeline
+        .split('|')
+        .filter(|step| !step.trim().is_empty())
+        .try_fold(text.to_string(), |text, step| {
+            apply_transform(step, &text, context, depth)
+        })
+}
+
+/// Fill in the placeholders in `template`
+pub fn expand(template: &str, context: &Context) -> Result<String, String> {
+    let mut result = String::new();
+    for segment in parse(template)? {
+        match segment {
+            Segment::Text(text) => result.push_str(&text),
+            Segment::Placeholder(placeholder) => {
+                let (source, pipeline) = match placeholder.split_once('|') {
+                    Some((source, pipeline)) => (source, pipeline),
+                    None => (placeholder.as_str(), ""),
+                };
+                let value = evaluate_source(source, context)?;
+                result.push_str(&run_pipeline(pipeline, &value, context, 0)?);
+            }
+        }
+    }
+    Ok(result)
+}
+
+fn valid_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err("Names can use letters, digits, - and _".to_string());
+    }
+    Ok(name.to_string())
+}
+
+fn load<R: Runtime>(app: &AppHandle<R>) -> SnippetStore {
+    crate::load_json_file(app, SNIPPETS_FILE)
+}
+
+fn save<R: Runtime>(app: &AppHandle<R>, store: &SnippetStore) -> Result<(), String> {
+    crate::save_json_file(app, SNIPPETS_FILE, store)
+}
+
+fn context<'a>(app: &AppHandle, transforms: &'a [CustomTransform]) -> Context<'a> {
+    Context {
+        now: Local::now(),
+        clipboard: app.clipboard().read_text().ok(),
+        transforms,
+    }
+}
+
+#[tauri::command]
+pub fn get_snippets(app: AppHandle) -> Result<SnippetStore, String> {
+    crate::app_lock::ensure_unlocked()?;
+    Ok(load(&app))
+}
+
+fn find_snippet<'a>(store: &'a mut SnippetStore, name: &str) -> Result<&'a mut Snippet, String> {
+    store
+        .snippets
+        .iter_mut()
+        .find(|s| s.name.eq_ignore_ascii_case(name.trim()))
+        .ok_or_else(|| format!("No snippet called \"{}\"", name.trim()))
+}
+
+// The snippet's template, decrypted if need be
+fn snippet_text(app: &AppHandle, snippet: &Snippet) -> Result<String, String> {
+    if snippet.encrypted {
+        secrets::decrypt(app, &snippet.text)
+    } else {
+        Ok(snippet.text.clone())
+    }
+}
+
+/// Add a snippet, or replace the one with the same name. `encrypted` defaults to what the
+/// replaced snippet had.
+#[tauri::command]
+pub fn save_snippet(
+    app: AppHandle,
+    name: String,
+    text: String,
+    encrypted: Option<bool>,
+) -> Result<Snippet, String> {
+    crate::app_lock::ensure_unlocked()?;
+    let name = valid_name(&name)?;
+    parse(&text)?;
+    let mut store = load(&app);
+    let existing = store
+        .snippets
+        .iter()
+        .position(|s| s.name.eq_ignore_ascii_case(&name));
+    let encrypted = encrypted
+        .or_else(|| existing.map(|i| store.snippets[i].encrypted))
+        .unwrap_or(false);
+    let text = if encrypted {
+        secrets::encrypt(&app, &text)?
+    } else {
+        text
+    };
+    let snippet = Snippet {
+        name,
+        text,
+        encrypted,
+    };
+    match existing {
+        Some(i) => store.snippets[i] = snippet.clone(),
+        None => store.snippets.push(snippet.clone()),
+    }
+    save(&app, &store)?;
+    Ok(snippet)
+}
+
+/// Encrypt a snippet with the secrets store key, or store it as plain text again
+#[tauri::command]
+pub fn set_snippet_encrypted(
+    app: AppHandle,
+    name: String,
+    encrypted: bool,
+) -> Result<Snippet, String> {
+    crate::app_lock::ensure_unlocked()?;
+    let mut store = load(&app);
+    let snippet = find_snippet(&mut store, &name)?;
+    if snippet.encrypted != encrypted {
+        let text = snippet_text(&app, snippet)?;
+        snippet.text = if encrypted {
+            secrets::encrypt(&app, &text)?
+        } else {
+            text
+        };
+        snippet.encrypted = encrypted;
+    }
+    let snippet = snippet.clone();
+    save(&app, &store)?;
+    Ok(snippet)
+}
+
+#[tauri::command]
+pub fn delete_snippet<R: Runtime>(app: AppHandle<R>, name: String) -> Result<(), String> {
+    crate::app_lock::ensure_unlocked()?;
+    let mut store = load(&app);
+    let count = store.snippets.len();
+    store
+        .snippets
+        .retain(|s| !s.name.eq_ignore_ascii_case(name.trim()));
+    if store.snippets.len() == count {
+        return Err(format!("No snippet called \"{}\"", name.trim()));
+    }
+    save(&app, &store)
+}
+
+/// Define a transform as a pipeline of others, e.g. shout = "trim|upper"
+#[tauri::command]
+pub fn save_snippet_transform<R: Runtime>(
+    app: AppHandle<R>,
+    name: String,
+    pipeline: String,
+) -> Result<CustomTransform, String> {
+    crate::app_lock::ensure_unlocked()?;
+    let name = valid_name(&name)?;
+    let shadowed = BUILTIN_TRANSFORMS
+        .iter()
+        .any(|b| b.eq_ignore_ascii_case(&name))
+        || text_transforms::TRANSFORMS
+            .iter()
+            .any(|t| t.id.eq_ignore_ascii_case(&name));
+    if shadowed {
+        return Err(format!("\"{}\" is a built-in transform", name));
+    }
+    let transform = CustomTransform { name, pipeline };
+    let mut store = load(&app);
+    store
+        .transforms
+        .retain(|t| !t.name.eq_ignore_ascii_case(&transform.name));
+    store.transforms.push(transform.clone());
+    save(&app, &store)?;
+    Ok(transform)
+}
+
+#[tauri::command]
+pub fn delete_snippet_transform<R: Runtime>(app: AppHandle<R>, name: String) -> Result<(), String> {
+    crate::app_lock::ensure_unlocked()?;
+    let mut store = load(&app);
+    store
+        .transforms
+        .retain(|t| !t.name.eq_ignore_ascii_case(name.trim()));
+    save(&app, &store)
+}
+
+/// Expand the named snippet and copy the result to the clipboard
+#[tauri::command]
+pub fn expand_snippet(app: AppHandle, name: String) -> Result<String, String> {
+    crate::app_lock::ensure_unlocked()?;
+    let mut store = load(&app);
+    let template = snippet_text(&app, find_snippet(&mut store, &name)?)?;
+    let text = expand(&template, &context(&app, &store.transforms))?;
+    app.clipboard()
+        .write_text(text.clone())
+        .map_err(|e| format!("Failed to write clipboard: {}", e))?;
+    Ok(text)
+}
+
+/// Preview `text` with its placeholders filled in
+#[tauri::command]
+pub fn expand_placeholders(app: AppHandle, text: String) -> Result<String, String> {
+    crate::app_lock::ensure_unlocked()?;
+    let store = load(&app);
+    expand(&text, &context(&app, &store.transforms))
+}
@@ -0,0 +1,94 @@
+// Startup health: problems found while the app was starting
+// Hotkey registration, tray creation and the bundled ffmpeg / yt-dlp are checked on launch.
+// Failures don't stop startup; they are collected here so the UI can explain why a hotkey
+// does nothing or a tool is unavailable. `get_startup_health` returns the report and
+// `startup-health` is emitted once the checks have finished. The report also says what was
+// cleaned up after a crashed session (see workspaces.rs).
+
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use crate::platform;
+use crate::workspaces::{self, WorkspaceCleanup};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StartupIssue {
+    pub component: String, // "hotkey", "tray" or "binary"
+    pub message: String,   // shown to the user
+    pub detail: String,    // underlying error, for the log and bug reports
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StartupHealth {
+    pub checked: bool, // false until the background checks have finished
+    pub issues: Vec<StartupIssue>,
+    pub leftovers: WorkspaceCleanup, // job workspaces of crashed sessions removed
+}
+
+static HEALTH: Mutex<StartupHealth> = Mutex::new(StartupHealth {
+    checked: false,
+    issues: Vec::new(),
+    leftovers: WorkspaceCleanup {
+        removed: 0,
+        reclaimed_bytes: 0,
+    },
+});
+
+/// Record a startup failure
+pub fn record(component: &str, message: String, detail: String) {
+    log::warn!("Startup: {} ({})", message, detail);
+    HEALTH.lock().unwrap().issues.push(StartupIssue {
+        component: component.to_string(),
+        message,
+        detail,
+    });
+}
+
+/// Record that `hotkey` (e.g. "Alt+Q") couldn't be registered
+pub fn record_hotkey(purpose: &str, hotkey: &str, error: String) {
+    record(
+        "hotkey",
+        format!(
+            "{} is taken by another app, so the {} hotkey doesn't work. Pick a different one in Settings.",
+            hotkey, purpose
+        ),
+        error,
+    );
+}
+
+fn check_binary(name: &str, feature: &str, resolved: Result<PathBuf, String>) {
+    if let Err(e) = resolved {
+        record(
+            "binary",
+            format!("{} wasn't found, so {} won't work", name, feature),
+            e,
+        );
+    }
+}
+
+/// Check the external binaries and remove leftover workspaces in the background, then emit
+/// `startup-health` with the report
+pub fn init(app: &AppHandle) {
+    let app = app.clone();
+    std::thread::spawn(move || {
+        check_binary("FFmpeg", "media conversion", platform::get_ffmpeg_path());
+        check_binary("yt-dlp", "video downloads", platform::get_ytdlp_path());
+        let leftovers = workspaces::cleanup_on_startup(&app);
+
+        let report = {
+            let mut health = HEALTH.lock().unwrap();
+            health.checked = true;
+            health.leftovers = leftovers;
+            health.clone()
+        };
+        let _ = app.emit("startup-health", &report);
+    });
+}
+
+#[tauri::command]
+pub fn get_startup_health() -> StartupHealth {
+    HEALTH.lock().unwrap().clone()
+}
@@ -0,0 +1,181 @@
+// Clipboard-based suggestions
+// Each time the window is shown the clipboard is classified and the result is sent to the
+// launcher as a `clipboard-suggestion` event (null when there is nothing to suggest), so the
+// matching tool can be offered first with the clipboard value already filled in.
+
+use std::path::Path;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+use crate::AppState;
+
+const VIDEO_EXTENSIONS: [&str; 9] = [
+    "mp4", "mkv", "mov", "avi", "webm", "wmv", "flv", "m4v", "mpg",
+];
+
+// Longer clipboard contents are documents, not something to act on
+const MAX_SUGGESTION_TEXT: usize = 2000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SuggestionKind {
+    YoutubeUrl,
+    GitUrl,
+    Color,
+    Translation,
+    ConvertFile,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ClipboardSuggestion {
+    pub kind: SuggestionKind,
+    pub tool: String,             // tool id to offer first
+    pub value: String,            // URL, hex color (#RRGGBB), text or file path
+    pub label: String,            // short description shown in the launcher
+    pub language: Option<String>, // detected language name, for translations
+}
+
+impl ClipboardSuggestion {
+    fn new(kind: SuggestionKind, tool: &str, value: String, label: String) -> Self {
+        Self {
+            kind,
+            tool: tool.to_string(),
+            value,
+            label,
+            language: None,
+        }
+    }
+}
+
+fn url_host(url: &str) -> Option<String> {
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))?;
+    let host = rest.split(['/', '?', '#']).next()?.to_lowercase();
+    Some(host.strip_prefix("www.").unwrap_or(&host).to_string())
+}
+
+fn normalize_hex_color(text: &str) -> Option<String> {
+    let hex = text.strip_prefix('#').unwrap_or(text);
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    match hex.len() {
+        // Bare 6-digit strings are too often ids/hashes, so only accept them with a '#'
+        6 if text.starts_with('#') => Some(format!("#{}", hex.to_uppercase())),
+        3 if text.starts_with('#') => Some(format!(
+            "#{}",
+            hex.chars()
+                .flat_map(|c| [c, c])
+                .collect::<String>()
+                .to_uppercase()
+        )),
+        _ => None,
+    }
+}
+
+/// Classify clipboard text. `target_language` is the ISO 639-1 code translations go to;
+/// text already in that language isn't suggested for translation.
+pub fn suggest_for_text(text: &str, target_language: &str) -> Option<ClipboardSuggestion> {
+    let text = text.trim();
+    if text.is_empty() || text.len() > MAX_SUGGESTION_TEXT {
+        return None;
+    }
+
+    // Single-token values: URLs, colors, file paths
+    if !text.contains('\n') {
+        if let Some(host) = url_host(text) {
+            return match host.as_str() {
+                "youtube.com" | "m.youtube.com" | "music.youtube.com" | "youtu.be" => {
+                    Some(ClipboardSuggestion::new(
+                        SuggestionKind::YoutubeUrl,
+                        "youtube-downloader",
+                        text.to_string(),
+                        "Download the video from your clipboard".to_string(),
+                    ))
+                }
+                "github.com" => Some(ClipboardSuggestion::new(
+                    SuggestionKind::GitUrl,
+                    "git-downloader",
+                    text.to_string(),
+                    "Download the repository from your clipboard".to_string(),
+                )),
+                _ => None,
+            };
+        }
+
+        if let Some(hex) = normalize_hex_color(text) {
+            return Some(ClipboardSuggestion::new(
+                SuggestionKind::Color,
+                "color-picker",
+                hex.clone(),
+                format!("Show {} from your clipboard", hex),
+            ));
+        }
+
+        // Explorer's "Copy as path" wraps paths in quotes
+        let path = text.trim_matches('"');
+        let is_video = Path::new(path)
+            .extension()
+            .map(|ext| VIDEO_EXTENSIONS.contains(&ext.to_string_lossy().to_lowercase().as_str()))
+            .unwrap_or(false);
+        if is_video && Path::new(path).is_file() {
+            let name = Path::new(path)
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            return Some(ClipboardSuggestion::new(
+                SuggestionKind::ConvertFile,
+                "video-converter",
+                path.to_string(),
+                format!("Convert {}", name),
+            ));
+        }
+    }
+
+    // Prose in another language
+    let info = whatlang::detect(text)?;
+    if !info.is_reliable() || text.split_whitespace().count() < 2 {
+        return None;
+    }
+    if crate::lang_to_code(info.lang()).unwrap_or("en") == target_language {
+        return None;
+    }
+    let language = crate::lang_to_name(info.lang()).to_string();
+    Some(ClipboardSuggestion {
+        language: Some(language.clone()),
+        ..ClipboardSuggestion::new(
+            SuggestionKind::Translation,
+            "quick-translation",
+            text.to_string(),
+            format!("Translate the {} text from your clipboard", language),
+        )
+    })
+}
+
+/// Classify the clipboard and emit `clipboard-suggestion`; called whenever the window is shown
+pub fn emit_clipboard_suggestion(app: &AppHandle) {
+    let (enabled, target_language) = {
+        let state = app.state::<AppState>();
+        let settings = state.settings.lock().unwrap();
+        (
+            settings.clipboard_suggestions,
+            settings.quick_translation_target_language.clone(),
+        )
+    };
+    if !enabled {
+        return;
+    }
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let suggestion = app
+            .clipboard()
+            .read_text()
+            .ok()
+            .and_then(|text| suggest_for_text(&text, &target_language));
+        let _ = app.emit("clipboard-suggestion", suggestion);
+    });
+}
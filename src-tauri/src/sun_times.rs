@@ -0,0 +1,212 @@
+// Sunrise, sunset, golden hour and day length, computed locally
+// Uses the NOAA solar position equations (accurate to about a minute away from the poles).
+// Times are Unix timestamps: the UI formats them in the location's time zone when the geocoder
+// knows it, and the night light schedules against them directly.
+
+use chrono::{Local, NaiveDate};
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::geocoding::{self, Location};
+
+// Sun elevation (degrees) at the events; sunrise and sunset account for refraction and the
+// sun's radius
+const SUNRISE_ELEVATION: f64 = -0.833;
+const CIVIL_TWILIGHT_ELEVATION: f64 = -6.0;
+// Golden hour: the sun between 6° above and 4° below the horizon
+const GOLDEN_HOUR_HIGH: f64 = 6.0;
+const GOLDEN_HOUR_LOW: f64 = -4.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct TimeRange {
+    pub start: i64,
+    pub end: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Polar {
+    Day,   // the sun doesn't set
+    Night, // the sun doesn't rise
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SunTimes {
+    pub sunrise: Option<i64>,
+    pub sunset: Option<i64>,
+    pub solar_noon: i64,
+    pub dawn: Option<i64>, // civil twilight
+    pub dusk: Option<i64>,
+    pub golden_hour_morning: Option<TimeRange>,
+    pub golden_hour_evening: Option<TimeRange>,
+    pub day_length: u32, // seconds of daylight
+    pub polar: Option<Polar>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SunReport {
+    pub location: Location,
+    pub date: NaiveDate,
+    #[serde(flatten)]
+    pub times: SunTimes,
+}
+
+struct SolarPosition {
+    declination: f64,      // radians
+    equation_of_time: f64, // minutes
+}
+
+// Julian centuries since J2000 for `minutes` after 00:00 UTC on `date`
+fn julian_century(date: NaiveDate, minutes: f64) -> f64 {
+    let unix_days = (date - NaiveDate::from_ymd_opt(1970, 1, 1).unwrap()).num_days() as f64;
+    let julian_day = unix_days + 2440587.5 + minutes / 1440.0;
+    (julian_day - 2451545.0) / 36525.0
+}
+
+fn solar_position(t: f64) -> SolarPosition {
+    let mean_longitude = (280.46646 + t * (36000.76983 + t * 0.0003032)).rem_euclid(360.0);
+    let mean_anomaly = 357.52911 + t * (35999.05029 - 0.0001537 * t);
+    let eccentricity = 0.016708634 - t * (0.000042037 + 0.0000001267 * t);
+    let m = mean_anomaly.to_radians();
+    let center = m.sin() * (1.914602 - t * (0.004817 + 0.000014 * t))
+        + (2.0 * m).sin() * (0.019993 - 0.000101 * t)
+        + (3.0 * m).sin() * 0.000289;
+    let omega = (125.04 - 1934.136 * t).to_radians();
+    let apparent_longitude =
+        (mean_longitude + center - 0.00569 - 0.00478 * omega.sin()).to_radians();
+    let mean_obliquity =
+        23.0 + (26.0 + (21.448 - t * (46.815 + t * (0.00059 - t * 0.001813))) / 60.0) / 60.0;
+    let obliquity = (mean_obliquity + 0.00256 * omega.cos()).to_radians();
+
+    let declination = (obliquity.sin() * apparent_longitude.sin()).asin();
+    let y = (obliquity / 2.0).tan().powi(2);
+    let l0 = mean_longitude.to_radians();
+    let equation_of_time = 4.0
+        * (y * (2.0 * l0).sin() - 2.0 * eccentricity * m.sin()
+            + 4.0 * eccentricity * y * m.sin() * (2.0 * l0).cos()
+            - 0.5 * y * y * (4.0 * l0).sin()
+            - 1.25 * eccentricity * eccentricity * (2.0 * m).sin())
+        .to_degrees();
+    SolarPosition {
+        declination,
+        equation_of_time,
+    }
+}
+
+fn solar_noon_minutes(date: NaiveDate, longitude: f64) -> f64 {
+    // Refine once with the position at the first estimate
+    let estimate = 720.0 - 4.0 * longitude;
+    let position = solar_position(julian_century(date, estimate));
+    720.0 - 4.0 * longitude - position.equation_of_time
+}
+
+/// Minutes after 00:00 UTC on `date` at which the sun passes `elevation` (rising or setting),
+/// or None if it stays above or below it all day
+fn event_minutes(
+    date: NaiveDate,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+    rising: bool,
+) -> Option<f64> {
+    let lat = latitude.to_radians();
+    let mut minutes = solar_noon_minutes(date, longitude);
+    for _ in 0..3 {
+        let position = solar_position(julian_century(date, minutes));
+        let cos_hour_angle = (elevation.to_radians().sin()
+            - lat.sin() * position.declination.sin())
+            / (lat.cos() * position.declination.cos());
+        if !(-1.0..=1.0).contains(&cos_hour_angle) {
+            return None;
+        }
+        let hour_angle = cos_hour_angle.acos().to_degrees();
+        let noon = 720.0 - 4.0 * longitude - position.equation_of_time;
+        minutes = if rising {
+            noon - 4.0 * hour_angle
+        } else {
+            noon + 4.0 * hour_angle
+        };
+    }
+    Some(minutes)
+}
+
+/// Sun elevation in degrees at solar noon
+fn noon_elevation(date: NaiveDate, latitude: f64, longitude: f64) -> f64 {
+    let position = solar_position(julian_century(date, solar_noon_minutes(date, longitude)));
+    90.0 - (latitude.to_radians() - position.declination)
+        .abs()
+        .to_degrees()
+}
+
+/// Sun times for `date` (the local solar day) at the given coordinates
+pub fn sun_times(latitude: f64, longitude: f64, date: NaiveDate) -> SunTimes {
+    let midnight = date.and_hms_opt(0, 0, 0).unwrap().and_utc().timestamp();
+    let at = |minutes: f64| midnight + (minutes * 60.0).round() as i64;
+    let event = |elevation: f64, rising: bool| {
+        event_minutes(date, latitude, longitude, elevation, rising).map(at)
+    };
+
+    let solar_noon = at(solar_noon_minutes(date, longitude));
+    let sunrise = event(SUNRISE_ELEVATION, true);
+    let sunset = event(SUNRISE_ELEVATION, false);
+    let polar = match (sunrise, sunset) {
+        (Some(_), Some(_)) => None,
+        _ if noon_elevation(date, latitude, longitude) > SUNRISE_ELEVATION => Some(Polar::Day),
+        _ => Some(Polar::Night),
+    };
+    let day_length = match (sunrise, sunset, polar) {
+        (Some(rise), Some(set), _) => (set - rise).max(0) as u32,
+        (_, _, Some(Polar::Day)) => 86_400,
+        _ => 0,
+    };
+
+    // When the sun never climbs past 6°, the golden hours meet at noon
+    let golden = |rising: bool| {
+        let low = event(GOLDEN_HOUR_LOW, rising)?;
+        let high = event(GOLDEN_HOUR_HIGH, rising).unwrap_or(solar_noon);
+        Some(if rising {
+            TimeRange {
+                start: low,
+                end: high,
+            }
+        } else {
+            TimeRange {
+                start: high,
+                end: low,
+            }
+        })
+    };
+
+    SunTimes {
+        sunrise,
+        sunset,
+        solar_noon,
+        dawn: event(CIVIL_TWILIGHT_ELEVATION, true),
+        dusk: event(CIVIL_TWILIGHT_ELEVATION, false),
+        golden_hour_morning: golden(true),
+        golden_hour_evening: golden(false),
+        day_length,
+        polar,
+    }
+}
+
+/// Sun times at `location` (place name or coordinates, default the last one used) on `date`
+/// (YYYY-MM-DD, default today)
+#[tauri::command]
+pub async fn get_sun_times(
+    app: AppHandle,
+    location: Option<String>,
+    date: Option<String>,
+) -> Result<SunReport, String> {
+    let date = match date.as_deref().map(str::trim).filter(|d| !d.is_empty()) {
+        Some(date) => NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map_err(|_| format!("Invalid date: {}", date))?,
+        None => Local::now().date_naive(),
+    };
+    let location = geocoding::resolve(&app, location.as_deref()).await?;
+    Ok(SunReport {
+        times: sun_times(location.latitude, location.longitude, date),
+        location,
+        date,
+    })
+}
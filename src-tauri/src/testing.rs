@@ -0,0 +1,248 @@
+// Test support for the command layer, only built with the `test-harness` feature.
+// Wraps the internal parsers for the integration tests under `tests/` and provides a mock
+// app that records emitted events, so progress relays can be driven with captured
+// ffmpeg / yt-dlp output from `tests/fixtures`.
+
+use std::io::BufRead;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use tauri::test::{mock_app, MockRuntime};
+use tauri::{App, AppHandle, Emitter, Listener};
+
+pub use crate::jobs::{CancellationToken, JobKind, JobLimits, JobManager};
+pub use crate::power::{policy_for, BatteryPolicy, BatterySettings, JobPower};
+pub use crate::locale_preview::{preview as preview_locale, LocalePreview};
+pub use crate::network::{is_vpn_interface, NetworkStatus};
+pub use crate::network_discovery::{
+    merge as merge_sightings, oui_prefix, parse_oui_line, parse_ssdp_response, service_label,
+    split_workstation_name, vendor_of, vendors_for, DiscoveredDevice, Sighting,
+};
+pub use crate::palette_export::{render as render_palette, PaletteFormat};
+pub use crate::path_policy::canonicalize_destination;
+pub use crate::platform::{
+    AudioCaptureInput, NetworkInfo, NetworkInterface, PortProcess, PowerStatus,
+    ScreenCaptureBackend,
+};
+pub use crate::annotation::{render as render_annotations, Annotation};
+pub use crate::banner::render as render_banner;
+pub use crate::calculator::{
+    apply_memory, format_number, push_entry, tape as calc_tape, CalcEntry, CalcHistory, MemoryOp,
+};
+pub use crate::clipboard_math::{parse_numbers, NumberSummary};
+pub use crate::screen_translate::clean_recognized_text;
+pub use crate::sun_times::{sun_times, Polar, SunTimes, TimeRange};
+pub use crate::app_lock::{hash_passcode, verify_passcode};
+pub use crate::clipboard_share::{
+    open_payload, seal_payload, share_key, Payload as SharePayload, SeenMessages,
+};
+pub use crate::glossary::{
+    entries_for as glossary_entries_for, protect as protect_glossary_terms,
+    restore as restore_glossary_terms, GlossaryEntry,
+};
+pub use crate::mdns::{
+    encode_query as encode_mdns_query, encode_response as encode_mdns_response,
+    parse as parse_mdns, service_instances, Record as MdnsRecord, RecordData as MdnsRecordData,
+};
+pub use crate::secrets::{derive_key as derive_secrets_key, open as open_secret, seal as seal_secret};
+pub use crate::translation_history::{
+    add_entry as add_translation, search as search_translations, TranslationHistoryEntry,
+};
+pub use crate::snippets::{
+    delete_snippet, delete_snippet_transform, expand as expand_snippet, save_snippet_transform,
+    Context as SnippetContext, CustomTransform as SnippetTransform,
+};
+pub use crate::offline_translation::{
+    parse_model_list, route as translation_route, Model as TranslationModel,
+};
+pub use crate::night_light::{schedule_at as night_light_schedule_at, whitepoint};
+pub use crate::time_tracking::{
+    build_report as build_time_report, range_days, to_csv as time_entries_csv, tracked_within,
+    IdlePeriod, ReportRange, TimeEntry, TimeLog,
+};
+pub use crate::color_formats::{
+    contrast, describe as describe_color, format as format_color, parse as parse_color,
+};
+pub use crate::conversion_queue::{
+    ConversionItem, ConversionQueue, QueueStatus as ConversionStatus,
+};
+pub use crate::countries::{
+    cache_is_fresh as holiday_cache_is_fresh, find as find_country, flag as country_flag,
+    lookup as lookup_country, parse_holidays, Country, PublicHoliday,
+};
+pub use crate::file_associations::{parse_download_list, parse_palette};
+pub use crate::geocoding::parse_coordinates;
+pub use crate::habits::{
+    find_habit, habit_id, parse_reminder, streaks as habit_streaks, Habit, HabitStore,
+};
+pub use crate::number_words::spell as number_to_words;
+pub use crate::project_files::{
+    combine as combine_gitignore, editorconfig, embedded_sections as gitignore_sections,
+};
+pub use crate::qr_payloads::{build as build_qr_payload, QrPayload, VcardFields, WifiSecurity};
+pub use crate::qr_decoder::{correct_errors, decode_matrix, Matrix};
+pub use crate::qr_scan::{classify as classify_scanned_code, scan_image as scan_qr_image, ScannedCode};
+pub use crate::random_tools::{parse_dice, roll_terms, DiceTerm};
+pub use crate::reference::{lookup as lookup_reference, ReferenceKind};
+pub use crate::screen_recording::{
+    ffmpeg_args as screen_recording_args, RecordingFormat, RecordingOptions, Region,
+};
+pub use crate::actions::Action;
+pub use crate::desktop_shortcuts::{default_shortcut_name, shortcut_action};
+pub use crate::platform::shortcut_file_stem;
+pub use crate::shell_integration::{
+    launch_args, launched_hidden, parse_launch_action, parse_launch_args, OpenFilesRequest,
+};
+pub use crate::suggestions::{suggest_for_text, ClipboardSuggestion, SuggestionKind};
+pub use crate::text_transforms::apply as apply_text_transform;
+pub use crate::wake_on_lan::{magic_packet, parse_mac};
+pub use crate::webhook::{
+    check_freshness as check_webhook_freshness, parse_head as parse_http_head, sign as sign_webhook,
+    verify_signature as verify_webhook_signature, RecentSignatures, WebhookAction,
+};
+pub use crate::language_detection::{detect as detect_language, LanguageDetection};
+pub use crate::file_translation::{
+    join_document, protect_markup, restore_markup, split_chunks, split_document, translated_path,
+    FileFormat, Piece,
+};
+pub use crate::translation_cache::{
+    add as add_cached_translation, lookup as lookup_cached_translation, CachedTranslation,
+    TokenBucket,
+};
+pub use crate::transcription::{
+    parse_progress as parse_whisper_progress, parse_whisper_json, render_subtitles, Segment,
+    SubtitleFormat,
+};
+pub use crate::dictionary::{parse_entries as parse_dictionary_entries, WordDefinition};
+pub use crate::voice_input::clean_utterance;
+pub use crate::audio_recording::{
+    ffmpeg_args as audio_recording_args, meter_level, parse_peak, AudioFormat,
+};
+pub use crate::crypto_prices::{coin_id, rate_from_prices};
+pub use crate::currency::{
+    multi_targets, parse_time_series, percent_change, RatePoint, RateTable,
+};
+pub use crate::expressions::{evaluate as evaluate_expression, uses_currency};
+pub use crate::external_windows::opacity_byte;
+pub use crate::onboarding::unlocked_tips;
+pub use crate::time_zones::{
+    convert_at as convert_time_at, convert_query_at as convert_time_query_at, decode_epoch,
+    parse_datetime, relative as relative_time, Zone,
+};
+pub use crate::transfers::{
+    add_usage, month_total, over_cap, stats as transfer_stats, CapAction, SavedTransfers,
+    TransferCap, TransferRange, Usage,
+};
+pub use crate::units::{convert as convert_unit, parse_query as parse_unit_query, UnitCategory};
+pub use crate::workspaces::{create_in as create_workspace_in, persist, remove_orphans};
+pub use crate::YouTubeDownloadProgress;
+
+#[cfg(target_os = "windows")]
+pub use crate::platform::parse_netstat_line;
+#[cfg(target_os = "linux")]
+pub use crate::platform::{
+    parse_ip_addr_json, parse_proc_net_arp, parse_proc_net_route, parse_resolv_conf,
+    parse_ss_line,
+};
+
+pub fn parse_time_from_progress(line: &str) -> Option<f64> {
+    crate::parse_time_from_progress(line)
+}
+
+pub fn parse_ytdlp_progress(line: &str) -> YouTubeDownloadProgress {
+    crate::parse_ytdlp_progress(line)
+}
+
+pub fn parse_ytdlp_output_path(line: &str) -> Option<String> {
+    crate::parse_ytdlp_output_path(line)
+}
+
+pub fn build_format_selector(quality: &str, mode: &str) -> String {
+    crate::build_format_selector(quality, mode)
+}
+
+pub fn relay_ffmpeg_progress(
+    app: &AppHandle<MockRuntime>,
+    reader: impl BufRead,
+    total_duration: f64,
+) {
+    crate::relay_ffmpeg_progress(reader, total_duration, |percent| {
+        let _ = app.emit("conversion-progress", percent);
+    })
+}
+
+/// Register a job with `manager` without an app, as `start_job` does before waiting for a slot
+pub fn register_job(manager: &JobManager, kind: JobKind) -> u64 {
+    manager.register(kind, format!("{:?}", kind)).0
+}
+
+pub fn finish_job(manager: &JobManager, id: u64) {
+    manager.finish(id, false)
+}
+
+/// Lock or unlock the app without a passcode, for the commands the lock protects
+pub fn set_app_locked(locked: bool) {
+    crate::app_lock::LOCKED.store(locked, std::sync::atomic::Ordering::SeqCst);
+}
+
+pub fn relay_ytdlp_output(
+    app: &AppHandle<MockRuntime>,
+    reader: impl BufRead,
+    token: &CancellationToken,
+) -> Result<Option<String>, String> {
+    crate::relay_ytdlp_output(app, reader, token, |_| {})
+}
+
+/// Path of a file under `tests/fixtures`
+pub fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name)
+}
+
+/// Contents of a file under `tests/fixtures`
+pub fn fixture(name: &str) -> String {
+    let path = fixture_path(name);
+    std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("Failed to read fixture {:?}: {}", path, e))
+}
+
+/// Mock app that records the payloads of the events it was asked to capture
+pub struct EventCapture {
+    app: App<MockRuntime>,
+    events: Arc<Mutex<Vec<(String, serde_json::Value)>>>,
+}
+
+impl EventCapture {
+    pub fn new(event_names: &[&str]) -> Self {
+        let app = mock_app();
+        let events: Arc<Mutex<Vec<(String, serde_json::Value)>>> = Arc::default();
+
+        for name in event_names {
+            let events = events.clone();
+            let name = name.to_string();
+            app.listen_any(name.clone(), move |event| {
+                let payload = serde_json::from_str(event.payload()).unwrap_or_default();
+                events.lock().unwrap().push((name.clone(), payload));
+            });
+        }
+
+        Self { app, events }
+    }
+
+    pub fn handle(&self) -> &AppHandle<MockRuntime> {
+        self.app.handle()
+    }
+
+    /// Payloads emitted for `name`, in order
+    pub fn payloads(&self, name: &str) -> Vec<serde_json::Value> {
+        self.events
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(event, _)| event == name)
+            .map(|(_, payload)| payload.clone())
+            .collect()
+    }
+}
@@ -0,0 +1,267 @@
+// Text transforms: Morse code, NATO spelling, ROT13, leetspeak...
+// Every transform is an entry in `TRANSFORMS`, so the launcher, command mode and the
+// clipboard transform all share one list; adding a transform is adding an entry there.
+
+use serde::Serialize;
+use tauri::AppHandle;
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TextTransform {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    #[serde(skip)]
+    apply: fn(&str) -> Result<String, String>,
+}
+
+pub const TRANSFORMS: &[TextTransform] = &[
+    TextTransform {
+        id: "morse",
+        name: "Morse Code",
+        description: "Encode text as Morse code",
+        apply: morse_encode,
+    },
+    TextTransform {
+        id: "unmorse",
+        name: "Decode Morse",
+        description: "Decode Morse code (dots, dashes, '/' between words)",
+        apply: morse_decode,
+    },
+    TextTransform {
+        id: "nato",
+        name: "NATO Alphabet",
+        description: "Spell text with the NATO phonetic alphabet",
+        apply: nato_spell,
+    },
+    TextTransform {
+        id: "rot13",
+        name: "ROT13",
+        description: "Rotate letters by 13 places (applying it twice restores the text)",
+        apply: rot13,
+    },
+    TextTransform {
+        id: "leet",
+        name: "Leetspeak",
+        description: "Replace letters with look-alike digits",
+        apply: leetspeak,
+    },
+    TextTransform {
+        id: "reverse",
+        name: "Reverse",
+        description: "Reverse the text, keeping accents on their letters",
+        apply: reverse,
+    },
+    TextTransform {
+        id: "unzalgo",
+        name: "Strip Zalgo",
+        description: "Remove the marks stacked on letters of glitched text, keeping accents",
+        apply: strip_zalgo,
+    },
+];
+
+#[rustfmt::skip]
+const MORSE: &[(char, &str)] = &[
+    ('A', ".-"), ('B', "-..."), ('C', "-.-."), ('D', "-.."), ('E', "."), ('F', "..-."),
+    ('G', "--."), ('H', "...."), ('I', ".."), ('J', ".---"), ('K', "-.-"), ('L', ".-.."),
+    ('M', "--"), ('N', "-."), ('O', "---"), ('P', ".--."), ('Q', "--.-"), ('R', ".-."),
+    ('S', "..."), ('T', "-"), ('U', "..-"), ('V', "...-"), ('W', ".--"), ('X', "-..-"),
+    ('Y', "-.--"), ('Z', "--.."),
+    ('0', "-----"), ('1', ".----"), ('2', "..---"), ('3', "...--"), ('4', "....-"),
+    ('5', "....."), ('6', "-...."), ('7', "--..."), ('8', "---.."), ('9', "----."),
+    ('.', ".-.-.-"), (',', "--..--"), ('?', "..--.."), ('\'', ".----."), ('!', "-.-.--"),
+    ('/', "-..-."), ('(', "-.--."), (')', "-.--.-"), ('&', ".-..."), (':', "---..."),
+    (';', "-.-.-."), ('=', "-...-"), ('+', ".-.-."), ('-', "-....-"), ('_', "..--.-"),
+    ('"', ".-..-."), ('$', "...-..-"), ('@', ".--.-."),
+];
+
+#[rustfmt::skip]
+const NATO: [&str; 26] = [
+    "Alfa", "Bravo", "Charlie", "Delta", "Echo", "Foxtrot", "Golf", "Hotel", "India",
+    "Juliett", "Kilo", "Lima", "Mike", "November", "Oscar", "Papa", "Quebec", "Romeo",
+    "Sierra", "Tango", "Uniform", "Victor", "Whiskey", "X-ray", "Yankee", "Zulu",
+];
+
+// Digits as radio operators say them ("niner" so it isn't confused with "nein")
+#[rustfmt::skip]
+const NATO_DIGITS: [&str; 10] = [
+    "Zero", "One", "Two", "Three", "Four", "Five", "Six", "Seven", "Eight", "Niner",
+];
+
+fn morse_encode(text: &str) -> Result<String, String> {
+    let words: Vec<String> = text
+        .split_whitespace()
+        .map(|word| {
+            word.chars()
+                .map(|c| {
+                    let upper = c.to_ascii_uppercase();
+                    MORSE
+                        .iter()
+                        .find(|(letter, _)| *letter == upper)
+                        .map(|(_, code)| *code)
+                        .ok_or_else(|| format!("'{}' has no Morse code", c))
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .map(|codes| codes.join(" "))
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(words.join(" / "))
+}
+
+fn morse_decode(text: &str) -> Result<String, String> {
+    // Accept the dot/dash look-alikes that word processors and chat apps substitute
+    let normalized: String = text
+        .chars()
+        .map(|c| match c {
+            '·' | '•' | '∙' => '.',
+            '−' | '–' | '—' | '_' => '-',
+            '|' => '/',
+            c => c,
+        })
+        .collect();
+
+    let words: Vec<String> = normalized
+        .split('/')
+        .filter(|word| !word.trim().is_empty())
+        .map(|word| {
+            word.split_whitespace()
+                .map(|code| {
+                    MORSE
+                        .iter()
+                        .find(|(_, known)| *known == code)
+                        .map(|(letter, _)| *letter)
+                        .ok_or_else(|| format!("'{}' is not a Morse code", code))
+                })
+                .collect::<Result<String, _>>()
+        })
+        .collect::<Result<_, _>>()?;
+    if words.is_empty() {
+        return Err("No Morse code found".to_string());
+    }
+    Ok(words.join(" "))
+}
+
+fn nato_spell(text: &str) -> Result<String, String> {
+    let words: Vec<String> = text
+        .split_whitespace()
+        .map(|word| {
+            word.chars()
+                .map(|c| match c.to_ascii_uppercase() {
+                    c @ 'A'..='Z' => NATO[(c as u8 - b'A') as usize].to_string(),
+                    c @ '0'..='9' => NATO_DIGITS[(c as u8 - b'0') as usize].to_string(),
+                    c => c.to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect();
+    Ok(words.join(" / "))
+}
+
+fn rot13(text: &str) -> Result<String, String> {
+    Ok(text
+        .chars()
+        .map(|c| match c {
+            'a'..='z' => ((c as u8 - b'a' + 13) % 26 + b'a') as char,
+            'A'..='Z' => ((c as u8 - b'A' + 13) % 26 + b'A') as char,
+            c => c,
+        })
+        .collect())
+}
+
+fn leetspeak(text: &str) -> Result<String, String> {
+    Ok(text
+        .chars()
+        .map(|c| match c.to_ascii_lowercase() {
+            'a' => '4',
+            'b' => '8',
+            'e' => '3',
+            'g' => '9',
+            'i' | 'l' => '1',
+            'o' => '0',
+            's' => '5',
+            't' => '7',
+            _ => c,
+        })
+        .collect())
+}
+
+/// Combining diacritical marks: accents, and the stacks that make up "zalgo" text
+fn is_combining_mark(c: char) -> bool {
+    matches!(
+        c,
+        '\u{0300}'..='\u{036F}'
+            | '\u{0483}'..='\u{0489}'
+            | '\u{1AB0}'..='\u{1AFF}'
+            | '\u{1DC0}'..='\u{1DFF}'
+            | '\u{20D0}'..='\u{20FF}'
+            | '\u{FE20}'..='\u{FE2F}'
+    )
+}
+
+fn reverse(text: &str) -> Result<String, String> {
+    // Reverse base characters together with the marks that follow them
+    let mut clusters: Vec<String> = Vec::new();
+    for c in text.chars() {
+        match clusters.last_mut() {
+            Some(cluster) if is_combining_mark(c) => cluster.push(c),
+            _ => clusters.push(c.to_string()),
+        }
+    }
+    Ok(clusters.into_iter().rev().collect())
+}
+
+// Decomposed (NFD) text, e.g. Vietnamese or file names from macOS, puts up to two accents on a
+// letter; anything stacked beyond that is glitch
+const MAX_MARKS_PER_LETTER: usize = 2;
+
+fn strip_zalgo(text: &str) -> Result<String, String> {
+    let mut marks = 0;
+    Ok(text
+        .chars()
+        .filter(|&c| {
+            if !is_combining_mark(c) {
+                marks = 0;
+                return true;
+            }
+            marks += 1;
+            marks <= MAX_MARKS_PER_LETTER
+        })
+        .collect())
+}
+
+/// Run the transform with the given id on `text`
+pub fn apply(transform: &str, text: &str) -> Result<String, String> {
+    let transform = TRANSFORMS
+        .iter()
+        .find(|t| t.id.eq_ignore_ascii_case(transform))
+        .ok_or_else(|| format!("Unknown transform: {}", transform))?;
+    if text.trim().is_empty() {
+        return Err("No text to transform".to_string());
+    }
+    (transform.apply)(text)
+}
+
+#[tauri::command]
+pub fn transform_text(text: String, transform: String) -> Result<String, String> {
+    apply(&transform, &text)
+}
+
+#[tauri::command]
+pub fn list_text_transforms() -> Vec<TextTransform> {
+    TRANSFORMS.to_vec()
+}
+
+/// Transform the clipboard text in place, returning the new clipboard contents
+#[tauri::command]
+pub fn transform_clipboard(app: AppHandle, transform: String) -> Result<String, String> {
+    let text = app
+        .clipboard()
+        .read_text()
+        .map_err(|e| format!("Failed to read clipboard: {}", e))?;
+    let result = apply(&transform, &text)?;
+    app.clipboard()
+        .write_text(result.clone())
+        .map_err(|e| format!("Failed to write clipboard: {}", e))?;
+    Ok(result)
+}
@@ -0,0 +1,417 @@
+// Time tracking
+// One entry runs at a time, labelled and optionally filed under a project. Entries live in
+// time_tracking.json, so a running entry survives restarts. While an entry runs, the session
+// state (see session_state) is followed: time spent idle or locked is recorded as an idle
+// period on the entry and left out of its duration. Reports total a day or week by project and
+// by day in local time, and entries can be exported as CSV.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use chrono::{DateTime, Datelike, Days, Local, NaiveDate, TimeZone};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::session_state;
+
+const TIME_TRACKING_FILE: &str = "time_tracking.json";
+// Matches the session state poll
+const IDLE_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IdlePeriod {
+    pub start: i64, // seconds since the epoch
+    pub end: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimeEntry {
+    pub id: u64,
+    pub label: String,
+    pub project: Option<String>,
+    pub start: i64,       // seconds since the epoch
+    pub end: Option<i64>, // None while running
+    #[serde(default)]
+    pub idle: Vec<IdlePeriod>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TimeLog {
+    pub entries: Vec<TimeEntry>, // oldest first; only the last one can be running
+    pub next_id: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportRange {
+    Today,
+    Yesterday,
+    Week, // Monday to Sunday
+    LastWeek,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectTotal {
+    pub project: String, // "" for entries without a project
+    pub seconds: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DayTotal {
+    pub date: String, // YYYY-MM-DD
+    pub seconds: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TimeReport {
+    pub range: ReportRange,
+    pub from: String, // first day, YYYY-MM-DD
+    pub to: String,   // last day
+    pub total_seconds: u64,
+    pub idle_seconds: u64,           // left out of the totals
+    pub projects: Vec<ProjectTotal>, // longest first
+    pub days: Vec<DayTotal>,         // every day of the range, in order
+    pub entries: Vec<TimeEntry>,     // entries overlapping the range
+}
+
+/// Seconds of `[start, end)` inside `[from, to)`
+fn overlap(start: i64, end: i64, from: i64, to: i64) -> u64 {
+    (end.min(to) - start.max(from)).max(0) as u64
+}
+
+/// Idle seconds of `entry` inside `[from, to)`
+pub fn idle_within(entry: &TimeEntry, from: i64, to: i64) -> u64 {
+    entry
+        .idle
+        .iter()
+        .map(|p| overlap(p.start, p.end, from, to))
+        .sum()
+}
+
+/// Tracked (non-idle) seconds of `entry` inside `[from, to)`
+pub fn tracked_within(entry: &TimeEntry, from: i64, to: i64, now: i64) -> u64 {
+    let end = entry.end.unwrap_or(now);
+    overlap(entry.start, end, from, to).saturating_sub(idle_within(entry, from, to))
+}
+
+/// Days covered by `range`, given today's date
+pub fn range_days(range: ReportRange, today: NaiveDate) -> Vec<NaiveDate> {
+    let monday = today - Days::new(u64::from(today.weekday().num_days_from_monday()));
+    let (first, count) = match range {
+        ReportRange::Today => (today, 1),
+        ReportRange::Yesterday => (today - Days::new(1), 1),
+        ReportRange::Week => (monday, 7),
+        ReportRange::LastWeek => (monday - Days::new(7), 7),
+    };
+    (0..count).map(|i| first + Days::new(i)).collect()
+}
+
+/// Start of `date` in `tz`, as seconds since the epoch
+fn day_start<Tz: TimeZone>(tz: &Tz, date: NaiveDate) -> i64 {
+    let midnight = date.and_hms_opt(0, 0, 0).unwrap_or_default();
+    tz.from_local_datetime(&midnight)
+        .earliest()
+        // Midnight can fall into a DST gap; the day then starts an hour later
+        .or_else(|| {
+            tz.from_local_datetime(&(midnight + chrono::Duration::hours(1)))
+                .earliest()
+        })
+        .map(|t| t.timestamp())
+        .unwrap_or_else(|| midnight.and_utc().timestamp())
+}
+
+/// Totals for `range` in the time zone `tz`
+pub fn build_report<Tz: TimeZone>(
+    log: &TimeLog,
+    range: ReportRange,
+    today: NaiveDate,
+    tz: &Tz,
+    now: i64,
+) -> TimeReport {
+    let dates = range_days(range, today);
+    let bounds: Vec<(NaiveDate, i64, i64)> = dates
+        .iter()
+        .map(|&date| {
+            let next = date + Days::new(1);
+            (date, day_start(tz, date), day_start(tz, next))
+        })
+        .collect();
+    let from = bounds.first().map(|b| b.1).unwrap_or(now);
+    let to = bounds.last().map(|b| b.2).unwrap_or(now);
+
+    let entries: Vec<TimeEntry> = log
+        .entries
+        .iter()
+        .filter(|e| e.start < to && e.end.unwrap_or(now) > from)
+        .cloned()
+        .collect();
+
+    let mut projects: BTreeMap<String, u64> = BTreeMap::new();
+    let mut idle_seconds = 0;
+    for entry in &entries {
+        let project = entry.project.clone().unwrap_or_default();
+        *projects.entry(project).or_default() += tracked_within(entry, from, to, now);
+        idle_seconds += idle_within(entry, from, to);
+    }
+    let mut projects: Vec<ProjectTotal> = projects
+        .into_iter()
+        .map(|(project, seconds)| ProjectTotal { project, seconds })
+        .collect();
+    projects.sort_by_key(|p| std::cmp::Reverse(p.seconds));
+
+    let days: Vec<DayTotal> = bounds
+        .iter()
+        .map(|&(date, start, end)| DayTotal {
+            date: date.format("%Y-%m-%d").to_string(),
+            seconds: entries
+                .iter()
+                .map(|e| tracked_within(e, start, end, now))
+                .sum(),
+        })
+        .collect();
+
+    TimeReport {
+        range,
+        from: dates
+            .first()
+            .map(|d| d.format("%Y-%m-%d").to_string())
+            .unwrap_or_default(),
+        to: dates
+            .last()
+            .map(|d| d.format("%Y-%m-%d").to_string())
+            .unwrap_or_default(),
+        total_seconds: days.iter().map(|d| d.seconds).sum(),
+        idle_seconds,
+        projects,
+        days,
+        entries,
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Entries as CSV with local RFC 3339 times; durations leave out idle time
+pub fn to_csv<Tz: TimeZone>(entries: &[TimeEntry], tz: &Tz, now: i64) -> String
+where
+    Tz::Offset: std::fmt::Display,
+{
+    let time = |secs: i64| {
+        DateTime::from_timestamp(secs, 0)
+            .map(|t| t.with_timezone(tz).to_rfc3339())
+            .unwrap_or_default()
+    };
+    let mut out = String::from("label,project,start,end,duration_seconds,idle_seconds\n");
+    for entry in entries {
+        let end = entry.end.unwrap_or(now);
+        out.push_str(&format!(
+            "{},{},{},{},{},{}\n",
+            csv_field(&entry.label),
+            csv_field(entry.project.as_deref().unwrap_or("")),
+            time(entry.start),
+            entry.end.map(time).unwrap_or_default(),
+            tracked_within(entry, entry.start, end, now),
+            idle_within(entry, entry.start, end),
+        ));
+    }
+    out
+}
+
+fn now_secs() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+// Serializes read-modify-write of the log between commands and the idle watcher
+static LOG_LOCK: Mutex<()> = Mutex::new(());
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+fn load(app: &AppHandle) -> TimeLog {
+    crate::load_json_file(app, TIME_TRACKING_FILE)
+}
+
+fn save(app: &AppHandle, log: &TimeLog) -> Result<(), String> {
+    crate::save_json_file(app, TIME_TRACKING_FILE, log)
+}
+
+fn running(log: &mut TimeLog) -> Option<&mut TimeEntry> {
+    log.entries.last_mut().filter(|e| e.end.is_none())
+}
+
+/// Close the running entry at `now`, including an idle period still open
+fn close_running(log: &mut TimeLog, now: i64, idle_since: Option<i64>) -> Option<TimeEntry> {
+    let entry = running(log)?;
+    if let Some(since) = idle_since.filter(|&s| s < now) {
+        entry.idle.push(IdlePeriod {
+            start: since.max(entry.start),
+            end: now,
+        });
+    }
+    entry.end = Some(now);
+    Some(entry.clone())
+}
+
+/// Follow idle / lock state while an entry runs
+fn watch_idle(app: AppHandle) {
+    let generation = GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    std::thread::spawn(move || {
+        let mut idle_since: Option<i64> = None;
+        loop {
+            std::thread::sleep(IDLE_POLL_INTERVAL);
+            if GENERATION.load(Ordering::SeqCst) != generation {
+                return;
+            }
+
+            let state = session_state::get_session_state();
+            let now = now_secs();
+            match (state.idle || state.locked, idle_since) {
+                (true, None) => idle_since = Some(now - state.idle_seconds as i64),
+                (false, Some(since)) => {
+                    idle_since = None;
+                    let _guard = LOG_LOCK.lock().unwrap();
+                    if GENERATION.load(Ordering::SeqCst) != generation {
+                        return;
+                    }
+                    let mut log = load(&app);
+                    let Some(entry) = running(&mut log) else {
+                        return;
+                    };
+                    let period = IdlePeriod {
+                        start: since.max(entry.start),
+                        end: now,
+                    };
+                    entry.idle.push(period);
+                    let entry = entry.clone();
+                    if let Err(e) = save(&app, &log) {
+                        log::warn!("Failed to save time tracking: {}", e);
+                    }
+                    log::info!(
+                        "Time tracking: {}s idle left out of \"{}\"",
+                        period.end - period.start,
+                        entry.label
+                    );
+                    let _ = app.emit("time-entry-idle", &entry);
+                }
+                _ => {}
+            }
+        }
+    });
+}
+
+/// Resume idle detection for an entry left running by the previous run
+pub fn init(app: &AppHandle) {
+    let mut log = load(app);
+    if running(&mut log).is_some() {
+        watch_idle(app.clone());
+    }
+}
+
+#[tauri::command]
+pub fn get_running_time_entry(app: AppHandle) -> Option<TimeEntry> {
+    let mut log = load(&app);
+    running(&mut log).cloned()
+}
+
+/// Start an entry, stopping the one that's running
+#[tauri::command]
+pub fn start_time_entry(
+    app: AppHandle,
+    label: String,
+    project: Option<String>,
+) -> Result<TimeEntry, String> {
+    let label = label.trim().to_string();
+    if label.is_empty() {
+        return Err("Give the entry a label".to_string());
+    }
+    let project = project
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty());
+
+    let entry = {
+        let _guard = LOG_LOCK.lock().unwrap();
+        let mut log = load(&app);
+        let now = now_secs();
+        close_running(&mut log, now, None);
+        log.next_id += 1;
+        let entry = TimeEntry {
+            id: log.next_id,
+            label,
+            project,
+            start: now,
+            end: None,
+            idle: Vec::new(),
+        };
+        log.entries.push(entry.clone());
+        save(&app, &log)?;
+        entry
+    };
+
+    watch_idle(app.clone());
+    let _ = app.emit("time-entry-changed", Some(&entry));
+    Ok(entry)
+}
+
+/// Stop the running entry and return it
+#[tauri::command]
+pub fn stop_time_entry(app: AppHandle) -> Result<TimeEntry, String> {
+    let _guard = LOG_LOCK.lock().unwrap();
+    GENERATION.fetch_add(1, Ordering::SeqCst);
+
+    let mut log = load(&app);
+    let now = now_secs();
+    // Still idle when stopped from a hotkey or trigger: the open idle period ends now
+    let state = session_state::get_session_state();
+    let idle_since = (state.idle || state.locked).then(|| now - state.idle_seconds as i64);
+    let entry = close_running(&mut log, now, idle_since)
+        .ok_or_else(|| "No time entry is running".to_string())?;
+    save(&app, &log)?;
+
+    let _ = app.emit("time-entry-changed", None::<TimeEntry>);
+    Ok(entry)
+}
+
+#[tauri::command]
+pub fn get_time_report(app: AppHandle, range: ReportRange) -> Result<TimeReport, String> {
+    crate::app_lock::ensure_unlocked()?;
+    let log = load(&app);
+    Ok(build_report(
+        &log,
+        range,
+        Local::now().date_naive(),
+        &Local,
+        now_secs(),
+    ))
+}
+
+/// Write entries to `path` as CSV; all of them, or those overlapping `range`
+#[tauri::command]
+pub async fn export_time_entries(
+    app: AppHandle,
+    path: String,
+    range: Option<ReportRange>,
+) -> Result<String, String> {
+    crate::app_lock::ensure_unlocked()?;
+    let log = load(&app);
+    let now = now_secs();
+    let entries = match range {
+        Some(range) => build_report(&log, range, Local::now().date_naive(), &Local, now).entries,
+        None => log.entries,
+    };
+    if entries.is_empty() {
+        return Err("There are no time entries to export".to_string());
+    }
+    let path = crate::path_policy::validate_write_path(&app, &path)?;
+    fs::write(&path, to_csv(&entries, &Local, now))
+        .map_err(|e| format!("Failed to write time entries: {}", e))?;
+    Ok(path.to_string_lossy().to_string())
+}
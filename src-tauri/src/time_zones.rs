@@ -0,0 +1,464 @@
+// Time zone and Unix timestamp conversion, offline with the IANA database from chrono-tz.
+// Zones are found by IANA name ("Europe/Berlin"), city ("Berlin", "new york"), common
+// abbreviation ("PST", "CET") or offset ("UTC+5:30"). Abbreviations stand for their region's
+// time, daylight saving included, so "3pm PST" in July is 3pm Pacific (PDT) time. Launcher
+// queries like "3pm PST in Berlin" or "time in tokyo" go through `convert_time_query`.
+
+use chrono::{
+    DateTime, Days, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeZone, Utc,
+};
+use chrono_tz::{Tz, TZ_VARIANTS};
+use serde::Serialize;
+
+// Abbreviation -> the zone it is usually meant for
+const ABBREVIATIONS: &[(&str, &str)] = &[
+    ("pst", "America/Los_Angeles"),
+    ("pdt", "America/Los_Angeles"),
+    ("pt", "America/Los_Angeles"),
+    ("pacific", "America/Los_Angeles"),
+    ("mst", "America/Denver"),
+    ("mdt", "America/Denver"),
+    ("mt", "America/Denver"),
+    ("cst", "America/Chicago"),
+    ("cdt", "America/Chicago"),
+    ("ct", "America/Chicago"),
+    ("est", "America/New_York"),
+    ("edt", "America/New_York"),
+    ("et", "America/New_York"),
+    ("eastern", "America/New_York"),
+    ("akst", "America/Anchorage"),
+    ("hst", "Pacific/Honolulu"),
+    ("gmt", "UTC"),
+    ("utc", "UTC"),
+    ("z", "UTC"),
+    ("bst", "Europe/London"),
+    ("wet", "Europe/Lisbon"),
+    ("west", "Europe/Lisbon"),
+    ("cet", "Europe/Berlin"),
+    ("cest", "Europe/Berlin"),
+    ("eet", "Europe/Athens"),
+    ("eest", "Europe/Athens"),
+    ("msk", "Europe/Moscow"),
+    ("ist", "Asia/Kolkata"),
+    ("pkt", "Asia/Karachi"),
+    ("sgt", "Asia/Singapore"),
+    ("hkt", "Asia/Hong_Kong"),
+    ("jst", "Asia/Tokyo"),
+    ("kst", "Asia/Seoul"),
+    ("aest", "Australia/Sydney"),
+    ("aedt", "Australia/Sydney"),
+    ("acst", "Australia/Adelaide"),
+    ("awst", "Australia/Perth"),
+    ("nzst", "Pacific/Auckland"),
+    ("nzdt", "Pacific/Auckland"),
+    ("brt", "America/Sao_Paulo"),
+    ("art", "America/Argentina/Buenos_Aires"),
+];
+
+// Large cities the IANA database names no zone after
+const CITIES: &[(&str, &str)] = &[
+    ("san francisco", "America/Los_Angeles"),
+    ("seattle", "America/Los_Angeles"),
+    ("las vegas", "America/Los_Angeles"),
+    ("washington", "America/New_York"),
+    ("boston", "America/New_York"),
+    ("miami", "America/New_York"),
+    ("atlanta", "America/New_York"),
+    ("dallas", "America/Chicago"),
+    ("houston", "America/Chicago"),
+    ("montreal", "America/Toronto"),
+    ("munich", "Europe/Berlin"),
+    ("frankfurt", "Europe/Berlin"),
+    ("hamburg", "Europe/Berlin"),
+    ("barcelona", "Europe/Madrid"),
+    ("milan", "Europe/Rome"),
+    ("geneva", "Europe/Zurich"),
+    ("st petersburg", "Europe/Moscow"),
+    ("beijing", "Asia/Shanghai"),
+    ("shenzhen", "Asia/Shanghai"),
+    ("mumbai", "Asia/Kolkata"),
+    ("delhi", "Asia/Kolkata"),
+    ("new delhi", "Asia/Kolkata"),
+    ("bangalore", "Asia/Kolkata"),
+    ("bengaluru", "Asia/Kolkata"),
+    ("osaka", "Asia/Tokyo"),
+    ("hanoi", "Asia/Bangkok"),
+    ("abu dhabi", "Asia/Dubai"),
+    ("tel aviv", "Asia/Jerusalem"),
+    ("canberra", "Australia/Sydney"),
+    ("wellington", "Pacific/Auckland"),
+    ("rio de janeiro", "America/Sao_Paulo"),
+    ("buenos aires", "America/Argentina/Buenos_Aires"),
+];
+
+/// A zone times are converted from or to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Zone {
+    Named(Tz),
+    Fixed(FixedOffset),
+    Local, // this computer's
+}
+
+/// A moment as the clocks of one zone show it
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ZoneTime {
+    pub zone: String,         // "Europe/Berlin", "UTC+05:30" or "Local"
+    pub abbreviation: String, // "CEST", or the offset where the zone has no abbreviation
+    pub utc_offset: i32,      // seconds
+    pub datetime: String,     // wall clock time, "2026-10-16T15:00:00"
+    pub display: String,      // "Fri 16 Oct 15:00 CEST"
+}
+
+/// A time converted between two zones (`convert_time`)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TimeConversion {
+    pub timestamp: i64, // unix seconds
+    pub from: ZoneTime,
+    pub to: ZoneTime,
+}
+
+/// A decoded Unix timestamp (`parse_epoch`)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EpochTime {
+    pub unit: String, // how the value was read: "seconds", "milliseconds", ...
+    pub utc: String,  // RFC 3339
+    pub local: ZoneTime,
+    pub relative: String, // "3 days ago", "in 2 hours"
+}
+
+fn zone_time<T: TimeZone>(time: DateTime<T>, zone: String) -> ZoneTime
+where
+    T::Offset: std::fmt::Display,
+{
+    let abbreviation = time.format("%Z").to_string();
+    ZoneTime {
+        zone,
+        utc_offset: time.offset().fix().local_minus_utc(),
+        datetime: time.format("%Y-%m-%dT%H:%M:%S").to_string(),
+        display: format!("{} {}", time.format("%a %-d %b %H:%M"), abbreviation),
+        abbreviation,
+    }
+}
+
+impl Zone {
+    /// The zone `name` means, see the top of the file
+    pub fn find(name: &str) -> Result<Zone, String> {
+        let key = name
+            .trim()
+            .to_lowercase()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        if key.is_empty() {
+            return Err("No time zone given".to_string());
+        }
+        if key == "local" || key == "here" {
+            return Ok(Zone::Local);
+        }
+        if let Some(offset) = parse_offset(&key) {
+            return Ok(Zone::Fixed(offset));
+        }
+        let named = |iana: &str| iana.parse::<Tz>().ok().map(Zone::Named);
+        if let Some(zone) = [ABBREVIATIONS, CITIES]
+            .iter()
+            .flat_map(|table| table.iter())
+            .find(|(alias, _)| *alias == key)
+            .and_then(|(_, iana)| named(iana))
+        {
+            return Ok(zone);
+        }
+        // IANA names, whole ("europe/berlin") or by their city ("berlin", "new york")
+        let underscored = key.replace(' ', "_");
+        TZ_VARIANTS
+            .iter()
+            .find(|tz| tz.name().eq_ignore_ascii_case(&underscored))
+            .or_else(|| {
+                TZ_VARIANTS.iter().find(|tz| {
+                    tz.name()
+                        .rsplit('/')
+                        .next()
+                        .is_some_and(|city| city.eq_ignore_ascii_case(&underscored))
+                })
+            })
+            .map(|tz| Zone::Named(*tz))
+            .ok_or_else(|| format!("Unknown time zone '{}'", name.trim()))
+    }
+
+    fn name(&self) -> String {
+        match self {
+            Zone::Named(tz) => tz.name().to_string(),
+            Zone::Fixed(offset) => format!("UTC{}", offset),
+            Zone::Local => "Local".to_string(),
+        }
+    }
+
+    /// The moment the zone's clocks show `local`; an error in the hour skipped for DST
+    pub fn to_utc(self, local: NaiveDateTime) -> Result<DateTime<Utc>, String> {
+        let utc = match self {
+            Zone::Named(tz) => tz
+                .from_local_datetime(&local)
+                .earliest()
+                .map(|t| t.with_timezone(&Utc)),
+            Zone::Fixed(offset) => offset
+                .from_local_datetime(&local)
+                .earliest()
+                .map(|t| t.with_timezone(&Utc)),
+            Zone::Local => Local
+                .from_local_datetime(&local)
+                .earliest()
+                .map(|t| t.with_timezone(&Utc)),
+        };
+        utc.ok_or_else(|| {
+            format!(
+                "{} doesn't exist in {}, the clocks skip it",
+                local.format("%H:%M"),
+                self.name()
+            )
+        })
+    }
+
+    /// `utc` on the zone's clocks
+    pub fn at(&self, utc: DateTime<Utc>) -> ZoneTime {
+        match self {
+            Zone::Named(tz) => zone_time(utc.with_timezone(tz), self.name()),
+            Zone::Fixed(offset) => zone_time(utc.with_timezone(offset), self.name()),
+            Zone::Local => zone_time(utc.with_timezone(&Local), self.name()),
+        }
+    }
+
+    fn today(&self, now: DateTime<Utc>) -> NaiveDate {
+        match self {
+            Zone::Named(tz) => now.with_timezone(tz).date_naive(),
+            Zone::Fixed(offset) => now.with_timezone(offset).date_naive(),
+            Zone::Local => now.with_timezone(&Local).date_naive(),
+        }
+    }
+}
+
+// "utc+2", "gmt-05:30", "+0530"
+fn parse_offset(text: &str) -> Option<FixedOffset> {
+    let text = text
+        .strip_prefix("utc")
+        .or_else(|| text.strip_prefix("gmt"))
+        .unwrap_or(text)
+        .trim();
+    let first = text.chars().next()?;
+    let sign = match first {
+        '+' => 1,
+        '-' | '−' => -1,
+        _ => return None,
+    };
+    let digits = text[first.len_utf8()..].trim();
+    if !digits.bytes().all(|b| b.is_ascii_digit() || b == b':') {
+        return None;
+    }
+    let (hours, minutes) = match digits.split_once(':') {
+        Some((h, m)) => (h.parse::<i32>().ok()?, m.parse::<i32>().ok()?),
+        None if digits.len() == 4 => (digits[..2].parse().ok()?, digits[2..].parse().ok()?),
+        None => (digits.parse().ok()?, 0),
+    };
+    if hours > 14 || minutes >= 60 {
+        return None;
+    }
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+// "3pm", "3:30 pm", "15:00", "15:00:30", "noon", "midnight"
+fn parse_clock(text: &str) -> Option<NaiveTime> {
+    match text {
+        "noon" => return NaiveTime::from_hms_opt(12, 0, 0),
+        "midnight" => return NaiveTime::from_hms_opt(0, 0, 0),
+        _ => {}
+    }
+    let (digits, meridiem) = if let Some(rest) = text.strip_suffix("am") {
+        (rest.trim(), Some(false))
+    } else if let Some(rest) = text.strip_suffix("pm") {
+        (rest.trim(), Some(true))
+    } else {
+        (text, None)
+    };
+    let mut parts = digits.split(':');
+    let hour: u32 = parts.next()?.parse().ok()?;
+    let minute: u32 = parts.next().map_or(Some(0), |m| m.parse().ok())?;
+    let second: u32 = parts.next().map_or(Some(0), |s| s.parse().ok())?;
+    if parts.next().is_some() || (meridiem.is_none() && !digits.contains(':')) {
+        return None; // a bare "3" is too likely to be something else
+    }
+    let hour = match meridiem {
+        Some(_) if hour == 0 || hour > 12 => return None,
+        Some(pm) => hour % 12 + if pm { 12 } else { 0 },
+        None => hour,
+    };
+    NaiveTime::from_hms_opt(hour, minute, second)
+}
+
+/// The wall clock time `text` describes, on `today` unless it names a day; None for "now".
+/// Takes "now", "3pm", "tomorrow 9:30", "2026-10-16 15:00", "2026-10-16T15:00:00".
+pub fn parse_datetime(text: &str, today: NaiveDate) -> Result<Option<NaiveDateTime>, String> {
+    let text = text.trim().to_lowercase();
+    if matches!(text.as_str(), "" | "now" | "time") {
+        return Ok(None);
+    }
+    for format in ["%Y-%m-%dt%H:%M:%S", "%Y-%m-%dt%H:%M"] {
+        if let Ok(datetime) = NaiveDateTime::parse_from_str(&text, format) {
+            return Ok(Some(datetime));
+        }
+    }
+
+    let unknown = || format!("Can't read '{}' as a time", text);
+    let mut date = today;
+    let mut clock = Vec::new();
+    for word in text.split_whitespace() {
+        match word {
+            "today" | "at" => {}
+            "tomorrow" => date = today.checked_add_days(Days::new(1)).ok_or_else(unknown)?,
+            "yesterday" => date = today.checked_sub_days(Days::new(1)).ok_or_else(unknown)?,
+            _ => match NaiveDate::parse_from_str(word, "%Y-%m-%d") {
+                Ok(day) => date = day,
+                Err(_) => clock.push(word),
+            },
+        }
+    }
+    let time = if clock.is_empty() {
+        NaiveTime::MIN
+    } else {
+        parse_clock(&clock.join(" ")).ok_or_else(unknown)?
+    };
+    Ok(Some(date.and_time(time)))
+}
+
+/// Convert `datetime` (see `parse_datetime`) on the clocks of `from` to `to`, with `now` for
+/// "now" and today's date
+pub fn convert_at(
+    datetime: &str,
+    from: Zone,
+    to: Zone,
+    now: DateTime<Utc>,
+) -> Result<TimeConversion, String> {
+    let utc = match parse_datetime(datetime, from.today(now))? {
+        Some(local) => from.to_utc(local)?,
+        None => now,
+    };
+    Ok(TimeConversion {
+        timestamp: utc.timestamp(),
+        from: from.at(utc),
+        to: to.at(utc),
+    })
+}
+
+/// Convert a query like "3pm PST in Berlin", "tomorrow 9:00 new york to tokyo" or
+/// "time in tokyo" (a time without a zone is local); None if it isn't one
+pub fn convert_query_at(query: &str, now: DateTime<Utc>) -> Option<TimeConversion> {
+    let words: Vec<&str> = query.split_whitespace().collect();
+    // The last "in" / "to" with a zone after it, and a time and maybe a zone before it
+    (1..words.len().saturating_sub(1)).rev().find_map(|i| {
+        if !matches!(words[i].to_lowercase().as_str(), "in" | "to") {
+            return None;
+        }
+        let to = Zone::find(&words[i + 1..].join(" ")).ok()?;
+        let left = &words[..i];
+        (1..=left.len()).rev().find_map(|split| {
+            let from = match &left[split..] {
+                [] => Zone::Local,
+                zone => Zone::find(&zone.join(" ")).ok()?,
+            };
+            convert_at(&left[..split].join(" "), from, to, now).ok()
+        })
+    })
+}
+
+/// When a Unix timestamp is, read as seconds, milliseconds, microseconds or nanoseconds by
+/// its size (seconds up to the year 5138)
+pub fn decode_epoch(value: &str) -> Result<(DateTime<Utc>, &'static str), String> {
+    let value = value.trim().replace(['_', ','], "");
+    let number: f64 = value
+        .parse()
+        .map_err(|_| format!("'{}' isn't a timestamp", value))?;
+    let (per_second, unit) = match number.abs() {
+        n if n < 1e11 => (1_i64, "seconds"),
+        n if n < 1e14 => (1_000, "milliseconds"),
+        n if n < 1e17 => (1_000_000, "microseconds"),
+        _ => (1_000_000_000, "nanoseconds"),
+    };
+    // Whole numbers exactly, fractions ("1700000000.5") as near as a float gets
+    let (seconds, nanos) = match value.parse::<i64>() {
+        Ok(whole) => (
+            whole.div_euclid(per_second),
+            whole.rem_euclid(per_second) * (1_000_000_000 / per_second),
+        ),
+        Err(_) => {
+            let seconds = number / per_second as f64;
+            let whole = seconds.floor();
+            (
+                whole as i64,
+                ((seconds - whole) * 1e9).round().min(999_999_999.0) as i64,
+            )
+        }
+    };
+    DateTime::from_timestamp(seconds, nanos as u32)
+        .map(|time| (time, unit))
+        .ok_or_else(|| format!("{} is out of range", value))
+}
+
+/// "3 days ago", "in 2 hours", "just now"
+pub fn relative(then: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let seconds = (then - now).num_seconds();
+    let span = seconds.unsigned_abs();
+    let (count, unit) = match span {
+        0..=59 => return "just now".to_string(),
+        60..=3599 => (span / 60, "minute"),
+        3600..=86_399 => (span / 3600, "hour"),
+        86_400..=2_629_799 => (span / 86_400, "day"),
+        2_629_800..=31_557_599 => (span / 2_629_800, "month"),
+        _ => (span / 31_557_600, "year"),
+    };
+    let amount = format!("{} {}{}", count, unit, if count == 1 { "" } else { "s" });
+    if seconds < 0 {
+        format!("{} ago", amount)
+    } else {
+        format!("in {}", amount)
+    }
+}
+
+/// Convert a time on the clocks of one zone to another, e.g. ("3pm", "PST", "Berlin")
+#[tauri::command]
+pub fn convert_time(
+    datetime: String,
+    from_tz: String,
+    to_tz: String,
+) -> Result<TimeConversion, String> {
+    convert_at(
+        &datetime,
+        Zone::find(&from_tz)?,
+        Zone::find(&to_tz)?,
+        Utc::now(),
+    )
+}
+
+/// The time right now in each zone, in the order given
+#[tauri::command]
+pub fn now_in(tz_list: Vec<String>) -> Result<Vec<ZoneTime>, String> {
+    let now = Utc::now();
+    tz_list
+        .iter()
+        .map(|name| Zone::find(name).map(|zone| zone.at(now)))
+        .collect()
+}
+
+/// Decode a Unix timestamp in seconds, milliseconds, microseconds or nanoseconds
+#[tauri::command]
+pub fn parse_epoch(value: String) -> Result<EpochTime, String> {
+    let (time, unit) = decode_epoch(&value)?;
+    Ok(EpochTime {
+        unit: unit.to_string(),
+        utc: time.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true),
+        local: Zone::Local.at(time),
+        relative: relative(time, Utc::now()),
+    })
+}
+
+/// Convert a launcher query like "3pm PST in Berlin"; None if it isn't a time conversion
+#[tauri::command]
+pub fn convert_time_query(query: String) -> Option<TimeConversion> {
+    convert_query_at(&query, Utc::now())
+}
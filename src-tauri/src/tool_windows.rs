@@ -0,0 +1,446 @@
+// Detached tool windows
+// Tools can be opened in their own regular (decorated, resizable) windows next to the
+// launcher, e.g. a progress panel that stays up while you work elsewhere. They don't follow
+// the launcher's hide-on-blur behavior, and each remembers its own size and position.
+// The frontend renders the tool named in the `?window=<tool>` query string.
+// The overlay timer is the exception: a frameless, transparent always-on-top countdown or
+// clock for screen recordings and presentations, placed in a corner of the cursor's monitor.
+// Display calibration patterns open full screen on a chosen monitor (the cursor's by default).
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{
+    AppHandle, Emitter, Manager, PhysicalPosition, WebviewUrl, WebviewWindow, WebviewWindowBuilder,
+};
+
+use crate::{platform, window_placement};
+
+const GEOMETRY_FILE: &str = "tool_windows.json";
+pub const LABEL_PREFIX: &str = "tool-";
+const DEFAULT_SIZE: (f64, f64) = (420.0, 520.0);
+const MIN_SIZE: (f64, f64) = (320.0, 200.0);
+
+const CALIBRATION_TOOL: &str = "calibration";
+const OVERLAY_TOOL: &str = "overlay-timer";
+const OVERLAY_DEFAULT_SIZE: (f64, f64) = (260.0, 110.0);
+const OVERLAY_MIN_SIZE: (f64, f64) = (120.0, 50.0);
+const OVERLAY_MARGIN: i32 = 24; // physical pixels from the work area edges
+
+// Geometry is saved once a window has stopped moving/resizing for this long
+const SAVE_DELAY: Duration = Duration::from_millis(500);
+
+// Latest move/resize per window label, so only the last event of a drag saves
+static PENDING_SAVES: Mutex<Option<HashMap<String, u64>>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ToolWindowOptions {
+    pub title: Option<String>,
+    pub width: Option<f64>, // initial size, until the user resizes the window
+    pub height: Option<f64>,
+    pub always_on_top: bool,
+}
+
+/// Logical position and size of a tool window
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct WindowGeometry {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+type SavedGeometry = HashMap<String, WindowGeometry>; // by tool id
+
+fn is_valid_tool_id(tool: &str) -> bool {
+    !tool.is_empty()
+        && tool
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+// Saved positions on a monitor that has since been unplugged are dropped
+fn is_on_screen(app: &AppHandle, geometry: &WindowGeometry) -> bool {
+    app.available_monitors()
+        .unwrap_or_default()
+        .iter()
+        .any(|monitor| {
+            let scale = monitor.scale_factor();
+            let position = monitor.position().to_logical::<f64>(scale);
+            let size = monitor.size().to_logical::<f64>(scale);
+            geometry.x >= position.x
+                && geometry.x < position.x + size.width
+                && geometry.y >= position.y
+                && geometry.y < position.y + size.height
+        })
+}
+
+fn save_geometry(app: &AppHandle, tool: &str, window: &WebviewWindow) {
+    let (Ok(position), Ok(size), Ok(scale)) = (
+        window.outer_position(),
+        window.inner_size(),
+        window.scale_factor(),
+    ) else {
+        return;
+    };
+    // Minimized windows report bogus positions on Windows
+    if window.is_minimized().unwrap_or(false) {
+        return;
+    }
+
+    let position = position.to_logical::<f64>(scale);
+    let size = size.to_logical::<f64>(scale);
+    let mut saved: SavedGeometry = crate::load_json_file(app, GEOMETRY_FILE);
+    saved.insert(
+        tool.to_string(),
+        WindowGeometry {
+            x: position.x,
+            y: position.y,
+            width: size.width,
+            height: size.height,
+        },
+    );
+    if let Err(e) = crate::save_json_file(app, GEOMETRY_FILE, &saved) {
+        log::warn!("Failed to save tool window geometry: {}", e);
+    }
+}
+
+fn schedule_save(app: &AppHandle, tool: &str, window: &WebviewWindow) {
+    let generation = {
+        let mut pending = PENDING_SAVES.lock().unwrap();
+        let generation = pending
+            .get_or_insert_with(HashMap::new)
+            .entry(window.label().to_string())
+            .or_insert(0);
+        *generation += 1;
+        *generation
+    };
+
+    let (app, tool, window) = (app.clone(), tool.to_string(), window.clone());
+    std::thread::spawn(move || {
+        std::thread::sleep(SAVE_DELAY);
+        let latest = PENDING_SAVES
+            .lock()
+            .unwrap()
+            .as_ref()
+            .and_then(|pending| pending.get(window.label()).copied());
+        if latest == Some(generation) {
+            save_geometry(&app, &tool, &window);
+        }
+    });
+}
+
+/// Open `tool` in its own window, or bring its existing window to the front
+// Async: creating a window from a synchronous command deadlocks on Windows
+#[tauri::command]
+pub async fn open_tool_window(
+    app: AppHandle,
+    tool: String,
+    options: Option<ToolWindowOptions>,
+) -> Result<(), String> {
+    if !is_valid_tool_id(&tool) {
+        return Err(format!("Invalid tool: {}", tool));
+    }
+    let options = options.unwrap_or_default();
+    let label = format!("{}{}", LABEL_PREFIX, tool);
+
+    if let Some(window) = app.get_webview_window(&label) {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+        return Ok(());
+    }
+
+    let saved: SavedGeometry = crate::load_json_file(&app, GEOMETRY_FILE);
+    let title = options.title.as_deref().unwrap_or(&tool);
+    let mut builder = WebviewWindowBuilder::new(
+        &app,
+        &label,
+        WebviewUrl::App(format!("index.html?window={}", tool).into()),
+    )
+    .title(format!("BunchaTools - {}", title))
+    .min_inner_size(MIN_SIZE.0, MIN_SIZE.1)
+    .always_on_top(options.always_on_top);
+
+    builder = match saved.get(&tool).filter(|g| is_on_screen(&app, g)) {
+        Some(geometry) => builder
+            .inner_size(geometry.width, geometry.height)
+            .position(geometry.x, geometry.y),
+        None => builder
+            .inner_size(
+                options.width.unwrap_or(DEFAULT_SIZE.0),
+                options.height.unwrap_or(DEFAULT_SIZE.1),
+            )
+            .center(),
+    };
+
+    let window = builder
+        .build()
+        .map_err(|e| format!("Failed to open {} window: {}", tool, e))?;
+
+    let window_clone = window.clone();
+    window.on_window_event(move |event| {
+        if matches!(
+            event,
+            tauri::WindowEvent::Moved(_) | tauri::WindowEvent::Resized(_)
+        ) {
+            schedule_save(&app, &tool, &window_clone);
+        }
+    });
+
+    Ok(())
+}
+
+/// Close `tool`'s window if it's open
+pub fn close_tool_window(app: &AppHandle, tool: &str) {
+    if let Some(window) = app.get_webview_window(&format!("{}{}", LABEL_PREFIX, tool)) {
+        let _ = window.close();
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverlayMode {
+    #[default]
+    Countdown,
+    Clock,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverlayPosition {
+    TopLeft,
+    #[default]
+    TopRight,
+    BottomLeft,
+    BottomRight,
+    Center,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct OverlayTimerConfig {
+    pub mode: OverlayMode,
+    pub duration: Option<u64>, // seconds, required for countdowns
+    pub label: Option<String>,
+    pub width: f64, // logical pixels
+    pub height: f64,
+    pub position: OverlayPosition,
+    pub opacity: f64, // background opacity, 0.2-1.0; the digits stay opaque
+}
+
+impl Default for OverlayTimerConfig {
+    fn default() -> Self {
+        Self {
+            mode: OverlayMode::default(),
+            duration: None,
+            label: None,
+            width: OVERLAY_DEFAULT_SIZE.0,
+            height: OVERLAY_DEFAULT_SIZE.1,
+            position: OverlayPosition::default(),
+            opacity: 0.85,
+        }
+    }
+}
+
+/// What the overlay window renders (`get_overlay_timer` / `overlay-timer-changed`)
+#[derive(Debug, Clone, Serialize)]
+pub struct OverlayTimer {
+    pub mode: OverlayMode,
+    pub ends_at: Option<u64>, // countdown end, milliseconds since the epoch
+    pub label: Option<String>,
+    pub opacity: f64,
+}
+
+static OVERLAY: Mutex<Option<OverlayTimer>> = Mutex::new(None);
+
+fn now_millis() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+// Corner of the cursor's work area, in physical pixels
+fn overlay_position(position: OverlayPosition, width: i32, height: i32) -> Option<(i32, i32)> {
+    let (x, y, work_width, work_height) = platform::get_cursor_monitor_work_area()?;
+    let left = x + OVERLAY_MARGIN;
+    let right = x + work_width - width - OVERLAY_MARGIN;
+    let top = y + OVERLAY_MARGIN;
+    let bottom = y + work_height - height - OVERLAY_MARGIN;
+    Some(match position {
+        OverlayPosition::TopLeft => (left, top),
+        OverlayPosition::TopRight => (right, top),
+        OverlayPosition::BottomLeft => (left, bottom),
+        OverlayPosition::BottomRight => (right, bottom),
+        OverlayPosition::Center => (x + (work_width - width) / 2, y + (work_height - height) / 2),
+    })
+}
+
+/// Show the countdown/clock overlay, or update the one already on screen
+// Async for the same reason as open_tool_window
+#[tauri::command]
+pub async fn show_overlay_timer(app: AppHandle, config: OverlayTimerConfig) -> Result<(), String> {
+    let ends_at = match config.mode {
+        OverlayMode::Countdown => match config.duration {
+            Some(seconds) if seconds > 0 => Some(now_millis() + seconds * 1000),
+            _ => return Err("Countdown needs a duration".to_string()),
+        },
+        OverlayMode::Clock => None,
+    };
+    let width = config.width.max(OVERLAY_MIN_SIZE.0);
+    let height = config.height.max(OVERLAY_MIN_SIZE.1);
+    let overlay = OverlayTimer {
+        mode: config.mode,
+        ends_at,
+        label: config
+            .label
+            .map(|label| label.trim().to_string())
+            .filter(|label| !label.is_empty()),
+        opacity: config.opacity.clamp(0.2, 1.0),
+    };
+    *OVERLAY.lock().unwrap() = Some(overlay.clone());
+
+    let label = format!("{}{}", LABEL_PREFIX, OVERLAY_TOOL);
+    let window = match app.get_webview_window(&label) {
+        Some(window) => {
+            let _ = window.set_size(tauri::LogicalSize::new(width, height));
+            let _ = app.emit_to(&label, "overlay-timer-changed", &overlay);
+            window
+        }
+        None => WebviewWindowBuilder::new(
+            &app,
+            &label,
+            WebviewUrl::App(format!("index.html?window={}", OVERLAY_TOOL).into()),
+        )
+        .title("BunchaTools - Timer")
+        .inner_size(width, height)
+        .decorations(false)
+        .transparent(true)
+        .shadow(false)
+        .resizable(false)
+        .always_on_top(true)
+        .skip_taskbar(true)
+        .focused(false)
+        .build()
+        .map_err(|e| format!("Failed to open timer overlay: {}", e))?,
+    };
+
+    if let Ok(size) = window.outer_size() {
+        if let Some((x, y)) =
+            overlay_position(config.position, size.width as i32, size.height as i32)
+        {
+            let _ = window.set_position(PhysicalPosition::new(x, y));
+        }
+    }
+    let _ = window.show();
+    Ok(())
+}
+
+/// Current overlay settings, for the overlay window when it loads
+#[tauri::command]
+pub fn get_overlay_timer() -> Option<OverlayTimer> {
+    OVERLAY.lock().unwrap().clone()
+}
+
+#[tauri::command]
+pub fn close_overlay_timer(app: AppHandle) {
+    *OVERLAY.lock().unwrap() = None;
+    close_tool_window(&app, OVERLAY_TOOL);
+}
+
+/// A monitor the calibration patterns can be shown on
+#[derive(Debug, Clone, Serialize)]
+pub struct DisplayInfo {
+    pub key: String, // window_placement::monitor_key
+    pub name: String,
+    pub width: u32, // physical pixels
+    pub height: u32,
+    pub scale_factor: f64,
+    pub is_primary: bool,
+}
+
+#[tauri::command]
+pub fn list_displays(app: AppHandle) -> Vec<DisplayInfo> {
+    let primary = app
+        .primary_monitor()
+        .ok()
+        .flatten()
+        .map(|m| window_placement::monitor_key(&m));
+    app.available_monitors()
+        .unwrap_or_default()
+        .iter()
+        .enumerate()
+        .map(|(i, monitor)| {
+            let key = window_placement::monitor_key(monitor);
+            DisplayInfo {
+                name: monitor
+                    .name()
+                    .cloned()
+                    .unwrap_or_else(|| format!("Display {}", i + 1)),
+                width: monitor.size().width,
+                height: monitor.size().height,
+                scale_factor: monitor.scale_factor(),
+                is_primary: primary.as_deref() == Some(key.as_str()),
+                key,
+            }
+        })
+        .collect()
+}
+
+/// Show calibration patterns full screen on `display` (a key from `list_displays`), or on
+/// the monitor under the cursor
+#[tauri::command]
+pub async fn open_calibration_window(
+    app: AppHandle,
+    display: Option<String>,
+) -> Result<(), String> {
+    let monitors = app.available_monitors().map_err(|e| e.to_string())?;
+    let monitor = match display {
+        Some(key) => monitors
+            .iter()
+            .find(|m| window_placement::monitor_key(m) == key)
+            .ok_or_else(|| format!("Display not found: {}", key))?,
+        None => {
+            let cursor_monitor =
+                platform::get_cursor_monitor_work_area().and_then(|(x, y, w, h)| {
+                    monitors
+                        .iter()
+                        .find(|m| window_placement::contains(m, x + w / 2, y + h / 2))
+                });
+            cursor_monitor
+                .or_else(|| monitors.first())
+                .ok_or("No display found")?
+        }
+    };
+
+    // One calibration window at a time; reopening moves it to the requested display
+    close_tool_window(&app, CALIBRATION_TOOL);
+    let scale = monitor.scale_factor();
+    let position = monitor.position().to_logical::<f64>(scale);
+    let size = monitor.size().to_logical::<f64>(scale);
+    WebviewWindowBuilder::new(
+        &app,
+        format!("{}{}", LABEL_PREFIX, CALIBRATION_TOOL),
+        WebviewUrl::App(format!("index.html?window={}", CALIBRATION_TOOL).into()),
+    )
+    .title("BunchaTools - Display Calibration")
+    .position(position.x, position.y)
+    .inner_size(size.width, size.height)
+    .decorations(false)
+    .always_on_top(true)
+    .skip_taskbar(true)
+    .fullscreen(true)
+    .focused(true)
+    .build()
+    .map_err(|e| format!("Failed to open calibration window: {}", e))?;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn close_calibration_window(app: AppHandle) {
+    close_tool_window(&app, CALIBRATION_TOOL);
+}
@@ -0,0 +1,352 @@
+// Subtitles from speech: the audio of a video or audio file is transcribed on this computer with
+// whisper.cpp and saved as SRT or WebVTT next to the file (movie.en.srt), ready for the
+// converter (burning in) or file translation (movie.de.srt).
+// whisper-cli is looked up like ffmpeg: next to the app, in binaries/, then on PATH. Its model
+// (ggml-base, multilingual, ~150 MB) is downloaded into the app data folder the first time.
+// ffmpeg turns the input into 16 kHz mono WAV first, which is what whisper.cpp reads.
+
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::jobs::{self, JobKind};
+use crate::models::ModelFile;
+use crate::notifications::{self, NotifyAction};
+
+const SPEECH_MODEL: ModelFile = ModelFile {
+    folder: "whisper",
+    name: "ggml-base.bin",
+    url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-base.bin",
+    sha256: "60ed5bc3dd14eea856493d334349b405782ddcaf0028d4b5df4088345fba2efe",
+    label: "speech model",
+    tool: "generate-subtitles",
+};
+const BINARY_NAMES: [&str; 2] = ["whisper-cli", "whisper-cpp"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SubtitleFormat {
+    #[default]
+    Srt,
+    Vtt,
+}
+
+impl SubtitleFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Srt => "srt",
+            Self::Vtt => "vtt",
+        }
+    }
+}
+
+/// One stretch of speech, times in milliseconds
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment {
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TranscriptionProgress {
+    pub path: String,
+    pub stage: String, // "downloading_model", "extracting_audio" or "transcribing"
+    pub percent: u8,
+}
+
+#[derive(Deserialize)]
+struct WhisperOutput {
+    #[serde(default)]
+    result: Option<WhisperResult>,
+    #[serde(default)]
+    transcription: Vec<WhisperSegment>,
+}
+
+#[derive(Deserialize)]
+struct WhisperResult {
+    language: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct WhisperSegment {
+    offsets: WhisperOffsets,
+    text: String,
+}
+
+#[derive(Deserialize)]
+struct WhisperOffsets {
+    from: u64,
+    to: u64,
+}
+
+/// The spoken language and segments from whisper-cli's `-oj` output; markers for silence
+/// like [BLANK_AUDIO] are left out
+pub fn parse_whisper_json(json: &str) -> Result<(Option<String>, Vec<Segment>), String> {
+    let output: WhisperOutput =
+        serde_json::from_str(json).map_err(|e| format!("Unreadable transcription: {}", e))?;
+    let segments = output
+        .transcription
+        .into_iter()
+        .map(|s| Segment {
+            start_ms: s.offsets.from,
+            end_ms: s.offsets.to.max(s.offsets.from),
+            text: s.text.trim().to_string(),
+        })
+        .filter(|s| !s.text.is_empty() && s.text != "[BLANK_AUDIO]")
+        .collect();
+    Ok((output.result.and_then(|r| r.language), segments))
+}
+
+/// Percent done from a line of whisper-cli's `-pp` output
+pub fn parse_progress(line: &str) -> Option<u8> {
+    let pattern = Regex::new(r"progress\s*=\s*(\d+)%").unwrap();
+    let percent: u32 = pattern.captures(line)?[1].parse().ok()?;
+    Some(percent.min(100) as u8)
+}
+
+fn timestamp(ms: u64, separator: char) -> String {
+    format!(
+        "{:02}:{:02}:{:02}{}{:03}",
+        ms / 3_600_000,
+        ms / 60_000 % 60,
+        ms / 1000 % 60,
+        separator,
+        ms % 1000
+    )
+}
+
+/// Subtitles for `segments` in `format`
+pub fn render_subtitles(segments: &[Segment], format: SubtitleFormat) -> String {
+    let mut out = String::new();
+    if format == SubtitleFormat::Vtt {
+        out.push_str("WEBVTT\n\n");
+    }
+    for (i, segment) in segments.iter().enumerate() {
+        let separator = match format {
+            SubtitleFormat::Srt => {
+                out.push_str(&format!("{}\n", i + 1));
+                ','
+            }
+            SubtitleFormat::Vtt => '.',
+        };
+        out.push_str(&format!(
+            "{} --> {}\n{}\n\n",
+            timestamp(segment.start_ms, separator),
+            timestamp(segment.end_ms, separator),
+            segment.text
+        ));
+    }
+    out
+}
+
+pub(crate) fn whisper_path() -> Result<PathBuf, String> {
+    let exe = |name: &str| format!("{}{}", name, std::env::consts::EXE_SUFFIX);
+    let exe_dir = std::env::current_exe()
+        .ok()
+        .and_then(|p| p.parent().map(Path::to_path_buf));
+    let bundled = exe_dir
+        .iter()
+        .flat_map(|dir| [dir.clone(), dir.join("binaries")])
+        .flat_map(|dir| BINARY_NAMES.map(|name| dir.join(exe(name))));
+    let on_path = std::env::var_os("PATH")
+        .map(|paths| std::env::split_paths(&paths).collect::<Vec<_>>())
+        .unwrap_or_default()
+        .into_iter()
+        .flat_map(|dir| BINARY_NAMES.map(|name| dir.join(exe(name))));
+    bundled.chain(on_path).find(|p| p.is_file()).ok_or_else(|| {
+        "Subtitles need whisper.cpp's whisper-cli (github.com/ggerganov/whisper.cpp)".to_string()
+    })
+}
+
+fn emit_progress(app: &AppHandle, path: &str, stage: &str, percent: u8) {
+    let _ = app.emit(
+        "transcription-progress",
+        TranscriptionProgress {
+            path: path.to_string(),
+            stage: stage.to_string(),
+            percent,
+        },
+    );
+}
+
+// The model file, downloaded first if needed; `cancelled` is checked between chunks
+pub(crate) async fn ensure_model(
+    app: &AppHandle,
+    on_progress: impl FnMut(u8),
+    cancelled: impl Fn() -> bool,
+) -> Result<PathBuf, String> {
+    crate::models::ensure(app, &SPEECH_MODEL, on_progress, cancelled).await
+}
+
+// movie.mp4 -> movie.en.srt, or movie.en (2).srt if that exists already
+fn subtitle_path(media: &Path, language: Option<&str>, format: SubtitleFormat) -> PathBuf {
+    let stem = media
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "subtitles".to_string());
+    let name = match language {
+        Some(language) => format!("{}.{}", stem, language),
+        None => stem,
+    };
+    let mut candidate = media.with_file_name(format!("{}.{}", name, format.extension()));
+    let mut n = 2;
+    while candidate.exists() {
+        candidate = media.with_file_name(format!("{} ({}).{}", name, n, format.extension()));
+        n += 1;
+    }
+    candidate
+}
+
+// Run a child to the end, killed if the job is cancelled; stderr lines go to `on_line`
+fn run_attached(
+    command: &mut std::process::Command,
+    job: &jobs::JobHandle,
+    mut on_line: impl FnMut(&str),
+) -> Result<bool, String> {
+    let mut child = command
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| e.to_string())?;
+    job.token().attach_child(child.id());
+    if let Some(stderr) = child.stderr.take() {
+        for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+            on_line(&line);
+        }
+    }
+    let status = child.wait().map_err(|e| e.to_string());
+    job.token().detach_child(child.id());
+    Ok(status?.success())
+}
+
+/// Transcribe the speech in an audio or video file to subtitles next to it. `language` is a
+/// code like "en", or empty / "auto" to detect it. Progress is sent as "transcription-progress"
+/// events; returns the subtitle file's path.
+#[tauri::command]
+pub async fn transcribe_media(
+    app: AppHandle,
+    path: String,
+    language: Option<String>,
+    format: Option<SubtitleFormat>,
+) -> Result<String, String> {
+    let media = PathBuf::from(&path);
+    if !media.is_file() {
+        return Err(format!("{} doesn't exist", path));
+    }
+    let language = match language.as_deref().map(|l| l.trim().to_lowercase()) {
+        None => None,
+        Some(l) if l.is_empty() || l == "auto" => None,
+        Some(l) if (2..=3).contains(&l.len()) && l.chars().all(|c| c.is_ascii_lowercase()) => {
+            Some(l)
+        }
+        Some(l) => return Err(format!("\"{}\" isn't a language code (e.g. en, de)", l)),
+    };
+    let format = format.unwrap_or_default();
+    // Fail before the long part if subtitles can't be written there
+    crate::path_policy::validate_write_path(
+        &app,
+        &subtitle_path(&media, language.as_deref(), format).to_string_lossy(),
+    )?;
+    let ffmpeg = crate::platform::get_ffmpeg_path()?;
+    let whisper = whisper_path()?;
+
+    let file_name = media
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.clone());
+    let job = jobs::start_job(
+        &app,
+        JobKind::Transcription,
+        format!("Transcribing {}", file_name),
+    );
+    let model = ensure_model(
+        &app,
+        |percent| emit_progress(&app, &path, "downloading_model", percent),
+        || job.token().is_cancelled(),
+    )
+    .await
+    .map_err(|e| {
+        if job.token().is_cancelled() {
+            "Transcription cancelled".to_string()
+        } else {
+            e
+        }
+    })?;
+
+    let work = job.workspace()?;
+    let wav = work.join("audio.wav");
+    let output_base = work.join("transcript");
+
+    emit_progress(&app, &path, "extracting_audio", 0);
+    let extracted = run_attached(
+        crate::hidden_command(&ffmpeg)
+            .args(["-y", "-i"])
+            .arg(&media)
+            .args(["-vn", "-ar", "16000", "-ac", "1", "-c:a", "pcm_s16le"])
+            .arg(&wav),
+        &job,
+        |_| {},
+    )?;
+    if job.token().is_cancelled() {
+        return Err("Transcription cancelled".to_string());
+    }
+    if !extracted {
+        return Err("ffmpeg couldn't read audio from this file".to_string());
+    }
+
+    emit_progress(&app, &path, "transcribing", 0);
+    let mut whisper_command = crate::hidden_command(&whisper);
+    whisper_command
+        .arg("-m")
+        .arg(&model)
+        .arg("-f")
+        .arg(&wav)
+        .args([
+            "-l",
+            language.as_deref().unwrap_or("auto"),
+            "-oj",
+            "-pp",
+            "-of",
+        ])
+        .arg(&output_base);
+    let transcribed = run_attached(&mut whisper_command, &job, |line| {
+        if let Some(percent) = parse_progress(line) {
+            job.report_progress(percent);
+            emit_progress(&app, &path, "transcribing", percent);
+        }
+    })?;
+    if job.token().is_cancelled() {
+        return Err("Transcription cancelled".to_string());
+    }
+    if !transcribed {
+        return Err("whisper-cli failed to transcribe this file".to_string());
+    }
+
+    let json = std::fs::read_to_string(output_base.with_extension("json"))
+        .map_err(|e| format!("whisper-cli wrote no transcription: {}", e))?;
+    let (detected, segments) = parse_whisper_json(&json)?;
+    if segments.is_empty() {
+        return Err("No speech found".to_string());
+    }
+    let spoken = language.or(detected).filter(|l| l != "auto");
+    let output = subtitle_path(&media, spoken.as_deref(), format);
+    let output = crate::path_policy::validate_write_path(&app, &output.to_string_lossy())?;
+    std::fs::write(&output, render_subtitles(&segments, format))
+        .map_err(|e| format!("Failed to write subtitles: {}", e))?;
+
+    job.complete();
+    emit_progress(&app, &path, "transcribing", 100);
+    let output = output.to_string_lossy().to_string();
+    notifications::notify_if_hidden(
+        &app,
+        "Subtitles ready",
+        &output,
+        NotifyAction::reveal(&output),
+    );
+    Ok(output)
+}
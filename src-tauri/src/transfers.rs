@@ -0,0 +1,313 @@
+// Bytes each tool downloads and uploads, per day, for anyone on a metered connection
+// Totals are kept by day and tool in `transfer_stats.json`. A `TransferMeter` counts while a
+// download streams and adds its total when the last clone is dropped, so a cancelled or failed
+// download still counts for what it used; small transfers are added with `record`.
+// An optional monthly cap on downloads plus uploads either warns (once a month) when it's
+// exceeded or refuses to start new downloads until the month is over or the cap is raised;
+// a download that is already running is finished.
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use chrono::{Datelike, Days, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::notifications::{self, NotifyAction};
+
+const TRANSFERS_FILE: &str = "transfer_stats.json";
+
+// Days older than this are dropped, which keeps the file small and a year comparable
+const KEEP_DAYS: u64 = 400;
+
+// Meters of concurrent downloads finishing at once mustn't overwrite each other's totals
+static SAVE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Bytes moved, in both directions
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Usage {
+    pub downloaded: u64,
+    pub uploaded: u64,
+}
+
+impl Usage {
+    pub fn total(&self) -> u64 {
+        self.downloaded + self.uploaded
+    }
+
+    fn add(&mut self, other: Usage) {
+        self.downloaded += other.downloaded;
+        self.uploaded += other.uploaded;
+    }
+}
+
+/// What happens once a month's transfers exceed the cap
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CapAction {
+    Warn,
+    Pause, // new downloads are refused
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TransferCap {
+    pub monthly_bytes: u64,
+    pub action: CapAction,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SavedTransfers {
+    pub days: BTreeMap<NaiveDate, BTreeMap<String, Usage>>, // by day, then tool id
+    pub cap: Option<TransferCap>,
+    #[serde(default)]
+    pub warned: Option<NaiveDate>, // first of the month the cap warning was shown for
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransferRange {
+    Today,
+    Week,  // the last 7 days
+    Month, // this calendar month, what the cap applies to
+    Year,  // the last 365 days
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ToolTransfer {
+    pub tool: String,
+    pub downloaded: u64,
+    pub uploaded: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DayTransfer {
+    pub date: NaiveDate,
+    pub downloaded: u64,
+    pub uploaded: u64,
+}
+
+/// Transfers over a range (`get_transfer_stats`)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TransferStats {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub total: Usage,
+    pub tools: Vec<ToolTransfer>, // most first
+    pub days: Vec<DayTransfer>,   // days with transfers, oldest first
+    pub cap: Option<TransferCap>,
+    pub month_total: u64, // this month's transfers, for the cap
+}
+
+fn first_of_month(day: NaiveDate) -> NaiveDate {
+    day.with_day(1).unwrap_or(day)
+}
+
+fn range_start(range: TransferRange, today: NaiveDate) -> NaiveDate {
+    let days_back = |days: u64| today.checked_sub_days(Days::new(days)).unwrap_or(today);
+    match range {
+        TransferRange::Today => today,
+        TransferRange::Week => days_back(6),
+        TransferRange::Month => first_of_month(today),
+        TransferRange::Year => days_back(364),
+    }
+}
+
+/// Add `usage` to `tool`'s total for `day`, forgetting days older than a year and a bit
+pub fn add_usage(saved: &mut SavedTransfers, day: NaiveDate, tool: &str, usage: Usage) {
+    saved
+        .days
+        .entry(day)
+        .or_default()
+        .entry(tool.to_string())
+        .or_default()
+        .add(usage);
+    if let Some(oldest) = day.checked_sub_days(Days::new(KEEP_DAYS)) {
+        saved.days.retain(|date, _| *date >= oldest);
+    }
+}
+
+/// Everything moved this month so far
+pub fn month_total(saved: &SavedTransfers, today: NaiveDate) -> u64 {
+    saved
+        .days
+        .range(first_of_month(today)..=today)
+        .flat_map(|(_, tools)| tools.values())
+        .map(Usage::total)
+        .sum()
+}
+
+/// Totals per tool and per day over `range`, ending `today`
+pub fn stats(saved: &SavedTransfers, range: TransferRange, today: NaiveDate) -> TransferStats {
+    let from = range_start(range, today);
+    let mut total = Usage::default();
+    let mut tools: BTreeMap<&str, Usage> = BTreeMap::new();
+    let mut days = Vec::new();
+    for (date, day_tools) in saved.days.range(from..=today) {
+        let mut day = Usage::default();
+        for (tool, usage) in day_tools {
+            tools.entry(tool).or_default().add(*usage);
+            day.add(*usage);
+        }
+        total.add(day);
+        days.push(DayTransfer {
+            date: *date,
+            downloaded: day.downloaded,
+            uploaded: day.uploaded,
+        });
+    }
+    let mut tools: Vec<ToolTransfer> = tools
+        .into_iter()
+        .map(|(tool, usage)| ToolTransfer {
+            tool: tool.to_string(),
+            downloaded: usage.downloaded,
+            uploaded: usage.uploaded,
+        })
+        .collect();
+    tools.sort_by_key(|tool| std::cmp::Reverse(tool.downloaded + tool.uploaded));
+    TransferStats {
+        from,
+        to: today,
+        total,
+        tools,
+        days,
+        cap: saved.cap,
+        month_total: month_total(saved, today),
+    }
+}
+
+/// Whether this month's transfers have gone over the cap
+pub fn over_cap(saved: &SavedTransfers, today: NaiveDate) -> bool {
+    saved
+        .cap
+        .is_some_and(|cap| month_total(saved, today) > cap.monthly_bytes)
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+/// Add a finished transfer to today's totals; the first one to go over the cap each month
+/// emits `transfer-cap-exceeded` and notifies
+pub fn record(app: &AppHandle, tool: &str, usage: Usage) {
+    if usage.total() == 0 {
+        return;
+    }
+    let today = Local::now().date_naive();
+    let _lock = SAVE_LOCK.lock().unwrap();
+    let mut saved: SavedTransfers = crate::load_json_file(app, TRANSFERS_FILE);
+    add_usage(&mut saved, today, tool, usage);
+
+    let month = first_of_month(today);
+    let warn = over_cap(&saved, today) && saved.warned != Some(month);
+    if warn {
+        saved.warned = Some(month);
+    }
+    if let Err(e) = crate::save_json_file(app, TRANSFERS_FILE, &saved) {
+        log::warn!("Failed to save transfer stats: {}", e);
+    }
+    if let (true, Some(cap)) = (warn, saved.cap) {
+        let body = match cap.action {
+            CapAction::Warn => format!(
+                "{} transferred this month, over the {} cap",
+                format_bytes(month_total(&saved, today)),
+                format_bytes(cap.monthly_bytes)
+            ),
+            CapAction::Pause => format!(
+                "Over the {} cap; new downloads are paused until next month",
+                format_bytes(cap.monthly_bytes)
+            ),
+        };
+        log::info!("Transfer cap exceeded: {}", body);
+        let _ = app.emit("transfer-cap-exceeded", &body);
+        notifications::notify(
+            app,
+            "Monthly data cap reached",
+            &body,
+            NotifyAction::ShowWindow,
+        );
+    }
+}
+
+/// Before starting a download: an error when the cap is exceeded and set to pause downloads
+pub fn check_cap(app: &AppHandle) -> Result<(), String> {
+    let saved: SavedTransfers = crate::load_json_file(app, TRANSFERS_FILE);
+    let today = Local::now().date_naive();
+    match saved.cap {
+        Some(cap) if cap.action == CapAction::Pause && over_cap(&saved, today) => Err(format!(
+            "This month's {} data cap is used up, so downloads are paused. Raise or remove the cap to download anyway.",
+            format_bytes(cap.monthly_bytes)
+        )),
+        _ => Ok(()),
+    }
+}
+
+struct MeterInner {
+    app: AppHandle,
+    tool: &'static str,
+    downloaded: AtomicU64,
+}
+
+impl Drop for MeterInner {
+    fn drop(&mut self) {
+        let usage = Usage {
+            downloaded: *self.downloaded.get_mut(),
+            uploaded: 0,
+        };
+        record(&self.app, self.tool, usage);
+    }
+}
+
+/// Counts one download's bytes for `tool`; clones share the count, which is recorded once the
+/// last of them is dropped
+#[derive(Clone)]
+pub struct TransferMeter {
+    inner: Arc<MeterInner>,
+}
+
+impl TransferMeter {
+    pub fn new(app: &AppHandle, tool: &'static str) -> Self {
+        TransferMeter {
+            inner: Arc::new(MeterInner {
+                app: app.clone(),
+                tool,
+                downloaded: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    pub fn downloaded(&self, bytes: u64) {
+        self.inner.downloaded.fetch_add(bytes, Ordering::Relaxed);
+    }
+}
+
+/// Transfers per tool and per day over `range`, with this month's total and the cap
+#[tauri::command]
+pub fn get_transfer_stats(app: AppHandle, range: TransferRange) -> TransferStats {
+    let saved: SavedTransfers = crate::load_json_file(&app, TRANSFERS_FILE);
+    stats(&saved, range, Local::now().date_naive())
+}
+
+/// Set the monthly cap, or remove it with None
+#[tauri::command]
+pub fn set_transfer_cap(app: AppHandle, cap: Option<TransferCap>) -> Result<(), String> {
+    if cap.is_some_and(|cap| cap.monthly_bytes == 0) {
+        return Err("The cap has to be more than 0 bytes".to_string());
+    }
+    let _lock = SAVE_LOCK.lock().unwrap();
+    let mut saved: SavedTransfers = crate::load_json_file(&app, TRANSFERS_FILE);
+    saved.cap = cap;
+    saved.warned = None; // a new cap warns again
+    crate::save_json_file(&app, TRANSFERS_FILE, &saved)
+}
@@ -0,0 +1,162 @@
+// Cache and rate limit in front of the translation engines, so translating the same UI strings
+// over and over doesn't use up the MyMemory daily quota
+// Translations are remembered by text and language pair, most recently used first, in memory
+// and in `translation_cache.json` (written a moment after the last change, so translating a
+// whole file doesn't rewrite it for every line). Requests to MyMemory go through a token
+// bucket: short bursts are fine, a steady stream is slowed down to one request a second.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+const CACHE_FILE: &str = "translation_cache.json";
+const SAVE_DELAY: Duration = Duration::from_secs(2);
+
+// Least recently used translations are dropped beyond this
+const CACHE_LIMIT: usize = 2000;
+
+// MyMemory: up to 10 requests at once, then one a second
+const BURST: f64 = 10.0;
+const PER_SECOND: f64 = 1.0;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CachedTranslation {
+    pub text: String,
+    pub source: String, // language codes
+    pub target: String,
+    pub translation: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SavedCache {
+    translations: Vec<CachedTranslation>, // most recently used first
+}
+
+static CACHE: Mutex<Option<Vec<CachedTranslation>>> = Mutex::new(None);
+static SAVE_PENDING: AtomicBool = AtomicBool::new(false);
+static BUCKET: Mutex<Option<TokenBucket>> = Mutex::new(None);
+
+/// The cached translation of `text`, which then counts as just used
+pub fn lookup(
+    entries: &mut Vec<CachedTranslation>,
+    text: &str,
+    source: &str,
+    target: &str,
+) -> Option<String> {
+    let index = entries
+        .iter()
+        .position(|e| e.text == text && e.source == source && e.target == target)?;
+    let entry = entries.remove(index);
+    let translation = entry.translation.clone();
+    entries.insert(0, entry);
+    Some(translation)
+}
+
+/// Remember a translation, dropping the least recently used ones beyond `limit`
+pub fn add(entries: &mut Vec<CachedTranslation>, entry: CachedTranslation, limit: usize) {
+    entries
+        .retain(|e| e.text != entry.text || e.source != entry.source || e.target != entry.target);
+    entries.insert(0, entry);
+    entries.truncate(limit);
+}
+
+/// Allows `capacity` requests at once, refilling at `per_second`
+#[derive(Debug, Clone)]
+pub struct TokenBucket {
+    capacity: f64,
+    per_second: f64,
+    tokens: f64,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    pub fn new(capacity: f64, per_second: f64, now: Instant) -> Self {
+        Self {
+            capacity,
+            per_second,
+            tokens: capacity,
+            updated: now,
+        }
+    }
+
+    /// Take a token at `now`, or say how long until there is one
+    pub fn take(&mut self, now: Instant) -> Result<(), Duration> {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.per_second).min(self.capacity);
+        self.updated = now;
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (1.0 - self.tokens) / self.per_second,
+            ))
+        }
+    }
+}
+
+fn with_cache<T>(app: &AppHandle, f: impl FnOnce(&mut Vec<CachedTranslation>) -> T) -> T {
+    let mut cache = CACHE.lock().unwrap();
+    let entries = cache
+        .get_or_insert_with(|| crate::load_json_file::<SavedCache>(app, CACHE_FILE).translations);
+    f(entries)
+}
+
+/// The remembered translation of `text` from `source` to `target`, if there is one
+pub fn get(app: &AppHandle, text: &str, source: &str, target: &str) -> Option<String> {
+    with_cache(app, |entries| lookup(entries, text, source, target))
+}
+
+/// Remember a translation; the cache file is written shortly after
+pub fn store(app: &AppHandle, text: &str, source: &str, target: &str, translation: &str) {
+    let entry = CachedTranslation {
+        text: text.to_string(),
+        source: source.to_string(),
+        target: target.to_string(),
+        translation: translation.to_string(),
+    };
+    with_cache(app, |entries| add(entries, entry, CACHE_LIMIT));
+    if SAVE_PENDING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(SAVE_DELAY).await;
+        SAVE_PENDING.store(false, Ordering::SeqCst);
+        let saved = SavedCache {
+            translations: with_cache(&app, |entries| entries.clone()),
+        };
+        if let Err(e) = crate::save_json_file(&app, CACHE_FILE, &saved) {
+            log::warn!("Failed to save translation cache: {}", e);
+        }
+    });
+}
+
+/// Wait until another MyMemory request is allowed
+pub async fn wait_for_online_slot() {
+    loop {
+        let wait = {
+            let mut bucket = BUCKET.lock().unwrap();
+            let now = Instant::now();
+            match bucket
+                .get_or_insert_with(|| TokenBucket::new(BURST, PER_SECOND, now))
+                .take(now)
+            {
+                Ok(()) => return,
+                Err(wait) => wait,
+            }
+        };
+        tokio::time::sleep(wait).await;
+    }
+}
+
+/// Forget every cached translation; returns how many there were
+#[tauri::command]
+pub fn clear_translation_cache(app: AppHandle) -> Result<usize, String> {
+    let count = with_cache(&app, |entries| std::mem::take(entries).len());
+    crate::save_json_file(&app, CACHE_FILE, &SavedCache::default())?;
+    Ok(count)
+}
@@ -0,0 +1,102 @@
+// History of translations, searchable, so a phrase translated before can be reused
+// Every translation (quick, screen and command mode) is saved with its languages and when it
+// was made. Translating the same text to the same language again moves it to the top instead
+// of adding a duplicate. The history stays on this computer.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::TranslationResult;
+
+const HISTORY_FILE: &str = "translation_history.json";
+
+// Oldest translations are dropped beyond this
+const HISTORY_LIMIT: usize = 1000;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranslationHistoryEntry {
+    pub id: u64,
+    pub source_text: String,
+    pub translated_text: String,
+    pub source_language: String, // display names, as in TranslationResult
+    pub target_language: String,
+    pub timestamp: u64, // seconds since the epoch
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SavedHistory {
+    translations: Vec<TranslationHistoryEntry>, // newest first
+}
+
+/// Put a translation at the top of `entries`, replacing an earlier one of the same text into
+/// the same language
+pub fn add_entry(entries: &mut Vec<TranslationHistoryEntry>, mut entry: TranslationHistoryEntry) {
+    entries.retain(|e| {
+        e.source_text != entry.source_text || e.target_language != entry.target_language
+    });
+    entry.id = entries.iter().map(|e| e.id).max().unwrap_or(0) + 1;
+    entries.insert(0, entry);
+    entries.truncate(HISTORY_LIMIT);
+}
+
+/// Entries whose text or translation contains every word of `query` (any case); all of them
+/// for an empty query
+pub fn search<'a>(
+    entries: &'a [TranslationHistoryEntry],
+    query: &str,
+) -> Vec<&'a TranslationHistoryEntry> {
+    let words: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+    entries
+        .iter()
+        .filter(|e| {
+            let haystack = format!("{}\n{}", e.source_text, e.translated_text).to_lowercase();
+            words.iter().all(|word| haystack.contains(word))
+        })
+        .collect()
+}
+
+/// Save a translation; failures are only logged so they never fail the translation itself
+pub fn record(app: &AppHandle, source_text: &str, result: &TranslationResult) {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let mut saved: SavedHistory = crate::load_json_file(app, HISTORY_FILE);
+    add_entry(
+        &mut saved.translations,
+        TranslationHistoryEntry {
+            id: 0,
+            source_text: source_text.to_string(),
+            translated_text: result.translated_text.clone(),
+            source_language: result.detected_language.clone(),
+            target_language: result.target_language.clone(),
+            timestamp,
+        },
+    );
+    if let Err(e) = crate::save_json_file(app, HISTORY_FILE, &saved) {
+        log::warn!("Failed to save translation history: {}", e);
+    }
+}
+
+/// Past translations matching `query`, newest first
+#[tauri::command]
+pub fn get_translation_history(
+    app: AppHandle,
+    query: Option<String>,
+) -> Result<Vec<TranslationHistoryEntry>, String> {
+    crate::app_lock::ensure_unlocked()?;
+    let saved: SavedHistory = crate::load_json_file(&app, HISTORY_FILE);
+    Ok(
+        search(&saved.translations, query.as_deref().unwrap_or_default())
+            .into_iter()
+            .cloned()
+            .collect(),
+    )
+}
+
+#[tauri::command]
+pub fn clear_translation_history(app: AppHandle) -> Result<(), String> {
+    crate::save_json_file(&app, HISTORY_FILE, &SavedHistory::default())
+}
@@ -0,0 +1,170 @@
+// Dynamic tray menu: recently used tools and user-configured quick actions
+// The menu is rebuilt whenever settings change or a tool is used.
+
+use serde::{Deserialize, Serialize};
+use tauri::menu::{CheckMenuItemBuilder, Menu, MenuBuilder, MenuItemBuilder};
+use tauri::{AppHandle, Manager};
+
+use crate::{actions, AppState};
+
+const RECENT_TOOLS_FILE: &str = "recent_tools.json";
+const MAX_RECENT_TOOLS: usize = 5;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecentTool {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct RecentTools {
+    tools: Vec<RecentTool>, // most recent first
+}
+
+pub fn build_tray_menu(app: &AppHandle) -> tauri::Result<Menu<tauri::Wry>> {
+    let settings = app.state::<AppState>().settings.lock().unwrap().clone();
+    let recent: RecentTools = crate::load_json_file(app, RECENT_TOOLS_FILE);
+
+    // The fallback in use when another app holds the configured hotkey
+    let active_hotkey = app.state::<AppState>().active_hotkey.lock().unwrap().clone();
+    let show_label = match active_hotkey {
+        Some(hotkey) => format!("Show ({})", hotkey.label()),
+        None => "Show (no hotkey)".to_string(),
+    };
+    let show_item = MenuItemBuilder::with_id("show", show_label).build(app)?;
+    let pin_item = CheckMenuItemBuilder::with_id("pin", "Keep on Top")
+        .checked(settings.always_on_top)
+        .build(app)?;
+    let awake_item = CheckMenuItemBuilder::with_id("keep-awake", "Keep Awake")
+        .checked(crate::keep_awake::is_active())
+        .build(app)?;
+    let night_light_item = CheckMenuItemBuilder::with_id("night-light", "Night Light")
+        .checked(crate::night_light::is_active())
+        .build(app)?;
+    let mut menu = MenuBuilder::new(app)
+        .item(&show_item)
+        .item(&pin_item)
+        .item(&awake_item)
+        .item(&night_light_item);
+
+    // Newest running job (download / conversion) can be cancelled from here
+    if let Some(job) = app.state::<AppState>().jobs.list().last() {
+        let cancel_item =
+            MenuItemBuilder::with_id(format!("cancel-job:{}", job.id), "Cancel current job")
+                .build(app)?;
+        menu = menu.item(&cancel_item);
+    }
+
+    if !recent.tools.is_empty() {
+        let header = MenuItemBuilder::with_id("recent-header", "Recent Tools")
+            .enabled(false)
+            .build(app)?;
+        menu = menu.separator().item(&header);
+        for tool in &recent.tools {
+            let item =
+                MenuItemBuilder::with_id(format!("recent:{}", tool.id), &tool.name).build(app)?;
+            menu = menu.item(&item);
+        }
+    }
+
+    if !settings.quick_actions.is_empty() {
+        let header = MenuItemBuilder::with_id("actions-header", "Quick Actions")
+            .enabled(false)
+            .build(app)?;
+        menu = menu.separator().item(&header);
+        for (index, action) in settings.quick_actions.iter().enumerate() {
+            let item =
+                MenuItemBuilder::with_id(format!("action:{}", index), &action.label).build(app)?;
+            menu = menu.item(&item);
+        }
+    }
+
+    let quit_item = MenuItemBuilder::with_id("quit", "Quit BunchaTools").build(app)?;
+    menu.separator().item(&quit_item).build()
+}
+
+/// Replace the tray menu so it reflects the current settings and recent tools
+pub fn rebuild_tray_menu(app: &AppHandle) {
+    let menu = match build_tray_menu(app) {
+        Ok(menu) => menu,
+        Err(e) => {
+            log::error!("Failed to build tray menu: {}", e);
+            return;
+        }
+    };
+    let state = app.state::<AppState>();
+    let tray = state.tray_handle.lock().unwrap().clone();
+    if let Some(tray) = tray {
+        let _ = tray.set_menu(Some(menu));
+    }
+}
+
+pub fn handle_menu_event(app: &AppHandle, id: &str) {
+    if let Some(job_id) = id.strip_prefix("cancel-job:") {
+        if let Ok(job_id) = job_id.parse::<u64>() {
+            app.state::<AppState>().jobs.cancel(job_id);
+        }
+    } else if let Some(tool) = id.strip_prefix("recent:") {
+        actions::open_tool(app, tool);
+    } else if let Some(index) = id.strip_prefix("action:") {
+        let action = index.parse::<usize>().ok().and_then(|i| {
+            let state = app.state::<AppState>();
+            let settings = state.settings.lock().unwrap();
+            settings.quick_actions.get(i).map(|a| a.action.clone())
+        });
+        if let Some(action) = action {
+            actions::dispatch(app, action);
+        }
+    } else {
+        match id {
+            "quit" => {
+                // Leave do-not-disturb the way we found it
+                crate::pomodoro::stop_session(app);
+                crate::night_light::shutdown(app);
+                app.exit(0)
+            }
+            "show" => crate::toggle_window(app),
+            "keep-awake" => {
+                let app = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    let mode = if crate::keep_awake::is_active() {
+                        crate::keep_awake::KeepAwakeMode::Off
+                    } else {
+                        crate::keep_awake::KeepAwakeMode::System
+                    };
+                    if let Err(e) = crate::keep_awake::set(&app, mode, None).await {
+                        log::error!("Failed to toggle keep awake: {}", e);
+                    }
+                });
+            }
+            "night-light" => {
+                let enabled = !crate::night_light::is_active();
+                if let Err(e) = crate::night_light::set(app, enabled) {
+                    log::error!("Failed to toggle the night light: {}", e);
+                }
+            }
+            "pin" => {
+                let pinned = !app.state::<AppState>().settings.lock().unwrap().always_on_top;
+                if let Err(e) = crate::set_always_on_top(app.clone(), pinned) {
+                    log::error!("Failed to pin window: {}", e);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Move a tool to the top of the recent list and refresh the tray menu; also counts the use
+/// towards the tool's tips
+#[tauri::command]
+pub fn record_tool_use(app: AppHandle, tool_id: String, name: String) -> Result<(), String> {
+    let mut recent: RecentTools = crate::load_json_file(&app, RECENT_TOOLS_FILE);
+    recent.tools.retain(|t| t.id != tool_id);
+    recent.tools.insert(0, RecentTool { id: tool_id.clone(), name });
+    recent.tools.truncate(MAX_RECENT_TOOLS);
+    crate::save_json_file(&app, RECENT_TOOLS_FILE, &recent)?;
+    crate::onboarding::record_use(&app, &tool_id);
+
+    rebuild_tray_menu(&app);
+    Ok(())
+}
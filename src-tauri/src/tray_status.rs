@@ -0,0 +1,214 @@
+// Tray icon status and styling
+// Progress that each download/conversion reports on its job (`JobHandle::report_progress`) is
+// reflected in the tray tooltip, plus a progress bar drawn over the icon on Windows and the indicator label elsewhere.
+// The icon itself can be the full-color app icon or a monochrome glyph that matches the taskbar
+// theme, with a badge while jobs are running ("busy"), after one failed ("error"), during a
+// Pomodoro focus period ("focus") or while keep awake is on ("awake"). Theme switches are also relayed as `system-theme-changed`.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tauri::image::Image;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::jobs::JobInfo;
+use crate::platform::{self, SystemTheme};
+use crate::power::JobPower;
+use crate::AppState;
+
+const BUSY_COLOR: [u8; 4] = [0xF5, 0x9E, 0x0B, 0xFF];
+const ERROR_COLOR: [u8; 4] = [0xEF, 0x44, 0x44, 0xFF];
+const FOCUS_COLOR: [u8; 4] = [0x8B, 0x5C, 0xF6, 0xFF];
+const AWAKE_COLOR: [u8; 4] = [0x06, 0xB6, 0xD4, 0xFF];
+
+/// Tray icon variant. `Light` / `Dark` name the taskbar the icon is made for (a dark glyph on
+/// light taskbars and vice versa); `Auto` picks between them from the OS theme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrayIconStyle {
+    #[default]
+    Color,
+    Auto,
+    Light,
+    Dark,
+}
+
+// Last theme reported by the OS watcher, so refreshes don't have to query the OS
+static DARK_THEME: AtomicBool = AtomicBool::new(false);
+
+/// Start following job progress events and the OS theme
+pub fn init(app: &AppHandle) {
+    DARK_THEME.store(
+        platform::get_system_theme_impl() == SystemTheme::Dark,
+        Ordering::SeqCst,
+    );
+    refresh(app);
+
+    let app_handle = app.clone();
+    platform::watch_system_theme_impl(Arc::new(move |theme| {
+        let dark = theme == SystemTheme::Dark;
+        if DARK_THEME.swap(dark, Ordering::SeqCst) != dark {
+            refresh(&app_handle);
+            let _ = app_handle.emit("system-theme-changed", theme);
+        }
+    }));
+}
+
+/// Current light/dark desktop theme, for the "system" app theme
+#[tauri::command]
+pub fn get_system_theme() -> SystemTheme {
+    if DARK_THEME.load(Ordering::SeqCst) {
+        SystemTheme::Dark
+    } else {
+        SystemTheme::Light
+    }
+}
+
+/// Clear the error state once the user has looked at the tray
+pub fn acknowledge_failure(app: &AppHandle) {
+    if app.state::<AppState>().jobs.clear_failure() {
+        refresh(app);
+    }
+}
+
+fn status_text(jobs: &[JobInfo]) -> Option<String> {
+    match jobs {
+        [] => None,
+        [job] => Some(match job.progress {
+            _ if job.paused => format!("{} (paused)", job.label),
+            _ if job.power == JobPower::Deferred => format!("{} (waiting for AC power)", job.label),
+            Some(percent) => format!("{} ({}%)", job.label, percent),
+            None => job.label.clone(),
+        }),
+        _ => {
+            let waiting = jobs.iter().filter(|j| j.queue_position.is_some()).count();
+            Some(match waiting {
+                0 => format!("{} jobs running", jobs.len()),
+                _ => format!("{} jobs running, {} waiting", jobs.len() - waiting, waiting),
+            })
+        }
+    }
+}
+
+/// Overall progress across running jobs (average of the ones that reported any)
+fn overall_progress(jobs: &[JobInfo]) -> Option<u8> {
+    let reported: Vec<u32> = jobs
+        .iter()
+        .filter_map(|j| j.progress.map(u32::from))
+        .collect();
+    if reported.is_empty() {
+        return None;
+    }
+    Some((reported.iter().sum::<u32>() / reported.len() as u32) as u8)
+}
+
+/// Update the tray tooltip/icon to reflect the running jobs
+pub fn refresh(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    let jobs = state.jobs.list();
+    let failure = state.jobs.last_failure();
+    let style = state.settings.lock().unwrap().tray_icon_style;
+    let tray = state.tray_handle.lock().unwrap().clone();
+    let Some(tray) = tray else {
+        return;
+    };
+
+    let focusing = crate::pomodoro::is_focusing();
+    let keeping_awake = crate::keep_awake::is_active();
+
+    let tooltip = match (status_text(&jobs), &failure) {
+        (Some(text), _) => format!("BunchaTools - {}", text),
+        (None, Some(label)) => format!("BunchaTools - {} failed", label),
+        (None, None) if focusing => "BunchaTools - Focusing".to_string(),
+        (None, None) if keeping_awake => "BunchaTools - Keeping awake".to_string(),
+        (None, None) => "BunchaTools".to_string(),
+    };
+    let _ = tray.set_tooltip(Some(tooltip));
+
+    let progress = overall_progress(&jobs);
+
+    if let Some(base) = app.default_window_icon() {
+        let (width, height) = (base.width(), base.height());
+        let mut rgba = styled_rgba(base, style);
+
+        if failure.is_some() && jobs.is_empty() {
+            draw_badge(&mut rgba, width, height, ERROR_COLOR);
+        } else if !jobs.is_empty() {
+            draw_busy(&mut rgba, width, height, progress);
+        } else if focusing {
+            draw_badge(&mut rgba, width, height, FOCUS_COLOR);
+        } else if keeping_awake {
+            draw_badge(&mut rgba, width, height, AWAKE_COLOR);
+        }
+
+        let _ = tray.set_icon(Some(Image::new_owned(rgba, width, height)));
+    }
+
+    // Tooltips aren't shown by most Linux trays, so use the indicator label instead
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = tray.set_title(progress.map(|percent| format!("{}%", percent)));
+    }
+}
+
+/// Icon pixels for a style; monochrome variants keep the alpha channel and flatten the color
+fn styled_rgba(base: &Image<'_>, style: TrayIconStyle) -> Vec<u8> {
+    let mut rgba = base.rgba().to_vec();
+    let dark_taskbar = match style {
+        TrayIconStyle::Color => return rgba,
+        TrayIconStyle::Auto => DARK_THEME.load(Ordering::SeqCst),
+        TrayIconStyle::Light => false,
+        TrayIconStyle::Dark => true,
+    };
+    let glyph = if dark_taskbar { 0xFF } else { 0x1F };
+    for pixel in rgba.chunks_exact_mut(4) {
+        pixel[..3].fill(glyph);
+    }
+    rgba
+}
+
+/// Busy state: Windows has no tray label, so known progress is drawn as a bar there
+fn draw_busy(rgba: &mut [u8], width: u32, height: u32, progress: Option<u8>) {
+    #[cfg(target_os = "windows")]
+    if let Some(percent) = progress {
+        draw_progress_bar(rgba, width, height, percent);
+        return;
+    }
+    #[cfg(not(target_os = "windows"))]
+    let _ = progress;
+
+    draw_badge(rgba, width, height, BUSY_COLOR);
+}
+
+/// Filled circle in the bottom-right corner
+fn draw_badge(rgba: &mut [u8], width: u32, height: u32, color: [u8; 4]) {
+    let radius = (width.min(height) as f32 * 0.22).max(2.0);
+    let (cx, cy) = (width as f32 - radius - 0.5, height as f32 - radius - 0.5);
+    for y in 0..height {
+        for x in 0..width {
+            let (dx, dy) = (x as f32 - cx, y as f32 - cy);
+            if dx * dx + dy * dy <= radius * radius {
+                let i = ((y * width + x) * 4) as usize;
+                rgba[i..i + 4].copy_from_slice(&color);
+            }
+        }
+    }
+}
+
+/// Draw a progress bar along the bottom of the tray icon
+#[cfg(target_os = "windows")]
+fn draw_progress_bar(rgba: &mut [u8], width: u32, height: u32, percent: u8) {
+    const FILLED: [u8; 4] = [0x22, 0xC5, 0x5E, 0xFF];
+    const EMPTY: [u8; 4] = [0x1F, 0x29, 0x37, 0xFF];
+
+    let bar_height = (height / 5).max(2);
+    let filled_width = width * percent as u32 / 100;
+    for y in height - bar_height..height {
+        for x in 0..width {
+            let i = ((y * width + x) * 4) as usize;
+            let color = if x < filled_width { FILLED } else { EMPTY };
+            rgba[i..i + 4].copy_from_slice(&color);
+        }
+    }
+}
@@ -0,0 +1,361 @@
+// External triggers: a Stream Deck plugin (or any local script) talking to a localhost
+// WebSocket, and MIDI note-on messages from a controller. Each configured trigger maps to an
+// action that runs through the same dispatcher as the tray menu.
+//
+// WebSocket protocol (text frames, JSON):
+//   -> {"trigger": "kill-3000", "token": "..."}   run the Stream Deck binding with key "kill-3000"
+//   <- {"ok": true} / {"ok": false, "error": "..."}
+//   -> {"list": true, "token": "..."}              list the configured Stream Deck keys
+//   <- {"ok": true, "keys": ["kill-3000", ...]}
+//
+// Every request must carry the token, which is generated the first time the server is enabled
+// and can be read from (or changed in) settings.json. Handshakes with an `Origin` header are
+// refused: browsers always send one, so no web page can reach the server, while the Stream
+// Deck plugin and local scripts don't.
+
+use futures_util::{SinkExt, StreamExt};
+use rand::distributions::Alphanumeric;
+use rand::rngs::OsRng;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_tungstenite::tungstenite::handshake::server::{ErrorResponse, Request, Response};
+use tokio_tungstenite::tungstenite::http::{header, StatusCode};
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::actions::{self, Action};
+use crate::AppState;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExternalTriggerSettings {
+    #[serde(default)]
+    pub websocket_enabled: bool,
+    #[serde(default = "default_websocket_port")]
+    pub websocket_port: u16,
+    #[serde(default)]
+    pub websocket_token: String, // generated when the server is first enabled
+    #[serde(default)]
+    pub midi_enabled: bool,
+    #[serde(default)]
+    pub midi_device: String, // substring of the MIDI input name, empty uses the first input
+    #[serde(default)]
+    pub bindings: Vec<TriggerBinding>,
+}
+
+fn default_websocket_port() -> u16 {
+    47821
+}
+
+impl Default for ExternalTriggerSettings {
+    fn default() -> Self {
+        Self {
+            websocket_enabled: false,
+            websocket_port: default_websocket_port(),
+            websocket_token: String::new(),
+            midi_enabled: false,
+            midi_device: String::new(),
+            bindings: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "source", rename_all = "snake_case")]
+pub enum Trigger {
+    StreamDeck {
+        key: String,
+    },
+    Midi {
+        note: u8,
+        #[serde(default)]
+        channel: Option<u8>, // 1-16, None matches any channel
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TriggerBinding {
+    pub trigger: Trigger,
+    pub action: Action,
+}
+
+/// Running listeners, replaced whenever the settings change
+#[derive(Default)]
+pub struct TriggerRuntime {
+    websocket: Option<(u16, tauri::async_runtime::JoinHandle<()>)>,
+    midi: Option<midir::MidiInputConnection<()>>,
+}
+
+/// Payload of the `external-trigger` event, used by the settings UI to learn keys / notes
+#[derive(Debug, Clone, Serialize)]
+struct ExternalTriggerEvent {
+    trigger: Trigger,
+    bound: bool,
+}
+
+fn handle_trigger(app: &AppHandle, trigger: Trigger) -> bool {
+    let action = {
+        let state = app.state::<AppState>();
+        let settings = state.settings.lock().unwrap();
+        settings
+            .external_triggers
+            .bindings
+            .iter()
+            .find(|b| matches_trigger(&b.trigger, &trigger))
+            .map(|b| b.action.clone())
+    };
+
+    let bound = action.is_some();
+    let _ = app.emit("external-trigger", ExternalTriggerEvent { trigger, bound });
+    if let Some(action) = action {
+        actions::dispatch(app, action);
+    }
+    bound
+}
+
+fn matches_trigger(binding: &Trigger, incoming: &Trigger) -> bool {
+    match (binding, incoming) {
+        (Trigger::StreamDeck { key: a }, Trigger::StreamDeck { key: b }) => a == b,
+        (
+            Trigger::Midi {
+                note: a,
+                channel: ca,
+            },
+            Trigger::Midi {
+                note: b,
+                channel: cb,
+            },
+        ) => a == b && (ca.is_none() || ca == cb),
+        _ => false,
+    }
+}
+
+// ============================================================================
+// WebSocket (Stream Deck)
+// ============================================================================
+
+const TOKEN_LENGTH: usize = 32;
+
+#[derive(Debug, Deserialize)]
+struct WsRequest {
+    #[serde(default)]
+    trigger: Option<String>,
+    #[serde(default)]
+    list: bool,
+    #[serde(default)]
+    token: String,
+}
+
+async fn run_websocket_server(app: AppHandle, port: u16) {
+    // Loopback only; the plugin always runs on the same machine
+    let listener = match TcpListener::bind(("127.0.0.1", port)).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            log::error!("Failed to start trigger server on port {}: {}", port, e);
+            return;
+        }
+    };
+    log::info!("Trigger server listening on 127.0.0.1:{}", port);
+
+    while let Ok((stream, _)) = listener.accept().await {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = handle_websocket_client(app, stream).await {
+                log::warn!("Trigger client error: {}", e);
+            }
+        });
+    }
+}
+
+async fn handle_websocket_client(app: AppHandle, stream: TcpStream) -> Result<(), String> {
+    let mut socket = tokio_tungstenite::accept_hdr_async(stream, reject_browsers)
+        .await
+        .map_err(|e| format!("WebSocket handshake failed: {}", e))?;
+
+    while let Some(message) = socket.next().await {
+        let message = message.map_err(|e| e.to_string())?;
+        let Ok(text) = message.to_text() else {
+            continue;
+        };
+        if text.is_empty() {
+            continue;
+        }
+
+        let reply = handle_websocket_request(&app, text);
+        socket
+            .send(Message::text(reply.to_string()))
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
+
+/// Refuse handshakes from web pages, which can open WebSockets to localhost too
+#[allow(clippy::result_large_err)] // the callback signature tungstenite expects
+fn reject_browsers(request: &Request, response: Response) -> Result<Response, ErrorResponse> {
+    if !request.headers().contains_key(header::ORIGIN) {
+        return Ok(response);
+    }
+    let mut refusal = ErrorResponse::new(Some("Browser connections are not allowed".to_string()));
+    *refusal.status_mut() = StatusCode::FORBIDDEN;
+    Err(refusal)
+}
+
+fn handle_websocket_request(app: &AppHandle, text: &str) -> serde_json::Value {
+    let request: WsRequest = match serde_json::from_str(text) {
+        Ok(request) => request,
+        Err(e) => {
+            return serde_json::json!({ "ok": false, "error": format!("Invalid request: {}", e) })
+        }
+    };
+
+    let settings = {
+        let state = app.state::<AppState>();
+        let settings = state.settings.lock().unwrap();
+        settings.external_triggers.clone()
+    };
+    if settings.websocket_token.is_empty() || request.token != settings.websocket_token {
+        return serde_json::json!({ "ok": false, "error": "Invalid token" });
+    }
+
+    if request.list {
+        let keys: Vec<&String> = settings
+            .bindings
+            .iter()
+            .filter_map(|b| match &b.trigger {
+                Trigger::StreamDeck { key } => Some(key),
+                _ => None,
+            })
+            .collect();
+        return serde_json::json!({ "ok": true, "keys": keys });
+    }
+
+    match request.trigger {
+        Some(key) => {
+            if handle_trigger(app, Trigger::StreamDeck { key: key.clone() }) {
+                serde_json::json!({ "ok": true })
+            } else {
+                serde_json::json!({ "ok": false, "error": format!("No action bound to '{}'", key) })
+            }
+        }
+        None => serde_json::json!({ "ok": false, "error": "Missing trigger" }),
+    }
+}
+
+// ============================================================================
+// MIDI
+// ============================================================================
+
+const MIDI_NOTE_ON: u8 = 0x90;
+
+fn connect_midi(app: &AppHandle, device: &str) -> Result<midir::MidiInputConnection<()>, String> {
+    let midi_in =
+        midir::MidiInput::new("BunchaTools").map_err(|e| format!("Failed to open MIDI: {}", e))?;
+
+    let ports = midi_in.ports();
+    let port = ports
+        .iter()
+        .find(|p| {
+            let name = midi_in.port_name(p).unwrap_or_default();
+            device.is_empty() || name.to_lowercase().contains(&device.to_lowercase())
+        })
+        .ok_or_else(|| {
+            if device.is_empty() {
+                "No MIDI input devices found".to_string()
+            } else {
+                format!("MIDI device '{}' not found", device)
+            }
+        })?
+        .clone();
+
+    let app = app.clone();
+    midi_in
+        .connect(
+            &port,
+            "bunchatools-triggers",
+            move |_timestamp, message, _| {
+                // Note-on with velocity 0 is a note-off by convention
+                if let [status, note, velocity] = *message {
+                    if status & 0xF0 == MIDI_NOTE_ON && velocity > 0 {
+                        let channel = (status & 0x0F) + 1;
+                        handle_trigger(
+                            &app,
+                            Trigger::Midi {
+                                note,
+                                channel: Some(channel),
+                            },
+                        );
+                    }
+                }
+            },
+            (),
+        )
+        .map_err(|e| format!("Failed to connect to MIDI device: {}", e))
+}
+
+/// List MIDI input devices for the settings UI
+#[tauri::command]
+pub fn list_midi_devices() -> Result<Vec<String>, String> {
+    let midi_in =
+        midir::MidiInput::new("BunchaTools").map_err(|e| format!("Failed to open MIDI: {}", e))?;
+    Ok(midi_in
+        .ports()
+        .iter()
+        .filter_map(|p| midi_in.port_name(p).ok())
+        .collect())
+}
+
+/// Give the WebSocket server a random token the first time it's enabled
+fn ensure_websocket_token(app: &AppHandle) -> Result<(), String> {
+    let settings = {
+        let state = app.state::<AppState>();
+        let mut settings = state.settings.lock().unwrap();
+        let triggers = &mut settings.external_triggers;
+        if !triggers.websocket_enabled || !triggers.websocket_token.is_empty() {
+            return Ok(());
+        }
+        triggers.websocket_token = (0..TOKEN_LENGTH)
+            .map(|_| OsRng.sample(Alphanumeric) as char)
+            .collect();
+        settings.clone()
+    };
+    crate::save_settings_to_file(app, &settings)?;
+    let _ = app.emit("settings-changed", &settings);
+    Ok(())
+}
+
+/// Start, stop or restart the listeners to match the settings
+pub fn apply_trigger_settings(app: &AppHandle, settings: &ExternalTriggerSettings) {
+    if let Err(e) = ensure_websocket_token(app) {
+        log::warn!("Failed to save the trigger server token: {}", e);
+    }
+    let state = app.state::<AppState>();
+    let mut runtime = state.triggers.lock().unwrap();
+
+    // WebSocket server: keep it running if the port didn't change
+    let wanted_port = settings
+        .websocket_enabled
+        .then_some(settings.websocket_port);
+    let running_port = runtime.websocket.as_ref().map(|(port, _)| *port);
+    if wanted_port != running_port {
+        if let Some((_, handle)) = runtime.websocket.take() {
+            handle.abort();
+        }
+        if let Some(port) = wanted_port {
+            let handle = tauri::async_runtime::spawn(run_websocket_server(app.clone(), port));
+            runtime.websocket = Some((port, handle));
+        }
+    }
+
+    // MIDI: always reconnect so a changed device takes effect
+    if let Some(connection) = runtime.midi.take() {
+        connection.close();
+    }
+    if settings.midi_enabled {
+        match connect_midi(app, &settings.midi_device) {
+            Ok(connection) => runtime.midi = Some(connection),
+            Err(e) => log::warn!("MIDI triggers unavailable: {}", e),
+        }
+    }
+}
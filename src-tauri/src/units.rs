@@ -0,0 +1,575 @@
+// Unit conversion, entirely offline: length, mass, temperature, area, volume, speed, data
+// size, energy, pressure and time. Every unit is a factor to its category's base unit (plus an
+// offset for temperatures), and is found by its name, plural, symbol or common abbreviation,
+// so "5 mi to km", "3 sq ft in m2" and "100 MiB to MB" all convert. The sibling of currency
+// conversion for everything with a fixed rate.
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnitCategory {
+    Length,
+    Mass,
+    Temperature,
+    Area,
+    Volume,
+    Speed,
+    DataSize,
+    Energy,
+    Pressure,
+    Time,
+}
+
+impl UnitCategory {
+    fn label(self) -> &'static str {
+        match self {
+            UnitCategory::Length => "length",
+            UnitCategory::Mass => "mass",
+            UnitCategory::Temperature => "temperature",
+            UnitCategory::Area => "area",
+            UnitCategory::Volume => "volume",
+            UnitCategory::Speed => "speed",
+            UnitCategory::DataSize => "data size",
+            UnitCategory::Energy => "energy",
+            UnitCategory::Pressure => "pressure",
+            UnitCategory::Time => "time",
+        }
+    }
+}
+
+struct Unit {
+    name: &'static str, // plural, as results show it
+    symbol: &'static str,
+    category: UnitCategory,
+    factor: f64, // base units per unit
+    offset: f64, // added after the factor (temperatures, base kelvin)
+    aliases: &'static [&'static str],
+}
+
+const fn unit(
+    name: &'static str,
+    symbol: &'static str,
+    category: UnitCategory,
+    factor: f64,
+    aliases: &'static [&'static str],
+) -> Unit {
+    Unit {
+        name,
+        symbol,
+        category,
+        factor,
+        offset: 0.0,
+        aliases,
+    }
+}
+
+use UnitCategory::*;
+
+// Base units: meter, kilogram, kelvin, square meter, liter, meter per second, byte, joule,
+// pascal, second. Names match exactly first (Mb is megabits, MB and mb megabytes), then
+// case-insensitively.
+const UNITS: &[Unit] = &[
+    // Length
+    unit("meters", "m", Length, 1.0, &["meter", "metre", "metres"]),
+    unit(
+        "kilometers",
+        "km",
+        Length,
+        1000.0,
+        &["kilometer", "kilometre", "kilometres", "kms"],
+    ),
+    unit(
+        "centimeters",
+        "cm",
+        Length,
+        0.01,
+        &["centimeter", "centimetre", "centimetres"],
+    ),
+    unit(
+        "millimeters",
+        "mm",
+        Length,
+        0.001,
+        &["millimeter", "millimetre", "millimetres"],
+    ),
+    unit(
+        "micrometers",
+        "µm",
+        Length,
+        1e-6,
+        &["micrometer", "micron", "microns", "um"],
+    ),
+    unit(
+        "nanometers",
+        "nm",
+        Length,
+        1e-9,
+        &["nanometer", "nanometre", "nanometres"],
+    ),
+    unit("miles", "mi", Length, 1609.344, &["mile"]),
+    unit("yards", "yd", Length, 0.9144, &["yard", "yds"]),
+    unit("feet", "ft", Length, 0.3048, &["foot", "'"]),
+    unit("inches", "in", Length, 0.0254, &["inch", "\""]),
+    unit("nautical miles", "nmi", Length, 1852.0, &["nautical mile"]),
+    unit(
+        "light years",
+        "ly",
+        Length,
+        9.460_730_472_580_8e15,
+        &["light year", "lightyear", "lightyears"],
+    ),
+    // Mass
+    unit(
+        "kilograms",
+        "kg",
+        Mass,
+        1.0,
+        &["kilogram", "kilo", "kilos", "kgs"],
+    ),
+    unit("grams", "g", Mass, 0.001, &["gram"]),
+    unit("milligrams", "mg", Mass, 1e-6, &["milligram"]),
+    unit("micrograms", "µg", Mass, 1e-9, &["microgram", "mcg", "ug"]),
+    unit(
+        "tonnes",
+        "t",
+        Mass,
+        1000.0,
+        &["tonne", "ton", "tons", "metric ton", "metric tons"],
+    ),
+    unit("pounds", "lb", Mass, 0.453_592_37, &["pound", "lbs"]),
+    unit("ounces", "oz", Mass, 0.028_349_523_125, &["ounce"]),
+    unit("stones", "st", Mass, 6.350_293_18, &["stone"]),
+    unit("carats", "ct", Mass, 0.0002, &["carat"]),
+    // Temperature
+    Unit {
+        name: "celsius",
+        symbol: "°C",
+        category: Temperature,
+        factor: 1.0,
+        offset: 273.15,
+        aliases: &["c", "degc", "degrees celsius", "centigrade"],
+    },
+    Unit {
+        name: "fahrenheit",
+        symbol: "°F",
+        category: Temperature,
+        factor: 5.0 / 9.0,
+        offset: 273.15 - 32.0 * 5.0 / 9.0,
+        aliases: &["f", "degf", "degrees fahrenheit"],
+    },
+    unit("kelvin", "K", Temperature, 1.0, &["k", "kelvins"]),
+    // Area
+    unit(
+        "square meters",
+        "m²",
+        Area,
+        1.0,
+        &[
+            "square meter",
+            "square metre",
+            "square metres",
+            "m2",
+            "sq m",
+            "sqm",
+        ],
+    ),
+    unit(
+        "square kilometers",
+        "km²",
+        Area,
+        1e6,
+        &["square kilometer", "square kilometre", "km2", "sq km"],
+    ),
+    unit(
+        "square centimeters",
+        "cm²",
+        Area,
+        1e-4,
+        &["square centimeter", "cm2", "sq cm"],
+    ),
+    unit(
+        "square millimeters",
+        "mm²",
+        Area,
+        1e-6,
+        &["square millimeter", "mm2", "sq mm"],
+    ),
+    unit("hectares", "ha", Area, 1e4, &["hectare"]),
+    unit("acres", "ac", Area, 4_046.856_422_4, &["acre"]),
+    unit(
+        "square miles",
+        "mi²",
+        Area,
+        2_589_988.110_336,
+        &["square mile", "mi2", "sq mi"],
+    ),
+    unit(
+        "square yards",
+        "yd²",
+        Area,
+        0.836_127_36,
+        &["square yard", "yd2", "sq yd"],
+    ),
+    unit(
+        "square feet",
+        "ft²",
+        Area,
+        0.092_903_04,
+        &["square foot", "ft2", "sq ft", "sqft"],
+    ),
+    unit(
+        "square inches",
+        "in²",
+        Area,
+        0.000_645_16,
+        &["square inch", "in2", "sq in"],
+    ),
+    // Volume
+    unit(
+        "liters",
+        "L",
+        Volume,
+        1.0,
+        &["liter", "litre", "litres", "l", "ltr"],
+    ),
+    unit(
+        "milliliters",
+        "mL",
+        Volume,
+        0.001,
+        &["milliliter", "millilitre", "millilitres", "ml"],
+    ),
+    unit(
+        "centiliters",
+        "cL",
+        Volume,
+        0.01,
+        &["centiliter", "centilitre", "cl"],
+    ),
+    unit(
+        "cubic meters",
+        "m³",
+        Volume,
+        1000.0,
+        &["cubic meter", "cubic metre", "m3"],
+    ),
+    unit(
+        "cubic centimeters",
+        "cm³",
+        Volume,
+        0.001,
+        &["cubic centimeter", "cm3", "cc"],
+    ),
+    unit(
+        "cubic feet",
+        "ft³",
+        Volume,
+        28.316_846_592,
+        &["cubic foot", "ft3", "cu ft"],
+    ),
+    unit(
+        "cubic inches",
+        "in³",
+        Volume,
+        0.016_387_064,
+        &["cubic inch", "in3", "cu in"],
+    ),
+    unit(
+        "gallons",
+        "gal",
+        Volume,
+        3.785_411_784,
+        &["gallon", "us gallons", "us gallon"],
+    ),
+    unit(
+        "imperial gallons",
+        "imp gal",
+        Volume,
+        4.546_09,
+        &["imperial gallon", "uk gallons", "uk gallon"],
+    ),
+    unit("quarts", "qt", Volume, 0.946_352_946, &["quart"]),
+    unit("pints", "pt", Volume, 0.473_176_473, &["pint"]),
+    unit("cups", "cup", Volume, 0.236_588_236_5, &[]),
+    unit(
+        "fluid ounces",
+        "fl oz",
+        Volume,
+        0.029_573_529_562_5,
+        &["fluid ounce", "floz"],
+    ),
+    unit(
+        "tablespoons",
+        "tbsp",
+        Volume,
+        0.014_786_764_781_25,
+        &["tablespoon"],
+    ),
+    unit(
+        "teaspoons",
+        "tsp",
+        Volume,
+        0.004_928_921_593_75,
+        &["teaspoon"],
+    ),
+    // Speed
+    unit(
+        "meters per second",
+        "m/s",
+        Speed,
+        1.0,
+        &["meter per second", "mps"],
+    ),
+    unit(
+        "kilometers per hour",
+        "km/h",
+        Speed,
+        1000.0 / 3600.0,
+        &["kilometer per hour", "kmh", "kph", "kmph"],
+    ),
+    unit(
+        "miles per hour",
+        "mph",
+        Speed,
+        0.447_04,
+        &["mile per hour", "mi/h"],
+    ),
+    unit(
+        "feet per second",
+        "ft/s",
+        Speed,
+        0.3048,
+        &["foot per second", "fps"],
+    ),
+    unit(
+        "knots",
+        "kn",
+        Speed,
+        1852.0 / 3600.0,
+        &["knot", "kt", "kts"],
+    ),
+    unit("mach", "Ma", Speed, 343.0, &[]),
+    // Data size, decimal and binary prefixes
+    unit("bits", "bit", DataSize, 0.125, &["b"]),
+    unit("bytes", "B", DataSize, 1.0, &["byte"]),
+    unit("kilobits", "kbit", DataSize, 125.0, &["kilobit", "Kb"]),
+    unit("megabits", "Mbit", DataSize, 125e3, &["megabit", "Mb"]),
+    unit("gigabits", "Gbit", DataSize, 125e6, &["gigabit", "Gb"]),
+    unit("terabits", "Tbit", DataSize, 125e9, &["terabit", "Tb"]),
+    unit("kilobytes", "KB", DataSize, 1e3, &["kilobyte", "kB", "kb"]),
+    unit("megabytes", "MB", DataSize, 1e6, &["megabyte", "mb"]),
+    unit("gigabytes", "GB", DataSize, 1e9, &["gigabyte", "gb"]),
+    unit("terabytes", "TB", DataSize, 1e12, &["terabyte", "tb"]),
+    unit("petabytes", "PB", DataSize, 1e15, &["petabyte"]),
+    unit("kibibytes", "KiB", DataSize, 1024.0, &["kibibyte"]),
+    unit("mebibytes", "MiB", DataSize, 1_048_576.0, &["mebibyte"]),
+    unit("gibibytes", "GiB", DataSize, 1_073_741_824.0, &["gibibyte"]),
+    unit(
+        "tebibytes",
+        "TiB",
+        DataSize,
+        1_099_511_627_776.0,
+        &["tebibyte"],
+    ),
+    // Energy
+    unit("joules", "J", Energy, 1.0, &["joule"]),
+    unit("kilojoules", "kJ", Energy, 1e3, &["kilojoule"]),
+    unit("megajoules", "MJ", Energy, 1e6, &["megajoule"]),
+    unit("calories", "cal", Energy, 4.184, &["calorie"]),
+    unit(
+        "kilocalories",
+        "kcal",
+        Energy,
+        4184.0,
+        &["kilocalorie", "Cal"],
+    ),
+    unit(
+        "watt hours",
+        "Wh",
+        Energy,
+        3600.0,
+        &["watt hour", "watthours"],
+    ),
+    unit(
+        "kilowatt hours",
+        "kWh",
+        Energy,
+        3.6e6,
+        &["kilowatt hour", "kilowatthours"],
+    ),
+    unit(
+        "british thermal units",
+        "BTU",
+        Energy,
+        1_055.055_852_62,
+        &["british thermal unit", "btus"],
+    ),
+    unit(
+        "electronvolts",
+        "eV",
+        Energy,
+        1.602_176_634e-19,
+        &["electronvolt", "electron volts"],
+    ),
+    // Pressure
+    unit("pascals", "Pa", Pressure, 1.0, &["pascal"]),
+    unit("hectopascals", "hPa", Pressure, 100.0, &["hectopascal"]),
+    unit("kilopascals", "kPa", Pressure, 1000.0, &["kilopascal"]),
+    unit("megapascals", "MPa", Pressure, 1e6, &["megapascal"]),
+    unit("bars", "bar", Pressure, 1e5, &[]),
+    unit("millibars", "mbar", Pressure, 100.0, &["millibar"]),
+    unit("atmospheres", "atm", Pressure, 101_325.0, &["atmosphere"]),
+    unit(
+        "pounds per square inch",
+        "psi",
+        Pressure,
+        6_894.757_293_168,
+        &["pound per square inch"],
+    ),
+    unit(
+        "millimeters of mercury",
+        "mmHg",
+        Pressure,
+        133.322_387_415,
+        &["torr"],
+    ),
+    unit(
+        "inches of mercury",
+        "inHg",
+        Pressure,
+        3_386.388_666_6,
+        &["inhg"],
+    ),
+    // Time
+    unit("seconds", "s", Time, 1.0, &["second", "sec", "secs"]),
+    unit("milliseconds", "ms", Time, 0.001, &["millisecond", "msec"]),
+    unit("minutes", "min", Time, 60.0, &["minute", "mins"]),
+    unit("hours", "h", Time, 3600.0, &["hour", "hr", "hrs"]),
+    unit("days", "d", Time, 86_400.0, &["day"]),
+    unit("weeks", "wk", Time, 604_800.0, &["week", "w", "wks"]),
+    unit("years", "yr", Time, 31_557_600.0, &["year", "yrs"]), // Julian year, 365.25 days
+];
+
+/// A converted value (`convert_unit`)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UnitResult {
+    pub value: f64,
+    pub from: String, // unit names, e.g. "miles"
+    pub to: String,
+    pub from_symbol: String,
+    pub to_symbol: String,
+    pub result: f64,
+    pub category: UnitCategory,
+}
+
+// "Sq. Ft" -> "sq ft", "°C" -> "c", "km²" stays
+fn normalize(name: &str) -> String {
+    name.trim()
+        .trim_start_matches('°')
+        .replace('.', "")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn find_unit(name: &str) -> Option<&'static Unit> {
+    let name = normalize(name);
+    if name.is_empty() {
+        return None;
+    }
+    let names = |unit: &'static Unit| {
+        [unit.name, unit.symbol]
+            .into_iter()
+            .chain(unit.aliases.iter().copied())
+    };
+    UNITS
+        .iter()
+        .find(|unit| names(unit).any(|n| n == name))
+        .or_else(|| {
+            UNITS
+                .iter()
+                .find(|unit| names(unit).any(|n| n.eq_ignore_ascii_case(&name)))
+        })
+}
+
+/// The symbol of the unit `name` stands for, None if it isn't one
+pub fn unit_symbol(name: &str) -> Option<&'static str> {
+    find_unit(name).map(|unit| unit.symbol)
+}
+
+/// Convert `value` from one unit to another; units are found by name, symbol or alias
+pub fn convert(value: f64, from: &str, to: &str) -> Result<UnitResult, String> {
+    let source = find_unit(from).ok_or_else(|| format!("Unknown unit '{}'", from.trim()))?;
+    let target = find_unit(to).ok_or_else(|| format!("Unknown unit '{}'", to.trim()))?;
+    if source.category != target.category {
+        return Err(format!(
+            "Can't convert {} ({}) to {} ({})",
+            source.name,
+            source.category.label(),
+            target.name,
+            target.category.label()
+        ));
+    }
+    let base = value * source.factor + source.offset;
+    Ok(UnitResult {
+        value,
+        from: source.name.to_string(),
+        to: target.name.to_string(),
+        from_symbol: source.symbol.to_string(),
+        to_symbol: target.symbol.to_string(),
+        result: (base - target.offset) / target.factor,
+        category: source.category,
+    })
+}
+
+// "1,5" and "1.5" are one and a half, "1,000" and "1,000.5" a thousand and more
+fn parse_number(text: &str) -> Option<f64> {
+    let thousands = text.contains('.')
+        || text
+            .rsplit_once(',')
+            .is_some_and(|(_, tail)| tail.len() == 3);
+    let text = if thousands {
+        text.replace(',', "")
+    } else {
+        text.replace(',', ".")
+    };
+    text.parse().ok()
+}
+
+/// The value and the two units of a query like "5 mi to km", "3,5 sq ft in m2" or "-40 °F as C"
+pub fn parse_query(query: &str) -> Option<(f64, String, String)> {
+    let query = query.trim();
+    let number_end = query
+        .char_indices()
+        .find(|&(i, c)| {
+            !(c.is_ascii_digit() || c == '.' || c == ',' || ((c == '-' || c == '+') && i == 0))
+        })
+        .map_or(query.len(), |(i, _)| i);
+    let value = parse_number(&query[..number_end])?;
+    let rest = query[number_end..].trim();
+
+    // The first separator with a unit on both sides, so "5 in to cm" keeps inches
+    let words: Vec<&str> = rest.split_whitespace().collect();
+    (1..words.len().saturating_sub(1))
+        .find(|&i| {
+            matches!(
+                words[i].to_lowercase().as_str(),
+                "to" | "in" | "into" | "as"
+            )
+        })
+        .map(|i| (value, words[..i].join(" "), words[i + 1..].join(" ")))
+}
+
+/// Convert a value between two units of the same kind, e.g. (5, "mi", "km")
+#[tauri::command]
+pub fn convert_unit(value: f64, from: String, to: String) -> Result<UnitResult, String> {
+    convert(value, &from, &to)
+}
+
+/// Convert a typed query like "5 mi to km"; None if it isn't a unit conversion
+#[tauri::command]
+pub fn convert_unit_query(query: String) -> Option<UnitResult> {
+    let (value, from, to) = parse_query(&query)?;
+    convert(value, &from, &to).ok()
+}
@@ -0,0 +1,236 @@
+// Push-to-talk voice input for the launcher: while the voice hotkey (or the microphone button)
+// is held, ffmpeg records the microphone; on release the recording is transcribed on this
+// computer with the same whisper.cpp model as subtitles, and the words become the launcher's
+// query, so "convert video to gif" can be said instead of typed.
+// Recordings stop by themselves after `MAX_SECONDS` and are deleted once transcribed.
+
+use std::io::Write;
+use std::process::{Child, Stdio};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use crate::workspaces::{self, Workspace};
+
+const MAX_SECONDS: u32 = 15;
+const STOP_TIMEOUT: Duration = Duration::from_secs(3);
+
+// Anything shorter is a tap on the hotkey rather than speech
+const MIN_DURATION: Duration = Duration::from_millis(400);
+
+struct Capture {
+    child: Child,
+    dir: Workspace, // holds the WAV file until transcribed
+    started: Instant,
+}
+
+static CAPTURE: Mutex<Option<Capture>> = Mutex::new(None);
+
+/// Sent as "voice-capture" events: listening, downloading_model, transcribing, idle or error
+#[derive(Debug, Clone, Serialize)]
+pub struct VoiceCaptureStatus {
+    pub state: String,
+    pub percent: Option<u8>,
+    pub message: Option<String>,
+}
+
+fn emit_status(app: &AppHandle, state: &str, percent: Option<u8>, message: Option<String>) {
+    let _ = app.emit(
+        "voice-capture",
+        VoiceCaptureStatus {
+            state: state.to_string(),
+            percent,
+            message,
+        },
+    );
+}
+
+/// A transcribed utterance as a launcher query: sound annotations like "[BLANK_AUDIO]",
+/// "(music)" or "*cough*" removed, whitespace collapsed and the closing punctuation dropped
+pub fn clean_utterance(text: &str) -> String {
+    let mut cleaned = String::with_capacity(text.len());
+    let mut closing: Option<char> = None;
+    for c in text.chars() {
+        match closing {
+            Some(end) if c == end => closing = None,
+            Some(_) => {}
+            None => match c {
+                '[' => closing = Some(']'),
+                '(' => closing = Some(')'),
+                '*' => closing = Some('*'),
+                _ => cleaned.push(c),
+            },
+        }
+    }
+    let cleaned = cleaned.split_whitespace().collect::<Vec<_>>().join(" ");
+    cleaned
+        .trim_matches(|c: char| c == '"' || c == '“' || c == '”')
+        .trim_end_matches(['.', '!', '?', '…', '。', '！', '？'])
+        .trim()
+        .to_string()
+}
+
+/// Start recording the microphone; does nothing if it's recording already
+pub fn start(app: &AppHandle) -> Result<(), String> {
+    let mut capture = CAPTURE.lock().unwrap();
+    if capture.is_some() {
+        return Ok(());
+    }
+    let ffmpeg = crate::platform::get_ffmpeg_path()?;
+    let input = crate::platform::audio_capture_input(&ffmpeg)?;
+    let dir = workspaces::create(app, "voice-input")?;
+    let child = crate::hidden_command(&ffmpeg)
+        .args([
+            "-y",
+            "-loglevel",
+            "error",
+            "-f",
+            &input.format,
+            "-i",
+            &input.device,
+        ])
+        .args(["-t", &MAX_SECONDS.to_string()])
+        .args(["-ar", "16000", "-ac", "1", "-c:a", "pcm_s16le"])
+        .arg(dir.path().join("voice.wav"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Failed to start recording: {}", e))?;
+    *capture = Some(Capture {
+        child,
+        dir,
+        started: Instant::now(),
+    });
+    emit_status(app, "listening", None, None);
+    Ok(())
+}
+
+// Ask ffmpeg to finish the WAV file, killing it if it doesn't within `STOP_TIMEOUT`
+fn finish_child(child: &mut Child) {
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(b"q");
+    }
+    let deadline = Instant::now() + STOP_TIMEOUT;
+    while Instant::now() < deadline {
+        if let Ok(Some(_)) = child.try_wait() {
+            return;
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+async fn transcribe(app: &AppHandle, mut capture: Capture) -> Result<String, String> {
+    let held = capture.started.elapsed();
+    let capture = tauri::async_runtime::spawn_blocking(move || {
+        finish_child(&mut capture.child);
+        capture
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+    if held < MIN_DURATION {
+        return Err("Hold the voice hotkey while you speak".to_string());
+    }
+
+    let whisper = crate::transcription::whisper_path()?;
+    let model = crate::transcription::ensure_model(
+        app,
+        |percent| emit_status(app, "downloading_model", Some(percent), None),
+        || false,
+    )
+    .await?;
+    emit_status(app, "transcribing", None, None);
+
+    let language = crate::load_settings(app).voice_language;
+    let wav = capture.dir.path().join("voice.wav");
+    let output_base = capture.dir.path().join("voice");
+    let mut command = crate::hidden_command(&whisper);
+    command
+        .arg("-m")
+        .arg(&model)
+        .arg("-f")
+        .arg(&wav)
+        .args([
+            "-l",
+            if language.is_empty() {
+                "auto"
+            } else {
+                language.as_str()
+            },
+        ])
+        .args(["-oj", "-np", "-of"])
+        .arg(&output_base)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    let status = tauri::async_runtime::spawn_blocking(move || command.status())
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| format!("Failed to run whisper-cli: {}", e))?;
+    if !status.success() {
+        return Err("whisper-cli failed to transcribe the recording".to_string());
+    }
+    let json = std::fs::read_to_string(output_base.with_extension("json"))
+        .map_err(|e| format!("whisper-cli wrote no transcription: {}", e))?;
+    let (_, segments) = crate::transcription::parse_whisper_json(&json)?;
+    let spoken = segments
+        .iter()
+        .map(|s| s.text.as_str())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let query = clean_utterance(&spoken);
+    if query.is_empty() {
+        return Err("Didn't catch that, try again".to_string());
+    }
+    Ok(query)
+}
+
+/// Stop recording and transcribe it. The text is sent to the launcher as a "voice-query"
+/// event (opening it if hidden) and returned.
+pub async fn stop(app: &AppHandle) -> Result<String, String> {
+    let capture = CAPTURE.lock().unwrap().take().ok_or("Not listening")?;
+    match transcribe(app, capture).await {
+        Ok(query) => {
+            log::info!("Voice query: {}", query);
+            emit_status(app, "idle", None, None);
+            crate::actions::show_main_window(app);
+            let _ = app.emit("voice-query", query.clone());
+            Ok(query)
+        }
+        Err(e) => {
+            emit_status(app, "error", None, Some(e.clone()));
+            Err(e)
+        }
+    }
+}
+
+/// The push-to-talk hotkey: pressing it opens the launcher and listens, releasing transcribes
+pub fn on_hotkey(app: &AppHandle, pressed: bool) {
+    let app = app.clone();
+    if pressed {
+        if let Err(e) = start(&app) {
+            emit_status(&app, "error", None, Some(e));
+            return;
+        }
+        tauri::async_runtime::spawn(async move { crate::actions::show_main_window(&app) });
+    } else if CAPTURE.lock().unwrap().is_some() {
+        tauri::async_runtime::spawn(async move {
+            let _ = stop(&app).await;
+        });
+    }
+}
+
+/// Start listening (the microphone button is pressed)
+#[tauri::command]
+pub fn start_voice_capture(app: AppHandle) -> Result<(), String> {
+    start(&app)
+}
+
+/// Stop listening and return what was said
+#[tauri::command]
+pub async fn stop_voice_capture(app: AppHandle) -> Result<String, String> {
+    stop(&app).await
+}
@@ -0,0 +1,152 @@
+// Wake-on-LAN: magic packets to wake machines on the local network, plus a saved-devices
+// list so a machine can be woken by name from the launcher ("wake desktop").
+
+use std::net::{Ipv4Addr, UdpSocket};
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+const DEVICES_FILE: &str = "wol_devices.json";
+
+// Port 9 (discard) is what most network cards and routers expect; 7 is the other convention
+const WOL_PORT: u16 = 9;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WolDevice {
+    pub name: String,
+    pub mac: String,                  // normalized to AA:BB:CC:DD:EE:FF
+    pub broadcast_ip: Option<String>, // subnet broadcast address; None for 255.255.255.255
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SavedDevices {
+    devices: Vec<WolDevice>,
+}
+
+/// Parse a MAC address written with ':', '-' or '.' separators, or none at all
+pub fn parse_mac(mac: &str) -> Result<[u8; 6], String> {
+    let hex: String = mac
+        .trim()
+        .chars()
+        .filter(|c| !matches!(c, ':' | '-' | '.' | ' '))
+        .collect();
+    if hex.len() != 12 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("Invalid MAC address: {}", mac));
+    }
+
+    let mut bytes = [0u8; 6];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).unwrap();
+    }
+    Ok(bytes)
+}
+
+fn format_mac(bytes: &[u8; 6]) -> String {
+    bytes
+        .iter()
+        .map(|b| format!("{:02X}", b))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// Six 0xFF bytes followed by the MAC address repeated 16 times
+pub fn magic_packet(mac: &[u8; 6]) -> Vec<u8> {
+    let mut packet = vec![0xFF; 6];
+    for _ in 0..16 {
+        packet.extend_from_slice(mac);
+    }
+    packet
+}
+
+fn send_magic_packet(mac: &str, broadcast_ip: Option<&str>) -> Result<(), String> {
+    let mac = parse_mac(mac)?;
+    let target: Ipv4Addr = match broadcast_ip.map(str::trim).filter(|ip| !ip.is_empty()) {
+        Some(ip) => ip
+            .parse()
+            .map_err(|_| format!("Invalid broadcast address: {}", ip))?,
+        None => Ipv4Addr::BROADCAST,
+    };
+
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))
+        .map_err(|e| format!("Failed to open UDP socket: {}", e))?;
+    socket
+        .set_broadcast(true)
+        .map_err(|e| format!("Failed to enable broadcast: {}", e))?;
+    socket
+        .send_to(&magic_packet(&mac), (target, WOL_PORT))
+        .map_err(|e| format!("Failed to send magic packet: {}", e))?;
+    log::info!(
+        "Sent Wake-on-LAN packet for {} to {}",
+        format_mac(&mac),
+        target
+    );
+    Ok(())
+}
+
+/// Send a magic packet to `mac`, to 255.255.255.255 unless a broadcast address is given
+#[tauri::command]
+pub fn send_wol(mac: String, broadcast_ip: Option<String>) -> Result<(), String> {
+    send_magic_packet(&mac, broadcast_ip.as_deref())
+}
+
+#[tauri::command]
+pub fn list_wol_devices(app: AppHandle) -> Vec<WolDevice> {
+    let saved: SavedDevices = crate::load_json_file(&app, DEVICES_FILE);
+    saved.devices
+}
+
+/// Add a device, replacing any saved device with the same name
+#[tauri::command]
+pub fn save_wol_device(app: AppHandle, device: WolDevice) -> Result<WolDevice, String> {
+    let name = device.name.trim().to_string();
+    if name.is_empty() {
+        return Err("Device name is empty".to_string());
+    }
+    if let Some(ip) = device
+        .broadcast_ip
+        .as_deref()
+        .filter(|ip| !ip.trim().is_empty())
+    {
+        ip.trim()
+            .parse::<Ipv4Addr>()
+            .map_err(|_| format!("Invalid broadcast address: {}", ip))?;
+    }
+    let device = WolDevice {
+        name,
+        mac: format_mac(&parse_mac(&device.mac)?),
+        broadcast_ip: device
+            .broadcast_ip
+            .map(|ip| ip.trim().to_string())
+            .filter(|ip| !ip.is_empty()),
+    };
+
+    let mut saved: SavedDevices = crate::load_json_file(&app, DEVICES_FILE);
+    saved
+        .devices
+        .retain(|d| !d.name.eq_ignore_ascii_case(&device.name));
+    saved.devices.push(device.clone());
+    crate::save_json_file(&app, DEVICES_FILE, &saved)?;
+    Ok(device)
+}
+
+#[tauri::command]
+pub fn remove_wol_device(app: AppHandle, name: String) -> Result<(), String> {
+    let mut saved: SavedDevices = crate::load_json_file(&app, DEVICES_FILE);
+    saved
+        .devices
+        .retain(|d| !d.name.eq_ignore_ascii_case(&name));
+    crate::save_json_file(&app, DEVICES_FILE, &saved)
+}
+
+/// Wake a saved device by name
+#[tauri::command]
+pub fn wake_device(app: AppHandle, name: String) -> Result<WolDevice, String> {
+    let saved: SavedDevices = crate::load_json_file(&app, DEVICES_FILE);
+    let device = saved
+        .devices
+        .into_iter()
+        .find(|d| d.name.eq_ignore_ascii_case(name.trim()))
+        .ok_or_else(|| format!("No saved device named {}", name.trim()))?;
+    send_magic_packet(&device.mac, device.broadcast_ip.as_deref())?;
+    Ok(device)
+}
@@ -0,0 +1,545 @@
+// Webhook listener: lets external systems (CI, home automation, scripts on other machines)
+// trigger BunchaTools with an HTTP POST to any path on the configured port.
+//
+// The body is JSON. The sender puts the current Unix time in an `X-BunchaTools-Timestamp`
+// header and signs "<timestamp>.<body>" with HMAC-SHA256 under the shared secret; the signature
+// goes in an `X-BunchaTools-Signature: sha256=<hex>` header. Requests without a valid signature,
+// more than `MAX_SKEW_SECS` away from our clock, or repeating a recent signature are refused, so
+// a captured request can't be replayed.
+//   {"action": "notify", "title": "CI", "message": "Build passed"}
+//   {"action": "download", "url": "https://..."}      into the downloads folder with yt-dlp
+//   {"action": "workflow", "name": "...", "actions": [<Action>, ...]}   see actions.rs
+// Answers are JSON too: {"ok": true, "message": "..."} or {"ok": false, "error": "..."}.
+//
+//   TS=$(date +%s); curl -d "$BODY" -H "X-BunchaTools-Timestamp: $TS" \
+//     -H "X-BunchaTools-Signature: sha256=$(printf %s "$TS.$BODY" |
+//     openssl dgst -sha256 -hmac "$SECRET" -r | cut -d' ' -f1)" http://host:47822/
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use ring::hmac;
+use serde::{Deserialize, Serialize};
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::file_associations::{self, Workflow};
+use crate::notifications::{self, NotifyAction};
+use crate::AppState;
+
+const SIGNATURE_HEADER: &str = "x-bunchatools-signature";
+const TIMESTAMP_HEADER: &str = "x-bunchatools-timestamp";
+// How far a request's timestamp may be from our clock
+const MAX_SKEW_SECS: i64 = 300;
+const MIN_SECRET_LEN: usize = 16;
+const MAX_HEAD: usize = 16 * 1024;
+const MAX_BODY: usize = 1024 * 1024;
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+const BIND_RETRIES: u32 = 5;
+// Slows down guessing
+const FAILED_ATTEMPT_DELAY: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WebhookSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_port")]
+    pub port: u16,
+    #[serde(default)]
+    pub secret: String,
+}
+
+fn default_port() -> u16 {
+    47822
+}
+
+impl Default for WebhookSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: default_port(),
+            secret: String::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookStatus {
+    pub running: bool,
+    pub port: u16,
+}
+
+/// What a webhook asks for
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum WebhookAction {
+    Notify {
+        #[serde(default)]
+        title: Option<String>,
+        message: String,
+    },
+    Download {
+        url: String,
+    },
+    Workflow(Workflow),
+}
+
+/// Method, path and headers (names in lower case) of a request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestHead {
+    pub method: String,
+    pub path: String,
+    pub headers: HashMap<String, String>,
+}
+
+// The listener task and the settings it runs with
+static LISTENER: Mutex<Option<(WebhookSettings, JoinHandle<()>)>> = Mutex::new(None);
+static RECENT: Mutex<RecentSignatures> = Mutex::new(RecentSignatures::new());
+
+/// Signatures of the requests accepted in the last `MAX_SKEW_SECS`, so a captured request
+/// can't be sent again while its timestamp is still accepted
+#[derive(Default)]
+pub struct RecentSignatures(Vec<(i64, String)>); // timestamp, signature as lowercase hex
+
+impl RecentSignatures {
+    pub const fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Remember the signature of a request stamped `timestamp`; false if it was already used
+    pub fn first_use(&mut self, signature: &str, timestamp: i64, now: i64) -> bool {
+        self.0
+            .retain(|(stamped, _)| (now - stamped).abs() <= MAX_SKEW_SECS);
+        let signature = signature.trim().to_ascii_lowercase();
+        let signature = signature.strip_prefix("sha256=").unwrap_or(&signature);
+        if self.0.iter().any(|(_, used)| used == signature) {
+            return false;
+        }
+        self.0.push((timestamp, signature.to_string()));
+        true
+    }
+}
+
+fn now_secs() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+// What gets signed: the timestamp, a dot, then the body
+fn signed_message(timestamp: &str, body: &[u8]) -> Vec<u8> {
+    [timestamp.as_bytes(), b".", body].concat()
+}
+
+/// The signature header value for a request stamped `timestamp`: "sha256=<hex HMAC-SHA256>"
+#[cfg_attr(not(feature = "test-harness"), allow(dead_code))] // senders sign, we only verify
+pub fn sign(secret: &str, timestamp: &str, body: &[u8]) -> String {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+    let tag = hmac::sign(&key, &signed_message(timestamp, body));
+    let hex: String = tag.as_ref().iter().map(|b| format!("{:02x}", b)).collect();
+    format!("sha256={}", hex)
+}
+
+/// True if `signature` (a header value from `sign`) matches `timestamp` and `body`; compared
+/// in constant time
+pub fn verify_signature(secret: &str, timestamp: &str, body: &[u8], signature: &str) -> bool {
+    let hex = signature.trim();
+    let hex = hex.strip_prefix("sha256=").unwrap_or(hex);
+    if hex.len() % 2 != 0 || !hex.is_ascii() {
+        return false;
+    }
+    let Some(tag) = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect::<Option<Vec<u8>>>()
+    else {
+        return false;
+    };
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+    hmac::verify(&key, &signed_message(timestamp, body), &tag).is_ok()
+}
+
+/// Why a correctly signed request stamped `timestamp` is refused at `now`, if it is
+pub fn check_freshness(
+    recent: &mut RecentSignatures,
+    signature: &str,
+    timestamp: i64,
+    now: i64,
+) -> Result<(), String> {
+    if (now - timestamp).abs() > MAX_SKEW_SECS {
+        return Err("Request expired (check both clocks)".to_string());
+    }
+    if !recent.first_use(signature, timestamp, now) {
+        return Err("Request already received".to_string());
+    }
+    Ok(())
+}
+
+/// Parse the request line and headers (everything before the blank line)
+pub fn parse_head(head: &str) -> Option<RequestHead> {
+    let mut lines = head.lines();
+    let mut request_line = lines.next()?.split_whitespace();
+    let (method, path, version) = (
+        request_line.next()?,
+        request_line.next()?,
+        request_line.next()?,
+    );
+    if !version.starts_with("HTTP/1.") {
+        return None;
+    }
+    let headers = lines
+        .take_while(|line| !line.is_empty())
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_lowercase(), value.trim().to_string()))
+        .collect();
+    Some(RequestHead {
+        method: method.to_string(),
+        path: path.to_string(),
+        headers,
+    })
+}
+
+fn webhook_settings(app: &AppHandle) -> WebhookSettings {
+    app.state::<AppState>()
+        .settings
+        .lock()
+        .unwrap()
+        .webhook
+        .clone()
+}
+
+fn status() -> WebhookStatus {
+    let listener = LISTENER.lock().unwrap();
+    WebhookStatus {
+        running: listener.is_some(),
+        port: listener
+            .as_ref()
+            .map_or_else(default_port, |(settings, _)| settings.port),
+    }
+}
+
+fn download(app: &AppHandle, url: String) -> Result<String, String> {
+    let parsed = reqwest::Url::parse(&url).map_err(|_| format!("Invalid URL: {}", url))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err("Only http and https URLs can be downloaded".to_string());
+    }
+    let output_path = app
+        .path()
+        .download_dir()
+        .map_err(|e| format!("Could not find downloads directory: {}", e))?
+        .to_string_lossy()
+        .to_string();
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let options = crate::YouTubeDownloadOptions {
+            quality: "best".to_string(),
+            mode: "video_audio".to_string(),
+        };
+        if let Err(e) = crate::download_youtube_video(app.clone(), url, output_path, options).await
+        {
+            notifications::notify(&app, "Download failed", &e, NotifyAction::None);
+        }
+    });
+    Ok("Download started".to_string())
+}
+
+fn run(app: &AppHandle, action: WebhookAction) -> Result<String, String> {
+    match action {
+        WebhookAction::Notify { title, message } => {
+            notifications::notify(
+                app,
+                title.as_deref().unwrap_or("BunchaTools"),
+                &message,
+                NotifyAction::None,
+            );
+            Ok("Notification shown".to_string())
+        }
+        WebhookAction::Download { url } => download(app, url),
+        WebhookAction::Workflow(workflow) => {
+            if workflow.actions.is_empty() {
+                return Err("The workflow has no actions".to_string());
+            }
+            file_associations::run_workflow_actions(app, workflow);
+            Ok("Workflow started".to_string())
+        }
+    }
+}
+
+// Read one request; returns its head and body
+async fn read_request(stream: &mut TcpStream) -> Result<(RequestHead, Vec<u8>), (u16, String)> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let head_end = loop {
+        if let Some(end) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            break end;
+        }
+        if buf.len() > MAX_HEAD {
+            return Err((431, "Request headers too large".to_string()));
+        }
+        let read = stream
+            .read(&mut chunk)
+            .await
+            .map_err(|e| (400, e.to_string()))?;
+        if read == 0 {
+            return Err((400, "Incomplete request".to_string()));
+        }
+        buf.extend_from_slice(&chunk[..read]);
+    };
+
+    let head = parse_head(&String::from_utf8_lossy(&buf[..head_end]))
+        .ok_or((400, "Malformed request".to_string()))?;
+    let length: usize = match head.headers.get("content-length") {
+        Some(length) => length
+            .parse()
+            .map_err(|_| (400, "Invalid Content-Length".to_string()))?,
+        None => 0,
+    };
+    if length > MAX_BODY {
+        return Err((413, "Body too large".to_string()));
+    }
+    let mut body = buf.split_off(head_end + 4);
+    if body.len() < length {
+        let mut rest = vec![0u8; length - body.len()];
+        stream
+            .read_exact(&mut rest)
+            .await
+            .map_err(|e| (400, e.to_string()))?;
+        body.extend(rest);
+    }
+    body.truncate(length);
+    Ok((head, body))
+}
+
+async fn respond(stream: &mut TcpStream, status: u16, body: serde_json::Value) {
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        431 => "Request Header Fields Too Large",
+        _ => "Error",
+    };
+    let body = body.to_string();
+    let response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason,
+        body.len(),
+        body
+    );
+    let _ = stream.write_all(response.as_bytes()).await;
+    let _ = stream.shutdown().await;
+}
+
+async fn handle_connection(app: AppHandle, mut stream: TcpStream, secret: String) {
+    let request = tokio::time::timeout(READ_TIMEOUT, read_request(&mut stream))
+        .await
+        .unwrap_or_else(|_| Err((400, "Timed out".to_string())));
+    let (head, body) = match request {
+        Ok(request) => request,
+        Err((status, error)) => {
+            respond(
+                &mut stream,
+                status,
+                serde_json::json!({ "ok": false, "error": error }),
+            )
+            .await;
+            return;
+        }
+    };
+    if head.method != "POST" {
+        let error = "Only POST requests are accepted";
+        respond(
+            &mut stream,
+            405,
+            serde_json::json!({ "ok": false, "error": error }),
+        )
+        .await;
+        return;
+    }
+
+    let timestamp = head.headers.get(TIMESTAMP_HEADER).map(|t| t.trim());
+    let signature = head.headers.get(SIGNATURE_HEADER);
+    let (Some(timestamp), Some(signature)) = (timestamp, signature) else {
+        let error = "Missing timestamp or signature";
+        respond(
+            &mut stream,
+            401,
+            serde_json::json!({ "ok": false, "error": error }),
+        )
+        .await;
+        return;
+    };
+    let stamped_at = timestamp.parse::<i64>().ok();
+    let verified = stamped_at.filter(|_| verify_signature(&secret, timestamp, &body, signature));
+    let refusal = match verified {
+        Some(stamped_at) => check_freshness(
+            &mut RECENT.lock().unwrap(),
+            signature,
+            stamped_at,
+            now_secs(),
+        )
+        .err(),
+        None => {
+            tokio::time::sleep(FAILED_ATTEMPT_DELAY).await;
+            Some("Wrong signature".to_string())
+        }
+    };
+    if let Some(error) = refusal {
+        log::warn!("Refused webhook: {}", error);
+        respond(
+            &mut stream,
+            401,
+            serde_json::json!({ "ok": false, "error": error }),
+        )
+        .await;
+        return;
+    }
+
+    let result = serde_json::from_slice::<WebhookAction>(&body)
+        .map_err(|e| format!("Invalid webhook: {}", e))
+        .and_then(|action| {
+            log::info!("Webhook: {:?}", action);
+            run(&app, action)
+        });
+    match result {
+        Ok(message) => {
+            respond(
+                &mut stream,
+                200,
+                serde_json::json!({ "ok": true, "message": message }),
+            )
+            .await
+        }
+        Err(error) => {
+            respond(
+                &mut stream,
+                400,
+                serde_json::json!({ "ok": false, "error": error }),
+            )
+            .await
+        }
+    }
+}
+
+async fn serve(app: AppHandle, listener: TcpListener, secret: String) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                tauri::async_runtime::spawn(handle_connection(app.clone(), stream, secret.clone()));
+            }
+            Err(e) => log::warn!("Webhook accept failed: {}", e),
+        }
+    }
+}
+
+/// Start listening with `settings`, replacing a running listener
+async fn start(app: &AppHandle, settings: &WebhookSettings) -> Result<(), String> {
+    if settings.secret.chars().count() < MIN_SECRET_LEN {
+        return Err(format!(
+            "Choose a secret of at least {} characters",
+            MIN_SECRET_LEN
+        ));
+    }
+    if let Some((_, task)) = LISTENER.lock().unwrap().take() {
+        task.abort();
+    }
+    // Reachable from other machines, which is the point; the signature keeps it closed.
+    // An aborted listener on the same port lets go of it shortly after, so retry a little.
+    let mut attempt = 0;
+    let listener = loop {
+        match TcpListener::bind(("0.0.0.0", settings.port)).await {
+            Ok(listener) => break listener,
+            Err(_) if attempt < BIND_RETRIES => {
+                attempt += 1;
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+            Err(e) => return Err(format!("Failed to listen on port {}: {}", settings.port, e)),
+        }
+    };
+    log::info!("Webhook listener on port {}", settings.port);
+    let task = tauri::async_runtime::spawn(serve(app.clone(), listener, settings.secret.clone()));
+    *LISTENER.lock().unwrap() = Some((settings.clone(), task));
+    Ok(())
+}
+
+fn stop() {
+    if let Some((_, task)) = LISTENER.lock().unwrap().take() {
+        task.abort();
+        log::info!("Webhook listener stopped");
+    }
+}
+
+/// Start, restart or stop the listener to match `settings`
+pub fn apply_settings(app: &AppHandle, settings: &WebhookSettings) {
+    let running = LISTENER
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|(running, _)| running.clone());
+    if !settings.enabled {
+        stop();
+        return;
+    }
+    if running.as_ref() == Some(settings) {
+        return;
+    }
+    let (app, settings) = (app.clone(), settings.clone());
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = start(&app, &settings).await {
+            log::warn!("Webhook listener unavailable: {}", e);
+        }
+    });
+}
+
+fn save(app: &AppHandle, webhook: WebhookSettings) -> Result<(), String> {
+    let settings = {
+        let state = app.state::<AppState>();
+        let mut settings = state.settings.lock().unwrap();
+        settings.webhook = webhook;
+        settings.clone()
+    };
+    crate::save_settings_to_file(app, &settings)?;
+    let _ = app.emit("settings-changed", &settings);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_webhook_status() -> WebhookStatus {
+    status()
+}
+
+/// Listen for signed webhooks on `port`; remembered, so the listener comes back on restart
+#[tauri::command]
+pub async fn start_webhook_listener(
+    app: AppHandle,
+    port: u16,
+    secret: String,
+) -> Result<WebhookStatus, String> {
+    let settings = WebhookSettings {
+        enabled: true,
+        port,
+        secret: secret.trim().to_string(),
+    };
+    start(&app, &settings).await?;
+    save(&app, settings)?;
+    Ok(status())
+}
+
+#[tauri::command]
+pub fn stop_webhook_listener(app: AppHandle) -> Result<WebhookStatus, String> {
+    stop();
+    save(
+        &app,
+        WebhookSettings {
+            enabled: false,
+            ..webhook_settings(&app)
+        },
+    )?;
+    Ok(status())
+}
@@ -0,0 +1,128 @@
+// Remembered launcher positions, one per monitor
+// Dragging the window records where it was left on that display; showing it on a monitor
+// restores the saved spot, or centers it when there is none. Monitors are keyed by name and
+// scale factor, so the same display at a different DPI setting gets its own position.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tauri::{AppHandle, Manager, Monitor, PhysicalPosition, WebviewWindow};
+
+use crate::{platform, AppState};
+
+// Moves are saved once the window has stopped moving for this long
+const SAVE_DELAY: Duration = Duration::from_millis(500);
+
+// Position we moved the window to ourselves, so the resulting Moved event isn't recorded
+static LAST_PLACED: Mutex<Option<(i32, i32)>> = Mutex::new(None);
+static MOVE_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Identifier for a display: its name plus scale factor, e.g. "DP-1@1.50"
+pub fn monitor_key(monitor: &Monitor) -> String {
+    let name = match monitor.name() {
+        Some(name) => name.clone(),
+        None => {
+            let (position, size) = (monitor.position(), monitor.size());
+            format!(
+                "{}x{}+{}+{}",
+                size.width, size.height, position.x, position.y
+            )
+        }
+    };
+    format!("{}@{:.2}", name, monitor.scale_factor())
+}
+
+/// Whether the physical point (x, y) is on `monitor`
+pub fn contains(monitor: &Monitor, x: i32, y: i32) -> bool {
+    let (position, size) = (monitor.position(), monitor.size());
+    x >= position.x
+        && x < position.x + size.width as i32
+        && y >= position.y
+        && y < position.y + size.height as i32
+}
+
+/// Monitor the launcher should open on: the one under the cursor (or the focused output)
+fn target_monitor(window: &WebviewWindow) -> Option<Monitor> {
+    let monitors = window.available_monitors().ok()?;
+    let (x, y, width, height) = platform::get_cursor_monitor_work_area()?;
+    let (cx, cy) = (x + width / 2, y + height / 2);
+    monitors.into_iter().find(|m| contains(m, cx, cy))
+}
+
+/// Move the window to its saved spot on the cursor's monitor, or center it there
+pub fn place_main_window(app: &AppHandle, window: &WebviewWindow) {
+    let saved = target_monitor(window).and_then(|monitor| {
+        let state = app.state::<AppState>();
+        let (x, y) = *state
+            .settings
+            .lock()
+            .unwrap()
+            .window_positions
+            .get(&monitor_key(&monitor))?;
+        // Skip positions that no longer land on the display (resolution changed)
+        contains(&monitor, x, y).then_some((x, y))
+    });
+
+    let position = saved.or_else(|| {
+        let size = window.outer_size().ok()?;
+        platform::get_centered_position_on_cursor_monitor(size.width as i32, size.height as i32)
+    });
+
+    match position {
+        Some((x, y)) => {
+            *LAST_PLACED.lock().unwrap() = Some((x, y));
+            let _ = window.set_position(PhysicalPosition::new(x, y));
+        }
+        None => {
+            let _ = window.center();
+        }
+    }
+}
+
+/// Record a window move (from the window's Moved event) for the monitor it ended up on
+pub fn record_move(app: &AppHandle, window: &WebviewWindow, position: PhysicalPosition<i32>) {
+    if LAST_PLACED.lock().unwrap().take() == Some((position.x, position.y)) {
+        return;
+    }
+
+    let generation = MOVE_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    let app = app.clone();
+    let window = window.clone();
+    std::thread::spawn(move || {
+        std::thread::sleep(SAVE_DELAY);
+        if MOVE_GENERATION.load(Ordering::SeqCst) != generation {
+            return;
+        }
+        let Ok(Some(monitor)) = window.current_monitor() else {
+            return;
+        };
+        let Ok(position) = window.outer_position() else {
+            return;
+        };
+
+        let settings = {
+            let state = app.state::<AppState>();
+            let mut settings = state.settings.lock().unwrap();
+            settings
+                .window_positions
+                .insert(monitor_key(&monitor), (position.x, position.y));
+            settings.clone()
+        };
+        if let Err(e) = crate::save_settings_to_file(&app, &settings) {
+            log::warn!("Failed to save window position: {}", e);
+        }
+    });
+}
+
+/// Forget the saved positions so the launcher opens centered again
+#[tauri::command]
+pub fn reset_window_positions(app: AppHandle) -> Result<(), String> {
+    let settings = {
+        let state = app.state::<AppState>();
+        let mut settings = state.settings.lock().unwrap();
+        settings.window_positions.clear();
+        settings.clone()
+    };
+    crate::save_settings_to_file(&app, &settings)
+}
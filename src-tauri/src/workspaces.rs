@@ -0,0 +1,158 @@
+// Temp workspaces for jobs, one directory each under `<app data>/workspaces`
+// A job downloads, extracts and renders into its workspace and only moves the finished file
+// to where the user asked for it, so a failed or cancelled job leaves nothing half-written
+// next to their files; the workspace is removed with the job (see `JobHandle::workspace`).
+// A crash can't clean up after itself, so on startup any workspace made by another process
+// is removed and the reclaimed space reported with the startup health.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+const WORKSPACES_DIR: &str = "workspaces";
+
+static NEXT_WORKSPACE: AtomicU64 = AtomicU64::new(1);
+
+/// Leftovers of earlier sessions removed on startup
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct WorkspaceCleanup {
+    pub removed: u32,         // workspaces
+    pub reclaimed_bytes: u64, // size of the files in them
+}
+
+/// A job's temp directory, removed with everything in it when dropped
+#[derive(Debug)]
+pub struct Workspace {
+    path: PathBuf,
+}
+
+impl Workspace {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for Workspace {
+    fn drop(&mut self) {
+        // Can fail while a killed child still has a file open; startup cleanup gets it then
+        if let Err(e) = fs::remove_dir_all(&self.path) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                log::warn!("Failed to remove workspace {}: {}", self.path.display(), e);
+            }
+        }
+    }
+}
+
+fn workspaces_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_data = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("No app data directory: {}", e))?;
+    Ok(app_data.join(WORKSPACES_DIR))
+}
+
+// Keep the label readable in the directory name without letting it pick the path
+fn sanitize_label(label: &str) -> String {
+    let label: String = label
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .take(32)
+        .collect();
+    label.trim_matches('-').to_string()
+}
+
+/// Make a workspace in `root`, named `<pid>-<n>-<label>` so cleanup can tell whose it is
+pub fn create_in(root: &Path, label: &str) -> Result<Workspace, String> {
+    let n = NEXT_WORKSPACE.fetch_add(1, Ordering::SeqCst);
+    let path = root.join(format!(
+        "{}-{}-{}",
+        std::process::id(),
+        n,
+        sanitize_label(label)
+    ));
+    fs::create_dir_all(&path).map_err(|e| format!("Failed to create temp workspace: {}", e))?;
+    Ok(Workspace { path })
+}
+
+/// Make a workspace under app data, e.g. `create(app, "voice-input")`
+pub fn create(app: &AppHandle, label: &str) -> Result<Workspace, String> {
+    create_in(&workspaces_dir(app)?, label)
+}
+
+/// Move a finished file out of a workspace to `destination`, replacing what's there. Copies
+/// when the destination is on another drive, which a rename can't do.
+pub fn persist(file: &Path, destination: &Path) -> Result<(), String> {
+    if fs::rename(file, destination).is_ok() {
+        return Ok(());
+    }
+    fs::copy(file, destination)
+        .map_err(|e| format!("Failed to save {}: {}", destination.display(), e))?;
+    let _ = fs::remove_file(file);
+    Ok(())
+}
+
+/// Bytes in `path`, with everything under it if it's a directory
+pub fn size_of(path: &Path) -> u64 {
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+    fs::read_dir(path)
+        .map(|entries| entries.flatten().map(|entry| size_of(&entry.path())).sum())
+        .unwrap_or(0)
+}
+
+/// Remove the workspaces in `root` not made by process `own_pid`. Only one BunchaTools runs at a
+/// time (the single-instance plugin), so those belong to a session that crashed or was killed.
+pub fn remove_orphans(root: &Path, own_pid: u32) -> WorkspaceCleanup {
+    let mut cleanup = WorkspaceCleanup::default();
+    let Ok(entries) = fs::read_dir(root) else {
+        return cleanup;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let pid = name
+            .split('-')
+            .next()
+            .and_then(|pid| pid.parse::<u32>().ok());
+        if pid == Some(own_pid) {
+            continue;
+        }
+        let path = entry.path();
+        let size = size_of(&path);
+        let removed = if path.is_dir() {
+            fs::remove_dir_all(&path)
+        } else {
+            fs::remove_file(&path)
+        };
+        match removed {
+            Ok(()) => {
+                cleanup.removed += 1;
+                cleanup.reclaimed_bytes += size;
+            }
+            Err(e) => log::warn!("Failed to remove leftover {}: {}", path.display(), e),
+        }
+    }
+    cleanup
+}
+
+/// Remove what crashed sessions left in the workspaces directory (run once on startup)
+pub fn cleanup_on_startup(app: &AppHandle) -> WorkspaceCleanup {
+    let cleanup = match workspaces_dir(app) {
+        Ok(root) => remove_orphans(&root, std::process::id()),
+        Err(_) => WorkspaceCleanup::default(),
+    };
+    if cleanup.removed > 0 {
+        log::info!(
+            "Removed {} leftover job workspace(s), reclaimed {} bytes",
+            cleanup.removed,
+            cleanup.reclaimed_bytes
+        );
+    }
+    cleanup
+}
@@ -0,0 +1,122 @@
+use base64::Engine;
+use bunchatools_lib::testing::{render_annotations, Annotation};
+use image::{Rgba, RgbaImage};
+
+const WHITE: Rgba<u8> = Rgba([255, 255, 255, 255]);
+
+fn annotations(json: serde_json::Value) -> Vec<Annotation> {
+    serde_json::from_value(json).unwrap()
+}
+
+#[test]
+fn arrows_and_rectangles_are_drawn_in_their_color() {
+    let image = RgbaImage::from_pixel(100, 100, WHITE);
+    let out = render_annotations(
+        &image,
+        &annotations(serde_json::json!([
+            { "kind": "arrow", "from": { "x": 10, "y": 50 }, "to": { "x": 90, "y": 50 }, "color": "#ff0000", "width": 4 },
+            { "kind": "rectangle", "area": { "x": 80, "y": 80, "width": -60, "height": -20 }, "color": "blue", "width": 2 },
+        ])),
+    )
+    .unwrap();
+
+    // Shaft and the tip of the head
+    assert_eq!(*out.get_pixel(40, 50), Rgba([255, 0, 0, 255]));
+    assert_eq!(*out.get_pixel(88, 50), Rgba([255, 0, 0, 255]));
+    // Dragged up-left from the bottom-right corner
+    assert_eq!(*out.get_pixel(50, 60), Rgba([0, 0, 255, 255]));
+    assert_eq!(*out.get_pixel(50, 70), WHITE);
+    // Untouched, and the input isn't modified
+    assert_eq!(*out.get_pixel(5, 5), WHITE);
+    assert_eq!(*image.get_pixel(40, 50), WHITE);
+}
+
+#[test]
+fn blur_stays_inside_its_area() {
+    let mut image = RgbaImage::from_pixel(40, 40, WHITE);
+    for x in 0..40 {
+        image.put_pixel(x, 10, Rgba([0, 0, 0, 255]));
+        image.put_pixel(x, 30, Rgba([0, 0, 0, 255]));
+    }
+    let out = render_annotations(
+        &image,
+        &annotations(serde_json::json!([
+            { "kind": "blur", "area": { "x": 0, "y": 0, "width": 40, "height": 20 }, "strength": 3 },
+        ])),
+    )
+    .unwrap();
+
+    assert_ne!(*out.get_pixel(20, 10), Rgba([0, 0, 0, 255]));
+    assert_ne!(*out.get_pixel(20, 12), WHITE);
+    assert_eq!(*out.get_pixel(20, 30), Rgba([0, 0, 0, 255]));
+}
+
+#[test]
+fn text_sprites_are_composited() {
+    let sprite = RgbaImage::from_fn(4, 2, |x, _| {
+        if x < 2 {
+            Rgba([0, 128, 0, 255])
+        } else {
+            Rgba([0, 0, 0, 0])
+        }
+    });
+    let mut png = Vec::new();
+    sprite
+        .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+        .unwrap();
+    let data_url = format!(
+        "data:image/png;base64,{}",
+        base64::engine::general_purpose::STANDARD.encode(&png)
+    );
+
+    let image = RgbaImage::from_pixel(10, 10, WHITE);
+    let out = render_annotations(
+        &image,
+        &annotations(serde_json::json!([
+            { "kind": "text", "at": { "x": 3, "y": 4 }, "sprite": data_url },
+        ])),
+    )
+    .unwrap();
+
+    assert_eq!(*out.get_pixel(3, 4), Rgba([0, 128, 0, 255]));
+    // Transparent parts of the sprite leave the screenshot visible
+    assert_eq!(*out.get_pixel(6, 4), WHITE);
+}
+
+#[test]
+fn bad_colors_and_sprites_are_rejected() {
+    let image = RgbaImage::from_pixel(10, 10, WHITE);
+    let bad_color = annotations(serde_json::json!([
+        { "kind": "rectangle", "area": { "x": 0, "y": 0, "width": 5, "height": 5 }, "color": "not a color", "width": 1 },
+    ]));
+    assert!(render_annotations(&image, &bad_color).is_err());
+
+    let bad_sprite = annotations(serde_json::json!([
+        { "kind": "text", "at": { "x": 0, "y": 0 }, "sprite": "data:image/png;base64,AAAA" },
+    ]));
+    assert!(render_annotations(&image, &bad_sprite).is_err());
+}
+
+#[test]
+fn pixelate_averages_each_block() {
+    // Black and white columns become the gray they average to, outside the area they stay
+    let image = RgbaImage::from_fn(20, 10, |x, _| {
+        if x % 2 == 0 {
+            Rgba([0, 0, 0, 255])
+        } else {
+            WHITE
+        }
+    });
+    let out = render_annotations(
+        &image,
+        &annotations(serde_json::json!([
+            { "kind": "pixelate", "area": { "x": 0, "y": 0, "width": 8, "height": 10 }, "block": 4 },
+        ])),
+    )
+    .unwrap();
+
+    assert_eq!(*out.get_pixel(0, 0), Rgba([127, 127, 127, 255]));
+    assert_eq!(*out.get_pixel(7, 9), Rgba([127, 127, 127, 255]));
+    assert_eq!(*out.get_pixel(8, 0), Rgba([0, 0, 0, 255]));
+    assert_eq!(*out.get_pixel(9, 0), WHITE);
+}
@@ -0,0 +1,49 @@
+use bunchatools_lib::testing::{hash_passcode, verify_passcode};
+
+// Argon2's minimum is 8 KiB per lane; the app uses 19 MiB
+const TEST_MEMORY_KIB: u32 = 64;
+
+// "2468" hashed by the PBKDF2 scheme used before Argon2 (1000 iterations)
+const LEGACY_HASH: &str =
+    "pbkdf2-sha256$1000$MDEyMzQ1Njc4OWFiY2RlZg==$RykCuFJiXjaaWy/+Tf9rr0wUj5t7g5zOKdXms+nU5TA=";
+
+#[test]
+fn only_the_right_passcode_verifies() {
+    let hash = hash_passcode("2468", TEST_MEMORY_KIB).unwrap();
+    assert!(hash.starts_with("$argon2id$v=19$m=64,t=2,p=1$"));
+    assert!(!hash.contains("2468$"));
+    assert!(verify_passcode(&hash, "2468"));
+    assert!(!verify_passcode(&hash, "2469"));
+    assert!(!verify_passcode(&hash, ""));
+
+    // Salted: the same passcode hashes differently each time
+    assert_ne!(hash_passcode("2468", TEST_MEMORY_KIB).unwrap(), hash);
+}
+
+#[test]
+fn hashes_from_before_argon2_still_verify() {
+    assert!(verify_passcode(LEGACY_HASH, "2468"));
+    assert!(!verify_passcode(LEGACY_HASH, "2469"));
+    assert!(!verify_passcode(
+        &LEGACY_HASH.replacen("$1000$", "$999$", 1),
+        "2468"
+    ));
+    assert!(!verify_passcode(
+        &LEGACY_HASH.replacen("$1000$", "$0$", 1),
+        "2468"
+    ));
+}
+
+#[test]
+fn malformed_hashes_never_verify() {
+    let hash = hash_passcode("2468", TEST_MEMORY_KIB).unwrap();
+    // Other Argon2 variants aren't accepted
+    assert!(!verify_passcode(
+        &hash.replacen("$argon2id$", "$argon2i$", 1),
+        "2468"
+    ));
+    assert!(!verify_passcode(&hash.replacen("m=64", "m=65", 1), "2468"));
+    assert!(!verify_passcode("", "2468"));
+    assert!(!verify_passcode("$argon2id$v=19$!!$??", "2468"));
+    assert!(!verify_passcode("pbkdf2-sha256$1000$!!$??", "2468"));
+}
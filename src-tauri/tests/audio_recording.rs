@@ -0,0 +1,52 @@
+use bunchatools_lib::testing::{
+    audio_recording_args, meter_level, parse_peak, AudioCaptureInput, AudioFormat,
+};
+
+fn microphone() -> AudioCaptureInput {
+    AudioCaptureInput {
+        format: "pulse".to_string(),
+        device: "alsa_input.usb-Blue_Yeti-00.analog-stereo".to_string(),
+    }
+}
+
+// The value following `flag`
+fn arg<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    let i = args.iter().position(|a| a == flag)?;
+    args.get(i + 1).map(String::as_str)
+}
+
+#[test]
+fn records_the_device_with_a_meter() {
+    let args = audio_recording_args(&microphone(), AudioFormat::Opus, "/home/a/Music/take.opus");
+    assert_eq!(arg(&args, "-f"), Some("pulse"));
+    assert_eq!(
+        arg(&args, "-i"),
+        Some("alsa_input.usb-Blue_Yeti-00.analog-stereo")
+    );
+    assert!(arg(&args, "-af").unwrap().contains("file=-"));
+    assert_eq!(arg(&args, "-c:a"), Some("libopus"));
+    assert_eq!(args.last().unwrap(), "/home/a/Music/take.opus");
+
+    let mp3 = audio_recording_args(&microphone(), AudioFormat::Mp3, "take.mp3");
+    assert_eq!(arg(&mp3, "-c:a"), Some("libmp3lame"));
+    let wav = audio_recording_args(&microphone(), AudioFormat::Wav, "take.wav");
+    assert_eq!(arg(&wav, "-c:a"), Some("pcm_s16le"));
+}
+
+#[test]
+fn peaks_become_meter_levels() {
+    assert_eq!(
+        parse_peak("lavfi.astats.Overall.Peak_level=-12.041200"),
+        Some(Some(-12.0412))
+    );
+    assert_eq!(
+        parse_peak("lavfi.astats.Overall.Peak_level=-inf"),
+        Some(None)
+    );
+    assert_eq!(parse_peak("frame:12   pts:12288   pts_time:0.256"), None);
+
+    assert_eq!(meter_level(Some(0.0)), 1.0);
+    assert_eq!(meter_level(Some(-30.0)), 0.5);
+    assert_eq!(meter_level(Some(-90.0)), 0.0);
+    assert_eq!(meter_level(None), 0.0);
+}
@@ -0,0 +1,31 @@
+use bunchatools_lib::testing::render_banner;
+
+#[test]
+fn standard_font_matches_figlet() {
+    // Output of `figlet Hello`, trailing spaces trimmed
+    let expected = [
+        r" _   _      _ _",
+        r"| | | | ___| | | ___",
+        r"| |_| |/ _ \ | |/ _ \",
+        r"|  _  |  __/ | | (_) |",
+        r"|_| |_|\___|_|_|\___/",
+    ]
+    .join("\n");
+    assert_eq!(render_banner("Hello", None).unwrap(), expected);
+}
+
+#[test]
+fn block_fonts_render_every_line() {
+    let banner = render_banner("ab\ncd", Some("tiny")).unwrap();
+    assert_eq!(banner.lines().count(), 6);
+    assert!(banner.chars().all(|c| " ▀▄█\n".contains(c)));
+
+    let solid = render_banner("Hi!", Some("SOLID")).unwrap();
+    assert_eq!(solid.lines().count(), 5);
+}
+
+#[test]
+fn rejects_unknown_fonts_and_empty_text() {
+    assert!(render_banner("x", Some("comic-sans")).is_err());
+    assert!(render_banner("   ", None).is_err());
+}
@@ -0,0 +1,65 @@
+use bunchatools_lib::testing::{
+    apply_memory, calc_tape, format_number, push_entry, CalcEntry, CalcHistory, MemoryOp,
+};
+
+fn entry(expression: &str, result: f64) -> CalcEntry {
+    CalcEntry {
+        expression: expression.to_string(),
+        result,
+        timestamp: 0,
+    }
+}
+
+#[test]
+fn numbers_lose_float_noise() {
+    assert_eq!(format_number(0.1 + 0.2), "0.3");
+    assert_eq!(format_number(42.0), "42");
+    assert_eq!(format_number(-2.5), "-2.5");
+    assert_eq!(format_number(-0.0), "0");
+}
+
+#[test]
+fn repeated_calculations_are_kept_once() {
+    let mut history = CalcHistory::default();
+    push_entry(&mut history, entry("2+2", 4.0));
+    push_entry(&mut history, entry("2+2", 4.0));
+    push_entry(&mut history, entry("3*3", 9.0));
+    push_entry(&mut history, entry("2+2", 4.0));
+    assert_eq!(history.entries.len(), 3);
+}
+
+#[test]
+fn tape_is_capped() {
+    let mut history = CalcHistory::default();
+    for i in 0..250 {
+        push_entry(&mut history, entry(&format!("{}+1", i), i as f64 + 1.0));
+    }
+    assert_eq!(history.entries.len(), 200);
+    assert_eq!(history.entries[0].expression, "50+1");
+}
+
+#[test]
+fn memory_keys() {
+    let mut history = CalcHistory::default();
+    assert!(apply_memory(&mut history, MemoryOp::Add, None).is_err());
+
+    push_entry(&mut history, entry("6*7", 42.0));
+    assert_eq!(apply_memory(&mut history, MemoryOp::Add, None), Ok(42.0));
+    assert_eq!(
+        apply_memory(&mut history, MemoryOp::Subtract, Some(2.0)),
+        Ok(40.0)
+    );
+    assert_eq!(apply_memory(&mut history, MemoryOp::Recall, None), Ok(40.0));
+    assert_eq!(apply_memory(&mut history, MemoryOp::Clear, None), Ok(0.0));
+}
+
+#[test]
+fn tape_lines_up_results() {
+    let mut history = CalcHistory::default();
+    push_entry(&mut history, entry("6*7", 42.0));
+    push_entry(&mut history, entry("100/8", 12.5));
+    assert_eq!(calc_tape(&history), "6*7   = 42\n100/8 = 12.5\n");
+
+    history.memory = 42.0;
+    assert!(calc_tape(&history).ends_with("\nM = 42\n"));
+}
@@ -0,0 +1,46 @@
+use bunchatools_lib::testing::{parse_numbers, NumberSummary};
+
+#[test]
+fn spreadsheet_column() {
+    let column = "Amount\n$1,234.50\n$99.99\n($34.49)\n-€10\n";
+    assert_eq!(
+        parse_numbers(column, '.'),
+        vec![1234.5, 99.99, -34.49, -10.0]
+    );
+
+    let summary = NumberSummary::from_numbers(&parse_numbers(column, '.')).unwrap();
+    assert_eq!(summary.count, 4);
+    assert!((summary.sum - 1290.0).abs() < 1e-9);
+    assert_eq!(summary.min, -34.49);
+    assert_eq!(summary.max, 1234.5);
+}
+
+#[test]
+fn european_separators() {
+    assert_eq!(
+        parse_numbers("1.234,56 € + 2.000.000 € + 12,5 %", ','),
+        vec![1234.56, 2_000_000.0, 12.5]
+    );
+    assert_eq!(
+        parse_numbers("1\u{a0}234,5 CHF 1'000", ','),
+        vec![1234.5, 1000.0]
+    );
+}
+
+#[test]
+fn ambiguous_grouping_follows_locale() {
+    assert_eq!(parse_numbers("1,234", '.'), vec![1234.0]);
+    assert_eq!(parse_numbers("1,234", ','), vec![1.234]);
+    assert_eq!(parse_numbers("1.234", ','), vec![1234.0]);
+    assert_eq!(parse_numbers("1.5", ','), vec![1.5]);
+}
+
+#[test]
+fn lists_and_noise() {
+    assert_eq!(parse_numbers("1, 2, 3.", '.'), vec![1.0, 2.0, 3.0]);
+    assert_eq!(parse_numbers("1,2,3", '.'), vec![1.0, 2.0, 3.0]);
+    // Versions / IP addresses aren't numbers; hyphens inside words aren't signs
+    assert_eq!(parse_numbers("v1.2.3 on 10.0.0.1", '.'), Vec::<f64>::new());
+    assert_eq!(parse_numbers("item-5", '.'), vec![5.0]);
+    assert_eq!(NumberSummary::from_numbers(&[]), None);
+}
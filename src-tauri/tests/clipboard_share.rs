@@ -0,0 +1,130 @@
+use std::net::{IpAddr, Ipv4Addr};
+
+use bunchatools_lib::testing::{
+    encode_mdns_query, encode_mdns_response, open_payload, parse_mdns, seal_payload,
+    service_instances, share_key, MdnsRecord, MdnsRecordData, SeenMessages, SharePayload,
+};
+
+const SERVICE: &str = "_bunchatools._tcp.local";
+
+fn payload(sent_at: i64) -> SharePayload {
+    SharePayload {
+        from: "Desktop".to_string(),
+        sent_at,
+        text: "copied on the desktop".to_string(),
+    }
+}
+
+#[test]
+fn only_devices_with_the_same_code_can_read_messages() {
+    let key = share_key("plum-harbor-42");
+    let message = seal_payload(&key, &payload(1_700_000_000)).unwrap();
+    assert!(!String::from_utf8_lossy(&message).contains("desktop"));
+
+    assert_eq!(
+        open_payload(&key, &message, 1_700_000_030).unwrap(),
+        payload(1_700_000_000)
+    );
+    // Surrounding whitespace in the code doesn't matter
+    assert!(open_payload(&share_key(" plum-harbor-42 "), &message, 1_700_000_030).is_ok());
+    assert!(open_payload(&share_key("plum-harbor-43"), &message, 1_700_000_030).is_err());
+}
+
+#[test]
+fn old_messages_are_refused() {
+    let key = share_key("plum-harbor-42");
+    let message = seal_payload(&key, &payload(1_700_000_000)).unwrap();
+    assert!(open_payload(&key, &message, 1_700_000_000 + 600).is_err());
+    assert!(open_payload(&key, &message, 1_700_000_000 - 600).is_err());
+}
+
+#[test]
+fn fresh_messages_are_only_accepted_once() {
+    let key = share_key("plum-harbor-42");
+    let first = seal_payload(&key, &payload(1_700_000_000)).unwrap();
+    let second = seal_payload(&key, &payload(1_700_000_000)).unwrap();
+    let mut seen = SeenMessages::default();
+
+    assert!(seen.first_sighting(&first, 1_700_000_000, 1_700_000_010));
+    assert!(!seen.first_sighting(&first, 1_700_000_000, 1_700_000_060));
+    // The same text sent again is a new message
+    assert!(seen.first_sighting(&second, 1_700_000_000, 1_700_000_060));
+    // Once too old to open anyway, the messages are forgotten
+    assert!(seen.first_sighting(&first, 1_700_000_000, 1_700_000_000 + 600));
+}
+
+#[test]
+fn advertisements_round_trip_to_service_instances() {
+    let full_name = format!("Laptop.{}", SERVICE);
+    let response = encode_mdns_response(&[
+        MdnsRecord {
+            name: SERVICE.to_string(),
+            ttl: 120,
+            data: MdnsRecordData::Ptr(full_name.clone()),
+        },
+        MdnsRecord {
+            name: full_name.clone(),
+            ttl: 120,
+            data: MdnsRecordData::Srv {
+                port: 50123,
+                target: "Laptop.local".to_string(),
+            },
+        },
+        MdnsRecord {
+            name: full_name,
+            ttl: 120,
+            data: MdnsRecordData::Txt(vec!["id=abc".to_string(), "clipboard=1".to_string()]),
+        },
+        MdnsRecord {
+            name: "Laptop.local".to_string(),
+            ttl: 120,
+            data: MdnsRecordData::A(Ipv4Addr::new(192, 168, 1, 20)),
+        },
+    ]);
+    let message = parse_mdns(&response).unwrap();
+    assert!(message.is_response);
+    assert_eq!(message.records.len(), 4);
+
+    let source = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+    let instances = service_instances(&message, SERVICE, source);
+    assert_eq!(instances.len(), 1);
+    assert_eq!(instances[0].instance, "Laptop");
+    assert_eq!(
+        instances[0].address,
+        IpAddr::V4(Ipv4Addr::new(192, 168, 1, 20))
+    );
+    assert_eq!(instances[0].port, 50123);
+    assert_eq!(instances[0].txt.get("id").map(String::as_str), Some("abc"));
+
+    // Other services are ignored
+    assert!(service_instances(&message, "_http._tcp.local", source).is_empty());
+}
+
+#[test]
+fn queries_and_compressed_names_parse() {
+    let query = parse_mdns(&encode_mdns_query(SERVICE, 12)).unwrap();
+    assert!(!query.is_response);
+    assert_eq!(query.questions, vec![(SERVICE.to_string(), 12)]);
+
+    // A PTR answer whose name and target point back at the question's name
+    let mut packet = vec![0, 0, 0x84, 0, 0, 1, 0, 1, 0, 0, 0, 0];
+    for label in ["_bunchatools", "_tcp", "local"] {
+        packet.push(label.len() as u8);
+        packet.extend_from_slice(label.as_bytes());
+    }
+    packet.extend_from_slice(&[0, 0, 12, 0, 1]);
+    packet.extend_from_slice(&[0xc0, 12, 0, 12, 0, 1, 0, 0, 0, 120, 0, 9]);
+    packet.extend_from_slice(&[6, b'L', b'a', b'p', b't', b'o', b'p', 0xc0, 12]);
+    let message = parse_mdns(&packet).unwrap();
+    assert_eq!(message.records[0].name, SERVICE);
+    assert_eq!(
+        message.records[0].data,
+        MdnsRecordData::Ptr(format!("Laptop.{}", SERVICE))
+    );
+
+    // Truncated and self-referencing packets are rejected, not looped over
+    assert!(parse_mdns(&packet[..packet.len() - 3]).is_none());
+    let mut looping = vec![0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0];
+    looping.extend_from_slice(&[0xc0, 12, 0, 12, 0, 1]);
+    assert!(parse_mdns(&looping).is_none());
+}
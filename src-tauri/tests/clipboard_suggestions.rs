@@ -0,0 +1,64 @@
+use bunchatools_lib::testing::{suggest_for_text, SuggestionKind};
+
+fn kind_of(text: &str) -> Option<SuggestionKind> {
+    suggest_for_text(text, "en").map(|s| s.kind)
+}
+
+#[test]
+fn urls() {
+    let suggestion = suggest_for_text("  https://www.youtube.com/watch?v=abc\n", "en").unwrap();
+    assert_eq!(suggestion.kind, SuggestionKind::YoutubeUrl);
+    assert_eq!(suggestion.tool, "youtube-downloader");
+    assert_eq!(suggestion.value, "https://www.youtube.com/watch?v=abc");
+
+    assert_eq!(
+        kind_of("https://youtu.be/abc"),
+        Some(SuggestionKind::YoutubeUrl)
+    );
+    assert_eq!(
+        kind_of("https://github.com/kayfgit/BunchaTools/tree/main/src"),
+        Some(SuggestionKind::GitUrl)
+    );
+    assert_eq!(kind_of("https://example.com/video.mp4"), None);
+}
+
+#[test]
+fn hex_colors() {
+    assert_eq!(suggest_for_text("#22c55e", "en").unwrap().value, "#22C55E");
+    assert_eq!(suggest_for_text("#f0a", "en").unwrap().value, "#FF00AA");
+    // Bare hex is more often a hash or id than a color
+    assert_eq!(kind_of("22c55e"), None);
+    assert_eq!(kind_of("#12345"), None);
+}
+
+#[test]
+fn video_file_paths() {
+    let dir = std::env::temp_dir().join("bunchatools-suggestion-test");
+    std::fs::create_dir_all(&dir).unwrap();
+    let video = dir.join("clip.MKV");
+    std::fs::write(&video, b"").unwrap();
+
+    let suggestion = suggest_for_text(&format!("\"{}\"", video.display()), "en").unwrap();
+    assert_eq!(suggestion.kind, SuggestionKind::ConvertFile);
+    assert_eq!(suggestion.value, video.to_string_lossy());
+
+    // Paths that don't exist aren't suggested
+    assert_eq!(kind_of(&dir.join("missing.mp4").to_string_lossy()), None);
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn foreign_text() {
+    let text = "Der schnelle braune Fuchs springt über den faulen Hund und läuft davon";
+    let suggestion = suggest_for_text(text, "en").unwrap();
+    assert_eq!(suggestion.kind, SuggestionKind::Translation);
+    assert_eq!(suggestion.tool, "quick-translation");
+    assert_eq!(suggestion.language.as_deref(), Some("German"));
+
+    // Already in the target language
+    assert_eq!(suggest_for_text(text, "de"), None);
+    assert_eq!(
+        kind_of("The quick brown fox jumps over the lazy dog and runs away"),
+        None
+    );
+}
@@ -0,0 +1,73 @@
+use bunchatools_lib::testing::{contrast, describe_color, format_color, parse_color};
+
+#[test]
+fn parses_notations() {
+    assert_eq!(parse_color("#1e90ff"), Ok([30, 144, 255]));
+    assert_eq!(parse_color("F0A"), Ok([255, 0, 170]));
+    assert_eq!(parse_color("DodgerBlue"), Ok([30, 144, 255]));
+    assert_eq!(parse_color("rgb(255 128 0 / 50%)"), Ok([255, 128, 0]));
+    assert_eq!(parse_color("rgb(100%, 0%, 50%)"), Ok([255, 0, 128]));
+    assert_eq!(parse_color("hsl(120, 100%, 25%)"), Ok([0, 128, 0]));
+    assert_eq!(parse_color("hsv(60, 100%, 100%)"), Ok([255, 255, 0]));
+    assert_eq!(parse_color("cmyk(0%, 100%, 100%, 0%)"), Ok([255, 0, 0]));
+    assert_eq!(parse_color("oklch(62.8% 0.258 29.23)"), Ok([255, 0, 0]));
+    assert_eq!(parse_color("oklch(0.628 0.258 29.23)"), Ok([255, 0, 0]));
+    assert_eq!(parse_color("lab(53% 80 67)"), Ok([254, 0, 0]));
+    assert!(parse_color("rgb(1, 2)").is_err());
+    assert!(parse_color("#12345").is_err());
+    assert!(parse_color("notacolor").is_err());
+}
+
+#[test]
+fn formats_match_the_color_panel() {
+    let formats = describe_color([30, 144, 255]);
+    assert_eq!(formats.hex, "#1E90FF");
+    assert_eq!(formats.rgb, "rgb(30, 144, 255)");
+    assert_eq!(formats.hsl, "hsl(210, 100%, 56%)");
+    assert_eq!(formats.hsv, "hsv(210, 88%, 100%)");
+    assert_eq!(formats.cmyk, "cmyk(88%, 44%, 0%, 0%)");
+    assert_eq!(formats.oklch, "oklch(65% 0.19 253)");
+    assert_eq!(formats.name, "dodgerblue");
+    assert!(formats.name_exact);
+
+    assert_eq!(
+        format_color([0, 0, 0], "cmyk").as_deref(),
+        Ok("cmyk(0%, 0%, 0%, 100%)")
+    );
+    assert_eq!(
+        format_color([128, 128, 128], "oklch").as_deref(),
+        Ok("oklch(60% 0 0)")
+    );
+    assert!(format_color([0, 0, 0], "pantone").is_err());
+}
+
+#[test]
+fn nearest_named_color() {
+    let formats = describe_color([250, 0, 5]);
+    assert_eq!(formats.name, "red");
+    assert!(!formats.name_exact);
+    // Shared values report the first name
+    assert_eq!(format_color([0, 255, 255], "name").as_deref(), Ok("aqua"));
+}
+
+#[test]
+fn contrast_levels_follow_wcag() {
+    let black_on_white = contrast([0, 0, 0], [255, 255, 255]);
+    assert_eq!(black_on_white.ratio, 21.0);
+    assert!(black_on_white.aaa_normal);
+    assert_eq!(contrast([255, 255, 255], [0, 0, 0]).ratio, 21.0);
+    assert_eq!(contrast([119, 119, 119], [119, 119, 119]).ratio, 1.0);
+
+    // #777 on white is 4.48:1, just short of AA for body text
+    let gray = contrast(parse_color("#777").unwrap(), [255, 255, 255]);
+    assert_eq!(gray.ratio, 4.48);
+    assert!(!gray.aa_normal);
+    assert!(gray.aa_large);
+    assert!(!gray.aaa_large);
+
+    let blue = contrast(parse_color("#0000ff").unwrap(), [255, 255, 255]);
+    assert_eq!(blue.foreground, "#0000FF");
+    assert_eq!(blue.background, "#FFFFFF");
+    assert_eq!(blue.ratio, 8.59);
+    assert!(blue.aaa_normal);
+}
@@ -0,0 +1,71 @@
+use bunchatools_lib::testing::{ConversionItem, ConversionQueue, ConversionStatus};
+
+fn items(names: &[&str]) -> Vec<ConversionItem> {
+    names
+        .iter()
+        .map(|name| ConversionItem {
+            input_path: format!("/videos/{}.mov", name),
+            output_path: format!("/out/{}.mp4", name),
+        })
+        .collect()
+}
+
+fn order(queue: &ConversionQueue) -> Vec<u64> {
+    queue.state().items.iter().map(|item| item.id).collect()
+}
+
+#[test]
+fn queue_starts_up_to_the_concurrency_and_reports_overall_progress() {
+    let mut queue = ConversionQueue::new();
+    queue.set_concurrency(2);
+    let ids = queue.add(items(&["a", "b", "c", "d"]), None);
+
+    let started: Vec<u64> = queue.start_next().iter().map(|item| item.id).collect();
+    assert_eq!(started, ids[..2]);
+    assert!(queue.start_next().is_empty());
+
+    assert!(queue.set_progress(ids[0], 50));
+    assert!(!queue.set_progress(ids[0], 50));
+    queue.finish(
+        ids[1],
+        ConversionStatus::Failed,
+        Some("Conversion failed".into()),
+    );
+    let state = queue.state();
+    assert_eq!(state.percent, (50 + 100) / 4);
+    assert_eq!((state.pending, state.done, state.failed), (3, 0, 1));
+
+    // A slot freed up, so the next queued file starts
+    let started: Vec<u64> = queue.start_next().iter().map(|item| item.id).collect();
+    assert_eq!(started, vec![ids[2]]);
+
+    // Raising the limit starts the rest
+    queue.set_concurrency(20);
+    assert_eq!(queue.state().concurrency, 8);
+    assert_eq!(queue.start_next().len(), 1);
+}
+
+#[test]
+fn only_queued_items_move_and_running_ones_are_not_removed() {
+    let mut queue = ConversionQueue::new();
+    queue.set_concurrency(1);
+    let ids = queue.add(items(&["a", "b", "c"]), None);
+    queue.start_next();
+
+    queue.move_item(ids[2], 1).unwrap();
+    assert_eq!(order(&queue), vec![ids[0], ids[2], ids[1]]);
+    queue.move_item(ids[2], 99).unwrap();
+    assert_eq!(order(&queue), vec![ids[0], ids[1], ids[2]]);
+    assert!(queue.move_item(ids[0], 2).is_err());
+
+    assert!(queue.remove(ids[0]).is_err());
+    queue.remove(ids[1]).unwrap();
+    assert_eq!(order(&queue), vec![ids[0], ids[2]]);
+    assert!(queue.remove(ids[1]).is_err());
+
+    // A new batch on an idle queue replaces the finished one
+    queue.finish(ids[0], ConversionStatus::Done, None);
+    queue.remove(ids[2]).unwrap();
+    let next = queue.add(items(&["d"]), None);
+    assert_eq!(order(&queue), next);
+}
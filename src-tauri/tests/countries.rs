@@ -0,0 +1,106 @@
+use bunchatools_lib::testing::{
+    country_flag, find_country, holiday_cache_is_fresh, lookup_country, parse_holidays,
+};
+use chrono::NaiveDate;
+
+fn codes(query: &str) -> Vec<String> {
+    lookup_country(query).into_iter().map(|c| c.code).collect()
+}
+
+fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+#[test]
+fn countries_are_found_by_code_name_and_alias() {
+    let germany = find_country("de").unwrap();
+    assert_eq!(
+        (germany.code3.as_str(), germany.name.as_str()),
+        ("DEU", "Germany")
+    );
+    assert_eq!(germany.calling_code, "+49");
+    assert_eq!(germany.currency, "EUR");
+    assert_eq!(germany.flag, "🇩🇪");
+
+    assert_eq!(find_country("JPN").unwrap().name, "Japan");
+    assert_eq!(find_country("uk").unwrap().code, "GB");
+    assert_eq!(find_country("Holland").unwrap().code, "NL");
+    // An exact name beats a longer one starting with it
+    assert_eq!(find_country("niger").unwrap().code, "NE");
+    assert_eq!(find_country("switz").unwrap().code, "CH");
+}
+
+#[test]
+fn calling_codes_and_currencies_list_every_country() {
+    assert_eq!(codes("+44"), ["GB", "GG", "IM", "JE"]);
+    assert_eq!(codes("1 876"), ["JM"]);
+    assert_eq!(codes("+1"), ["CA", "US"]);
+
+    let euro = codes("EUR");
+    assert!(euro.len() > 20);
+    assert!(euro.contains(&"DE".to_string()) && euro.contains(&"HR".to_string()));
+    assert!(!euro.contains(&"GB".to_string()));
+
+    assert_eq!(codes(""), Vec::<String>::new());
+    assert_eq!(codes("Atlantis"), Vec::<String>::new());
+}
+
+#[test]
+fn ambiguous_queries_list_the_candidates() {
+    // An exact name wins over Equatorial Guinea, Guinea-Bissau, ...
+    assert_eq!(find_country("guinea").unwrap().code, "GN");
+    let error = find_country("saint").unwrap_err();
+    assert!(
+        error.starts_with("\"saint\" could be Saint Barthélemy"),
+        "{}",
+        error
+    );
+    assert_eq!(
+        find_country("Narnia").unwrap_err(),
+        "No country called \"Narnia\""
+    );
+}
+
+#[test]
+fn flags_are_regional_indicators() {
+    assert_eq!(country_flag("us"), "🇺🇸");
+    assert_eq!(country_flag("JP"), "🇯🇵");
+}
+
+#[test]
+fn holiday_responses_are_parsed_and_sorted() {
+    let json = r#"[
+        {"date":"2026-12-25","localName":"Erster Weihnachtstag","name":"Christmas Day","countryCode":"DE",
+         "fixed":false,"global":true,"counties":null,"launchYear":null,"types":["Public"]},
+        {"date":"2026-01-06","localName":"Heilige Drei Könige","name":"Epiphany","countryCode":"DE",
+         "fixed":false,"global":false,"counties":["DE-BW","DE-BY","DE-ST"],"launchYear":null,"types":["Public"]}
+    ]"#;
+    let holidays = parse_holidays(json).unwrap();
+    assert_eq!(holidays.len(), 2);
+    assert_eq!(holidays[0].name, "Epiphany");
+    assert_eq!(holidays[0].date, date(2026, 1, 6));
+    assert!(!holidays[0].global);
+    let regions = holidays[0].regions.clone().unwrap();
+    assert_eq!(regions, ["DE-BW", "DE-BY", "DE-ST"]);
+    assert_eq!(holidays[1].local_name, "Erster Weihnachtstag");
+    assert_eq!(holidays[1].regions, None);
+
+    // The cache stores them in the app's own (snake_case) form, which parses back the same
+    let cached = serde_json::to_string(&holidays).unwrap();
+    assert!(cached.contains("\"local_name\""));
+    assert_eq!(parse_holidays(&cached).unwrap(), holidays);
+
+    assert!(parse_holidays("{\"status\":404}").is_err());
+}
+
+#[test]
+fn holiday_cache_expires_except_for_finished_years() {
+    let today = date(2026, 10, 16);
+    assert!(holiday_cache_is_fresh(2026, date(2026, 10, 1), today));
+    assert!(!holiday_cache_is_fresh(2026, date(2026, 9, 1), today));
+    assert!(!holiday_cache_is_fresh(2027, date(2026, 8, 1), today));
+    // Fetched after the year ended: final
+    assert!(holiday_cache_is_fresh(2024, date(2025, 2, 1), today));
+    // Fetched during the year: refetched once
+    assert!(!holiday_cache_is_fresh(2025, date(2025, 6, 1), today));
+}
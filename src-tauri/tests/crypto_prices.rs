@@ -0,0 +1,34 @@
+use bunchatools_lib::testing::{coin_id, rate_from_prices};
+use serde_json::json;
+
+#[test]
+fn symbols_map_to_coins() {
+    assert_eq!(coin_id("btc"), Some("bitcoin"));
+    assert_eq!(coin_id("SOL"), Some("solana"));
+    assert_eq!(coin_id("EUR"), None);
+}
+
+#[test]
+fn rates_come_from_a_common_quote() {
+    let prices = json!({
+        "bitcoin": { "eur": 60000.0, "usd": 65000.0 },
+        "ethereum": { "usd": 3250.0 },
+    });
+    // Fiat to coin and back, priced in the fiat side
+    assert_eq!(
+        rate_from_prices(&prices, "BTC", "EUR", "eur").unwrap(),
+        60000.0
+    );
+    assert_eq!(
+        rate_from_prices(&prices, "EUR", "BTC", "eur").unwrap(),
+        1.0 / 60000.0
+    );
+    // Coin to coin through dollars
+    assert_eq!(
+        rate_from_prices(&prices, "BTC", "ETH", "usd").unwrap(),
+        20.0
+    );
+
+    let error = rate_from_prices(&prices, "ETH", "EUR", "eur").unwrap_err();
+    assert_eq!(error, "No EUR price for ETH");
+}
@@ -0,0 +1,52 @@
+use bunchatools_lib::testing::{multi_targets, parse_time_series, percent_change, RateTable};
+
+#[test]
+fn any_pair_converts_through_the_base() {
+    // Shape of frankfurter's /latest
+    let table: RateTable = serde_json::from_str(
+        r#"{"amount":1.0,"base":"EUR","date":"2026-10-15","rates":{"USD":1.1,"JPY":165.0,"GBP":0.85}}"#,
+    )
+    .unwrap();
+    assert_eq!(table.date, "2026-10-15");
+    assert_eq!(table.fetched_at, 0);
+
+    assert_eq!(table.rate("EUR", "USD").unwrap(), 1.1);
+    assert_eq!(table.rate("usd", "eur").unwrap(), 1.0 / 1.1);
+    assert!((table.rate("USD", "JPY").unwrap() - 150.0).abs() < 1e-9);
+    assert_eq!(
+        table.rate("USD", "XYZ").unwrap_err(),
+        "Currency 'XYZ' not found"
+    );
+}
+
+#[test]
+fn changes_compare_with_the_last_working_day_before() {
+    // Shape of frankfurter's /2026-10-01..?from=USD&to=EUR
+    let history = parse_time_series(
+        r#"{"amount":1.0,"base":"USD","start_date":"2026-10-01","end_date":"2026-10-15","rates":{
+            "2026-10-15":{"EUR":0.9},"2026-10-02":{"EUR":0.8},"2026-10-08":{"EUR":1.0},"2026-10-01":{"EUR":0.75}}}"#,
+        "eur",
+    )
+    .unwrap();
+    assert_eq!(history.first().unwrap().date, "2026-10-01");
+    assert_eq!(history.len(), 4);
+
+    let today = chrono::NaiveDate::from_ymd_opt(2026, 10, 15).unwrap();
+    // 7 days back is the 8th
+    let week = percent_change(&history, 0.9, 7, today).unwrap();
+    assert!((week - -10.0).abs() < 1e-9);
+    // No rate on the 4th, the 2nd is the last one before it
+    let eleven_days = percent_change(&history, 0.9, 11, today).unwrap();
+    assert!((eleven_days - 12.5).abs() < 1e-9);
+    // Further back than the history goes
+    assert_eq!(percent_change(&history, 0.9, 30, today), None);
+}
+
+#[test]
+fn favorites_are_each_converted_once() {
+    let favorites: Vec<String> = ["usd", "EUR", " gbp ", "USD", "", "btc"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    assert_eq!(multi_targets("eur", &favorites), ["USD", "GBP", "BTC"]);
+}
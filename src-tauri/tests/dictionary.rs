@@ -0,0 +1,51 @@
+use bunchatools_lib::testing::parse_dictionary_entries;
+
+// Shaped like dictionaryapi.dev's answer for "bank": two entries (etymologies), both with nouns
+const BANK: &str = r#"[
+  {"word": "bank", "phonetics": [{"text": ""}, {"text": "/bæŋk/", "audio": ""}],
+   "meanings": [
+     {"partOfSpeech": "noun",
+      "definitions": [{"definition": "An institution where one can deposit money.",
+                       "example": "I need to go to the bank.", "synonyms": ["lender"], "antonyms": []}],
+      "synonyms": ["lender", "treasury"], "antonyms": []},
+     {"partOfSpeech": "verb",
+      "definitions": [{"definition": "To deposit in a bank.", "synonyms": [], "antonyms": []}],
+      "synonyms": [], "antonyms": ["withdraw"]}
+   ]},
+  {"word": "bank", "phonetic": "/bæŋk/",
+   "meanings": [
+     {"partOfSpeech": "noun",
+      "definitions": [{"definition": "The edge of a river.", "example": ""}],
+      "synonyms": ["shore"]}
+   ]}
+]"#;
+
+#[test]
+fn entries_merge_by_part_of_speech() {
+    let definition = parse_dictionary_entries(BANK, "en").unwrap();
+    assert_eq!(definition.word, "bank");
+    assert_eq!(definition.phonetic.as_deref(), Some("/bæŋk/"));
+    let parts: Vec<&str> = definition
+        .meanings
+        .iter()
+        .map(|m| m.part_of_speech.as_str())
+        .collect();
+    assert_eq!(parts, ["noun", "verb"]);
+
+    let noun = &definition.meanings[0];
+    assert_eq!(noun.senses.len(), 2);
+    assert_eq!(
+        noun.senses[0].example.as_deref(),
+        Some("I need to go to the bank.")
+    );
+    // Empty examples count as none
+    assert_eq!(noun.senses[1].example, None);
+    assert_eq!(noun.synonyms, ["lender", "treasury", "shore"]);
+    assert_eq!(definition.meanings[1].antonyms, ["withdraw"]);
+}
+
+#[test]
+fn nothing_to_define() {
+    assert!(parse_dictionary_entries("[]", "en").is_err());
+    assert!(parse_dictionary_entries(r#"{"title": "No Definitions Found"}"#, "en").is_err());
+}
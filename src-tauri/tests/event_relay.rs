@@ -0,0 +1,79 @@
+use std::io::Cursor;
+
+use bunchatools_lib::testing::{
+    fixture, relay_ffmpeg_progress, relay_ytdlp_output, CancellationToken, EventCapture,
+};
+
+#[test]
+fn ffmpeg_progress_is_emitted_in_steps_of_ten() {
+    let capture = EventCapture::new(&["conversion-progress"]);
+
+    relay_ffmpeg_progress(
+        capture.handle(),
+        Cursor::new(fixture("ffmpeg_progress.txt")),
+        20.0,
+    );
+
+    // Completion (100) is emitted by the command after ffmpeg exits, so the relay caps at 99
+    let progress: Vec<i64> = capture
+        .payloads("conversion-progress")
+        .iter()
+        .filter_map(|v| v.as_i64())
+        .collect();
+    assert_eq!(progress, vec![20, 50, 70, 90]);
+}
+
+#[test]
+fn ffmpeg_progress_needs_a_duration() {
+    let capture = EventCapture::new(&["conversion-progress"]);
+
+    relay_ffmpeg_progress(
+        capture.handle(),
+        Cursor::new(fixture("ffmpeg_progress.txt")),
+        0.0,
+    );
+
+    assert!(capture.payloads("conversion-progress").is_empty());
+}
+
+#[test]
+fn ytdlp_progress_and_final_path() {
+    let capture = EventCapture::new(&["youtube-download-progress"]);
+    let token = CancellationToken::default();
+
+    let path = relay_ytdlp_output(
+        capture.handle(),
+        Cursor::new(fixture("ytdlp_download.txt")),
+        &token,
+    )
+    .unwrap();
+
+    // The merged file wins over the per-format destinations
+    assert_eq!(
+        path.as_deref(),
+        Some("/home/user/Downloads/Never Gonna Give You Up.mp4")
+    );
+
+    let percents: Vec<f64> = capture
+        .payloads("youtube-download-progress")
+        .iter()
+        .filter_map(|v| v["percent"].as_f64())
+        .collect();
+    assert_eq!(percents, vec![0.0, 12.5, 100.0, 100.0]);
+}
+
+#[test]
+fn ytdlp_relay_stops_when_cancelled() {
+    let capture = EventCapture::new(&["youtube-download-progress"]);
+    let token = CancellationToken::default();
+    token.cancel();
+
+    let result = relay_ytdlp_output(
+        capture.handle(),
+        Cursor::new(fixture("ytdlp_download.txt")),
+        &token,
+    );
+
+    assert_eq!(result, Err("Download cancelled".to_string()));
+    assert!(capture.payloads("youtube-download-progress").is_empty());
+}
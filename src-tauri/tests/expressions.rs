@@ -0,0 +1,75 @@
+use std::collections::HashMap;
+
+use bunchatools_lib::testing::{evaluate_expression, uses_currency, RateTable};
+
+fn display(expr: &str) -> String {
+    match evaluate_expression(expr, None) {
+        Ok(result) => result.display,
+        Err(e) => panic!("{}: {}", expr, e),
+    }
+}
+
+#[test]
+fn arithmetic_percentages_and_functions() {
+    assert_eq!(display("2 + 3 * 4"), "14");
+    assert_eq!(display("-2^2"), "-4");
+    assert_eq!(display("2 ** 10 / 4"), "256");
+    assert_eq!(display("(1 + 2) * 3"), "9");
+    assert_eq!(display("0.1 + 0.2"), "0.3");
+    assert_eq!(display("1,000 * 1.5e2"), "150000");
+    assert_eq!(display("200 + 15%"), "230");
+    assert_eq!(display("80 - 25%"), "60");
+    assert_eq!(display("15% of 80"), "12");
+    assert_eq!(display("50% * 10"), "5");
+    assert_eq!(display("10 % 3"), "1");
+    assert_eq!(display("sqrt(16) + abs(-2)"), "6");
+    assert_eq!(display("round(pi * 100)"), "314");
+
+    assert!(evaluate_expression("1 / 0", None).is_err());
+    assert!(evaluate_expression("2 +", None).is_err());
+    assert!(evaluate_expression("(2 + 3", None).is_err());
+    assert!(evaluate_expression("2 pizzas", None).is_err());
+}
+
+#[test]
+fn bit_operations_and_bases() {
+    assert_eq!(display("0xFF & 0b1010"), "10");
+    assert_eq!(display("0xF0 | 0x0F in hex"), "0xFF");
+    assert_eq!(display("6 xor 3 in bin"), "0b101");
+    assert_eq!(display("1 << 10"), "1024");
+    assert_eq!(display("~0"), "-1");
+    assert_eq!(display("0o17 in dec"), "15");
+    assert_eq!(display("255 to hex"), "0xFF");
+    assert_eq!(display("-16 in hex"), "-0x10");
+
+    assert!(evaluate_expression("1.5 & 1", None).is_err());
+    assert!(evaluate_expression("2.5 in hex", None).is_err());
+}
+
+#[test]
+fn units_and_currencies_convert_to_the_first_term() {
+    assert_eq!(display("5 km + 300 m"), "5.3 km");
+    assert_eq!(display("1 mi + 1 km in m"), "2609.344 m");
+    assert_eq!(display("2 in in cm"), "5.08 cm");
+    assert_eq!(display("3 * 2 kg"), "6 kg");
+    assert_eq!(display("1 km / 250 m"), "4");
+    assert!(evaluate_expression("5 km + 2 kg", None).is_err());
+    assert!(evaluate_expression("12 in km", None).is_err());
+
+    assert!(uses_currency("20 usd + 15 eur"));
+    assert!(!uses_currency("5 km + 300 m in mi"));
+    assert!(!uses_currency("sqrt(0xff) in hex"));
+
+    let rates = RateTable {
+        base: "EUR".to_string(),
+        date: "2026-10-15".to_string(),
+        rates: HashMap::from([("USD".to_string(), 1.25)]),
+        fetched_at: 0,
+    };
+    let total = evaluate_expression("20 usd + 10 eur", Some(&rates)).unwrap();
+    assert_eq!(total.display, "32.50 USD");
+    assert_eq!(total.result, "32.50");
+    let total = evaluate_expression("(20 usd + 10 eur) * 2 in eur", Some(&rates)).unwrap();
+    assert_eq!(total.display, "52.00 EUR");
+    assert!(evaluate_expression("20 usd + 10 eur", None).is_err());
+}
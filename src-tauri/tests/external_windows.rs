@@ -0,0 +1,11 @@
+use bunchatools_lib::testing::opacity_byte;
+
+#[test]
+fn opacity_is_a_byte_and_never_invisible() {
+    assert_eq!(opacity_byte(1.0).unwrap(), 255);
+    assert_eq!(opacity_byte(0.5).unwrap(), 128);
+    assert_eq!(opacity_byte(0.1).unwrap(), 26);
+    assert!(opacity_byte(0.05).is_err());
+    assert!(opacity_byte(1.5).is_err());
+    assert!(opacity_byte(f32::NAN).is_err());
+}
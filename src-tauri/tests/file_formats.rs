@@ -0,0 +1,25 @@
+use bunchatools_lib::testing::{parse_download_list, parse_palette};
+
+#[test]
+fn download_list_skips_comments_and_blanks() {
+    let list = "# weekend queue\nhttps://youtu.be/abc\n\n  https://youtu.be/def  \n#https://youtu.be/skipped\n";
+    assert_eq!(
+        parse_download_list(list),
+        vec!["https://youtu.be/abc", "https://youtu.be/def"]
+    );
+}
+
+#[test]
+fn gimp_palette() {
+    let palette = "GIMP Palette\nName: Brand\nColumns: 4\n#\n255   0   0\tRed\n  0 128 255\tSky\n 12  34  56\n";
+    assert_eq!(
+        parse_palette(palette),
+        vec!["#FF0000", "#0080FF", "#0C2238"]
+    );
+}
+
+#[test]
+fn hex_palette() {
+    let palette = "#1f2937\n#22C55E accent\nnot a color\n#12345\n";
+    assert_eq!(parse_palette(palette), vec!["#1F2937", "#22C55E"]);
+}
@@ -0,0 +1,127 @@
+use std::path::Path;
+
+use bunchatools_lib::testing::{
+    join_document, protect_markup, restore_markup, split_chunks, split_document, translated_path,
+    FileFormat, Piece,
+};
+
+fn to_translate(pieces: &[Piece]) -> Vec<String> {
+    pieces
+        .iter()
+        .filter_map(|p| match p {
+            Piece::Translate(text) => Some(text.clone()),
+            Piece::Keep(_) => None,
+        })
+        .collect()
+}
+
+fn round_trips(content: &str, format: FileFormat) -> Vec<String> {
+    let pieces = split_document(content, format);
+    let texts = to_translate(&pieces);
+    assert_eq!(join_document(&pieces, &texts), content);
+    texts
+}
+
+#[test]
+fn subtitles_keep_numbers_and_timings() {
+    let srt = "1\r\n00:00:01,000 --> 00:00:03,500\r\n<i>Where are you going?</i>\r\n\r\n\
+               2\r\n00:00:04,000 --> 00:00:06,000\r\nHome.\r\nIt's late.\r\n\r\n\
+               3\r\n00:00:07,000 --> 00:00:08,000\r\n♪ ♪\r\n";
+    let texts = round_trips(srt, FileFormat::Srt);
+    assert_eq!(
+        texts,
+        ["<i>Where are you going?</i>", "Home.\r\nIt's late."]
+    );
+
+    let vtt = "WEBVTT\nKind: captions\n\nNOTE made by hand\n\nintro\n00:01.000 --> 00:04.000 align:start\n<v Anna>Hello there\n";
+    assert_eq!(round_trips(vtt, FileFormat::Vtt), ["<v Anna>Hello there"]);
+
+    let pieces = split_document(srt, FileFormat::Srt);
+    let translated = join_document(
+        &pieces,
+        &[
+            "<i>Wohin gehst du?</i>".to_string(),
+            "Nach Hause.\r\nEs ist spät.".to_string(),
+        ],
+    );
+    assert!(
+        translated.starts_with("1\r\n00:00:01,000 --> 00:00:03,500\r\n<i>Wohin gehst du?</i>\r\n")
+    );
+    assert!(translated
+        .contains("\r\n2\r\n00:00:04,000 --> 00:00:06,000\r\nNach Hause.\r\nEs ist spät.\r\n"));
+}
+
+#[test]
+fn markdown_keeps_code_and_structure() {
+    let md = "---\ntitle: Notes\n---\n# Getting started\n\nInstall the app and\nopen it.\n\n\
+              ```sh\nnpm install\n```\n\n- [x] Read the *guide*\n1. Click [here](https://example.com)\n\n\
+              | Key | Action |\n|-----|--------|\n| Esc | Close it |\n";
+    let texts = round_trips(md, FileFormat::Markdown);
+    assert_eq!(
+        texts,
+        [
+            "Getting started",
+            "Install the app and\nopen it.",
+            "Read the *guide*",
+            "Click [here](https://example.com)",
+            "Key",
+            "Action",
+            "Esc",
+            "Close it",
+        ]
+    );
+
+    let text = round_trips(
+        "First paragraph\nstill first.\n\n  Second.  \n",
+        FileFormat::Text,
+    );
+    assert_eq!(text, ["First paragraph\nstill first.", "Second."]);
+}
+
+#[test]
+fn markup_survives_as_placeholders() {
+    let pattern = regex::Regex::new(r"`[^`\n]+`|\]\([^)\n]*\)").unwrap();
+    let (protected, markup) = protect_markup("Run `cargo test` or see [docs](a.md).", &pattern);
+    assert_eq!(protected, "Run [M0] or see [docs[M1].");
+    assert_eq!(
+        restore_markup("Führe [ M0 ] aus oder siehe [Doku[M1].", &markup),
+        "Führe `cargo test` aus oder siehe [Doku](a.md)."
+    );
+}
+
+#[test]
+fn chunks_break_at_sentences_and_fit() {
+    let text = "First sentence here. Second one is longer than that! Third?\nNext line.";
+    let chunks = split_chunks(text, 30);
+    assert_eq!(chunks.concat(), text);
+    assert!(chunks.iter().all(|c| c.len() <= 30));
+    assert_eq!(chunks[0], "First sentence here.");
+
+    let cjk = "これはテストです。次の文です。";
+    let chunks = split_chunks(cjk, 20);
+    assert_eq!(chunks.concat(), cjk);
+    assert!(chunks.iter().all(|c| c.len() <= 20 && !c.is_empty()));
+
+    assert_eq!(split_chunks("short", 450), ["short"]);
+}
+
+#[test]
+fn output_is_named_after_the_target_language() {
+    let dir = std::env::temp_dir().join(format!("bt-file-translation-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let movie = dir.join("movie.en.srt");
+    assert_eq!(
+        translated_path(&movie, "en", "de"),
+        dir.join("movie.de.srt")
+    );
+    std::fs::write(dir.join("notes.de.md"), "").unwrap();
+    assert_eq!(
+        translated_path(&dir.join("notes.md"), "en", "de"),
+        dir.join("notes.de (2).md")
+    );
+    assert_eq!(
+        translated_path(Path::new("/tmp/en.txt"), "en", "fr"),
+        Path::new("/tmp/en.fr.txt")
+    );
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,76 @@
+use bunchatools_lib::testing::{
+    glossary_entries_for, protect_glossary_terms, restore_glossary_terms, GlossaryEntry,
+};
+
+fn entry(id: u64, source_lang: &str, term: &str, translation: &str) -> GlossaryEntry {
+    GlossaryEntry {
+        id,
+        source_lang: source_lang.to_string(),
+        target_lang: "de".to_string(),
+        term: term.to_string(),
+        translation: translation.to_string(),
+    }
+}
+
+#[test]
+fn terms_become_placeholders_and_come_back_translated() {
+    let entries = [
+        entry(1, "", "BunchaTools", "BunchaTools"),
+        entry(2, "en", "pull request", "Pull Request"),
+        entry(3, "en", "request", "Anfrage"),
+    ];
+    let applicable = glossary_entries_for(&entries, "en", "de");
+    let protected = protect_glossary_terms(
+        "Open a Pull Request for bunchatools; requests and C++ stay.",
+        &applicable,
+    );
+    // Whole words only, any case, and the longer term wins
+    assert_eq!(
+        protected.text,
+        "Open a [G0] for [G1]; requests and C++ stay."
+    );
+    assert_eq!(protected.replacements, vec!["Pull Request", "BunchaTools"]);
+
+    // Engines sometimes space out the placeholders
+    let translated = "Öffne einen [ G0 ] für [G1]; Anfragen und C++ bleiben.";
+    assert_eq!(
+        restore_glossary_terms(translated, &protected.replacements),
+        "Öffne einen Pull Request für BunchaTools; Anfragen und C++ bleiben."
+    );
+}
+
+#[test]
+fn language_specific_entries_win_over_any_language_ones() {
+    let entries = [
+        entry(1, "", "cloud", "Cloud"),
+        entry(2, "fr", "cloud", "Wolke"),
+        entry(3, "en", "Sprint", "Sprint"),
+        GlossaryEntry {
+            target_lang: "ja".to_string(),
+            ..entry(4, "", "cloud", "クラウド")
+        },
+    ];
+    let from_french: Vec<u64> = glossary_entries_for(&entries, "fr", "de")
+        .iter()
+        .map(|e| e.id)
+        .collect();
+    assert_eq!(from_french, vec![2]);
+    let from_english: Vec<u64> = glossary_entries_for(&entries, "en", "de")
+        .iter()
+        .map(|e| e.id)
+        .collect();
+    assert_eq!(from_english, vec![3, 1]);
+}
+
+#[test]
+fn text_without_terms_is_left_alone() {
+    let entries = [entry(1, "", "C++", "C++")];
+    let applicable = glossary_entries_for(&entries, "en", "de");
+    let protected = protect_glossary_terms("No glossary terms here", &applicable);
+    assert_eq!(protected.text, "No glossary terms here");
+    assert!(protected.replacements.is_empty());
+    assert_eq!(restore_glossary_terms("[G0] stays", &[]), "[G0] stays");
+
+    let protected = protect_glossary_terms("Use C++, not C", &applicable);
+    assert_eq!(protected.text, "Use [G0], not C");
+}
@@ -0,0 +1,80 @@
+use bunchatools_lib::testing::{
+    find_habit, habit_id, habit_streaks, parse_reminder, Habit, HabitStore,
+};
+use chrono::NaiveDate;
+
+fn date(d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2024, 3, d).unwrap()
+}
+
+fn store(names: &[&str]) -> HabitStore {
+    HabitStore {
+        habits: names
+            .iter()
+            .map(|name| Habit {
+                id: habit_id(name),
+                name: name.to_string(),
+                reminder: None,
+            })
+            .collect(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn ids_are_slugs_of_the_name() {
+    assert_eq!(habit_id("Drink water"), "drink-water");
+    assert_eq!(habit_id("  Read 20 pages!! "), "read-20-pages");
+    assert_eq!(habit_id("?!"), "");
+}
+
+#[test]
+fn habits_are_found_by_name_or_a_unique_word() {
+    let store = store(&["Drink water", "Water plants", "Stretch"]);
+    assert_eq!(find_habit(&store, "stretch").unwrap().id, "stretch");
+    assert_eq!(find_habit(&store, "Drink Water").unwrap().id, "drink-water");
+    assert_eq!(find_habit(&store, "plants").unwrap().id, "water-plants");
+    assert!(find_habit(&store, "water")
+        .unwrap_err()
+        .contains("Drink water, Water plants"));
+    assert!(find_habit(&store, "run").is_err());
+}
+
+#[test]
+fn reminder_times_are_normalized() {
+    assert_eq!(parse_reminder("9:05").unwrap(), "09:05");
+    assert_eq!(parse_reminder("21:30").unwrap(), "21:30");
+    assert_eq!(parse_reminder("7pm").unwrap(), "19:00");
+    assert_eq!(parse_reminder("12 am").unwrap(), "00:00");
+    assert_eq!(parse_reminder("12:15pm").unwrap(), "12:15");
+    assert!(parse_reminder("25:00").is_err());
+    assert!(parse_reminder("13pm").is_err());
+    assert!(parse_reminder("noon").is_err());
+}
+
+#[test]
+fn streaks_survive_until_the_day_is_over() {
+    let mut store = store(&["Water", "Stretch"]);
+    store
+        .check_ins
+        .insert("water".to_string(), [1, 2, 3, 5, 6, 7].map(date).into());
+    store
+        .check_ins
+        .insert("stretch".to_string(), [1, 2].map(date).into());
+
+    // Not checked off on the 8th yet: the streak through the 7th still counts
+    let streaks = habit_streaks(&store, date(8));
+    assert!(!streaks[0].done_today);
+    assert_eq!(
+        (streaks[0].current, streaks[0].longest, streaks[0].total),
+        (3, 3, 6)
+    );
+    assert_eq!((streaks[1].current, streaks[1].longest), (0, 2));
+
+    let streaks = habit_streaks(&store, date(7));
+    assert!(streaks[0].done_today);
+    assert_eq!(streaks[0].current, 3);
+
+    // A missed day ends it
+    assert_eq!(habit_streaks(&store, date(9))[0].current, 0);
+}
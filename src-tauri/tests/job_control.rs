@@ -0,0 +1,94 @@
+use bunchatools_lib::testing::{
+    finish_job, register_job, CancellationToken, JobKind, JobLimits, JobManager, JobPower,
+};
+
+// The state letter in /proc/<pid>/stat: "T" while stopped
+#[cfg(target_os = "linux")]
+fn process_state(pid: u32) -> String {
+    let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid)).unwrap();
+    let after_name = &stat[stat.rfind(')').unwrap() + 2..];
+    after_name[..1].to_string()
+}
+
+#[test]
+fn pausing_needs_a_running_child() {
+    let token = CancellationToken::default();
+    assert!(token.set_paused(true).is_err());
+    assert!(!token.is_paused());
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn paused_children_are_stopped_until_resumed_and_can_still_be_cancelled() {
+    use std::process::Command;
+    use std::time::Duration;
+
+    let mut child = Command::new("sleep").arg("30").spawn().unwrap();
+    let token = CancellationToken::default();
+    token.attach_child(child.id());
+
+    token.set_paused(true).unwrap();
+    assert!(token.is_paused());
+    std::thread::sleep(Duration::from_millis(100));
+    assert_eq!(process_state(child.id()), "T");
+
+    token.set_paused(false).unwrap();
+    assert!(!token.is_paused());
+    std::thread::sleep(Duration::from_millis(100));
+    assert_ne!(process_state(child.id()), "T");
+
+    token.set_paused(true).unwrap();
+    token.cancel();
+    assert!(!child.wait().unwrap().success());
+    assert!(token.set_paused(false).is_err());
+}
+
+#[test]
+fn jobs_start_in_order_within_each_category_limit() {
+    let jobs = JobManager::default();
+    let limits = JobLimits {
+        conversions: 1,
+        downloads: 2,
+        scans: 1,
+    };
+    let encode = register_job(&jobs, JobKind::Conversion);
+    let next_encode = register_job(&jobs, JobKind::Conversion);
+    let downloads: Vec<u64> = (0..3)
+        .map(|_| register_job(&jobs, JobKind::GitDownload))
+        .collect();
+    let transcription = register_job(&jobs, JobKind::Transcription);
+    let start = |id, force| jobs.try_start(id, &limits, JobPower::Normal, force);
+
+    assert_eq!(start(encode, false), None);
+    assert_eq!(start(next_encode, false), Some(1));
+    assert_eq!(start(downloads[0], false), None);
+    assert_eq!(start(downloads[1], false), None);
+    assert_eq!(start(downloads[2], false), Some(1));
+    // Transcriptions share the conversion limit and queue behind the waiting encode
+    assert_eq!(start(transcription, false), Some(2));
+
+    let seen = jobs.generation();
+    finish_job(&jobs, encode);
+    assert_ne!(jobs.generation(), seen);
+    assert_eq!(start(transcription, false), Some(2));
+    assert_eq!(start(next_encode, false), None);
+    assert_eq!(start(transcription, false), Some(1));
+
+    // Cancelled while waiting: starts anyway, to end right away
+    assert_eq!(start(transcription, true), None);
+
+    let running: Vec<Option<usize>> = jobs.list().iter().map(|j| j.queue_position).collect();
+    assert_eq!(running.iter().filter(|p| p.is_some()).count(), 1);
+}
+
+#[test]
+fn job_limits_stay_between_one_and_eight() {
+    let limits = JobLimits {
+        conversions: 0,
+        downloads: 20,
+        scans: 1,
+    };
+    assert_eq!(limits.limit(JobKind::Conversion.category()), 1);
+    assert_eq!(limits.limit(JobKind::YoutubeDownload.category()), 8);
+    assert_eq!(limits.limit(JobKind::NetworkScan.category()), 1);
+}
@@ -0,0 +1,46 @@
+use bunchatools_lib::testing::detect_language;
+
+#[test]
+fn clear_text_is_reliable_with_runner_ups() {
+    let detection = detect_language(
+        "Der schnelle braune Fuchs springt über den faulen Hund und läuft dann weiter in den Wald.",
+    )
+    .unwrap();
+    assert_eq!(detection.code, "de");
+    assert_eq!(detection.language, "German");
+    assert!(detection.reliable);
+    assert!(detection.confidence > 0.5 && detection.confidence <= 1.0);
+    assert!(!detection.alternatives.is_empty() && detection.alternatives.len() <= 3);
+    let mut codes: Vec<&str> = detection
+        .alternatives
+        .iter()
+        .map(|a| a.code.as_str())
+        .collect();
+    codes.push("de");
+    codes.sort();
+    codes.dedup();
+    assert_eq!(codes.len(), detection.alternatives.len() + 1);
+}
+
+#[test]
+fn short_text_is_flagged_unreliable() {
+    let detection = detect_language("ok").unwrap();
+    assert!(!detection.reliable);
+}
+
+#[test]
+fn languages_the_engines_lack_keep_their_own_code() {
+    // Estonian has no two-letter code in the translation list; it must not turn into English
+    let detection = detect_language(
+        "Tere hommikust! Ma lähen täna poodi ja ostan leiba, piima ning natuke juustu õhtusöögiks.",
+    )
+    .unwrap();
+    assert_ne!(detection.code, "en");
+    assert_ne!(detection.language, "Unknown");
+}
+
+#[test]
+fn nothing_to_go_on() {
+    assert!(detect_language("").is_none());
+    assert!(detect_language("12345 !!! ...").is_none());
+}
@@ -0,0 +1,180 @@
+use std::path::Path;
+
+use bunchatools_lib::testing::{
+    default_shortcut_name, launch_args, launched_hidden, parse_launch_action, parse_launch_args,
+    shortcut_action, shortcut_file_stem, Action, OpenFilesRequest,
+};
+
+fn parse(args: &[&str], cwd: &str) -> Option<OpenFilesRequest> {
+    let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+    parse_launch_args(&args, Path::new(cwd))
+}
+
+#[test]
+fn plain_launch() {
+    assert_eq!(parse(&["bunchatools"], "/home/me"), None);
+}
+
+#[test]
+fn hidden_launch() {
+    let args = |a: &[&str]| a.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+    assert!(launched_hidden(&args(&["bunchatools", "--hidden"])));
+    assert!(!launched_hidden(&args(&["bunchatools"])));
+    // The flag is not a file to open
+    assert_eq!(parse(&["bunchatools", "--hidden"], "/home/me"), None);
+}
+
+#[test]
+fn tool_without_files() {
+    assert_eq!(
+        parse(
+            &["bunchatools", "--open-tool", "video-converter"],
+            "/home/me"
+        ),
+        Some(OpenFilesRequest {
+            tool: "video-converter".to_string(),
+            paths: vec![],
+        })
+    );
+}
+
+#[test]
+fn missing_tool_name() {
+    assert_eq!(parse(&["bunchatools", "--open-tool"], "/home/me"), None);
+}
+
+#[cfg(unix)]
+#[test]
+fn absolute_and_relative_files() {
+    assert_eq!(
+        parse(
+            &[
+                "/usr/bin/bunchatools",
+                "--open-tool",
+                "video-converter",
+                "/videos/a.mp4",
+                "b.mkv",
+            ],
+            "/home/me",
+        ),
+        Some(OpenFilesRequest {
+            tool: "video-converter".to_string(),
+            paths: vec!["/videos/a.mp4".to_string(), "/home/me/b.mkv".to_string()],
+        })
+    );
+}
+
+#[cfg(windows)]
+#[test]
+fn send_to_paths() {
+    // Send To appends the selected files after the shortcut's own arguments
+    assert_eq!(
+        parse(
+            &[
+                "C:\\Program Files\\BunchaTools\\bunchatools.exe",
+                "--open-tool",
+                "video-converter",
+                "C:\\Videos\\a.mp4",
+                "D:\\b.mkv",
+            ],
+            "C:\\Users\\me",
+        ),
+        Some(OpenFilesRequest {
+            tool: "video-converter".to_string(),
+            paths: vec!["C:\\Videos\\a.mp4".to_string(), "D:\\b.mkv".to_string()],
+        })
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn associated_files_pick_the_tool() {
+    assert_eq!(
+        parse(
+            &["bunchatools", "notes.txt", "brand.gpl", "old.GPL"],
+            "/home/me"
+        ),
+        Some(OpenFilesRequest {
+            tool: "color-picker".to_string(),
+            paths: vec![
+                "/home/me/brand.gpl".to_string(),
+                "/home/me/old.GPL".to_string()
+            ],
+        })
+    );
+}
+
+#[test]
+fn unassociated_files_are_ignored() {
+    assert_eq!(parse(&["bunchatools", "notes.txt"], "/home/me"), None);
+}
+
+#[test]
+fn jump_list_actions() {
+    let args = |a: &[&str]| a.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+    assert!(matches!(
+        parse_launch_action(&args(&["bunchatools", "--action", "pick_color"])),
+        Some(Action::PickColor)
+    ));
+    // Actions that need parameters can't be launched without them
+    assert!(parse_launch_action(&args(&["bunchatools", "--action", "kill_port"])).is_none());
+    assert!(parse_launch_action(&args(&["bunchatools", "--action"])).is_none());
+    assert!(parse_launch_action(&args(&["bunchatools"])).is_none());
+}
+
+#[test]
+fn shortcut_actions_round_trip_through_launch_args() {
+    let launch = |action: &Action| {
+        let mut args = vec!["bunchatools".to_string()];
+        args.extend(launch_args(action));
+        args
+    };
+
+    let port = shortcut_action("kill_port", [("port".to_string(), 3000.into())].into()).unwrap();
+    assert_eq!(launch(&port)[1..], ["--action", "kill_port", "port=3000"]);
+    assert!(matches!(
+        parse_launch_action(&launch(&port)),
+        Some(Action::KillPort { port: 3000 })
+    ));
+
+    // Text that would read back as a number stays text
+    let timer = Action::StartTimer {
+        seconds: 300,
+        label: Some("300".to_string()),
+    };
+    assert!(matches!(
+        parse_launch_action(&launch(&timer)),
+        Some(Action::StartTimer { seconds: 300, label: Some(label) }) if label == "300"
+    ));
+
+    let tool = shortcut_action(
+        "open_tool",
+        [("tool".to_string(), "color-picker".into())].into(),
+    )
+    .unwrap();
+    assert_eq!(
+        parse(
+            &launch(&tool).iter().map(String::as_str).collect::<Vec<_>>(),
+            "/"
+        ),
+        Some(OpenFilesRequest {
+            tool: "color-picker".to_string(),
+            paths: vec![],
+        })
+    );
+    assert_eq!(default_shortcut_name(&tool), "Color Picker");
+    assert_eq!(default_shortcut_name(&port), "Kill port 3000");
+
+    assert!(shortcut_action("kill_port", Default::default()).is_err());
+    assert!(shortcut_action("format_disk", Default::default()).is_err());
+}
+
+#[test]
+fn shortcut_names_make_file_names() {
+    assert_eq!(shortcut_file_stem("Kill port 3000"), "Kill port 3000");
+    assert_eq!(
+        shortcut_file_stem("QR code: scan / make?"),
+        "QR code scan  make"
+    );
+    assert_eq!(shortcut_file_stem("..."), "BunchaTools");
+}
@@ -0,0 +1,113 @@
+use bunchatools_lib::testing::{preview_locale, LocalePreview};
+
+fn preview(locale: &str, samples: &[&str]) -> LocalePreview {
+    let samples: Vec<String> = samples.iter().map(|s| s.to_string()).collect();
+    preview_locale(locale, &samples).unwrap()
+}
+
+fn formatted(locale: &str, sample: &str) -> (String, String) {
+    let result = preview(locale, &[sample]);
+    let number = &result.numbers[0];
+    (number.formatted.clone(), number.currency.clone())
+}
+
+#[test]
+fn separators_and_grouping() {
+    assert_eq!(
+        formatted("en-US", "1234567.891"),
+        ("1,234,567.891".to_string(), "$1,234,567.89".to_string())
+    );
+    assert_eq!(
+        formatted("de-DE", "1234.5"),
+        ("1.234,5".to_string(), "1.234,50\u{a0}€".to_string())
+    );
+    assert_eq!(formatted("fr", "1234.5").0, "1\u{202f}234,5");
+    assert_eq!(formatted("en-IN", "12345678").0, "1,23,45,678");
+    // Spanish and Polish leave four-digit numbers ungrouped
+    assert_eq!(formatted("es-ES", "1234").0, "1234");
+    assert_eq!(formatted("es-ES", "12345").0, "12.345");
+    assert_eq!(
+        formatted("pt-BR", "-0.755"),
+        ("-0,755".to_string(), "-R$\u{a0}0,76".to_string())
+    );
+    assert_eq!(formatted("ja-JP", "1234.5").1, "￥1,235");
+    assert_eq!(formatted("en-US", "999.995").1, "$1,000.00");
+}
+
+#[test]
+fn plural_categories() {
+    let categories = |locale: &str, samples: &[&str]| -> Vec<String> {
+        preview(locale, samples)
+            .numbers
+            .into_iter()
+            .map(|n| n.cardinal)
+            .collect()
+    };
+    assert_eq!(
+        categories("en", &["1", "1.0", "2"]),
+        ["one", "other", "other"]
+    );
+    assert_eq!(
+        categories("fr", &["0", "1.5", "2", "1000000"]),
+        ["one", "one", "other", "many"]
+    );
+    assert_eq!(
+        categories("ru", &["1", "3", "5", "11", "21", "1.5"]),
+        ["one", "few", "many", "many", "one", "other"]
+    );
+    assert_eq!(
+        categories("pl", &["1", "22", "25", "12"]),
+        ["one", "few", "many", "many"]
+    );
+    assert_eq!(
+        categories("cs", &["1", "3", "5", "0.5"]),
+        ["one", "few", "other", "many"]
+    );
+    assert_eq!(categories("ja", &["1"]), ["other"]);
+
+    let ordinals: Vec<Option<String>> = preview("en", &["1", "2", "3", "11", "22", "1.5"])
+        .numbers
+        .into_iter()
+        .map(|n| n.ordinal)
+        .collect();
+    assert_eq!(
+        ordinals,
+        [
+            Some("one".to_string()),
+            Some("two".to_string()),
+            Some("few".to_string()),
+            Some("other".to_string()),
+            Some("two".to_string()),
+            None
+        ]
+    );
+}
+
+#[test]
+fn dates() {
+    let date = |locale: &str| preview(locale, &["2026-03-09T14:05"]).dates.remove(0);
+    let us = date("en-US");
+    assert_eq!(us.short, "3/9/26");
+    assert_eq!(us.long, "March 9, 2026");
+    assert_eq!(us.time, "2:05\u{202f}PM");
+    let de = date("de-DE");
+    assert_eq!(de.short, "09.03.26");
+    assert_eq!(de.long, "9. März 2026");
+    assert_eq!(de.time, "14:05");
+    assert_eq!(date("es").long, "9 de marzo de 2026");
+    assert_eq!(date("ru").long, "9 марта 2026 г.");
+    assert_eq!(date("ja").long, "2026年3月9日");
+    assert_eq!(date("ko").time, "오후 2:05");
+}
+
+#[test]
+fn resolves_locales_and_sorts_samples() {
+    let result = preview("de_AT", &["12", "2026-02-30", "abc", "2024-02-29"]);
+    assert_eq!(result.locale, "de-DE");
+    assert_eq!(result.numbers.len(), 1);
+    assert_eq!(result.dates.len(), 1);
+    assert_eq!(result.invalid, ["2026-02-30", "abc"]);
+
+    assert!(!preview("en", &[]).numbers.is_empty());
+    assert!(preview_locale("xx-YY", &[]).is_err());
+}
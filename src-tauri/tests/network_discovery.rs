@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+
+use bunchatools_lib::testing::{
+    merge_sightings, oui_prefix, parse_oui_line, parse_ssdp_response, service_label,
+    split_workstation_name, vendor_of, vendors_for, Sighting,
+};
+
+fn sighting(ip: &str, name: Option<&str>, mac: Option<&str>, service: Option<&str>) -> Sighting {
+    Sighting {
+        ip: ip.to_string(),
+        name: name.map(String::from),
+        mac: mac.map(String::from),
+        service: service.map(String::from),
+    }
+}
+
+#[test]
+fn mdns_names_and_services() {
+    assert_eq!(service_label("_googlecast._tcp.local"), "googlecast");
+    assert_eq!(
+        split_workstation_name("nas [00:11:32:aa:bb:cc]"),
+        ("nas".to_string(), Some("00:11:32:AA:BB:CC".to_string()))
+    );
+    // Brackets that aren't a MAC stay part of the name
+    assert_eq!(
+        split_workstation_name("Office [2nd floor]"),
+        ("Office [2nd floor]".to_string(), None)
+    );
+}
+
+#[test]
+fn ssdp_answers() {
+    let response = "HTTP/1.1 200 OK\r\nCACHE-CONTROL: max-age=1800\r\n\
+                    LOCATION: http://192.168.1.30:8060/dial/dd.xml\r\n\
+                    SERVER: Roku/12.0 UPnP/1.0\r\nST: upnp:rootdevice\r\n\r\n";
+    let headers = parse_ssdp_response(response).unwrap();
+    assert_eq!(
+        headers.get("location").map(String::as_str),
+        Some("http://192.168.1.30:8060/dial/dd.xml")
+    );
+    assert_eq!(
+        headers.get("server").map(String::as_str),
+        Some("Roku/12.0 UPnP/1.0")
+    );
+    // Other clients' searches aren't answers
+    assert!(parse_ssdp_response("M-SEARCH * HTTP/1.1\r\nST: ssdp:all\r\n\r\n").is_none());
+}
+
+#[test]
+fn sightings_merge_per_address() {
+    let devices = merge_sightings(vec![
+        sighting(
+            "192.168.1.30",
+            Some("Living Room"),
+            None,
+            Some("googlecast"),
+        ),
+        sighting(
+            "192.168.1.4",
+            Some("nas"),
+            Some("00:11:32:aa:bb:cc"),
+            Some("smb"),
+        ),
+        sighting("192.168.1.30", Some("Roku Ultra"), None, Some("upnp")),
+        sighting("192.168.1.30", None, Some("b0:a7:37:01:02:03"), None),
+        sighting("192.168.1.4", None, None, Some("smb")),
+        sighting("192.168.1.1", None, Some("a0:63:91:2b:4c:01"), None),
+    ]);
+    let ips: Vec<&str> = devices.iter().map(|d| d.ip.as_str()).collect();
+    // Numeric order, not text order
+    assert_eq!(ips, ["192.168.1.1", "192.168.1.4", "192.168.1.30"]);
+
+    let tv = &devices[2];
+    assert_eq!(tv.name.as_deref(), Some("Living Room"));
+    assert_eq!(tv.mac.as_deref(), Some("B0:A7:37:01:02:03"));
+    assert_eq!(tv.services, ["googlecast", "upnp"]);
+    assert_eq!(devices[1].services, ["smb"]);
+    assert_eq!(devices[0].name, None);
+}
+
+#[test]
+fn mac_vendors() {
+    assert_eq!(
+        parse_oui_line("00-11-32   (hex)\t\tSynology Incorporated"),
+        Some(("001132".to_string(), "Synology Incorporated".to_string()))
+    );
+    assert_eq!(
+        parse_oui_line("001132     (base 16)\t\tSynology Incorporated"),
+        Some(("001132".to_string(), "Synology Incorporated".to_string()))
+    );
+    assert_eq!(
+        parse_oui_line("B0A737 Roku"),
+        Some(("B0A737".to_string(), "Roku".to_string()))
+    );
+    assert_eq!(
+        parse_oui_line("B0:A7:37\tRoku\tRoku, Inc."),
+        Some(("B0A737".to_string(), "Roku, Inc.".to_string()))
+    );
+    assert_eq!(parse_oui_line("# comment"), None);
+    assert_eq!(parse_oui_line("  Synology Incorporated"), None);
+    assert_eq!(oui_prefix("b8-27-eb-11-22-33").as_deref(), Some("B827EB"));
+
+    let macs = [
+        "B0:A7:37:01:02:03",
+        "B8:27:EB:11:22:33",
+        "3C:7C:3F:12:AB:CD",
+    ];
+    let list = "B0:A7:37\tRoku\tRoku, Inc.\n00:00:0C\tCisco\tCisco Systems, Inc\n".to_string();
+    let vendors = vendors_for(&macs, &[list]);
+    // Only the prefixes asked for, from the list or built in
+    assert_eq!(vendors.len(), 2);
+    assert_eq!(vendor_of(macs[0], &vendors).as_deref(), Some("Roku, Inc."));
+    assert_eq!(
+        vendor_of(macs[1], &vendors).as_deref(),
+        Some("Raspberry Pi Foundation")
+    );
+    assert_eq!(vendor_of(macs[2], &vendors), None);
+    // Randomized addresses have the locally administered bit set
+    assert_eq!(
+        vendor_of("DA:A1:19:00:00:01", &HashMap::new()).as_deref(),
+        Some("Private address")
+    );
+}
@@ -0,0 +1,109 @@
+use bunchatools_lib::testing::{is_vpn_interface, NetworkInfo, NetworkInterface, NetworkStatus};
+
+fn interface(name: &str, description: &str, is_tunnel: bool) -> NetworkInterface {
+    NetworkInterface {
+        name: name.to_string(),
+        description: description.to_string(),
+        mac_address: None,
+        addresses: vec!["10.0.0.2".to_string()],
+        is_up: true,
+        is_loopback: false,
+        is_tunnel,
+    }
+}
+
+#[test]
+fn vpn_adapters() {
+    assert!(is_vpn_interface(&interface("wg0", "wireguard", true)));
+    assert!(is_vpn_interface(&interface("tailscale0", "tun", false)));
+    assert!(is_vpn_interface(&interface(
+        "Ethernet 3",
+        "TAP-Windows Adapter V9 for OpenVPN Connect",
+        false
+    )));
+    assert!(!is_vpn_interface(&interface(
+        "Wi-Fi",
+        "Intel(R) Wi-Fi 6 AX201 160MHz",
+        false
+    )));
+    assert!(!is_vpn_interface(&interface("docker0", "bridge", false)));
+}
+
+#[test]
+fn offline_without_default_route() {
+    let info = NetworkInfo {
+        interfaces: vec![interface("eth0", "ether", false)],
+        ..Default::default()
+    };
+    assert!(!NetworkStatus::from_info(info.clone()).online);
+
+    let info = NetworkInfo {
+        gateway: Some("10.0.0.1".to_string()),
+        ..info
+    };
+    assert!(NetworkStatus::from_info(info).online);
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use bunchatools_lib::testing::{
+        fixture, parse_ip_addr_json, parse_proc_net_arp, parse_proc_net_route, parse_resolv_conf,
+    };
+
+    #[test]
+    fn ip_addr_interfaces() {
+        let interfaces = parse_ip_addr_json(&fixture("ip_addr.json")).unwrap();
+        let names: Vec<&str> = interfaces.iter().map(|i| i.name.as_str()).collect();
+        assert_eq!(names, ["lo", "enp3s0", "wlp2s0", "wg0", "docker0"]);
+
+        let ethernet = &interfaces[1];
+        assert!(ethernet.is_up && !ethernet.is_tunnel);
+        assert_eq!(ethernet.mac_address.as_deref(), Some("3C:7C:3F:12:AB:CD"));
+        assert_eq!(
+            ethernet.addresses,
+            ["192.168.1.23", "fe80::3e7c:3fff:fe12:abcd"]
+        );
+
+        assert!(interfaces[0].is_loopback && interfaces[0].mac_address.is_none());
+        // Administratively up but without a carrier
+        assert!(!interfaces[2].is_up);
+        // WireGuard links have an UNKNOWN operstate and no MAC address
+        let wireguard = &interfaces[3];
+        assert!(wireguard.is_up && wireguard.is_tunnel);
+        assert_eq!(wireguard.description, "wireguard");
+        assert_eq!(wireguard.mac_address, None);
+    }
+
+    #[test]
+    fn default_route_with_lowest_metric() {
+        assert_eq!(
+            parse_proc_net_route(&fixture("proc_net_route.txt")),
+            Some(("enp3s0".to_string(), "192.168.1.1".to_string()))
+        );
+        assert_eq!(parse_proc_net_route("Iface\tDestination\tGateway\n"), None);
+    }
+
+    #[test]
+    fn resolv_conf_nameservers() {
+        let conf = "# Generated by NetworkManager\nsearch lan\nnameserver 192.168.1.1\n\
+                    nameserver 2001:db8::53\noptions edns0\n#nameserver 8.8.8.8\n";
+        assert_eq!(parse_resolv_conf(conf), ["192.168.1.1", "2001:db8::53"]);
+    }
+
+    #[test]
+    fn arp_cache_neighbors() {
+        let neighbors = parse_proc_net_arp(&fixture("proc_net_arp.txt"));
+        let found: Vec<(&str, &str)> = neighbors
+            .iter()
+            .map(|n| (n.ip.as_str(), n.mac.as_str()))
+            .collect();
+        // Incomplete entries (flags 0x0) are skipped
+        assert_eq!(
+            found,
+            [
+                ("192.168.1.1", "A0:63:91:2B:4C:01"),
+                ("192.168.1.42", "B8:27:EB:11:22:33"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,78 @@
+use bunchatools_lib::testing::{night_light_schedule_at, whitepoint};
+
+fn at(text: &str) -> i64 {
+    chrono::NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M")
+        .unwrap()
+        .and_utc()
+        .timestamp()
+}
+
+// Assert the next switch is within two minutes of `expected` (UTC)
+fn assert_next_near(next: Option<i64>, expected: &str) {
+    let next = next.expect("no next switch");
+    assert!(
+        (next - at(expected)).abs() <= 120,
+        "{} vs {}",
+        next,
+        expected
+    );
+}
+
+#[test]
+fn night_runs_from_sunset_to_sunrise() {
+    // London, summer solstice: sunrise 03:43, sunset 20:21 UTC
+    let (night, next) = night_light_schedule_at(51.5074, -0.1278, at("2024-06-21 12:00"));
+    assert!(!night);
+    assert_next_near(next, "2024-06-21 20:21");
+
+    let (night, next) = night_light_schedule_at(51.5074, -0.1278, at("2024-06-21 23:00"));
+    assert!(night);
+    assert_next_near(next, "2024-06-22 03:43");
+
+    let (night, _) = night_light_schedule_at(51.5074, -0.1278, at("2024-06-21 01:00"));
+    assert!(night);
+}
+
+#[test]
+fn the_local_evening_can_be_on_another_utc_day() {
+    // Sydney: 22:00 AEST is 12:00 UTC; the next sunrise (07:00 AEST) is 21:00 UTC
+    let (night, next) = night_light_schedule_at(-33.8688, 151.2093, at("2024-06-21 12:00"));
+    assert!(night);
+    assert_next_near(next, "2024-06-21 21:00");
+
+    let (night, next) = night_light_schedule_at(-33.8688, 151.2093, at("2024-06-21 02:00"));
+    assert!(!night);
+    assert_next_near(next, "2024-06-21 06:54");
+}
+
+#[test]
+fn polar_days_and_nights_never_switch() {
+    // Tromsø
+    assert_eq!(
+        night_light_schedule_at(69.6492, 18.9553, at("2026-06-21 23:00")),
+        (false, None)
+    );
+    assert_eq!(
+        night_light_schedule_at(69.6492, 18.9553, at("2026-12-21 12:00")),
+        (true, None)
+    );
+}
+
+#[test]
+fn warmer_temperatures_dim_blue_most() {
+    let (red, green, blue) = whitepoint(6500);
+    assert!((red - 1.0).abs() < 1e-9 && (green - 1.0).abs() < 1e-9 && (blue - 1.0).abs() < 1e-9);
+
+    let (red, green, blue) = whitepoint(3400);
+    assert_eq!(red, 1.0);
+    assert!(
+        0.0 < blue && blue < green && green < 1.0,
+        "{} {}",
+        green,
+        blue
+    );
+
+    // Out of range temperatures are clamped
+    assert_eq!(whitepoint(10_000), whitepoint(6500));
+    assert_eq!(whitepoint(500), whitepoint(1000));
+}
@@ -0,0 +1,79 @@
+use bunchatools_lib::testing::number_to_words;
+
+fn words(amount: f64, lang: &str, currency: &str) -> String {
+    number_to_words(amount, lang, Some(currency)).unwrap()
+}
+
+#[test]
+fn spells_english_amounts() {
+    assert_eq!(
+        words(1234.5, "en", "EUR"),
+        "one thousand two hundred thirty-four euros and fifty cents"
+    );
+    assert_eq!(words(1.0, "en", "usd"), "one dollar");
+    assert_eq!(words(0.01, "en", "GBP"), "one penny");
+    assert_eq!(words(0.0, "en", "USD"), "zero dollars");
+    assert_eq!(words(-21.0, "en", "USD"), "minus twenty-one dollars");
+    assert_eq!(words(1_000_000.0, "en-US", "JPY"), "one million yen");
+    assert_eq!(
+        number_to_words(215.07, "en", None).as_deref(),
+        Ok("two hundred fifteen and 07/100")
+    );
+}
+
+#[test]
+fn follows_spanish_agreement() {
+    assert_eq!(
+        words(1234.5, "es", "EUR"),
+        "mil doscientos treinta y cuatro euros con cincuenta céntimos"
+    );
+    assert_eq!(words(21.0, "es", "EUR"), "veintiún euros");
+    assert_eq!(words(21.0, "es", "GBP"), "veintiuna libras");
+    assert_eq!(words(200_000.0, "es", "GBP"), "doscientas mil libras");
+    assert_eq!(words(100.0, "es", "USD"), "cien dólares");
+    assert_eq!(words(1_000_000.0, "es", "EUR"), "un millón de euros");
+    assert_eq!(
+        number_to_words(21.0, "es", None).as_deref(),
+        Ok("veintiuno")
+    );
+}
+
+#[test]
+fn follows_french_spelling() {
+    assert_eq!(
+        words(1234.5, "fr", "EUR"),
+        "mille deux cent trente-quatre euros et cinquante centimes"
+    );
+    assert_eq!(words(80.0, "fr", "EUR"), "quatre-vingts euros");
+    assert_eq!(words(80_000.0, "fr", "EUR"), "quatre-vingt mille euros");
+    assert_eq!(words(71.0, "fr", "EUR"), "soixante et onze euros");
+    assert_eq!(words(91.0, "fr", "EUR"), "quatre-vingt-onze euros");
+    assert_eq!(words(21.0, "fr", "GBP"), "vingt et une livres");
+    assert_eq!(words(200.0, "fr", "EUR"), "deux cents euros");
+    assert_eq!(words(0.0, "fr", "EUR"), "zéro euro");
+    assert_eq!(words(2_000_000.0, "fr", "EUR"), "deux millions d'euros");
+}
+
+#[test]
+fn writes_german_as_one_word() {
+    assert_eq!(
+        words(1234.5, "de", "EUR"),
+        "eintausendzweihundertvierunddreißig Euro und fünfzig Cent"
+    );
+    assert_eq!(words(1.0, "de", "EUR"), "ein Euro");
+    assert_eq!(number_to_words(1.0, "de", None).as_deref(), Ok("eins"));
+    assert_eq!(
+        words(2_001_021.0, "de", "CHF"),
+        "zwei Millionen eintausendeinundzwanzig Franken"
+    );
+}
+
+#[test]
+fn rejects_unsupported_input() {
+    assert_eq!(
+        number_to_words(1.0, "ja", None),
+        Err("Number words aren't available in Japanese".to_string())
+    );
+    assert!(number_to_words(1.0, "en", Some("XYZ")).is_err());
+    assert!(number_to_words(1e16, "en", None).is_err());
+}
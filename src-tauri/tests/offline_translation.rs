@@ -0,0 +1,52 @@
+use bunchatools_lib::testing::{parse_model_list, translation_route};
+
+const INSTALLED: &str = "\
+Czech-English type: base version: 1; To invoke do -m cs-en-base
+English-German type: tiny version: 2; To invoke do -m en-de-tiny
+English-German type: base version: 1; To invoke do -m en-de-base
+German-English type: tiny version: 2; To invoke do -m de-en-tiny
+English-French type: tiny version: 1; To invoke do -m en-fr-tiny
+";
+
+fn names(route: Option<Vec<&bunchatools_lib::testing::TranslationModel>>) -> Option<Vec<&str>> {
+    route.map(|models| models.iter().map(|m| m.name.as_str()).collect())
+}
+
+#[test]
+fn model_lists_are_parsed() {
+    let models = parse_model_list(INSTALLED);
+    assert_eq!(models.len(), 5);
+    assert_eq!(models[0].name, "cs-en-base");
+    assert_eq!(
+        (models[0].source.as_str(), models[0].target.as_str()),
+        ("cs", "en")
+    );
+    assert_eq!(models[1].kind, "tiny");
+
+    // --available-models says "To download do -d ..."
+    let available = parse_model_list(
+        "Spanish-English type: tiny version: 1; To download do -d es-en-tiny\nNo models\n",
+    );
+    assert_eq!(available.len(), 1);
+    assert_eq!(available[0].name, "es-en-tiny");
+}
+
+#[test]
+fn routes_prefer_direct_base_models_then_english() {
+    let models = parse_model_list(INSTALLED);
+    assert_eq!(
+        names(translation_route(&models, "en", "de")),
+        Some(vec!["en-de-base"])
+    );
+    assert_eq!(
+        names(translation_route(&models, "de", "en")),
+        Some(vec!["de-en-tiny"])
+    );
+    // No direct Czech-French model: through English
+    assert_eq!(
+        names(translation_route(&models, "cs", "fr")),
+        Some(vec!["cs-en-base", "en-fr-tiny"])
+    );
+    assert_eq!(names(translation_route(&models, "fr", "en")), None);
+    assert_eq!(names(translation_route(&models, "de", "ja")), None);
+}
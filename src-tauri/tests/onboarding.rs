@@ -0,0 +1,19 @@
+use bunchatools_lib::testing::unlocked_tips;
+
+#[test]
+fn tips_unlock_with_uses_until_dismissed() {
+    let ids = |uses, dismissed: &[String]| -> Vec<String> {
+        unlocked_tips("screenshot-annotate", uses, dismissed)
+            .into_iter()
+            .map(|tip| tip.id)
+            .collect()
+    };
+    assert!(ids(0, &[]).is_empty());
+    assert_eq!(ids(1, &[]), ["annotate-tool-keys"]);
+    assert_eq!(ids(5, &[]), ["annotate-tool-keys", "annotate-shortcuts"]);
+    assert_eq!(
+        ids(5, &["annotate-tool-keys".to_string()]),
+        ["annotate-shortcuts"]
+    );
+    assert!(unlocked_tips("no-such-tool", 100, &[]).is_empty());
+}
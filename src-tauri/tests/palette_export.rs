@@ -0,0 +1,63 @@
+use bunchatools_lib::testing::{parse_palette, render_palette, PaletteFormat};
+
+fn colors() -> Vec<String> {
+    vec!["#1e90ff".to_string(), "rgb(255, 0, 0)".to_string()]
+}
+
+fn text(format: PaletteFormat) -> String {
+    String::from_utf8(render_palette(&colors(), format, "Brand").unwrap()).unwrap()
+}
+
+#[test]
+fn gpl_reads_back_as_a_palette() {
+    let gpl = text(PaletteFormat::Gpl);
+    assert!(gpl.starts_with("GIMP Palette\nName: Brand\n"));
+    assert!(gpl.contains(" 30 144 255\t#1E90FF\n"));
+    assert_eq!(parse_palette(&gpl), ["#1E90FF", "#FF0000"]);
+}
+
+#[test]
+fn text_formats() {
+    assert_eq!(
+        text(PaletteFormat::Css),
+        ":root {\n  --color-1: #1E90FF;\n  --color-2: #FF0000;\n}\n"
+    );
+    assert_eq!(
+        text(PaletteFormat::Scss),
+        "$color-1: #1E90FF;\n$color-2: #FF0000;\n"
+    );
+    let tokens: serde_json::Value = serde_json::from_str(&text(PaletteFormat::Json)).unwrap();
+    assert_eq!(tokens["Brand"]["color-2"]["$type"], "color");
+    assert_eq!(tokens["Brand"]["color-2"]["$value"], "#FF0000");
+}
+
+#[test]
+fn ase_layout() {
+    let ase = render_palette(&colors(), PaletteFormat::Ase, "Brand").unwrap();
+    assert_eq!(&ase[..4], b"ASEF");
+    assert_eq!(&ase[4..8], [0, 1, 0, 0]);
+    assert_eq!(u32::from_be_bytes(ase[8..12].try_into().unwrap()), 4); // group + 2 colors + end
+
+    // Group start block named "Brand"
+    assert_eq!(&ase[12..14], [0xC0, 0x01]);
+    let group_len = u32::from_be_bytes(ase[14..18].try_into().unwrap()) as usize;
+    assert_eq!(group_len, 2 + 6 * 2);
+
+    // First swatch: "#1E90FF" in RGB floats
+    let entry = 18 + group_len;
+    assert_eq!(&ase[entry..entry + 2], [0x00, 0x01]);
+    let body = &ase[entry + 6..];
+    assert_eq!(&body[..2], [0, 8]);
+    let model = 2 + 8 * 2;
+    assert_eq!(&body[model..model + 4], b"RGB ");
+    let red = f32::from_be_bytes(body[model + 4..model + 8].try_into().unwrap());
+    assert!((red - 30.0 / 255.0).abs() < 1e-6);
+
+    assert_eq!(&ase[ase.len() - 6..], [0xC0, 0x02, 0, 0, 0, 0]);
+}
+
+#[test]
+fn rejects_empty_and_invalid_colors() {
+    assert!(render_palette(&[], PaletteFormat::Css, "Empty").is_err());
+    assert!(render_palette(&["nope".to_string()], PaletteFormat::Gpl, "Bad").is_err());
+}
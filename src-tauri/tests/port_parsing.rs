@@ -0,0 +1,83 @@
+#[allow(unused_imports)]
+use bunchatools_lib::testing::fixture;
+
+#[cfg(target_os = "linux")]
+mod ss {
+    use super::fixture;
+    use bunchatools_lib::testing::parse_ss_line;
+
+    fn find(port: u16) -> Option<(u32, String)> {
+        fixture("ss_listening.txt")
+            .lines()
+            .find_map(|line| parse_ss_line(line, port))
+            .map(|p| (p.pid, p.name))
+    }
+
+    #[test]
+    fn ipv4_listener() {
+        assert_eq!(find(3000), Some((12345, "node".to_string())));
+    }
+
+    #[test]
+    fn ipv6_listener() {
+        assert_eq!(find(8080), Some((2222, "python3".to_string())));
+    }
+
+    #[test]
+    fn interface_scoped_listener() {
+        assert_eq!(find(53), Some((678, "systemd-resolve".to_string())));
+    }
+
+    #[test]
+    fn listener_without_process_info() {
+        // ss only shows users:(...) for sockets we are allowed to inspect
+        assert_eq!(find(5432), None);
+    }
+
+    #[test]
+    fn unused_port() {
+        assert_eq!(find(9999), None);
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod netstat {
+    use super::fixture;
+    use bunchatools_lib::testing::parse_netstat_line;
+
+    fn find_all(port: u16) -> Vec<(String, u16, u32)> {
+        fixture("netstat_ano.txt")
+            .lines()
+            .filter_map(|line| parse_netstat_line(line, port))
+            .collect()
+    }
+
+    #[test]
+    fn listening_and_established() {
+        assert_eq!(
+            find_all(3000),
+            vec![
+                ("TCP".to_string(), 3000, 12345),
+                ("TCP".to_string(), 3000, 12345)
+            ]
+        );
+    }
+
+    #[test]
+    fn ipv6_listener() {
+        assert_eq!(find_all(8080), vec![("TCP".to_string(), 8080, 2222)]);
+    }
+
+    #[test]
+    fn port_prefix_does_not_match() {
+        assert_eq!(find_all(300), vec![]);
+    }
+
+    #[test]
+    fn header_lines_are_ignored() {
+        assert_eq!(
+            parse_netstat_line("  Proto  Local Address  Foreign Address  State  PID", 0),
+            None
+        );
+    }
+}
@@ -0,0 +1,149 @@
+use bunchatools_lib::testing::{
+    policy_for, register_job, BatteryPolicy, BatterySettings, JobKind, JobLimits, JobManager,
+    JobPower, PowerStatus,
+};
+
+fn on_battery(percent: Option<u8>) -> PowerStatus {
+    PowerStatus {
+        on_battery: true,
+        battery_percent: percent,
+    }
+}
+
+#[test]
+fn the_policy_applies_on_battery_at_or_below_the_threshold() {
+    let battery = BatterySettings {
+        policy: BatteryPolicy::Defer,
+        below_percent: 30,
+    };
+    let plugged_in = PowerStatus {
+        on_battery: false,
+        battery_percent: Some(10),
+    };
+    assert_eq!(policy_for(&plugged_in, &battery), JobPower::Normal);
+    assert_eq!(
+        policy_for(&on_battery(Some(31)), &battery),
+        JobPower::Normal
+    );
+    assert_eq!(
+        policy_for(&on_battery(Some(30)), &battery),
+        JobPower::Deferred
+    );
+    // A battery that can't tell its charge counts as low
+    assert_eq!(policy_for(&on_battery(None), &battery), JobPower::Deferred);
+    // Heavy jobs run as usual until a policy is chosen
+    assert_eq!(
+        policy_for(&on_battery(Some(5)), &BatterySettings::default()),
+        JobPower::Normal
+    );
+
+    let throttle = BatterySettings {
+        policy: BatteryPolicy::Throttle,
+        below_percent: 100,
+    };
+    assert_eq!(
+        policy_for(&on_battery(Some(95)), &throttle),
+        JobPower::Throttled
+    );
+    let run = BatterySettings {
+        policy: BatteryPolicy::Run,
+        ..throttle
+    };
+    assert_eq!(policy_for(&on_battery(Some(5)), &run), JobPower::Normal);
+}
+
+#[test]
+fn deferred_jobs_wait_without_holding_up_the_rest() {
+    let jobs = JobManager::default();
+    let limits = JobLimits::default();
+    let encode = register_job(&jobs, JobKind::Conversion);
+    let download = register_job(&jobs, JobKind::YoutubeDownload);
+
+    assert_eq!(
+        jobs.try_start(encode, &limits, JobPower::Deferred, false),
+        Some(1)
+    );
+    // Another heavy job told to run anyway doesn't queue behind the deferred one
+    let transcribe = register_job(&jobs, JobKind::Transcription);
+    jobs.run_on_battery(transcribe).unwrap();
+    assert!(jobs.runs_on_battery(transcribe));
+    assert_eq!(
+        jobs.try_start(transcribe, &limits, JobPower::Normal, false),
+        None
+    );
+    assert_eq!(
+        jobs.try_start(download, &limits, JobPower::Normal, false),
+        None
+    );
+
+    // Back on AC the deferred job starts
+    assert_eq!(
+        jobs.try_start(encode, &limits, JobPower::Normal, false),
+        None
+    );
+    assert!(jobs.run_on_battery(99).is_err());
+}
+
+#[test]
+fn running_heavy_jobs_follow_the_policy_unless_told_to_run_anyway() {
+    let jobs = JobManager::default();
+    let limits = JobLimits::default();
+    let encode = register_job(&jobs, JobKind::Conversion);
+    let transcribe = register_job(&jobs, JobKind::Transcription);
+    let download = register_job(&jobs, JobKind::GitDownload);
+    for id in [encode, transcribe, download] {
+        jobs.try_start(id, &limits, JobPower::Normal, false);
+    }
+    jobs.run_on_battery(transcribe).unwrap();
+
+    assert!(jobs.apply_power(JobPower::Throttled));
+    assert!(!jobs.apply_power(JobPower::Throttled));
+    assert_eq!(jobs.throttled_tokens().len(), 1);
+    let power: Vec<JobPower> = jobs.list().iter().map(|j| j.power).collect();
+    assert_eq!(
+        power,
+        vec![JobPower::Throttled, JobPower::Normal, JobPower::Normal]
+    );
+
+    assert!(jobs.apply_power(JobPower::Normal));
+    assert!(jobs.throttled_tokens().is_empty());
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn held_and_resting_children_are_stopped_but_a_user_pause_outlasts_them() {
+    use bunchatools_lib::testing::CancellationToken;
+    use std::process::Command;
+    use std::time::Duration;
+
+    let state = |pid: u32| {
+        let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid)).unwrap();
+        stat[stat.rfind(')').unwrap() + 2..][..1].to_string()
+    };
+    let settle = || std::thread::sleep(Duration::from_millis(100));
+
+    let mut child = Command::new("sleep").arg("30").spawn().unwrap();
+    let token = CancellationToken::default();
+    token.attach_child(child.id());
+
+    token.set_throttled(true);
+    assert!(!token.is_suspended());
+    token.set_resting(true);
+    settle();
+    assert_eq!(state(child.id()), "T");
+    token.set_resting(false);
+    settle();
+    assert_ne!(state(child.id()), "T");
+
+    token.set_held(true);
+    token.set_paused(true).unwrap();
+    token.set_held(false);
+    settle();
+    assert_eq!(state(child.id()), "T");
+    token.set_paused(false).unwrap();
+    settle();
+    assert_ne!(state(child.id()), "T");
+
+    token.cancel();
+    assert!(!child.wait().unwrap().success());
+}
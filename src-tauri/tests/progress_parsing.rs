@@ -0,0 +1,100 @@
+use bunchatools_lib::testing::{
+    build_format_selector, fixture, parse_time_from_progress, parse_ytdlp_output_path,
+    parse_ytdlp_progress,
+};
+
+#[test]
+fn ffmpeg_progress_times() {
+    assert_eq!(parse_time_from_progress("out_time_ms=5138000"), Some(5.138));
+    assert_eq!(
+        parse_time_from_progress("out_time=01:02:03.500000"),
+        Some(3723.5)
+    );
+    assert_eq!(parse_time_from_progress("out_time=N/A"), None);
+    assert_eq!(parse_time_from_progress("progress=continue"), None);
+}
+
+#[test]
+fn ffmpeg_fixture_times_are_monotonic() {
+    let times: Vec<f64> = fixture("ffmpeg_progress.txt")
+        .lines()
+        .filter(|line| line.starts_with("out_time="))
+        .filter_map(parse_time_from_progress)
+        .collect();
+
+    assert_eq!(times, vec![0.0, 5.138, 10.1, 15.093, 20.0]);
+}
+
+#[test]
+fn ytdlp_progress_line() {
+    let progress =
+        parse_ytdlp_progress("[download]  12.5% of   79.60MiB at    5.23MiB/s ETA 00:13");
+    assert_eq!(progress.stage, "downloading");
+    assert_eq!(progress.percent, 12.5);
+    assert_eq!(progress.file_size.as_deref(), Some("79.60MiB"));
+    assert_eq!(progress.download_speed.as_deref(), Some("5.23MiB/s"));
+    assert_eq!(progress.eta.as_deref(), Some("00:13"));
+}
+
+#[test]
+fn ytdlp_progress_without_eta() {
+    let progress = parse_ytdlp_progress("[download] 100% of   79.60MiB in 00:00:15 at 5.12MiB/s");
+    assert_eq!(progress.percent, 100.0);
+    assert_eq!(progress.download_speed.as_deref(), Some("5.12MiB/s"));
+    assert_eq!(progress.eta, None);
+}
+
+#[test]
+fn ytdlp_progress_unknown_speed() {
+    let progress =
+        parse_ytdlp_progress("[download]   0.0% of   79.60MiB at  Unknown B/s ETA Unknown");
+    assert_eq!(progress.percent, 0.0);
+    assert_eq!(progress.download_speed.as_deref(), Some("Unknown B/s"));
+    assert_eq!(progress.eta.as_deref(), Some("Unknown"));
+}
+
+#[test]
+fn ytdlp_output_paths() {
+    assert_eq!(
+        parse_ytdlp_output_path("[download] Destination: /tmp/out/video.f137.mp4").as_deref(),
+        Some("/tmp/out/video.f137.mp4")
+    );
+    assert_eq!(
+        parse_ytdlp_output_path(r#"[Merger] Merging formats into "/tmp/out/video.mp4""#).as_deref(),
+        Some("/tmp/out/video.mp4")
+    );
+    assert_eq!(
+        parse_ytdlp_output_path("[download] /tmp/out/video.mp4 has already been downloaded")
+            .as_deref(),
+        Some("/tmp/out/video.mp4")
+    );
+    assert_eq!(
+        parse_ytdlp_output_path("[youtube] dQw4w9WgXcQ: Downloading webpage"),
+        None
+    );
+}
+
+#[test]
+fn format_selector_modes() {
+    assert_eq!(
+        build_format_selector("best", "audio_only"),
+        "bestaudio[ext=m4a]/bestaudio/best"
+    );
+    assert_eq!(
+        build_format_selector("720p", "video_only"),
+        "bestvideo[height<=720]/best[height<=720]/bestvideo/best"
+    );
+    assert_eq!(
+        build_format_selector("1080p", "video_audio"),
+        "bestvideo[height<=1080]+bestaudio/best[height<=1080]/bestvideo+bestaudio/best"
+    );
+}
+
+#[test]
+fn format_selector_falls_back_for_unknown_quality() {
+    assert_eq!(build_format_selector("8k", "video_only"), "bestvideo/best");
+    assert_eq!(
+        build_format_selector("8k", "video_audio"),
+        "bestvideo+bestaudio/best"
+    );
+}
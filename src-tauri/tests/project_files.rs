@@ -0,0 +1,35 @@
+use bunchatools_lib::testing::{combine_gitignore, editorconfig, gitignore_sections};
+
+fn strings(items: &[&str]) -> Vec<String> {
+    items.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn resolves_aliases_and_reports_unknown_templates() {
+    let (sections, missing) = gitignore_sections(&strings(&["Rust", "ts", "node", "elixir"]));
+    let names: Vec<&str> = sections.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, ["Rust", "Node"]);
+    assert_eq!(missing, ["elixir"]);
+}
+
+#[test]
+fn combines_sections_without_repeating_patterns() {
+    let (sections, _) = gitignore_sections(&strings(&["node", "python", "macos"]));
+    let gitignore = combine_gitignore(&sections);
+    assert!(gitignore.starts_with("# Created by BunchaTools\n\n### Node ###\n"));
+    assert!(gitignore.contains("\n### Python ###\n"));
+    assert!(gitignore.contains("\n### macOS ###\n.DS_Store\n"));
+    assert_eq!(gitignore.matches("\nbuild/\n").count(), 1);
+    assert_eq!(gitignore.matches("\n.env\n").count(), 1);
+    assert!(!gitignore.contains("\n\n\n"));
+}
+
+#[test]
+fn editorconfig_adds_language_overrides() {
+    let config = editorconfig(&strings(&["rust", "golang"]));
+    assert!(config.contains("root = true\n"));
+    assert!(config.contains("[*.rs]\nindent_size = 4\n"));
+    assert!(config.contains("[*.go]\nindent_style = tab\n"));
+    assert!(!config.contains("[*.py]"));
+    assert!(!config.contains("crlf"));
+}
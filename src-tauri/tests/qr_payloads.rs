@@ -0,0 +1,77 @@
+use bunchatools_lib::testing::{build_qr_payload, QrPayload, VcardFields, WifiSecurity};
+
+fn wifi(ssid: &str, password: &str, security: WifiSecurity) -> QrPayload {
+    QrPayload::Wifi {
+        ssid: ssid.to_string(),
+        password: password.to_string(),
+        security,
+        hidden: false,
+    }
+}
+
+#[test]
+fn wifi_payloads() {
+    assert_eq!(
+        build_qr_payload(&wifi("Home;Net", "pa:ss\\word", WifiSecurity::Wpa)).as_deref(),
+        Ok(r"WIFI:T:WPA;S:Home\;Net;P:pa\:ss\\word;;")
+    );
+    assert_eq!(
+        build_qr_payload(&wifi("Cafe", "", WifiSecurity::None)).as_deref(),
+        Ok("WIFI:T:nopass;S:Cafe;;")
+    );
+    assert!(build_qr_payload(&wifi("Home", "short", WifiSecurity::Wpa)).is_err());
+    assert!(build_qr_payload(&wifi("Home", "0123456789", WifiSecurity::Wep)).is_ok());
+    assert!(build_qr_payload(&wifi("Home", "012345", WifiSecurity::Wep)).is_err());
+    assert!(build_qr_payload(&wifi("", "password123", WifiSecurity::Wpa)).is_err());
+}
+
+#[test]
+fn vcard_escaping() {
+    let card = build_qr_payload(&QrPayload::Vcard(VcardFields {
+        first_name: "Ada".to_string(),
+        last_name: "Lovelace".to_string(),
+        organization: "Analytical Engines, Ltd; London".to_string(),
+        email: "ada@example.com".to_string(),
+        ..Default::default()
+    }))
+    .unwrap();
+    assert_eq!(
+        card.split("\r\n").collect::<Vec<_>>(),
+        [
+            "BEGIN:VCARD",
+            "VERSION:3.0",
+            "N:Lovelace;Ada;;;",
+            "FN:Ada Lovelace",
+            r"ORG:Analytical Engines\, Ltd\; London",
+            "EMAIL:ada@example.com",
+            "END:VCARD",
+        ]
+    );
+    assert!(build_qr_payload(&QrPayload::Vcard(VcardFields::default())).is_err());
+}
+
+#[test]
+fn geo_and_mailto() {
+    let geo = |latitude: &str, longitude: &str| {
+        build_qr_payload(&QrPayload::Geo {
+            latitude: latitude.to_string(),
+            longitude: longitude.to_string(),
+        })
+    };
+    assert_eq!(
+        geo("48.8584", " 2.2945000 ").as_deref(),
+        Ok("geo:48.8584,2.2945")
+    );
+    assert!(geo("91", "0").is_err());
+    assert!(geo("north", "0").is_err());
+
+    assert_eq!(
+        build_qr_payload(&QrPayload::Mailto {
+            email: "hi@example.com".to_string(),
+            subject: "Hello there & welcome".to_string(),
+            body: String::new(),
+        })
+        .as_deref(),
+        Ok("mailto:hi@example.com?subject=Hello%20there%20%26%20welcome")
+    );
+}
@@ -0,0 +1,180 @@
+use bunchatools_lib::testing::{
+    classify_scanned_code, correct_errors, decode_matrix, fixture, scan_qr_image, Matrix,
+    ScannedCode,
+};
+use image::{Rgba, RgbaImage};
+
+const WIFI: &str = r"WIFI:T:WPA;S:Cafe\;Upstairs;P:c0ffee\:beans;H:true;;";
+
+// Module grids from tests/fixtures, '#' for dark
+fn matrix(name: &str) -> Matrix {
+    fixture(name)
+        .lines()
+        .map(|line| line.chars().map(|c| c == '#').collect())
+        .collect()
+}
+
+fn flip(matrix: &mut Matrix, x: usize, y: usize, size: usize) {
+    for row in matrix.iter_mut().skip(y).take(size) {
+        for module in row.iter_mut().skip(x).take(size) {
+            *module = !*module;
+        }
+    }
+}
+
+// Draw the code with a 4-module quiet zone, somewhere inside a larger gray image
+fn render(matrix: &Matrix, scale: u32, dark: [u8; 3], light: [u8; 3]) -> RgbaImage {
+    let size = matrix.len() as u32;
+    let (left, top) = (37, 51);
+    let mut image = RgbaImage::from_pixel(
+        left * 2 + (size + 8) * scale,
+        top * 2 + (size + 8) * scale,
+        Rgba([128, 128, 128, 255]),
+    );
+    for y in 0..(size + 8) * scale {
+        for x in 0..(size + 8) * scale {
+            let (mx, my) = ((x / scale) as i64 - 4, (y / scale) as i64 - 4);
+            let is_dark = (0..size as i64).contains(&mx)
+                && (0..size as i64).contains(&my)
+                && matrix[my as usize][mx as usize];
+            let [r, g, b] = if is_dark { dark } else { light };
+            image.put_pixel(left + x, top + y, Rgba([r, g, b, 255]));
+        }
+    }
+    image
+}
+
+#[test]
+fn reed_solomon_corrects_up_to_half_the_error_codewords() {
+    // HELLO WORLD as 1-Q: 13 data and 13 error correction codewords
+    let block: Vec<u8> = vec![
+        32, 91, 11, 120, 209, 114, 220, 77, 67, 64, 236, 17, 236, 168, 72, 22, 82, 217, 54, 156, 0,
+        46, 15, 180, 122, 16,
+    ];
+    let mut damaged = block.clone();
+    for i in [0, 3, 7, 12, 19, 25] {
+        damaged[i] ^= 0x5A;
+    }
+    assert_eq!(correct_errors(&mut damaged, 13), Some(6));
+    assert_eq!(damaged, block);
+
+    let mut intact = block.clone();
+    assert_eq!(correct_errors(&mut intact, 13), Some(0));
+
+    // Seven errors are more than 13 codewords can locate
+    let mut damaged = block.clone();
+    for i in [0, 3, 7, 12, 19, 22, 25] {
+        damaged[i] ^= 0x5A;
+    }
+    let fixed = correct_errors(&mut damaged, 13);
+    assert!(fixed.is_none() || damaged != block);
+}
+
+#[test]
+fn module_grids_decode() {
+    assert_eq!(
+        decode_matrix(&matrix("qr_hello_world_v1.txt")).as_deref(),
+        Some("HELLO WORLD")
+    );
+    assert_eq!(
+        decode_matrix(&matrix("qr_url_v3.txt")).as_deref(),
+        Some("https://example.com/menu?table=12")
+    );
+    // ECI 26 (UTF-8) byte segment followed by a numeric segment
+    assert_eq!(
+        decode_matrix(&matrix("qr_eci_v2.txt")).as_deref(),
+        Some("Grüße, 世界0123456789")
+    );
+    // Version 7 and up carry version info; 7-Q mixes blocks of two lengths
+    assert_eq!(
+        decode_matrix(&matrix("qr_wifi_v7.txt")).as_deref(),
+        Some(WIFI)
+    );
+}
+
+#[test]
+fn damaged_modules_are_corrected() {
+    let mut hello = matrix("qr_hello_world_v1.txt");
+    flip(&mut hello, 15, 15, 3);
+    assert_eq!(decode_matrix(&hello).as_deref(), Some("HELLO WORLD"));
+
+    let mut wifi = matrix("qr_wifi_v7.txt");
+    flip(&mut wifi, 12, 12, 5);
+    assert_eq!(decode_matrix(&wifi).as_deref(), Some(WIFI));
+
+    // Wrong sizes and noise are rejected rather than misread
+    assert_eq!(decode_matrix(&vec![vec![false; 20]; 20]), None);
+    assert_eq!(decode_matrix(&vec![vec![true; 21]; 21]), None);
+}
+
+#[test]
+fn codes_are_found_in_screenshots() {
+    let wifi = matrix("qr_wifi_v7.txt");
+    let expected = vec![ScannedCode::Wifi {
+        text: WIFI.to_string(),
+        ssid: "Cafe;Upstairs".to_string(),
+        password: "c0ffee:beans".to_string(),
+        security: "WPA".to_string(),
+        hidden: true,
+    }];
+    assert_eq!(
+        scan_qr_image(&render(&wifi, 4, [0, 0, 0], [255, 255, 255])),
+        expected
+    );
+    // Dark mode: light modules on a dark background
+    assert_eq!(
+        scan_qr_image(&render(&wifi, 3, [230, 230, 230], [30, 30, 40])),
+        expected
+    );
+
+    // Mirrored, as in a selfie camera preview
+    let url = matrix("qr_url_v3.txt");
+    let mirrored: Matrix = url
+        .iter()
+        .map(|row| row.iter().rev().copied().collect())
+        .collect();
+    assert_eq!(
+        scan_qr_image(&render(&mirrored, 5, [20, 40, 90], [250, 245, 230])),
+        vec![ScannedCode::Url {
+            text: "https://example.com/menu?table=12".to_string()
+        }]
+    );
+
+    assert_eq!(
+        scan_qr_image(&RgbaImage::from_pixel(300, 200, Rgba([255, 255, 255, 255]))),
+        vec![]
+    );
+}
+
+#[test]
+fn scanned_text_is_classified() {
+    assert_eq!(
+        classify_scanned_code("HTTPS://Example.com/a"),
+        ScannedCode::Url {
+            text: "HTTPS://Example.com/a".to_string()
+        }
+    );
+    assert_eq!(
+        classify_scanned_code("https://example.com is down"),
+        ScannedCode::Text {
+            text: "https://example.com is down".to_string()
+        }
+    );
+    assert_eq!(
+        classify_scanned_code("WIFI:S:Guest;;"),
+        ScannedCode::Wifi {
+            text: "WIFI:S:Guest;;".to_string(),
+            ssid: "Guest".to_string(),
+            password: String::new(),
+            security: "nopass".to_string(),
+            hidden: false,
+        }
+    );
+    // No network name: not usable as Wi-Fi credentials
+    assert_eq!(
+        classify_scanned_code("WIFI:P:secret;;"),
+        ScannedCode::Text {
+            text: "WIFI:P:secret;;".to_string()
+        }
+    );
+}
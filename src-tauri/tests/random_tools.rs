@@ -0,0 +1,39 @@
+use bunchatools_lib::testing::{parse_dice, roll_terms, DiceTerm};
+
+fn term(count: u32, sides: u32, negative: bool) -> DiceTerm {
+    DiceTerm {
+        count,
+        sides,
+        negative,
+    }
+}
+
+#[test]
+fn dice_notation() {
+    assert_eq!(
+        parse_dice("3d6+2"),
+        Ok(vec![term(3, 6, false), term(2, 0, false)])
+    );
+    assert_eq!(parse_dice("d20"), Ok(vec![term(1, 20, false)]));
+    assert_eq!(parse_dice("D%"), Ok(vec![term(1, 100, false)]));
+    assert_eq!(
+        parse_dice("2d8 + 1d4 - 1"),
+        Ok(vec![term(2, 8, false), term(1, 4, false), term(1, 0, true)])
+    );
+
+    for invalid in ["", "5", "3d", "d1", "0d6", "3d6+", "3x6", "1000d6"] {
+        assert!(parse_dice(invalid).is_err(), "{}", invalid);
+    }
+}
+
+#[test]
+fn dice_totals() {
+    let terms = parse_dice("3d6+2-1d4").unwrap();
+    // Every die lands on its highest face
+    let roll = roll_terms("3d6+2-1d4", &terms, |sides| sides);
+    assert_eq!(roll.groups.len(), 2);
+    assert_eq!(roll.groups[0].rolls, vec![6, 6, 6]);
+    assert_eq!(roll.groups[1].rolls, vec![4]);
+    assert_eq!(roll.modifier, 2);
+    assert_eq!(roll.total, 18 + 2 - 4);
+}
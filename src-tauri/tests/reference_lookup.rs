@@ -0,0 +1,42 @@
+use bunchatools_lib::testing::{lookup_reference, ReferenceKind};
+
+fn first(kind: ReferenceKind, query: &str) -> (String, String) {
+    let entry = lookup_reference(kind, query)
+        .into_iter()
+        .next()
+        .unwrap_or_else(|| panic!("no match for {:?} {}", kind, query));
+    (entry.key, entry.name)
+}
+
+#[test]
+fn http_status_by_code_or_phrase() {
+    assert_eq!(
+        first(ReferenceKind::HttpStatus, "404"),
+        ("404".into(), "Not Found".into())
+    );
+    assert_eq!(first(ReferenceKind::HttpStatus, "teapot").0, "418");
+    // Prefix: all the 50x codes, starting with 500
+    let results = lookup_reference(ReferenceKind::HttpStatus, "50");
+    assert_eq!(results[0].key, "500");
+    assert!(results.iter().all(|e| e.key.starts_with("50")));
+}
+
+#[test]
+fn mime_types_both_ways() {
+    assert_eq!(first(ReferenceKind::MimeType, ".SVG").1, "image/svg+xml");
+    assert_eq!(first(ReferenceKind::MimeType, "application/json").0, "json");
+}
+
+#[test]
+fn ports_and_entities() {
+    assert_eq!(first(ReferenceKind::Port, "postgres").0, "5432");
+    assert_eq!(first(ReferenceKind::Port, "6379").1, "Redis");
+
+    assert_eq!(first(ReferenceKind::HtmlEntity, "&copy;").1, "©");
+    assert_eq!(first(ReferenceKind::HtmlEntity, "©").0, "copy");
+    // Entity names are case-sensitive
+    assert_eq!(first(ReferenceKind::HtmlEntity, "rArr").1, "⇒");
+    assert_eq!(first(ReferenceKind::HtmlEntity, "rarr").1, "→");
+
+    assert!(lookup_reference(ReferenceKind::Port, "  ").is_empty());
+}
@@ -0,0 +1,121 @@
+use bunchatools_lib::testing::{
+    screen_recording_args, AudioCaptureInput, RecordingFormat, RecordingOptions, Region,
+    ScreenCaptureBackend,
+};
+
+fn options(format: RecordingFormat, region: Option<Region>, audio: bool) -> RecordingOptions {
+    RecordingOptions {
+        region,
+        fps: 24,
+        audio,
+        format,
+        output_path: None,
+    }
+}
+
+fn microphone() -> AudioCaptureInput {
+    AudioCaptureInput {
+        format: "dshow".to_string(),
+        device: "audio=Microphone (USB)".to_string(),
+    }
+}
+
+// The value following `flag`
+fn arg<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    let i = args.iter().position(|a| a == flag)?;
+    args.get(i + 1).map(String::as_str)
+}
+
+#[test]
+fn gdigrab_region_and_audio() {
+    let region = Region {
+        x: -1920,
+        y: 100,
+        width: 641,
+        height: 480,
+    };
+    let args = screen_recording_args(
+        &ScreenCaptureBackend::Gdigrab,
+        &options(RecordingFormat::Mp4, Some(region), true),
+        Some(&microphone()),
+        "C:\\Videos\\clip.mp4",
+    );
+    assert_eq!(arg(&args, "-f"), Some("gdigrab"));
+    assert_eq!(arg(&args, "-framerate"), Some("24"));
+    assert_eq!(arg(&args, "-offset_x"), Some("-1920"));
+    assert_eq!(arg(&args, "-video_size"), Some("641x480"));
+    assert!(args
+        .windows(2)
+        .any(|w| w == ["-i", "audio=Microphone (USB)"]));
+    assert_eq!(arg(&args, "-c:v"), Some("libx264"));
+    assert_eq!(arg(&args, "-c:a"), Some("aac"));
+    assert_eq!(arg(&args, "-vf"), Some("scale=trunc(iw/2)*2:trunc(ih/2)*2"));
+    assert_eq!(args.last().unwrap(), "C:\\Videos\\clip.mp4");
+}
+
+#[test]
+fn x11grab_offsets_the_display() {
+    let backend = ScreenCaptureBackend::X11grab {
+        display: ":1".to_string(),
+    };
+    let region = Region {
+        x: 10,
+        y: 20,
+        width: 300,
+        height: 200,
+    };
+    let args = screen_recording_args(
+        &backend,
+        &options(RecordingFormat::Mkv, Some(region), false),
+        None,
+        "out.mkv",
+    );
+    assert_eq!(arg(&args, "-i"), Some(":1+10,20"));
+    assert_eq!(arg(&args, "-video_size"), Some("300x200"));
+    assert_eq!(arg(&args, "-c:a"), None);
+
+    let full = screen_recording_args(
+        &backend,
+        &options(RecordingFormat::Mkv, None, false),
+        None,
+        "out.mkv",
+    );
+    assert_eq!(arg(&full, "-i"), Some(":1"));
+    assert_eq!(arg(&full, "-video_size"), None);
+}
+
+#[test]
+fn pipewire_crops_the_region() {
+    let region = Region {
+        x: 10,
+        y: 20,
+        width: 300,
+        height: 200,
+    };
+    let args = screen_recording_args(
+        &ScreenCaptureBackend::Pipewire,
+        &options(RecordingFormat::Webm, Some(region), true),
+        Some(&microphone()),
+        "out.webm",
+    );
+    assert_eq!(arg(&args, "-i"), Some("pipewiregrab=framerate=24"));
+    assert_eq!(
+        arg(&args, "-vf"),
+        Some("crop=300:200:10:20,scale=trunc(iw/2)*2:trunc(ih/2)*2")
+    );
+    assert_eq!(arg(&args, "-c:v"), Some("libvpx-vp9"));
+    assert_eq!(arg(&args, "-c:a"), Some("libopus"));
+}
+
+#[test]
+fn gif_has_no_audio() {
+    let args = screen_recording_args(
+        &ScreenCaptureBackend::Gdigrab,
+        &options(RecordingFormat::Gif, None, true),
+        Some(&microphone()),
+        "out.gif",
+    );
+    assert!(!args.iter().any(|a| a.starts_with("audio=")));
+    assert_eq!(arg(&args, "-loop"), Some("0"));
+    assert_eq!(arg(&args, "-vf"), None);
+}
@@ -0,0 +1,29 @@
+use bunchatools_lib::testing::clean_recognized_text;
+
+#[test]
+fn lines_of_a_paragraph_are_joined() {
+    assert_eq!(
+        clean_recognized_text("The quick brown\nfox   jumps over\n  the lazy dog"),
+        "The quick brown fox jumps over the lazy dog"
+    );
+}
+
+#[test]
+fn hyphenated_line_breaks_are_rejoined() {
+    assert_eq!(
+        clean_recognized_text("an impor-\ntant message"),
+        "an important message"
+    );
+    // A dash before a capitalized word or a number is kept
+    assert_eq!(clean_recognized_text("Jean-\nPaul"), "Jean- Paul");
+    assert_eq!(clean_recognized_text("pages 10-\n12"), "pages 10- 12");
+}
+
+#[test]
+fn blank_lines_separate_paragraphs() {
+    assert_eq!(
+        clean_recognized_text("\nFirst line\ncontinues\n\n\nSecond\n\n"),
+        "First line continues\n\nSecond"
+    );
+    assert_eq!(clean_recognized_text(" \n\t\n"), "");
+}
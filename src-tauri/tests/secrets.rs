@@ -0,0 +1,32 @@
+use bunchatools_lib::testing::{derive_secrets_key, open_secret, seal_secret};
+
+#[test]
+fn sealed_text_opens_with_the_same_key() {
+    let key = derive_secrets_key("correct horse battery", b"0123456789abcdef", 1000);
+    let sealed = seal_secret(&key, b"ABCD-1234-EFGH").unwrap();
+    assert!(!sealed.contains("ABCD"));
+    assert_eq!(open_secret(&key, &sealed).unwrap(), b"ABCD-1234-EFGH");
+
+    // A fresh nonce every time
+    assert_ne!(seal_secret(&key, b"ABCD-1234-EFGH").unwrap(), sealed);
+}
+
+#[test]
+fn the_wrong_passphrase_or_altered_data_fails() {
+    let salt = b"0123456789abcdef";
+    let key = derive_secrets_key("correct horse battery", salt, 1000);
+    let sealed = seal_secret(&key, b"license key").unwrap();
+
+    let wrong = derive_secrets_key("correct horse battery!", salt, 1000);
+    assert!(open_secret(&wrong, &sealed).is_err());
+    // The salt matters too
+    let resalted = derive_secrets_key("correct horse battery", b"fedcba9876543210", 1000);
+    assert!(open_secret(&resalted, &sealed).is_err());
+
+    let mut altered = sealed.clone().into_bytes();
+    let last = altered.len() - 3;
+    altered[last] = if altered[last] == b'A' { b'B' } else { b'A' };
+    assert!(open_secret(&key, &String::from_utf8(altered).unwrap()).is_err());
+    assert!(open_secret(&key, "not base64!").is_err());
+    assert!(open_secret(&key, "").is_err());
+}
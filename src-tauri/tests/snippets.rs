@@ -0,0 +1,124 @@
+use bunchatools_lib::testing::{
+    delete_snippet, delete_snippet_transform, expand_snippet, save_snippet_transform,
+    set_app_locked, EventCapture, SnippetContext, SnippetTransform,
+};
+use chrono::{Local, TimeZone};
+
+fn context(transforms: &[SnippetTransform]) -> SnippetContext<'_> {
+    SnippetContext {
+        now: Local.with_ymd_and_hms(2026, 10, 16, 14, 5, 0).unwrap(),
+        clipboard: Some("  hello world  ".to_string()),
+        transforms,
+    }
+}
+
+fn expand(template: &str) -> Result<String, String> {
+    expand_snippet(template, &context(&[]))
+}
+
+#[test]
+fn dates_and_times_with_offsets_and_formats() {
+    assert_eq!(expand("Due {date+3d}").unwrap(), "Due 2026-10-19");
+    assert_eq!(expand("{date-1w:%d.%m.%Y}").unwrap(), "09.10.2026");
+    assert_eq!(expand("{time}").unwrap(), "14:05");
+    assert_eq!(expand("{time+2h:%H:%M}").unwrap(), "16:05");
+    assert_eq!(expand("{date:%A|upper}").unwrap(), "FRIDAY");
+
+    assert!(expand("{date+3x}").is_err());
+    assert!(expand("{date:%Q}").is_err());
+    assert!(expand("{time+9999999999999999s}").is_err());
+    assert!(expand("{date+999999999w}").is_err());
+    assert!(expand("{date-999999999w}").is_err());
+}
+
+#[test]
+fn generated_values() {
+    let uuid = expand("{uuid}").unwrap();
+    let groups: Vec<usize> = uuid.split('-').map(str::len).collect();
+    assert_eq!(groups, [8, 4, 4, 4, 12]);
+    assert_eq!(&uuid[14..15], "4");
+    assert_ne!(uuid, expand("{uuid}").unwrap());
+
+    let random = expand("{random:12}").unwrap();
+    assert_eq!(random.len(), 12);
+    assert!(random.chars().all(|c| c.is_ascii_alphanumeric()));
+    assert_eq!(expand("{random}").unwrap().len(), 8);
+    assert!(expand("{random:0}").is_err());
+}
+
+#[test]
+fn clipboard_through_transforms() {
+    assert_eq!(expand("{clipboard|trim|upper}").unwrap(), "HELLO WORLD");
+    assert_eq!(expand("{clipboard|title}").unwrap(), "  Hello World  ");
+    assert_eq!(expand("{clipboard|slug}").unwrap(), "hello-world");
+    assert_eq!(
+        expand("{clipboard|trim|replace:o:0}").unwrap(),
+        "hell0 w0rld"
+    );
+    // The text transforms work too
+    assert_eq!(expand("{clipboard|trim|rot13}").unwrap(), "uryyb jbeyq");
+    assert!(expand("{clipboard|nope}").is_err());
+
+    let empty = SnippetContext {
+        clipboard: None,
+        ..context(&[])
+    };
+    assert!(expand_snippet("{clipboard}", &empty).is_err());
+}
+
+#[test]
+fn user_pipelines_can_build_on_each_other() {
+    let transforms = [
+        SnippetTransform {
+            name: "shout".to_string(),
+            pipeline: "trim|upper|replace:$:!".to_string(),
+        },
+        SnippetTransform {
+            name: "shout-slug".to_string(),
+            pipeline: "shout|slug".to_string(),
+        },
+        SnippetTransform {
+            name: "loop".to_string(),
+            pipeline: "loop".to_string(),
+        },
+    ];
+    let context = context(&transforms);
+    assert_eq!(
+        expand_snippet("{clipboard|shout}", &context).unwrap(),
+        "HELLO WORLD!"
+    );
+    assert_eq!(
+        expand_snippet("{clipboard|shout-slug}", &context).unwrap(),
+        "hello-world"
+    );
+    assert!(expand_snippet("{clipboard|loop}", &context).is_err());
+}
+
+#[test]
+fn literal_braces_and_syntax_errors() {
+    assert_eq!(
+        expand("fn main() {{ }} // {date}").unwrap(),
+        "fn main() { } // 2026-10-16"
+    );
+    assert_eq!(expand("plain text").unwrap(), "plain text");
+    assert!(expand("{date").is_err());
+    assert!(expand("oops }").is_err());
+    assert_eq!(expand("{nope}").unwrap_err(), "Unknown placeholder: {nope}");
+}
+
+#[test]
+fn a_locked_app_leaves_snippets_alone() {
+    let capture = EventCapture::new(&[]);
+    let app = capture.handle();
+    set_app_locked(true);
+    let locked = |result: Result<(), String>| result.unwrap_err().contains("locked");
+    assert!(locked(delete_snippet(app.clone(), "sig".to_string())));
+    assert!(locked(delete_snippet_transform(
+        app.clone(),
+        "shout".to_string()
+    )));
+    assert!(locked(
+        save_snippet_transform(app.clone(), "shout".to_string(), "upper".to_string()).map(|_| ())
+    ));
+    set_app_locked(false);
+}
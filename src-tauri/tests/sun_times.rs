@@ -0,0 +1,84 @@
+use bunchatools_lib::testing::{parse_coordinates, sun_times, Polar};
+use chrono::{DateTime, NaiveDate};
+
+fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+// Assert `timestamp` is within two minutes of `expected` (UTC, "YYYY-MM-DD HH:MM")
+fn assert_near(timestamp: Option<i64>, expected: &str) {
+    let expected = chrono::NaiveDateTime::parse_from_str(expected, "%Y-%m-%d %H:%M")
+        .unwrap()
+        .and_utc()
+        .timestamp();
+    let timestamp = timestamp.expect("no time");
+    assert!(
+        (timestamp - expected).abs() <= 120,
+        "{} is not near {}",
+        DateTime::from_timestamp(timestamp, 0).unwrap(),
+        DateTime::from_timestamp(expected, 0).unwrap()
+    );
+}
+
+#[test]
+fn sunrise_and_sunset_match_published_times() {
+    // London, summer solstice (04:43 / 21:21 BST)
+    let london = sun_times(51.5074, -0.1278, date(2024, 6, 21));
+    assert_near(london.sunrise, "2024-06-21 03:43");
+    assert_near(london.sunset, "2024-06-21 20:21");
+    assert_eq!(london.polar, None);
+    assert!((london.day_length as i64 - (16 * 3600 + 38 * 60)).abs() <= 180);
+
+    // New York, winter solstice (07:16 / 16:32 EST)
+    let new_york = sun_times(40.7128, -74.0060, date(2024, 12, 21));
+    assert_near(new_york.sunrise, "2024-12-21 12:16");
+    assert_near(new_york.sunset, "2024-12-21 21:32");
+
+    // Sydney: the local morning is the previous UTC day (07:00 / 16:54 AEST)
+    let sydney = sun_times(-33.8688, 151.2093, date(2024, 6, 21));
+    assert_near(sydney.sunrise, "2024-06-20 21:00");
+    assert_near(sydney.sunset, "2024-06-21 06:54");
+}
+
+#[test]
+fn twilight_and_golden_hours_surround_sunrise_and_sunset() {
+    let times = sun_times(48.8566, 2.3522, date(2026, 10, 16));
+    let (rise, set) = (times.sunrise.unwrap(), times.sunset.unwrap());
+    let morning = times.golden_hour_morning.unwrap();
+    let evening = times.golden_hour_evening.unwrap();
+
+    assert!(times.dawn.unwrap() < morning.start && morning.start < rise && rise < morning.end);
+    assert!(evening.start < set && set < evening.end && evening.end < times.dusk.unwrap());
+    assert!(rise < times.solar_noon && times.solar_noon < set);
+    // About an hour each at mid latitudes
+    assert!((2400..5400).contains(&(morning.end - morning.start)));
+    assert!((2400..5400).contains(&(evening.end - evening.start)));
+}
+
+#[test]
+fn polar_day_and_night() {
+    // Tromsø
+    let summer = sun_times(69.6492, 18.9553, date(2026, 6, 21));
+    assert_eq!(summer.polar, Some(Polar::Day));
+    assert_eq!((summer.sunrise, summer.sunset), (None, None));
+    assert_eq!(summer.day_length, 86_400);
+
+    let winter = sun_times(69.6492, 18.9553, date(2026, 12, 21));
+    assert_eq!(winter.polar, Some(Polar::Night));
+    assert_eq!(winter.day_length, 0);
+    // The sun stays just below the horizon: twilight, and a golden hour that meets at noon
+    assert!(winter.dawn.is_some());
+    let morning = winter.golden_hour_morning.unwrap();
+    assert_eq!(morning.end, winter.solar_noon);
+}
+
+#[test]
+fn coordinates_are_parsed() {
+    assert_eq!(parse_coordinates("52.52, 13.405"), Some((52.52, 13.405)));
+    assert_eq!(parse_coordinates("-33.87 151.21"), Some((-33.87, 151.21)));
+    assert_eq!(parse_coordinates("33.87S, 151.21E"), Some((-33.87, 151.21)));
+    assert_eq!(parse_coordinates("40.71°N 74.01°W"), Some((40.71, -74.01)));
+    assert_eq!(parse_coordinates("95, 10"), None);
+    assert_eq!(parse_coordinates("New York"), None);
+    assert_eq!(parse_coordinates("Paris, France"), None);
+}
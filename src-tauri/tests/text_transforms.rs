@@ -0,0 +1,54 @@
+use bunchatools_lib::testing::apply_text_transform;
+
+#[test]
+fn morse_round_trip() {
+    let encoded = apply_text_transform("morse", "SOS help").unwrap();
+    assert_eq!(encoded, "... --- ... / .... . .-.. .--.");
+    assert_eq!(
+        apply_text_transform("unmorse", &encoded).unwrap(),
+        "SOS HELP"
+    );
+    // Typographic dots and dashes from chat apps
+    assert_eq!(apply_text_transform("unmorse", "·− −···").unwrap(), "AB");
+    assert!(apply_text_transform("unmorse", "..--..--").is_err());
+}
+
+#[test]
+fn nato_rot13_and_leet() {
+    assert_eq!(
+        apply_text_transform("nato", "Hi 9").unwrap(),
+        "Hotel India / Niner"
+    );
+    assert_eq!(
+        apply_text_transform("rot13", "Hello, World!").unwrap(),
+        "Uryyb, Jbeyq!"
+    );
+    assert_eq!(apply_text_transform("leet", "elite").unwrap(), "31173");
+}
+
+#[test]
+fn reverse_keeps_accents_and_unzalgo_strips_stacks() {
+    // "cafe" + combining acute accent on the e
+    assert_eq!(
+        apply_text_transform("reverse", "cafe\u{301}!").unwrap(),
+        "!e\u{301}fac"
+    );
+    assert_eq!(
+        apply_text_transform(
+            "unzalgo",
+            "z\u{335}\u{31b}\u{358}\u{322}a\u{358}\u{322}\u{335}\u{31b}\u{301}lgo"
+        )
+        .unwrap(),
+        "z\u{335}\u{31b}a\u{358}\u{322}lgo"
+    );
+    // Decomposed Vietnamese "Việt": e + dot below + circumflex stays as it is
+    assert_eq!(
+        apply_text_transform("unzalgo", "Vie\u{323}\u{302}t").unwrap(),
+        "Vie\u{323}\u{302}t"
+    );
+}
+
+#[test]
+fn unknown_transform_is_an_error() {
+    assert!(apply_text_transform("pig-latin", "hello").is_err());
+}
@@ -0,0 +1,105 @@
+use bunchatools_lib::testing::{
+    build_time_report, range_days, time_entries_csv, tracked_within, IdlePeriod, ReportRange,
+    TimeEntry, TimeLog,
+};
+use chrono::{NaiveDate, Utc};
+
+const HOUR: i64 = 3600;
+// 2024-05-15 00:00 UTC, a Wednesday
+const WEDNESDAY: i64 = 1_715_731_200;
+
+fn entry(label: &str, project: Option<&str>, start: i64, end: Option<i64>) -> TimeEntry {
+    TimeEntry {
+        id: 0,
+        label: label.to_string(),
+        project: project.map(str::to_string),
+        start,
+        end,
+        idle: Vec::new(),
+    }
+}
+
+fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+#[test]
+fn weeks_run_monday_to_sunday() {
+    let days = range_days(ReportRange::Week, date(2024, 5, 15));
+    assert_eq!(days.first(), Some(&date(2024, 5, 13)));
+    assert_eq!(days.last(), Some(&date(2024, 5, 19)));
+
+    let days = range_days(ReportRange::LastWeek, date(2024, 5, 13));
+    assert_eq!(days.first(), Some(&date(2024, 5, 6)));
+    assert_eq!(days.len(), 7);
+}
+
+#[test]
+fn idle_time_is_left_out() {
+    let mut e = entry("Review", None, 0, Some(2 * HOUR));
+    e.idle.push(IdlePeriod {
+        start: HOUR,
+        end: HOUR + 900,
+    });
+    assert_eq!(tracked_within(&e, 0, 10 * HOUR, 0), 2 * HOUR as u64 - 900);
+    // Only the part inside the window counts
+    assert_eq!(tracked_within(&e, HOUR, 10 * HOUR, 0), HOUR as u64 - 900);
+}
+
+#[test]
+fn report_splits_entries_across_days_and_projects() {
+    let log = TimeLog {
+        entries: vec![
+            // 22:00 Tuesday to 02:00 Wednesday
+            entry(
+                "Deploy",
+                Some("Ops"),
+                WEDNESDAY - 2 * HOUR,
+                Some(WEDNESDAY + 2 * HOUR),
+            ),
+            entry(
+                "Write docs",
+                None,
+                WEDNESDAY + 9 * HOUR,
+                Some(WEDNESDAY + 10 * HOUR),
+            ),
+            // Still running, counted until now
+            entry("Fix bug", Some("Ops"), WEDNESDAY + 11 * HOUR, None),
+        ],
+        next_id: 3,
+    };
+    let now = WEDNESDAY + 12 * HOUR;
+
+    let today = build_time_report(&log, ReportRange::Today, date(2024, 5, 15), &Utc, now);
+    assert_eq!(today.total_seconds, 4 * HOUR as u64);
+    assert_eq!(today.projects[0].project, "Ops");
+    assert_eq!(today.projects[0].seconds, 3 * HOUR as u64);
+    assert_eq!(today.projects[1].project, "");
+
+    let week = build_time_report(&log, ReportRange::Week, date(2024, 5, 15), &Utc, now);
+    assert_eq!(week.days.len(), 7);
+    assert_eq!(week.days[1].date, "2024-05-14");
+    assert_eq!(week.days[1].seconds, 2 * HOUR as u64);
+    assert_eq!(week.total_seconds, 6 * HOUR as u64);
+    assert_eq!(week.entries.len(), 3);
+}
+
+#[test]
+fn csv_quotes_fields_that_need_it() {
+    let entries = vec![entry(
+        "Call with \"Acme\", Inc",
+        Some("Sales"),
+        WEDNESDAY,
+        Some(WEDNESDAY + 1800),
+    )];
+    let csv = time_entries_csv(&entries, &Utc, WEDNESDAY + HOUR);
+    let mut lines = csv.lines();
+    assert_eq!(
+        lines.next(),
+        Some("label,project,start,end,duration_seconds,idle_seconds")
+    );
+    assert_eq!(
+        lines.next(),
+        Some("\"Call with \"\"Acme\"\", Inc\",Sales,2024-05-15T00:00:00+00:00,2024-05-15T00:30:00+00:00,1800,0")
+    );
+}
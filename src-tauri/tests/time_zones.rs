@@ -0,0 +1,95 @@
+use bunchatools_lib::testing::{
+    convert_time_at, convert_time_query_at, decode_epoch, parse_datetime, relative_time, Zone,
+};
+use chrono::{NaiveDate, TimeZone, Utc};
+
+#[test]
+fn zones_are_found_by_name_city_abbreviation_and_offset() {
+    let name = |z: &str| match Zone::find(z).unwrap() {
+        Zone::Named(tz) => tz.name().to_string(),
+        Zone::Fixed(offset) => offset.to_string(),
+        Zone::Local => "local".to_string(),
+    };
+    assert_eq!(name("Europe/Berlin"), "Europe/Berlin");
+    assert_eq!(name("berlin"), "Europe/Berlin");
+    assert_eq!(name("New York"), "America/New_York");
+    assert_eq!(name("PST"), "America/Los_Angeles");
+    assert_eq!(name("San Francisco"), "America/Los_Angeles");
+    assert_eq!(name("UTC+5:30"), "+05:30");
+    assert_eq!(name("gmt-3"), "-03:00");
+    assert_eq!(name("utc"), "UTC");
+    assert_eq!(name("local"), "local");
+    assert!(Zone::find("Atlantis").is_err());
+    assert!(Zone::find("+🙂").is_err());
+    assert!(Zone::find("utc+aé1").is_err());
+}
+
+#[test]
+fn times_convert_with_daylight_saving() {
+    let now = Utc.with_ymd_and_hms(2026, 1, 15, 12, 0, 0).unwrap();
+    let pst = Zone::find("PST").unwrap();
+    let berlin = Zone::find("Berlin").unwrap();
+
+    // Winter: 3pm PST is midnight in Berlin
+    let winter = convert_time_at("3pm", pst, berlin, now).unwrap();
+    assert_eq!(winter.to.datetime, "2026-01-16T00:00:00");
+    assert_eq!(winter.to.abbreviation, "CET");
+    assert_eq!(winter.from.abbreviation, "PST");
+
+    // Summer: "PST" means Pacific time, which is PDT then
+    let summer = convert_time_at("2026-07-01 15:00", pst, berlin, now).unwrap();
+    assert_eq!(summer.from.abbreviation, "PDT");
+    assert_eq!(summer.to.datetime, "2026-07-02T00:00:00");
+
+    // 2:30 doesn't happen in Berlin when the clocks go forward
+    assert!(convert_time_at("2026-03-29 2:30", berlin, pst, now).is_err());
+
+    let query = convert_time_query_at("3pm PST in Berlin", now).unwrap();
+    assert_eq!(query, winter);
+    let query = convert_time_query_at("tomorrow 9:00 new york to tokyo", now).unwrap();
+    assert_eq!(query.to.datetime, "2026-01-16T23:00:00");
+    assert_eq!(
+        convert_time_query_at("time in tokyo", now)
+            .unwrap()
+            .timestamp,
+        now.timestamp()
+    );
+    assert!(convert_time_query_at("5 km in m", now).is_none());
+    assert!(convert_time_query_at("3pm utc+aé1 in berlin", now).is_none());
+}
+
+#[test]
+fn clock_times_and_days() {
+    let today = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+    let at = |text: &str| parse_datetime(text, today).unwrap().map(|t| t.to_string());
+    assert_eq!(at("now"), None);
+    assert_eq!(at("3pm").unwrap(), "2026-10-16 15:00:00");
+    assert_eq!(at("12 am").unwrap(), "2026-10-16 00:00:00");
+    assert_eq!(at("tomorrow 9:30").unwrap(), "2026-10-17 09:30:00");
+    assert_eq!(at("noon").unwrap(), "2026-10-16 12:00:00");
+    assert!(parse_datetime("3", today).is_err());
+    assert!(parse_datetime("13pm", today).is_err());
+}
+
+#[test]
+fn epochs_are_read_by_their_size() {
+    let (time, unit) = decode_epoch("1700000000").unwrap();
+    assert_eq!(
+        (time.to_rfc3339(), unit),
+        ("2023-11-14T22:13:20+00:00".to_string(), "seconds")
+    );
+    let (time, unit) = decode_epoch("1700000000123").unwrap();
+    assert_eq!(unit, "milliseconds");
+    assert_eq!(time.timestamp_millis(), 1_700_000_000_123);
+    assert_eq!(
+        decode_epoch("1700000000000000000").unwrap().1,
+        "nanoseconds"
+    );
+    assert!(decode_epoch("soon").is_err());
+
+    let now = Utc.with_ymd_and_hms(2026, 1, 15, 12, 0, 0).unwrap();
+    let then = |s: i64| now + chrono::Duration::seconds(s);
+    assert_eq!(relative_time(then(-3 * 86_400), now), "3 days ago");
+    assert_eq!(relative_time(then(7200), now), "in 2 hours");
+    assert_eq!(relative_time(then(10), now), "just now");
+}
@@ -0,0 +1,63 @@
+use bunchatools_lib::testing::{
+    parse_whisper_json, parse_whisper_progress, render_subtitles, Segment, SubtitleFormat,
+};
+
+const WHISPER_JSON: &str = r#"{
+  "systeminfo": "AVX = 1",
+  "model": {"type": "base"},
+  "result": {"language": "en"},
+  "transcription": [
+    {"timestamps": {"from": "00:00:00,000", "to": "00:00:02,500"},
+     "offsets": {"from": 0, "to": 2500}, "text": " Hello and welcome."},
+    {"timestamps": {"from": "00:00:02,500", "to": "00:00:04,000"},
+     "offsets": {"from": 2500, "to": 4000}, "text": " [BLANK_AUDIO]"},
+    {"timestamps": {"from": "01:02:03,040", "to": "01:02:05,000"},
+     "offsets": {"from": 3723040, "to": 3725000}, "text": " Goodbye."}
+  ]
+}"#;
+
+#[test]
+fn whisper_output_becomes_subtitles() {
+    let (language, segments) = parse_whisper_json(WHISPER_JSON).unwrap();
+    assert_eq!(language.as_deref(), Some("en"));
+    assert_eq!(
+        segments,
+        [
+            Segment {
+                start_ms: 0,
+                end_ms: 2500,
+                text: "Hello and welcome.".to_string()
+            },
+            Segment {
+                start_ms: 3_723_040,
+                end_ms: 3_725_000,
+                text: "Goodbye.".to_string()
+            },
+        ]
+    );
+
+    assert_eq!(
+        render_subtitles(&segments, SubtitleFormat::Srt),
+        "1\n00:00:00,000 --> 00:00:02,500\nHello and welcome.\n\n\
+         2\n01:02:03,040 --> 01:02:05,000\nGoodbye.\n\n"
+    );
+    assert_eq!(
+        render_subtitles(&segments, SubtitleFormat::Vtt),
+        "WEBVTT\n\n00:00:00.000 --> 00:00:02.500\nHello and welcome.\n\n\
+         01:02:03.040 --> 01:02:05.000\nGoodbye.\n\n"
+    );
+    assert!(parse_whisper_json("not json").is_err());
+}
+
+#[test]
+fn progress_lines() {
+    assert_eq!(
+        parse_whisper_progress("whisper_print_progress_callback: progress =  45%"),
+        Some(45)
+    );
+    assert_eq!(parse_whisper_progress("progress = 100%"), Some(100));
+    assert_eq!(
+        parse_whisper_progress("whisper_init_from_file: loading model"),
+        None
+    );
+}
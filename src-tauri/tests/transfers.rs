@@ -0,0 +1,91 @@
+use bunchatools_lib::testing::{
+    add_usage, month_total, over_cap, transfer_stats, CapAction, SavedTransfers, TransferCap,
+    TransferRange, Usage,
+};
+use chrono::NaiveDate;
+
+fn day(d: &str) -> NaiveDate {
+    d.parse().unwrap()
+}
+
+fn down(bytes: u64) -> Usage {
+    Usage {
+        downloaded: bytes,
+        uploaded: 0,
+    }
+}
+
+#[test]
+fn usage_adds_up_per_tool_and_day_over_a_range() {
+    let mut saved = SavedTransfers::default();
+    add_usage(&mut saved, day("2026-09-30"), "git-downloader", down(500));
+    add_usage(
+        &mut saved,
+        day("2026-10-10"),
+        "youtube-downloader",
+        down(3000),
+    );
+    add_usage(&mut saved, day("2026-10-16"), "git-downloader", down(700));
+    add_usage(&mut saved, day("2026-10-16"), "git-downloader", down(100));
+    let upload = Usage {
+        downloaded: 0,
+        uploaded: 40,
+    };
+    add_usage(&mut saved, day("2026-10-16"), "clipboard-share", upload);
+
+    let today = day("2026-10-16");
+    let stats = transfer_stats(&saved, TransferRange::Today, today);
+    assert_eq!(
+        stats.total,
+        Usage {
+            downloaded: 800,
+            uploaded: 40
+        }
+    );
+    assert_eq!(stats.tools[0].tool, "git-downloader");
+    assert_eq!(stats.days.len(), 1);
+
+    let month = transfer_stats(&saved, TransferRange::Month, today);
+    assert_eq!(month.from, day("2026-10-01"));
+    assert_eq!(month.total.downloaded, 3800);
+    assert_eq!(month.tools[0].tool, "youtube-downloader");
+    assert_eq!(month.month_total, 3840);
+
+    let week = transfer_stats(&saved, TransferRange::Week, today);
+    assert_eq!(week.from, day("2026-10-10"));
+    assert_eq!(week.days.len(), 2);
+    assert_eq!(
+        transfer_stats(&saved, TransferRange::Year, today)
+            .total
+            .downloaded,
+        4300
+    );
+
+    // Days over a year old are forgotten
+    add_usage(&mut saved, day("2027-12-01"), "git-downloader", down(1));
+    assert_eq!(saved.days.len(), 1);
+}
+
+#[test]
+fn the_cap_counts_this_month_only() {
+    let mut saved = SavedTransfers::default();
+    add_usage(
+        &mut saved,
+        day("2026-09-29"),
+        "youtube-downloader",
+        down(10_000),
+    );
+    add_usage(&mut saved, day("2026-10-02"), "git-downloader", down(900));
+    let today = day("2026-10-16");
+    assert_eq!(month_total(&saved, today), 900);
+    assert!(!over_cap(&saved, today));
+
+    saved.cap = Some(TransferCap {
+        monthly_bytes: 1000,
+        action: CapAction::Pause,
+    });
+    assert!(!over_cap(&saved, today));
+    add_usage(&mut saved, today, "generate-subtitles", down(200));
+    assert!(over_cap(&saved, today));
+    assert!(!over_cap(&saved, day("2026-11-01")));
+}
@@ -0,0 +1,64 @@
+use std::time::{Duration, Instant};
+
+use bunchatools_lib::testing::{
+    add_cached_translation, lookup_cached_translation, CachedTranslation, TokenBucket,
+};
+
+fn cached(text: &str, target: &str, translation: &str) -> CachedTranslation {
+    CachedTranslation {
+        text: text.to_string(),
+        source: "en".to_string(),
+        target: target.to_string(),
+        translation: translation.to_string(),
+    }
+}
+
+#[test]
+fn least_recently_used_translations_go_first() {
+    let mut entries = Vec::new();
+    add_cached_translation(&mut entries, cached("Save", "de", "Speichern"), 2);
+    add_cached_translation(&mut entries, cached("Save", "fr", "Enregistrer"), 2);
+    // Each language pair is its own entry
+    assert_eq!(
+        lookup_cached_translation(&mut entries, "Save", "en", "de").as_deref(),
+        Some("Speichern")
+    );
+    assert_eq!(
+        lookup_cached_translation(&mut entries, "Save", "de", "en"),
+        None
+    );
+
+    // "Save" into French was used longest ago, so it makes room
+    add_cached_translation(&mut entries, cached("Open", "de", "Öffnen"), 2);
+    assert_eq!(entries.len(), 2);
+    assert_eq!(
+        lookup_cached_translation(&mut entries, "Save", "en", "fr"),
+        None
+    );
+    assert!(lookup_cached_translation(&mut entries, "Save", "en", "de").is_some());
+
+    // Storing again replaces instead of duplicating
+    add_cached_translation(&mut entries, cached("Open", "de", "Oeffnen"), 2);
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].translation, "Oeffnen");
+}
+
+#[test]
+fn bucket_allows_bursts_then_paces() {
+    let start = Instant::now();
+    let mut bucket = TokenBucket::new(3.0, 1.0, start);
+    for _ in 0..3 {
+        assert!(bucket.take(start).is_ok());
+    }
+    let wait = bucket.take(start).unwrap_err();
+    assert!(wait > Duration::from_millis(990) && wait <= Duration::from_secs(1));
+
+    assert!(bucket.take(start + Duration::from_millis(500)).is_err());
+    assert!(bucket.take(start + Duration::from_secs(1)).is_ok());
+    // A long pause refills only up to the burst size
+    let later = start + Duration::from_secs(60);
+    for _ in 0..3 {
+        assert!(bucket.take(later).is_ok());
+    }
+    assert!(bucket.take(later).is_err());
+}
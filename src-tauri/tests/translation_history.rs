@@ -0,0 +1,57 @@
+use bunchatools_lib::testing::{add_translation, search_translations, TranslationHistoryEntry};
+
+fn entry(source: &str, translated: &str, target: &str, timestamp: u64) -> TranslationHistoryEntry {
+    TranslationHistoryEntry {
+        id: 0,
+        source_text: source.to_string(),
+        translated_text: translated.to_string(),
+        source_language: "English".to_string(),
+        target_language: target.to_string(),
+        timestamp,
+    }
+}
+
+#[test]
+fn repeated_translations_move_to_the_top() {
+    let mut history = Vec::new();
+    add_translation(
+        &mut history,
+        entry("Good morning", "Guten Morgen", "German", 1),
+    );
+    add_translation(&mut history, entry("Thank you", "Danke", "German", 2));
+    add_translation(&mut history, entry("Good morning", "Bonjour", "French", 3));
+    assert_eq!(history.len(), 3);
+
+    add_translation(
+        &mut history,
+        entry("Good morning", "Guten Morgen", "German", 4),
+    );
+    assert_eq!(history.len(), 3);
+    assert_eq!(history[0].timestamp, 4);
+    assert_eq!(history[0].id, 4);
+    assert_eq!(history[1].translated_text, "Bonjour");
+    assert_eq!(history[2].translated_text, "Danke");
+}
+
+#[test]
+fn search_matches_every_word_in_either_text() {
+    let mut history = Vec::new();
+    add_translation(
+        &mut history,
+        entry("Good morning", "Guten Morgen", "German", 1),
+    );
+    add_translation(&mut history, entry("Good night", "Gute Nacht", "German", 2));
+    add_translation(&mut history, entry("Thank you", "Merci", "French", 3));
+
+    let found = |query: &str| -> Vec<String> {
+        search_translations(&history, query)
+            .into_iter()
+            .map(|e| e.source_text.clone())
+            .collect()
+    };
+    assert_eq!(found("good"), ["Good night", "Good morning"]);
+    assert_eq!(found("GUTE nacht"), ["Good night"]);
+    assert_eq!(found("merci"), ["Thank you"]);
+    assert_eq!(found("good merci"), Vec::<String>::new());
+    assert_eq!(found("  ").len(), 3);
+}
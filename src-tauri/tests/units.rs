@@ -0,0 +1,53 @@
+use bunchatools_lib::testing::{convert_unit, parse_unit_query, UnitCategory};
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9 * b.abs().max(1.0)
+}
+
+#[test]
+fn units_convert_within_their_category() {
+    let miles = convert_unit(5.0, "mi", "km").unwrap();
+    assert!(close(miles.result, 8.04672));
+    assert_eq!(miles.from, "miles");
+    assert_eq!(miles.category, UnitCategory::Length);
+
+    assert!(close(convert_unit(-40.0, "°F", "C").unwrap().result, -40.0));
+    assert!(close(
+        convert_unit(100.0, "celsius", "kelvin").unwrap().result,
+        373.15
+    ));
+    assert!(close(
+        convert_unit(1.0, "sq ft", "square inches").unwrap().result,
+        144.0
+    ));
+    assert!(close(
+        convert_unit(1.0, "MiB", "KiB").unwrap().result,
+        1024.0
+    ));
+    // Case picks bits or bytes, lower case means bytes
+    assert!(close(convert_unit(8.0, "Mb", "MB").unwrap().result, 1.0));
+    assert!(close(convert_unit(1.0, "gb", "mb").unwrap().result, 1000.0));
+    assert!(close(convert_unit(1.0, "kWh", "MJ").unwrap().result, 3.6));
+
+    assert!(convert_unit(1.0, "kg", "m").is_err());
+    assert!(convert_unit(1.0, "parsecs-ish", "m").is_err());
+}
+
+#[test]
+fn queries_split_on_the_separator_between_two_units() {
+    let parse = parse_unit_query;
+    assert_eq!(parse("5 mi to km"), Some((5.0, "mi".into(), "km".into())));
+    assert_eq!(parse("5 in to cm"), Some((5.0, "in".into(), "cm".into())));
+    assert_eq!(parse("2 in in mm"), Some((2.0, "in".into(), "mm".into())));
+    assert_eq!(
+        parse("3,5 sq ft in m2"),
+        Some((3.5, "sq ft".into(), "m2".into()))
+    );
+    assert_eq!(
+        parse("1,000.5 m as km"),
+        Some((1000.5, "m".into(), "km".into()))
+    );
+    assert_eq!(parse("-40f to c"), Some((-40.0, "f".into(), "c".into())));
+    assert_eq!(parse("5 mi"), None);
+    assert_eq!(parse("miles to km"), None);
+}
@@ -0,0 +1,22 @@
+use bunchatools_lib::testing::clean_utterance;
+
+#[test]
+fn utterances_become_launcher_queries() {
+    assert_eq!(
+        clean_utterance(" Convert video to GIF. "),
+        "Convert video to GIF"
+    );
+    assert_eq!(
+        clean_utterance("[BLANK_AUDIO] translate  hello\n to German! (keyboard clicking)"),
+        "translate hello to German"
+    );
+    assert_eq!(
+        clean_utterance("\"What's 5 times 12?\""),
+        "What's 5 times 12"
+    );
+    assert_eq!(
+        clean_utterance("*cough* 100 usd in euro..."),
+        "100 usd in euro"
+    );
+    assert_eq!(clean_utterance("(silence) [Music]"), "");
+}
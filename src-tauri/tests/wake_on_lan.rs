@@ -0,0 +1,25 @@
+use bunchatools_lib::testing::{magic_packet, parse_mac};
+
+const MAC: [u8; 6] = [0x00, 0x1A, 0x2B, 0x3C, 0x4D, 0x5E];
+
+#[test]
+fn mac_address_notations() {
+    for mac in [
+        "00:1A:2B:3C:4D:5E",
+        "00-1a-2b-3c-4d-5e",
+        "001a.2b3c.4d5e",
+        "001A2B3C4D5E",
+    ] {
+        assert_eq!(parse_mac(mac), Ok(MAC), "{}", mac);
+    }
+    assert!(parse_mac("00:1A:2B:3C:4D").is_err());
+    assert!(parse_mac("00:1A:2B:3C:4D:5G").is_err());
+}
+
+#[test]
+fn magic_packet_layout() {
+    let packet = magic_packet(&MAC);
+    assert_eq!(packet.len(), 102);
+    assert_eq!(packet[..6], [0xFF; 6]);
+    assert!(packet[6..].chunks(6).all(|chunk| chunk == MAC));
+}
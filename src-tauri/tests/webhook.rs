@@ -0,0 +1,119 @@
+use bunchatools_lib::testing::{
+    check_webhook_freshness, parse_http_head, sign_webhook, verify_webhook_signature,
+    RecentSignatures, WebhookAction,
+};
+
+const SECRET: &str = "correct horse battery staple";
+const STAMP: &str = "1700000000";
+
+#[test]
+fn signatures() {
+    let body = br#"{"action":"notify","message":"Build passed"}"#;
+    let signature = sign_webhook(SECRET, STAMP, body);
+    // What `openssl dgst -sha256 -hmac` prints, with the GitHub-style prefix
+    assert!(signature.starts_with("sha256="));
+    assert_eq!(signature.len(), "sha256=".len() + 64);
+
+    assert!(verify_webhook_signature(SECRET, STAMP, body, &signature));
+    assert!(verify_webhook_signature(
+        SECRET,
+        STAMP,
+        body,
+        &signature.to_uppercase()[7..]
+    ));
+    assert!(!verify_webhook_signature(
+        "another secret",
+        STAMP,
+        body,
+        &signature
+    ));
+    assert!(!verify_webhook_signature(
+        SECRET,
+        STAMP,
+        br#"{"action":"notify","message":"Build failed"}"#,
+        &signature
+    ));
+    // The timestamp is signed too, so a captured request can't be restamped
+    assert!(!verify_webhook_signature(
+        SECRET,
+        "1700000600",
+        body,
+        &signature
+    ));
+    assert!(!verify_webhook_signature(SECRET, STAMP, body, "sha256=zz"));
+    assert!(!verify_webhook_signature(SECRET, STAMP, body, "sha256=é1"));
+    assert!(!verify_webhook_signature(SECRET, STAMP, body, ""));
+}
+
+#[test]
+fn stale_and_repeated_requests_are_refused() {
+    let body = br#"{"action":"notify","message":"Build passed"}"#;
+    let signature = sign_webhook(SECRET, STAMP, body);
+    let stamped_at: i64 = STAMP.parse().unwrap();
+    let mut recent = RecentSignatures::default();
+
+    assert!(
+        check_webhook_freshness(&mut recent, &signature, stamped_at, stamped_at + 600).is_err()
+    );
+    assert!(
+        check_webhook_freshness(&mut recent, &signature, stamped_at, stamped_at - 600).is_err()
+    );
+    assert!(check_webhook_freshness(&mut recent, &signature, stamped_at, stamped_at + 5).is_ok());
+    assert!(check_webhook_freshness(&mut recent, &signature, stamped_at, stamped_at + 10).is_err());
+    // Changing the case of the hex doesn't make it a new signature
+    let shouted = signature.to_uppercase();
+    assert!(check_webhook_freshness(&mut recent, &shouted, stamped_at, stamped_at + 10).is_err());
+
+    let later = sign_webhook(SECRET, "1700000030", body);
+    assert!(check_webhook_freshness(&mut recent, &later, stamped_at + 30, stamped_at + 31).is_ok());
+}
+
+#[test]
+fn request_heads() {
+    let head = parse_http_head(
+        "POST /hooks/ci HTTP/1.1\r\nHost: desktop:47822\r\n\
+         X-BunchaTools-Signature: sha256=abcd\r\nContent-Length: 42",
+    )
+    .unwrap();
+    assert_eq!(head.method, "POST");
+    assert_eq!(head.path, "/hooks/ci");
+    assert_eq!(
+        head.headers
+            .get("x-bunchatools-signature")
+            .map(String::as_str),
+        Some("sha256=abcd")
+    );
+    assert_eq!(
+        head.headers.get("content-length").map(String::as_str),
+        Some("42")
+    );
+
+    assert!(parse_http_head("POST /").is_none());
+    assert!(parse_http_head("SSH-2.0-OpenSSH_9.6 junk here").is_none());
+}
+
+#[test]
+fn actions() {
+    let action: WebhookAction =
+        serde_json::from_str(r#"{"action":"notify","message":"Deployed"}"#).unwrap();
+    assert!(matches!(
+        action,
+        WebhookAction::Notify { title: None, ref message } if message == "Deployed"
+    ));
+
+    let action: WebhookAction = serde_json::from_str(
+        r#"{"action":"workflow","name":"Evening","actions":[
+            {"type":"kill_port","port":3000},
+            {"type":"start_timer","seconds":600,"label":"Break"}
+        ]}"#,
+    )
+    .unwrap();
+    let WebhookAction::Workflow(workflow) = action else {
+        panic!("expected a workflow");
+    };
+    assert_eq!(workflow.name.as_deref(), Some("Evening"));
+    assert_eq!(workflow.actions.len(), 2);
+
+    assert!(serde_json::from_str::<WebhookAction>(r#"{"action":"format_disk"}"#).is_err());
+    assert!(serde_json::from_str::<WebhookAction>(r#"{"action":"download"}"#).is_err());
+}
@@ -0,0 +1,42 @@
+use bunchatools_lib::testing::{create_workspace_in, persist, remove_orphans};
+
+#[test]
+fn workspaces_are_removed_with_the_job_and_keep_the_finished_file() {
+    let root = tempfile::tempdir().unwrap();
+    let output = tempfile::tempdir().unwrap();
+
+    let workspace = create_workspace_in(root.path(), "job-1/../x").unwrap();
+    let path = workspace.path().to_path_buf();
+    assert_eq!(path.parent(), Some(root.path()));
+    std::fs::write(path.join("partial.zip"), b"half").unwrap();
+    std::fs::write(path.join("out.mp4"), b"video").unwrap();
+
+    let destination = output.path().join("out.mp4");
+    persist(&path.join("out.mp4"), &destination).unwrap();
+    drop(workspace);
+
+    assert_eq!(std::fs::read(&destination).unwrap(), b"video");
+    assert!(!path.exists());
+}
+
+#[test]
+fn orphan_cleanup_spares_this_process_and_counts_what_it_removed() {
+    let root = tempfile::tempdir().unwrap();
+    let own = create_workspace_in(root.path(), "job-2").unwrap();
+    std::fs::write(own.path().join("audio.wav"), [0u8; 64]).unwrap();
+
+    // Left by a crashed session, and something that isn't a workspace at all
+    let crashed = root.path().join("1-5-job-9");
+    std::fs::create_dir_all(crashed.join("fragments")).unwrap();
+    std::fs::write(crashed.join("video.part"), [0u8; 1000]).unwrap();
+    std::fs::write(crashed.join("fragments").join("frag1"), [0u8; 24]).unwrap();
+    std::fs::write(root.path().join("stray.tmp"), [0u8; 10]).unwrap();
+
+    let cleanup = remove_orphans(root.path(), std::process::id());
+    assert_eq!(cleanup.removed, 2);
+    assert_eq!(cleanup.reclaimed_bytes, 1034);
+    assert!(!crashed.exists());
+    assert!(own.path().join("audio.wav").exists());
+
+    assert_eq!(remove_orphans(root.path(), std::process::id()).removed, 0);
+}
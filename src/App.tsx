@@ -3,7 +3,7 @@ import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
 import { getCurrentWindow } from "@tauri-apps/api/window";
 import { readText, writeText } from "@tauri-apps/plugin-clipboard-manager";
-import { open, save } from "@tauri-apps/plugin-dialog";
+import { ask, open } from "@tauri-apps/plugin-dialog";
 import { downloadDir } from "@tauri-apps/api/path";
 import { useWindowAutoSize } from "./hooks";
 import {
@@ -20,6 +20,41 @@ import {
   Palette,
   GitBranch,
   Youtube,
+  Sigma,
+  Activity,
+  BookOpen,
+  Type,
+  Timer,
+  Wifi,
+  WifiOff,
+  Power,
+  Coffee,
+  MousePointerClick,
+  Lock,
+  Moon,
+  RotateCcw,
+  PowerOff,
+  MonitorCheck,
+  HeartPulse,
+  TriangleAlert,
+  ScreenShare,
+  ScrollText,
+  CircleStop,
+  ScanText,
+  Clock,
+  CalendarClock,
+  Globe,
+  Sunrise,
+  SunMoon,
+  TextQuote,
+  History,
+  PenLine,
+  ListChecks,
+  Radar,
+  FileText,
+  Captions,
+  Book,
+  Mic,
 } from "lucide-react";
 import QRCodeLib from "qrcode";
 
@@ -30,8 +65,20 @@ import type {
   VideoFileMetadata,
   VideoAdvancedSettings,
   PortProcess,
+  KillProcessError,
   CurrencyResult,
   TranslationResult,
+  LanguageDetection,
+  FileTranslationProgress,
+  TranscriptionProgress,
+  WordDefinition,
+  VoiceCaptureStatus,
+  ExternalWindow,
+  ScreenTranslation,
+  ScannedCode,
+  Country,
+  HolidayCalendar,
+  SunReport,
   QRCodeType,
   QRCodeData,
   ColorFormats,
@@ -44,24 +91,111 @@ import type {
   YouTubeDownloadOptions,
   YouTubeDownloadProgress,
   YouTubeUrlInfo,
+  OpenFilesRequest,
+  FileFilter,
+  ChordStart,
+  QuickActionType,
+  Workflow,
+  ClipboardSuggestion,
+  NumberSummary,
+  ReferenceEntry,
+  UnitResult,
+  TimeConversion,
+  EpochTime,
+  ExpressionResult,
+  TransferRange,
+  TransferStats,
+  ToolTip,
+  PomodoroStatus,
+  NetworkStatus,
+  SystemTheme,
+  WolDevice,
+  KeepAwakeStatus,
+  NightLightStatus,
+  SecretsStatus,
+  AppLockStatus,
+  ShareDevice,
+  DiscoveredDevice,
+  SnippetStore,
+  TranslationHistoryEntry,
+  GlossaryEntry,
+  KeepAwakeMode,
+  AutoClickerStatus,
+  MouseButton,
+  ScheduledShutdown,
+  DiceRoll,
+  RandomHistoryEntry,
+  OverlayTimerConfig,
+  DisplayInfo,
+  ColorHistoryEntry,
+  ContrastResult,
+  GeneratedFile,
+  PaletteFormat,
+  LocalePreview,
+  StartupHealth,
+  HotkeyBinding,
+  RecordingFormat,
+  RecordingStatus,
+  AudioInputDevice,
+  AudioRecordingStatus,
+  AudioLevel,
+  CalcHistory,
+  TimeEntry,
+  Habit,
+  HabitStreak,
+  TimeReport,
+  TimeReportRange,
+  ConversionQueueState,
 } from "./types";
 
 // Import constants
-import { VIDEO_FILE_FILTERS, VIDEO_QUALITY_PRESETS, DEFAULT_QR_DATA } from "./constants";
+import { VIDEO_FILE_FILTERS, VIDEO_QUALITY_PRESETS, DEFAULT_QR_DATA, TRANSLATABLE_FILE_FILTERS, LANGUAGE_NAMES, MEDIA_FILE_FILTERS } from "./constants";
 
 // Import utils
 import {
-  convertHexToFormats,
   generateQRContent,
+  qrPayloadFor,
   parseUnitQuery,
   parsePartialUnitQuery,
   parseCurrencyQuery,
   parsePartialCurrencyQuery,
   evaluateExpression,
   parseColorQuery,
+  parseReferenceQuery,
+  looksLikeUnitQuery,
+  looksLikeTimeQuery,
+  looksLikeExpression,
+  parseEpochQuery,
+  formatUnitResult,
   rgbToHex,
   parseGitHubUrl,
   parseYouTubeUrl,
+  formatNetworkSummary,
+  formatDiscoveredDevices,
+  formatDetectionWarning,
+  formatDefinition,
+  formatStartupHealth,
+  formatLeftoverCleanup,
+  formatTransferStats,
+  formatFileSize,
+  parseByteSize,
+  formatDuration,
+  formatHoursMinutes,
+  formatHabitStreak,
+  describeScannedCode,
+  formatCountry,
+  formatHolidays,
+  localeCountry,
+  formatSunTimes,
+  formatTranslationHistory,
+  timeEntryDuration,
+  formatTimeReport,
+  formatLevelMeter,
+  formatCurrencyAmount,
+  currencyStaleNote,
+  currencyChangeNote,
+  formatCurrencyResults,
+  findExternalWindow,
 } from "./utils";
 
 // Import components
@@ -82,8 +216,11 @@ import type { CommandStatus } from "./components/CommandPalette";
 function App() {
   const [query, setQuery] = useState("");
   const [filteredTools, setFilteredTools] = useState<Tool[]>([]);
+  const [clipboardSuggestion, setClipboardSuggestion] = useState<ClipboardSuggestion | null>(null);
   const [selectedIndex, setSelectedIndex] = useState(0);
   const [status, setStatus] = useState<string | null>(null);
+  const [toolTip, setToolTip] = useState<ToolTip | null>(null);
+  const [tipsVersion, setTipsVersion] = useState(0); // bumped when uses or dismissals change the tips
   const [showSettings, setShowSettings] = useState(false);
   const [settings, setSettings] = useState<Settings>({
     hotkey_modifiers: ["Alt"],
@@ -108,9 +245,25 @@ function App() {
   const toolItemRefs = useRef<(HTMLDivElement | null)[]>([]);
   const gitDownloadingRef = useRef(false);
 
+  // Destinations come from the backend's dialogs, which refuse system folders and allow later
+  // writes to the picked folder (see path_policy.rs); null when cancelled or refused
+  const pickDestination = async (command: string, args: Record<string, unknown>) => {
+    try {
+      return await invoke<string | null>(command, args);
+    } catch (e) {
+      setStatus(String(e));
+      setTimeout(() => setStatus(null), 3000);
+      return null;
+    }
+  };
+  const pickSavePath = (defaultName: string, filters: FileFilter[]) =>
+    pickDestination("pick_save_path", { defaultName, filters });
+  const pickOutputDirectory = (title: string) => pickDestination("pick_output_directory", { title });
+
   // Video Converter state
   const [showVideoConverter, setShowVideoConverter] = useState(false);
   const [videoFile, setVideoFile] = useState<VideoFileMetadata | null>(null);
+  const [videoBatch, setVideoBatch] = useState<string[]>([]); // every file, when several were picked
   const [videoFormat, setVideoFormat] = useState<string>("mp4");
   const [videoQuality, setVideoQuality] = useState<string>("high");
   const [videoAdvancedSettings, setVideoAdvancedSettings] = useState<VideoAdvancedSettings>({
@@ -141,6 +294,7 @@ function App() {
   const [translationInput, setTranslationInput] = useState("");
   const [translationOutput, setTranslationOutput] = useState("");
   const [detectedLanguage, setDetectedLanguage] = useState("Detecting...");
+  const [detectionWarning, setDetectionWarning] = useState<string | null>(null);
   const [targetLanguage, setTargetLanguage] = useState("en");
   const [isTranslating, setIsTranslating] = useState(false);
   const [translationError, setTranslationError] = useState<string | null>(null);
@@ -148,10 +302,12 @@ function App() {
 
   // Quick result state (calculator, unit conversion, currency)
   const [quickResult, setQuickResult] = useState<QuickResult | null>(null);
+  const [calcHistory, setCalcHistory] = useState<CalcHistory | null>(null);
 
   // Color Picker Details state
   const [showColorPicker, setShowColorPicker] = useState(false);
   const [pickedColor, setPickedColor] = useState<ColorFormats | null>(null);
+  const [colorHistory, setColorHistory] = useState<ColorHistoryEntry[]>([]);
   const [copiedFormat, setCopiedFormat] = useState<string | null>(null);
 
   // QR Code Generator state
@@ -162,6 +318,8 @@ function App() {
   const [qrBackgroundColor, setQRBackgroundColor] = useState("#FFFFFF");
   const [showQRCustomization, setShowQRCustomization] = useState(false);
   const [qrImageDataUrl, setQRImageDataUrl] = useState<string>("");
+  const [qrContent, setQRContent] = useState("");
+  const [qrError, setQRError] = useState<string | null>(null);
   const [qrCopied, setQRCopied] = useState(false);
   const [selectedExportFormat, setSelectedExportFormat] = useState<"PNG" | "SVG" | "PDF">("PNG");
 
@@ -219,6 +377,23 @@ function App() {
   const [lastResultPath, setLastResultPath] = useState<string | null>(null);
   const [timerRemaining, setTimerRemaining] = useState<number | null>(null);
   const [timerLabel, setTimerLabel] = useState<string>("");
+  const [pomodoro, setPomodoro] = useState<PomodoroStatus | null>(null);
+  const [network, setNetwork] = useState<NetworkStatus | null>(null);
+  const [startupHealth, setStartupHealth] = useState<StartupHealth | null>(null);
+  const [activeHotkey, setActiveHotkey] = useState<HotkeyBinding | null>(null);
+  const [systemTheme, setSystemTheme] = useState<SystemTheme>("dark");
+  const [wolDevices, setWolDevices] = useState<WolDevice[]>([]);
+  const [keepAwake, setKeepAwake] = useState<KeepAwakeStatus | null>(null);
+  const [nightLight, setNightLight] = useState<NightLightStatus | null>(null);
+  const [autoClicker, setAutoClicker] = useState<AutoClickerStatus | null>(null);
+  const [recording, setRecording] = useState<RecordingStatus | null>(null);
+  const [audioRecording, setAudioRecording] = useState<AudioRecordingStatus | null>(null);
+  const [audioLevel, setAudioLevel] = useState<AudioLevel | null>(null);
+  const [timeEntry, setTimeEntry] = useState<TimeEntry | null>(null);
+  const [habitStreaks, setHabitStreaks] = useState<HabitStreak[]>([]);
+  const [weekReport, setWeekReport] = useState<TimeReport | null>(null);
+  const lastTimeEntry = weekReport?.entries[weekReport.entries.length - 1] ?? null;
+  const [scheduledShutdown, setScheduledShutdown] = useState<ScheduledShutdown | null>(null);
 
   // Define tools
   const tools: Tool[] = [
@@ -230,9 +405,9 @@ function App() {
       keywords: ["color", "picker", "eyedropper", "hex", "rgb", "colour"],
       action: async () => {
         try {
-          const color = await invoke<string>("pick_color");
-          const formats = convertHexToFormats(color);
+          const formats = await invoke<ColorFormats>("pick_color");
           setPickedColor(formats);
+          setColorHistory(await invoke<ColorHistoryEntry[]>("get_color_history").catch(() => [])); // empty while the app is locked
           setCopiedFormat(null);
           setShowColorPicker(true);
           setQuery("");
@@ -245,6 +420,52 @@ function App() {
         }
       },
     },
+    {
+      id: "palette-picker",
+      name: "Pick Palette",
+      description: "Click several colors in a row, then Escape or right-click to finish",
+      icon: Palette,
+      keywords: ["palette", "colors", "multi", "pick", "eyedropper", "swatches", "colour"],
+      action: async () => {
+        try {
+          const colors = await invoke<string[]>("pick_colors_continuous");
+          setQuery("");
+          if (colors.length === 0) return;
+          await writeText(colors.join("\n"));
+          setPickedColor(await invoke<ColorFormats>("get_color_formats", { input: colors[colors.length - 1] }));
+          setColorHistory(await invoke<ColorHistoryEntry[]>("get_color_history").catch(() => [])); // empty while the app is locked
+          setCopiedFormat(null);
+          setShowColorPicker(true);
+          await invoke("set_auto_hide", { enabled: true });
+          await invoke("show_window");
+          setStatus(`Copied ${colors.length} ${colors.length === 1 ? "color" : "colors"}`);
+          setTimeout(() => setStatus(null), 2000);
+        } catch (e) {
+          console.error("Palette picker error:", e);
+        }
+      },
+    },
+    {
+      id: "contrast-checker",
+      name: "Contrast Checker",
+      description: "Pick a text color, then its background, to check WCAG contrast",
+      icon: Palette,
+      keywords: ["contrast", "wcag", "accessibility", "a11y", "aa", "aaa", "color", "readability"],
+      action: async () => {
+        try {
+          const result = await invoke<ContrastResult>("pick_contrast");
+          setQuery("");
+          await invoke("set_auto_hide", { enabled: true });
+          await invoke("show_window");
+          setStatus(formatContrast(result));
+          setTimeout(() => setStatus(null), 6000);
+        } catch (e) {
+          if (e !== "Cancelled") {
+            console.error("Contrast checker error:", e);
+          }
+        }
+      },
+    },
     {
       id: "video-converter",
       name: "Video Converter",
@@ -255,6 +476,7 @@ function App() {
         await invoke("set_auto_hide", { enabled: true });
         setShowVideoConverter(true);
         setVideoFile(null);
+        setVideoBatch([]);
         setVideoFormat("mp4");
         setVideoQuality("high");
         setVideoAdvancedSettings({
@@ -304,6 +526,45 @@ function App() {
         setShowTranslation(true);
       },
     },
+    {
+      id: "translation-history",
+      name: "Translation History",
+      description: 'Recent translations; "translations <words>" in command mode finds and copies one',
+      icon: History,
+      keywords: ["translation", "translations", "history", "recent", "past", "phrase", "reuse"],
+      action: async () => {
+        setQuery("");
+        try {
+          const entries = await invoke<TranslationHistoryEntry[]>("get_translation_history", {});
+          setStatus(entries.length > 0 ? formatTranslationHistory(entries) : "No translations yet");
+        } catch (e) {
+          setStatus(String(e));
+        }
+        setTimeout(() => setStatus(null), 4000);
+      },
+    },
+    {
+      id: "screen-translation",
+      name: "Translate Screen Text",
+      description: "Select part of the screen and translate the text in it",
+      icon: ScanText,
+      keywords: ["translate", "screen", "ocr", "image", "text", "game", "video", "subtitle", "recognize"],
+      action: async () => {
+        setQuery("");
+        await translateScreenRegion();
+      },
+    },
+    {
+      id: "qr-scanner",
+      name: "Scan QR Code",
+      description: "Select a QR code on screen and copy what it contains (links, Wi-Fi passwords)",
+      icon: QrCode,
+      keywords: ["qr", "qrcode", "scan", "read", "decode", "screen", "wifi", "password", "link"],
+      action: async () => {
+        setQuery("");
+        await scanQrCode();
+      },
+    },
     {
       id: "qr-generator",
       name: "QR Code Generator",
@@ -319,6 +580,7 @@ function App() {
         setQRBackgroundColor("#FFFFFF");
         setShowQRCustomization(false);
         setQRImageDataUrl("");
+        setQRError(null);
         setQRCopied(false);
         setSelectedExportFormat("PNG");
         setQuery("");
@@ -393,268 +655,2481 @@ function App() {
       },
     },
     {
-      id: "settings",
-      name: "Settings",
-      description: "Configure BunchaTools preferences",
-      icon: SettingsIcon,
-      keywords: ["settings", "preferences", "config", "options", "hotkey", "startup"],
-      isSettings: true,
+      id: "clipboard-sum",
+      name: "Sum Clipboard Numbers",
+      description: "Total, average, min and max of the numbers you copied",
+      icon: Sigma,
+      keywords: ["sum", "total", "add", "average", "avg", "min", "max", "numbers", "clipboard", "math"],
+      action: async () => {
+        setQuery("");
+        try {
+          const summary = await sumClipboardNumbers();
+          await writeText(String(summary.sum));
+          setStatus(`${formatNumberSummary(summary)} (sum copied)`);
+          setTimeout(() => setStatus(null), 4000);
+        } catch (e) {
+          setStatus(String(e));
+          setTimeout(() => setStatus(null), 3000);
+        }
+      },
     },
-  ];
-
-  // Load settings and command history on mount, and mark app as ready
-  useEffect(() => {
-    const initialize = async () => {
-      try {
-        const s = await invoke<Settings>("get_settings");
-        setSettings(s);
-        // Mark as initialized after a tick to avoid triggering auto-save
-        setTimeout(() => {
-          settingsInitialized.current = true;
-        }, 0);
-
-        // Load command history
-        const history = await invoke<string[]>("load_command_history");
-        setCommandHistory(history);
-        historyLoadedRef.current = true;
-
-        // Signal to backend that the app is fully loaded and ready
-        await invoke("mark_app_ready");
-      } catch (e) {
-        console.error("Failed to initialize:", e);
-      }
-    };
-    initialize();
-  }, []);
-
-  // Auto-save settings when they change
-  useEffect(() => {
-    if (!settingsInitialized.current) return;
-
-    const timeoutId = setTimeout(async () => {
-      try {
-        await invoke("save_settings", { settings });
-      } catch (e) {
-        console.error("Failed to auto-save settings:", e);
-      }
-    }, 300); // 300ms debounce
-
-    return () => clearTimeout(timeoutId);
-  }, [settings]);
-
-  // Auto-save command history when it changes
-  useEffect(() => {
-    // Don't save until history has been loaded (avoid overwriting with empty array on init)
-    if (!historyLoadedRef.current) return;
-
-    const timeoutId = setTimeout(async () => {
-      try {
-        await invoke("save_command_history", { commands: commandHistory });
-      } catch (e) {
-        console.error("Failed to save command history:", e);
-      }
-    }, 300); // 300ms debounce
-
-    return () => clearTimeout(timeoutId);
-  }, [commandHistory]);
-
-  // Generate QR code when data changes
-  useEffect(() => {
-    if (!showQRGenerator) return;
-
-    const generateQR = async () => {
-      const content = generateQRContent(qrType, qrData);
-      if (!content) {
-        setQRImageDataUrl("");
-        return;
-      }
-
-      try {
-        const dataUrl = await QRCodeLib.toDataURL(content, {
-          color: {
-            dark: qrForegroundColor,
-            light: qrBackgroundColor,
-          },
-          width: 300,
-          margin: 2,
-          errorCorrectionLevel: "M",
-        });
-        setQRImageDataUrl(dataUrl);
-      } catch (err) {
-        console.error("QR generation error:", err);
-        setQRImageDataUrl("");
-      }
-    };
-
-    const timeoutId = setTimeout(generateQR, 100);
-    return () => clearTimeout(timeoutId);
-  }, [showQRGenerator, qrType, qrData, qrForegroundColor, qrBackgroundColor]);
-
-  // Debounced translation when input changes (for manual typing)
-  useEffect(() => {
-    if (!showTranslation || !translationInput.trim()) {
-      return;
-    }
-
-    const timeoutId = setTimeout(async () => {
-      setIsTranslating(true);
-      setTranslationError(null);
-      try {
-        const result = await invoke<TranslationResult>("translate_text", {
-          text: translationInput,
-          targetLang: targetLanguage,
-        });
-        setTranslationOutput(result.translated_text);
-        setDetectedLanguage(result.detected_language);
-      } catch (err) {
-        setTranslationError(String(err));
-      } finally {
-        setIsTranslating(false);
-      }
-    }, 500); // 500ms debounce
-
-    return () => clearTimeout(timeoutId);
-  }, [translationInput, targetLanguage, showTranslation]);
-
-  // Listen for conversion progress events
-  useEffect(() => {
-    const unlisten = listen<number>("conversion-progress", (event) => {
-      setVideoProgress(event.payload);
-    });
-
-    return () => {
-      unlisten.then((fn) => fn());
-    };
-  }, []);
-
-  // Listen for git download progress events
-  useEffect(() => {
-    const unlisten = listen<GitDownloadProgress>("git-download-progress", (event) => {
-      setGitProgress(event.payload);
-      // Also update command status if in command-only mode and downloading
-      if (settings.command_only_mode && event.payload.stage === 'downloading') {
-        setCommandStatus({
-          message: `Downloading... ${event.payload.percent}%`,
-          type: 'progress',
-        });
-      }
-    });
-
-    return () => {
-      unlisten.then((fn) => fn());
-    };
-  }, [settings.command_only_mode]);
-
-  // Listen for YouTube download progress events
-  useEffect(() => {
-    const unlisten = listen<YouTubeDownloadProgress>("youtube-download-progress", (event) => {
-      setYtProgress(event.payload);
-      // Also update command status if in command-only mode and downloading
-      if (settings.command_only_mode && event.payload.stage === 'downloading') {
-        setCommandStatus({
-          message: `Downloading... ${Math.round(event.payload.percent)}%`,
-          type: 'progress',
-        });
-      }
-    });
-
-    return () => {
-      unlisten.then((fn) => fn());
-    };
-  }, [settings.command_only_mode]);
-
-  // Listen for timer tick events
-  useEffect(() => {
-    const unlisten = listen<{ remaining: number; label: string }>("timer-tick", (event) => {
-      const { remaining, label } = event.payload;
-
-      if (label === "cancelled" || remaining === 0) {
-        // Timer completed or cancelled
-        setTimerRemaining(null);
-        setTimerLabel("");
-        if (remaining === 0 && label !== "cancelled") {
-          // Timer completed - show success briefly
-          setCommandStatus({
-            message: `${label} complete!`,
-            type: 'success',
-          });
-          // Auto-dismiss after 3 seconds
-          setTimeout(() => {
-            setCommandStatus({ message: "Type a command...", type: 'idle' });
-          }, 3000);
+    {
+      id: "ascii-banner",
+      name: "ASCII Banner",
+      description: "Turn the copied text into figlet-style ASCII art",
+      icon: Type,
+      keywords: ["banner", "ascii", "art", "figlet", "text", "motd", "readme", "big"],
+      action: async () => {
+        setQuery("");
+        try {
+          const text = await readText();
+          const banner = await invoke<string>("generate_banner", { text: text ?? "" });
+          await writeText(banner);
+          setStatus("Banner copied to clipboard");
+        } catch (e) {
+          setStatus(String(e));
         }
-      } else {
-        setTimerRemaining(remaining);
-        setTimerLabel(label);
-      }
-    });
-
-    return () => {
-      unlisten.then((fn) => fn());
-    };
-  }, []);
-
-  // Keep ref in sync with git download status
-  useEffect(() => {
-    gitDownloadingRef.current = gitProgress.stage === 'fetching' ||
-                                gitProgress.stage === 'downloading' ||
+        setTimeout(() => setStatus(null), 3000);
+      },
+    },
+    {
+      id: "pomodoro",
+      name: pomodoro ? "Stop Focus Session" : "Focus Session",
+      description: pomodoro
+        ? `${pomodoro.phase === "focus" ? "Focusing" : "On a break"}, ${Math.ceil(pomodoro.remaining / 60)} min left`
+        : "Pomodoro timer: focus, then take a break",
+      icon: Timer,
+      keywords: ["pomodoro", "focus", "break", "dnd", "do not disturb", "timer", "concentrate"],
+      action: async () => {
+        setQuery("");
+        try {
+          if (pomodoro) {
+            await invoke("stop_pomodoro");
+            setStatus("Focus session stopped");
+          } else {
+            await invoke("start_pomodoro", {});
+            setStatus("Focus session started");
+          }
+        } catch (e) {
+          setStatus(String(e));
+        }
+        setTimeout(() => setStatus(null), 2000);
+      },
+    },
+    {
+      id: "keep-awake",
+      name: keepAwake ? "Stop Keeping Awake" : "Keep Awake",
+      description: keepAwake
+        ? keepAwake.mode === "jiggle"
+          ? "Stop jiggling the mouse and let the computer sleep again"
+          : "Let the computer sleep again"
+        : "Stop the computer from sleeping or turning the screen off",
+      icon: Coffee,
+      keywords: ["awake", "caffeinate", "caffeine", "sleep", "insomnia", "screen", "display", "presentation", "jiggle", "jiggler"],
+      action: async () => {
+        setQuery("");
+        try {
+          await invoke("set_keep_awake", { enabled: !keepAwake });
+          setStatus(keepAwake ? "Sleep allowed again" : "Keeping the computer awake");
+        } catch (e) {
+          setStatus(String(e));
+        }
+        setTimeout(() => setStatus(null), 2000);
+      },
+    },
+    {
+      id: "snippets",
+      name: "Snippets",
+      description: 'Saved text with placeholders like {uuid}, {date+3d} or {clipboard|upper}; "snip <name>" in command mode',
+      icon: TextQuote,
+      keywords: ["snippet", "snippets", "template", "text", "expand", "placeholder", "boilerplate", "uuid"],
+      action: async () => {
+        setQuery("");
+        try {
+          const store = await invoke<SnippetStore>("get_snippets");
+          setStatus(
+            store.snippets.length > 0
+              ? `Snippets: ${store.snippets.map((s) => (s.encrypted ? `${s.name} (encrypted)` : s.name)).join(", ")}`
+              : 'No snippets yet: "snip add <name> <text>" in command mode'
+          );
+        } catch (e) {
+          setStatus(String(e));
+        }
+        setTimeout(() => setStatus(null), 4000);
+      },
+    },
+    {
+      id: "night-light",
+      name: nightLight?.enabled ? "Turn Off Night Light" : "Night Light",
+      description: nightLight?.scheduled
+        ? "Warmer screen colors; the schedule switches again at the next sunrise or sunset"
+        : "Warmer screen colors for the evening (schedule it for sunset in Settings)",
+      icon: SunMoon,
+      keywords: ["night", "light", "shift", "warm", "color", "temperature", "blue", "redshift", "flux", "eyes"],
+      action: async () => {
+        setQuery("");
+        try {
+          const status = await invoke<NightLightStatus>("set_night_light", { enabled: !nightLight?.enabled });
+          setNightLight(status);
+          setStatus(status.enabled ? "Night light on" : "Night light off");
+        } catch (e) {
+          setStatus(String(e));
+        }
+        setTimeout(() => setStatus(null), 2000);
+      },
+    },
+    {
+      id: "auto-clicker",
+      name: autoClicker ? "Stop Auto-Clicker" : "Auto-Clicker",
+      description: autoClicker
+        ? `${autoClicker.clicks} clicks so far (${autoClicker.stop_hotkey} also stops it)`
+        : "Click every 100 ms wherever the cursor is, until F6 is pressed",
+      icon: MousePointerClick,
+      keywords: ["click", "clicker", "auto", "autoclick", "mouse", "repeat", "macro"],
+      action: async () => {
+        setQuery("");
+        try {
+          if (autoClicker) {
+            const clicks = await invoke<number | null>("stop_auto_clicker");
+            setStatus(`Stopped after ${clicks ?? 0} clicks`);
+          } else {
+            await invoke("hide_window");
+            await invoke("start_auto_clicker", {});
+            return;
+          }
+        } catch (e) {
+          await invoke("show_window");
+          setStatus(String(e));
+        }
+        setTimeout(() => setStatus(null), 2000);
+      },
+    },
+    {
+      id: "calculator-tape",
+      name: "Calculator Tape",
+      description:
+        calcHistory && calcHistory.entries.length > 0
+          ? `${calcHistory.entries.length} calculations${calcHistory.memory !== 0 ? `, M = ${calcHistory.memory}` : ""} - save as text`
+          : "Calculations you copy are kept here (m+, m-, mr, mc for memory)",
+      icon: ScrollText,
+      keywords: ["calculator", "tape", "history", "memory", "m+", "calc", "sum"],
+      action: async () => {
+        setQuery("");
+        try {
+          const saved = await exportCalcTape();
+          if (!saved) return;
+          setStatus("Tape saved");
+        } catch (e) {
+          setStatus(String(e));
+        }
+        setTimeout(() => setStatus(null), 2000);
+      },
+    },
+    {
+      id: "screen-recording",
+      name: recording ? "Stop Recording" : "Record Screen",
+      description: recording
+        ? `Recording for ${formatDuration(recording.elapsed_secs)} - stop and save`
+        : "Record the whole screen to an MP4 in your Videos folder",
+      icon: recording ? CircleStop : ScreenShare,
+      keywords: ["record", "recording", "screen", "capture", "video", "screencast", "gif"],
+      action: async () => {
+        setQuery("");
+        try {
+          if (recording) {
+            const finished = await invoke<RecordingStatus | null>("stop_screen_recording");
+            if (finished) {
+              setStatus(`Saved ${formatDuration(finished.elapsed_secs)} recording to ${finished.path}`);
+            }
+          } else {
+            await invoke("hide_window");
+            await invoke("start_screen_recording", {});
+            return;
+          }
+        } catch (e) {
+          await invoke("show_window");
+          setStatus(String(e));
+        }
+        setTimeout(() => setStatus(null), 3000);
+      },
+    },
+    {
+      id: "audio-recording",
+      name: audioRecording ? "Stop Audio Recording" : "Record Audio",
+      description: audioRecording
+        ? `${formatDuration(audioLevel?.elapsed_secs ?? audioRecording.elapsed_secs)} ${formatLevelMeter(audioLevel?.level ?? 0)} - stop and save`
+        : "Record the microphone to an MP3 in your Music folder",
+      icon: audioRecording ? CircleStop : Mic,
+      keywords: ["record", "recording", "audio", "microphone", "mic", "voice", "memo", "mp3", "sound"],
+      action: async () => {
+        setQuery("");
+        try {
+          if (audioRecording) {
+            const finished = await invoke<AudioRecordingStatus | null>("stop_audio_recording");
+            if (finished) {
+              setStatus(`Saved ${formatDuration(finished.elapsed_secs)} recording to ${finished.path}`);
+            }
+          } else {
+            const started = await invoke<AudioRecordingStatus>("start_audio_recording", {});
+            setStatus(`Recording from ${started.device}`);
+          }
+        } catch (e) {
+          setStatus(String(e));
+        }
+        setTimeout(() => setStatus(null), 3000);
+      },
+    },
+    {
+      id: "screenshot-annotate",
+      name: "Annotate Screenshot",
+      description: "Capture part of the screen and add arrows, boxes, text or blur",
+      icon: PenLine,
+      keywords: ["screenshot", "snip", "annotate", "markup", "sketch", "capture", "arrow", "blur", "redact"],
+      action: async () => {
+        setQuery("");
+        try {
+          await invoke("start_screen_annotation");
+        } catch (e) {
+          await invoke("show_window");
+          if (e !== "Cancelled") {
+            setStatus(String(e));
+            setTimeout(() => setStatus(null), 2000);
+          }
+        }
+      },
+    },
+    {
+      id: "habits",
+      name: "Habits",
+      description:
+        habitStreaks.length > 0
+          ? `${habitStreaks.filter((h) => h.done_today).length}/${habitStreaks.length} done today - ${habitStreaks
+              .filter((h) => !h.done_today)
+              .map((h) => h.name)
+              .join(", ") || "all done"}`
+          : 'Track daily habits: "habit add Water at 9am", then "habit done water"',
+      icon: ListChecks,
+      keywords: ["habit", "habits", "streak", "daily", "routine", "check", "tracker"],
+      action: async () => {
+        setQuery("");
+        setStatus(habitStreaks.length > 0 ? habitStreaks.map(formatHabitStreak).join(" · ") : "No habits yet");
+        setTimeout(() => setStatus(null), 4000);
+      },
+    },
+    {
+      id: "public-holidays",
+      name: "Public Holidays",
+      description: 'Upcoming public holidays here; "holidays <country> [year]" or "country <code, name, +44, EUR>" in command mode',
+      icon: Globe,
+      keywords: ["holiday", "holidays", "public", "bank", "country", "countries", "calling", "code", "iso", "flag"],
+      action: async () => {
+        setQuery("");
+        const country = localeCountry();
+        if (!country) {
+          setStatus('Type "holidays <country>" in command mode');
+        } else {
+          try {
+            setStatus(formatHolidays(await invoke<HolidayCalendar>("get_public_holidays", { country })));
+          } catch (e) {
+            setStatus(String(e));
+          }
+        }
+        setTimeout(() => setStatus(null), 4000);
+      },
+    },
+    {
+      id: "sun-times",
+      name: "Sunrise & Sunset",
+      description: 'Sunrise, sunset and golden hour for the last place used; "sun <place or lat, lon> [date]" in command mode',
+      icon: Sunrise,
+      keywords: ["sun", "sunrise", "sunset", "golden", "hour", "daylight", "dawn", "dusk", "twilight", "photo"],
+      action: async () => {
+        setQuery("");
+        try {
+          setStatus(formatSunTimes(await invoke<SunReport>("get_sun_times", {})));
+        } catch (e) {
+          setStatus(String(e));
+        }
+        setTimeout(() => setStatus(null), 4000);
+      },
+    },
+    {
+      id: "time-tracking",
+      name: timeEntry ? "Stop Tracking" : "Track Time",
+      description: timeEntry
+        ? `Tracking "${timeEntry.label}"${timeEntry.project ? ` (${timeEntry.project})` : ""} since ${new Date(timeEntry.start * 1000).toLocaleTimeString([], { hour: "2-digit", minute: "2-digit" })}`
+        : lastTimeEntry
+          ? `Resume "${lastTimeEntry.label}" (or type "track <label> @project" in command mode)`
+          : "Start a timer for what you're working on",
+      icon: timeEntry ? CircleStop : Clock,
+      keywords: ["time", "track", "tracking", "timesheet", "hours", "work", "project", "billable", "clock"],
+      action: async () => {
+        setQuery("");
+        try {
+          if (timeEntry) {
+            const stopped = await invoke<TimeEntry>("stop_time_entry");
+            setStatus(`Tracked ${formatHoursMinutes(timeEntryDuration(stopped))} on "${stopped.label}"`);
+          } else {
+            const started = await invoke<TimeEntry>("start_time_entry", {
+              label: lastTimeEntry?.label ?? "Work",
+              project: lastTimeEntry?.project ?? null,
+            });
+            setStatus(`Tracking "${started.label}"`);
+          }
+        } catch (e) {
+          setStatus(String(e));
+        }
+        setTimeout(() => setStatus(null), 2000);
+      },
+    },
+    {
+      id: "time-report",
+      name: "Time Report",
+      description: weekReport
+        ? `${formatTimeReport(weekReport)} - save as CSV`
+        : "This week's tracked time by project - save as CSV",
+      icon: CalendarClock,
+      keywords: ["time", "report", "timesheet", "hours", "week", "csv", "export", "tracking"],
+      action: async () => {
+        setQuery("");
+        try {
+          const saved = await exportTimeEntries("week");
+          if (!saved) return;
+          setStatus("Time entries saved");
+        } catch (e) {
+          setStatus(String(e));
+        }
+        setTimeout(() => setStatus(null), 2000);
+      },
+    },
+    {
+      id: "lock-screen",
+      name: "Lock Screen",
+      description: "Lock the computer",
+      icon: Lock,
+      keywords: ["lock", "screen", "away", "power"],
+      action: () => runPowerAction("lock_screen"),
+    },
+    {
+      id: "sleep-system",
+      name: "Sleep",
+      description: "Put the computer to sleep",
+      icon: Moon,
+      keywords: ["sleep", "suspend", "standby", "power"],
+      action: () => runPowerAction("sleep_system"),
+    },
+    {
+      id: "restart-system",
+      name: "Restart",
+      description: "Restart the computer",
+      icon: RotateCcw,
+      keywords: ["restart", "reboot", "power"],
+      action: async () => {
+        if (await ask("Restart the computer now? Unsaved work in other apps may be lost.", { title: "Restart", kind: "warning" })) {
+          await runPowerAction("restart_system");
+        }
+      },
+    },
+    {
+      id: "shutdown-system",
+      name: scheduledShutdown ? "Cancel Shutdown" : "Shut Down",
+      description: scheduledShutdown
+        ? "Keep the computer running"
+        : "Shut down in a minute, with a window to cancel",
+      icon: PowerOff,
+      keywords: ["shutdown", "shut down", "power off", "turn off", "power"],
+      action: () =>
+        scheduledShutdown ? runPowerAction("cancel_shutdown") : runPowerAction("shutdown_system", { delay: 60 }),
+    },
+    {
+      id: "network-status",
+      name: "Network Status",
+      description: network
+        ? network.online
+          ? `Online via ${network.gateway_interface ?? "unknown"}${network.vpn_interfaces.length > 0 ? ", VPN connected" : ""} - copy details`
+          : "Offline - copy details"
+        : "Copy interfaces, gateway, DNS servers, VPN and proxy",
+      icon: network && !network.online ? WifiOff : Wifi,
+      keywords: ["network", "ip", "address", "gateway", "dns", "vpn", "proxy", "mac", "interface", "wifi", "online"],
+      action: async () => {
+        setQuery("");
+        try {
+          const status = await invoke<NetworkStatus>("get_network_status");
+          setNetwork(status);
+          await writeText(formatNetworkSummary(status));
+          setStatus("Network details copied");
+        } catch (e) {
+          setStatus(String(e));
+        }
+        setTimeout(() => setStatus(null), 2000);
+      },
+    },
+    {
+      id: "lan-devices",
+      name: "LAN Devices",
+      description: "Find devices on the local network with their names, addresses and MAC vendors - copy the list",
+      icon: Radar,
+      keywords: ["lan", "devices", "scan", "discover", "network", "mdns", "bonjour", "upnp", "arp", "mac", "vendor", "ip"],
+      action: async () => {
+        setQuery("");
+        setStatus("Looking for devices...");
+        try {
+          const devices = await invoke<DiscoveredDevice[]>("discover_network_devices");
+          await writeText(formatDiscoveredDevices(devices));
+          setStatus(`${devices.length} devices found - list copied`);
+        } catch (e) {
+          setStatus(String(e));
+        }
+        setTimeout(() => setStatus(null), 3000);
+      },
+    },
+    {
+      id: "translate-file",
+      name: "Translate File",
+      description: `Translate a text, subtitle (.srt, .vtt) or Markdown file into ${LANGUAGE_NAMES[settings.quick_translation_target_language] ?? settings.quick_translation_target_language} - saved next to it`,
+      icon: FileText,
+      keywords: ["translate", "file", "subtitles", "srt", "vtt", "markdown", "md", "txt", "document"],
+      action: async () => {
+        setQuery("");
+        isDialogOpenRef.current = true;
+        await invoke("set_auto_hide", { enabled: false });
+        const file = await open({ filters: TRANSLATABLE_FILE_FILTERS, multiple: false });
+        await invoke("set_auto_hide", { enabled: true });
+        isDialogOpenRef.current = false;
+        if (!file) {
+          return;
+        }
+        const unlisten = await listen<FileTranslationProgress>("file-translation-progress", (event) => {
+          setStatus(`Translating... ${event.payload.done}/${event.payload.total}`);
+        });
+        try {
+          const output = await invoke<string>("translate_file", {
+            path: file,
+            targetLang: settings.quick_translation_target_language,
+          });
+          setStatus(`Saved ${output.split(/[\\/]/).pop()}`);
+        } catch (e) {
+          setStatus(String(e));
+        } finally {
+          unlisten();
+        }
+        setTimeout(() => setStatus(null), 3000);
+      },
+    },
+    {
+      id: "dictionary",
+      name: "Dictionary",
+      description: 'Define the word on the clipboard - or "define <word> [in <lang>]" in command mode',
+      icon: Book,
+      keywords: ["define", "definition", "dictionary", "meaning", "word", "synonym", "thesaurus", "example"],
+      action: async () => {
+        setQuery("");
+        const word = (await readText().catch(() => ""))?.trim() ?? "";
+        if (!word || word.split(/\s+/).length > 3) {
+          setStatus('Copy a word first, or type "define <word>" in command mode');
+        } else {
+          setStatus(`Looking up "${word}"...`);
+          try {
+            const definition = await invoke<WordDefinition>("define_word", { word });
+            setStatus(formatDefinition(definition));
+          } catch (e) {
+            setStatus(String(e));
+          }
+        }
+        setTimeout(() => setStatus(null), 8000);
+      },
+    },
+    {
+      id: "generate-subtitles",
+      name: "Generate Subtitles",
+      description: "Transcribe the speech in a video or audio file to an .srt next to it - runs on this computer",
+      icon: Captions,
+      keywords: ["subtitles", "captions", "transcribe", "transcription", "speech", "whisper", "srt", "vtt", "video", "audio"],
+      action: async () => {
+        setQuery("");
+        isDialogOpenRef.current = true;
+        await invoke("set_auto_hide", { enabled: false });
+        const file = await open({ filters: MEDIA_FILE_FILTERS, multiple: false });
+        await invoke("set_auto_hide", { enabled: true });
+        isDialogOpenRef.current = false;
+        if (!file) {
+          return;
+        }
+        const stages = {
+          downloading_model: "Downloading speech model",
+          extracting_audio: "Reading audio",
+          transcribing: "Transcribing",
+        };
+        const unlisten = await listen<TranscriptionProgress>("transcription-progress", (event) => {
+          setStatus(`${stages[event.payload.stage]}... ${event.payload.percent}%`);
+        });
+        try {
+          const output = await invoke<string>("transcribe_media", { path: file });
+          setStatus(`Saved ${output.split(/[\\/]/).pop()}`);
+        } catch (e) {
+          setStatus(String(e));
+        } finally {
+          unlisten();
+        }
+        setTimeout(() => setStatus(null), 3000);
+      },
+    },
+    {
+      id: "startup-health",
+      name: "Startup Health",
+      description:
+        startupHealth && startupHealth.issues.length > 0
+          ? `${startupHealth.issues[0].message}${startupHealth.issues.length > 1 ? ` (+${startupHealth.issues.length - 1} more)` : ""} - copy details`
+          : "All startup checks passed",
+      icon: startupHealth && startupHealth.issues.length > 0 ? TriangleAlert : HeartPulse,
+      keywords: ["startup", "health", "diagnostics", "problems", "hotkey", "tray", "ffmpeg", "yt-dlp", "not working"],
+      action: async () => {
+        setQuery("");
+        try {
+          const health = await invoke<StartupHealth>("get_startup_health");
+          setStartupHealth(health);
+          await writeText(formatStartupHealth(health));
+          setStatus(health.issues.length > 0 ? "Startup problems copied" : "All startup checks passed");
+        } catch (e) {
+          setStatus(String(e));
+        }
+        setTimeout(() => setStatus(null), 2000);
+      },
+    },
+    {
+      id: "display-calibration",
+      name: "Display Calibration",
+      description: "Full-screen test patterns: gray ramps, color bars, dead pixels",
+      icon: MonitorCheck,
+      keywords: ["calibrate", "calibration", "monitor", "display", "dead pixel", "test pattern", "gamma", "color bars"],
+      action: async () => {
+        setQuery("");
+        try {
+          await invoke("hide_window");
+          await invoke("open_calibration_window", {});
+        } catch (e) {
+          setStatus(String(e));
+          setTimeout(() => setStatus(null), 2000);
+        }
+      },
+    },
+    {
+      id: "jobs-window",
+      name: "Running Jobs",
+      description: "Keep download and conversion progress open in its own window",
+      icon: Activity,
+      keywords: ["jobs", "progress", "downloads", "conversions", "window", "panel", "detach", "cancel"],
+      action: async () => {
+        setQuery("");
+        await invoke("hide_window");
+        await invoke("open_tool_window", { tool: "jobs", options: { title: "Running Jobs" } });
+      },
+    },
+    // One entry per saved Wake-on-LAN device ("wol add <name> <mac>" in command mode)
+    ...wolDevices.map((device) => ({
+      id: `wake-${device.name}`,
+      name: `Wake ${device.name}`,
+      description: `Send a Wake-on-LAN packet to ${device.mac}`,
+      icon: Power,
+      keywords: ["wake", "wol", "wake on lan", "power on", "boot", device.name.toLowerCase()],
+      action: async () => {
+        setQuery("");
+        try {
+          await invoke("wake_device", { name: device.name });
+          setStatus(`Wake packet sent to ${device.name}`);
+        } catch (e) {
+          setStatus(String(e));
+        }
+        setTimeout(() => setStatus(null), 2000);
+      },
+    })),
+    {
+      id: "settings",
+      name: "Settings",
+      description: "Configure BunchaTools preferences",
+      icon: SettingsIcon,
+      keywords: ["settings", "preferences", "config", "options", "hotkey", "startup"],
+      isSettings: true,
+    },
+  ];
+
+  // Load settings and command history on mount, and mark app as ready
+  useEffect(() => {
+    const initialize = async () => {
+      try {
+        const s = await invoke<Settings>("get_settings");
+        setSettings(s);
+        // Mark as initialized after a tick to avoid triggering auto-save
+        setTimeout(() => {
+          settingsInitialized.current = true;
+        }, 0);
+
+        // Load command history (refused while the app lock is on; loaded again on unlock)
+        try {
+          setCommandHistory(await invoke<string[]>("load_command_history"));
+          historyLoadedRef.current = true;
+        } catch (e) {
+          console.warn("Command history not loaded:", e);
+        }
+
+        // Signal to backend that the app is fully loaded and ready
+        await invoke("mark_app_ready");
+      } catch (e) {
+        console.error("Failed to initialize:", e);
+      }
+    };
+    initialize();
+  }, []);
+
+  // Auto-save settings when they change
+  useEffect(() => {
+    if (!settingsInitialized.current) return;
+
+    const timeoutId = setTimeout(async () => {
+      try {
+        await invoke("save_settings", { settings });
+      } catch (e) {
+        console.error("Failed to auto-save settings:", e);
+      }
+      // A fallback may have taken over if the new hotkey is in use
+      invoke<HotkeyBinding | null>("get_active_hotkey").then(setActiveHotkey).catch(() => {});
+    }, 300); // 300ms debounce
+
+    return () => clearTimeout(timeoutId);
+  }, [settings]);
+
+  // Auto-save command history when it changes
+  useEffect(() => {
+    // Don't save until history has been loaded (avoid overwriting with empty array on init)
+    if (!historyLoadedRef.current) return;
+
+    const timeoutId = setTimeout(async () => {
+      try {
+        await invoke("save_command_history", { commands: commandHistory });
+      } catch (e) {
+        console.error("Failed to save command history:", e);
+      }
+    }, 300); // 300ms debounce
+
+    return () => clearTimeout(timeoutId);
+  }, [commandHistory]);
+
+  // Generate QR code when data changes
+  useEffect(() => {
+    if (!showQRGenerator) return;
+
+    const generateQR = async () => {
+      // Wi-Fi, contact, location and email codes are validated and escaped in Rust
+      const payload = qrPayloadFor(qrType, qrData);
+      let content: string;
+      try {
+        content = payload ? await invoke<string>("build_qr_payload", { payload }) : generateQRContent(qrType, qrData);
+        setQRError(null);
+      } catch (e) {
+        setQRError(String(e));
+        content = "";
+      }
+      setQRContent(content);
+      if (!content) {
+        setQRImageDataUrl("");
+        return;
+      }
+
+      try {
+        const dataUrl = await QRCodeLib.toDataURL(content, {
+          color: {
+            dark: qrForegroundColor,
+            light: qrBackgroundColor,
+          },
+          width: 300,
+          margin: 2,
+          errorCorrectionLevel: "M",
+        });
+        setQRImageDataUrl(dataUrl);
+      } catch (err) {
+        console.error("QR generation error:", err);
+        setQRImageDataUrl("");
+      }
+    };
+
+    const timeoutId = setTimeout(generateQR, 100);
+    return () => clearTimeout(timeoutId);
+  }, [showQRGenerator, qrType, qrData, qrForegroundColor, qrBackgroundColor]);
+
+  // Debounced translation when input changes (for manual typing)
+  useEffect(() => {
+    if (!showTranslation || !translationInput.trim()) {
+      return;
+    }
+
+    const timeoutId = setTimeout(async () => {
+      setIsTranslating(true);
+      setTranslationError(null);
+      try {
+        const [result, detection] = await Promise.all([
+          invoke<TranslationResult>("translate_text", {
+            text: translationInput,
+            targetLang: targetLanguage,
+          }),
+          invoke<LanguageDetection>("detect_language", { text: translationInput }).catch(() => null),
+        ]);
+        setTranslationOutput(result.translated_text);
+        setDetectedLanguage(result.detected_language);
+        setDetectionWarning(formatDetectionWarning(detection));
+      } catch (err) {
+        setTranslationError(String(err));
+      } finally {
+        setIsTranslating(false);
+      }
+    }, 500); // 500ms debounce
+
+    return () => clearTimeout(timeoutId);
+  }, [translationInput, targetLanguage, showTranslation]);
+
+  // Listen for conversion progress events
+  useEffect(() => {
+    const unlisten = listen<number>("conversion-progress", (event) => {
+      setVideoProgress(event.payload);
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  // Listen for git download progress events
+  useEffect(() => {
+    const unlisten = listen<GitDownloadProgress>("git-download-progress", (event) => {
+      setGitProgress(event.payload);
+      // Also update command status if in command-only mode and downloading
+      if (settings.command_only_mode && event.payload.stage === 'downloading') {
+        setCommandStatus({
+          message: `Downloading... ${event.payload.percent}%`,
+          type: 'progress',
+        });
+      }
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [settings.command_only_mode]);
+
+  // Listen for YouTube download progress events
+  useEffect(() => {
+    const unlisten = listen<YouTubeDownloadProgress>("youtube-download-progress", (event) => {
+      setYtProgress(event.payload);
+      // Also update command status if in command-only mode and downloading
+      if (settings.command_only_mode && event.payload.stage === 'downloading') {
+        setCommandStatus({
+          message: `Downloading... ${Math.round(event.payload.percent)}%`,
+          type: 'progress',
+        });
+      }
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [settings.command_only_mode]);
+
+  useEffect(() => {
+    invoke<WolDevice[]>("list_wol_devices").then(setWolDevices).catch(() => {});
+  }, []);
+
+  // Follow the desktop's light/dark switch for the "system" theme
+  useEffect(() => {
+    invoke<SystemTheme>("get_system_theme").then(setSystemTheme).catch(() => {});
+    const unlisten = listen<SystemTheme>("system-theme-changed", (event) => {
+      setSystemTheme(event.payload);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  // Native controls (scrollbars, inputs) follow the effective theme
+  useEffect(() => {
+    const theme = settings.theme === "system" ? systemTheme : settings.theme;
+    document.documentElement.dataset.theme = theme;
+    document.documentElement.style.colorScheme = theme;
+  }, [settings.theme, systemTheme]);
+
+  // Follow keep awake, which the tray menu and its timer can also change
+  useEffect(() => {
+    invoke<KeepAwakeStatus | null>("get_keep_awake_status").then(setKeepAwake).catch(() => {});
+    const unlisten = listen<KeepAwakeStatus | null>("keep-awake-changed", (event) => {
+      setKeepAwake(event.payload);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  // Follow the night light, which the tray menu and the sunset schedule can also change
+  useEffect(() => {
+    invoke<NightLightStatus>("get_night_light_status").then(setNightLight).catch(() => {});
+    const unlisten = listen<NightLightStatus>("night-light-changed", (event) => {
+      setNightLight(event.payload);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  // The calculator tape and memory survive restarts
+  useEffect(() => {
+    invoke<CalcHistory>("get_calc_history").then(setCalcHistory).catch(() => {});
+  }, []);
+
+  // Histories the app lock held back are loaded once it's unlocked
+  useEffect(() => {
+    const unlisten = listen<AppLockStatus>("app-lock-changed", (event) => {
+      if (event.payload.locked) return;
+      invoke<CalcHistory>("get_calc_history").then(setCalcHistory).catch(() => {});
+      if (!historyLoadedRef.current) {
+        invoke<string[]>("load_command_history")
+          .then((history) => {
+            setCommandHistory(history);
+            historyLoadedRef.current = true;
+          })
+          .catch(() => {});
+      }
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  // Clipboard text another device sent is already on the clipboard; just say so
+  useEffect(() => {
+    const unlisten = listen<string>("clipboard-received", (event) => {
+      setCommandStatus({ message: `Clipboard received from ${event.payload}`, type: 'success' });
+      setTimeout(() => {
+        setCommandStatus({ message: "Type a command...", type: 'idle' });
+      }, 3000);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  // Follow the screen recording, including ffmpeg exiting on its own
+  useEffect(() => {
+    invoke<RecordingStatus | null>("get_screen_recording").then(setRecording).catch(() => {});
+    const unlistenChanged = listen<RecordingStatus | null>("screen-recording-changed", (event) => {
+      setRecording(event.payload);
+    });
+    const unlistenTick = listen<RecordingStatus>("screen-recording-tick", (event) => {
+      setRecording(event.payload);
+    });
+    return () => {
+      unlistenChanged.then((fn) => fn());
+      unlistenTick.then((fn) => fn());
+    };
+  }, []);
+
+  // Follow the audio recording and its input level
+  useEffect(() => {
+    invoke<AudioRecordingStatus | null>("get_audio_recording").then(setAudioRecording).catch(() => {});
+    const unlistenChanged = listen<AudioRecordingStatus | null>("audio-recording-changed", (event) => {
+      setAudioRecording(event.payload);
+      setAudioLevel(null);
+    });
+    const unlistenLevel = listen<AudioLevel>("audio-recording-level", (event) => {
+      setAudioLevel(event.payload);
+    });
+    return () => {
+      unlistenChanged.then((fn) => fn());
+      unlistenLevel.then((fn) => fn());
+    };
+  }, []);
+
+  // Habit streaks, refreshed on every check-in
+  useEffect(() => {
+    invoke<HabitStreak[]>("get_streaks").then(setHabitStreaks).catch(() => {});
+    const unlisten = listen<HabitStreak[]>("habits-changed", (event) => {
+      setHabitStreaks(event.payload);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  // Follow the running time entry; idle time is left out by the backend
+  useEffect(() => {
+    const refreshReport = () => {
+      invoke<TimeReport>("get_time_report", { range: "week" }).then(setWeekReport).catch(() => {});
+    };
+    invoke<TimeEntry | null>("get_running_time_entry").then(setTimeEntry).catch(() => {});
+    refreshReport();
+    const unlistenChanged = listen<TimeEntry | null>("time-entry-changed", (event) => {
+      setTimeEntry(event.payload);
+      refreshReport();
+    });
+    const unlistenIdle = listen<TimeEntry>("time-entry-idle", (event) => {
+      setTimeEntry(event.payload);
+      const idle = event.payload.idle[event.payload.idle.length - 1];
+      if (idle) {
+        setStatus(`Left ${formatHoursMinutes(idle.end - idle.start)} away out of "${event.payload.label}"`);
+        setTimeout(() => setStatus(null), 5000);
+      }
+    });
+    return () => {
+      unlistenChanged.then((fn) => fn());
+      unlistenIdle.then((fn) => fn());
+    };
+  }, []);
+
+  // Follow the auto-clicker, which its stop hotkey or click limit can end
+  useEffect(() => {
+    invoke<AutoClickerStatus | null>("get_auto_clicker").then(setAutoClicker).catch(() => {});
+    const unlisten = listen<AutoClickerStatus | null>("auto-clicker-changed", (event) => {
+      setAutoClicker(event.payload);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  // Follow the delayed shutdown, which its own window can cancel
+  useEffect(() => {
+    invoke<ScheduledShutdown | null>("get_scheduled_shutdown").then(setScheduledShutdown).catch(() => {});
+    const unlisten = listen<ScheduledShutdown | null>("shutdown-scheduled", (event) => {
+      setScheduledShutdown(event.payload);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  // Follow the Pomodoro session (null once it's stopped)
+  useEffect(() => {
+    invoke<PomodoroStatus | null>("get_pomodoro_status").then(setPomodoro).catch(() => {});
+    const unlisten = listen<PomodoroStatus | null>("pomodoro-tick", (event) => {
+      setPomodoro(event.payload);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  // Keep the network status current for tools that need connectivity
+  useEffect(() => {
+    invoke<NetworkStatus>("get_network_status").then(setNetwork).catch(() => {});
+    const unlisten = listen<NetworkStatus>("network-changed", (event) => {
+      setNetwork(event.payload);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  // Explain startup failures (e.g. the hotkey is taken by another app) instead of silently not working
+  useEffect(() => {
+    const show = (health: StartupHealth) => {
+      setStartupHealth(health);
+      const leftovers = formatLeftoverCleanup(health);
+      if (health.issues.length > 0) {
+        setStatus(health.issues[0].message);
+        setTimeout(() => setStatus(null), 8000);
+      } else if (leftovers) {
+        setStatus(leftovers);
+        setTimeout(() => setStatus(null), 4000);
+      }
+    };
+    invoke<HotkeyBinding | null>("get_active_hotkey").then(setActiveHotkey).catch(() => {});
+    invoke<StartupHealth>("get_startup_health")
+      .then((health) => {
+        // Wait for the event if the background checks are still running
+        if (health.checked) show(health);
+      })
+      .catch(() => {});
+    const unlisten = listen<StartupHealth>("startup-health", (event) => {
+      show(event.payload);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  // A download went over the monthly data cap
+  useEffect(() => {
+    const unlisten = listen<string>("transfer-cap-exceeded", (event) => {
+      setStatus(event.payload);
+      setTimeout(() => setStatus(null), 8000);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  // Listen for timer tick events
+  useEffect(() => {
+    const unlisten = listen<{ remaining: number; label: string }>("timer-tick", (event) => {
+      const { remaining, label } = event.payload;
+
+      if (label === "cancelled" || remaining === 0) {
+        // Timer completed or cancelled
+        setTimerRemaining(null);
+        setTimerLabel("");
+        if (remaining === 0 && label !== "cancelled") {
+          // Timer completed - show success briefly
+          setCommandStatus({
+            message: `${label} complete!`,
+            type: 'success',
+          });
+          // Auto-dismiss after 3 seconds
+          setTimeout(() => {
+            setCommandStatus({ message: "Type a command...", type: 'idle' });
+          }, 3000);
+        }
+      } else {
+        setTimerRemaining(remaining);
+        setTimerLabel(label);
+      }
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  // Keep ref in sync with git download status
+  useEffect(() => {
+    gitDownloadingRef.current = gitProgress.stage === 'fetching' ||
+                                gitProgress.stage === 'downloading' ||
                                 gitProgress.stage === 'extracting';
   }, [gitProgress.stage]);
 
-  // Clear help status when user starts typing in command-only mode
+  // Clear help status when user starts typing in command-only mode
+  useEffect(() => {
+    if (settings.command_only_mode && query.length > 0 && commandStatus.type === 'help') {
+      setCommandStatus({ message: "Type a command...", type: 'idle' });
+    }
+  }, [query, settings.command_only_mode, commandStatus.type]);
+
+  useEffect(() => {
+    const unlisten = listen("focus-search", async () => {
+      await invoke("set_auto_hide", { enabled: true });
+      setQuery("");
+      setSelectedIndex(0);
+      setStatus(null);
+      setShowSettings(false);
+      setShowVideoConverter(false);
+      setVideoFile(null);
+      setVideoBatch([]);
+      setVideoFormat("mp4");
+      setVideoQuality("high");
+      setVideoAdvancedSettings({
+        resolution: "Keep Original",
+        frameRate: "Keep Original",
+        codec: "H.264",
+        keepAudio: true,
+      });
+      setShowVideoAdvanced(false);
+      setVideoConverting(false);
+      setVideoProgress(0);
+      setVideoConversionStatus('idle');
+      setShowPortKiller(false);
+      setPortInput("");
+      setPortProcesses([]);
+      setScannedPort(null);
+      setCurrencyResult(null);
+      setLastCurrencyQuery("");
+      setShowTranslation(false);
+      setTranslationInput("");
+      setTranslationOutput("");
+      setDetectedLanguage("Detecting...");
+      setTranslationError(null);
+      setIsTranslationSettingsOpen(false);
+      setShowColorPicker(false);
+      setPickedColor(null);
+      setCopiedFormat(null);
+      setShowQRGenerator(false);
+      setQRType("url");
+      setQRData({ ...DEFAULT_QR_DATA });
+      setQRForegroundColor("#000000");
+      setQRBackgroundColor("#FFFFFF");
+      setShowQRCustomization(false);
+      setQRImageDataUrl("");
+      setQRError(null);
+      setQRCopied(false);
+      setShowRegexTester(false);
+      // Only reset Git Downloader if not actively downloading
+      if (!gitDownloadingRef.current) {
+        setShowGitDownloader(false);
+        setGitUrlInput("");
+        setGitParsedUrl(null);
+        setGitDownloadPath("");
+        setGitDownloadOptions({
+          extractFiles: true,
+          flattenStructure: false,
+          createSubfolder: true,
+        });
+        setGitProgress({
+          stage: 'idle',
+          percent: 0,
+          message: '',
+        });
+      }
+      // Always close YouTube Downloader panel, but don't reset state if downloading
+      // (download continues in background, user returns to command palette)
+      setShowYouTubeDownloader(false);
+      inputRef.current?.focus();
+    });
+
+    inputRef.current?.focus();
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  // Select a screen region, read its text (OCR) and show it translated in the translation panel
+  const translateScreenRegion = async (targetLang?: string) => {
+    const target = targetLang || settings.quick_translation_target_language;
+    setTranslationInput("");
+    setTranslationOutput("");
+    setDetectedLanguage("Reading screen...");
+    setTranslationError(null);
+    // Close any open panels first
+    setShowSettings(false);
+    setShowVideoConverter(false);
+    setShowPortKiller(false);
+    setShowColorPicker(false);
+    setShowQRGenerator(false);
+    setShowRegexTester(false);
+    setShowGitDownloader(false);
+
+    setIsTranslating(true);
+    try {
+      const result = await invoke<ScreenTranslation>("translate_screen_region", { targetLang: target });
+      setTranslationInput(result.text);
+      setTranslationOutput(result.translated_text);
+      setDetectedLanguage(result.detected_language);
+      setTargetLanguage(result.target_language || target);
+      setShowTranslation(true);
+    } catch (e) {
+      // User cancelled (pressed Escape), don't show translation window
+      if (e !== "Cancelled") {
+        setTargetLanguage(target);
+        setDetectedLanguage("");
+        setTranslationError(String(e));
+        setShowTranslation(true);
+      }
+    } finally {
+      setIsTranslating(false);
+    }
+  };
+
+  // Read the QR codes in a screen region (or on every screen) and copy their contents
+  const scanQrCode = async (fullScreen = false) => {
+    try {
+      const codes = await invoke<ScannedCode[]>("scan_qr_from_screen", { fullScreen });
+      const described = codes.map(describeScannedCode);
+      await writeText(described.map((d) => d.copy).join("\n"));
+      setStatus(described.map((d) => d.status).join(" · "));
+      setTimeout(() => setStatus(null), 4000);
+    } catch (e) {
+      if (e !== "Cancelled") {
+        setStatus(String(e));
+        setTimeout(() => setStatus(null), 2000);
+      }
+    }
+  };
+
+  // Listen for screen translation hotkey trigger
+  useEffect(() => {
+    const unlisten = listen("trigger-screen-translation", () => {
+      translateScreenRegion();
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [settings.quick_translation_target_language]);
+
+  // Listen for quick translation hotkey trigger
+  useEffect(() => {
+    const unlisten = listen("trigger-quick-translation", async () => {
+      // Reset translation state
+      setTranslationInput("");
+      setTranslationOutput("");
+      setDetectedLanguage("Detecting...");
+      setTranslationError(null);
+      setIsTranslating(false);
+      // Close any open panels first
+      setShowSettings(false);
+      setShowVideoConverter(false);
+      setShowPortKiller(false);
+      setShowColorPicker(false);
+      setShowQRGenerator(false);
+      setShowRegexTester(false);
+      setShowGitDownloader(false);
+
+      try {
+        // Start text selection mode using the app handle version (works from hotkey context)
+        await invoke("start_text_selection_from_hotkey");
+
+        // Read the copied text from clipboard
+        const clipboardText = await readText();
+
+        if (clipboardText && clipboardText.trim()) {
+          setTranslationInput(clipboardText);
+          setShowTranslation(true);
+
+          // Start translation immediately using the saved target language
+          setIsTranslating(true);
+          try {
+            const result = await invoke<TranslationResult>("translate_text", {
+              text: clipboardText,
+              targetLang: settings.quick_translation_target_language,
+            });
+            setTranslationOutput(result.translated_text);
+            setDetectedLanguage(result.detected_language);
+            setTargetLanguage(settings.quick_translation_target_language);
+            setIsTranslating(false);
+          } catch (translationErr) {
+            setTranslationError(String(translationErr));
+            setIsTranslating(false);
+          }
+        } else {
+          // No text was selected/copied
+          setShowTranslation(true);
+          setTranslationInput("");
+          setDetectedLanguage("No text selected");
+          setTargetLanguage(settings.quick_translation_target_language);
+        }
+      } catch (e) {
+        // User cancelled (pressed Escape), don't show translation window
+        if (e !== "Cancelled") {
+          console.error("Text selection error:", e);
+        }
+      }
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [settings.quick_translation_target_language]);
+
+  useEffect(() => {
+    if (query.trim() === "") {
+      // Offer the tool matching the clipboard first
+      const suggested = clipboardSuggestion && tools.find((t) => t.id === clipboardSuggestion.tool);
+      setFilteredTools(
+        suggested
+          ? [
+              { ...suggested, description: clipboardSuggestion.label },
+              ...tools.filter((t) => t !== suggested),
+            ]
+          : tools
+      );
+      setSelectedIndex(0);
+      setQuickResult(null);
+      setCurrencyResult(null);
+      setLastCurrencyQuery("");
+    } else {
+      const q = query.toLowerCase();
+      const filtered = tools.filter(
+        (tool) =>
+          tool.name.toLowerCase().includes(q) ||
+          tool.description.toLowerCase().includes(q) ||
+          tool.keywords.some((k) => k.includes(q))
+      );
+      setFilteredTools(filtered);
+      setSelectedIndex(0);
+
+      // Check for calculator result first
+      const calcResult = evaluateExpression(query);
+      if (calcResult) {
+        setQuickResult({
+          type: "calculator",
+          query: query,
+          result: calcResult,
+          icon: Calculator,
+          copyValue: calcResult.replace(/\./g, "").replace(/,/g, "."), // Convert from de-DE format to number
+        });
+        setCurrencyResult(null);
+        return;
+      }
+
+      // Check for color conversion
+      const colorResult = parseColorQuery(query);
+      if (colorResult) {
+        const hexColor = rgbToHex(colorResult.rgb.r, colorResult.rgb.g, colorResult.rgb.b);
+        setQuickResult({
+          type: "color",
+          query: colorResult.displayQuery,
+          result: colorResult.result,
+          icon: Palette,
+          copyValue: colorResult.result,
+          colorPreview: hexColor,
+        });
+        setCurrencyResult(null);
+        return;
+      }
+
+      // Unix timestamps, decoded to local time
+      const epoch = parseEpochQuery(query);
+      if (epoch) {
+        let stale = false;
+        invoke<EpochTime>("parse_epoch", { value: epoch })
+          .then((time) => {
+            if (stale) return;
+            setQuickResult({
+              type: "time",
+              query: `Unix ${time.unit}`,
+              result: `${time.local.display} · ${time.relative}`,
+              icon: Clock,
+              copyValue: time.utc,
+            });
+          })
+          .catch(() => setQuickResult(null));
+        setCurrencyResult(null);
+        return () => {
+          stale = true;
+        };
+      }
+
+      // Times in other zones ("3pm PST in Berlin")
+      if (looksLikeTimeQuery(query)) {
+        let stale = false;
+        invoke<TimeConversion | null>("convert_time_query", { query })
+          .then((time) => {
+            if (stale) return;
+            setQuickResult(
+              time && {
+                type: "time",
+                query: time.from.display,
+                result: time.to.display,
+                icon: Clock,
+                copyValue: time.to.display,
+              }
+            );
+          })
+          .catch(() => setQuickResult(null));
+        setCurrencyResult(null);
+        return () => {
+          stale = true;
+        };
+      }
+
+      // Expressions the calculator above doesn't do ("0xFF & 0b1010 in bin", "200 + 15%",
+      // "5 km + 300 m in mi")
+      if (looksLikeExpression(query)) {
+        let stale = false;
+        invoke<ExpressionResult>("evaluate_expression", { expr: query })
+          .then((result) => {
+            if (stale) return;
+            setQuickResult({
+              type: "calculator",
+              query: query,
+              result: result.display,
+              icon: Calculator,
+              copyValue: result.result,
+            });
+          })
+          .catch(() => setQuickResult(null));
+        setCurrencyResult(null);
+        return () => {
+          stale = true;
+        };
+      }
+
+      // Check for unit conversion
+      const unitResult = parseUnitQuery(query);
+      if (unitResult) {
+        const formattedResult = unitResult.result.toLocaleString("en-US", {
+          minimumFractionDigits: 0,
+          maximumFractionDigits: 3,
+        });
+        setQuickResult({
+          type: "unit",
+          query: query,
+          result: `${formattedResult} ${unitResult.toUnit}`,
+          icon: Ruler,
+          copyValue: unitResult.result.toString(),
+        });
+        setCurrencyResult(null);
+        return;
+      }
+
+      // Check for reference lookup (HTTP status, MIME type, port, HTML entity)
+      const referenceQuery = parseReferenceQuery(query);
+      if (referenceQuery) {
+        let stale = false;
+        invoke<ReferenceEntry[]>("lookup_reference", referenceQuery)
+          .then((entries) => {
+            if (stale) return;
+            const entry = entries[0];
+            if (!entry) {
+              setQuickResult(null);
+              return;
+            }
+            const result = {
+              http_status: `${entry.key} ${entry.name}`,
+              mime_type: entry.name,
+              port: `${entry.key} · ${entry.name}`,
+              html_entity: `&${entry.key};  ${entry.name}`,
+            }[entry.kind];
+            const copyValue = {
+              http_status: entry.key,
+              mime_type: entry.name,
+              port: entry.key,
+              html_entity: `&${entry.key};`,
+            }[entry.kind];
+            setQuickResult({
+              type: "reference",
+              query: entry.kind === "mime_type" ? `.${entry.key} · ${entry.description}` : entry.description,
+              result,
+              icon: BookOpen,
+              copyValue,
+            });
+          })
+          .catch(() => setQuickResult(null));
+        setCurrencyResult(null);
+        return () => {
+          stale = true;
+        };
+      }
+
+      const currencyQuery = parseCurrencyQuery(query);
+      const showUnitResult = (result: UnitResult | null) =>
+        setQuickResult(
+          result && {
+            type: "unit",
+            query: query,
+            result: formatUnitResult(result),
+            icon: Ruler,
+            copyValue: result.result.toString(),
+          }
+        );
+
+      // Units the quick converter above doesn't know (area, speed, data size, energy, ...)
+      if (!currencyQuery && looksLikeUnitQuery(query)) {
+        let stale = false;
+        invoke<UnitResult | null>("convert_unit_query", { query })
+          .then((result) => {
+            if (!stale) showUnitResult(result);
+          })
+          .catch(() => setQuickResult(null));
+        setCurrencyResult(null);
+        return () => {
+          stale = true;
+        };
+      }
+
+      // Check for currency query
+      if (currencyQuery && query !== lastCurrencyQuery) {
+        setLastCurrencyQuery(query);
+        setCurrencyLoading(true);
+        setQuickResult(null);
+        invoke<CurrencyResult>("convert_currency", {
+          amount: currencyQuery.amount,
+          from: currencyQuery.from,
+          to: currencyQuery.to,
+        })
+          .then((result) => {
+            setCurrencyResult(result);
+            setCurrencyLoading(false);
+            // Set quick result for currency
+            const formattedResult = formatCurrencyAmount(result.result);
+            setQuickResult({
+              type: "currency",
+              query: query,
+              result: `${formattedResult} ${result.to}${currencyStaleNote(result)}${currencyChangeNote(result)}`,
+              icon: DollarSign,
+              copyValue: formatCurrencyAmount(result.result, false),
+            });
+          })
+          .catch(() => {
+            setCurrencyResult(null);
+            setCurrencyLoading(false);
+            // "100 kwh to btu" looks like a pair of currency codes too
+            invoke<UnitResult | null>("convert_unit_query", { query })
+              .then(showUnitResult)
+              .catch(() => setQuickResult(null));
+          });
+      } else if (currencyQuery && currencyResult) {
+        // Keep existing currency quick result
+        const formattedResult = formatCurrencyAmount(currencyResult.result);
+        setQuickResult({
+          type: "currency",
+          query: query,
+          result: `${formattedResult} ${currencyResult.to}${currencyStaleNote(currencyResult)}${currencyChangeNote(currencyResult)}`,
+          icon: DollarSign,
+          copyValue: formatCurrencyAmount(currencyResult.result, false),
+        });
+      } else if (!currencyQuery) {
+        setCurrencyResult(null);
+
+        // No exact match found - check for partial queries and show preview
+        // Check for partial unit query (e.g., "10 fahr" → "10 fahrenheit to celsius")
+        const partialUnit = parsePartialUnitQuery(query);
+        if (partialUnit) {
+          // Compute the result for the suggested query
+          const suggestedResult = parseUnitQuery(partialUnit.suggestedQuery);
+          if (suggestedResult) {
+            const formattedResult = suggestedResult.result.toLocaleString("en-US", {
+              minimumFractionDigits: 0,
+              maximumFractionDigits: 3,
+            });
+            setQuickResult({
+              type: "unit",
+              query: partialUnit.suggestedQuery,
+              result: `${formattedResult} ${suggestedResult.toUnit}`,
+              icon: Ruler,
+              copyValue: suggestedResult.result.toString(),
+              isPreview: true,
+            });
+            return;
+          }
+        }
+
+        // Check for partial currency query (e.g., "10 yen" → "10 yen to usd")
+        const partialCurrency = parsePartialCurrencyQuery(query);
+        const favoriteCurrencies = (settings.favorite_currencies ?? [])
+          .filter((code) => code !== partialCurrency?.from)
+          .slice(0, 5);
+        if (partialCurrency && favoriteCurrencies.length > 1) {
+          // Show the amount in every favorite currency at once
+          setCurrencyLoading(true);
+          invoke<CurrencyResult[]>("convert_currency_multi", {
+            amount: partialCurrency.amount,
+            from: partialCurrency.from,
+            targets: favoriteCurrencies,
+          })
+            .then((results) => {
+              setCurrencyLoading(false);
+              setQuickResult({
+                type: "currency",
+                query: partialCurrency.suggestedQuery,
+                result: formatCurrencyResults(results),
+                icon: DollarSign,
+                copyValue: results.map((r) => `${formatCurrencyAmount(r.result, false)} ${r.to}`).join("\n"),
+                isPreview: true,
+              });
+            })
+            .catch(() => {
+              setCurrencyLoading(false);
+              setQuickResult(null);
+            });
+          return;
+        }
+        if (partialCurrency) {
+          // Show loading state for currency preview
+          setCurrencyLoading(true);
+          invoke<CurrencyResult>("convert_currency", {
+            amount: partialCurrency.amount,
+            from: partialCurrency.from,
+            to: partialCurrency.to,
+          })
+            .then((result) => {
+              setCurrencyLoading(false);
+              const formattedResult = formatCurrencyAmount(result.result);
+              setQuickResult({
+                type: "currency",
+                query: partialCurrency.suggestedQuery,
+                result: `${formattedResult} ${result.to}${currencyStaleNote(result)}${currencyChangeNote(result)}`,
+                icon: DollarSign,
+                copyValue: formatCurrencyAmount(result.result, false),
+                isPreview: true,
+              });
+            })
+            .catch(() => {
+              setCurrencyLoading(false);
+              setQuickResult(null);
+            });
+          return;
+        }
+
+        setQuickResult(null);
+      }
+    }
+  }, [query, clipboardSuggestion]);
+
+  // Tips the selected tool's uses have unlocked (onboarding.rs)
+  const selectedToolId = filteredTools[selectedIndex]?.id;
+  useEffect(() => {
+    if (!selectedToolId || settings.command_only_mode) {
+      setToolTip(null);
+      return;
+    }
+    let stale = false;
+    invoke<ToolTip[]>("get_tool_tips", { tool: selectedToolId })
+      .then((tips) => {
+        if (!stale) setToolTip(tips[0] ?? null);
+      })
+      .catch(() => setToolTip(null));
+    return () => {
+      stale = true;
+    };
+  }, [selectedToolId, settings.command_only_mode, tipsVersion]);
+
+  const dismissToolTip = (id: string) => {
+    invoke("dismiss_tip", { id })
+      .then(() => setTipsVersion((v) => v + 1))
+      .catch(() => {});
+  };
+
+  // Scroll selected tool into view when navigating with arrow keys
+  useEffect(() => {
+    const selectedElement = toolItemRefs.current[selectedIndex];
+    if (selectedElement) {
+      selectedElement.scrollIntoView({
+        behavior: 'smooth',
+        block: 'nearest'
+      });
+    }
+  }, [selectedIndex]);
+
+  // Calculate math expression result for command-only mode
+  const calcResult = useMemo(() => {
+    if (!settings.command_only_mode || !query.trim()) return null;
+    return evaluateExpression(query);
+  }, [settings.command_only_mode, query]);
+
+  // Window size configuration based on active view
+  // In command only mode, window always stays compact (no tool list shown)
+  const isCommandOnlyCollapsed = settings.command_only_mode;
+  const windowSizeConfig = useMemo(() => {
+    if (showVideoConverter) {
+      return { width: 900, minHeight: 400, maxHeight: 900 };
+    } else if (showPortKiller) {
+      return { width: 680, minHeight: 300, maxHeight: 600 };
+    } else if (showTranslation) {
+      return { width: 680, minHeight: 300, maxHeight: 600 };
+    } else if (showSettings) {
+      return { width: 680, minHeight: 200, maxHeight: 550 };
+    } else if (showColorPicker) {
+      return { width: 880, minHeight: 400, maxHeight: 700 };
+    } else if (showQRGenerator) {
+      return { width: showQRCustomization ? 1000 : 800, minHeight: 400, maxHeight: 700 };
+    } else if (showRegexTester) {
+      return { width: 1000, minHeight: 400, maxHeight: 700 };
+    } else if (showGitDownloader) {
+      return { width: 720, minHeight: 350, maxHeight: 720 };
+    } else if (showYouTubeDownloader) {
+      return { width: 720, minHeight: 400, maxHeight: 800 };
+    }
+    // Command palette - use smaller height in command only mode when collapsed
+    if (isCommandOnlyCollapsed) {
+      return { width: 680, minHeight: 62, maxHeight: 70 };
+    }
+    return { width: 680, minHeight: 200, maxHeight: 550 };
+  }, [showVideoConverter, showPortKiller, showTranslation, showSettings, showColorPicker, showQRGenerator, showQRCustomization, showRegexTester, showGitDownloader, showYouTubeDownloader, isCommandOnlyCollapsed]);
+
+  // Auto-resize window based on content
+  const { contentRef, measureAndResize } = useWindowAutoSize<HTMLDivElement>({
+    config: windowSizeConfig,
+    enabled: true,
+  });
+
+  // Force window resize when switching between views
+  // This handles cases where the content changes but config values are similar
+  useEffect(() => {
+    // Give React time to re-render the new view
+    const timeout = setTimeout(() => {
+      measureAndResize();
+    }, 50);
+    return () => clearTimeout(timeout);
+  }, [showSettings, settings.command_only_mode, measureAndResize]);
+
+  const executeTool = async (tool: Tool) => {
+    // Feeds the "Recent Tools" section of the tray menu, and unlocks the tool's tips
+    invoke("record_tool_use", { toolId: tool.id, name: tool.name })
+      .then(() => setTipsVersion((v) => v + 1))
+      .catch(() => {});
+
+    const suggestion = clipboardSuggestion?.tool === tool.id ? clipboardSuggestion : null;
+    setClipboardSuggestion(null);
+
+    if (suggestion?.kind === "color") {
+      // Show the copied color instead of starting a screen pick
+      setPickedColor(await invoke<ColorFormats>("get_color_formats", { input: suggestion.value }));
+      setCopiedFormat(null);
+      setShowColorPicker(true);
+      setQuery("");
+      await invoke("set_auto_hide", { enabled: true });
+      return;
+    }
+
+    if (tool.isSettings) {
+      setShowSettings(true);
+      setQuery("");
+    } else if (tool.action) {
+      setQuery("");
+      await tool.action();
+    }
+
+    // Fill the tool in with the clipboard value it was suggested for
+    switch (suggestion?.kind) {
+      case "youtube_url":
+        setYtUrlInput(suggestion.value);
+        break;
+      case "git_url":
+        handleGitUrlChange(suggestion.value);
+        break;
+      case "translation":
+        setTranslationInput(suggestion.value);
+        break;
+      case "convert_file":
+        await loadVideoFile(suggestion.value);
+        break;
+    }
+  };
+
+  // Pinning can also be toggled from the tray menu
+  useEffect(() => {
+    const unlisten = listen<boolean>("always-on-top-changed", (event) => {
+      setSettings((prev) => ({ ...prev, always_on_top: event.payload }));
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  // settings.json was edited outside the app; the backend has already applied it
+  useEffect(() => {
+    const unlisten = listen<Settings>("settings-changed", (event) => {
+      // Skip the auto-save, the file already has these settings
+      settingsInitialized.current = false;
+      setSettings(event.payload);
+      setTimeout(() => {
+        settingsInitialized.current = true;
+      }, 0);
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  // Suggestions are sent each time the window is shown (null when nothing matches)
+  useEffect(() => {
+    const unlisten = listen<ClipboardSuggestion | null>("clipboard-suggestion", (event) => {
+      setClipboardSuggestion(event.payload);
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  // Open a tool by id when the backend asks (quick actions) or after a hotkey chord
+  const openToolRef = useRef<(toolId: string) => void>(() => {});
+  openToolRef.current = (toolId: string) => {
+    const tool = tools.find((t) => t.id === toolId);
+    if (tool) {
+      executeTool(tool);
+    }
+  };
+
+  useEffect(() => {
+    const unlisten = listen<string>("open-tool", (event) => {
+      openToolRef.current(event.payload);
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  // Second key of a hotkey chord: the first key pressed after the main hotkey opened the
+  // launcher either opens its tool or ends the chord and types as usual
+  useEffect(() => {
+    let chord: (ChordStart & { deadline: number }) | null = null;
+    const unlistenStarted = listen<ChordStart>("chord-started", (event) => {
+      chord = { ...event.payload, deadline: Date.now() + event.payload.timeout_ms };
+    });
+    const unlistenCancelled = listen("chord-cancelled", () => {
+      chord = null;
+    });
+
+    const onKeyDown = (e: KeyboardEvent) => {
+      if (!chord || ["Control", "Alt", "Shift", "Meta"].includes(e.key)) return;
+      const pending = chord;
+      chord = null;
+      if (Date.now() > pending.deadline) return;
+
+      const held = [e.altKey && "alt", e.ctrlKey && "ctrl", e.shiftKey && "shift", e.metaKey && "win"].filter(Boolean);
+      const aliases: Record<string, string> = { control: "ctrl", super: "win", meta: "win" };
+      const main = pending.modifiers.map((m) => aliases[m.toLowerCase()] ?? m.toLowerCase());
+      const modifiersOk = held.length === 0 || (held.length === main.length && held.every((m) => main.includes(m as string)));
+      const key = e.code.replace(/^(Key|Digit)/, "").toUpperCase();
+      const binding = pending.bindings.find((b) => b.key.toUpperCase() === key);
+      if (binding && modifiersOk) {
+        e.preventDefault();
+        e.stopPropagation();
+        openToolRef.current(binding.tool);
+      }
+    };
+    window.addEventListener("keydown", onKeyDown, true);
+
+    return () => {
+      unlistenStarted.then((fn) => fn());
+      unlistenCancelled.then((fn) => fn());
+      window.removeEventListener("keydown", onKeyDown, true);
+    };
+  }, []);
+
+  // Push-to-talk: show what voice input is doing, then put the words in the query
+  useEffect(() => {
+    const unlistenStatus = listen<VoiceCaptureStatus>("voice-capture", (event) => {
+      const { state, percent, message } = event.payload;
+      if (state === "listening") {
+        setStatus("Listening...");
+      } else if (state === "downloading_model") {
+        setStatus(`Downloading speech model... ${percent ?? 0}%`);
+      } else if (state === "transcribing") {
+        setStatus("Transcribing...");
+      } else if (state === "error") {
+        setStatus(`Voice input: ${message ?? "failed"}`);
+        setTimeout(() => setStatus(null), 3000);
+      } else {
+        setStatus(null);
+      }
+    });
+    const unlistenQuery = listen<string>("voice-query", (event) => {
+      setQuery(event.payload);
+      inputRef.current?.focus();
+    });
+
+    return () => {
+      unlistenStatus.then((fn) => fn());
+      unlistenQuery.then((fn) => fn());
+    };
+  }, []);
+
+  const describeAction = (action: QuickActionType) => {
+    switch (action.type) {
+      case "open_tool":
+        return `Open ${tools.find((t) => t.id === action.tool)?.name ?? action.tool}`;
+      case "kill_port":
+        return `Kill the process on port ${action.port}`;
+      case "pick_color":
+        return "Pick a color";
+      case "start_timer":
+        return `Start a ${action.seconds}s timer${action.label ? ` (${action.label})` : ""}`;
+      case "cancel_timer":
+        return "Cancel the timer";
+    }
+  };
+
+  const confirmWorkflow = async (path: string) => {
+    try {
+      const workflow = await invoke<Workflow>("read_workflow_file", { path });
+      const steps = workflow.actions.map((action, i) => `${i + 1}. ${describeAction(action)}`);
+      const confirmed = await ask(
+        `"${workflow.name ?? "Workflow"}" will run these actions:\n\n${steps.join("\n")}\n\nOnly run workflows from sources you trust.`,
+        { title: "Run Workflow?", kind: "warning", okLabel: "Run", cancelLabel: "Cancel" }
+      );
+      if (confirmed) {
+        await invoke("run_workflow", { workflow });
+      }
+    } catch (e) {
+      setStatus(`Workflow: ${e}`);
+      setTimeout(() => setStatus(null), 3000);
+    }
+  };
+
+  // Open a tool with files from the file manager ("Convert with BunchaTools")
+  const openFilesRef = useRef<(request: OpenFilesRequest) => void>(() => {});
+  openFilesRef.current = async (request: OpenFilesRequest) => {
+    const [path] = request.paths;
+
+    // Workflows list what they will do and only run once confirmed
+    if (request.tool === "workflow") {
+      await invoke("show_window");
+      for (const workflowPath of request.paths) {
+        await confirmWorkflow(workflowPath);
+      }
+      return;
+    }
+
+    // Palettes show their first color instead of starting a screen pick
+    if (request.tool === "color-picker") {
+      if (!path) return;
+      try {
+        const colors = await invoke<string[]>("read_palette_file", { path });
+        setPickedColor(await invoke<ColorFormats>("get_color_formats", { input: colors[0] }));
+        setCopiedFormat(null);
+        setShowColorPicker(true);
+        setQuery("");
+        await invoke("set_auto_hide", { enabled: true });
+        await invoke("show_window");
+      } catch (e) {
+        console.error("Failed to open palette:", e);
+      }
+      return;
+    }
+
+    await invoke("show_window");
+    openToolRef.current(request.tool);
+    if (!path) return;
+
+    if (request.tool === "video-converter") {
+      await loadVideoFile(path);
+      setVideoBatch(request.paths.length > 1 ? request.paths : []);
+    } else if (request.tool === "youtube-downloader") {
+      try {
+        const urls = await invoke<string[]>("read_download_list", { path });
+        if (urls.length > 0) {
+          setYtUrlInput(urls[0]);
+        }
+      } catch (e) {
+        console.error("Failed to open download list:", e);
+      }
+    }
+  };
+
+  useEffect(() => {
+    const unlisten = listen<OpenFilesRequest>("open-files", (event) => {
+      openFilesRef.current(event.payload);
+    });
+
+    // Files passed on our own command line wait in the backend until now
+    invoke<OpenFilesRequest | null>("take_launch_request").then((request) => {
+      if (request) {
+        openFilesRef.current(request);
+      }
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  const handleKeyDown = async (e: React.KeyboardEvent) => {
+    // Escape is handled by the global handler
+    if (e.key === "Escape") return;
+
+    // Handle navigation only in command palette mode
+    if (!showSettings && !showVideoConverter && !showPortKiller && !showTranslation && !showQRGenerator && !showRegexTester && !showGitDownloader && !showYouTubeDownloader) {
+      // Command-only mode: handle Enter and history navigation
+      if (settings.command_only_mode) {
+        if (e.key === "Enter" && query.trim()) {
+          e.preventDefault();
+          // If there's a calculator result, copy it to clipboard
+          if (calcResult) {
+            try {
+              await writeText(calcResult);
+              recordCalculation(query, calcResult);
+              setQuery("");
+              setCommandStatus({ message: `Copied ${calcResult}`, type: 'success' });
+              if (commandStatusTimeoutRef.current) {
+                clearTimeout(commandStatusTimeoutRef.current);
+              }
+              commandStatusTimeoutRef.current = setTimeout(() => {
+                setCommandStatus({ message: "Type a command...", type: 'idle' });
+              }, 1500);
+            } catch (err) {
+              console.error("Failed to copy:", err);
+            }
+          } else {
+            // Otherwise execute as command
+            const commandToExecute = query;
+            setQuery(""); // Clear input immediately
+            await executeCommand(commandToExecute);
+          }
+        } else if (e.key === "ArrowUp") {
+          e.preventDefault();
+          if (commandHistory.length > 0) {
+            const newIndex = historyIndex < commandHistory.length - 1 ? historyIndex + 1 : historyIndex;
+            setHistoryIndex(newIndex);
+            setQuery(commandHistory[newIndex] || "");
+          }
+        } else if (e.key === "ArrowDown") {
+          e.preventDefault();
+          if (historyIndex > 0) {
+            const newIndex = historyIndex - 1;
+            setHistoryIndex(newIndex);
+            setQuery(commandHistory[newIndex] || "");
+          } else if (historyIndex === 0) {
+            setHistoryIndex(-1);
+            setQuery("");
+          }
+        }
+        return;
+      }
+
+      // Normal mode: tool navigation
+      if (e.key === "ArrowDown") {
+        e.preventDefault();
+        setSelectedIndex((prev) =>
+          prev < filteredTools.length - 1 ? prev + 1 : prev
+        );
+      } else if (e.key === "ArrowUp") {
+        e.preventDefault();
+        setSelectedIndex((prev) => (prev > 0 ? prev - 1 : prev));
+      } else if (e.key === "Enter" && quickResult?.type === "calculator" && filteredTools.length === 0) {
+        // Copy the result and keep it on the calculator tape
+        e.preventDefault();
+        await writeText(quickResult.copyValue);
+        recordCalculation(quickResult.query, quickResult.result);
+        setStatus(`Copied ${quickResult.result}`);
+        setTimeout(() => setStatus(null), 1500);
+      } else if (e.key === "Enter" && filteredTools.length > 0) {
+        e.preventDefault();
+        await executeTool(filteredTools[selectedIndex]);
+      }
+    }
+  };
+
+  const handleHotkeyKeyDown = (e: React.KeyboardEvent) => {
+    e.preventDefault();
+    e.stopPropagation();
+
+    if (e.key === "Escape") {
+      setIsRecordingHotkey(false);
+      return;
+    }
+
+    const modifiers: string[] = [];
+    if (e.altKey) modifiers.push("Alt");
+    if (e.ctrlKey) modifiers.push("Ctrl");
+    if (e.shiftKey) modifiers.push("Shift");
+    if (e.metaKey) modifiers.push("Win");
+
+    // Use e.code for reliable key detection (works better with Alt combinations)
+    const code = e.code;
+    let mappedKey = "";
+
+    // Handle letter keys (KeyA, KeyB, etc.)
+    if (code.startsWith("Key")) {
+      mappedKey = code.substring(3); // "KeyQ" -> "Q"
+    }
+    // Handle digit keys (Digit0, Digit1, etc.)
+    else if (code.startsWith("Digit")) {
+      mappedKey = code.substring(5); // "Digit1" -> "1"
+    }
+    // Handle numpad keys
+    else if (code.startsWith("Numpad") && code.length > 6) {
+      const numpadPart = code.substring(6);
+      if (/^\d$/.test(numpadPart)) {
+        mappedKey = "Num" + numpadPart;
+      } else {
+        mappedKey = "Num" + numpadPart;
+      }
+    }
+    // Handle function keys
+    else if (/^F\d{1,2}$/.test(code)) {
+      mappedKey = code;
+    }
+    // Handle special keys
+    else {
+      const specialKeys: Record<string, string> = {
+        Space: "Space",
+        Enter: "Enter",
+        Tab: "Tab",
+        Backspace: "Backspace",
+        Delete: "Delete",
+        Insert: "Insert",
+        Home: "Home",
+        End: "End",
+        PageUp: "PageUp",
+        PageDown: "PageDown",
+        ArrowUp: "Up",
+        ArrowDown: "Down",
+        ArrowLeft: "Left",
+        ArrowRight: "Right",
+        Backquote: "`",
+        Minus: "-",
+        Equal: "=",
+        BracketLeft: "[",
+        BracketRight: "]",
+        Backslash: "\\",
+        Semicolon: ";",
+        Quote: "'",
+        Comma: ",",
+        Period: ".",
+        Slash: "/",
+      };
+      mappedKey = specialKeys[code] || "";
+    }
+
+    // Skip if only modifiers were pressed or no valid key
+    if (!mappedKey || ["ControlLeft", "ControlRight", "AltLeft", "AltRight", "ShiftLeft", "ShiftRight", "MetaLeft", "MetaRight"].includes(code)) {
+      return;
+    }
+
+    // Require at least one modifier for non-function keys
+    const isFunctionKey = /^F\d{1,2}$/.test(mappedKey);
+    if (modifiers.length > 0 || isFunctionKey) {
+      setSettings((prev) => ({
+        ...prev,
+        hotkey_modifiers: modifiers,
+        hotkey_key: mappedKey,
+      }));
+      setIsRecordingHotkey(false);
+    }
+  };
+
+  // Handle mouse buttons for hotkey recording (side buttons)
+  const handleHotkeyMouseDown = (e: React.MouseEvent) => {
+    // Only handle extra buttons (side buttons are typically 3 and 4)
+    if (e.button < 3) return;
+
+    e.preventDefault();
+    e.stopPropagation();
+
+    const modifiers: string[] = [];
+    if (e.altKey) modifiers.push("Alt");
+    if (e.ctrlKey) modifiers.push("Ctrl");
+    if (e.shiftKey) modifiers.push("Shift");
+    if (e.metaKey) modifiers.push("Win");
+
+    // Map mouse buttons to names
+    const mouseButtonNames: Record<number, string> = {
+      3: "Mouse4", // Back button
+      4: "Mouse5", // Forward button
+    };
+
+    const buttonName = mouseButtonNames[e.button];
+    if (buttonName) {
+      setSettings((prev) => ({
+        ...prev,
+        hotkey_modifiers: modifiers,
+        hotkey_key: buttonName,
+      }));
+      setIsRecordingHotkey(false);
+    }
+  };
+
+  // Quick Translation hotkey recording handler
+  const handleQuickTranslationHotkeyKeyDown = (e: React.KeyboardEvent) => {
+    e.preventDefault();
+    e.stopPropagation();
+
+    if (e.key === "Escape") {
+      setIsRecordingQuickTranslationHotkey(false);
+      return;
+    }
+
+    const modifiers: string[] = [];
+    if (e.altKey) modifiers.push("Alt");
+    if (e.ctrlKey) modifiers.push("Ctrl");
+    if (e.shiftKey) modifiers.push("Shift");
+    if (e.metaKey) modifiers.push("Win");
+
+    const code = e.code;
+    let mappedKey = "";
+
+    if (code.startsWith("Key")) {
+      mappedKey = code.substring(3);
+    } else if (code.startsWith("Digit")) {
+      mappedKey = code.substring(5);
+    } else if (code.startsWith("Numpad") && code.length > 6) {
+      const numpadPart = code.substring(6);
+      if (/^\d$/.test(numpadPart)) {
+        mappedKey = "Num" + numpadPart;
+      } else {
+        mappedKey = "Num" + numpadPart;
+      }
+    } else if (/^F\d{1,2}$/.test(code)) {
+      mappedKey = code;
+    } else {
+      const specialKeys: Record<string, string> = {
+        Space: "Space",
+        Enter: "Enter",
+        Tab: "Tab",
+        Backspace: "Backspace",
+        Delete: "Delete",
+        Insert: "Insert",
+        Home: "Home",
+        End: "End",
+        PageUp: "PageUp",
+        PageDown: "PageDown",
+        ArrowUp: "Up",
+        ArrowDown: "Down",
+        ArrowLeft: "Left",
+        ArrowRight: "Right",
+        Backquote: "`",
+        Minus: "-",
+        Equal: "=",
+        BracketLeft: "[",
+        BracketRight: "]",
+        Backslash: "\\",
+        Semicolon: ";",
+        Quote: "'",
+        Comma: ",",
+        Period: ".",
+        Slash: "/",
+      };
+      mappedKey = specialKeys[code] || "";
+    }
+
+    if (!mappedKey || ["ControlLeft", "ControlRight", "AltLeft", "AltRight", "ShiftLeft", "ShiftRight", "MetaLeft", "MetaRight"].includes(code)) {
+      return;
+    }
+
+    const isFunctionKey = /^F\d{1,2}$/.test(mappedKey);
+    if (modifiers.length > 0 || isFunctionKey) {
+      setSettings((prev) => ({
+        ...prev,
+        quick_translation_hotkey_modifiers: modifiers,
+        quick_translation_hotkey_key: mappedKey,
+      }));
+      setIsRecordingQuickTranslationHotkey(false);
+    }
+  };
+
+  // Quick Translation hotkey mouse button handler
+  const handleQuickTranslationHotkeyMouseDown = (e: React.MouseEvent) => {
+    if (e.button < 3) return;
+
+    e.preventDefault();
+    e.stopPropagation();
+
+    const modifiers: string[] = [];
+    if (e.altKey) modifiers.push("Alt");
+    if (e.ctrlKey) modifiers.push("Ctrl");
+    if (e.shiftKey) modifiers.push("Shift");
+    if (e.metaKey) modifiers.push("Win");
+
+    const mouseButtonNames: Record<number, string> = {
+      3: "Mouse4",
+      4: "Mouse5",
+    };
+
+    const buttonName = mouseButtonNames[e.button];
+    if (buttonName) {
+      setSettings((prev) => ({
+        ...prev,
+        quick_translation_hotkey_modifiers: modifiers,
+        quick_translation_hotkey_key: buttonName,
+      }));
+      setIsRecordingQuickTranslationHotkey(false);
+    }
+  };
+
+  // Universal Escape key handler - works from any state
+  useEffect(() => {
+    const handleGlobalEscape = async (e: KeyboardEvent) => {
+      if (e.key !== "Escape") return;
+
+      // Don't handle if we're recording a hotkey (Escape cancels recording)
+      if (isRecordingHotkey) {
+        setIsRecordingHotkey(false);
+        return;
+      }
+      if (isRecordingQuickTranslationHotkey) {
+        setIsRecordingQuickTranslationHotkey(false);
+        return;
+      }
+
+      // In command-only mode, cancel downloads in progress
+      if (settings.command_only_mode && commandStatus.type === 'progress') {
+        // Cancel any ongoing downloads
+        try {
+          await invoke("cancel_git_download");
+        } catch {
+          // Ignore - may not be a git download
+        }
+        try {
+          await invoke("cancel_youtube_download");
+        } catch {
+          // Ignore - may not be a youtube download
+        }
+        setCommandStatus({ message: "Cancelled", type: 'error' });
+        if (commandStatusTimeoutRef.current) {
+          clearTimeout(commandStatusTimeoutRef.current);
+        }
+        commandStatusTimeoutRef.current = setTimeout(() => {
+          setCommandStatus({ message: "Type a command...", type: 'idle' });
+        }, 1500);
+        return;
+      }
+
+      // Close panels in order of priority (most specific first)
+      if (showVideoConverter) {
+        setShowVideoConverter(false);
+        setVideoFile(null);
+        setVideoBatch([]);
+        setVideoFormat("mp4");
+        setVideoQuality("high");
+        setVideoAdvancedSettings({
+          resolution: "Keep Original",
+          frameRate: "Keep Original",
+          codec: "H.264",
+          keepAudio: true,
+        });
+        setShowVideoAdvanced(false);
+        setVideoConverting(false);
+        setVideoProgress(0);
+        setVideoConversionStatus('idle');
+      } else if (showPortKiller) {
+        setShowPortKiller(false);
+        setPortInput("");
+        setPortProcesses([]);
+        setScannedPort(null);
+      } else if (showTranslation) {
+        setShowTranslation(false);
+      } else if (showSettings) {
+        setShowSettings(false);
+      } else if (showColorPicker) {
+        setShowColorPicker(false);
+        setPickedColor(null);
+        setCopiedFormat(null);
+      } else if (showQRGenerator) {
+        setShowQRGenerator(false);
+      } else if (showRegexTester) {
+        setShowRegexTester(false);
+      } else if (showGitDownloader) {
+        // Don't reset if download is actively in progress
+        const isDownloading = gitProgress.stage === 'fetching' ||
+                             gitProgress.stage === 'downloading' ||
+                             gitProgress.stage === 'extracting';
+        if (!isDownloading) {
+          setShowGitDownloader(false);
+          setGitUrlInput("");
+          setGitParsedUrl(null);
+          setGitDownloadPath("");
+          setGitDownloadOptions({
+            extractFiles: true,
+            flattenStructure: false,
+            createSubfolder: true,
+          });
+          setGitProgress({
+            stage: 'idle',
+            percent: 0,
+            message: '',
+          });
+        }
+      } else if (showYouTubeDownloader) {
+        const isDownloading = ytProgress.stage === 'downloading';
+        // Always close the panel
+        setShowYouTubeDownloader(false);
+        // Only reset state if not downloading (download continues in background)
+        if (!isDownloading) {
+          setYtUrlInput("");
+          setYtParsedUrl(null);
+          setYtVideoInfo(null);
+          setYtDownloadPath("");
+          setYtDownloadOptions({
+            quality: 'best',
+            mode: 'video_audio',
+          });
+          setYtProgress({
+            stage: 'idle',
+            percent: 0,
+            message: '',
+          });
+        }
+      } else {
+        // No panel open - hide the window
+        await invoke("hide_window");
+        setQuery("");
+      }
+    };
+
+    window.addEventListener("keydown", handleGlobalEscape);
+    return () => window.removeEventListener("keydown", handleGlobalEscape);
+  }, [showVideoConverter, showPortKiller, showTranslation, showSettings, showColorPicker, showQRGenerator, showRegexTester, showGitDownloader, showYouTubeDownloader, gitProgress.stage, ytProgress.stage, isRecordingHotkey, isRecordingQuickTranslationHotkey, settings.command_only_mode, commandStatus.type]);
+
+  // Color picker blur handler
   useEffect(() => {
-    if (settings.command_only_mode && query.length > 0 && commandStatus.type === 'help') {
-      setCommandStatus({ message: "Type a command...", type: 'idle' });
-    }
-  }, [query, settings.command_only_mode, commandStatus.type]);
+    if (!showColorPicker) return;
+
+    const handleBlur = () => {
+      // Only reset state if not dragging (dragging causes temporary blur)
+      if (!isDraggingRef.current) {
+        setShowColorPicker(false);
+        setPickedColor(null);
+        setCopiedFormat(null);
+      }
+    };
 
+    window.addEventListener("blur", handleBlur);
+
+    return () => {
+      window.removeEventListener("blur", handleBlur);
+    };
+  }, [showColorPicker]);
+
+  // Video Converter blur handler (prevents flickering on reopen)
   useEffect(() => {
-    const unlisten = listen("focus-search", async () => {
-      await invoke("set_auto_hide", { enabled: true });
-      setQuery("");
-      setSelectedIndex(0);
-      setStatus(null);
-      setShowSettings(false);
-      setShowVideoConverter(false);
-      setVideoFile(null);
-      setVideoFormat("mp4");
-      setVideoQuality("high");
-      setVideoAdvancedSettings({
-        resolution: "Keep Original",
-        frameRate: "Keep Original",
-        codec: "H.264",
-        keepAudio: true,
-      });
-      setShowVideoAdvanced(false);
-      setVideoConverting(false);
-      setVideoProgress(0);
-      setVideoConversionStatus('idle');
-      setShowPortKiller(false);
-      setPortInput("");
-      setPortProcesses([]);
-      setScannedPort(null);
-      setCurrencyResult(null);
-      setLastCurrencyQuery("");
-      setShowTranslation(false);
-      setTranslationInput("");
-      setTranslationOutput("");
-      setDetectedLanguage("Detecting...");
-      setTranslationError(null);
-      setIsTranslationSettingsOpen(false);
-      setShowColorPicker(false);
-      setPickedColor(null);
-      setCopiedFormat(null);
-      setShowQRGenerator(false);
-      setQRType("url");
-      setQRData({ ...DEFAULT_QR_DATA });
-      setQRForegroundColor("#000000");
-      setQRBackgroundColor("#FFFFFF");
-      setShowQRCustomization(false);
-      setQRImageDataUrl("");
-      setQRCopied(false);
-      setShowRegexTester(false);
-      // Only reset Git Downloader if not actively downloading
-      if (!gitDownloadingRef.current) {
+    if (!showVideoConverter) return;
+
+    const handleBlur = () => {
+      if (!isDraggingRef.current && !isDialogOpenRef.current) {
+        setShowVideoConverter(false);
+        setVideoFile(null);
+        setVideoBatch([]);
+        setVideoFormat("mp4");
+        setVideoQuality("high");
+        setVideoAdvancedSettings({
+          resolution: "Keep Original",
+          frameRate: "Keep Original",
+          codec: "H.264",
+          keepAudio: true,
+        });
+        setShowVideoAdvanced(false);
+        setVideoConverting(false);
+        setVideoProgress(0);
+        setVideoConversionStatus('idle');
+      }
+    };
+
+    window.addEventListener("blur", handleBlur);
+
+    return () => {
+      window.removeEventListener("blur", handleBlur);
+    };
+  }, [showVideoConverter]);
+
+  // Port Killer blur handler (prevents flickering on reopen)
+  useEffect(() => {
+    if (!showPortKiller) return;
+
+    const handleBlur = () => {
+      if (!isDraggingRef.current) {
+        setShowPortKiller(false);
+        setPortInput("");
+        setPortProcesses([]);
+        setScannedPort(null);
+      }
+    };
+
+    window.addEventListener("blur", handleBlur);
+
+    return () => {
+      window.removeEventListener("blur", handleBlur);
+    };
+  }, [showPortKiller]);
+
+  // Regex Tester blur handler (prevents flickering on reopen)
+  useEffect(() => {
+    if (!showRegexTester) return;
+
+    const handleBlur = () => {
+      if (!isDraggingRef.current) {
+        setShowRegexTester(false);
+      }
+    };
+
+    window.addEventListener("blur", handleBlur);
+
+    return () => {
+      window.removeEventListener("blur", handleBlur);
+    };
+  }, [showRegexTester]);
+
+  // Git Downloader blur handler
+  // Don't reset if download is in progress - keep state so user can return to see progress
+  useEffect(() => {
+    if (!showGitDownloader) return;
+
+    const handleBlur = () => {
+      // Don't reset if download is actively in progress
+      const isDownloading = gitProgress.stage === 'fetching' ||
+                           gitProgress.stage === 'downloading' ||
+                           gitProgress.stage === 'extracting';
+
+      if (!isDraggingRef.current && !isDialogOpenRef.current && !isDownloading) {
         setShowGitDownloader(false);
         setGitUrlInput("");
         setGitParsedUrl(null);
@@ -670,1545 +3145,1923 @@ function App() {
           message: '',
         });
       }
-      // Always close YouTube Downloader panel, but don't reset state if downloading
-      // (download continues in background, user returns to command palette)
-      setShowYouTubeDownloader(false);
-      inputRef.current?.focus();
-    });
+    };
 
-    inputRef.current?.focus();
+    window.addEventListener("blur", handleBlur);
 
     return () => {
-      unlisten.then((fn) => fn());
+      window.removeEventListener("blur", handleBlur);
     };
-  }, []);
+  }, [showGitDownloader, gitProgress.stage]);
 
-  // Listen for quick translation hotkey trigger
+  // YouTube Downloader blur handler
+  // Close panel on blur, but preserve state if download is in progress
   useEffect(() => {
-    const unlisten = listen("trigger-quick-translation", async () => {
-      // Reset translation state
-      setTranslationInput("");
-      setTranslationOutput("");
-      setDetectedLanguage("Detecting...");
-      setTranslationError(null);
-      setIsTranslating(false);
-      // Close any open panels first
-      setShowSettings(false);
-      setShowVideoConverter(false);
-      setShowPortKiller(false);
-      setShowColorPicker(false);
-      setShowQRGenerator(false);
-      setShowRegexTester(false);
-      setShowGitDownloader(false);
-
-      try {
-        // Start text selection mode using the app handle version (works from hotkey context)
-        await invoke("start_text_selection_from_hotkey");
-
-        // Read the copied text from clipboard
-        const clipboardText = await readText();
-
-        if (clipboardText && clipboardText.trim()) {
-          setTranslationInput(clipboardText);
-          setShowTranslation(true);
+    if (!showYouTubeDownloader) return;
 
-          // Start translation immediately using the saved target language
-          setIsTranslating(true);
-          try {
-            const result = await invoke<TranslationResult>("translate_text", {
-              text: clipboardText,
-              targetLang: settings.quick_translation_target_language,
-            });
-            setTranslationOutput(result.translated_text);
-            setDetectedLanguage(result.detected_language);
-            setTargetLanguage(settings.quick_translation_target_language);
-            setIsTranslating(false);
-          } catch (translationErr) {
-            setTranslationError(String(translationErr));
-            setIsTranslating(false);
-          }
-        } else {
-          // No text was selected/copied
-          setShowTranslation(true);
-          setTranslationInput("");
-          setDetectedLanguage("No text selected");
-          setTargetLanguage(settings.quick_translation_target_language);
-        }
-      } catch (e) {
-        // User cancelled (pressed Escape), don't show translation window
-        if (e !== "Cancelled") {
-          console.error("Text selection error:", e);
-        }
+    const handleBlur = () => {
+      if (isDraggingRef.current || isDialogOpenRef.current) return;
+
+      const isDownloading = ytProgress.stage === 'downloading';
+
+      // Always close the panel
+      setShowYouTubeDownloader(false);
+
+      // Only reset state if not downloading (download continues in background)
+      if (!isDownloading) {
+        setYtUrlInput("");
+        setYtParsedUrl(null);
+        setYtVideoInfo(null);
+        setYtDownloadPath("");
+        setYtDownloadOptions({
+          quality: 'best',
+          mode: 'video_audio',
+        });
+        setYtProgress({
+          stage: 'idle',
+          percent: 0,
+          message: '',
+        });
       }
-    });
+    };
+
+    window.addEventListener("blur", handleBlur);
 
     return () => {
-      unlisten.then((fn) => fn());
+      window.removeEventListener("blur", handleBlur);
     };
-  }, [settings.quick_translation_target_language]);
+  }, [showYouTubeDownloader, ytProgress.stage]);
 
+  // Command palette blur handler (hides window on blur)
   useEffect(() => {
-    if (query.trim() === "") {
-      setFilteredTools(tools);
-      setSelectedIndex(0);
-      setQuickResult(null);
-      setCurrencyResult(null);
-      setLastCurrencyQuery("");
-    } else {
-      const q = query.toLowerCase();
-      const filtered = tools.filter(
-        (tool) =>
-          tool.name.toLowerCase().includes(q) ||
-          tool.description.toLowerCase().includes(q) ||
-          tool.keywords.some((k) => k.includes(q))
-      );
-      setFilteredTools(filtered);
-      setSelectedIndex(0);
+    // Only handle blur when command palette is visible (no other panel is open)
+    const isCommandPaletteVisible = !showVideoConverter && !showPortKiller && !showTranslation && !showSettings && !showColorPicker && !showQRGenerator && !showRegexTester && !showGitDownloader && !showYouTubeDownloader;
+    if (!isCommandPaletteVisible) return;
 
-      // Check for calculator result first
-      const calcResult = evaluateExpression(query);
-      if (calcResult) {
-        setQuickResult({
-          type: "calculator",
-          query: query,
-          result: calcResult,
-          icon: Calculator,
-          copyValue: calcResult.replace(/\./g, "").replace(/,/g, "."), // Convert from de-DE format to number
-        });
-        setCurrencyResult(null);
-        return;
+    const handleBlur = async () => {
+      if (!isDraggingRef.current) {
+        await invoke("hide_window");
       }
+    };
 
-      // Check for color conversion
-      const colorResult = parseColorQuery(query);
-      if (colorResult) {
-        const hexColor = rgbToHex(colorResult.rgb.r, colorResult.rgb.g, colorResult.rgb.b);
-        setQuickResult({
-          type: "color",
-          query: colorResult.displayQuery,
-          result: colorResult.result,
-          icon: Palette,
-          copyValue: colorResult.result,
-          colorPreview: hexColor,
-        });
-        setCurrencyResult(null);
-        return;
+    window.addEventListener("blur", handleBlur);
+
+    return () => {
+      window.removeEventListener("blur", handleBlur);
+    };
+  }, [showVideoConverter, showPortKiller, showTranslation, showSettings, showColorPicker, showQRGenerator, showRegexTester, showGitDownloader, showYouTubeDownloader]);
+
+  // Port killer functions
+  const handleScanPort = async (port: number) => {
+    setIsScanning(true);
+    setScannedPort(port);
+    try {
+      const processes = await invoke<PortProcess[]>("scan_port", { port });
+      setPortProcesses(processes);
+    } catch (e) {
+      console.error("Scan port error:", e);
+      setPortProcesses([]);
+    } finally {
+      setIsScanning(false);
+    }
+  };
+
+  // Lock / sleep / restart / shutdown tools, reporting failures in the status line
+  const runPowerAction = async (command: string, args?: Record<string, unknown>) => {
+    setQuery("");
+    try {
+      await invoke(command, args);
+    } catch (e) {
+      setStatus(String(e));
+      setTimeout(() => setStatus(null), 2000);
+    }
+  };
+
+  // Kill a process, asking before the UAC / polkit prompt if it belongs to SYSTEM or another user.
+  // Rejects with a message string, like invoke does.
+  const killProcess = async (pid: number, name?: string) => {
+    try {
+      await invoke("kill_port_process", { pid });
+    } catch (e) {
+      const error = e as KillProcessError;
+      if (!error.requires_elevation) {
+        throw String(error.message ?? e);
       }
+      const confirmed = await ask(
+        `${name ?? `Process ${pid}`} is running as another user or a system service. Kill it with administrator rights?`,
+        { title: "Administrator Permission", kind: "warning" }
+      );
+      if (!confirmed) {
+        throw "Not killed - administrator permission needed";
+      }
+      try {
+        await invoke("kill_port_process", { pid, elevate: true });
+      } catch (e) {
+        throw String((e as KillProcessError).message ?? e);
+      }
+    }
+  };
 
-      // Check for unit conversion
-      const unitResult = parseUnitQuery(query);
-      if (unitResult) {
-        const formattedResult = unitResult.result.toLocaleString("en-US", {
-          minimumFractionDigits: 0,
-          maximumFractionDigits: 3,
-        });
-        setQuickResult({
-          type: "unit",
-          query: query,
-          result: `${formattedResult} ${unitResult.toUnit}`,
-          icon: Ruler,
-          copyValue: unitResult.result.toString(),
-        });
-        setCurrencyResult(null);
-        return;
+  const handleKillProcess = async (pid: number) => {
+    try {
+      await killProcess(pid, portProcesses.find((p) => p.pid === pid)?.name);
+      // Re-scan to update the list
+      if (scannedPort !== null) {
+        await handleScanPort(scannedPort);
       }
+      setStatus("Process killed");
+      setTimeout(() => setStatus(null), 2000);
+    } catch (e) {
+      console.error("Kill process error:", e);
+      setStatus(`Failed: ${e}`);
+      setTimeout(() => setStatus(null), 3000);
+    }
+  };
 
-      // Check for currency query
-      const currencyQuery = parseCurrencyQuery(query);
-      if (currencyQuery && query !== lastCurrencyQuery) {
-        setLastCurrencyQuery(query);
-        setCurrencyLoading(true);
-        setQuickResult(null);
-        invoke<CurrencyResult>("convert_currency", {
-          amount: currencyQuery.amount,
-          from: currencyQuery.from,
-          to: currencyQuery.to,
-        })
-          .then((result) => {
-            setCurrencyResult(result);
-            setCurrencyLoading(false);
-            // Set quick result for currency
-            const formattedResult = result.result.toLocaleString("en-US", {
-              minimumFractionDigits: 2,
-              maximumFractionDigits: 2,
-            });
-            setQuickResult({
-              type: "currency",
-              query: query,
-              result: `${formattedResult} ${result.to}`,
-              icon: DollarSign,
-              copyValue: result.result.toFixed(2),
-            });
-          })
-          .catch(() => {
-            setCurrencyResult(null);
-            setCurrencyLoading(false);
-            setQuickResult(null);
-          });
-      } else if (currencyQuery && currencyResult) {
-        // Keep existing currency quick result
-        const formattedResult = currencyResult.result.toLocaleString("en-US", {
-          minimumFractionDigits: 2,
-          maximumFractionDigits: 2,
-        });
-        setQuickResult({
-          type: "currency",
-          query: query,
-          result: `${formattedResult} ${currencyResult.to}`,
-          icon: DollarSign,
-          copyValue: currencyResult.result.toFixed(2),
-        });
-      } else if (!currencyQuery) {
-        setCurrencyResult(null);
+  // Handle window dragging - only drag from elements with data-drag-region
+  const handleDragStart = async (e: React.MouseEvent) => {
+    // Only start drag if clicking directly on a drag region (not on buttons inside it)
+    const target = e.target as HTMLElement;
+    if (!target.closest('[data-drag-region]') || target.closest('button, input')) return;
 
-        // No exact match found - check for partial queries and show preview
-        // Check for partial unit query (e.g., "10 fahr" → "10 fahrenheit to celsius")
-        const partialUnit = parsePartialUnitQuery(query);
-        if (partialUnit) {
-          // Compute the result for the suggested query
-          const suggestedResult = parseUnitQuery(partialUnit.suggestedQuery);
-          if (suggestedResult) {
-            const formattedResult = suggestedResult.result.toLocaleString("en-US", {
-              minimumFractionDigits: 0,
-              maximumFractionDigits: 3,
-            });
-            setQuickResult({
-              type: "unit",
-              query: partialUnit.suggestedQuery,
-              result: `${formattedResult} ${suggestedResult.toUnit}`,
-              icon: Ruler,
-              copyValue: suggestedResult.result.toString(),
-              isPreview: true,
-            });
-            return;
-          }
-        }
+    // Set dragging flag to prevent auto-hide during drag
+    isDraggingRef.current = true;
+    await invoke("set_dragging", { dragging: true });
 
-        // Check for partial currency query (e.g., "10 yen" → "10 yen to usd")
-        const partialCurrency = parsePartialCurrencyQuery(query);
-        if (partialCurrency) {
-          // Show loading state for currency preview
-          setCurrencyLoading(true);
-          invoke<CurrencyResult>("convert_currency", {
-            amount: partialCurrency.amount,
-            from: partialCurrency.from,
-            to: partialCurrency.to,
-          })
-            .then((result) => {
-              setCurrencyLoading(false);
-              const formattedResult = result.result.toLocaleString("en-US", {
-                minimumFractionDigits: 2,
-                maximumFractionDigits: 2,
-              });
-              setQuickResult({
-                type: "currency",
-                query: partialCurrency.suggestedQuery,
-                result: `${formattedResult} ${result.to}`,
-                icon: DollarSign,
-                copyValue: result.result.toFixed(2),
-                isPreview: true,
-              });
-            })
-            .catch(() => {
-              setCurrencyLoading(false);
-              setQuickResult(null);
-            });
-          return;
-        }
+    try {
+      await getCurrentWindow().startDragging();
+    } finally {
+      // Clear dragging flag after drag ends
+      isDraggingRef.current = false;
+      await invoke("set_dragging", { dragging: false });
+    }
+  };
+
+  // Handle video file selection via dialog
+  const handleVideoSelectFile = async () => {
+    // Mark dialog as open to prevent blur handler from closing
+    isDialogOpenRef.current = true;
+    await invoke("set_auto_hide", { enabled: false });
+
+    const result = await open({
+      filters: VIDEO_FILE_FILTERS,
+      multiple: true,
+    });
 
-        setQuickResult(null);
-      }
+    // Re-enable after dialog closes
+    await invoke("set_auto_hide", { enabled: true });
+    isDialogOpenRef.current = false;
+
+    const paths = Array.isArray(result) ? result : result ? [result] : [];
+    if (paths.length > 0) {
+      await loadVideoFile(paths[0]);
+      setVideoBatch(paths.length > 1 ? paths : []);
     }
-  }, [query]);
+  };
 
-  // Scroll selected tool into view when navigating with arrow keys
-  useEffect(() => {
-    const selectedElement = toolItemRefs.current[selectedIndex];
-    if (selectedElement) {
-      selectedElement.scrollIntoView({
-        behavior: 'smooth',
-        block: 'nearest'
+  // Load a video (from the dialog or the file manager) with its metadata
+  const loadVideoFile = async (path: string) => {
+    const name = path.split(/[\\/]/).pop() || "";
+
+    // Get video metadata from backend
+    try {
+      const metadata = await invoke<{
+        duration: number;
+        size: number;
+        width: number;
+        height: number;
+        frame_rate: number;
+        codec: string;
+      }>("get_video_metadata", { path });
+
+      setVideoFile({
+        name,
+        path,
+        size: metadata.size,
+        duration: metadata.duration,
+        width: metadata.width,
+        height: metadata.height,
+        frameRate: metadata.frame_rate,
+        codec: metadata.codec,
+      });
+    } catch (e) {
+      console.error("Failed to get video metadata:", e);
+      // Still allow selection with minimal info
+      setVideoFile({
+        name,
+        path,
+        size: 0,
+        duration: 0,
+        width: 0,
+        height: 0,
+        frameRate: 0,
+        codec: "unknown",
       });
     }
-  }, [selectedIndex]);
+  };
 
-  // Calculate math expression result for command-only mode
-  const calcResult = useMemo(() => {
-    if (!settings.command_only_mode || !query.trim()) return null;
-    return evaluateExpression(query);
-  }, [settings.command_only_mode, query]);
+  // Convert every picked file into `outputDir` through the backend's queue, resolving once the
+  // queue is done (with an error if any of them failed)
+  const convertVideoBatch = async (outputDir: string, options: Record<string, unknown>) => {
+    const ext = videoFormat.toLowerCase();
+    const separator = outputDir.includes("\\") ? "\\" : "/";
+    const items = videoBatch.map((inputPath) => {
+      const name = (inputPath.split(/[\\/]/).pop() || "video").replace(/\.[^.]+$/, "");
+      let outputPath = `${outputDir}${separator}${name}.${ext}`;
+      // Never write over a source file
+      if (outputPath === inputPath) {
+        outputPath = `${outputDir}${separator}${name}-converted.${ext}`;
+      }
+      return { input_path: inputPath, output_path: outputPath };
+    });
 
-  // Window size configuration based on active view
-  // In command only mode, window always stays compact (no tool list shown)
-  const isCommandOnlyCollapsed = settings.command_only_mode;
-  const windowSizeConfig = useMemo(() => {
-    if (showVideoConverter) {
-      return { width: 900, minHeight: 400, maxHeight: 900 };
-    } else if (showPortKiller) {
-      return { width: 680, minHeight: 300, maxHeight: 600 };
-    } else if (showTranslation) {
-      return { width: 680, minHeight: 300, maxHeight: 600 };
-    } else if (showSettings) {
-      return { width: 680, minHeight: 200, maxHeight: 550 };
-    } else if (showColorPicker) {
-      return { width: 880, minHeight: 400, maxHeight: 700 };
-    } else if (showQRGenerator) {
-      return { width: showQRCustomization ? 1000 : 800, minHeight: 400, maxHeight: 700 };
-    } else if (showRegexTester) {
-      return { width: 1000, minHeight: 400, maxHeight: 700 };
-    } else if (showGitDownloader) {
-      return { width: 720, minHeight: 350, maxHeight: 720 };
-    } else if (showYouTubeDownloader) {
-      return { width: 720, minHeight: 400, maxHeight: 800 };
+    let finish: (queue: ConversionQueueState) => void = () => {};
+    const finished = new Promise<ConversionQueueState>((resolve) => {
+      finish = resolve;
+    });
+    const unlisten = await listen<ConversionQueueState>("conversion-queue-changed", (event) => {
+      setVideoProgress(event.payload.percent);
+      if (event.payload.pending === 0) {
+        finish(event.payload);
+      }
+    });
+    try {
+      await invoke("enqueue_conversion", { items, options });
+      const queue = await finished;
+      if (queue.failed > 0) {
+        throw `${queue.failed} of ${queue.items.length} conversions failed or were cancelled`;
+      }
+    } finally {
+      unlisten();
     }
-    // Command palette - use smaller height in command only mode when collapsed
-    if (isCommandOnlyCollapsed) {
-      return { width: 680, minHeight: 62, maxHeight: 70 };
+  };
+
+  // Handle video conversion
+  const handleVideoConvert = async () => {
+    if (!videoFile) return;
+
+    const ext = videoFormat.toLowerCase();
+    const defaultName = videoFile.name.replace(/\.[^.]+$/, `.${ext}`);
+    const isBatch = videoBatch.length > 1;
+
+    // Mark dialog as open to prevent blur handler from closing
+    isDialogOpenRef.current = true;
+    await invoke("set_auto_hide", { enabled: false });
+
+    // Several files go to a folder, named after their sources
+    const outputPath = isBatch
+      ? await pickOutputDirectory("Save converted videos to")
+      : await pickSavePath(defaultName, [{ name: videoFormat.toUpperCase(), extensions: [ext] }]);
+
+    // Re-enable after dialog closes
+    await invoke("set_auto_hide", { enabled: true });
+    isDialogOpenRef.current = false;
+
+    if (!outputPath) return;
+
+    setVideoConverting(true);
+    setVideoProgress(0);
+    setVideoConversionStatus('converting');
+
+    // Get bitrate and resolution from selected quality preset
+    const selectedPreset = VIDEO_QUALITY_PRESETS.find(p => p.id === videoQuality);
+    const bitrate = selectedPreset?.bitrate || 0;
+
+    // Use quality preset resolution if Advanced Settings is "Keep Original" and preset has a specific resolution
+    let resolution = videoAdvancedSettings.resolution;
+    if (resolution === "Keep Original" && selectedPreset && selectedPreset.resolution !== "original") {
+      // Convert preset resolution format (e.g., "1920x1080" -> "1080p", "1280x720" -> "720p")
+      const resolutionMap: Record<string, string> = {
+        "3840x2160": "4K",
+        "1920x1080": "1080p",
+        "1280x720": "720p",
+        "854x480": "480p",
+      };
+      resolution = resolutionMap[selectedPreset.resolution] || "Keep Original";
     }
-    return { width: 680, minHeight: 200, maxHeight: 550 };
-  }, [showVideoConverter, showPortKiller, showTranslation, showSettings, showColorPicker, showQRGenerator, showQRCustomization, showRegexTester, showGitDownloader, showYouTubeDownloader, isCommandOnlyCollapsed]);
 
-  // Auto-resize window based on content
-  const { contentRef, measureAndResize } = useWindowAutoSize<HTMLDivElement>({
-    config: windowSizeConfig,
-    enabled: true,
-  });
+    const options = {
+      resolution,
+      frame_rate: videoAdvancedSettings.frameRate,
+      codec: videoAdvancedSettings.codec,
+      keep_audio: videoAdvancedSettings.keepAudio,
+      bitrate,
+    };
 
-  // Force window resize when switching between views
-  // This handles cases where the content changes but config values are similar
-  useEffect(() => {
-    // Give React time to re-render the new view
-    const timeout = setTimeout(() => {
-      measureAndResize();
-    }, 50);
-    return () => clearTimeout(timeout);
-  }, [showSettings, settings.command_only_mode, measureAndResize]);
+    try {
+      if (isBatch) {
+        await convertVideoBatch(outputPath, options);
+      } else {
+        await invoke("convert_video", {
+          inputPath: videoFile.path,
+          outputPath,
+          options,
+        });
+      }
 
-  const executeTool = async (tool: Tool) => {
-    if (tool.isSettings) {
-      setShowSettings(true);
-      setQuery("");
-    } else if (tool.action) {
-      setQuery("");
-      await tool.action();
+      // Show success state
+      setVideoConversionStatus('success');
+      setVideoProgress(100);
+
+      // Wait a moment to show success, then close
+      setTimeout(async () => {
+        await invoke("set_auto_hide", { enabled: true });
+        setShowVideoConverter(false);
+        setVideoFile(null);
+        setVideoBatch([]);
+        setVideoFormat("mp4");
+        setVideoQuality("high");
+        setVideoAdvancedSettings({
+          resolution: "Keep Original",
+          frameRate: "Keep Original",
+          codec: "H.264",
+          keepAudio: true,
+        });
+        setShowVideoAdvanced(false);
+        setVideoConverting(false);
+        setVideoConversionStatus('idle');
+        setVideoProgress(0);
+      }, 1500);
+    } catch (e) {
+      console.error("Video conversion error:", e);
+      setVideoConversionStatus('error');
+      setStatus(String(e));
+
+      // Reset after showing error
+      setTimeout(() => {
+        setVideoConverting(false);
+        setVideoConversionStatus('idle');
+        setVideoProgress(0);
+        setStatus(null);
+      }, 4000);
     }
   };
 
-  const handleKeyDown = async (e: React.KeyboardEvent) => {
-    // Escape is handled by the global handler
-    if (e.key === "Escape") return;
+  // Handle video converter reset
+  const handleVideoReset = () => {
+    setVideoFile(null);
+    setVideoBatch([]);
+    setVideoFormat("mp4");
+    setVideoQuality("high");
+    setVideoAdvancedSettings({
+      resolution: "Keep Original",
+      frameRate: "Keep Original",
+      codec: "H.264",
+      keepAudio: true,
+    });
+    setShowVideoAdvanced(false);
+    setVideoConverting(false);
+    setVideoProgress(0);
+    setVideoConversionStatus('idle');
+  };
 
-    // Handle navigation only in command palette mode
-    if (!showSettings && !showVideoConverter && !showPortKiller && !showTranslation && !showQRGenerator && !showRegexTester && !showGitDownloader && !showYouTubeDownloader) {
-      // Command-only mode: handle Enter and history navigation
-      if (settings.command_only_mode) {
-        if (e.key === "Enter" && query.trim()) {
-          e.preventDefault();
-          // If there's a calculator result, copy it to clipboard
-          if (calcResult) {
-            try {
-              await writeText(calcResult);
-              setQuery("");
-              setCommandStatus({ message: `Copied ${calcResult}`, type: 'success' });
-              if (commandStatusTimeoutRef.current) {
-                clearTimeout(commandStatusTimeoutRef.current);
-              }
-              commandStatusTimeoutRef.current = setTimeout(() => {
-                setCommandStatus({ message: "Type a command...", type: 'idle' });
-              }, 1500);
-            } catch (err) {
-              console.error("Failed to copy:", err);
-            }
-          } else {
-            // Otherwise execute as command
-            const commandToExecute = query;
-            setQuery(""); // Clear input immediately
-            await executeCommand(commandToExecute);
-          }
-        } else if (e.key === "ArrowUp") {
-          e.preventDefault();
-          if (commandHistory.length > 0) {
-            const newIndex = historyIndex < commandHistory.length - 1 ? historyIndex + 1 : historyIndex;
-            setHistoryIndex(newIndex);
-            setQuery(commandHistory[newIndex] || "");
-          }
-        } else if (e.key === "ArrowDown") {
-          e.preventDefault();
-          if (historyIndex > 0) {
-            const newIndex = historyIndex - 1;
-            setHistoryIndex(newIndex);
-            setQuery(commandHistory[newIndex] || "");
-          } else if (historyIndex === 0) {
-            setHistoryIndex(-1);
-            setQuery("");
-          }
-        }
-        return;
-      }
+  // Git Downloader handlers
+  const handleGitUrlChange = (url: string) => {
+    setGitUrlInput(url);
+    const parsed = parseGitHubUrl(url);
+    setGitParsedUrl(parsed);
+  };
 
-      // Normal mode: tool navigation
-      if (e.key === "ArrowDown") {
-        e.preventDefault();
-        setSelectedIndex((prev) =>
-          prev < filteredTools.length - 1 ? prev + 1 : prev
-        );
-      } else if (e.key === "ArrowUp") {
-        e.preventDefault();
-        setSelectedIndex((prev) => (prev > 0 ? prev - 1 : prev));
-      } else if (e.key === "Enter" && filteredTools.length > 0) {
-        e.preventDefault();
-        await executeTool(filteredTools[selectedIndex]);
+  const handleGitPaste = async () => {
+    try {
+      const clipboardText = await readText();
+      if (clipboardText) {
+        handleGitUrlChange(clipboardText);
       }
+    } catch (e) {
+      console.error("Failed to read clipboard:", e);
     }
   };
 
-  const handleHotkeyKeyDown = (e: React.KeyboardEvent) => {
-    e.preventDefault();
-    e.stopPropagation();
+  // Save the recent picks as a palette; the chosen extension decides the format
+  // Keep a calculator result on the tape; `formatted` is the de-DE display form
+  const recordCalculation = (expression: string, formatted: string) => {
+    const result = Number(formatted.replace(/\./g, "").replace(/,/g, "."));
+    invoke<CalcHistory>("record_calculation", { expression, result }).then(setCalcHistory).catch(() => {});
+  };
+
+  // Returns the saved path, or null if the dialog was cancelled
+  const exportTimeEntries = async (range: TimeReportRange | null): Promise<string | null> => {
+    isDialogOpenRef.current = true;
+    await invoke("set_auto_hide", { enabled: false });
+    const path = await pickSavePath(
+      range ? `time-${range.replace("_", "-")}.csv` : "time-entries.csv",
+      [{ name: "CSV", extensions: ["csv"] }]
+    );
+    await invoke("set_auto_hide", { enabled: true });
+    isDialogOpenRef.current = false;
+    if (!path) return null;
+    return invoke<string>("export_time_entries", { path, range });
+  };
+
+  const exportCalcTape = async (): Promise<string | null> => {
+    isDialogOpenRef.current = true;
+    await invoke("set_auto_hide", { enabled: false });
+    const path = await pickSavePath("calculator-tape.txt", [{ name: "Text", extensions: ["txt"] }]);
+    await invoke("set_auto_hide", { enabled: true });
+    isDialogOpenRef.current = false;
+    if (!path) return null;
+    return invoke<string>("export_calc_tape", { path });
+  };
 
-    if (e.key === "Escape") {
-      setIsRecordingHotkey(false);
-      return;
-    }
+  const handleExportPalette = async () => {
+    isDialogOpenRef.current = true;
+    await invoke("set_auto_hide", { enabled: false });
 
-    const modifiers: string[] = [];
-    if (e.altKey) modifiers.push("Alt");
-    if (e.ctrlKey) modifiers.push("Ctrl");
-    if (e.shiftKey) modifiers.push("Shift");
-    if (e.metaKey) modifiers.push("Win");
+    const path = await pickSavePath("palette.ase", [
+      { name: "Adobe Swatch Exchange", extensions: ["ase"] },
+      { name: "GIMP Palette", extensions: ["gpl"] },
+      { name: "CSS Variables", extensions: ["css"] },
+      { name: "SCSS Variables", extensions: ["scss"] },
+      { name: "JSON Design Tokens", extensions: ["json"] },
+    ]);
 
-    // Use e.code for reliable key detection (works better with Alt combinations)
-    const code = e.code;
-    let mappedKey = "";
+    await invoke("set_auto_hide", { enabled: true });
+    isDialogOpenRef.current = false;
 
-    // Handle letter keys (KeyA, KeyB, etc.)
-    if (code.startsWith("Key")) {
-      mappedKey = code.substring(3); // "KeyQ" -> "Q"
-    }
-    // Handle digit keys (Digit0, Digit1, etc.)
-    else if (code.startsWith("Digit")) {
-      mappedKey = code.substring(5); // "Digit1" -> "1"
-    }
-    // Handle numpad keys
-    else if (code.startsWith("Numpad") && code.length > 6) {
-      const numpadPart = code.substring(6);
-      if (/^\d$/.test(numpadPart)) {
-        mappedKey = "Num" + numpadPart;
-      } else {
-        mappedKey = "Num" + numpadPart;
-      }
-    }
-    // Handle function keys
-    else if (/^F\d{1,2}$/.test(code)) {
-      mappedKey = code;
-    }
-    // Handle special keys
-    else {
-      const specialKeys: Record<string, string> = {
-        Space: "Space",
-        Enter: "Enter",
-        Tab: "Tab",
-        Backspace: "Backspace",
-        Delete: "Delete",
-        Insert: "Insert",
-        Home: "Home",
-        End: "End",
-        PageUp: "PageUp",
-        PageDown: "PageDown",
-        ArrowUp: "Up",
-        ArrowDown: "Down",
-        ArrowLeft: "Left",
-        ArrowRight: "Right",
-        Backquote: "`",
-        Minus: "-",
-        Equal: "=",
-        BracketLeft: "[",
-        BracketRight: "]",
-        Backslash: "\\",
-        Semicolon: ";",
-        Quote: "'",
-        Comma: ",",
-        Period: ".",
-        Slash: "/",
-      };
-      mappedKey = specialKeys[code] || "";
+    if (!path) return;
+    const extension = path.split(".").pop()?.toLowerCase() ?? "";
+    const format: PaletteFormat = (["ase", "gpl", "css", "scss", "json"] as const).find((f) => f === extension) ?? "ase";
+    try {
+      // History is newest first; palettes read better in pick order
+      const colors = colorHistory.map((entry) => entry.hex).reverse();
+      await invoke("export_palette", { colors, format, path });
+      setStatus(`Saved ${colors.length} colors`);
+    } catch (e) {
+      setStatus(String(e));
     }
+    setTimeout(() => setStatus(null), 2000);
+  };
 
-    // Skip if only modifiers were pressed or no valid key
-    if (!mappedKey || ["ControlLeft", "ControlRight", "AltLeft", "AltRight", "ShiftLeft", "ShiftRight", "MetaLeft", "MetaRight"].includes(code)) {
-      return;
-    }
+  const handleGitSelectFolder = async () => {
+    isDialogOpenRef.current = true;
+    await invoke("set_auto_hide", { enabled: false });
 
-    // Require at least one modifier for non-function keys
-    const isFunctionKey = /^F\d{1,2}$/.test(mappedKey);
-    if (modifiers.length > 0 || isFunctionKey) {
-      setSettings((prev) => ({
-        ...prev,
-        hotkey_modifiers: modifiers,
-        hotkey_key: mappedKey,
-      }));
-      setIsRecordingHotkey(false);
+    const result = await pickOutputDirectory("Download to");
+
+    await invoke("set_auto_hide", { enabled: true });
+    isDialogOpenRef.current = false;
+
+    if (result) {
+      setGitDownloadPath(result as string);
     }
   };
 
-  // Handle mouse buttons for hotkey recording (side buttons)
-  const handleHotkeyMouseDown = (e: React.MouseEvent) => {
-    // Only handle extra buttons (side buttons are typically 3 and 4)
-    if (e.button < 3) return;
-
-    e.preventDefault();
-    e.stopPropagation();
+  const handleGitDownload = async () => {
+    if (!gitParsedUrl || !gitDownloadPath) return;
 
-    const modifiers: string[] = [];
-    if (e.altKey) modifiers.push("Alt");
-    if (e.ctrlKey) modifiers.push("Ctrl");
-    if (e.shiftKey) modifiers.push("Shift");
-    if (e.metaKey) modifiers.push("Win");
+    setGitProgress({
+      stage: 'fetching',
+      percent: 0,
+      message: 'Connecting to GitHub...',
+    });
 
-    // Map mouse buttons to names
-    const mouseButtonNames: Record<number, string> = {
-      3: "Mouse4", // Back button
-      4: "Mouse5", // Forward button
-    };
+    try {
+      const result = await invoke<GitDownloadResult>("download_github_folder", {
+        urlInfo: {
+          owner: gitParsedUrl.owner,
+          repo: gitParsedUrl.repo,
+          branch: gitParsedUrl.branch,
+          path: gitParsedUrl.path,
+        },
+        outputPath: gitDownloadPath,
+        options: {
+          extract_files: gitDownloadOptions.extractFiles,
+          flatten_structure: gitDownloadOptions.flattenStructure,
+          create_subfolder: gitDownloadOptions.createSubfolder,
+        },
+      });
 
-    const buttonName = mouseButtonNames[e.button];
-    if (buttonName) {
-      setSettings((prev) => ({
-        ...prev,
-        hotkey_modifiers: modifiers,
-        hotkey_key: buttonName,
-      }));
-      setIsRecordingHotkey(false);
+      setGitProgress({
+        stage: 'complete',
+        percent: 100,
+        message: `Successfully downloaded ${result.files_count} files`,
+        processedFiles: result.files_count,
+        outputPath: result.output_path,
+      });
+    } catch (e) {
+      setGitProgress({
+        stage: 'error',
+        percent: 0,
+        message: '',
+        errorMessage: String(e),
+      });
     }
   };
 
-  // Quick Translation hotkey recording handler
-  const handleQuickTranslationHotkeyKeyDown = (e: React.KeyboardEvent) => {
-    e.preventDefault();
-    e.stopPropagation();
-
-    if (e.key === "Escape") {
-      setIsRecordingQuickTranslationHotkey(false);
-      return;
+  const handleGitOpenFolder = async () => {
+    const path = gitProgress.outputPath || gitDownloadPath;
+    if (path) {
+      try {
+        await invoke("open_folder_in_explorer", { path });
+      } catch (e) {
+        console.error("Failed to open folder:", e);
+      }
     }
+  };
 
-    const modifiers: string[] = [];
-    if (e.altKey) modifiers.push("Alt");
-    if (e.ctrlKey) modifiers.push("Ctrl");
-    if (e.shiftKey) modifiers.push("Shift");
-    if (e.metaKey) modifiers.push("Win");
+  const handleGitReset = async () => {
+    // Cancel any ongoing download
+    try {
+      await invoke("cancel_git_download");
+    } catch (e) {
+      // Ignore errors if no download in progress
+    }
 
-    const code = e.code;
-    let mappedKey = "";
+    setGitUrlInput("");
+    setGitParsedUrl(null);
+    setGitDownloadPath("");
+    setGitDownloadOptions({
+      extractFiles: true,
+      flattenStructure: false,
+      createSubfolder: true,
+    });
+    setGitProgress({
+      stage: 'idle',
+      percent: 0,
+      message: '',
+    });
+  };
 
-    if (code.startsWith("Key")) {
-      mappedKey = code.substring(3);
-    } else if (code.startsWith("Digit")) {
-      mappedKey = code.substring(5);
-    } else if (code.startsWith("Numpad") && code.length > 6) {
-      const numpadPart = code.substring(6);
-      if (/^\d$/.test(numpadPart)) {
-        mappedKey = "Num" + numpadPart;
-      } else {
-        mappedKey = "Num" + numpadPart;
-      }
-    } else if (/^F\d{1,2}$/.test(code)) {
-      mappedKey = code;
-    } else {
-      const specialKeys: Record<string, string> = {
-        Space: "Space",
-        Enter: "Enter",
-        Tab: "Tab",
-        Backspace: "Backspace",
-        Delete: "Delete",
-        Insert: "Insert",
-        Home: "Home",
-        End: "End",
-        PageUp: "PageUp",
-        PageDown: "PageDown",
-        ArrowUp: "Up",
-        ArrowDown: "Down",
-        ArrowLeft: "Left",
-        ArrowRight: "Right",
-        Backquote: "`",
-        Minus: "-",
-        Equal: "=",
-        BracketLeft: "[",
-        BracketRight: "]",
-        Backslash: "\\",
-        Semicolon: ";",
-        Quote: "'",
-        Comma: ",",
-        Period: ".",
-        Slash: "/",
-      };
-      mappedKey = specialKeys[code] || "";
-    }
+  // Command-only mode: Helper to resolve destination (full path or alias)
+  const resolveDestination = async (destination: string): Promise<{ path: string; learned: boolean } | null> => {
+    // Check if it looks like a full path
+    const isFullPath = await invoke<boolean>("is_valid_path", { path: destination });
 
-    if (!mappedKey || ["ControlLeft", "ControlRight", "AltLeft", "AltRight", "ShiftLeft", "ShiftRight", "MetaLeft", "MetaRight"].includes(code)) {
-      return;
+    if (isFullPath) {
+      // It's a full path - learn it as an alias for future use
+      try {
+        await invoke<string>("learn_path_alias", { path: destination });
+      } catch (e) {
+        // Path might not exist yet, that's ok
+      }
+      return { path: destination, learned: true };
     }
 
-    const isFunctionKey = /^F\d{1,2}$/.test(mappedKey);
-    if (modifiers.length > 0 || isFunctionKey) {
-      setSettings((prev) => ({
-        ...prev,
-        quick_translation_hotkey_modifiers: modifiers,
-        quick_translation_hotkey_key: mappedKey,
-      }));
-      setIsRecordingQuickTranslationHotkey(false);
+    // Try to resolve as an alias
+    const resolved = await invoke<string | null>("resolve_path_alias", { alias: destination });
+    if (resolved) {
+      return { path: resolved, learned: false };
     }
+
+    return null;
   };
 
-  // Quick Translation hotkey mouse button handler
-  const handleQuickTranslationHotkeyMouseDown = (e: React.MouseEvent) => {
-    if (e.button < 3) return;
+  // Parse time duration strings like "5m", "30 seconds", "1h30m", "90"
+  const parseTimeDuration = (input: string): number | null => {
+    const normalized = input.toLowerCase().trim();
 
-    e.preventDefault();
-    e.stopPropagation();
+    // Try parsing compound durations like "1h30m" or "1 hour 30 minutes"
+    let totalSeconds = 0;
+    let matched = false;
 
-    const modifiers: string[] = [];
-    if (e.altKey) modifiers.push("Alt");
-    if (e.ctrlKey) modifiers.push("Ctrl");
-    if (e.shiftKey) modifiers.push("Shift");
-    if (e.metaKey) modifiers.push("Win");
+    // Pattern for hours
+    const hoursMatch = normalized.match(/(\d+)\s*(?:h|hour|hours)/);
+    if (hoursMatch) {
+      totalSeconds += parseInt(hoursMatch[1], 10) * 3600;
+      matched = true;
+    }
 
-    const mouseButtonNames: Record<number, string> = {
-      3: "Mouse4",
-      4: "Mouse5",
-    };
+    // Pattern for minutes
+    const minutesMatch = normalized.match(/(\d+)\s*(?:m|min|mins|minute|minutes)/);
+    if (minutesMatch) {
+      totalSeconds += parseInt(minutesMatch[1], 10) * 60;
+      matched = true;
+    }
 
-    const buttonName = mouseButtonNames[e.button];
-    if (buttonName) {
-      setSettings((prev) => ({
-        ...prev,
-        quick_translation_hotkey_modifiers: modifiers,
-        quick_translation_hotkey_key: buttonName,
-      }));
-      setIsRecordingQuickTranslationHotkey(false);
+    // Pattern for seconds
+    const secondsMatch = normalized.match(/(\d+)\s*(?:s|sec|secs|second|seconds)/);
+    if (secondsMatch) {
+      totalSeconds += parseInt(secondsMatch[1], 10);
+      matched = true;
     }
-  };
 
-  // Universal Escape key handler - works from any state
-  useEffect(() => {
-    const handleGlobalEscape = async (e: KeyboardEvent) => {
-      if (e.key !== "Escape") return;
+    if (matched) {
+      return totalSeconds > 0 ? totalSeconds : null;
+    }
 
-      // Don't handle if we're recording a hotkey (Escape cancels recording)
-      if (isRecordingHotkey) {
-        setIsRecordingHotkey(false);
-        return;
-      }
-      if (isRecordingQuickTranslationHotkey) {
-        setIsRecordingQuickTranslationHotkey(false);
-        return;
-      }
+    // Try bare number (treat as seconds)
+    const bareNumber = parseInt(normalized, 10);
+    if (!isNaN(bareNumber) && bareNumber > 0) {
+      return bareNumber;
+    }
 
-      // In command-only mode, cancel downloads in progress
-      if (settings.command_only_mode && commandStatus.type === 'progress') {
-        // Cancel any ongoing downloads
-        try {
-          await invoke("cancel_git_download");
-        } catch {
-          // Ignore - may not be a git download
-        }
-        try {
-          await invoke("cancel_youtube_download");
-        } catch {
-          // Ignore - may not be a youtube download
-        }
-        setCommandStatus({ message: "Cancelled", type: 'error' });
-        if (commandStatusTimeoutRef.current) {
-          clearTimeout(commandStatusTimeoutRef.current);
-        }
-        commandStatusTimeoutRef.current = setTimeout(() => {
-          setCommandStatus({ message: "Type a command...", type: 'idle' });
-        }, 1500);
-        return;
-      }
+    return null;
+  };
 
-      // Close panels in order of priority (most specific first)
-      if (showVideoConverter) {
-        setShowVideoConverter(false);
-        setVideoFile(null);
-        setVideoFormat("mp4");
-        setVideoQuality("high");
-        setVideoAdvancedSettings({
-          resolution: "Keep Original",
-          frameRate: "Keep Original",
-          codec: "H.264",
-          keepAudio: true,
-        });
-        setShowVideoAdvanced(false);
-        setVideoConverting(false);
-        setVideoProgress(0);
-        setVideoConversionStatus('idle');
-      } else if (showPortKiller) {
-        setShowPortKiller(false);
-        setPortInput("");
-        setPortProcesses([]);
-        setScannedPort(null);
-      } else if (showTranslation) {
-        setShowTranslation(false);
-      } else if (showSettings) {
-        setShowSettings(false);
-      } else if (showColorPicker) {
-        setShowColorPicker(false);
-        setPickedColor(null);
-        setCopiedFormat(null);
-      } else if (showQRGenerator) {
-        setShowQRGenerator(false);
-      } else if (showRegexTester) {
-        setShowRegexTester(false);
-      } else if (showGitDownloader) {
-        // Don't reset if download is actively in progress
-        const isDownloading = gitProgress.stage === 'fetching' ||
-                             gitProgress.stage === 'downloading' ||
-                             gitProgress.stage === 'extracting';
-        if (!isDownloading) {
-          setShowGitDownloader(false);
-          setGitUrlInput("");
-          setGitParsedUrl(null);
-          setGitDownloadPath("");
-          setGitDownloadOptions({
-            extractFiles: true,
-            flattenStructure: false,
-            createSubfolder: true,
-          });
-          setGitProgress({
-            stage: 'idle',
-            percent: 0,
-            message: '',
-          });
-        }
-      } else if (showYouTubeDownloader) {
-        const isDownloading = ytProgress.stage === 'downloading';
-        // Always close the panel
-        setShowYouTubeDownloader(false);
-        // Only reset state if not downloading (download continues in background)
-        if (!isDownloading) {
-          setYtUrlInput("");
-          setYtParsedUrl(null);
-          setYtVideoInfo(null);
-          setYtDownloadPath("");
-          setYtDownloadOptions({
-            quality: 'best',
-            mode: 'video_audio',
-          });
-          setYtProgress({
-            stage: 'idle',
-            percent: 0,
-            message: '',
-          });
-        }
-      } else {
-        // No panel open - hide the window
-        await invoke("hide_window");
-        setQuery("");
-      }
-    };
+  // Format seconds as MM:SS or HH:MM:SS
+  const formatTimerDisplay = (seconds: number): string => {
+    const h = Math.floor(seconds / 3600);
+    const m = Math.floor((seconds % 3600) / 60);
+    const s = seconds % 60;
 
-    window.addEventListener("keydown", handleGlobalEscape);
-    return () => window.removeEventListener("keydown", handleGlobalEscape);
-  }, [showVideoConverter, showPortKiller, showTranslation, showSettings, showColorPicker, showQRGenerator, showRegexTester, showGitDownloader, showYouTubeDownloader, gitProgress.stage, ytProgress.stage, isRecordingHotkey, isRecordingQuickTranslationHotkey, settings.command_only_mode, commandStatus.type]);
+    if (h > 0) {
+      return `${h}:${m.toString().padStart(2, '0')}:${s.toString().padStart(2, '0')}`;
+    }
+    return `${m}:${s.toString().padStart(2, '0')}`;
+  };
 
-  // Color picker blur handler
-  useEffect(() => {
-    if (!showColorPicker) return;
+  // Clipboard numbers, read with the user's locale decimal separator for ambiguous values like "1,234"
+  const sumClipboardNumbers = () => {
+    const decimalSeparator =
+      new Intl.NumberFormat().formatToParts(1.5).find((p) => p.type === "decimal")?.value ?? ".";
+    return invoke<NumberSummary>("sum_clipboard_numbers", { decimalSeparator });
+  };
 
-    const handleBlur = () => {
-      // Only reset state if not dragging (dragging causes temporary blur)
-      if (!isDraggingRef.current) {
-        setShowColorPicker(false);
-        setPickedColor(null);
-        setCopiedFormat(null);
-      }
-    };
+  const formatNumberSummary = (summary: NumberSummary) => {
+    const fmt = (n: number) => n.toLocaleString(undefined, { maximumFractionDigits: 4 });
+    return `Sum ${fmt(summary.sum)} · Avg ${fmt(summary.average)} · Min ${fmt(summary.min)} · Max ${fmt(summary.max)} · ${summary.count} numbers`;
+  };
 
-    window.addEventListener("blur", handleBlur);
+  const formatContrast = (result: ContrastResult) => {
+    const level = (pass: boolean) => (pass ? "✓" : "✗");
+    return `${result.ratio}:1 · AA ${level(result.aa_normal)} (large ${level(result.aa_large)}) · AAA ${level(result.aaa_normal)} (large ${level(result.aaa_large)})`;
+  };
 
-    return () => {
-      window.removeEventListener("blur", handleBlur);
-    };
-  }, [showColorPicker]);
+  // Command-only mode: Helper to show error and reset
+  const showCommandError = (message: string) => {
+    setCommandStatus({ message, type: 'error' });
+    commandStatusTimeoutRef.current = setTimeout(() => {
+      setCommandStatus({ message: "Type a command...", type: 'idle' });
+    }, 1500);
+  };
+
+  // Command-only mode: Helper to show success and reset
+  const showCommandSuccess = (message: string, resultPath?: string) => {
+    if (resultPath) {
+      setLastResultPath(resultPath);
+    }
+    setCommandStatus({ message, type: 'success' });
+    commandStatusTimeoutRef.current = setTimeout(() => {
+      setCommandStatus({ message: "Type a command...", type: 'idle' });
+    }, 1500);
+  };
+
+  // Command-only mode: Execute command from natural language input
+  const executeCommand = async (input: string) => {
+    // Clear any existing timeout
+    if (commandStatusTimeoutRef.current) {
+      clearTimeout(commandStatusTimeoutRef.current);
+      commandStatusTimeoutRef.current = null;
+    }
 
-  // Video Converter blur handler (prevents flickering on reopen)
-  useEffect(() => {
-    if (!showVideoConverter) return;
+    // Add to history (avoid duplicates of last command), except commands carrying a passcode,
+    // passphrase or secret snippet
+    const trimmedInput = input.trim();
+    const carriesSecret = /^(?:unlock|secrets?\s+unlock|snip\s+secret)\s/i.test(trimmedInput);
+    if (trimmedInput && !carriesSecret && (commandHistory.length === 0 || commandHistory[0] !== trimmedInput)) {
+      setCommandHistory(prev => [trimmedInput, ...prev].slice(0, 50)); // Keep last 50 commands
+    }
+    setHistoryIndex(-1);
 
-    const handleBlur = () => {
-      if (!isDraggingRef.current && !isDialogOpenRef.current) {
-        setShowVideoConverter(false);
-        setVideoFile(null);
-        setVideoFormat("mp4");
-        setVideoQuality("high");
-        setVideoAdvancedSettings({
-          resolution: "Keep Original",
-          frameRate: "Keep Original",
-          codec: "H.264",
-          keepAudio: true,
-        });
-        setShowVideoAdvanced(false);
-        setVideoConverting(false);
-        setVideoProgress(0);
-        setVideoConversionStatus('idle');
-      }
-    };
+    // Normalize command: expand shortcuts
+    let normalizedInput = trimmedInput;
+    if (normalizedInput.match(/^dl\s/i)) {
+      normalizedInput = normalizedInput.replace(/^dl\s/i, 'download ');
+    }
+    if (normalizedInput.match(/^op\s/i) || normalizedInput.match(/^op$/i)) {
+      normalizedInput = normalizedInput.replace(/^op(\s|$)/i, 'open$1');
+    }
 
-    window.addEventListener("blur", handleBlur);
+    // === HELP COMMAND ===
+    if (normalizedInput.match(/^(help|\?)$/i)) {
+      setCommandStatus({
+        message: "dl | open | scan | kill | launch | copy | clipboard | sum | banner | morse | nato | rot13 | focus | timer",
+        type: 'help',
+      });
+      // Help doesn't auto-dismiss - user types to clear
+      return;
+    }
 
-    return () => {
-      window.removeEventListener("blur", handleBlur);
-    };
-  }, [showVideoConverter]);
+    // === OPEN COMMAND ===
+    // "open" or "open <alias/path>"
+    const openMatch = normalizedInput.match(/^open(?:\s+(.+))?$/i);
+    if (openMatch) {
+      const target = openMatch[1]?.trim();
 
-  // Port Killer blur handler (prevents flickering on reopen)
-  useEffect(() => {
-    if (!showPortKiller) return;
+      let pathToOpen: string | null = null;
 
-    const handleBlur = () => {
-      if (!isDraggingRef.current) {
-        setShowPortKiller(false);
-        setPortInput("");
-        setPortProcesses([]);
-        setScannedPort(null);
+      if (!target) {
+        // No target - use last result path
+        if (lastResultPath) {
+          pathToOpen = lastResultPath;
+        } else {
+          showCommandError("No recent path to open");
+          return;
+        }
+      } else {
+        // Resolve the target
+        const resolved = await resolveDestination(target);
+        if (resolved) {
+          pathToOpen = resolved.path;
+        } else {
+          showCommandError(`Unknown location: ${target}`);
+          return;
+        }
       }
-    };
 
-    window.addEventListener("blur", handleBlur);
+      try {
+        await invoke("open_folder_in_explorer", { path: pathToOpen });
+        showCommandSuccess(`Opened ${pathToOpen}`, pathToOpen);
+      } catch (e) {
+        showCommandError(String(e));
+      }
+      return;
+    }
 
-    return () => {
-      window.removeEventListener("blur", handleBlur);
-    };
-  }, [showPortKiller]);
+    // === DOWNLOAD COMMAND ===
+    // "download <url> [to <destination>]" or "download clipboard [to <destination>]"
+    // Auto-detects URL type: GitHub, YouTube, etc.
+    const downloadMatch = normalizedInput.match(/^download\s+(\S+)(?:\s+to\s+(.+))?$/i);
+    if (downloadMatch) {
+      let url = downloadMatch[1];
+      const destination = downloadMatch[2]?.trim();
 
-  // Regex Tester blur handler (prevents flickering on reopen)
-  useEffect(() => {
-    if (!showRegexTester) return;
+      // Handle "clipboard" keyword
+      if (url.toLowerCase() === 'clipboard') {
+        try {
+          const clipboardText = await readText();
+          if (!clipboardText) {
+            showCommandError("Clipboard is empty");
+            return;
+          }
+          url = clipboardText.trim();
+        } catch (e) {
+          showCommandError("Could not read clipboard");
+          return;
+        }
+      }
 
-    const handleBlur = () => {
-      if (!isDraggingRef.current) {
-        setShowRegexTester(false);
+      // Resolve destination path first (common for all download types)
+      let outputPath: string;
+      if (destination) {
+        const resolved = await resolveDestination(destination);
+        if (resolved) {
+          outputPath = resolved.path;
+        } else {
+          showCommandError(`Unknown location: ${destination}`);
+          return;
+        }
+      } else {
+        try {
+          outputPath = await invoke<string>("get_downloads_path");
+        } catch (e) {
+          showCommandError("Could not find Downloads folder");
+          return;
+        }
       }
-    };
 
-    window.addEventListener("blur", handleBlur);
+      // Try to detect URL type and download accordingly
 
-    return () => {
-      window.removeEventListener("blur", handleBlur);
-    };
-  }, [showRegexTester]);
+      // Check if it's a YouTube URL
+      const ytParsed = parseYouTubeUrl(url);
+      if (ytParsed?.isValid) {
+        setCommandStatus({ message: "Downloading video...", type: 'progress' });
+        try {
+          const result = await invoke<string>("download_youtube_video", {
+            url: url,
+            outputPath: outputPath,
+            options: {
+              quality: 'best',
+              mode: 'video_audio',
+            },
+          });
+          showCommandSuccess(result, result);
+        } catch (e) {
+          showCommandError(String(e));
+        }
+        return;
+      }
 
-  // Git Downloader blur handler
-  // Don't reset if download is in progress - keep state so user can return to see progress
-  useEffect(() => {
-    if (!showGitDownloader) return;
+      // Check if it's a GitHub URL
+      const gitParsed = parseGitHubUrl(url);
+      if (gitParsed?.isValid) {
+        setCommandStatus({ message: "Downloading... 0%", type: 'progress' });
+        try {
+          const result = await invoke<GitDownloadResult>("download_github_folder", {
+            urlInfo: {
+              owner: gitParsed.owner,
+              repo: gitParsed.repo,
+              branch: gitParsed.branch,
+              path: gitParsed.path,
+            },
+            outputPath: outputPath,
+            options: {
+              extract_files: true,
+              flatten_structure: false,
+              create_subfolder: true,
+            },
+          });
+          showCommandSuccess(result.output_path, result.output_path);
+        } catch (e) {
+          showCommandError(String(e));
+        }
+        return;
+      }
 
-    const handleBlur = () => {
-      // Don't reset if download is actively in progress
-      const isDownloading = gitProgress.stage === 'fetching' ||
-                           gitProgress.stage === 'downloading' ||
-                           gitProgress.stage === 'extracting';
+      // TODO: Add TikTok and other URL type detection here
 
-      if (!isDraggingRef.current && !isDialogOpenRef.current && !isDownloading) {
-        setShowGitDownloader(false);
-        setGitUrlInput("");
-        setGitParsedUrl(null);
-        setGitDownloadPath("");
-        setGitDownloadOptions({
-          extractFiles: true,
-          flattenStructure: false,
-          createSubfolder: true,
-        });
-        setGitProgress({
-          stage: 'idle',
-          percent: 0,
-          message: '',
-        });
-      }
-    };
+      // Unknown URL type
+      showCommandError("Unsupported URL type");
+      return;
+    }
 
-    window.addEventListener("blur", handleBlur);
+    // === SCAN COMMAND ===
+    // "scan <port>" - scan for processes using a port
+    const scanMatch = normalizedInput.match(/^scan\s+(\d+)$/i);
+    if (scanMatch) {
+      const port = parseInt(scanMatch[1], 10);
 
-    return () => {
-      window.removeEventListener("blur", handleBlur);
-    };
-  }, [showGitDownloader, gitProgress.stage]);
+      if (isNaN(port) || port <= 0 || port > 65535) {
+        showCommandError("Invalid port number (1-65535)");
+        return;
+      }
 
-  // YouTube Downloader blur handler
-  // Close panel on blur, but preserve state if download is in progress
-  useEffect(() => {
-    if (!showYouTubeDownloader) return;
+      setCommandStatus({ message: "Scanning...", type: 'progress' });
 
-    const handleBlur = () => {
-      if (isDraggingRef.current || isDialogOpenRef.current) return;
+      try {
+        const processes = await invoke<PortProcess[]>("scan_port", { port });
 
-      const isDownloading = ytProgress.stage === 'downloading';
+        if (processes.length === 0) {
+          showCommandError(`Port ${port} is free`);
+        } else if (processes.length === 1) {
+          const p = processes[0];
+          showCommandSuccess(`${p.name} (PID ${p.pid}) ${p.protocol}`);
+        } else {
+          // Multiple processes found
+          const names = [...new Set(processes.map(p => p.name))].join(', ');
+          showCommandSuccess(`${processes.length} processes: ${names}`);
+        }
+      } catch (e) {
+        showCommandError(String(e));
+      }
+      return;
+    }
 
-      // Always close the panel
-      setShowYouTubeDownloader(false);
+    // === KILL COMMAND ===
+    // "kill <port>" - kills the process using that port
+    const killMatch = normalizedInput.match(/^kill\s+(\d+)$/i);
+    if (killMatch) {
+      const port = parseInt(killMatch[1], 10);
 
-      // Only reset state if not downloading (download continues in background)
-      if (!isDownloading) {
-        setYtUrlInput("");
-        setYtParsedUrl(null);
-        setYtVideoInfo(null);
-        setYtDownloadPath("");
-        setYtDownloadOptions({
-          quality: 'best',
-          mode: 'video_audio',
-        });
-        setYtProgress({
-          stage: 'idle',
-          percent: 0,
-          message: '',
-        });
+      if (isNaN(port) || port <= 0 || port > 65535) {
+        showCommandError("Invalid port number");
+        return;
       }
-    };
 
-    window.addEventListener("blur", handleBlur);
+      setCommandStatus({ message: "Finding process...", type: 'progress' });
 
-    return () => {
-      window.removeEventListener("blur", handleBlur);
-    };
-  }, [showYouTubeDownloader, ytProgress.stage]);
+      try {
+        // First scan to find the PID
+        const processes = await invoke<PortProcess[]>("scan_port", { port });
 
-  // Command palette blur handler (hides window on blur)
-  useEffect(() => {
-    // Only handle blur when command palette is visible (no other panel is open)
-    const isCommandPaletteVisible = !showVideoConverter && !showPortKiller && !showTranslation && !showSettings && !showColorPicker && !showQRGenerator && !showRegexTester && !showGitDownloader && !showYouTubeDownloader;
-    if (!isCommandPaletteVisible) return;
+        if (processes.length === 0) {
+          showCommandError(`No process on port ${port}`);
+          return;
+        }
 
-    const handleBlur = async () => {
-      if (!isDraggingRef.current) {
-        await invoke("hide_window");
+        // Kill all processes on this port
+        for (const p of processes) {
+          await killProcess(p.pid, p.name);
+        }
+
+        if (processes.length === 1) {
+          showCommandSuccess(`Killed ${processes[0].name} (PID ${processes[0].pid})`);
+        } else {
+          showCommandSuccess(`Killed ${processes.length} processes on port ${port}`);
+        }
+      } catch (e) {
+        showCommandError(String(e));
       }
-    };
+      return;
+    }
 
-    window.addEventListener("blur", handleBlur);
+    // === LAUNCH/RUN/START COMMAND ===
+    // "launch <app>", "run <app>", "start <app>" - launch an application
+    const launchMatch = normalizedInput.match(/^(?:launch|run|start)\s+(.+)$/i);
+    if (launchMatch) {
+      const appName = launchMatch[1].trim();
 
-    return () => {
-      window.removeEventListener("blur", handleBlur);
-    };
-  }, [showVideoConverter, showPortKiller, showTranslation, showSettings, showColorPicker, showQRGenerator, showRegexTester, showGitDownloader, showYouTubeDownloader]);
+      if (!appName) {
+        showCommandError("Specify an app name");
+        return;
+      }
 
-  // Port killer functions
-  const handleScanPort = async (port: number) => {
-    setIsScanning(true);
-    setScannedPort(port);
-    try {
-      const processes = await invoke<PortProcess[]>("scan_port", { port });
-      setPortProcesses(processes);
-    } catch (e) {
-      console.error("Scan port error:", e);
-      setPortProcesses([]);
-    } finally {
-      setIsScanning(false);
-    }
-  };
+      setCommandStatus({ message: `Launching ${appName}...`, type: 'progress' });
 
-  const handleKillProcess = async (pid: number) => {
-    try {
-      await invoke("kill_port_process", { pid });
-      // Re-scan to update the list
-      if (scannedPort !== null) {
-        await handleScanPort(scannedPort);
+      try {
+        await invoke("launch_app", { appName });
+        showCommandSuccess(`Launched ${appName}`);
+      } catch (e) {
+        showCommandError(String(e));
       }
-      setStatus("Process killed");
-      setTimeout(() => setStatus(null), 2000);
-    } catch (e) {
-      console.error("Kill process error:", e);
-      setStatus(`Failed: ${e}`);
-      setTimeout(() => setStatus(null), 3000);
+      return;
     }
-  };
 
-  // Handle window dragging - only drag from elements with data-drag-region
-  const handleDragStart = async (e: React.MouseEvent) => {
-    // Only start drag if clicking directly on a drag region (not on buttons inside it)
-    const target = e.target as HTMLElement;
-    if (!target.closest('[data-drag-region]') || target.closest('button, input')) return;
+    // === CLIPBOARD COMMANDS ===
+    // "copy: <text>" or "copy this: <text>" - write text to clipboard
+    const copyMatch = normalizedInput.match(/^copy(?:\s+this)?[:\s]+(.+)$/i);
+    if (copyMatch) {
+      const textToCopy = copyMatch[1].trim();
 
-    // Set dragging flag to prevent auto-hide during drag
-    isDraggingRef.current = true;
-    await invoke("set_dragging", { dragging: true });
+      if (!textToCopy) {
+        showCommandError("Specify text to copy");
+        return;
+      }
 
-    try {
-      await getCurrentWindow().startDragging();
-    } finally {
-      // Clear dragging flag after drag ends
-      isDraggingRef.current = false;
-      await invoke("set_dragging", { dragging: false });
+      try {
+        await invoke("write_clipboard", { text: textToCopy });
+        showCommandSuccess("Copied to clipboard");
+      } catch (e) {
+        showCommandError(String(e));
+      }
+      return;
     }
-  };
-
-  // Handle video file selection via dialog
-  const handleVideoSelectFile = async () => {
-    // Mark dialog as open to prevent blur handler from closing
-    isDialogOpenRef.current = true;
-    await invoke("set_auto_hide", { enabled: false });
 
-    const result = await open({
-      filters: VIDEO_FILE_FILTERS,
-      multiple: false,
-    });
+    // "clipboard", "show clipboard", "what's in clipboard", "paste" - read clipboard
+    const clipboardMatch = normalizedInput.match(/^(?:clipboard|show\s+clipboard|what'?s?\s+in\s+(?:my\s+)?clipboard|paste)$/i);
+    if (clipboardMatch) {
+      try {
+        const content = await invoke<string>("read_clipboard");
+        if (!content || content.trim() === "") {
+          showCommandError("Clipboard is empty");
+        } else {
+          // Truncate long content for display
+          const displayContent = content.length > 50 ? content.substring(0, 47) + "..." : content;
+          showCommandSuccess(displayContent);
+        }
+      } catch (e) {
+        showCommandError(String(e));
+      }
+      return;
+    }
 
-    // Re-enable after dialog closes
-    await invoke("set_auto_hide", { enabled: true });
-    isDialogOpenRef.current = false;
+    // "sum", "sum clipboard" - totals of the numbers on the clipboard
+    const sumMatch = normalizedInput.match(/^(?:sum|total)(?:\s+(?:my\s+)?clipboard)?$/i);
+    if (sumMatch) {
+      try {
+        const summary = await sumClipboardNumbers();
+        await writeText(String(summary.sum));
+        setCommandStatus({ message: formatNumberSummary(summary), type: 'success' });
+        commandStatusTimeoutRef.current = setTimeout(() => {
+          setCommandStatus({ message: "Type a command...", type: 'idle' });
+        }, 4000);
+      } catch (e) {
+        showCommandError(String(e));
+      }
+      return;
+    }
 
-    if (result) {
-      const path = result as string;
-      const name = path.split(/[\\/]/).pop() || "";
-
-      // Get video metadata from backend
-      try {
-        const metadata = await invoke<{
-          duration: number;
-          size: number;
-          width: number;
-          height: number;
-          frame_rate: number;
-          codec: string;
-        }>("get_video_metadata", { path });
-
-        setVideoFile({
-          name,
-          path,
-          size: metadata.size,
-          duration: metadata.duration,
-          width: metadata.width,
-          height: metadata.height,
-          frameRate: metadata.frame_rate,
-          codec: metadata.codec,
+    // "banner <text>", "banner <text> in tiny" - ASCII art banner, copied to clipboard
+    const bannerMatch = normalizedInput.match(/^banner\s+(.+?)(?:\s+in\s+(standard|solid|tiny))?$/i);
+    if (bannerMatch) {
+      try {
+        const banner = await invoke<string>("generate_banner", {
+          text: bannerMatch[1],
+          font: bannerMatch[2]?.toLowerCase(),
         });
+        await writeText(banner);
+        showCommandSuccess("Banner copied to clipboard");
       } catch (e) {
-        console.error("Failed to get video metadata:", e);
-        // Still allow selection with minimal info
-        setVideoFile({
-          name,
-          path,
-          size: 0,
-          duration: 0,
-          width: 0,
-          height: 0,
-          frameRate: 0,
-          codec: "unknown",
-        });
+        showCommandError(String(e));
       }
+      return;
     }
-  };
-
-  // Handle video conversion
-  const handleVideoConvert = async () => {
-    if (!videoFile) return;
-
-    const ext = videoFormat.toLowerCase();
-    const defaultName = videoFile.name.replace(/\.[^.]+$/, `.${ext}`);
-
-    // Mark dialog as open to prevent blur handler from closing
-    isDialogOpenRef.current = true;
-    await invoke("set_auto_hide", { enabled: false });
-
-    const outputPath = await save({
-      defaultPath: defaultName,
-      filters: [{ name: videoFormat.toUpperCase(), extensions: [ext] }],
-    });
 
-    // Re-enable after dialog closes
-    await invoke("set_auto_hide", { enabled: true });
-    isDialogOpenRef.current = false;
+    // "secrets unlock <passphrase>" (the first one sets it), "secrets lock", "secrets" - the key
+    // for encrypted snippets
+    const secretsMatch = normalizedInput.match(/^secrets?(?:\s+(unlock|lock)(?:\s+(.+))?)?$/i);
+    if (secretsMatch) {
+      const describe = (status: SecretsStatus) =>
+        !status.configured
+          ? 'No passphrase yet: "secrets unlock <passphrase>" sets one'
+          : status.unlocked
+            ? `Secrets unlocked, locking in ${Math.ceil((status.remaining ?? 0) / 60)} min`
+            : "Secrets locked";
+      try {
+        const action = secretsMatch[1]?.toLowerCase();
+        if (action === "unlock") {
+          if (!secretsMatch[2]) {
+            showCommandError("Usage: secrets unlock <passphrase>");
+            return;
+          }
+          showCommandSuccess(describe(await invoke<SecretsStatus>("unlock_secrets", { passphrase: secretsMatch[2] })));
+        } else if (action === "lock") {
+          showCommandSuccess(describe(await invoke<SecretsStatus>("lock_secrets")));
+        } else {
+          showCommandSuccess(describe(await invoke<SecretsStatus>("get_secrets_status")));
+        }
+      } catch (e) {
+        showCommandError(String(e));
+      }
+      return;
+    }
 
-    if (!outputPath) return;
+    // "snip add sig Best, {clipboard|trim}", "snip secret key ABCD-1234" (encrypted), "snip rm sig",
+    // "snip encrypt|decrypt key", "snip transform shout trim|upper", "snips" - manage snippets;
+    // "snip sig" or "snip {uuid}" - expand and copy
+    const snipMatch = normalizedInput.match(/^(?:snip|snippet)s?(?:\s+(.+))?$/i);
+    if (snipMatch) {
+      const args = snipMatch[1] ?? "";
+      const addMatch = args.match(/^(add|save|secret)\s+(\S+)\s+([\s\S]+)$/i);
+      const removeMatch = args.match(/^(?:rm|remove|delete)\s+(\S+)$/i);
+      const encryptMatch = args.match(/^(encrypt|decrypt)\s+(\S+)$/i);
+      const transformDefMatch = args.match(/^transform\s+(\S+)\s*=?\s*(\S.*)$/i);
+      try {
+        if (addMatch) {
+          const encrypted = addMatch[1].toLowerCase() === "secret" ? true : undefined;
+          await invoke("save_snippet", { name: addMatch[2], text: addMatch[3], encrypted });
+          showCommandSuccess(`Saved ${encrypted ? "encrypted " : ""}snippet "${addMatch[2]}"`);
+        } else if (encryptMatch) {
+          const encrypted = encryptMatch[1].toLowerCase() === "encrypt";
+          await invoke("set_snippet_encrypted", { name: encryptMatch[2], encrypted });
+          showCommandSuccess(`Snippet "${encryptMatch[2]}" ${encrypted ? "encrypted" : "stored as plain text"}`);
+        } else if (removeMatch) {
+          await invoke("delete_snippet", { name: removeMatch[1] });
+          showCommandSuccess(`Deleted snippet "${removeMatch[1]}"`);
+        } else if (transformDefMatch) {
+          await invoke("save_snippet_transform", { name: transformDefMatch[1], pipeline: transformDefMatch[2] });
+          showCommandSuccess(`Saved transform "${transformDefMatch[1]}"`);
+        } else if (!args) {
+          const store = await invoke<SnippetStore>("get_snippets");
+          showCommandSuccess(
+            store.snippets.length > 0
+              ? `Snippets: ${store.snippets.map((s) => (s.encrypted ? `${s.name} (encrypted)` : s.name)).join(", ")}`
+              : "No snippets yet"
+          );
+        } else {
+          // A snippet name, or a template typed inline
+          const result = args.includes("{")
+            ? await invoke<string>("expand_placeholders", { text: args })
+            : await invoke<string>("expand_snippet", { name: args });
+          if (args.includes("{")) await writeText(result);
+          const displayResult = result.length > 50 ? result.substring(0, 47) + "..." : result;
+          showCommandSuccess(`Copied: ${displayResult}`);
+        }
+      } catch (e) {
+        showCommandError(String(e));
+      }
+      return;
+    }
 
-    setVideoConverting(true);
-    setVideoProgress(0);
-    setVideoConversionStatus('converting');
+    // "rot13 <text>", "morse" (clipboard), "unmorse", "nato", "leet", "reverse", "unzalgo"
+    const transformMatch = normalizedInput.match(/^(morse|unmorse|nato|rot13|leet|reverse|unzalgo)(?:\s+(.+))?$/i);
+    if (transformMatch) {
+      const transform = transformMatch[1].toLowerCase();
+      try {
+        let result: string;
+        if (transformMatch[2]) {
+          result = await invoke<string>("transform_text", { text: transformMatch[2], transform });
+          await writeText(result);
+        } else {
+          // No text given: transform the clipboard in place
+          result = await invoke<string>("transform_clipboard", { transform });
+        }
+        const displayResult = result.length > 50 ? result.substring(0, 47) + "..." : result;
+        showCommandSuccess(`Copied: ${displayResult}`);
+      } catch (e) {
+        showCommandError(String(e));
+      }
+      return;
+    }
 
-    // Get bitrate and resolution from selected quality preset
-    const selectedPreset = VIDEO_QUALITY_PRESETS.find(p => p.id === videoQuality);
-    const bitrate = selectedPreset?.bitrate || 0;
+    // "words 1234.50 eur", "words 99 usd in es" - amount spelled out for checks and invoices
+    const wordsMatch = normalizedInput.match(/^words\s+(-?[\d,]*\.?\d+)(?:\s+([a-z]{3}))?(?:\s+in\s+([a-z]{2}))?$/i);
+    if (wordsMatch) {
+      try {
+        const words = await invoke<string>("number_to_words", {
+          amount: parseFloat(wordsMatch[1].replace(/,/g, "")),
+          lang: wordsMatch[3]?.toLowerCase() ?? "en",
+          currency: wordsMatch[2]?.toUpperCase(),
+        });
+        await writeText(words);
+        const displayWords = words.length > 50 ? words.substring(0, 47) + "..." : words;
+        showCommandSuccess(`Copied: ${displayWords}`);
+      } catch (e) {
+        showCommandError(String(e));
+      }
+      return;
+    }
 
-    // Use quality preset resolution if Advanced Settings is "Keep Original" and preset has a specific resolution
-    let resolution = videoAdvancedSettings.resolution;
-    if (resolution === "Keep Original" && selectedPreset && selectedPreset.resolution !== "original") {
-      // Convert preset resolution format (e.g., "1920x1080" -> "1080p", "1280x720" -> "720p")
-      const resolutionMap: Record<string, string> = {
-        "3840x2160": "4K",
-        "1920x1080": "1080p",
-        "1280x720": "720p",
-        "854x480": "480p",
-      };
-      resolution = resolutionMap[selectedPreset.resolution] || "Keep Original";
+    // "contrast #777 white" - WCAG contrast of two colors; "contrast" alone picks both from the screen
+    const contrastMatch = normalizedInput.match(/^contrast(?:\s+(\S+)\s+(?:on\s+)?(\S+))?$/i);
+    if (contrastMatch) {
+      try {
+        const result = contrastMatch[1]
+          ? await invoke<ContrastResult>("check_contrast", { fg: contrastMatch[1], bg: contrastMatch[2] })
+          : await invoke<ContrastResult>("pick_contrast");
+        setCommandStatus({ message: formatContrast(result), type: 'success' });
+        commandStatusTimeoutRef.current = setTimeout(() => {
+          setCommandStatus({ message: "Type a command...", type: 'idle' });
+        }, 6000);
+      } catch (e) {
+        if (e !== "Cancelled") showCommandError(String(e));
+      }
+      return;
     }
 
-    try {
-      await invoke("convert_video", {
-        inputPath: videoFile.path,
-        outputPath,
-        options: {
-          resolution,
-          frame_rate: videoAdvancedSettings.frameRate,
-          codec: videoAdvancedSettings.codec,
-          keep_audio: videoAdvancedSettings.keepAudio,
-          bitrate,
-        },
-      });
+    // "locale de-DE", "locale fr 1 1.5 2026-03-09" - how numbers, currency, dates and plurals render; report copied
+    const localeMatch = normalizedInput.match(/^locale\s+([a-z]{2,3}(?:[-_][a-z0-9]+)?)(?:\s+(.+))?$/i);
+    if (localeMatch) {
+      try {
+        const samples = localeMatch[2]?.split(/\s+/).filter(Boolean);
+        const preview = await invoke<LocalePreview>("preview_locale_formatting", { locale: localeMatch[1], samples });
+        const lines = [`${preview.name} (${preview.locale})`];
+        for (const n of preview.numbers) {
+          const ordinal = n.ordinal ? `, ordinal ${n.ordinal}` : "";
+          lines.push(`${n.input}\t${n.formatted}\t${n.currency}\t${n.cardinal}${ordinal}`);
+        }
+        for (const d of preview.dates) {
+          lines.push(`${d.input}\t${d.short}\t${d.long}\t${d.time}`);
+        }
+        if (preview.invalid.length > 0) lines.push(`Not a number or date: ${preview.invalid.join(", ")}`);
+        await writeText(lines.join("\n"));
+        const first = preview.numbers.find((n) => n.input.includes(".")) ?? preview.numbers[0];
+        const summary = [first?.formatted, first?.currency, preview.dates[0]?.short].filter(Boolean).join(" · ");
+        showCommandSuccess(`${preview.locale}: ${summary} (report copied)`);
+      } catch (e) {
+        showCommandError(String(e));
+      }
+      return;
+    }
 
-      // Show success state
-      setVideoConversionStatus('success');
-      setVideoProgress(100);
+    // "gitignore rust node vscode", "editorconfig rust" - write a starter file into a picked project folder
+    const projectFileMatch = normalizedInput.match(/^(gitignore|editorconfig)(?:\s+(.+))?$/i);
+    if (projectFileMatch) {
+      const kind = projectFileMatch[1].toLowerCase();
+      const technologies = (projectFileMatch[2] ?? "").split(/[\s,]+/).filter(Boolean);
+      if (kind === "gitignore" && technologies.length === 0) {
+        showCommandError("Usage: gitignore <language/editor...>");
+        return;
+      }
+      try {
+        isDialogOpenRef.current = true;
+        await invoke("set_auto_hide", { enabled: false });
+        const directory = await invoke<string | null>("pick_output_directory", { title: "Choose the project folder" });
+        await invoke("set_auto_hide", { enabled: true });
+        isDialogOpenRef.current = false;
+        if (!directory) return;
 
-      // Wait a moment to show success, then close
-      setTimeout(async () => {
+        const command = kind === "gitignore" ? "generate_gitignore" : "generate_editorconfig";
+        let file: GeneratedFile;
+        try {
+          file = await invoke<GeneratedFile>(command, { technologies, directory });
+        } catch (e) {
+          if (!String(e).includes("already has a")) throw e;
+          if (!(await ask(`${String(e)}. Replace it?`, { title: "Replace File", kind: "warning" }))) return;
+          file = await invoke<GeneratedFile>(command, { technologies, directory, overwrite: true });
+        }
+        if (file.path) setLastResultPath(directory);
+        showCommandSuccess(kind === "gitignore" ? "Wrote .gitignore" : "Wrote .editorconfig");
+      } catch (e) {
+        isDialogOpenRef.current = false;
         await invoke("set_auto_hide", { enabled: true });
-        setShowVideoConverter(false);
-        setVideoFile(null);
-        setVideoFormat("mp4");
-        setVideoQuality("high");
-        setVideoAdvancedSettings({
-          resolution: "Keep Original",
-          frameRate: "Keep Original",
-          codec: "H.264",
-          keepAudio: true,
-        });
-        setShowVideoAdvanced(false);
-        setVideoConverting(false);
-        setVideoConversionStatus('idle');
-        setVideoProgress(0);
-      }, 1500);
-    } catch (e) {
-      console.error("Video conversion error:", e);
-      setVideoConversionStatus('error');
-      setStatus(String(e));
+        showCommandError(String(e));
+      }
+      return;
+    }
 
-      // Reset after showing error
-      setTimeout(() => {
-        setVideoConverting(false);
-        setVideoConversionStatus('idle');
-        setVideoProgress(0);
-        setStatus(null);
-      }, 4000);
+    // === TIMER COMMANDS ===
+    // "timer <duration>" - start a timer
+    const timerMatch = normalizedInput.match(/^timer\s+(.+)$/i);
+    if (timerMatch) {
+      const durationStr = timerMatch[1].trim();
+      const seconds = parseTimeDuration(durationStr);
+
+      if (!seconds) {
+        showCommandError("Invalid duration (e.g., 5m, 30s, 1h30m)");
+        return;
+      }
+
+      try {
+        await invoke("start_timer", { seconds, label: `Timer ${formatTimerDisplay(seconds)}` });
+        setTimerRemaining(seconds);
+        setTimerLabel(`Timer ${formatTimerDisplay(seconds)}`);
+        showCommandSuccess(`Timer set for ${formatTimerDisplay(seconds)}`);
+      } catch (e) {
+        showCommandError(String(e));
+      }
+      return;
     }
-  };
 
-  // Handle video converter reset
-  const handleVideoReset = () => {
-    setVideoFile(null);
-    setVideoFormat("mp4");
-    setVideoQuality("high");
-    setVideoAdvancedSettings({
-      resolution: "Keep Original",
-      frameRate: "Keep Original",
-      codec: "H.264",
-      keepAudio: true,
-    });
-    setShowVideoAdvanced(false);
-    setVideoConverting(false);
-    setVideoProgress(0);
-    setVideoConversionStatus('idle');
-  };
+    // "calibrate", "calibrate 2" - test patterns on the cursor's display, or display #2
+    const calibrateMatch = normalizedInput.match(/^(?:calibrate|calibration|dead\s*pixels?)(?:\s+(\d+))?$/i);
+    if (calibrateMatch) {
+      try {
+        let display: string | null = null;
+        if (calibrateMatch[1]) {
+          const displays = await invoke<DisplayInfo[]>("list_displays");
+          const chosen = displays[Number(calibrateMatch[1]) - 1];
+          if (!chosen) {
+            showCommandError(`There ${displays.length === 1 ? "is 1 display" : `are ${displays.length} displays`}`);
+            return;
+          }
+          display = chosen.key;
+        }
+        await invoke("hide_window");
+        await invoke("open_calibration_window", { display });
+      } catch (e) {
+        showCommandError(String(e));
+      }
+      return;
+    }
 
-  // Git Downloader handlers
-  const handleGitUrlChange = (url: string) => {
-    setGitUrlInput(url);
-    const parsed = parseGitHubUrl(url);
-    setGitParsedUrl(parsed);
-  };
+    // "overlay 10m [label]", "overlay clock", "overlay off" - on-screen countdown for recordings/talks
+    const overlayMatch = normalizedInput.match(/^overlay(?:\s+(\S+)(?:\s+(.+))?)?$/i);
+    if (overlayMatch) {
+      const [, arg, label] = overlayMatch;
+      try {
+        if (arg && /^(?:off|close|stop|hide)$/i.test(arg)) {
+          await invoke("close_overlay_timer");
+          showCommandSuccess("Overlay closed");
+          return;
+        }
+        let config: OverlayTimerConfig;
+        if (!arg || /^clock$/i.test(arg)) {
+          config = { mode: "clock", label };
+        } else {
+          const duration = parseTimeDuration(arg);
+          if (!duration) {
+            showCommandError("Invalid duration (e.g., 5m, 30s, 1h30m)");
+            return;
+          }
+          config = { mode: "countdown", duration, label };
+        }
+        await invoke("show_overlay_timer", { config });
+        showCommandSuccess(config.mode === "clock" ? "Clock overlay shown" : `Overlay counting down ${formatTimerDisplay(config.duration!)}`);
+      } catch (e) {
+        showCommandError(String(e));
+      }
+      return;
+    }
 
-  const handleGitPaste = async () => {
-    try {
-      const clipboardText = await readText();
-      if (clipboardText) {
-        handleGitUrlChange(clipboardText);
+    // "focus", "focus 50", "pomodoro 50/10" - start a Pomodoro focus session
+    const focusMatch = normalizedInput.match(/^(?:focus|pomodoro)(?:\s+(\d+)(?:\s*\/\s*(\d+))?)?$/i);
+    if (focusMatch) {
+      try {
+        const focusMinutes = focusMatch[1] ? Number(focusMatch[1]) : undefined;
+        const breakMinutes = focusMatch[2] ? Number(focusMatch[2]) : undefined;
+        await invoke("start_pomodoro", { focusMinutes, breakMinutes });
+        showCommandSuccess(`Focusing for ${focusMinutes ?? settings.pomodoro_focus_minutes ?? 25} minutes`);
+      } catch (e) {
+        showCommandError(String(e));
       }
-    } catch (e) {
-      console.error("Failed to read clipboard:", e);
+      return;
     }
-  };
 
-  const handleGitSelectFolder = async () => {
-    isDialogOpenRef.current = true;
-    await invoke("set_auto_hide", { enabled: false });
+    // "network", "ip", "vpn" - copy the network details
+    if (/^(?:network|net|ip|vpn|dns)$/i.test(normalizedInput)) {
+      try {
+        const status = await invoke<NetworkStatus>("get_network_status");
+        await writeText(formatNetworkSummary(status));
+        showCommandSuccess(status.online ? `Online via ${status.gateway_interface} - details copied` : "Offline - details copied");
+      } catch (e) {
+        showCommandError(String(e));
+      }
+      return;
+    }
 
-    const result = await open({
-      directory: true,
-      multiple: false,
-    });
+    // "devices", "lan", "scan network" - list what's on the local network and copy it
+    if (/^(?:devices|lan(?:\s+devices)?|network\s+devices|scan\s+(?:network|lan))$/i.test(normalizedInput)) {
+      setCommandStatus({ message: "Looking for devices...", type: 'progress' });
+      try {
+        const devices = await invoke<DiscoveredDevice[]>("discover_network_devices");
+        await writeText(formatDiscoveredDevices(devices));
+        showCommandSuccess(`${devices.length} devices found - list copied`);
+      } catch (e) {
+        showCommandError(String(e));
+      }
+      return;
+    }
 
-    await invoke("set_auto_hide", { enabled: true });
-    isDialogOpenRef.current = false;
+    // "wol add desktop AA:BB:CC:DD:EE:FF [192.168.1.255]", "wol remove desktop"
+    const wolAddMatch = normalizedInput.match(/^wol\s+(?:add|save)\s+(\S+)\s+([0-9a-f:.-]{12,17})(?:\s+(\S+))?$/i);
+    if (wolAddMatch) {
+      try {
+        const device = await invoke<WolDevice>("save_wol_device", {
+          device: { name: wolAddMatch[1], mac: wolAddMatch[2], broadcast_ip: wolAddMatch[3] ?? null },
+        });
+        setWolDevices(await invoke<WolDevice[]>("list_wol_devices"));
+        showCommandSuccess(`Saved ${device.name} (${device.mac})`);
+      } catch (e) {
+        showCommandError(String(e));
+      }
+      return;
+    }
+    const wolRemoveMatch = normalizedInput.match(/^wol\s+(?:remove|delete|rm)\s+(\S+)$/i);
+    if (wolRemoveMatch) {
+      try {
+        await invoke("remove_wol_device", { name: wolRemoveMatch[1] });
+        setWolDevices(await invoke<WolDevice[]>("list_wol_devices"));
+        showCommandSuccess(`Removed ${wolRemoveMatch[1]}`);
+      } catch (e) {
+        showCommandError(String(e));
+      }
+      return;
+    }
 
-    if (result) {
-      setGitDownloadPath(result as string);
+    // "wake desktop", "wake AA:BB:CC:DD:EE:FF [192.168.1.255]"
+    const wakeMatch = normalizedInput.match(/^(?:wake|wol)\s+(\S+)(?:\s+(\S+))?$/i);
+    if (wakeMatch) {
+      const [, target, broadcastIp] = wakeMatch;
+      try {
+        if (/^[0-9a-f]{2}([:-]?)(?:[0-9a-f]{2}\1){4}[0-9a-f]{2}$/i.test(target)) {
+          await invoke("send_wol", { mac: target, broadcastIp: broadcastIp ?? null });
+        } else {
+          await invoke("wake_device", { name: target });
+        }
+        showCommandSuccess(`Wake packet sent to ${target}`);
+      } catch (e) {
+        showCommandError(String(e));
+      }
+      return;
     }
-  };
 
-  const handleGitDownload = async () => {
-    if (!gitParsedUrl || !gitDownloadPath) return;
+    // "send clipboard", "send clipboard to <device>", "share devices" - clipboard sharing on the LAN
+    const sendClipboardMatch = normalizedInput.match(/^send\s+clip(?:board)?(?:\s+to\s+(.+))?$/i);
+    if (sendClipboardMatch || /^share\s+devices$/i.test(normalizedInput)) {
+      setCommandStatus({ message: "Looking for devices...", type: 'progress' });
+      try {
+        if (sendClipboardMatch) {
+          const device = await invoke<string>("send_clipboard_to", { device: sendClipboardMatch[1] ?? "" });
+          showCommandSuccess(`Clipboard sent to ${device}`);
+        } else {
+          const devices = await invoke<ShareDevice[]>("get_clipboard_devices");
+          showCommandSuccess(
+            devices.length
+              ? `Devices: ${devices.map((d) => d.name).join(", ")}`
+              : "No other devices found; is clipboard sharing on there too?"
+          );
+        }
+      } catch (e) {
+        showCommandError(String(e));
+      }
+      return;
+    }
 
-    setGitProgress({
-      stage: 'fetching',
-      percent: 0,
-      message: 'Connecting to GitHub...',
-    });
+    // "unlock <passcode>", "lock app" - the app lock in front of histories and secrets
+    const unlockMatch = normalizedInput.match(/^unlock\s+(.+)$/i);
+    if (unlockMatch || /^(?:lock\s+app|app\s+lock)$/i.test(normalizedInput)) {
+      try {
+        if (unlockMatch) {
+          await invoke<AppLockStatus>("unlock_app", { passcode: unlockMatch[1] });
+          showCommandSuccess("Unlocked");
+        } else {
+          await invoke<AppLockStatus>("lock_app");
+          showCommandSuccess("Locked: \"unlock <passcode>\" to open histories and secrets again");
+        }
+      } catch (e) {
+        showCommandError(String(e));
+      }
+      return;
+    }
 
-    try {
-      const result = await invoke<GitDownloadResult>("download_github_folder", {
-        urlInfo: {
-          owner: gitParsedUrl.owner,
-          repo: gitParsedUrl.repo,
-          branch: gitParsedUrl.branch,
-          path: gitParsedUrl.path,
-        },
-        outputPath: gitDownloadPath,
-        options: {
-          extract_files: gitDownloadOptions.extractFiles,
-          flatten_structure: gitDownloadOptions.flattenStructure,
-          create_subfolder: gitDownloadOptions.createSubfolder,
-        },
-      });
+    // "lock", "sleep", "restart", "shutdown", "shutdown 10m", "cancel shutdown"
+    const powerMatch = normalizedInput.match(/^(lock|sleep|suspend|restart|reboot|shutdown|shut\s+down)(?:\s+(?:in\s+)?(\S+))?$/i);
+    if (powerMatch) {
+      const action = powerMatch[1].toLowerCase();
+      const delay = powerMatch[2] ? parseTimeDuration(powerMatch[2]) : null;
+      if (powerMatch[2] && (!delay || !/^shut/.test(action))) {
+        showCommandError("Only shutdowns can be delayed (e.g., shutdown 10m)");
+        return;
+      }
+      try {
+        if (action === "lock") {
+          await invoke("lock_screen");
+        } else if (action === "sleep" || action === "suspend") {
+          await invoke("sleep_system");
+        } else if (action === "restart" || action === "reboot") {
+          if (!(await ask("Restart the computer now?", { title: "Restart", kind: "warning" }))) {
+            return;
+          }
+          await invoke("restart_system");
+        } else {
+          // Without a delay, the cancel window still gives a minute to change your mind
+          await invoke("shutdown_system", { delay: delay ?? 60 });
+          showCommandSuccess(`Shutting down in ${formatTimerDisplay(delay ?? 60)}`);
+          return;
+        }
+        showCommandSuccess("Done");
+      } catch (e) {
+        showCommandError(String(e));
+      }
+      return;
+    }
+    if (/^(?:cancel|abort|stop)\s+shut\s*down$/i.test(normalizedInput)) {
+      try {
+        await invoke("cancel_shutdown");
+        showCommandSuccess("Shutdown cancelled");
+      } catch (e) {
+        showCommandError(String(e));
+      }
+      return;
+    }
 
-      setGitProgress({
-        stage: 'complete',
-        percent: 100,
-        message: `Successfully downloaded ${result.files_count} files`,
-        processedFiles: result.files_count,
-        outputPath: result.output_path,
-      });
-    } catch (e) {
-      setGitProgress({
-        stage: 'error',
-        percent: 0,
-        message: '',
-        errorMessage: String(e),
-      });
+    // "roll 3d6+2", "roll d20" - dice, with the individual rolls
+    const rollMatch = normalizedInput.match(/^(?:roll|dice)\s+(.+)$/i);
+    if (rollMatch) {
+      try {
+        const roll = await invoke<DiceRoll>("roll_dice", { notation: rollMatch[1] });
+        const rolls = roll.groups.flatMap((g) => g.rolls).join(", ");
+        showCommandSuccess(`${roll.notation}: ${roll.total} (${rolls})`);
+      } catch (e) {
+        showCommandError(String(e));
+      }
+      return;
     }
-  };
 
-  const handleGitOpenFolder = async () => {
-    const path = gitProgress.outputPath || gitDownloadPath;
-    if (path) {
+    // "flip", "coin"
+    if (/^(?:flip(?:\s+a)?(?:\s+coin)?|coin(?:\s+flip)?)$/i.test(normalizedInput)) {
       try {
-        await invoke("open_folder_in_explorer", { path });
+        const side = await invoke<string>("flip_coin");
+        showCommandSuccess(side === "heads" ? "Heads" : "Tails");
       } catch (e) {
-        console.error("Failed to open folder:", e);
+        showCommandError(String(e));
       }
+      return;
     }
-  };
 
-  const handleGitReset = async () => {
-    // Cancel any ongoing download
-    try {
-      await invoke("cancel_git_download");
-    } catch (e) {
-      // Ignore errors if no download in progress
+    // "pick pizza, sushi, tacos", "pick 2 from alice, bob, carol"
+    const pickMatch = normalizedInput.match(/^(?:pick|choose)\s+(?:(\d+)\s+(?:from|of)\s+)?(.+)$/i);
+    if (pickMatch && pickMatch[2].includes(",")) {
+      try {
+        const picked = await invoke<string[]>("random_choice", {
+          items: pickMatch[2].split(","),
+          count: pickMatch[1] ? Number(pickMatch[1]) : null,
+        });
+        showCommandSuccess(`Picked ${picked.join(", ")}`);
+      } catch (e) {
+        showCommandError(String(e));
+      }
+      return;
     }
 
-    setGitUrlInput("");
-    setGitParsedUrl(null);
-    setGitDownloadPath("");
-    setGitDownloadOptions({
-      extractFiles: true,
-      flattenStructure: false,
-      createSubfolder: true,
-    });
-    setGitProgress({
-      stage: 'idle',
-      percent: 0,
-      message: '',
-    });
-  };
-
-  // Command-only mode: Helper to resolve destination (full path or alias)
-  const resolveDestination = async (destination: string): Promise<{ path: string; learned: boolean } | null> => {
-    // Check if it looks like a full path
-    const isFullPath = await invoke<boolean>("is_valid_path", { path: destination });
-
-    if (isFullPath) {
-      // It's a full path - learn it as an alias for future use
+    // "rolls", "random history" - copy recent picks, rolls and flips
+    if (/^(?:rolls|random\s+history)$/i.test(normalizedInput)) {
       try {
-        await invoke<string>("learn_path_alias", { path: destination });
+        const history = await invoke<RandomHistoryEntry[]>("get_random_history");
+        if (history.length === 0) {
+          showCommandError("Nothing rolled yet");
+          return;
+        }
+        await writeText(history.map((h) => (h.input ? `${h.input} → ${h.result}` : h.result)).join("\n"));
+        showCommandSuccess(`Copied ${history.length} results`);
       } catch (e) {
-        // Path might not exist yet, that's ok
+        showCommandError(String(e));
       }
-      return { path: destination, learned: true };
+      return;
     }
 
-    // Try to resolve as an alias
-    const resolved = await invoke<string | null>("resolve_path_alias", { alias: destination });
-    if (resolved) {
-      return { path: resolved, learned: false };
+    // "awake", "awake 45", "caffeinate 2h", "jiggle 1h" - keep the computer awake, optionally for a while;
+    // "jiggle" also nudges the mouse so chat apps don't go idle
+    const awakeMatch = normalizedInput.match(/^(awake|caffeinate|keep\s+awake|jiggle|mouse\s+jiggler?)(?:\s+(\d+)\s*(m|min|mins|minutes?|h|hr|hrs|hours?)?)?$/i);
+    if (awakeMatch) {
+      const mode: KeepAwakeMode = /jiggle/i.test(awakeMatch[1]) ? "jiggle" : "system";
+      const amount = awakeMatch[2] ? Number(awakeMatch[2]) : null;
+      const hours = /^h/i.test(awakeMatch[3] ?? "");
+      const duration = amount === null ? null : amount * (hours ? 3600 : 60);
+      const what = mode === "jiggle" ? "Jiggling the mouse" : "Keeping awake";
+      try {
+        await invoke("keep_awake", { mode, duration });
+        showCommandSuccess(
+          amount === null ? `${what} until turned off` : `${what} for ${amount} ${hours ? "h" : "min"}`
+        );
+      } catch (e) {
+        showCommandError(String(e));
+      }
+      return;
     }
 
-    return null;
-  };
-
-  // Parse time duration strings like "5m", "30 seconds", "1h30m", "90"
-  const parseTimeDuration = (input: string): number | null => {
-    const normalized = input.toLowerCase().trim();
-
-    // Try parsing compound durations like "1h30m" or "1 hour 30 minutes"
-    let totalSeconds = 0;
-    let matched = false;
-
-    // Pattern for hours
-    const hoursMatch = normalized.match(/(\d+)\s*(?:h|hour|hours)/);
-    if (hoursMatch) {
-      totalSeconds += parseInt(hoursMatch[1], 10) * 3600;
-      matched = true;
+    // "night light", "night light on", "night light off" - warmer screen colors
+    const nightLightMatch = normalizedInput.match(/^night\s*light(?:\s+(on|off))?$/i);
+    if (nightLightMatch) {
+      const enabled = nightLightMatch[1] ? nightLightMatch[1].toLowerCase() === "on" : !nightLight?.enabled;
+      try {
+        const status = await invoke<NightLightStatus>("set_night_light", { enabled });
+        setNightLight(status);
+        showCommandSuccess(status.enabled ? "Night light on" : "Night light off");
+      } catch (e) {
+        showCommandError(String(e));
+      }
+      return;
     }
 
-    // Pattern for minutes
-    const minutesMatch = normalized.match(/(\d+)\s*(?:m|min|mins|minute|minutes)/);
-    if (minutesMatch) {
-      totalSeconds += parseInt(minutesMatch[1], 10) * 60;
-      matched = true;
+    // "stop awake", "stop jiggle", "allow sleep"
+    if (/^(?:(?:stop|end|cancel)\s+(?:awake|caffeinate|keep\s+awake|jiggle|jiggling|mouse\s+jiggler?)|allow\s+sleep)$/i.test(normalizedInput)) {
+      try {
+        await invoke("keep_awake", { mode: "off" });
+        showCommandSuccess("Sleep allowed again");
+      } catch (e) {
+        showCommandError(String(e));
+      }
+      return;
     }
 
-    // Pattern for seconds
-    const secondsMatch = normalized.match(/(\d+)\s*(?:s|sec|secs|second|seconds)/);
-    if (secondsMatch) {
-      totalSeconds += parseInt(secondsMatch[1], 10);
-      matched = true;
+    // "autoclick", "autoclick 50ms right", "autoclick 2s x20" - click at the cursor until the stop hotkey (F6)
+    const autoclickMatch = normalizedInput.match(
+      /^(?:auto\s*-?\s*click(?:er)?|clicker)(?:\s+(\d+(?:\.\d+)?)\s*(ms|s|sec|secs|seconds?)?)?(?:\s+(left|right|middle))?(?:\s+x\s*(\d+))?$/i
+    );
+    if (autoclickMatch) {
+      const amount = autoclickMatch[1] ? Number(autoclickMatch[1]) : 100;
+      const seconds = /^s/i.test(autoclickMatch[2] ?? "");
+      try {
+        await invoke("hide_window");
+        await invoke("start_auto_clicker", {
+          config: {
+            interval_ms: Math.round(seconds ? amount * 1000 : amount),
+            button: (autoclickMatch[3]?.toLowerCase() ?? "left") as MouseButton,
+            max_clicks: autoclickMatch[4] ? Number(autoclickMatch[4]) : null,
+          },
+        });
+      } catch (e) {
+        await invoke("show_window");
+        showCommandError(String(e));
+      }
+      return;
     }
 
-    if (matched) {
-      return totalSeconds > 0 ? totalSeconds : null;
+    // "m+", "m+ 12.5", "m-", "mr", "mc" - calculator memory; without a number the last result is used
+    const memoryMatch = normalizedInput.match(/^m\s*([+-]|r|c)(?:\s+(-?\d+(?:[.,]\d+)?))?$/i);
+    if (memoryMatch) {
+      const op = { "+": "add", "-": "subtract", r: "recall", c: "clear" }[memoryMatch[1].toLowerCase()];
+      try {
+        const memory = await invoke<number>("calc_memory", {
+          op,
+          value: memoryMatch[2] ? Number(memoryMatch[2].replace(",", ".")) : null,
+        });
+        setCalcHistory((prev) => (prev ? { ...prev, memory } : prev));
+        if (op === "recall") {
+          // Continue calculating with the recalled value
+          setQuery(String(memory));
+          await writeText(String(memory));
+          showCommandSuccess(`M = ${memory}, copied`);
+        } else {
+          showCommandSuccess(`M = ${memory}`);
+        }
+      } catch (e) {
+        showCommandError(String(e));
+      }
+      return;
     }
 
-    // Try bare number (treat as seconds)
-    const bareNumber = parseInt(normalized, 10);
-    if (!isNaN(bareNumber) && bareNumber > 0) {
-      return bareNumber;
+    // "tape" - save the calculator tape, "clear tape" - empty it (memory is kept)
+    if (/^(?:calc(?:ulator)?\s+(?:tape|history)|tape)$/i.test(normalizedInput)) {
+      try {
+        const saved = await exportCalcTape();
+        if (saved) showCommandSuccess("Tape saved");
+      } catch (e) {
+        showCommandError(String(e));
+      }
+      return;
     }
-
-    return null;
-  };
-
-  // Format seconds as MM:SS or HH:MM:SS
-  const formatTimerDisplay = (seconds: number): string => {
-    const h = Math.floor(seconds / 3600);
-    const m = Math.floor((seconds % 3600) / 60);
-    const s = seconds % 60;
-
-    if (h > 0) {
-      return `${h}:${m.toString().padStart(2, '0')}:${s.toString().padStart(2, '0')}`;
+    if (/^clear\s+(?:calc(?:ulator)?\s+)?tape$/i.test(normalizedInput)) {
+      try {
+        await invoke("clear_calc_history");
+        setCalcHistory((prev) => (prev ? { ...prev, entries: [] } : prev));
+        showCommandSuccess("Tape cleared");
+      } catch (e) {
+        showCommandError(String(e));
+      }
+      return;
     }
-    return `${m}:${s.toString().padStart(2, '0')}`;
-  };
-
-  // Command-only mode: Helper to show error and reset
-  const showCommandError = (message: string) => {
-    setCommandStatus({ message, type: 'error' });
-    commandStatusTimeoutRef.current = setTimeout(() => {
-      setCommandStatus({ message: "Type a command...", type: 'idle' });
-    }, 1500);
-  };
 
-  // Command-only mode: Helper to show success and reset
-  const showCommandSuccess = (message: string, resultPath?: string) => {
-    if (resultPath) {
-      setLastResultPath(resultPath);
+    // "snip", "annotate" - capture a region and mark it up
+    if (/^(?:snip|annotate|screenshot|markup)$/i.test(normalizedInput)) {
+      try {
+        await invoke("start_screen_annotation");
+      } catch (e) {
+        await invoke("show_window");
+        if (e !== "Cancelled") showCommandError(String(e));
+      }
+      return;
     }
-    setCommandStatus({ message, type: 'success' });
-    commandStatusTimeoutRef.current = setTimeout(() => {
-      setCommandStatus({ message: "Type a command...", type: 'idle' });
-    }, 1500);
-  };
 
-  // Command-only mode: Execute command from natural language input
-  const executeCommand = async (input: string) => {
-    // Clear any existing timeout
-    if (commandStatusTimeoutRef.current) {
-      clearTimeout(commandStatusTimeoutRef.current);
-      commandStatusTimeoutRef.current = null;
+    // "define serendipity", "define Bank in de" - definitions, synonyms and examples
+    const defineMatch = normalizedInput.match(/^(?:define|def|dict)\s+(.+?)(?:\s+in\s+([a-z]{2}))?$/i);
+    if (defineMatch) {
+      setCommandStatus({ message: `Looking up "${defineMatch[1]}"...`, type: 'progress' });
+      try {
+        const definition = await invoke<WordDefinition>("define_word", {
+          word: defineMatch[1],
+          lang: defineMatch[2]?.toLowerCase(),
+        });
+        setCommandStatus({ message: formatDefinition(definition, 2), type: 'help' });
+      } catch (e) {
+        showCommandError(String(e));
+      }
+      return;
     }
 
-    // Add to history (avoid duplicates of last command)
-    const trimmedInput = input.trim();
-    if (trimmedInput && (commandHistory.length === 0 || commandHistory[0] !== trimmedInput)) {
-      setCommandHistory(prev => [trimmedInput, ...prev].slice(0, 50)); // Keep last 50 commands
+    // "record audio", "record audio wav from yeti", "mics" - microphone recording
+    if (/^(?:mics|microphones|audio\s+devices)$/i.test(normalizedInput)) {
+      try {
+        const devices = await invoke<AudioInputDevice[]>("list_audio_devices");
+        setCommandStatus({
+          message: devices.length > 0 ? devices.map((d) => d.name).join(" · ") : "No microphones found",
+          type: 'help',
+        });
+      } catch (e) {
+        showCommandError(String(e));
+      }
+      return;
     }
-    setHistoryIndex(-1);
-
-    // Normalize command: expand shortcuts
-    let normalizedInput = trimmedInput;
-    if (normalizedInput.match(/^dl\s/i)) {
-      normalizedInput = normalizedInput.replace(/^dl\s/i, 'download ');
+    const recordAudioMatch = normalizedInput.match(/^record\s+audio(?:\s+(mp3|wav|opus))?(?:\s+from\s+(.+))?$/i);
+    if (recordAudioMatch) {
+      try {
+        let device: string | undefined;
+        if (recordAudioMatch[2]) {
+          const wanted = recordAudioMatch[2].toLowerCase();
+          const devices = await invoke<AudioInputDevice[]>("list_audio_devices");
+          const match = devices.find((d) => d.name.toLowerCase().includes(wanted));
+          if (!match) {
+            showCommandError(`No microphone matching "${recordAudioMatch[2]}"`);
+            return;
+          }
+          device = match.id;
+        }
+        const started = await invoke<AudioRecordingStatus>("start_audio_recording", {
+          device,
+          format: recordAudioMatch[1]?.toLowerCase(),
+        });
+        showCommandSuccess(`Recording from ${started.device}`);
+      } catch (e) {
+        showCommandError(String(e));
+      }
+      return;
     }
-    if (normalizedInput.match(/^op\s/i) || normalizedInput.match(/^op$/i)) {
-      normalizedInput = normalizedInput.replace(/^op(\s|$)/i, 'open$1');
+    if (/^stop\s+audio(?:\s+recording)?$/i.test(normalizedInput)) {
+      try {
+        const finished = await invoke<AudioRecordingStatus | null>("stop_audio_recording");
+        if (finished) {
+          showCommandSuccess(`Saved ${formatDuration(finished.elapsed_secs)} recording to ${finished.path}`);
+        } else {
+          showCommandError("Nothing is being recorded");
+        }
+      } catch (e) {
+        showCommandError(String(e));
+      }
+      return;
     }
 
-    // === HELP COMMAND ===
-    if (normalizedInput.match(/^(help|\?)$/i)) {
-      setCommandStatus({
-        message: "dl | open | scan | kill | launch | copy | clipboard | timer",
-        type: 'help',
-      });
-      // Help doesn't auto-dismiss - user types to clear
+    // "windows", "pin notepad", "unpin notepad", "opacity 60 youtube" - other apps' windows
+    if (/^windows$/i.test(normalizedInput)) {
+      try {
+        const windows = await invoke<ExternalWindow[]>("list_external_windows");
+        setCommandStatus({
+          message:
+            windows.length > 0
+              ? windows
+                  .slice(0, 12)
+                  .map((w) => `${w.topmost ? "📌 " : ""}${w.title}${w.opacity < 1 ? ` (${Math.round(w.opacity * 100)}%)` : ""}`)
+                  .join(" · ")
+              : "No windows found",
+          type: 'help',
+        });
+      } catch (e) {
+        showCommandError(String(e));
+      }
       return;
     }
-
-    // === OPEN COMMAND ===
-    // "open" or "open <alias/path>"
-    const openMatch = normalizedInput.match(/^open(?:\s+(.+))?$/i);
-    if (openMatch) {
-      const target = openMatch[1]?.trim();
-
-      let pathToOpen: string | null = null;
-
-      if (!target) {
-        // No target - use last result path
-        if (lastResultPath) {
-          pathToOpen = lastResultPath;
-        } else {
-          showCommandError("No recent path to open");
+    const pinMatch = normalizedInput.match(/^(pin|unpin)\s+(.+)$/i);
+    const opacityMatch = normalizedInput.match(/^opacity\s+(\d{1,3})%?\s+(.+)$/i);
+    if (pinMatch || opacityMatch) {
+      try {
+        const wanted = (pinMatch ?? opacityMatch)![2];
+        const windows = await invoke<ExternalWindow[]>("list_external_windows");
+        const target = findExternalWindow(windows, wanted);
+        if (!target) {
+          showCommandError(`No window matching "${wanted}"`);
           return;
         }
-      } else {
-        // Resolve the target
-        const resolved = await resolveDestination(target);
-        if (resolved) {
-          pathToOpen = resolved.path;
+        if (pinMatch) {
+          const on = pinMatch[1].toLowerCase() === "pin";
+          await invoke("set_external_window_topmost", { windowId: target.id, on });
+          showCommandSuccess(`${on ? "Pinned" : "Unpinned"} ${target.title}`);
         } else {
-          showCommandError(`Unknown location: ${target}`);
-          return;
+          const percent = Number(opacityMatch![1]);
+          await invoke("set_external_window_opacity", { windowId: target.id, alpha: percent / 100 });
+          showCommandSuccess(`${target.title} at ${percent}% opacity`);
         }
+      } catch (e) {
+        showCommandError(String(e));
+      }
+      return;
+    }
+
+    // "shortcut color picker" - a desktop icon that opens the tool directly
+    const shortcutMatch = normalizedInput.match(/^shortcut\s+(.+)$/i);
+    if (shortcutMatch) {
+      const wanted = shortcutMatch[1].trim().toLowerCase();
+      const tool =
+        tools.find((t) => t.id === wanted || t.name.toLowerCase() === wanted) ??
+        tools.find((t) => t.name.toLowerCase().startsWith(wanted)) ??
+        tools.find((t) => t.name.toLowerCase().includes(wanted));
+      if (!tool || tool.isSettings) {
+        showCommandError(`No tool matching "${shortcutMatch[1].trim()}"`);
+        return;
+      }
+      try {
+        const path = await invoke<string>("create_desktop_shortcut", {
+          action: "open_tool",
+          args: { tool: tool.id },
+          name: tool.name,
+        });
+        showCommandSuccess(`Desktop shortcut for ${tool.name} created`, path);
+      } catch (e) {
+        showCommandError(String(e));
+      }
+      return;
+    }
+
+    // "data usage", "data usage week" - what the tools downloaded and uploaded;
+    // "data cap 50gb pause", "data cap 20 gb" (warns), "data cap off"
+    const usageMatch = normalizedInput.match(/^data\s+usage(?:\s+(today|week|month|year))?$/i);
+    if (usageMatch) {
+      try {
+        const range = (usageMatch[1]?.toLowerCase() ?? "month") as TransferRange;
+        const stats = await invoke<TransferStats>("get_transfer_stats", { range });
+        setCommandStatus({ message: formatTransferStats(stats, tools), type: 'help' });
+      } catch (e) {
+        showCommandError(String(e));
+      }
+      return;
+    }
+    const capMatch = normalizedInput.match(/^data\s+cap\s+(off|none|[\d.]+\s*[kmgt]?i?b?)(?:\s+(warn|pause))?$/i);
+    if (capMatch) {
+      const off = /^(off|none)$/i.test(capMatch[1]);
+      const bytes = off ? null : parseByteSize(capMatch[1]);
+      if (!off && !bytes) {
+        showCommandError(`"${capMatch[1]}" isn't a size, try 50gb`);
+        return;
       }
-
       try {
-        await invoke("open_folder_in_explorer", { path: pathToOpen });
-        showCommandSuccess(`Opened ${pathToOpen}`, pathToOpen);
+        const action = (capMatch[2]?.toLowerCase() ?? "warn") as "warn" | "pause";
+        await invoke("set_transfer_cap", { cap: off ? null : { monthly_bytes: bytes, action } });
+        showCommandSuccess(
+          off
+            ? "Monthly data cap removed"
+            : `Monthly data cap ${formatFileSize(bytes!)}, ${action === "pause" ? "pausing downloads" : "warning"} when it's reached`
+        );
       } catch (e) {
         showCommandError(String(e));
       }
       return;
     }
 
-    // === DOWNLOAD COMMAND ===
-    // "download <url> [to <destination>]" or "download clipboard [to <destination>]"
-    // Auto-detects URL type: GitHub, YouTube, etc.
-    const downloadMatch = normalizedInput.match(/^download\s+(\S+)(?:\s+to\s+(.+))?$/i);
-    if (downloadMatch) {
-      let url = downloadMatch[1];
-      const destination = downloadMatch[2]?.trim();
-
-      // Handle "clipboard" keyword
-      if (url.toLowerCase() === 'clipboard') {
-        try {
-          const clipboardText = await readText();
-          if (!clipboardText) {
-            showCommandError("Clipboard is empty");
-            return;
-          }
-          url = clipboardText.trim();
-        } catch (e) {
-          showCommandError("Could not read clipboard");
-          return;
-        }
+    // "clear translation cache" - translate remembered strings with the engine again
+    if (/^clear\s+translation\s+cache$/i.test(normalizedInput)) {
+      try {
+        const count = await invoke<number>("clear_translation_cache");
+        showCommandSuccess(`${count} cached translation${count === 1 ? "" : "s"} forgotten`);
+      } catch (e) {
+        showCommandError(String(e));
       }
+      return;
+    }
 
-      // Resolve destination path first (common for all download types)
-      let outputPath: string;
-      if (destination) {
-        const resolved = await resolveDestination(destination);
-        if (resolved) {
-          outputPath = resolved.path;
+    // "translations", "translations good morning" - find a past translation and copy the newest match;
+    // "clear translations" forgets them
+    if (/^clear\s+translations$/i.test(normalizedInput)) {
+      try {
+        await invoke("clear_translation_history");
+        showCommandSuccess("Translation history cleared");
+      } catch (e) {
+        showCommandError(String(e));
+      }
+      return;
+    }
+    const translationsMatch = normalizedInput.match(/^(?:translations|translation\s+history)(?:\s+(.+))?$/i);
+    if (translationsMatch) {
+      try {
+        const entries = await invoke<TranslationHistoryEntry[]>("get_translation_history", {
+          query: translationsMatch[1] ?? null,
+        });
+        if (entries.length === 0) {
+          showCommandError(translationsMatch[1] ? `No translation matches "${translationsMatch[1]}"` : "No translations yet");
         } else {
-          showCommandError(`Unknown location: ${destination}`);
-          return;
-        }
-      } else {
-        try {
-          outputPath = await invoke<string>("get_downloads_path");
-        } catch (e) {
-          showCommandError("Could not find Downloads folder");
-          return;
+          await writeText(entries[0].translated_text);
+          const more = entries.length > 1 ? ` (+${entries.length - 1} more)` : "";
+          showCommandSuccess(`Copied: ${formatTranslationHistory(entries, 1)}${more}`);
         }
+      } catch (e) {
+        showCommandError(String(e));
       }
+      return;
+    }
 
-      // Try to detect URL type and download accordingly
-
-      // Check if it's a YouTube URL
-      const ytParsed = parseYouTubeUrl(url);
-      if (ytParsed?.isValid) {
-        setCommandStatus({ message: "Downloading video...", type: 'progress' });
-        try {
-          const result = await invoke<string>("download_youtube_video", {
-            url: url,
-            outputPath: outputPath,
-            options: {
-              quality: 'best',
-              mode: 'video_audio',
-            },
+    // "glossary de Kubernetes = Kubernetes", "glossary en>de pull request = Pull Request" - always
+    // translate a term the same way; "glossary rm <term>", "glossary", "glossary de" - list
+    const glossaryMatch = normalizedInput.match(/^glossary(?:\s+(.+))?$/i);
+    if (glossaryMatch) {
+      const args = glossaryMatch[1] ?? "";
+      const addMatch = args.match(/^(?:([a-z]{2,3})\s*>\s*)?([a-z]{2,3})\s+(.+?)\s*=\s*(.+)$/i);
+      const removeMatch = args.match(/^(?:rm|remove|delete)\s+(.+)$/i);
+      try {
+        if (addMatch) {
+          const entry = await invoke<GlossaryEntry>("save_glossary_entry", {
+            sourceLang: addMatch[1] ?? null,
+            targetLang: addMatch[2],
+            term: addMatch[3],
+            translation: addMatch[4],
           });
-          showCommandSuccess(result, result);
-        } catch (e) {
-          showCommandError(String(e));
+          showCommandSuccess(`Glossary: "${entry.term}" → "${entry.translation}" (${entry.source_lang || "any"} → ${entry.target_lang})`);
+        } else if (removeMatch) {
+          const removed = await invoke<number>("delete_glossary_entry", { term: removeMatch[1] });
+          showCommandSuccess(`Removed ${removed} glossary ${removed === 1 ? "entry" : "entries"}`);
+        } else if (!args || /^[a-z]{2,3}$/i.test(args)) {
+          const entries = await invoke<GlossaryEntry[]>("get_glossary", { targetLang: args || null });
+          showCommandSuccess(
+            entries.length > 0
+              ? `Glossary: ${entries.map((e) => `${e.term} → ${e.translation} (${e.target_lang})`).join(", ")}`
+              : 'No glossary terms yet: "glossary de <term> = <translation>"'
+          );
+        } else {
+          showCommandError("Usage: glossary [en>]de <term> = <translation>");
         }
-        return;
+      } catch (e) {
+        showCommandError(String(e));
       }
+      return;
+    }
 
-      // Check if it's a GitHub URL
-      const gitParsed = parseGitHubUrl(url);
-      if (gitParsed?.isValid) {
-        setCommandStatus({ message: "Downloading... 0%", type: 'progress' });
-        try {
-          const result = await invoke<GitDownloadResult>("download_github_folder", {
-            urlInfo: {
-              owner: gitParsed.owner,
-              repo: gitParsed.repo,
-              branch: gitParsed.branch,
-              path: gitParsed.path,
-            },
-            outputPath: outputPath,
-            options: {
-              extract_files: true,
-              flatten_structure: false,
-              create_subfolder: true,
-            },
-          });
-          showCommandSuccess(result.output_path, result.output_path);
-        } catch (e) {
+    // "ocr", "translate screen", "ocr ja" - translate text read from a screen region
+    const ocrMatch = normalizedInput.match(/^(?:ocr|translate\s+screen|screen\s+translat(?:e|ion))(?:\s+(?:to\s+)?([a-z]{2,3}(?:-[a-z]{2,4})?))?$/i);
+    if (ocrMatch) {
+      setQuery("");
+      await translateScreenRegion(ocrMatch[1]);
+      return;
+    }
+
+    // "scan qr", "qr scan full" - read QR codes from a screen region, or the whole screen
+    const qrScanMatch = normalizedInput.match(/^(?:scan\s+qr|qr\s+scan|read\s+qr)(?:\s+(full|screen|all))?$/i);
+    if (qrScanMatch) {
+      setQuery("");
+      try {
+        const codes = await invoke<ScannedCode[]>("scan_qr_from_screen", { fullScreen: !!qrScanMatch[1] });
+        const described = codes.map(describeScannedCode);
+        await writeText(described.map((d) => d.copy).join("\n"));
+        showCommandSuccess(described.map((d) => d.status).join(" · "));
+      } catch (e) {
+        if (e !== "Cancelled") {
           showCommandError(String(e));
         }
-        return;
       }
-
-      // TODO: Add TikTok and other URL type detection here
-
-      // Unknown URL type
-      showCommandError("Unsupported URL type");
       return;
     }
 
-    // === SCAN COMMAND ===
-    // "scan <port>" - scan for processes using a port
-    const scanMatch = normalizedInput.match(/^scan\s+(\d+)$/i);
-    if (scanMatch) {
-      const port = parseInt(scanMatch[1], 10);
-
-      if (isNaN(port) || port <= 0 || port > 65535) {
-        showCommandError("Invalid port number (1-65535)");
-        return;
+    // "record", "record gif", "record webm audio 60fps", "record 1280x720+0+0" - record the screen until stopped
+    const recordMatch = normalizedInput.match(
+      /^(?:record|rec|screen\s*record(?:ing)?)((?:\s+(?:mp4|mkv|webm|gif|audio|\d+\s*fps|\d+x\d+[+-]\d+[+-]\d+))*)$/i
+    );
+    if (recordMatch) {
+      const words = recordMatch[1].trim().toLowerCase().split(/\s+/).filter(Boolean);
+      const format = words.find((w) => /^(?:mp4|mkv|webm|gif)$/.test(w)) as RecordingFormat | undefined;
+      const fps = recordMatch[1].match(/(\d+)\s*fps/i);
+      const region = recordMatch[1].match(/(\d+)x(\d+)([+-]\d+)([+-]\d+)/);
+      try {
+        await invoke("hide_window");
+        await invoke("start_screen_recording", {
+          options: {
+            format: format ?? "mp4",
+            audio: words.includes("audio"),
+            fps: fps ? Number(fps[1]) : 30,
+            region: region
+              ? { width: Number(region[1]), height: Number(region[2]), x: Number(region[3]), y: Number(region[4]) }
+              : null,
+          },
+        });
+      } catch (e) {
+        await invoke("show_window");
+        showCommandError(String(e));
       }
+      return;
+    }
 
-      setCommandStatus({ message: "Scanning...", type: 'progress' });
-
+    // "habit done water", "habit undo water", "habit add Water at 9am", "habit remove water", "habits"
+    const habitMatch = normalizedInput.match(/^habits?(?:\s+(done|did|check|undo|uncheck|add|new|remove|delete)\s+(.+))?$/i);
+    if (habitMatch) {
+      const [, verb, rest] = habitMatch;
       try {
-        const processes = await invoke<PortProcess[]>("scan_port", { port });
-
-        if (processes.length === 0) {
-          showCommandError(`Port ${port} is free`);
-        } else if (processes.length === 1) {
-          const p = processes[0];
-          showCommandSuccess(`${p.name} (PID ${p.pid}) ${p.protocol}`);
+        if (!verb) {
+          const streaks = await invoke<HabitStreak[]>("get_streaks");
+          if (streaks.length === 0) {
+            showCommandError('No habits yet - "habit add <name> [at <time>]"');
+          } else {
+            showCommandSuccess(streaks.map(formatHabitStreak).join(" · "));
+          }
+        } else if (/^(?:done|did|check)$/i.test(verb)) {
+          const streak = await invoke<HabitStreak>("check_in_habit", { habit: rest });
+          showCommandSuccess(formatHabitStreak(streak));
+        } else if (/^(?:undo|uncheck)$/i.test(verb)) {
+          const streak = await invoke<HabitStreak>("undo_habit_check_in", { habit: rest });
+          showCommandSuccess(`Unchecked ${streak.name}`);
+        } else if (/^(?:add|new)$/i.test(verb)) {
+          const [, name, reminder] = rest.match(/^(.+?)(?:\s+at\s+(\S+(?:\s*[ap]m)?))?$/i)!;
+          const habit = await invoke<Habit>("add_habit", { name, reminder: reminder ?? null });
+          showCommandSuccess(`Added ${habit.name}${habit.reminder ? `, reminder at ${habit.reminder}` : ""}`);
         } else {
-          // Multiple processes found
-          const names = [...new Set(processes.map(p => p.name))].join(', ');
-          showCommandSuccess(`${processes.length} processes: ${names}`);
+          await invoke("delete_habit", { habit: rest });
+          showCommandSuccess("Habit removed");
         }
       } catch (e) {
         showCommandError(String(e));
@@ -2216,97 +5069,97 @@ function App() {
       return;
     }
 
-    // === KILL COMMAND ===
-    // "kill <port>" - kills the process using that port
-    const killMatch = normalizedInput.match(/^kill\s+(\d+)$/i);
-    if (killMatch) {
-      const port = parseInt(killMatch[1], 10);
+    // "country de", "country +44", "country EUR" - ISO codes, calling code, currency and flag
+    const countryMatch = normalizedInput.match(/^(?:country|countries)\s+(.+)$/i);
+    if (countryMatch) {
+      const countries = await invoke<Country[]>("lookup_country", { query: countryMatch[1] });
+      if (countries.length === 0) {
+        showCommandError(`No country called "${countryMatch[1]}"`);
+      } else if (countries.length === 1) {
+        showCommandSuccess(formatCountry(countries[0]));
+      } else {
+        const names = countries.slice(0, 8).map((c) => `${c.flag} ${c.name}`);
+        showCommandSuccess(`${countries.length} countries: ${names.join(", ")}${countries.length > 8 ? ", ..." : ""}`);
+      }
+      return;
+    }
 
-      if (isNaN(port) || port <= 0 || port > 65535) {
-        showCommandError("Invalid port number");
+    // "holidays", "holidays japan", "holidays de 2027" - public holidays (of the locale's country by default)
+    const holidaysMatch = normalizedInput.match(/^(?:public\s+)?holidays?(?:\s+(?!\d{4}$)(.+?))?(?:\s+(\d{4}))?$/i);
+    if (holidaysMatch) {
+      const country = holidaysMatch[1] ?? localeCountry();
+      if (!country) {
+        showCommandError("Which country? e.g. holidays de");
         return;
       }
-
-      setCommandStatus({ message: "Finding process...", type: 'progress' });
-
       try {
-        // First scan to find the PID
-        const processes = await invoke<PortProcess[]>("scan_port", { port });
-
-        if (processes.length === 0) {
-          showCommandError(`No process on port ${port}`);
-          return;
-        }
-
-        // Kill all processes on this port
-        for (const p of processes) {
-          await invoke("kill_port_process", { pid: p.pid });
-        }
-
-        if (processes.length === 1) {
-          showCommandSuccess(`Killed ${processes[0].name} (PID ${processes[0].pid})`);
-        } else {
-          showCommandSuccess(`Killed ${processes.length} processes on port ${port}`);
-        }
+        const calendar = await invoke<HolidayCalendar>("get_public_holidays", {
+          country,
+          year: holidaysMatch[2] ? Number(holidaysMatch[2]) : null,
+        });
+        showCommandSuccess(formatHolidays(calendar));
       } catch (e) {
         showCommandError(String(e));
       }
       return;
     }
 
-    // === LAUNCH/RUN/START COMMAND ===
-    // "launch <app>", "run <app>", "start <app>" - launch an application
-    const launchMatch = normalizedInput.match(/^(?:launch|run|start)\s+(.+)$/i);
-    if (launchMatch) {
-      const appName = launchMatch[1].trim();
-
-      if (!appName) {
-        showCommandError("Specify an app name");
-        return;
+    // "sun", "sunrise paris", "sunset 52.52, 13.40 tomorrow", "sun tokyo 2026-12-21"
+    const sunMatch = normalizedInput.match(/^(?:sun|sunrise|sunset|golden\s+hour)(?:\s+(?!(?:today|tomorrow|\d{4}-\d{2}-\d{2})$)(.+?))?(?:\s+(today|tomorrow|\d{4}-\d{2}-\d{2}))?$/i);
+    if (sunMatch) {
+      let date: string | null = sunMatch[2] ?? null;
+      if (date && !/^\d/.test(date)) {
+        const day = new Date();
+        if (date.toLowerCase() === "tomorrow") day.setDate(day.getDate() + 1);
+        date = `${day.getFullYear()}-${String(day.getMonth() + 1).padStart(2, "0")}-${String(day.getDate()).padStart(2, "0")}`;
       }
-
-      setCommandStatus({ message: `Launching ${appName}...`, type: 'progress' });
-
       try {
-        await invoke("launch_app", { appName });
-        showCommandSuccess(`Launched ${appName}`);
+        const report = await invoke<SunReport>("get_sun_times", { location: sunMatch[1] ?? null, date });
+        showCommandSuccess(formatSunTimes(report));
       } catch (e) {
         showCommandError(String(e));
       }
       return;
     }
 
-    // === CLIPBOARD COMMANDS ===
-    // "copy: <text>" or "copy this: <text>" - write text to clipboard
-    const copyMatch = normalizedInput.match(/^copy(?:\s+this)?[:\s]+(.+)$/i);
-    if (copyMatch) {
-      const textToCopy = copyMatch[1].trim();
-
-      if (!textToCopy) {
-        showCommandError("Specify text to copy");
-        return;
+    // "track Write docs @Acme" - start a time entry, "stop tracking" - stop it
+    const trackMatch = normalizedInput.match(/^track\s+(.+?)(?:\s+@(\S.*))?$/i);
+    if (trackMatch && !/^(?:time|report)$/i.test(trackMatch[1])) {
+      try {
+        const started = await invoke<TimeEntry>("start_time_entry", {
+          label: trackMatch[1],
+          project: trackMatch[2] ?? null,
+        });
+        showCommandSuccess(`Tracking "${started.label}"${started.project ? ` for ${started.project}` : ""}`);
+      } catch (e) {
+        showCommandError(String(e));
       }
-
+      return;
+    }
+    if (/^(?:stop|end|finish)\s+(?:tracking|track(?:ing)?\s+time|time\s+entry|timesheet)$/i.test(normalizedInput)) {
       try {
-        await invoke("write_clipboard", { text: textToCopy });
-        showCommandSuccess("Copied to clipboard");
+        const stopped = await invoke<TimeEntry>("stop_time_entry");
+        showCommandSuccess(`Tracked ${formatHoursMinutes(timeEntryDuration(stopped))} on "${stopped.label}"`);
       } catch (e) {
         showCommandError(String(e));
       }
       return;
     }
 
-    // "clipboard", "show clipboard", "what's in clipboard", "paste" - read clipboard
-    const clipboardMatch = normalizedInput.match(/^(?:clipboard|show\s+clipboard|what'?s?\s+in\s+(?:my\s+)?clipboard|paste)$/i);
-    if (clipboardMatch) {
+    // "time report", "time report yesterday", "timesheet last week"; "export time week" saves CSV
+    const timeReportMatch = normalizedInput.match(
+      /^(export\s+)?(?:time(?:\s*report|sheet)?|hours)(?:\s+(today|yesterday|(?:this\s+)?week|last\s+week|all))?$/i
+    );
+    if (timeReportMatch && (timeReportMatch[1] || !/^(?:time|hours)$/i.test(normalizedInput))) {
+      const word = (timeReportMatch[2] ?? (timeReportMatch[1] ? "all" : "today")).toLowerCase().replace(/^this\s+/, "");
+      const range = word === "all" ? null : (word.replace(/\s+/, "_") as TimeReportRange);
       try {
-        const content = await invoke<string>("read_clipboard");
-        if (!content || content.trim() === "") {
-          showCommandError("Clipboard is empty");
+        if (timeReportMatch[1]) {
+          const saved = await exportTimeEntries(range);
+          if (saved) showCommandSuccess("Time entries saved");
         } else {
-          // Truncate long content for display
-          const displayContent = content.length > 50 ? content.substring(0, 47) + "..." : content;
-          showCommandSuccess(displayContent);
+          const report = await invoke<TimeReport>("get_time_report", { range: range ?? "week" });
+          showCommandSuccess(formatTimeReport(report));
         }
       } catch (e) {
         showCommandError(String(e));
@@ -2314,23 +5167,39 @@ function App() {
       return;
     }
 
-    // === TIMER COMMANDS ===
-    // "timer <duration>" - start a timer
-    const timerMatch = normalizedInput.match(/^timer\s+(.+)$/i);
-    if (timerMatch) {
-      const durationStr = timerMatch[1].trim();
-      const seconds = parseTimeDuration(durationStr);
+    // "stop recording"
+    if (/^(?:stop|end|finish)\s+(?:screen\s+)?record(?:ing)?$/i.test(normalizedInput)) {
+      try {
+        const finished = await invoke<RecordingStatus | null>("stop_screen_recording");
+        if (finished === null) {
+          showCommandError("Nothing is being recorded");
+        } else {
+          await writeText(finished.path);
+          showCommandSuccess(`Saved ${formatDuration(finished.elapsed_secs)} recording, path copied`);
+        }
+      } catch (e) {
+        showCommandError(String(e));
+      }
+      return;
+    }
 
-      if (!seconds) {
-        showCommandError("Invalid duration (e.g., 5m, 30s, 1h30m)");
-        return;
+    // "stop autoclick"
+    if (/^(?:stop|end|cancel)\s+(?:auto\s*-?\s*click(?:er)?|clicker|clicking)$/i.test(normalizedInput)) {
+      const clicks = await invoke<number | null>("stop_auto_clicker");
+      if (clicks === null) {
+        showCommandError("The auto-clicker isn't running");
+      } else {
+        showCommandSuccess(`Stopped after ${clicks} clicks`);
       }
+      return;
+    }
 
+    // "stop focus", "stop pomodoro"
+    const stopFocusMatch = normalizedInput.match(/^(?:stop|end|cancel)\s+(?:focus|pomodoro)$/i);
+    if (stopFocusMatch) {
       try {
-        await invoke("start_timer", { seconds, label: `Timer ${formatTimerDisplay(seconds)}` });
-        setTimerRemaining(seconds);
-        setTimerLabel(`Timer ${formatTimerDisplay(seconds)}`);
-        showCommandSuccess(`Timer set for ${formatTimerDisplay(seconds)}`);
+        await invoke("stop_pomodoro");
+        showCommandSuccess("Focus session stopped");
       } catch (e) {
         showCommandError(String(e));
       }
@@ -2422,10 +5291,7 @@ function App() {
     isDialogOpenRef.current = true;
     await invoke("set_auto_hide", { enabled: false });
 
-    const result = await open({
-      directory: true,
-      multiple: false,
-    });
+    const result = await pickOutputDirectory("Download to");
 
     await invoke("set_auto_hide", { enabled: true });
     isDialogOpenRef.current = false;
@@ -2537,6 +5403,8 @@ function App() {
           inputRef={inputRef}
           toolItemRefs={toolItemRefs}
           onDragStart={handleDragStart}
+          tip={toolTip}
+          onDismissTip={dismissToolTip}
         />
       )}
 
@@ -2545,6 +5413,7 @@ function App() {
         <SettingsPanel
           settings={settings}
           setSettings={setSettings}
+          activeHotkey={activeHotkey}
           isRecordingHotkey={isRecordingHotkey}
           setIsRecordingHotkey={setIsRecordingHotkey}
           hotkeyInputRef={hotkeyInputRef}
@@ -2558,6 +5427,7 @@ function App() {
       {showVideoConverter && (
         <VideoConverter
           videoFile={videoFile}
+          batchCount={videoBatch.length}
           setVideoFile={setVideoFile}
           selectedFormat={videoFormat}
           setSelectedFormat={setVideoFormat}
@@ -2598,6 +5468,7 @@ function App() {
           setTranslationInput={setTranslationInput}
           translationOutput={translationOutput}
           detectedLanguage={detectedLanguage}
+          detectionWarning={detectionWarning}
           targetLanguage={targetLanguage}
           setTargetLanguage={(lang) => {
             setTargetLanguage(lang);
@@ -2621,6 +5492,10 @@ function App() {
               quick_translation_hotkey_key: "",
             }));
           }}
+          replaceSelection={settings.quick_translation_replace ?? false}
+          setReplaceSelection={(replace) => {
+            setSettings((prev) => ({ ...prev, quick_translation_replace: replace }));
+          }}
         />
       )}
 
@@ -2631,6 +5506,20 @@ function App() {
           copiedFormat={copiedFormat}
           setCopiedFormat={setCopiedFormat}
           onDragStart={handleDragStart}
+          colorHistory={colorHistory}
+          onSelectHistory={async (hex) => {
+            setPickedColor(await invoke<ColorFormats>("get_color_formats", { input: hex }));
+            setCopiedFormat(null);
+          }}
+          onDeleteHistory={async (id) => {
+            await invoke("delete_color_history_item", { id }).catch(() => {});
+            setColorHistory((history) => history.filter((entry) => entry.id !== id));
+          }}
+          onClearHistory={async () => {
+            await invoke("clear_color_history").catch(() => {});
+            setColorHistory([]);
+          }}
+          onExportHistory={handleExportPalette}
         />
       )}
 
@@ -2648,6 +5537,8 @@ function App() {
           showCustomization={showQRCustomization}
           setShowCustomization={setShowQRCustomization}
           qrImageDataUrl={qrImageDataUrl}
+          qrContent={qrContent}
+          qrError={qrError}
           qrCopied={qrCopied}
           setQRCopied={setQRCopied}
           selectedExportFormat={selectedExportFormat}
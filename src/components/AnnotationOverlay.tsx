@@ -0,0 +1,388 @@
+import { useState, useEffect, useRef } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { getCurrentWindow } from "@tauri-apps/api/window";
+import { MoveUpRight, Square, Type, Droplet, Grid3x3, Undo2, Copy, Save, X } from "lucide-react";
+import type { Annotation, AnnotationPoint } from "../types";
+
+type AnnotationTool = "arrow" | "rectangle" | "text" | "blur" | "pixelate";
+
+// Preview-only data kept next to text annotations
+type Drawn = Annotation & { spriteWidth?: number; spriteHeight?: number };
+
+const COLORS = ["#EF4444", "#F59E0B", "#22C55E", "#3B82F6", "#FFFFFF", "#111111"];
+const TOOLS: { id: AnnotationTool; icon: typeof Square; title: string }[] = [
+  { id: "arrow", icon: MoveUpRight, title: "Arrow (A)" },
+  { id: "rectangle", icon: Square, title: "Rectangle (R)" },
+  { id: "text", icon: Type, title: "Text (T)" },
+  { id: "blur", icon: Droplet, title: "Blur (B)" },
+  { id: "pixelate", icon: Grid3x3, title: "Pixelate (P)" },
+];
+
+// Line width and text size grow with the screenshot so they read the same on HiDPI captures
+const strokeWidth = (imageWidth: number) => Math.max(3, Math.round(imageWidth / 400));
+const fontSize = (imageWidth: number) => Math.max(18, Math.round(imageWidth / 60));
+
+// Text is rasterized here, where the fonts are, and composited by the backend
+function renderTextSprite(text: string, color: string, size: number) {
+  const canvas = document.createElement("canvas");
+  const ctx = canvas.getContext("2d")!;
+  const font = `600 ${size}px system-ui, sans-serif`;
+  ctx.font = font;
+  const lines = text.split("\n");
+  const lineHeight = Math.round(size * 1.25);
+  canvas.width = Math.ceil(Math.max(...lines.map((line) => ctx.measureText(line).width))) + 4;
+  canvas.height = lineHeight * lines.length + 4;
+  ctx.font = font;
+  ctx.fillStyle = color;
+  ctx.textBaseline = "top";
+  lines.forEach((line, i) => ctx.fillText(line, 2, 2 + i * lineHeight));
+  return { sprite: canvas.toDataURL("image/png"), width: canvas.width, height: canvas.height };
+}
+
+// Screenshot annotation (`index.html?window=annotate`)
+// Drag to draw with the selected tool; Ctrl+Z undoes, Ctrl+C copies, Ctrl+S saves, Esc closes.
+export function AnnotationOverlay() {
+  const [imageUrl, setImageUrl] = useState<string | null>(null);
+  const [size, setSize] = useState({ width: 0, height: 0 });
+  const [tool, setTool] = useState<AnnotationTool>("arrow");
+  const [color, setColor] = useState(COLORS[0]);
+  const [annotations, setAnnotations] = useState<Drawn[]>([]);
+  const [draft, setDraft] = useState<Drawn | null>(null);
+  const [textInput, setTextInput] = useState<{ at: AnnotationPoint; value: string } | null>(null);
+  const [status, setStatus] = useState<string | null>(null);
+  const svgRef = useRef<SVGSVGElement>(null);
+  const dragStart = useRef<AnnotationPoint | null>(null);
+
+  const loadImage = async () => {
+    try {
+      const png = await invoke<ArrayBuffer>("get_annotation_image");
+      const url = URL.createObjectURL(new Blob([png], { type: "image/png" }));
+      const img = new Image();
+      img.onload = () => setSize({ width: img.naturalWidth, height: img.naturalHeight });
+      img.src = url;
+      setImageUrl((previous) => {
+        if (previous) URL.revokeObjectURL(previous);
+        return url;
+      });
+      setAnnotations([]);
+      setDraft(null);
+      setTextInput(null);
+    } catch (e) {
+      setStatus(String(e));
+    }
+  };
+
+  useEffect(() => {
+    loadImage();
+    const unlisten = listen("annotation-image-changed", () => loadImage());
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  const payload = (): Annotation[] =>
+    annotations.map(({ spriteWidth: _w, spriteHeight: _h, ...annotation }) => annotation as Annotation);
+
+  const flash = (message: string) => {
+    setStatus(message);
+    setTimeout(() => setStatus(null), 2000);
+  };
+
+  const copy = async () => {
+    try {
+      await invoke("copy_annotated_image", { annotations: payload() });
+      flash("Copied to clipboard");
+    } catch (e) {
+      flash(String(e));
+    }
+  };
+
+  const saveImage = async () => {
+    try {
+      const path = await invoke<string | null>("pick_save_path", {
+        defaultName: "Screenshot.png",
+        filters: [{ name: "PNG", extensions: ["png"] }],
+      });
+      if (!path) return;
+      const saved = await invoke<string>("save_annotated_image", { annotations: payload(), path });
+      flash(`Saved to ${saved}`);
+    } catch (e) {
+      flash(String(e));
+    }
+  };
+
+  const close = () => invoke("close_screen_annotation").catch(() => {});
+
+  useEffect(() => {
+    const onKeyDown = (e: KeyboardEvent) => {
+      if (textInput) return;
+      const ctrl = e.ctrlKey || e.metaKey;
+      if (e.key === "Escape") {
+        close();
+      } else if (ctrl && e.key === "z") {
+        setAnnotations((list) => list.slice(0, -1));
+      } else if (ctrl && e.key === "c") {
+        copy();
+      } else if (ctrl && e.key === "s") {
+        saveImage();
+      } else if (!ctrl && ["a", "r", "t", "b", "p"].includes(e.key)) {
+        setTool(
+          ({ a: "arrow", r: "rectangle", t: "text", b: "blur", p: "pixelate" } as const)[e.key as "a" | "r" | "t" | "b" | "p"]
+        );
+      } else {
+        return;
+      }
+      e.preventDefault();
+    };
+    window.addEventListener("keydown", onKeyDown);
+    return () => window.removeEventListener("keydown", onKeyDown);
+  }, [annotations, textInput]);
+
+  // Mouse position in image pixels
+  const toImage = (e: React.MouseEvent): AnnotationPoint => {
+    const rect = svgRef.current!.getBoundingClientRect();
+    return {
+      x: Math.round(((e.clientX - rect.left) / rect.width) * size.width),
+      y: Math.round(((e.clientY - rect.top) / rect.height) * size.height),
+    };
+  };
+
+  const shapeFor = (from: AnnotationPoint, to: AnnotationPoint): Drawn => {
+    const area = { x: from.x, y: from.y, width: to.x - from.x, height: to.y - from.y };
+    switch (tool) {
+      case "arrow":
+        return { kind: "arrow", from, to, color, width: strokeWidth(size.width) };
+      case "blur":
+        return { kind: "blur", area, strength: Math.max(6, Math.round(size.width / 200)) };
+      case "pixelate":
+        return { kind: "pixelate", area, block: Math.max(8, Math.round(size.width / 100)) };
+      default:
+        return { kind: "rectangle", area, color, width: strokeWidth(size.width) };
+    }
+  };
+
+  const onMouseDown = (e: React.MouseEvent) => {
+    if (e.button !== 0 || !size.width) return;
+    const point = toImage(e);
+    if (tool === "text") {
+      setTextInput({ at: point, value: "" });
+      return;
+    }
+    dragStart.current = point;
+  };
+
+  const onMouseMove = (e: React.MouseEvent) => {
+    if (dragStart.current) setDraft(shapeFor(dragStart.current, toImage(e)));
+  };
+
+  const onMouseUp = (e: React.MouseEvent) => {
+    if (!dragStart.current) return;
+    const shape = shapeFor(dragStart.current, toImage(e));
+    dragStart.current = null;
+    setDraft(null);
+    const tooSmall =
+      shape.kind === "arrow"
+        ? Math.hypot(shape.to.x - shape.from.x, shape.to.y - shape.from.y) < 5
+        : "area" in shape && (Math.abs(shape.area.width) < 3 || Math.abs(shape.area.height) < 3);
+    if (!tooSmall) setAnnotations((list) => [...list, shape]);
+  };
+
+  const commitText = () => {
+    if (textInput && textInput.value.trim()) {
+      const { sprite, width, height } = renderTextSprite(textInput.value, color, fontSize(size.width));
+      setAnnotations((list) => [
+        ...list,
+        { kind: "text", at: textInput.at, sprite, spriteWidth: width, spriteHeight: height },
+      ]);
+    }
+    setTextInput(null);
+  };
+
+  const renderShape = (shape: Drawn, key: number | string) => {
+    const norm = (a: { x: number; y: number; width: number; height: number }) => ({
+      x: Math.min(a.x, a.x + a.width),
+      y: Math.min(a.y, a.y + a.height),
+      width: Math.abs(a.width),
+      height: Math.abs(a.height),
+    });
+    switch (shape.kind) {
+      case "arrow":
+        return (
+          <line
+            key={key}
+            x1={shape.from.x}
+            y1={shape.from.y}
+            x2={shape.to.x}
+            y2={shape.to.y}
+            stroke={shape.color}
+            strokeWidth={shape.width}
+            strokeLinecap="round"
+            markerEnd={`url(#head-${shape.color.slice(1)})`}
+          />
+        );
+      case "rectangle":
+        return <rect key={key} {...norm(shape.area)} fill="none" stroke={shape.color} strokeWidth={shape.width} />;
+      case "blur": {
+        const area = norm(shape.area);
+        return (
+          <g key={key}>
+            <clipPath id={`blur-${key}`}>
+              <rect {...area} />
+            </clipPath>
+            <image
+              href={imageUrl ?? ""}
+              width={size.width}
+              height={size.height}
+              clipPath={`url(#blur-${key})`}
+              filter={`blur(${shape.strength}px)`}
+              style={{ filter: `blur(${shape.strength}px)` }}
+            />
+          </g>
+        );
+      }
+      case "pixelate": {
+        // Samples a dot per block and grows it to fill the block, like the backend's averaging
+        const area = norm(shape.area);
+        const dot = Math.max(1, Math.floor(shape.block / 2));
+        return (
+          <g key={key}>
+            <filter id={`pixelate-${key}`} x="0" y="0" width="1" height="1">
+              <feFlood x={dot} y={dot} width="1" height="1" />
+              <feComposite width={shape.block} height={shape.block} />
+              <feTile result="dots" />
+              <feComposite in="SourceGraphic" in2="dots" operator="in" />
+              <feMorphology operator="dilate" radius={dot} />
+            </filter>
+            <clipPath id={`pixelate-clip-${key}`}>
+              <rect {...area} />
+            </clipPath>
+            <g clipPath={`url(#pixelate-clip-${key})`}>
+              <image href={imageUrl ?? ""} width={size.width} height={size.height} filter={`url(#pixelate-${key})`} />
+            </g>
+          </g>
+        );
+      }
+      case "label":
+        // Only annotate_image takes labels, the overlay places text as sprites
+        return null;
+      case "text":
+        return (
+          <image
+            key={key}
+            href={shape.sprite}
+            x={shape.at.x}
+            y={shape.at.y}
+            width={shape.spriteWidth}
+            height={shape.spriteHeight}
+          />
+        );
+    }
+  };
+
+  return (
+    <div className="h-screen flex flex-col bg-buncha-bg text-buncha-text select-none overflow-hidden">
+      <div
+        className="h-12 shrink-0 flex items-center gap-1 px-2 border-b border-buncha-border cursor-move"
+        onMouseDown={(e) => {
+          if (e.target === e.currentTarget) getCurrentWindow().startDragging().catch(() => {});
+        }}
+      >
+        {TOOLS.map(({ id, icon: Icon, title }) => (
+          <button
+            key={id}
+            title={title}
+            onClick={() => setTool(id)}
+            className={`p-2 rounded-lg ${tool === id ? "bg-buncha-accent/20 text-buncha-accent" : "hover:bg-buncha-surface"}`}
+          >
+            <Icon className="w-4 h-4" />
+          </button>
+        ))}
+        <div className="w-px h-6 mx-1 bg-buncha-border" />
+        {COLORS.map((c) => (
+          <button
+            key={c}
+            title={c}
+            onClick={() => setColor(c)}
+            className={`w-5 h-5 rounded-full border ${color === c ? "ring-2 ring-buncha-accent" : "border-buncha-border"}`}
+            style={{ backgroundColor: c }}
+          />
+        ))}
+        <div className="flex-1 text-xs text-buncha-text-muted truncate px-2 pointer-events-none">{status}</div>
+        <button title="Undo (Ctrl+Z)" onClick={() => setAnnotations((list) => list.slice(0, -1))} className="p-2 rounded-lg hover:bg-buncha-surface">
+          <Undo2 className="w-4 h-4" />
+        </button>
+        <button title="Copy (Ctrl+C)" onClick={copy} className="p-2 rounded-lg hover:bg-buncha-surface">
+          <Copy className="w-4 h-4" />
+        </button>
+        <button title="Save (Ctrl+S)" onClick={saveImage} className="p-2 rounded-lg hover:bg-buncha-surface">
+          <Save className="w-4 h-4" />
+        </button>
+        <button title="Close (Esc)" onClick={close} className="p-2 rounded-lg hover:bg-red-500/20 hover:text-red-400">
+          <X className="w-4 h-4" />
+        </button>
+      </div>
+
+      <div className="relative flex-1 min-h-0 flex items-center justify-center">
+        {imageUrl && size.width > 0 && (
+          <svg
+            ref={svgRef}
+            viewBox={`0 0 ${size.width} ${size.height}`}
+            className={`max-w-full max-h-full ${tool === "text" ? "cursor-text" : "cursor-crosshair"}`}
+            onMouseDown={onMouseDown}
+            onMouseMove={onMouseMove}
+            onMouseUp={onMouseUp}
+          >
+            <defs>
+              {COLORS.map((c) => (
+                <marker
+                  key={c}
+                  id={`head-${c.slice(1)}`}
+                  viewBox="0 0 10 10"
+                  refX="6"
+                  refY="5"
+                  markerWidth="4"
+                  markerHeight="4"
+                  orient="auto"
+                >
+                  <path d="M0,0 L10,5 L0,10 z" fill={c} />
+                </marker>
+              ))}
+            </defs>
+            <image href={imageUrl} width={size.width} height={size.height} />
+            {annotations.map((shape, i) => renderShape(shape, i))}
+            {draft && renderShape(draft, "draft")}
+          </svg>
+        )}
+        {textInput && svgRef.current && (
+          <textarea
+            autoFocus
+            value={textInput.value}
+            onChange={(e) => setTextInput({ ...textInput, value: e.target.value })}
+            onBlur={commitText}
+            onKeyDown={(e) => {
+              if (e.key === "Enter" && !e.shiftKey) {
+                e.preventDefault();
+                commitText();
+              } else if (e.key === "Escape") {
+                e.preventDefault();
+                setTextInput(null);
+              }
+            }}
+            className="absolute bg-black/40 border border-buncha-accent rounded px-1 text-sm outline-none resize"
+            style={(() => {
+              const rect = svgRef.current!.getBoundingClientRect();
+              const parent = svgRef.current!.parentElement!.getBoundingClientRect();
+              return {
+                left: rect.left - parent.left + (textInput.at.x / size.width) * rect.width,
+                top: rect.top - parent.top + (textInput.at.y / size.height) * rect.height,
+                color,
+              };
+            })()}
+            placeholder="Text, Enter to place"
+          />
+        )}
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,113 @@
+import { useState, useEffect, type ReactNode } from "react";
+import { invoke } from "@tauri-apps/api/core";
+
+interface Pattern {
+  name: string;
+  render: () => ReactNode;
+}
+
+const steps = (count: number, from: number, to: number) =>
+  Array.from({ length: count }, (_, i) => Math.round(from + ((to - from) * i) / (count - 1)));
+
+const gray = (v: number) => `rgb(${v}, ${v}, ${v})`;
+
+const Bars = ({ colors }: { colors: string[] }) => (
+  <div className="flex w-full h-full">
+    {colors.map((color, i) => (
+      <div key={i} className="flex-1" style={{ backgroundColor: color }} />
+    ))}
+  </div>
+);
+
+const Solid = ({ color }: { color: string }) => <div className="w-full h-full" style={{ backgroundColor: color }} />;
+
+const PATTERNS: Pattern[] = [
+  { name: "Grayscale steps", render: () => <Bars colors={steps(16, 0, 255).map(gray)} /> },
+  {
+    name: "Grayscale gradient",
+    render: () => <div className="w-full h-full" style={{ background: "linear-gradient(to right, #000, #fff)" }} />,
+  },
+  {
+    name: "Color bars",
+    render: () => <Bars colors={["#FFFFFF", "#FFFF00", "#00FFFF", "#00FF00", "#FF00FF", "#FF0000", "#0000FF", "#000000"]} />,
+  },
+  {
+    name: "RGB gradients",
+    render: () => (
+      <div className="flex flex-col w-full h-full">
+        {["#f00", "#0f0", "#00f", "#fff"].map((color) => (
+          <div key={color} className="flex-1" style={{ background: `linear-gradient(to right, #000, ${color})` }} />
+        ))}
+      </div>
+    ),
+  },
+  // Steps that disappear into black / white show crushed shadows / clipped highlights
+  { name: "Black level (0-31)", render: () => <Bars colors={steps(16, 0, 31).map(gray)} /> },
+  { name: "White level (224-255)", render: () => <Bars colors={steps(16, 224, 255).map(gray)} /> },
+  {
+    name: "Pixel checkerboard",
+    render: () => (
+      <div
+        className="w-full h-full"
+        style={{ background: "repeating-conic-gradient(#000 0% 25%, #fff 0% 50%)", backgroundSize: "2px 2px" }}
+      />
+    ),
+  },
+  // Dead / stuck pixel checks
+  { name: "Black", render: () => <Solid color="#000000" /> },
+  { name: "White", render: () => <Solid color="#FFFFFF" /> },
+  { name: "Red", render: () => <Solid color="#FF0000" /> },
+  { name: "Green", render: () => <Solid color="#00FF00" /> },
+  { name: "Blue", render: () => <Solid color="#0000FF" /> },
+];
+
+// How long the pattern name stays up after switching
+const HINT_DURATION_MS = 2000;
+
+// Full-screen calibration patterns (`index.html?window=calibration`)
+// Arrows / Space cycle, Esc closes.
+export function CalibrationPatterns() {
+  const [index, setIndex] = useState(0);
+  const [showHint, setShowHint] = useState(true);
+
+  useEffect(() => {
+    const onKeyDown = (e: KeyboardEvent) => {
+      if (e.key === "Escape" || e.key === "q") {
+        invoke("close_calibration_window").catch(() => {});
+      } else if (["ArrowRight", "ArrowDown", " ", "Enter"].includes(e.key)) {
+        setIndex((i) => (i + 1) % PATTERNS.length);
+      } else if (["ArrowLeft", "ArrowUp", "Backspace"].includes(e.key)) {
+        setIndex((i) => (i - 1 + PATTERNS.length) % PATTERNS.length);
+      } else if (e.key === "h") {
+        setShowHint((show) => !show);
+        return;
+      } else {
+        return;
+      }
+      e.preventDefault();
+      setShowHint(true);
+    };
+    window.addEventListener("keydown", onKeyDown);
+    return () => window.removeEventListener("keydown", onKeyDown);
+  }, []);
+
+  useEffect(() => {
+    if (!showHint) return;
+    const timeout = setTimeout(() => setShowHint(false), HINT_DURATION_MS);
+    return () => clearTimeout(timeout);
+  }, [showHint, index]);
+
+  return (
+    <div
+      className="relative w-screen h-screen cursor-none"
+      onClick={() => setIndex((i) => (i + 1) % PATTERNS.length)}
+    >
+      {PATTERNS[index].render()}
+      {showHint && (
+        <div className="absolute bottom-6 left-1/2 -translate-x-1/2 px-4 py-2 rounded-lg bg-black/70 text-white text-sm font-mono pointer-events-none">
+          {index + 1}/{PATTERNS.length} · {PATTERNS[index].name} · ←/→ to switch, Esc to close
+        </div>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,49 @@
+import { useState, useEffect } from "react";
+import { listen } from "@tauri-apps/api/event";
+
+interface LoupeFrame {
+  size: number;
+  pixels: string[];
+  hex: string;
+}
+
+// Magnified pixels around the cursor during a color pick (`index.html?window=loupe`)
+export function ColorLoupe() {
+  const [frame, setFrame] = useState<LoupeFrame | null>(null);
+
+  useEffect(() => {
+    const unlisten = listen<LoupeFrame>("loupe-frame", (event) => {
+      setFrame(event.payload);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  if (!frame) {
+    return null;
+  }
+
+  const center = Math.floor(frame.size / 2) * frame.size + Math.floor(frame.size / 2);
+
+  return (
+    <div className="h-screen flex flex-col items-center justify-center gap-1.5 p-1.5 bg-buncha-bg rounded-xl border border-buncha-border select-none">
+      <div
+        className="grid w-full aspect-square rounded-md overflow-hidden"
+        style={{ gridTemplateColumns: `repeat(${frame.size}, 1fr)` }}
+      >
+        {frame.pixels.map((color, i) => (
+          <div
+            key={i}
+            style={{ backgroundColor: color }}
+            className={i === center ? "outline-2 outline-white ring-2 ring-black z-10" : ""}
+          />
+        ))}
+      </div>
+      <div className="flex items-center gap-1.5 font-mono text-xs text-buncha-text">
+        <span className="w-3 h-3 rounded-sm border border-buncha-border" style={{ backgroundColor: frame.hex }} />
+        {frame.hex}
+      </div>
+    </div>
+  );
+}
@@ -3,15 +3,23 @@ import {
   Pipette,
   Copy,
   Check,
+  X,
+  Trash2,
+  Download,
 } from "lucide-react";
 import { writeText } from "@tauri-apps/plugin-clipboard-manager";
-import type { ColorFormats } from "../types";
+import type { ColorFormats, ColorHistoryEntry } from "../types";
 
 interface ColorPickerPanelProps {
   pickedColor: ColorFormats;
   copiedFormat: string | null;
   setCopiedFormat: (format: string | null) => void;
   onDragStart: (e: React.MouseEvent) => void;
+  colorHistory: ColorHistoryEntry[];
+  onSelectHistory: (hex: string) => void;
+  onDeleteHistory: (id: number) => void;
+  onClearHistory: () => void;
+  onExportHistory: () => void;
 }
 
 export function ColorPickerPanel({
@@ -19,6 +27,11 @@ export function ColorPickerPanel({
   copiedFormat,
   setCopiedFormat,
   onDragStart,
+  colorHistory,
+  onSelectHistory,
+  onDeleteHistory,
+  onClearHistory,
+  onExportHistory,
 }: ColorPickerPanelProps) {
   return (
     <div className="w-[880px] bg-buncha-bg rounded-lg overflow-hidden" onMouseDown={onDragStart}>
@@ -80,6 +93,7 @@ export function ColorPickerPanel({
               { label: "CMYK", value: pickedColor.cmyk, description: "Cyan Magenta Yellow Black" },
               { label: "LAB", value: pickedColor.lab, description: "Lightness A B" },
               { label: "XYZ", value: pickedColor.xyz, description: "CIE XYZ color space" },
+              { label: "NAME", value: pickedColor.name, description: pickedColor.name_exact ? "CSS named color" : "Nearest CSS named color" },
             ].map((format) => (
               <div
                 key={format.label}
@@ -116,6 +130,52 @@ export function ColorPickerPanel({
           </div>
         </div>
       </div>
+
+      {/* Recent Picks */}
+      {colorHistory.length > 1 && (
+        <div className="px-6 pb-5">
+          <div className="flex items-center justify-between mb-2">
+            <h3 className="text-sm font-medium text-buncha-text-muted">Recent Picks</h3>
+            <div className="flex items-center gap-3">
+              <button
+                onClick={onExportHistory}
+                title="Save as ASE, GPL, CSS, SCSS or JSON tokens"
+                className="flex items-center gap-1 text-xs text-buncha-text-muted hover:text-buncha-accent transition-colors cursor-pointer"
+              >
+                <Download className="w-3 h-3" />
+                Export
+              </button>
+              <button
+                onClick={onClearHistory}
+                className="flex items-center gap-1 text-xs text-buncha-text-muted hover:text-red-400 transition-colors cursor-pointer"
+              >
+                <Trash2 className="w-3 h-3" />
+                Clear
+              </button>
+            </div>
+          </div>
+          <div className="flex flex-wrap gap-2">
+            {colorHistory.slice(0, 24).map((entry) => (
+              <div key={entry.id} className="relative group/swatch">
+                <button
+                  onClick={() => onSelectHistory(entry.hex)}
+                  title={`${entry.hex} · ${new Date(entry.timestamp * 1000).toLocaleString()}${entry.monitor ? ` · ${entry.monitor}` : ""}`}
+                  className={`w-8 h-8 rounded-md ring-1 cursor-pointer transition-transform hover:scale-110 ${
+                    entry.hex === pickedColor.hex ? "ring-2 ring-buncha-accent" : "ring-black/10"
+                  }`}
+                  style={{ backgroundColor: entry.hex }}
+                />
+                <button
+                  onClick={() => onDeleteHistory(entry.id)}
+                  className="absolute -top-1.5 -right-1.5 p-0.5 rounded-full bg-buncha-surface border border-buncha-border opacity-0 group-hover/swatch:opacity-100 transition-opacity cursor-pointer"
+                >
+                  <X className="w-2.5 h-2.5 text-buncha-text-muted" />
+                </button>
+              </div>
+            ))}
+          </div>
+        </div>
+      )}
     </div>
   );
 }
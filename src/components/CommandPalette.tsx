@@ -6,9 +6,11 @@ import {
   Check,
   Settings,
   ChevronRight,
+  Lightbulb,
+  X,
 } from "lucide-react";
 import { writeText } from "@tauri-apps/plugin-clipboard-manager";
-import type { Tool, QuickResult } from "../types";
+import type { Tool, QuickResult, ToolTip } from "../types";
 
 export interface CommandStatus {
   message: string;
@@ -34,6 +36,8 @@ interface CommandPaletteProps {
   inputRef: React.RefObject<HTMLInputElement | null>;
   toolItemRefs: React.MutableRefObject<(HTMLDivElement | null)[]>;
   onDragStart: (e: React.MouseEvent) => void;
+  tip: ToolTip | null; // for the selected tool
+  onDismissTip: (id: string) => void;
 }
 
 export function CommandPalette({
@@ -55,6 +59,8 @@ export function CommandPalette({
   inputRef,
   toolItemRefs,
   onDragStart,
+  tip,
+  onDismissTip,
 }: CommandPaletteProps) {
   // In command only mode, never show tool suggestions - only the command input
   const isExpanded = !commandOnlyMode;
@@ -278,6 +284,22 @@ export function CommandPalette({
                       <p className="text-sm text-buncha-text-muted line-clamp-1">
                         {tool.description}
                       </p>
+                      {index === selectedIndex && tip?.tool === tool.id && (
+                        <p className="mt-1 flex items-center gap-1.5 text-xs text-buncha-accent">
+                          <Lightbulb className="w-3.5 h-3.5 shrink-0" />
+                          <span className="line-clamp-1">{tip.text}</span>
+                          <button
+                            onClick={(e) => {
+                              e.stopPropagation();
+                              onDismissTip(tip.id);
+                            }}
+                            className="p-0.5 rounded hover:bg-buncha-accent/20 shrink-0"
+                            title="Don't show this tip again"
+                          >
+                            <X className="w-3 h-3" />
+                          </button>
+                        </p>
+                      )}
                     </div>
                     <ArrowRight className={`w-5 h-5 text-buncha-text-muted transition-all ${
                       index === selectedIndex
@@ -0,0 +1,70 @@
+import { useState, useEffect } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { getCurrentWindow } from "@tauri-apps/api/window";
+import type { OverlayTimer as OverlayTimerState } from "../types";
+
+const pad = (n: number) => n.toString().padStart(2, "0");
+
+function formatRemaining(ms: number) {
+  const total = Math.max(0, Math.ceil(ms / 1000));
+  const hours = Math.floor(total / 3600);
+  const minutes = Math.floor((total % 3600) / 60);
+  const seconds = total % 60;
+  return hours > 0 ? `${hours}:${pad(minutes)}:${pad(seconds)}` : `${pad(minutes)}:${pad(seconds)}`;
+}
+
+// Frameless always-on-top countdown / clock (`index.html?window=overlay-timer`)
+// Drag to move, double-click to close.
+export function OverlayTimer() {
+  const [overlay, setOverlay] = useState<OverlayTimerState | null>(null);
+  const [now, setNow] = useState(Date.now());
+
+  useEffect(() => {
+    invoke<OverlayTimerState | null>("get_overlay_timer").then(setOverlay).catch(() => {});
+    const unlisten = listen<OverlayTimerState>("overlay-timer-changed", (event) => {
+      setOverlay(event.payload);
+    });
+    const interval = setInterval(() => setNow(Date.now()), 250);
+
+    return () => {
+      clearInterval(interval);
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  if (!overlay) {
+    return null;
+  }
+
+  const remaining = overlay.ends_at !== null ? overlay.ends_at - now : null;
+  const finished = remaining !== null && remaining <= 0;
+  const text =
+    remaining !== null
+      ? formatRemaining(remaining)
+      : new Date(now).toLocaleTimeString([], { hour: "2-digit", minute: "2-digit", second: "2-digit" });
+
+  return (
+    <div
+      onMouseDown={(e) => {
+        if (e.button === 0 && e.detail === 1) {
+          getCurrentWindow().startDragging().catch(() => {});
+        }
+      }}
+      onDoubleClick={() => invoke("close_overlay_timer").catch(() => {})}
+      className="h-screen flex flex-col items-center justify-center rounded-buncha select-none cursor-move"
+      style={{ backgroundColor: `rgba(10, 10, 10, ${overlay.opacity})` }}
+      title="Drag to move, double-click to close"
+    >
+      <div
+        className={`font-mono font-semibold tabular-nums leading-none ${finished ? "text-red-500 animate-pulse" : "text-white"}`}
+        style={{ fontSize: "min(22vw, 60vh)" }}
+      >
+        {text}
+      </div>
+      {overlay.label && (
+        <div className="mt-1 text-xs text-white/70 truncate max-w-full px-3">{overlay.label}</div>
+      )}
+    </div>
+  );
+}
@@ -6,12 +6,10 @@ import {
   Download,
 } from "lucide-react";
 import { invoke } from "@tauri-apps/api/core";
-import { save } from "@tauri-apps/plugin-dialog";
 import QRCodeLib from "qrcode";
 import jsPDF from "jspdf";
 import type { QRCodeType, QRCodeData } from "../types";
 import { QR_TYPES } from "../constants";
-import { generateQRContent } from "../utils";
 
 interface QRGeneratorProps {
   qrType: QRCodeType;
@@ -25,6 +23,8 @@ interface QRGeneratorProps {
   showCustomization: boolean;
   setShowCustomization: (show: boolean) => void;
   qrImageDataUrl: string;
+  qrContent: string; // encoded text, for SVG export
+  qrError: string | null; // why the entered data can't be encoded
   qrCopied: boolean;
   setQRCopied: (copied: boolean) => void;
   selectedExportFormat: "PNG" | "SVG" | "PDF";
@@ -44,6 +44,8 @@ export function QRGenerator({
   showCustomization,
   setShowCustomization,
   qrImageDataUrl,
+  qrContent,
+  qrError,
   qrCopied,
   setQRCopied,
   selectedExportFormat,
@@ -70,14 +72,13 @@ export function QRGenerator({
   const handleExport = async () => {
     if (!qrImageDataUrl) return;
 
-    const outputPath = await save({
-      defaultPath: `qrcode-${qrType}.${selectedExportFormat.toLowerCase()}`,
-      filters: [{ name: selectedExportFormat, extensions: [selectedExportFormat.toLowerCase()] }],
-    });
-
-    if (!outputPath) return;
-
     try {
+      const outputPath = await invoke<string | null>("pick_save_path", {
+        defaultName: `qrcode-${qrType}.${selectedExportFormat.toLowerCase()}`,
+        filters: [{ name: selectedExportFormat, extensions: [selectedExportFormat.toLowerCase()] }],
+      });
+      if (!outputPath) return;
+
       if (selectedExportFormat === "PNG") {
         const base64Data = qrImageDataUrl.split(",")[1];
         const binaryData = atob(base64Data);
@@ -87,8 +88,7 @@ export function QRGenerator({
         }
         await invoke("save_binary_file", { path: outputPath, data: Array.from(bytes) });
       } else if (selectedExportFormat === "SVG") {
-        const content = generateQRContent(qrType, qrData);
-        const svgString = await QRCodeLib.toString(content, {
+        const svgString = await QRCodeLib.toString(qrContent, {
           type: "svg",
           color: { dark: qrForegroundColor, light: qrBackgroundColor },
           width: 300,
@@ -431,6 +431,8 @@ export function QRGenerator({
               >
                 {qrImageDataUrl ? (
                   <img src={qrImageDataUrl} alt="QR Code" className="w-full h-full object-contain" />
+                ) : qrError ? (
+                  <div className="text-red-500 text-sm text-center px-4">{qrError}</div>
                 ) : (
                   <div className="text-buncha-text-muted text-sm text-center px-4">
                     Enter data to generate QR code
@@ -16,6 +16,7 @@ interface QuickTranslationProps {
   setTranslationInput: (text: string) => void;
   translationOutput: string;
   detectedLanguage: string;
+  detectionWarning: string | null; // shown when the source language is a guess
   targetLanguage: string;
   setTargetLanguage: (lang: string) => void;
   isTranslating: boolean;
@@ -32,6 +33,8 @@ interface QuickTranslationProps {
   onHotkeyKeyDown: (e: React.KeyboardEvent) => void;
   onHotkeyMouseDown: (e: React.MouseEvent) => void;
   onClearHotkey: () => void;
+  replaceSelection: boolean; // the hotkey types the translation over the selection
+  setReplaceSelection: (replace: boolean) => void;
 }
 
 export function QuickTranslation({
@@ -39,6 +42,7 @@ export function QuickTranslation({
   setTranslationInput,
   translationOutput,
   detectedLanguage,
+  detectionWarning,
   targetLanguage,
   setTargetLanguage,
   isTranslating,
@@ -54,6 +58,8 @@ export function QuickTranslation({
   onHotkeyKeyDown,
   onHotkeyMouseDown,
   onClearHotkey,
+  replaceSelection,
+  setReplaceSelection,
 }: QuickTranslationProps) {
   // Focus the hotkey input when recording starts
   useEffect(() => {
@@ -151,6 +157,22 @@ export function QuickTranslation({
               )}
             </div>
           </div>
+
+          {/* Translate-and-replace */}
+          <label className="flex items-start gap-2 cursor-pointer">
+            <input
+              type="checkbox"
+              checked={replaceSelection}
+              onChange={(e) => setReplaceSelection(e.target.checked)}
+              className="mt-0.5 accent-buncha-accent cursor-pointer"
+            />
+            <span>
+              <span className="text-sm text-buncha-text block">Replace the selection</span>
+              <span className="text-xs text-buncha-text-muted/70">
+                The hotkey types the translation over the selected text instead of opening this window
+              </span>
+            </span>
+          </label>
         </div>
       )}
 
@@ -159,8 +181,13 @@ export function QuickTranslation({
         {/* Source Text */}
         <div className="mb-6">
           <div className="flex items-center justify-between">
-            <span className="text-sm font-semibold text-buncha-accent uppercase tracking-wider">
-              {detectedLanguage || "Source"}
+            <span className="flex items-baseline gap-2">
+              <span className="text-sm font-semibold text-buncha-accent uppercase tracking-wider">
+                {detectedLanguage || "Source"}
+              </span>
+              {detectionWarning && (
+                <span className="text-xs text-amber-400">{detectionWarning}</span>
+              )}
             </span>
             <div className="flex items-center gap-1">
               <button className="p-1.5 hover:bg-buncha-surface rounded-lg transition-colors cursor-pointer group">
@@ -1,10 +1,28 @@
 import React from "react";
+import { invoke } from "@tauri-apps/api/core";
 import { Settings as SettingsIcon } from "lucide-react";
-import type { Settings } from "../types";
+import type {
+  AppLockSettings,
+  ClipboardShareSettings,
+  WebhookSettings,
+  WebhookStatus,
+  AppLockStatus,
+  AutostartMethod,
+  BatterySettings,
+  HotkeyBinding,
+  JobLimits,
+  NightLightSettings,
+  Settings,
+  TrayIconStyle,
+} from "../types";
+
+// Only Linux has a choice of autostart mechanism
+const isLinux = navigator.userAgent.includes("Linux");
 
 interface SettingsPanelProps {
   settings: Settings;
   setSettings: React.Dispatch<React.SetStateAction<Settings>>;
+  activeHotkey: HotkeyBinding | null; // differs from the setting when a fallback is in use
   isRecordingHotkey: boolean;
   setIsRecordingHotkey: (recording: boolean) => void;
   hotkeyInputRef: React.RefObject<HTMLDivElement | null>;
@@ -16,6 +34,7 @@ interface SettingsPanelProps {
 export function SettingsPanel({
   settings,
   setSettings,
+  activeHotkey,
   isRecordingHotkey,
   setIsRecordingHotkey,
   hotkeyInputRef,
@@ -23,6 +42,42 @@ export function SettingsPanel({
   onHotkeyMouseDown,
   onDragStart,
 }: SettingsPanelProps) {
+  const nightLight: NightLightSettings = settings.night_light ?? { schedule: false, temperature: 3400, location: "" };
+  const appLock: AppLockSettings = settings.app_lock ?? { enabled: false, idle_minutes: 10, passcode_hash: null };
+  const clipboardShare: ClipboardShareSettings = settings.clipboard_share ?? { enabled: false, device_name: "", code: "" };
+  const [currentPasscode, setCurrentPasscode] = React.useState("");
+  const [newPasscode, setNewPasscode] = React.useState("");
+  const [passcodeMessage, setPasscodeMessage] = React.useState<string | null>(null);
+
+  const webhook: WebhookSettings = settings.webhook ?? { enabled: false, port: 47822, secret: "" };
+  const jobLimits: JobLimits = settings.job_limits ?? { conversions: 2, downloads: 3, scans: 1 };
+  const battery: BatterySettings = settings.battery ?? { policy: "run", below_percent: 30 };
+  const [webhookPort, setWebhookPort] = React.useState(String(webhook.port));
+  const [webhookSecret, setWebhookSecret] = React.useState(webhook.secret);
+  const [webhookMessage, setWebhookMessage] = React.useState<string | null>(null);
+
+  const toggleWebhook = () =>
+    (webhook.enabled
+      ? invoke<WebhookStatus>("stop_webhook_listener")
+      : invoke<WebhookStatus>("start_webhook_listener", { port: Number(webhookPort), secret: webhookSecret })
+    )
+      .then((status) => setWebhookMessage(status.running ? `Listening on port ${status.port}` : "Stopped"))
+      .catch((e) => setWebhookMessage(String(e)));
+
+  const generateWebhookSecret = () => {
+    const bytes = crypto.getRandomValues(new Uint8Array(24));
+    setWebhookSecret(Array.from(bytes, (b) => b.toString(16).padStart(2, "0")).join(""));
+  };
+
+  const savePasscode = (passcode: string | null) =>
+    invoke<AppLockStatus>("set_app_passcode", { current: currentPasscode || null, passcode })
+      .then(() => {
+        setCurrentPasscode("");
+        setNewPasscode("");
+        setPasscodeMessage(passcode ? "Passcode saved" : "Passcode removed");
+      })
+      .catch((e) => setPasscodeMessage(String(e)));
+
   return (
     <div className="w-[680px] bg-buncha-bg rounded-lg overflow-hidden" onMouseDown={onDragStart}>
       {/* Header */}
@@ -38,6 +93,13 @@ export function SettingsPanel({
           <div>
             <h3 className="text-buncha-text font-medium mb-0.5">Keyboard Shortcut</h3>
             <p className="text-sm text-buncha-text-muted">Global hotkey to open command palette</p>
+            {activeHotkey &&
+              (activeHotkey.key !== settings.hotkey_key ||
+                activeHotkey.modifiers.join("+") !== settings.hotkey_modifiers.join("+")) && (
+                <p className="text-xs text-amber-400 mt-0.5">
+                  In use by another app, {[...activeHotkey.modifiers, activeHotkey.key].join("+")} works instead
+                </p>
+              )}
           </div>
           <div
             ref={hotkeyInputRef}
@@ -93,6 +155,116 @@ export function SettingsPanel({
           </button>
         </div>
 
+        {/* Start Hidden */}
+        <div className="flex items-center justify-between py-3">
+          <div>
+            <h3 className="text-buncha-text font-medium mb-0.5">Start Hidden</h3>
+            <p className="text-sm text-buncha-text-muted">Stay in the tray when opened, until you press the hotkey</p>
+          </div>
+          <button
+            onClick={() =>
+              setSettings((prev) => ({
+                ...prev,
+                start_hidden: !prev.start_hidden,
+              }))
+            }
+            className={`relative w-11 h-6 rounded-full transition-colors cursor-pointer ${
+              settings.start_hidden
+                ? "bg-buncha-accent"
+                : "bg-buncha-surface border border-buncha-border"
+            }`}
+          >
+            <div
+              className={`absolute top-0.5 w-5 h-5 bg-white rounded-full transition-transform ${
+                settings.start_hidden
+                  ? "right-0.5"
+                  : "left-0.5"
+              }`}
+            />
+          </button>
+        </div>
+
+        {/* Autostart Method (Linux) */}
+        {isLinux && settings.launch_at_startup && (
+          <div className="flex items-center justify-between py-3">
+            <div>
+              <h3 className="text-buncha-text font-medium mb-0.5">Startup Method</h3>
+              <p className="text-sm text-buncha-text-muted">Use a systemd user service if autostart entries don't run</p>
+            </div>
+            <select
+              value={settings.autostart_method ?? "desktop_entry"}
+              onChange={(e) =>
+                setSettings((prev) => ({
+                  ...prev,
+                  autostart_method: e.target.value as AutostartMethod,
+                }))
+              }
+              className="px-3 py-1.5 bg-buncha-surface rounded-lg border border-buncha-border text-sm text-buncha-text outline-none cursor-pointer hover:border-buncha-text-muted transition-colors"
+            >
+              <option value="desktop_entry">Autostart entry</option>
+              <option value="systemd">systemd user service</option>
+            </select>
+          </div>
+        )}
+
+        {/* Shell Integration */}
+        <div className="flex items-center justify-between py-3">
+          <div>
+            <h3 className="text-buncha-text font-medium mb-0.5">File Manager Integration</h3>
+            <p className="text-sm text-buncha-text-muted">Add "Convert with BunchaTools" for video files</p>
+          </div>
+          <button
+            onClick={() =>
+              setSettings((prev) => ({
+                ...prev,
+                shell_integration: !prev.shell_integration,
+              }))
+            }
+            className={`relative w-11 h-6 rounded-full transition-colors cursor-pointer ${
+              settings.shell_integration
+                ? "bg-buncha-accent"
+                : "bg-buncha-surface border border-buncha-border"
+            }`}
+          >
+            <div
+              className={`absolute top-0.5 w-5 h-5 bg-white rounded-full transition-transform ${
+                settings.shell_integration
+                  ? "right-0.5"
+                  : "left-0.5"
+              }`}
+            />
+          </button>
+        </div>
+
+        {/* File Associations */}
+        <div className="flex items-center justify-between py-3">
+          <div>
+            <h3 className="text-buncha-text font-medium mb-0.5">Open Files with BunchaTools</h3>
+            <p className="text-sm text-buncha-text-muted">Download lists, workflows and .gpl palettes</p>
+          </div>
+          <button
+            onClick={() =>
+              setSettings((prev) => ({
+                ...prev,
+                file_associations: !prev.file_associations,
+              }))
+            }
+            className={`relative w-11 h-6 rounded-full transition-colors cursor-pointer ${
+              settings.file_associations
+                ? "bg-buncha-accent"
+                : "bg-buncha-surface border border-buncha-border"
+            }`}
+          >
+            <div
+              className={`absolute top-0.5 w-5 h-5 bg-white rounded-full transition-transform ${
+                settings.file_associations
+                  ? "right-0.5"
+                  : "left-0.5"
+              }`}
+            />
+          </button>
+        </div>
+
         {/* Show in System Tray */}
         <div className="flex items-center justify-between py-3">
           <div>
@@ -173,6 +345,637 @@ export function SettingsPanel({
           </select>
         </div>
 
+        {/* Tray Icon Style */}
+        <div className="flex items-center justify-between py-3">
+          <div>
+            <h3 className="text-buncha-text font-medium mb-0.5">Tray Icon</h3>
+            <p className="text-sm text-buncha-text-muted">Color icon or a monochrome one for your taskbar</p>
+          </div>
+          <select
+            value={settings.tray_icon_style ?? "color"}
+            onChange={(e) =>
+              setSettings((prev) => ({
+                ...prev,
+                tray_icon_style: e.target.value as TrayIconStyle,
+              }))
+            }
+            className="px-3 py-1.5 bg-buncha-surface rounded-lg border border-buncha-border text-sm text-buncha-text outline-none cursor-pointer hover:border-buncha-text-muted transition-colors"
+          >
+            <option value="color">Color</option>
+            <option value="auto">Monochrome (auto)</option>
+            <option value="light">Light taskbar</option>
+            <option value="dark">Dark taskbar</option>
+          </select>
+        </div>
+
+        {/* Keep on Top */}
+        <div className="flex items-center justify-between py-3">
+          <div>
+            <h3 className="text-buncha-text font-medium mb-0.5">Keep on Top</h3>
+            <p className="text-sm text-buncha-text-muted">Stay visible above other apps instead of hiding on focus loss</p>
+          </div>
+          <button
+            onClick={() =>
+              setSettings((prev) => ({
+                ...prev,
+                always_on_top: !prev.always_on_top,
+              }))
+            }
+            className={`relative w-11 h-6 rounded-full transition-colors cursor-pointer ${
+              settings.always_on_top
+                ? "bg-buncha-accent"
+                : "bg-buncha-surface border border-buncha-border"
+            }`}
+          >
+            <div
+              className={`absolute top-0.5 w-5 h-5 bg-white rounded-full transition-transform ${
+                settings.always_on_top
+                  ? "right-0.5"
+                  : "left-0.5"
+              }`}
+            />
+          </button>
+        </div>
+
+        {/* Focus: Do Not Disturb */}
+        <div className="flex items-center justify-between py-3">
+          <div>
+            <h3 className="text-buncha-text font-medium mb-0.5">Focus: Do Not Disturb</h3>
+            <p className="text-sm text-buncha-text-muted">Silence all notifications during Pomodoro focus sessions</p>
+          </div>
+          <button
+            onClick={() =>
+              setSettings((prev) => ({
+                ...prev,
+                pomodoro_dnd: !prev.pomodoro_dnd,
+              }))
+            }
+            className={`relative w-11 h-6 rounded-full transition-colors cursor-pointer ${
+              settings.pomodoro_dnd
+                ? "bg-buncha-accent"
+                : "bg-buncha-surface border border-buncha-border"
+            }`}
+          >
+            <div
+              className={`absolute top-0.5 w-5 h-5 bg-white rounded-full transition-transform ${
+                settings.pomodoro_dnd
+                  ? "right-0.5"
+                  : "left-0.5"
+              }`}
+            />
+          </button>
+        </div>
+
+        {/* Focus: Hold Job Notifications */}
+        <div className="flex items-center justify-between py-3">
+          <div>
+            <h3 className="text-buncha-text font-medium mb-0.5">Focus: Hold Job Notifications</h3>
+            <p className="text-sm text-buncha-text-muted">Deliver download and conversion results at the break</p>
+          </div>
+          <button
+            onClick={() =>
+              setSettings((prev) => ({
+                ...prev,
+                pomodoro_pause_notifications: !(prev.pomodoro_pause_notifications ?? true),
+              }))
+            }
+            className={`relative w-11 h-6 rounded-full transition-colors cursor-pointer ${
+              (settings.pomodoro_pause_notifications ?? true)
+                ? "bg-buncha-accent"
+                : "bg-buncha-surface border border-buncha-border"
+            }`}
+          >
+            <div
+              className={`absolute top-0.5 w-5 h-5 bg-white rounded-full transition-transform ${
+                (settings.pomodoro_pause_notifications ?? true)
+                  ? "right-0.5"
+                  : "left-0.5"
+              }`}
+            />
+          </button>
+        </div>
+
+        {/* Simultaneous Jobs */}
+        <div className="flex items-center justify-between py-3">
+          <div>
+            <h3 className="text-buncha-text font-medium mb-0.5">Simultaneous Jobs</h3>
+            <p className="text-sm text-buncha-text-muted">Conversions, downloads and network scans at once; more wait their turn</p>
+          </div>
+          <div className="flex items-center gap-2">
+            {(["conversions", "downloads", "scans"] as const).map((category) => (
+              <select
+                key={category}
+                title={category[0].toUpperCase() + category.slice(1)}
+                value={jobLimits[category]}
+                onChange={(e) =>
+                  setSettings((prev) => ({
+                    ...prev,
+                    job_limits: { ...jobLimits, [category]: Number(e.target.value) },
+                  }))
+                }
+                className="px-2 py-1.5 bg-buncha-surface rounded-lg border border-buncha-border text-sm text-buncha-text outline-none cursor-pointer hover:border-buncha-text-muted transition-colors"
+              >
+                {[1, 2, 3, 4, 6, 8].map((limit) => (
+                  <option key={limit} value={limit}>
+                    {limit} {category === "conversions" ? "encode" : category === "downloads" ? "download" : "scan"}
+                    {limit === 1 ? "" : "s"}
+                  </option>
+                ))}
+              </select>
+            ))}
+          </div>
+        </div>
+
+        {/* On Battery */}
+        <div className="flex items-center justify-between py-3">
+          <div>
+            <h3 className="text-buncha-text font-medium mb-0.5">On Battery</h3>
+            <p className="text-sm text-buncha-text-muted">What conversions and transcriptions do when the battery runs low</p>
+          </div>
+          <div className="flex items-center gap-2">
+            <select
+              value={battery.policy}
+              onChange={(e) =>
+                setSettings((prev) => ({
+                  ...prev,
+                  battery: { ...battery, policy: e.target.value as BatterySettings["policy"] },
+                }))
+              }
+              className="px-2 py-1.5 bg-buncha-surface rounded-lg border border-buncha-border text-sm text-buncha-text outline-none cursor-pointer hover:border-buncha-text-muted transition-colors"
+            >
+              <option value="run">Run as usual</option>
+              <option value="throttle">Run at half speed</option>
+              <option value="defer">Wait for AC power</option>
+            </select>
+            <select
+              value={battery.below_percent}
+              disabled={battery.policy === "run"}
+              onChange={(e) =>
+                setSettings((prev) => ({
+                  ...prev,
+                  battery: { ...battery, below_percent: Number(e.target.value) },
+                }))
+              }
+              className="px-2 py-1.5 bg-buncha-surface rounded-lg border border-buncha-border text-sm text-buncha-text outline-none cursor-pointer hover:border-buncha-text-muted transition-colors disabled:opacity-50"
+            >
+              {[100, 50, 30, 20, 10].map((percent) => (
+                <option key={percent} value={percent}>
+                  {percent === 100 ? "Always" : `Below ${percent}%`}
+                </option>
+              ))}
+            </select>
+          </div>
+        </div>
+
+        {/* Idle Threshold */}
+        <div className="flex items-center justify-between py-3">
+          <div>
+            <h3 className="text-buncha-text font-medium mb-0.5">Idle After</h3>
+            <p className="text-sm text-buncha-text-muted">Time without input before you count as away</p>
+          </div>
+          <select
+            value={settings.idle_threshold_minutes ?? 5}
+            onChange={(e) =>
+              setSettings((prev) => ({
+                ...prev,
+                idle_threshold_minutes: Number(e.target.value),
+              }))
+            }
+            className="px-3 py-1.5 bg-buncha-surface rounded-lg border border-buncha-border text-sm text-buncha-text outline-none cursor-pointer hover:border-buncha-text-muted transition-colors"
+          >
+            {[1, 2, 5, 10, 15, 30].map((minutes) => (
+              <option key={minutes} value={minutes}>
+                {minutes} min
+              </option>
+            ))}
+          </select>
+        </div>
+
+        {/* Color Sampling */}
+        <div className="flex items-center justify-between py-3">
+          <div>
+            <h3 className="text-buncha-text font-medium mb-0.5">Color Sampling</h3>
+            <p className="text-sm text-buncha-text-muted">Average an area around the click when picking colors</p>
+          </div>
+          <select
+            value={settings.color_sample_size ?? 1}
+            onChange={(e) =>
+              setSettings((prev) => ({
+                ...prev,
+                color_sample_size: Number(e.target.value) as Settings["color_sample_size"],
+              }))
+            }
+            className="px-3 py-1.5 bg-buncha-surface rounded-lg border border-buncha-border text-sm text-buncha-text outline-none cursor-pointer hover:border-buncha-text-muted transition-colors"
+          >
+            {[1, 3, 5, 9].map((size) => (
+              <option key={size} value={size}>
+                {size === 1 ? "Single pixel" : `${size}×${size} average`}
+              </option>
+            ))}
+          </select>
+        </div>
+
+        {/* Translation Engine */}
+        <div className="flex items-center justify-between py-3">
+          <div>
+            <h3 className="text-buncha-text font-medium mb-0.5">Translation Engine</h3>
+            <p className="text-sm text-buncha-text-muted">Offline keeps text on this computer (needs translateLocally; models download per language pair)</p>
+          </div>
+          <select
+            value={settings.translation_engine ?? "online"}
+            onChange={(e) =>
+              setSettings((prev) => ({
+                ...prev,
+                translation_engine: e.target.value as Settings["translation_engine"],
+              }))
+            }
+            className="px-3 py-1.5 bg-buncha-surface rounded-lg border border-buncha-border text-sm text-buncha-text outline-none cursor-pointer hover:border-buncha-text-muted transition-colors"
+          >
+            <option value="online">Online (MyMemory)</option>
+            <option value="offline">Offline</option>
+          </select>
+        </div>
+
+        {/* Favorite Currencies */}
+        <div className="flex items-center justify-between py-3">
+          <div>
+            <h3 className="text-buncha-text font-medium mb-0.5">Favorite Currencies</h3>
+            <p className="text-sm text-buncha-text-muted">Typing an amount like "100 usd" converts it into these (up to five)</p>
+          </div>
+          <input
+            type="text"
+            key={(settings.favorite_currencies ?? []).join(",")}
+            defaultValue={(settings.favorite_currencies ?? []).join(", ")}
+            placeholder="USD, EUR, BTC"
+            onBlur={(e) =>
+              setSettings((prev) => ({
+                ...prev,
+                favorite_currencies: e.target.value
+                  .split(/[\s,]+/)
+                  .filter(Boolean)
+                  .map((code) => code.toUpperCase())
+                  .slice(0, 5),
+              }))
+            }
+            className="w-40 px-3 py-1.5 bg-buncha-surface rounded-lg border border-buncha-border text-sm text-buncha-text outline-none hover:border-buncha-text-muted focus:border-buncha-accent transition-colors"
+          />
+        </div>
+
+        {/* Secrets Lock */}
+        <div className="flex items-center justify-between py-3">
+          <div>
+            <h3 className="text-buncha-text font-medium mb-0.5">Lock Secrets After</h3>
+            <p className="text-sm text-buncha-text-muted">Encrypted snippets need the passphrase again after this long unused</p>
+          </div>
+          <select
+            value={settings.secrets_lock_minutes ?? 5}
+            onChange={(e) =>
+              setSettings((prev) => ({
+                ...prev,
+                secrets_lock_minutes: Number(e.target.value),
+              }))
+            }
+            className="px-3 py-1.5 bg-buncha-surface rounded-lg border border-buncha-border text-sm text-buncha-text outline-none cursor-pointer hover:border-buncha-text-muted transition-colors"
+          >
+            {[1, 5, 15, 30, 60].map((minutes) => (
+              <option key={minutes} value={minutes}>
+                {minutes} min
+              </option>
+            ))}
+          </select>
+        </div>
+
+        {/* Night Light */}
+        <div className="flex items-center justify-between py-3">
+          <div>
+            <h3 className="text-buncha-text font-medium mb-0.5">Night Light at Sunset</h3>
+            <p className="text-sm text-buncha-text-muted">Warm the screen from sunset to sunrise (toggle it any time from the tray)</p>
+          </div>
+          <button
+            onClick={() =>
+              setSettings((prev) => ({
+                ...prev,
+                night_light: { ...nightLight, schedule: !nightLight.schedule },
+              }))
+            }
+            className={`relative w-11 h-6 rounded-full transition-colors cursor-pointer ${
+              nightLight.schedule
+                ? "bg-buncha-accent"
+                : "bg-buncha-surface border border-buncha-border"
+            }`}
+          >
+            <div
+              className={`absolute top-0.5 w-5 h-5 bg-white rounded-full transition-transform ${
+                nightLight.schedule
+                  ? "right-0.5"
+                  : "left-0.5"
+              }`}
+            />
+          </button>
+        </div>
+
+        <div className="flex items-center justify-between py-3">
+          <div>
+            <h3 className="text-buncha-text font-medium mb-0.5">Night Light Warmth</h3>
+            <p className="text-sm text-buncha-text-muted">Color temperature while the night light is on</p>
+          </div>
+          <select
+            value={nightLight.temperature}
+            onChange={(e) =>
+              setSettings((prev) => ({
+                ...prev,
+                night_light: { ...nightLight, temperature: Number(e.target.value) },
+              }))
+            }
+            className="px-3 py-1.5 bg-buncha-surface rounded-lg border border-buncha-border text-sm text-buncha-text outline-none cursor-pointer hover:border-buncha-text-muted transition-colors"
+          >
+            {[2700, 3000, 3400, 4000, 4500, 5000].map((kelvin) => (
+              <option key={kelvin} value={kelvin}>
+                {kelvin} K
+              </option>
+            ))}
+          </select>
+        </div>
+
+        {nightLight.schedule && (
+          <div className="flex items-center justify-between py-3">
+            <div>
+              <h3 className="text-buncha-text font-medium mb-0.5">Night Light Location</h3>
+              <p className="text-sm text-buncha-text-muted">Place or coordinates for sunset; empty uses the last sun times lookup</p>
+            </div>
+            <input
+              type="text"
+              value={nightLight.location}
+              placeholder="e.g. Berlin"
+              onChange={(e) =>
+                setSettings((prev) => ({
+                  ...prev,
+                  night_light: { ...nightLight, location: e.target.value },
+                }))
+              }
+              className="w-40 px-3 py-1.5 bg-buncha-surface rounded-lg border border-buncha-border text-sm text-buncha-text outline-none hover:border-buncha-text-muted focus:border-buncha-accent transition-colors"
+            />
+          </div>
+        )}
+
+        {/* App Lock */}
+        <div className="flex items-center justify-between py-3">
+          <div>
+            <h3 className="text-buncha-text font-medium mb-0.5">App Lock</h3>
+            <p className="text-sm text-buncha-text-muted">
+              {appLock.passcode_hash
+                ? 'Ask for the passcode before showing histories and secrets ("unlock <passcode>")'
+                : "Set a passcode below to lock histories and secrets on shared machines"}
+            </p>
+          </div>
+          <button
+            disabled={!appLock.passcode_hash}
+            onClick={() =>
+              setSettings((prev) => ({
+                ...prev,
+                app_lock: { ...appLock, enabled: !appLock.enabled },
+              }))
+            }
+            className={`relative w-11 h-6 rounded-full transition-colors cursor-pointer disabled:opacity-50 disabled:cursor-default ${
+              appLock.enabled
+                ? "bg-buncha-accent"
+                : "bg-buncha-surface border border-buncha-border"
+            }`}
+          >
+            <div
+              className={`absolute top-0.5 w-5 h-5 bg-white rounded-full transition-transform ${
+                appLock.enabled
+                  ? "right-0.5"
+                  : "left-0.5"
+              }`}
+            />
+          </button>
+        </div>
+
+        {appLock.enabled && (
+          <div className="flex items-center justify-between py-3">
+            <div>
+              <h3 className="text-buncha-text font-medium mb-0.5">Lock After</h3>
+              <p className="text-sm text-buncha-text-muted">Time without input before the app locks (it also locks with the session)</p>
+            </div>
+            <select
+              value={appLock.idle_minutes}
+              onChange={(e) =>
+                setSettings((prev) => ({
+                  ...prev,
+                  app_lock: { ...appLock, idle_minutes: Number(e.target.value) },
+                }))
+              }
+              className="px-3 py-1.5 bg-buncha-surface rounded-lg border border-buncha-border text-sm text-buncha-text outline-none cursor-pointer hover:border-buncha-text-muted transition-colors"
+            >
+              <option value={0}>Never</option>
+              {[1, 5, 10, 15, 30, 60].map((minutes) => (
+                <option key={minutes} value={minutes}>
+                  {minutes} min
+                </option>
+              ))}
+            </select>
+          </div>
+        )}
+
+        <div className="flex items-center justify-between py-3">
+          <div>
+            <h3 className="text-buncha-text font-medium mb-0.5">{appLock.passcode_hash ? "Change Passcode" : "Passcode"}</h3>
+            <p className="text-sm text-buncha-text-muted">{passcodeMessage ?? "At least 4 characters; only a hash is saved"}</p>
+          </div>
+          <div className="flex items-center gap-2">
+            {appLock.passcode_hash && (
+              <input
+                type="password"
+                value={currentPasscode}
+                placeholder="Current"
+                onChange={(e) => setCurrentPasscode(e.target.value)}
+                className="w-24 px-3 py-1.5 bg-buncha-surface rounded-lg border border-buncha-border text-sm text-buncha-text outline-none hover:border-buncha-text-muted focus:border-buncha-accent transition-colors"
+              />
+            )}
+            <input
+              type="password"
+              value={newPasscode}
+              placeholder="New"
+              onChange={(e) => setNewPasscode(e.target.value)}
+              className="w-24 px-3 py-1.5 bg-buncha-surface rounded-lg border border-buncha-border text-sm text-buncha-text outline-none hover:border-buncha-text-muted focus:border-buncha-accent transition-colors"
+            />
+            <button
+              disabled={!newPasscode}
+              onClick={() => savePasscode(newPasscode)}
+              className="px-3 py-1.5 bg-buncha-surface rounded-lg border border-buncha-border text-sm text-buncha-text cursor-pointer hover:border-buncha-text-muted transition-colors disabled:opacity-50 disabled:cursor-default"
+            >
+              Save
+            </button>
+            {appLock.passcode_hash && (
+              <button
+                onClick={() => savePasscode(null)}
+                className="px-3 py-1.5 bg-buncha-surface rounded-lg border border-buncha-border text-sm text-buncha-text cursor-pointer hover:border-buncha-text-muted transition-colors"
+              >
+                Remove
+              </button>
+            )}
+          </div>
+        </div>
+
+        {/* Clipboard Sharing */}
+        <div className="flex items-center justify-between py-3">
+          <div>
+            <h3 className="text-buncha-text font-medium mb-0.5">Clipboard Sharing</h3>
+            <p className="text-sm text-buncha-text-muted">Send the clipboard to your other computers on this network ("send clipboard to &lt;device&gt;")</p>
+          </div>
+          <button
+            onClick={() =>
+              setSettings((prev) => ({
+                ...prev,
+                clipboard_share: { ...clipboardShare, enabled: !clipboardShare.enabled },
+              }))
+            }
+            className={`relative w-11 h-6 rounded-full transition-colors cursor-pointer ${
+              clipboardShare.enabled
+                ? "bg-buncha-accent"
+                : "bg-buncha-surface border border-buncha-border"
+            }`}
+          >
+            <div
+              className={`absolute top-0.5 w-5 h-5 bg-white rounded-full transition-transform ${
+                clipboardShare.enabled
+                  ? "right-0.5"
+                  : "left-0.5"
+              }`}
+            />
+          </button>
+        </div>
+
+        {clipboardShare.enabled && (
+          <>
+            <div className="flex items-center justify-between py-3">
+              <div>
+                <h3 className="text-buncha-text font-medium mb-0.5">Device Name</h3>
+                <p className="text-sm text-buncha-text-muted">How this computer shows up on the others; empty uses the computer name</p>
+              </div>
+              <input
+                type="text"
+                value={clipboardShare.device_name}
+                placeholder="e.g. Laptop"
+                onChange={(e) =>
+                  setSettings((prev) => ({
+                    ...prev,
+                    clipboard_share: { ...clipboardShare, device_name: e.target.value },
+                  }))
+                }
+                className="w-40 px-3 py-1.5 bg-buncha-surface rounded-lg border border-buncha-border text-sm text-buncha-text outline-none hover:border-buncha-text-muted focus:border-buncha-accent transition-colors"
+              />
+            </div>
+
+            <div className="flex items-center justify-between py-3">
+              <div>
+                <h3 className="text-buncha-text font-medium mb-0.5">Share Code</h3>
+                <p className="text-sm text-buncha-text-muted">The same code on every device, at least 8 characters; it encrypts what is sent</p>
+              </div>
+              <input
+                type="password"
+                value={clipboardShare.code}
+                onChange={(e) =>
+                  setSettings((prev) => ({
+                    ...prev,
+                    clipboard_share: { ...clipboardShare, code: e.target.value },
+                  }))
+                }
+                className="w-40 px-3 py-1.5 bg-buncha-surface rounded-lg border border-buncha-border text-sm text-buncha-text outline-none hover:border-buncha-text-muted focus:border-buncha-accent transition-colors"
+              />
+            </div>
+          </>
+        )}
+
+        {/* Webhooks */}
+        <div className="flex items-center justify-between py-3">
+          <div>
+            <h3 className="text-buncha-text font-medium mb-0.5">Webhooks</h3>
+            <p className="text-sm text-buncha-text-muted">
+              {webhookMessage ?? "Signed POSTs from CI or home automation show notifications, start downloads and run workflows"}
+            </p>
+          </div>
+          <div className="flex items-center gap-2">
+            <input
+              type="number"
+              min={1}
+              max={65535}
+              value={webhookPort}
+              disabled={webhook.enabled}
+              onChange={(e) => setWebhookPort(e.target.value)}
+              className="px-3 py-1.5 bg-buncha-surface rounded-lg border border-buncha-border text-sm text-buncha-text outline-none hover:border-buncha-text-muted focus:border-buncha-accent transition-colors disabled:opacity-50 w-20"
+            />
+            <input
+              type="password"
+              value={webhookSecret}
+              placeholder="Secret"
+              disabled={webhook.enabled}
+              onChange={(e) => setWebhookSecret(e.target.value)}
+              className="px-3 py-1.5 bg-buncha-surface rounded-lg border border-buncha-border text-sm text-buncha-text outline-none hover:border-buncha-text-muted focus:border-buncha-accent transition-colors disabled:opacity-50 w-28"
+            />
+            {!webhook.enabled && (
+              <button onClick={generateWebhookSecret} className="px-3 py-1.5 bg-buncha-surface rounded-lg border border-buncha-border text-sm text-buncha-text cursor-pointer hover:border-buncha-text-muted transition-colors disabled:opacity-50 disabled:cursor-default">
+                Generate
+              </button>
+            )}
+            <button
+              disabled={!webhook.enabled && !webhookSecret}
+              onClick={toggleWebhook}
+              className="px-3 py-1.5 bg-buncha-surface rounded-lg border border-buncha-border text-sm text-buncha-text cursor-pointer hover:border-buncha-text-muted transition-colors disabled:opacity-50 disabled:cursor-default"
+            >
+              {webhook.enabled ? "Stop" : "Start"}
+            </button>
+          </div>
+        </div>
+
+        {/* Window Position */}
+        <div className="flex items-center justify-between py-3">
+          <div>
+            <h3 className="text-buncha-text font-medium mb-0.5">Window Position</h3>
+            <p className="text-sm text-buncha-text-muted">Drag the launcher to remember a spot on each monitor</p>
+          </div>
+          <button
+            onClick={() =>
+              invoke("reset_window_positions")
+                .then(() => setSettings((prev) => ({ ...prev, window_positions: {} })))
+                .catch((e) => console.error("Failed to reset window positions:", e))
+            }
+            className="px-3 py-1.5 bg-buncha-surface rounded-lg border border-buncha-border text-sm text-buncha-text cursor-pointer hover:border-buncha-text-muted transition-colors"
+          >
+            Reset
+          </button>
+        </div>
+
+        {/* Clipboard Suggestions */}
+        <div className="flex items-center justify-between py-3">
+          <div>
+            <h3 className="text-buncha-text font-medium mb-0.5">Clipboard Suggestions</h3>
+            <p className="text-sm text-buncha-text-muted">Offer the right tool for copied links, colors, files and text</p>
+          </div>
+          <button
+            onClick={() =>
+              setSettings((prev) => ({
+                ...prev,
+                clipboard_suggestions: !(prev.clipboard_suggestions ?? true),
+              }))
+            }
+            className={`relative w-11 h-6 rounded-full transition-colors cursor-pointer ${
+              settings.clipboard_suggestions ?? true
+                ? "bg-buncha-accent"
+                : "bg-buncha-surface border border-buncha-border"
+            }`}
+          >
+            <div
+              className={`absolute top-0.5 w-5 h-5 bg-white rounded-full transition-transform ${
+                settings.clipboard_suggestions ?? true
+                  ? "right-0.5"
+                  : "left-0.5"
+              }`}
+            />
+          </button>
+        </div>
+
         {/* Command Only Mode */}
         <div className="flex items-center justify-between py-3">
           <div>
@@ -0,0 +1,189 @@
+import { useState, useEffect } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { Activity, X, Loader2, PowerOff, Pause, Play, BatteryCharging } from "lucide-react";
+import type { JobInfo, ScheduledShutdown } from "../types";
+
+const JOB_KIND_LABELS: Record<JobInfo["kind"], string> = {
+  git_download: "Git download",
+  youtube_download: "YouTube download",
+  conversion: "Conversion",
+  transcription: "Transcription",
+  network_scan: "Network scan",
+};
+
+// Progress events don't update the job list, so it's also polled while the window is open
+const JOBS_POLL_INTERVAL_MS = 1000;
+
+function JobsPanel() {
+  const [jobs, setJobs] = useState<JobInfo[]>([]);
+
+  useEffect(() => {
+    const refresh = () => invoke<JobInfo[]>("list_jobs").then(setJobs).catch(() => {});
+    refresh();
+    const interval = setInterval(refresh, JOBS_POLL_INTERVAL_MS);
+    const unlisten = listen<JobInfo[]>("jobs-changed", (event) => {
+      setJobs(event.payload);
+    });
+
+    return () => {
+      clearInterval(interval);
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  if (jobs.length === 0) {
+    return (
+      <div className="flex-1 flex items-center justify-center text-sm text-buncha-text-muted">
+        No downloads or conversions running
+      </div>
+    );
+  }
+
+  return (
+    <div className="flex-1 overflow-y-auto p-4 space-y-3">
+      {jobs.map((job) => (
+        <div key={job.id} className="bg-buncha-surface border border-buncha-border rounded-lg p-3">
+          <div className="flex items-center justify-between gap-3 mb-2">
+            <div className="min-w-0">
+              <div className="text-buncha-text text-sm truncate">{job.label}</div>
+              <div className="text-buncha-text-muted text-xs">{JOB_KIND_LABELS[job.kind]}</div>
+            </div>
+            <div className="flex items-center gap-1">
+              {job.power !== "normal" && (
+                <button
+                  onClick={() => invoke("run_job_on_battery", { jobId: job.id }).catch(() => {})}
+                  className="p-1.5 rounded-lg text-buncha-text-muted hover:text-buncha-text hover:bg-buncha-bg transition-colors cursor-pointer"
+                  title="Run at full speed on battery"
+                >
+                  <BatteryCharging className="w-4 h-4" />
+                </button>
+              )}
+              {job.kind === "conversion" && (
+                <button
+                  onClick={() =>
+                    invoke(job.paused ? "resume_conversion" : "pause_conversion", { jobId: job.id }).catch(() => {})
+                  }
+                  className="p-1.5 rounded-lg text-buncha-text-muted hover:text-buncha-text hover:bg-buncha-bg transition-colors cursor-pointer"
+                  title={job.paused ? "Resume" : "Pause"}
+                >
+                  {job.paused ? <Play className="w-4 h-4" /> : <Pause className="w-4 h-4" />}
+                </button>
+              )}
+              <button
+                onClick={() =>
+                  invoke(job.kind === "conversion" ? "cancel_conversion" : "cancel_job", { jobId: job.id }).catch(() => {})
+                }
+                className="p-1.5 rounded-lg text-buncha-text-muted hover:text-buncha-text hover:bg-buncha-bg transition-colors cursor-pointer"
+                title="Cancel"
+              >
+                <X className="w-4 h-4" />
+              </button>
+            </div>
+          </div>
+          {job.queue_position !== null && job.power === "deferred" ? (
+            <div className="text-xs text-buncha-text-muted">Waiting for AC power</div>
+          ) : job.queue_position !== null ? (
+            <div className="text-xs text-buncha-text-muted">
+              Waiting for a free slot ({job.queue_position === 1 ? "next" : `#${job.queue_position} in line`})
+            </div>
+          ) : job.progress !== null ? (
+            <div className="flex items-center gap-2">
+              <div className="flex-1 h-1.5 bg-buncha-bg rounded-full overflow-hidden">
+                <div className="h-full bg-buncha-accent transition-all" style={{ width: `${job.progress}%` }} />
+              </div>
+              <span className="text-xs text-buncha-text-muted w-20 text-right">
+                {job.paused
+                  ? "Paused"
+                  : job.power === "deferred"
+                    ? "On hold"
+                    : job.power === "throttled"
+                      ? `${job.progress}% (slow)`
+                      : `${job.progress}%`}
+              </span>
+            </div>
+          ) : (
+            <div className="flex items-center gap-2 text-xs text-buncha-text-muted">
+              <Loader2 className="w-3 h-3 animate-spin" />
+              Starting...
+            </div>
+          )}
+        </div>
+      ))}
+    </div>
+  );
+}
+
+// Countdown for a delayed shutdown, with a last chance to cancel (opened by shutdown_system)
+function ShutdownPanel() {
+  const [endsAt, setEndsAt] = useState<number | null>(null);
+  const [now, setNow] = useState(Date.now());
+
+  useEffect(() => {
+    const update = (scheduled: ScheduledShutdown | null) =>
+      setEndsAt(scheduled ? Date.now() + scheduled.remaining * 1000 : null);
+    invoke<ScheduledShutdown | null>("get_scheduled_shutdown").then(update).catch(() => {});
+    const unlisten = listen<ScheduledShutdown | null>("shutdown-scheduled", (event) => update(event.payload));
+    const interval = setInterval(() => setNow(Date.now()), 250);
+
+    return () => {
+      clearInterval(interval);
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  const remaining = endsAt !== null ? Math.max(0, Math.ceil((endsAt - now) / 1000)) : null;
+
+  return (
+    <div className="flex-1 flex flex-col items-center justify-center gap-4 p-4">
+      <div className="text-center">
+        <div className="text-3xl font-mono text-buncha-text tabular-nums">
+          {remaining !== null ? `${Math.floor(remaining / 60)}:${(remaining % 60).toString().padStart(2, "0")}` : "--:--"}
+        </div>
+        <div className="text-xs text-buncha-text-muted mt-1">until the computer shuts down</div>
+      </div>
+      <div className="flex gap-2">
+        <button
+          onClick={() => invoke("cancel_shutdown").catch(() => {})}
+          className="px-4 py-2 rounded-lg bg-buncha-accent text-white text-sm hover:opacity-90 transition-opacity cursor-pointer"
+        >
+          Cancel
+        </button>
+        <button
+          onClick={() => invoke("shutdown_system", { delay: 0 }).catch(() => {})}
+          className="px-4 py-2 rounded-lg bg-buncha-surface border border-buncha-border text-buncha-text text-sm hover:bg-buncha-bg transition-colors cursor-pointer"
+        >
+          Shut Down Now
+        </button>
+      </div>
+    </div>
+  );
+}
+
+const TOOL_TITLES: Record<string, string> = {
+  jobs: "Running Jobs",
+  shutdown: "Shutting Down",
+};
+
+// Root of a detached tool window (`index.html?window=<tool>`)
+export function ToolWindow({ tool }: { tool: string }) {
+  return (
+    <div className="h-screen flex flex-col bg-buncha-bg">
+      <div className="bg-buncha-surface/30 border-b border-buncha-border px-4 py-3 flex items-center justify-center">
+        <div className="flex items-center gap-2 text-sm text-buncha-text-muted">
+          {tool === "shutdown" ? <PowerOff className="w-4 h-4" /> : <Activity className="w-4 h-4" />}
+          <span>{TOOL_TITLES[tool] ?? tool}</span>
+        </div>
+      </div>
+      {tool === "jobs" ? (
+        <JobsPanel />
+      ) : tool === "shutdown" ? (
+        <ShutdownPanel />
+      ) : (
+        <div className="flex-1 flex items-center justify-center text-sm text-buncha-text-muted">
+          This tool can't be opened in its own window
+        </div>
+      )}
+    </div>
+  );
+}
@@ -25,6 +25,7 @@ import { formatFileSize, formatDuration, estimateOutputSize } from "../utils";
 
 interface VideoConverterProps {
   videoFile: VideoFileMetadata | null;
+  batchCount: number; // files picked at once, converted in the queue when more than one
   setVideoFile: (file: VideoFileMetadata | null) => void;
   selectedFormat: string;
   setSelectedFormat: (format: string) => void;
@@ -45,6 +46,7 @@ interface VideoConverterProps {
 
 export function VideoConverter({
   videoFile,
+  batchCount,
   setVideoFile,
   selectedFormat,
   setSelectedFormat,
@@ -112,6 +114,11 @@ export function VideoConverter({
                         </div>
                         <div className="flex-1 min-w-0">
                           <p className="font-medium text-sm truncate text-buncha-text">{videoFile.name}</p>
+                          {batchCount > 1 && (
+                            <p className="text-xs text-buncha-text-muted mt-0.5">
+                              and {batchCount - 1} more, converted with the same settings
+                            </p>
+                          )}
                           <div className="flex items-center gap-3 mt-2 text-xs text-buncha-text-muted">
                             <span className="flex items-center gap-1">
                               <HardDrive className="w-3 h-3" />
@@ -384,7 +391,9 @@ export function VideoConverter({
               className="flex items-center gap-2 px-6 py-3 bg-buncha-accent hover:bg-buncha-accent/90 text-white font-medium rounded-xl transition-all hover:scale-[1.02] active:scale-[0.98] cursor-pointer"
             >
               <Zap className="w-4 h-4" />
-              Convert to {selectedFormat.toUpperCase()}
+              {batchCount > 1
+                ? `Convert ${batchCount} files to ${selectedFormat.toUpperCase()}`
+                : `Convert to ${selectedFormat.toUpperCase()}`}
             </button>
           </div>
         )}
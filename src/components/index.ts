@@ -8,3 +8,8 @@ export { QRGenerator } from './QRGenerator';
 export { RegexTester } from './RegexTester';
 export { GitDownloader } from './GitDownloader';
 export { YouTubeDownloader } from './YouTubeDownloader';
+export { ToolWindow } from './ToolWindow';
+export { OverlayTimer } from './OverlayTimer';
+export { ColorLoupe } from './ColorLoupe';
+export { CalibrationPatterns } from './CalibrationPatterns';
+export { AnnotationOverlay } from './AnnotationOverlay';
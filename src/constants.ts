@@ -55,6 +55,16 @@ export const VIDEO_FILE_FILTERS = [
   { name: "Video", extensions: ["mp4", "avi", "mov", "mkv", "webm", "wmv", "flv", "m4v"] }
 ];
 
+// Files translate_file can handle
+export const TRANSLATABLE_FILE_FILTERS = [
+  { name: "Text, subtitles and Markdown", extensions: ["txt", "srt", "vtt", "md", "markdown"] }
+];
+
+// Files transcribe_media can take speech from
+export const MEDIA_FILE_FILTERS = [
+  { name: "Video and audio", extensions: ["mp4", "avi", "mov", "mkv", "webm", "wmv", "flv", "m4v", "mp3", "wav", "m4a", "flac", "ogg", "opus"] }
+];
+
 // Common ports for port killer
 export const COMMON_PORTS = [3000, 3001, 5173, 8080, 8000, 4200, 5000, 1420];
 
@@ -223,8 +233,26 @@ export const CURRENCY_ALIASES: Record<string, string> = {
   aud: "AUD", cad: "CAD", nzd: "NZD", sgd: "SGD", hkd: "HKD",
   dkk: "DKK", pln: "PLN", czk: "CZK", huf: "HUF", ils: "ILS",
   thb: "THB", myr: "MYR", php: "PHP", idr: "IDR",
+  // Cryptocurrencies (priced through CoinGecko)
+  bitcoin: "BTC", bitcoins: "BTC", btc: "BTC",
+  ethereum: "ETH", ether: "ETH", eth: "ETH",
+  solana: "SOL", sol: "SOL",
+  dogecoin: "DOGE", doge: "DOGE",
+  litecoin: "LTC", ltc: "LTC",
+  tether: "USDT", usdt: "USDT", usdc: "USDC",
+  xrp: "XRP", ripple: "XRP",
+  cardano: "ADA", ada: "ADA",
+  monero: "XMR", xmr: "XMR",
+  bnb: "BNB", ton: "TON", dot: "DOT", avax: "AVAX", link: "LINK", shib: "SHIB", pepe: "PEPE",
 };
 
+// Coin symbols convert_currency knows besides 3-letter fiat codes (matches crypto_prices.rs)
+export const CRYPTO_SYMBOLS = new Set([
+  "BTC", "ETH", "USDT", "USDC", "BNB", "SOL", "XRP", "DOGE", "ADA", "TRX", "TON", "AVAX",
+  "SHIB", "DOT", "LINK", "BCH", "LTC", "XLM", "XMR", "ATOM", "UNI", "NEAR", "APT", "SUI",
+  "ARB", "OP", "POL", "PEPE", "DAI", "ETC",
+]);
+
 // YouTube Downloader Constants
 export const YOUTUBE_QUALITY_OPTIONS: { id: YouTubeQuality; label: string; description: string; icon: LucideIcon }[] = [
   { id: 'best', label: 'Best', description: 'Highest available', icon: Sparkles },
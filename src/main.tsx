@@ -1,10 +1,26 @@
 import React from "react";
 import ReactDOM from "react-dom/client";
 import App from "./App";
+import { ToolWindow, OverlayTimer, ColorLoupe, CalibrationPatterns, AnnotationOverlay } from "./components";
 import "./index.css";
 
+// Detached tool windows load the same page with ?window=<tool>
+const detachedTool = new URLSearchParams(window.location.search).get("window");
+
 ReactDOM.createRoot(document.getElementById("root") as HTMLElement).render(
   <React.StrictMode>
-    <App />
+    {detachedTool === "overlay-timer" ? (
+      <OverlayTimer />
+    ) : detachedTool === "loupe" ? (
+      <ColorLoupe />
+    ) : detachedTool === "calibration" ? (
+      <CalibrationPatterns />
+    ) : detachedTool === "annotate" ? (
+      <AnnotationOverlay />
+    ) : detachedTool ? (
+      <ToolWindow tool={detachedTool} />
+    ) : (
+      <App />
+    )}
   </React.StrictMode>
 );
@@ -10,10 +10,19 @@ export interface Tool {
   isSettings?: boolean;
 }
 
+// A global hotkey (get_active_hotkey, Settings.hotkey_fallbacks)
+export interface HotkeyBinding {
+  modifiers: string[];
+  key: string;
+}
+
 export interface Settings {
   hotkey_modifiers: string[];
   hotkey_key: string;
+  hotkey_fallbacks?: HotkeyBinding[]; // tried in order when another app holds the hotkey
   launch_at_startup: boolean;
+  autostart_method?: AutostartMethod;
+  window_positions?: Record<string, [number, number]>; // per monitor, recorded by the backend
   show_in_tray: boolean;
   automatic_updates: boolean;
   theme: "dark" | "light" | "system";
@@ -22,6 +31,366 @@ export interface Settings {
   quick_translation_hotkey_modifiers: string[];
   quick_translation_hotkey_key: string;
   quick_translation_target_language: string;
+  quick_translation_replace?: boolean; // the hotkey types the translation over the selection
+  // Screen translation (OCR) hotkey; empty key disables it
+  screen_translation_hotkey_modifiers?: string[];
+  screen_translation_hotkey_key?: string;
+  // Push-to-talk hotkey that speaks a launcher query; empty key disables it
+  voice_hotkey_modifiers?: string[];
+  voice_hotkey_key?: string;
+  voice_language?: string; // empty detects the language
+  favorite_currencies?: string[]; // "100 usd" is shown in these, at most five
+  // Hotkey chords (main hotkey, then a single key)
+  chord_bindings?: ChordBinding[];
+  chord_timeout_ms?: number;
+  mouse_trigger?: MouseTrigger;
+  quick_actions?: QuickAction[];
+  external_triggers?: ExternalTriggerSettings;
+  shell_integration?: boolean;
+  file_associations?: boolean;
+  tray_icon_style?: TrayIconStyle;
+  clipboard_suggestions?: boolean;
+  always_on_top?: boolean;
+  // Pomodoro focus sessions
+  pomodoro_focus_minutes?: number;
+  pomodoro_break_minutes?: number;
+  pomodoro_dnd?: boolean;
+  pomodoro_pause_notifications?: boolean;
+  start_hidden?: boolean;
+  idle_threshold_minutes?: number;
+  color_sample_size?: 1 | 3 | 5 | 9; // pixels averaged by the color picker, per side
+  night_light?: NightLightSettings;
+  translation_engine?: "online" | "offline"; // offline keeps text on this computer (translateLocally)
+  secrets_lock_minutes?: number; // unused minutes before encrypted snippets lock again
+  app_lock?: AppLockSettings;
+  clipboard_share?: ClipboardShareSettings;
+  webhook?: WebhookSettings;
+  job_limits?: JobLimits;
+  battery?: BatterySettings;
+}
+
+// Signed HTTP webhooks (start_webhook_listener / stop_webhook_listener)
+export interface WebhookSettings {
+  enabled: boolean;
+  port: number;
+  secret: string; // HMAC-SHA256 key senders sign the body with
+}
+
+export interface WebhookStatus {
+  running: boolean;
+  port: number;
+}
+
+// Sending the clipboard to other computers on the LAN (Settings.clipboard_share, "clipboard-received")
+export interface ClipboardShareSettings {
+  enabled: boolean;
+  device_name: string; // empty uses the computer name
+  code: string; // shared secret, the same on every device
+}
+
+export interface ShareDevice {
+  name: string;
+  address: string;
+  port: number;
+}
+
+// Passcode in front of histories and secrets (Settings.app_lock, get_app_lock_status / "app-lock-changed")
+export interface AppLockSettings {
+  enabled: boolean;
+  idle_minutes: number; // 0 = only on demand and when the session locks
+  passcode_hash: string | null; // set through set_app_passcode only
+}
+
+export interface AppLockStatus {
+  enabled: boolean; // on, with a passcode set
+  locked: boolean;
+}
+
+// Warmer screen colors (Settings.night_light, get_night_light_status / "night-light-changed")
+export interface NightLightSettings {
+  schedule: boolean; // on at sunset, off at sunrise
+  temperature: number; // kelvin
+  location: string; // place or coordinates; empty uses the last one sun times used
+}
+
+export interface NightLightStatus {
+  enabled: boolean;
+  scheduled: boolean;
+  next_change: number | null; // Unix time (seconds) of the next scheduled switch
+}
+
+// Next action proposed from the clipboard when the window opens ("clipboard-suggestion" event)
+export interface ClipboardSuggestion {
+  kind: "youtube_url" | "git_url" | "color" | "translation" | "convert_file";
+  tool: string;
+  value: string;
+  label: string;
+  language: string | null;
+}
+
+// Current Pomodoro phase ("pomodoro-tick" event, null once stopped)
+export interface PomodoroStatus {
+  phase: "focus" | "break";
+  remaining: number; // seconds
+  round: number;
+}
+
+// Network configuration (get_network_status / network-changed)
+export interface NetworkInterface {
+  name: string;
+  description: string;
+  mac_address: string | null;
+  addresses: string[];
+  is_up: boolean;
+  is_loopback: boolean;
+  is_tunnel: boolean;
+}
+
+export interface NetworkStatus {
+  interfaces: NetworkInterface[];
+  gateway: string | null;
+  gateway_interface: string | null;
+  dns_servers: string[];
+  proxy: string | null;
+  vpn_interfaces: string[];
+  online: boolean;
+}
+
+// A device on the LAN (discover_network_devices)
+export interface DiscoveredDevice {
+  ip: string;
+  name: string | null;
+  mac: string | null;
+  vendor: string | null; // "Private address" for randomized MACs
+  services: string[]; // e.g. "googlecast", "ipp", "upnp"
+}
+
+// Lock / idle state (get_session_state / session-state-changed)
+export interface SessionState {
+  locked: boolean;
+  idle: boolean;
+  idle_seconds: number;
+}
+
+// Keep-awake state (keep_awake / set_keep_awake / keep-awake-changed); null while off
+export type KeepAwakeMode = "off" | "system" | "jiggle"; // jiggle also nudges the mouse
+
+export interface KeepAwakeStatus {
+  mode: KeepAwakeMode;
+  remaining: number | null; // seconds until it turns itself off; null = until turned off
+}
+
+// Running auto-clicker (start_auto_clicker / auto-clicker-changed); null once stopped
+export type MouseButton = "left" | "right" | "middle";
+
+export interface AutoClickerConfig {
+  interval_ms?: number;
+  button?: MouseButton;
+  position?: [number, number] | null; // screen pixels; null follows the cursor
+  max_clicks?: number | null;
+  start_delay_ms?: number;
+  stop_hotkey_modifiers?: string[];
+  stop_hotkey_key?: string;
+}
+
+export interface AutoClickerStatus {
+  interval_ms: number;
+  button: MouseButton;
+  position: [number, number] | null;
+  max_clicks: number | null;
+  clicks: number;
+  stop_hotkey: string;
+}
+
+// Screen recording (start_screen_recording / screen-recording-tick / screen-recording-changed)
+export type RecordingFormat = "mp4" | "mkv" | "webm" | "gif"; // gif has no audio
+
+export interface RecordingOptions {
+  region?: { x: number; y: number; width: number; height: number } | null; // physical pixels
+  fps?: number;
+  audio?: boolean;
+  format?: RecordingFormat;
+  output_path?: string | null; // defaults to the Videos folder
+}
+
+export interface RecordingStatus {
+  path: string;
+  format: RecordingFormat;
+  audio: boolean;
+  elapsed_secs: number;
+}
+
+// Microphone recording (start_audio_recording / audio-recording-level / audio-recording-changed)
+export type AudioFormat = "mp3" | "wav" | "opus";
+
+export interface AudioInputDevice {
+  id: string; // passed to start_audio_recording
+  name: string;
+}
+
+export interface AudioRecordingStatus {
+  path: string;
+  format: AudioFormat;
+  device: string; // device name
+  elapsed_secs: number;
+}
+
+export interface AudioLevel {
+  peak_db: number | null; // null for silence
+  level: number; // 0..1
+  elapsed_secs: number;
+}
+
+// Pending delayed shutdown (get_scheduled_shutdown / shutdown-scheduled); null when none
+export interface ScheduledShutdown {
+  remaining: number; // seconds
+}
+
+// Dice roll result (roll_dice); modifier is the sum of the flat +/- terms
+export interface DiceGroup {
+  count: number;
+  sides: number;
+  negative: boolean;
+  rolls: number[];
+}
+
+export interface DiceRoll {
+  notation: string;
+  groups: DiceGroup[];
+  modifier: number;
+  total: number;
+}
+
+// Recent random_choice / roll_dice / flip_coin results (get_random_history), newest first
+export interface RandomHistoryEntry {
+  kind: "choice" | "dice" | "coin";
+  input: string;
+  result: string;
+  timestamp: number;
+}
+
+// A monitor to show calibration patterns on (list_displays)
+export interface DisplayInfo {
+  key: string;
+  name: string;
+  width: number;
+  height: number;
+  scale_factor: number;
+  is_primary: boolean;
+}
+
+// Countdown/clock overlay window (show_overlay_timer / get_overlay_timer)
+export type OverlayMode = "countdown" | "clock";
+export type OverlayPosition = "top_left" | "top_right" | "bottom_left" | "bottom_right" | "center";
+
+export interface OverlayTimerConfig {
+  mode?: OverlayMode;
+  duration?: number; // seconds, for countdowns
+  label?: string;
+  width?: number;
+  height?: number;
+  position?: OverlayPosition;
+  opacity?: number; // background opacity, 0.2-1
+}
+
+export interface OverlayTimer {
+  mode: OverlayMode;
+  ends_at: number | null; // ms since the epoch
+  label: string | null;
+  opacity: number;
+}
+
+// Saved Wake-on-LAN target (list_wol_devices / save_wol_device)
+export interface WolDevice {
+  name: string;
+  mac: string;
+  broadcast_ip: string | null;
+}
+
+// Offline reference lookups (lookup_reference)
+export type ReferenceKind = "http_status" | "mime_type" | "port" | "html_entity";
+
+export interface ReferenceEntry {
+  kind: ReferenceKind;
+  key: string;
+  name: string;
+  description: string;
+}
+
+// Result of sum_clipboard_numbers
+export interface NumberSummary {
+  count: number;
+  sum: number;
+  average: number;
+  min: number;
+  max: number;
+}
+
+// File type choice for pick_save_path, which allows writing to the picked file's folder
+export interface FileFilter {
+  name: string;
+  extensions: string[];
+}
+
+// Tool to open with files passed from the file manager ("open-files" event)
+export interface OpenFilesRequest {
+  tool: string;
+  paths: string[];
+}
+
+export type QuickActionType =
+  | { type: 'open_tool'; tool: string }
+  | { type: 'kill_port'; port: number }
+  | { type: 'pick_color' }
+  | { type: 'start_timer'; seconds: number; label?: string }
+  | { type: 'cancel_timer' };
+
+export type QuickAction = QuickActionType & { label: string };
+
+// Contents of a .bunchatools-workflow file (read_workflow_file / run_workflow)
+export interface Workflow {
+  name: string | null;
+  actions: QuickActionType[];
+}
+
+export type ExternalTrigger =
+  | { source: 'stream_deck'; key: string }
+  | { source: 'midi'; note: number; channel?: number | null };
+
+export interface TriggerBinding {
+  trigger: ExternalTrigger;
+  action: QuickActionType;
+}
+
+export interface ExternalTriggerSettings {
+  websocket_enabled: boolean;
+  websocket_port: number;
+  websocket_token: string;
+  midi_enabled: boolean;
+  midi_device: string;
+  bindings: TriggerBinding[];
+}
+
+export type MouseTrigger = 'none' | 'xbutton1' | 'xbutton2' | 'middle_click_desktop';
+
+// Light/dark name the taskbar the monochrome icon is made for; auto follows the OS theme
+export type TrayIconStyle = 'color' | 'auto' | 'light' | 'dark';
+
+export type AutostartMethod = 'desktop_entry' | 'systemd';
+
+// Desktop light/dark theme (get_system_theme / system-theme-changed)
+export type SystemTheme = 'light' | 'dark';
+
+export interface ChordBinding {
+  key: string;
+  tool: string; // tool id
+}
+
+// Payload of "chord-started": the launcher waits this long for the second key
+export interface ChordStart {
+  bindings: ChordBinding[];
+  modifiers: string[]; // main hotkey modifiers, which may still be held
+  timeout_ms: number;
 }
 
 // Video Converter Types
@@ -58,6 +427,25 @@ export interface VideoFileMetadata {
   codec: string;
 }
 
+// enqueue_conversion / "conversion-queue-changed"
+export interface QueuedConversion {
+  id: number;
+  input_path: string;
+  output_path: string;
+  status: "queued" | "converting" | "done" | "failed" | "cancelled";
+  progress: number; // percent
+  error: string | null;
+}
+
+export interface ConversionQueueState {
+  items: QueuedConversion[]; // in the order they run
+  concurrency: number;
+  percent: number; // overall
+  pending: number; // queued or converting
+  done: number;
+  failed: number; // failed or cancelled
+}
+
 export interface PortProcess {
   pid: number;
   name: string;
@@ -65,12 +453,30 @@ export interface PortProcess {
   protocol: string;
 }
 
+// Rejection of kill_port_process; retry with { elevate: true } after asking the user
+export interface KillProcessError {
+  message: string;
+  requires_elevation: boolean;
+}
+
 export interface CurrencyResult {
   amount: number;
   from: string;
   to: string;
   result: number;
   rate: number;
+  as_of: string; // date of the rates, "2026-10-15"
+  is_stale: boolean; // offline, last rates that were fetched
+  // Percent change of the rate, null when the history didn't load
+  change_7d: number | null;
+  change_30d: number | null;
+  change_365d: number | null;
+}
+
+// One day of get_rate_history
+export interface RatePoint {
+  date: string;
+  rate: number;
 }
 
 export interface CurrencyQuery {
@@ -85,6 +491,72 @@ export interface TranslationResult {
   target_language: string;
 }
 
+export interface LanguageCandidate {
+  language: string;
+  code: string;
+  confidence: number; // 0 to 1
+}
+
+// Detected source language; unreliable detections are worth a warning
+export interface LanguageDetection extends LanguageCandidate {
+  reliable: boolean;
+  alternatives: LanguageCandidate[];
+}
+
+// Sent while translate_file works through a file
+export interface FileTranslationProgress {
+  path: string;
+  done: number;
+  total: number;
+}
+
+// Sent while transcribe_media works: model download, audio extraction, then speech to text
+export interface TranscriptionProgress {
+  path: string;
+  stage: 'downloading_model' | 'extracting_audio' | 'transcribing';
+  percent: number;
+}
+
+export interface WordSense {
+  definition: string;
+  example: string | null;
+}
+
+export interface WordMeaning {
+  part_of_speech: string;
+  senses: WordSense[];
+  synonyms: string[];
+  antonyms: string[];
+}
+
+// define_word: one meaning per part of speech
+export interface WordDefinition {
+  word: string;
+  language: string;
+  phonetic: string | null;
+  meanings: WordMeaning[];
+}
+
+// Another app's window (list_external_windows)
+export interface ExternalWindow {
+  id: number;
+  title: string;
+  topmost: boolean;
+  opacity: number; // 0..1
+}
+
+// Push-to-talk voice input ("voice-capture" events)
+export interface VoiceCaptureStatus {
+  state: 'listening' | 'downloading_model' | 'transcribing' | 'idle' | 'error';
+  percent: number | null;
+  message: string | null;
+}
+
+// Text read from a screen region, with its translation
+export interface ScreenTranslation extends TranslationResult {
+  text: string;
+}
+
 export type QRCodeType = "url" | "wifi" | "email" | "phone" | "text" | "vcard" | "location" | "event";
 
 export interface QRCodeData {
@@ -98,6 +570,36 @@ export interface QRCodeData {
   event: { title: string; location: string; startDate: string; endDate: string };
 }
 
+// Structured payload validated and encoded by build_qr_payload
+export type QrPayload =
+  | { type: "wifi"; ssid: string; password: string; security: "WPA" | "WEP" | "nopass"; hidden?: boolean }
+  | { type: "vcard"; first_name: string; last_name: string; phone: string; email: string }
+  | { type: "geo"; latitude: string; longitude: string }
+  | { type: "mailto"; email: string; subject: string };
+
+// Saved screen pick (get_color_history), newest first
+// WCAG 2.1 contrast between two colors
+export interface ContrastResult {
+  foreground: string;
+  background: string;
+  ratio: number;
+  aa_normal: boolean;
+  aa_large: boolean;
+  aaa_normal: boolean;
+  aaa_large: boolean;
+}
+
+// Palette file formats for export_palette
+export type PaletteFormat = 'ase' | 'gpl' | 'css' | 'scss' | 'json';
+
+export interface ColorHistoryEntry {
+  id: number;
+  hex: string;
+  timestamp: number; // seconds since the epoch
+  monitor: string | null;
+}
+
+// Every notation of a color (pick_color / get_color_formats)
 export interface ColorFormats {
   hex: string;
   rgb: string;
@@ -107,6 +609,8 @@ export interface ColorFormats {
   cmyk: string;
   lab: string;
   xyz: string;
+  name: string; // nearest CSS named color
+  name_exact: boolean;
 }
 
 export interface UnitCategory {
@@ -122,8 +626,249 @@ export interface UnitConversionResult {
   category: string;
 }
 
+// A tip a tool's uses have unlocked (get_tool_tips / dismiss_tip)
+export interface ToolTip {
+  id: string;
+  tool: string;
+  text: string;
+}
+
+// A moment on one zone's clocks (time_zones.rs)
+export interface ZoneTime {
+  zone: string; // "Europe/Berlin", "UTC+05:30" or "Local"
+  abbreviation: string;
+  utc_offset: number; // seconds
+  datetime: string; // wall clock time, "2026-10-16T15:00:00"
+  display: string; // "Fri 16 Oct 15:00 CEST"
+}
+
+// convert_time / convert_time_query
+export interface TimeConversion {
+  timestamp: number; // unix seconds
+  from: ZoneTime;
+  to: ZoneTime;
+}
+
+// parse_epoch
+export interface EpochTime {
+  unit: "seconds" | "milliseconds" | "microseconds" | "nanoseconds";
+  utc: string; // RFC 3339
+  local: ZoneTime;
+  relative: string; // "3 days ago"
+}
+
+// get_transfer_stats / set_transfer_cap
+export type TransferRange = "today" | "week" | "month" | "year";
+
+export interface TransferCap {
+  monthly_bytes: number;
+  action: "warn" | "pause"; // pause refuses new downloads
+}
+
+export interface TransferStats {
+  from: string; // "2026-10-01"
+  to: string;
+  total: { downloaded: number; uploaded: number };
+  tools: { tool: string; downloaded: number; uploaded: number }[]; // most first
+  days: { date: string; downloaded: number; uploaded: number }[];
+  cap: TransferCap | null;
+  month_total: number;
+}
+
+// evaluate_expression
+export interface ExpressionResult {
+  value: number;
+  unit: string | null; // unit symbol or currency code
+  result: string; // to copy, e.g. "8.5" or "0xFF"
+  display: string; // e.g. "8.5 km"
+}
+
+// A conversion by the backend's unit table (convert_unit / convert_unit_query)
+export interface UnitResult {
+  value: number;
+  from: string; // unit names, e.g. "miles"
+  to: string;
+  from_symbol: string;
+  to_symbol: string;
+  result: number;
+  category:
+    | "length" | "mass" | "temperature" | "area" | "volume"
+    | "speed" | "data_size" | "energy" | "pressure" | "time";
+}
+
+// Calculator tape and memory register (get_calc_history / record_calculation)
+export interface CalcEntry {
+  expression: string;
+  result: number;
+  timestamp: number; // seconds
+}
+
+export interface CalcHistory {
+  entries: CalcEntry[]; // oldest first
+  memory: number;
+}
+
+// Screenshot annotations (save_annotated_image / copy_annotated_image), in image pixels
+export interface AnnotationPoint {
+  x: number;
+  y: number;
+}
+
+export interface AnnotationArea {
+  x: number;
+  y: number;
+  width: number; // negative when dragged up or left
+  height: number;
+}
+
+export type Annotation =
+  | { kind: "arrow"; from: AnnotationPoint; to: AnnotationPoint; color: string; width: number }
+  | { kind: "rectangle"; area: AnnotationArea; color: string; width: number }
+  | { kind: "text"; at: AnnotationPoint; sprite: string } // PNG data URL
+  | { kind: "blur"; area: AnnotationArea; strength: number }
+  | { kind: "pixelate"; area: AnnotationArea; block: number }
+  // Drawn by the backend with the system font (annotate_image)
+  | { kind: "label"; at: AnnotationPoint; text: string; color: string; size: number; background?: string };
+
+// Habits (add_habit / update_habit) and their streaks (get_streaks / habits-changed)
+export interface Habit {
+  id: string;
+  name: string;
+  reminder: string | null; // HH:MM
+}
+
+export interface HabitStreak {
+  id: string;
+  name: string;
+  reminder: string | null; // HH:MM
+  done_today: boolean;
+  current: number; // days
+  longest: number;
+  total: number;
+}
+
+// QR codes read from the screen (scan_qr_from_screen)
+export type ScannedCode =
+  | { kind: "url"; text: string }
+  | { kind: "wifi"; text: string; ssid: string; password: string; security: string; hidden: boolean }
+  | { kind: "text"; text: string };
+
+// Country reference (lookup_country) and public holidays (get_public_holidays)
+export interface Country {
+  code: string; // ISO 3166-1 alpha-2
+  code3: string;
+  name: string;
+  calling_code: string;
+  currency: string;
+  flag: string;
+}
+
+export interface PublicHoliday {
+  date: string; // YYYY-MM-DD
+  local_name: string;
+  name: string;
+  global: boolean;
+  regions: string[] | null;
+  types: string[];
+}
+
+export interface HolidayCalendar {
+  country: Country;
+  year: number;
+  holidays: PublicHoliday[];
+  cached: boolean; // offline, showing an older copy
+}
+
+// Sun times (get_sun_times); times are Unix timestamps in seconds
+export interface GeoLocation {
+  name: string;
+  latitude: number;
+  longitude: number;
+  timezone: string | null; // IANA name, when the geocoder knows it
+}
+
+export interface SunReport {
+  location: GeoLocation;
+  date: string; // YYYY-MM-DD
+  sunrise: number | null;
+  sunset: number | null;
+  solar_noon: number;
+  dawn: number | null; // civil twilight
+  dusk: number | null;
+  golden_hour_morning: { start: number; end: number } | null;
+  golden_hour_evening: { start: number; end: number } | null;
+  day_length: number; // seconds
+  polar: "day" | "night" | null;
+}
+
+// Past translations (get_translation_history), newest first
+export interface TranslationHistoryEntry {
+  id: number;
+  source_text: string;
+  translated_text: string;
+  source_language: string;
+  target_language: string;
+  timestamp: number; // seconds since the epoch
+}
+
+// Preferred translations for terms (get_glossary), applied around every translation
+export interface GlossaryEntry {
+  id: number;
+  source_lang: string; // language code; empty for any language
+  target_lang: string;
+  term: string;
+  translation: string;
+}
+
+// Text snippets (get_snippets); placeholders like {uuid} or {clipboard|upper} fill in on expansion
+export interface Snippet {
+  name: string;
+  text: string; // ciphertext when encrypted
+  encrypted: boolean; // needs the secrets store unlocked to expand
+}
+
+// Key for encrypted snippets (get_secrets_status / unlock_secrets / "secrets-changed")
+export interface SecretsStatus {
+  configured: boolean; // a passphrase has been set
+  unlocked: boolean;
+  remaining: number | null; // seconds until it locks itself
+}
+
+export interface SnippetTransform {
+  name: string;
+  pipeline: string; // e.g. "trim|upper"
+}
+
+export interface SnippetStore {
+  snippets: Snippet[];
+  transforms: SnippetTransform[];
+}
+
+// Time tracking (start_time_entry / time-entry-changed / get_time_report); times in seconds
+export interface TimeEntry {
+  id: number;
+  label: string;
+  project: string | null;
+  start: number;
+  end: number | null; // null while running
+  idle: { start: number; end: number }[]; // left out of the duration
+}
+
+export type TimeReportRange = "today" | "yesterday" | "week" | "last_week";
+
+export interface TimeReport {
+  range: TimeReportRange;
+  from: string; // YYYY-MM-DD
+  to: string;
+  total_seconds: number;
+  idle_seconds: number;
+  projects: { project: string; seconds: number }[]; // longest first, "" = no project
+  days: { date: string; seconds: number }[];
+  entries: TimeEntry[];
+}
+
 export interface QuickResult {
-  type: "calculator" | "unit" | "currency" | "color";
+  type: "calculator" | "unit" | "currency" | "color" | "reference" | "time";
   query: string;
   result: string;
   icon: LucideIcon;
@@ -213,3 +958,91 @@ export interface YouTubeUrlInfo {
   isValid: boolean;
   originalUrl: string;
 }
+
+// Job Manager Types
+export type JobKind = 'git_download' | 'youtube_download' | 'conversion' | 'transcription' | 'network_scan';
+
+// What the battery policy does to a heavy job (conversions, transcriptions) now
+export type JobPower = 'normal' | 'throttled' | 'deferred';
+
+export interface JobInfo {
+  id: number;
+  kind: JobKind;
+  label: string;
+  started_at: number;
+  progress: number | null;
+  paused: boolean; // conversions only (pause_conversion / resume_conversion)
+  queue_position: number | null; // 1-based while waiting for a free slot
+  power: JobPower;
+  run_on_battery: boolean; // ignores the battery policy (run_job_on_battery)
+}
+
+// "job-queue-progress": a waiting job's place in line, null once it has started
+export interface JobQueueProgress {
+  id: number;
+  kind: JobKind;
+  position: number | null;
+  waiting_for_power: boolean;
+}
+
+// How many jobs of each category run at once (1 to 8); transcriptions count as conversions
+export interface JobLimits {
+  conversions: number;
+  downloads: number;
+  scans: number;
+}
+
+// What heavy jobs do on battery at or below `below_percent` (100 for whenever on battery)
+export interface BatterySettings {
+  policy: 'run' | 'throttle' | 'defer';
+  below_percent: number;
+}
+
+// get_power_status / "power-changed"
+export interface PowerState {
+  on_battery: boolean;
+  battery_percent: number | null;
+  job_power: JobPower;
+}
+
+// Project file generators (.gitignore / .editorconfig)
+export interface GeneratedFile {
+  content: string;
+  path: string | null;
+  fetched: string[];
+}
+
+// Locale formatting preview (numbers, currency, dates, plural categories)
+export interface LocalePreview {
+  requested: string;
+  locale: string;
+  name: string;
+  numbers: {
+    input: string;
+    formatted: string;
+    currency: string;
+    cardinal: string;
+    ordinal: string | null;
+  }[];
+  dates: { input: string; short: string; long: string; time: string }[];
+  invalid: string[];
+}
+
+// Problems found on launch (get_startup_health / startup-health)
+export interface StartupIssue {
+  component: "hotkey" | "tray" | "binary";
+  message: string;
+  detail: string;
+}
+
+// Job workspaces of crashed sessions removed on launch
+export interface WorkspaceCleanup {
+  removed: number;
+  reclaimed_bytes: number;
+}
+
+export interface StartupHealth {
+  checked: boolean;
+  issues: StartupIssue[];
+  leftovers: WorkspaceCleanup;
+}
@@ -1,15 +1,34 @@
 import type {
-  ColorFormats,
+  WordDefinition,
+  LanguageDetection,
   UnitConversionResult,
+  UnitResult,
+  TimeEntry,
+  HabitStreak,
+  ScannedCode,
+  Country,
+  HolidayCalendar,
+  SunReport,
+  TranslationHistoryEntry,
+  TimeReport,
+  TimeReportRange,
   PartialUnitSuggestion,
   PartialCurrencySuggestion,
   CurrencyQuery,
+  CurrencyResult,
   QRCodeType,
   QRCodeData,
+  QrPayload,
   GitHubUrlInfo,
   YouTubeUrlInfo,
+  ReferenceKind,
+  NetworkStatus,
+  DiscoveredDevice,
+  StartupHealth,
+  TransferStats,
+  ExternalWindow,
 } from "./types";
-import { UNIT_CATEGORIES, DEFAULT_UNIT_TARGETS, CURRENCY_ALIASES } from "./constants";
+import { UNIT_CATEGORIES, DEFAULT_UNIT_TARGETS, CURRENCY_ALIASES, CRYPTO_SYMBOLS } from "./constants";
 
 // ============ Color Conversion Utilities ============
 
@@ -174,27 +193,6 @@ export function rgbToOklch(r: number, g: number, b: number): { l: number; c: num
   };
 }
 
-export function convertHexToFormats(hex: string): ColorFormats {
-  const rgb = hexToRgb(hex);
-  const hsl = rgbToHsl(rgb.r, rgb.g, rgb.b);
-  const hsv = rgbToHsv(rgb.r, rgb.g, rgb.b);
-  const xyz = rgbToXyz(rgb.r, rgb.g, rgb.b);
-  const lab = rgbToLab(rgb.r, rgb.g, rgb.b);
-  const cmyk = rgbToCmyk(rgb.r, rgb.g, rgb.b);
-  const oklch = rgbToOklch(rgb.r, rgb.g, rgb.b);
-
-  return {
-    hex: hex.toUpperCase(),
-    rgb: `rgb(${rgb.r}, ${rgb.g}, ${rgb.b})`,
-    hsl: `hsl(${hsl.h}, ${hsl.s}%, ${hsl.l}%)`,
-    hsv: `hsv(${hsv.h}, ${hsv.s}%, ${hsv.v}%)`,
-    oklch: `oklch(${oklch.l}% ${oklch.c} ${oklch.h})`,
-    cmyk: `cmyk(${cmyk.c}%, ${cmyk.m}%, ${cmyk.y}%, ${cmyk.k}%)`,
-    lab: `lab(${lab.l}% ${lab.a} ${lab.b})`,
-    xyz: `xyz(${xyz.x}%, ${xyz.y}%, ${xyz.z}%)`,
-  };
-}
-
 // ============ Reverse Color Conversion Utilities ============
 
 export function hslToRgb(h: number, s: number, l: number): { r: number; g: number; b: number } {
@@ -518,16 +516,42 @@ export function parseColorQuery(query: string): ColorConversionResult | null {
 
 // ============ QR Code Content Generator ============
 
+// Payload for the types Rust validates (build_qr_payload); null for the others, or while
+// the form is still empty
+export function qrPayloadFor(type: QRCodeType, data: QRCodeData): QrPayload | null {
+  switch (type) {
+    case "wifi":
+      return data.wifi.ssid
+        ? { type: "wifi", ssid: data.wifi.ssid, password: data.wifi.password, security: data.wifi.encryption }
+        : null;
+    case "vcard": {
+      const v = data.vcard;
+      return v.firstName || v.lastName || v.phone || v.email
+        ? { type: "vcard", first_name: v.firstName, last_name: v.lastName, phone: v.phone, email: v.email }
+        : null;
+    }
+    case "location":
+      return data.location.latitude || data.location.longitude
+        ? { type: "geo", latitude: data.location.latitude, longitude: data.location.longitude }
+        : null;
+    case "email":
+      return data.email.email ? { type: "mailto", email: data.email.email, subject: data.email.subject } : null;
+    default:
+      return null;
+  }
+}
+
 export function generateQRContent(type: QRCodeType, data: QRCodeData): string {
   switch (type) {
     case "url":
       return data.url.url;
     case "wifi": {
       const { ssid, password, encryption } = data.wifi;
-      return `WIFI:T:${encryption};S:${ssid};P:${password};;`;
+      return ssid ? `WIFI:T:${encryption};S:${ssid};P:${password};;` : "";
     }
     case "email": {
       const { email, subject } = data.email;
+      if (!email) return "";
       const params = new URLSearchParams();
       if (subject) params.set("subject", subject);
       const queryString = params.toString();
@@ -539,6 +563,7 @@ export function generateQRContent(type: QRCodeType, data: QRCodeData): string {
       return data.text.text;
     case "vcard": {
       const v = data.vcard;
+      if (!v.firstName && !v.lastName && !v.phone && !v.email) return "";
       return [
         "BEGIN:VCARD",
         "VERSION:3.0",
@@ -551,7 +576,7 @@ export function generateQRContent(type: QRCodeType, data: QRCodeData): string {
     }
     case "location": {
       const { latitude, longitude } = data.location;
-      return `geo:${latitude},${longitude}`;
+      return latitude || longitude ? `geo:${latitude},${longitude}` : "";
     }
     case "event": {
       const e = data.event;
@@ -594,6 +619,26 @@ export function getCanonicalUnit(input: string): string | null {
   return null;
 }
 
+// Parse reference lookups like "http 404", "mime svg", "port 5432", "entity copy" or "&copy;"
+export function parseReferenceQuery(query: string): { kind: ReferenceKind; query: string } | null {
+  const trimmed = query.trim();
+  if (/^&\w+;?$/.test(trimmed)) {
+    return { kind: "html_entity", query: trimmed };
+  }
+
+  const match = trimmed.match(/^(http|status|mime|port|entity)\s+(.+)$/i);
+  if (!match) return null;
+
+  const kinds: Record<string, ReferenceKind> = {
+    http: "http_status",
+    status: "http_status",
+    mime: "mime_type",
+    port: "port",
+    entity: "html_entity",
+  };
+  return { kind: kinds[match[1].toLowerCase()], query: match[2] };
+}
+
 // Parse unit conversion query like "10 feet to meters"
 export function parseUnitQuery(query: string): UnitConversionResult | null {
   const cleaned = query.toLowerCase().trim();
@@ -645,6 +690,47 @@ export function parseUnitQuery(query: string): UnitConversionResult | null {
   return null;
 }
 
+// "3pm PST in Berlin", "tomorrow 9:00 new york to tokyo", "time in tokyo" for convert_time_query
+export function looksLikeTimeQuery(query: string): boolean {
+  return /^(?:now|time|noon|midnight|today|tomorrow|yesterday|\d{4}-\d{2}-\d{2}|\d{1,2}(?::\d{2}){0,2}\s*[ap]m|\d{1,2}:\d{2})\b.*\s(?:in|to)\s+\S/i.test(
+    query.trim()
+  );
+}
+
+// Expressions for evaluate_expression that evaluateExpression can't do: hex / binary literals
+// and results, bit operations, percentages, functions, and sums of units or currencies
+export function looksLikeExpression(query: string): boolean {
+  const q = query.trim().toLowerCase();
+  if (!/\d/.test(q)) return false;
+  return (
+    /\b0[xbo][0-9a-f]/.test(q) ||
+    /[&|~%]|<<|>>|\b(?:xor|mod|of)\b/.test(q) ||
+    /\b(?:sqrt|abs|round|floor|ceil|ln|log2?|exp|sin|cos|tan)\s*\(/.test(q) ||
+    /\s(?:in|to|as)\s+(?:hex|bin|oct|dec|binary|octal|decimal|hexadecimal)$/.test(q) ||
+    /\d\s*[a-z°]+\s*[-+*/]\s*[\d(]/.test(q)
+  );
+}
+
+// The timestamp in "1700000000", "1700000000123" or "epoch 1700000000", for parse_epoch
+export function parseEpochQuery(query: string): string | null {
+  const match = query.trim().match(/^(?:(?:epoch|unix|timestamp)\s+(-?\d+(?:\.\d+)?)|(\d{10}|\d{13}|\d{16}|\d{19}))$/i);
+  return match ? (match[1] ?? match[2]) : null;
+}
+
+// "<amount> <unit> to <unit>" for the backend's unit table, e.g. "3 sq ft in m2" or "5 kwh to mj"
+export function looksLikeUnitQuery(query: string): boolean {
+  return /^[-+]?[\d.,]+\s*\S.*\s(?:to|in|into|as)\s+\S/i.test(query.trim());
+}
+
+// "8.047 km", as the quick result of convert_unit_query
+export function formatUnitResult(result: UnitResult): string {
+  const formatted = result.result.toLocaleString("en-US", {
+    minimumFractionDigits: 0,
+    maximumFractionDigits: Math.abs(result.result) < 1 ? 6 : 3,
+  });
+  return `${formatted} ${result.to_symbol}`;
+}
+
 // Parse partial unit query like "10 fahr" and suggest completion
 export function parsePartialUnitQuery(query: string): PartialUnitSuggestion | null {
   const cleaned = query.toLowerCase().trim();
@@ -740,6 +826,42 @@ export function parsePartialCurrencyQuery(query: string): PartialCurrencySuggest
   };
 }
 
+// 2 decimals, or 6 significant digits below 1 so small coin amounts don't round to 0.00
+export function formatCurrencyAmount(value: number, grouping = true): string {
+  if (value !== 0 && Math.abs(value) < 1) {
+    return value.toLocaleString("en-US", { maximumSignificantDigits: 6, useGrouping: grouping });
+  }
+  return value.toLocaleString("en-US", {
+    minimumFractionDigits: 2,
+    maximumFractionDigits: 2,
+    useGrouping: grouping,
+  });
+}
+
+// " (offline, rates of 2026-10-14)" when converted with cached rates
+export function currencyStaleNote(result: CurrencyResult): string {
+  return result.is_stale ? ` (offline, rates of ${result.as_of})` : "";
+}
+
+// " · 7d +0.4% · 30d -1.2% · 1y +3.0%", for the changes that are known
+export function currencyChangeNote(result: CurrencyResult): string {
+  const changes: [string, number | null][] = [
+    ["7d", result.change_7d],
+    ["30d", result.change_30d],
+    ["1y", result.change_365d],
+  ];
+  return changes
+    .filter((change): change is [string, number] => change[1] !== null)
+    .map(([label, percent]) => ` · ${label} ${percent >= 0 ? "+" : ""}${percent.toFixed(1)}%`)
+    .join("");
+}
+
+// "92.14 EUR · 79.30 GBP · 0.00091 BTC", for an amount in the favorite currencies
+export function formatCurrencyResults(results: CurrencyResult[]): string {
+  const line = results.map((r) => `${formatCurrencyAmount(r.result)} ${r.to}`).join(" · ");
+  return results.length > 0 ? `${line}${currencyStaleNote(results[0])}` : line;
+}
+
 // Parse currency query like "20 usd in yen" or "100 eur to usd"
 export function parseCurrencyQuery(query: string): CurrencyQuery | null {
   const cleaned = query.toLowerCase().trim();
@@ -757,8 +879,9 @@ export function parseCurrencyQuery(query: string): CurrencyQuery | null {
   const from = CURRENCY_ALIASES[fromInput] || fromInput.toUpperCase();
   const to = CURRENCY_ALIASES[toInput] || toInput.toUpperCase();
 
-  // Basic validation - currency codes are 3 letters
-  if (from.length !== 3 || to.length !== 3) return null;
+  // Basic validation - currency codes are 3 letters, coin symbols 2 to 5
+  if (!/^[A-Z]{2,5}$/.test(from) || !/^[A-Z]{2,5}$/.test(to)) return null;
+  if ((from.length !== 3 && !CRYPTO_SYMBOLS.has(from)) || (to.length !== 3 && !CRYPTO_SYMBOLS.has(to))) return null;
   if (from === to) return null;
 
   return { amount, from, to };
@@ -822,12 +945,107 @@ export function getFileExtension(filename: string): string {
 export function formatFileSize(bytes: number): string {
   if (bytes < 1024) return `${bytes} B`;
   if (bytes < 1024 * 1024) return `${(bytes / 1024).toFixed(1)} KB`;
-  return `${(bytes / (1024 * 1024)).toFixed(2)} MB`;
+  if (bytes < 1024 * 1024 * 1024) return `${(bytes / (1024 * 1024)).toFixed(2)} MB`;
+  return `${(bytes / (1024 * 1024 * 1024)).toFixed(2)} GB`;
+}
+
+// One line per startup problem with the underlying error, for copying into a bug report
+export function formatStartupHealth(health: StartupHealth): string {
+  const leftovers = formatLeftoverCleanup(health);
+  if (health.issues.length === 0) {
+    return ["All startup checks passed", leftovers].filter(Boolean).join("\n");
+  }
+  return [...health.issues.map((issue) => `${issue.message}\n  ${issue.component}: ${issue.detail}`), leftovers]
+    .filter(Boolean)
+    .join("\n");
+}
+
+// What startup removed of the temp files a crashed session left behind, if anything
+export function formatLeftoverCleanup(health: StartupHealth): string | null {
+  if (health.leftovers.removed === 0) {
+    return null;
+  }
+  return `Cleaned up after an earlier crash, freed ${formatFileSize(health.leftovers.reclaimed_bytes)}`;
+}
+
+// "50gb", "1.5 GB", "500 MiB" in bytes (binary units, like formatFileSize)
+export function parseByteSize(text: string): number | null {
+  const match = text.trim().match(/^([\d.]+)\s*([kmgt]?)i?b?$/i);
+  if (!match) return null;
+  const value = parseFloat(match[1]);
+  const power = " kmgt".indexOf(match[2].toLowerCase() || " ");
+  const bytes = Math.round(value * 1024 ** power);
+  return isFinite(bytes) && bytes > 0 ? bytes : null;
+}
+
+// "This month: 1.20 GB down, 3.0 KB up · YouTube Downloader 1.10 GB · ..." with the cap
+export function formatTransferStats(stats: TransferStats, tools: { id: string; name: string }[]): string {
+  const name = (id: string) => tools.find((t) => t.id === id)?.name ?? id;
+  const period = stats.from === stats.to ? "Today" : `Since ${stats.from}`;
+  const parts = [
+    `${period}: ${formatFileSize(stats.total.downloaded)} down, ${formatFileSize(stats.total.uploaded)} up`,
+    ...stats.tools.slice(0, 4).map((t) => `${name(t.tool)} ${formatFileSize(t.downloaded + t.uploaded)}`),
+  ];
+  if (stats.cap) {
+    parts.push(`${formatFileSize(stats.month_total)} of the ${formatFileSize(stats.cap.monthly_bytes)} monthly cap`);
+  }
+  return parts.join(" · ");
 }
 
 // ============ Video Converter Utilities ============
 
 // Format video duration as MM:SS or HH:MM:SS
+// One line per interface plus gateway, DNS, VPN and proxy, for copying into a bug report
+export function formatNetworkSummary(status: NetworkStatus): string {
+  const lines = status.interfaces
+    .filter((i) => !i.is_loopback && i.is_up)
+    .map((i) => `${i.name}: ${i.addresses.join(", ") || "no address"}${i.mac_address ? ` (${i.mac_address})` : ""}`);
+  lines.push(`Gateway: ${status.gateway ? `${status.gateway} via ${status.gateway_interface}` : "none"}`);
+  lines.push(`DNS: ${status.dns_servers.join(", ") || "none"}`);
+  if (status.vpn_interfaces.length > 0) {
+    lines.push(`VPN: ${status.vpn_interfaces.join(", ")}`);
+  }
+  if (status.proxy) {
+    lines.push(`Proxy: ${status.proxy}`);
+  }
+  return lines.join("\n");
+}
+
+// One line per LAN device: name, address, MAC and vendor, services
+export function formatDiscoveredDevices(devices: DiscoveredDevice[]): string {
+  return devices
+    .map((d) => {
+      const hardware = d.mac ? ` ${d.mac}${d.vendor ? ` (${d.vendor})` : ""}` : "";
+      const services = d.services.length > 0 ? ` [${d.services.join(", ")}]` : "";
+      return `${d.name ?? "Unknown"} - ${d.ip}${hardware}${services}`;
+    })
+    .join("\n");
+}
+
+// "Not sure: 42% German, maybe Dutch or Danish", or null when detection is reliable
+export function formatDetectionWarning(detection: LanguageDetection | null): string | null {
+  if (!detection || detection.reliable) {
+    return null;
+  }
+  const percent = Math.round(detection.confidence * 100);
+  const others = detection.alternatives.slice(0, 2).map((a) => a.language);
+  const maybe = others.length > 0 ? `, maybe ${others.join(" or ")}` : "";
+  return `Not sure: ${percent}% ${detection.language}${maybe}`;
+}
+
+// "bank /bæŋk/ · noun: An institution where one can deposit money. (lender, treasury) · verb: ..."
+// with the first `senses` definitions of each part of speech
+export function formatDefinition(definition: WordDefinition, senses = 1): string {
+  const head = definition.phonetic ? `${definition.word} ${definition.phonetic}` : definition.word;
+  const meanings = definition.meanings.map((m) => {
+    const text = m.senses.slice(0, senses).map((s) => s.definition).join(" / ");
+    const example = m.senses[0]?.example ? ` "${m.senses[0].example}"` : "";
+    const synonyms = m.synonyms.length > 0 ? ` (${m.synonyms.slice(0, 4).join(", ")})` : "";
+    return `${m.part_of_speech}: ${text}${example}${synonyms}`;
+  });
+  return [head, ...meanings].join(" · ");
+}
+
 export function formatDuration(seconds: number): string {
   const hrs = Math.floor(seconds / 3600);
   const mins = Math.floor((seconds % 3600) / 60);
@@ -839,6 +1057,120 @@ export function formatDuration(seconds: number): string {
   return `${mins}:${secs.toString().padStart(2, '0')}`;
 }
 
+// Text level meter, "▮▮▮▮▮▯▯▯▯▯" for half scale
+export function formatLevelMeter(level: number, width = 10): string {
+  const filled = Math.round(Math.min(Math.max(level, 0), 1) * width);
+  return "▮".repeat(filled) + "▯".repeat(width - filled);
+}
+
+// "Water: 5 day streak" / "Water: done, 6 day streak"
+export function formatHabitStreak(streak: HabitStreak): string {
+  const days = `${streak.current} day${streak.current === 1 ? "" : "s"}`;
+  return `${streak.name}: ${streak.done_today ? "done, " : ""}${days} streak`;
+}
+
+// What to copy for a scanned QR code (the password for Wi-Fi) and a status line describing it
+export function describeScannedCode(code: ScannedCode): { copy: string; status: string } {
+  switch (code.kind) {
+    case "wifi":
+      return code.password
+        ? { copy: code.password, status: `Wi-Fi "${code.ssid}" (${code.security}) - password copied` }
+        : { copy: code.ssid, status: `Wi-Fi "${code.ssid}" (open network) - name copied` };
+    case "url":
+      return { copy: code.text, status: `Link copied: ${code.text}` };
+    default:
+      return { copy: code.text, status: `Copied: ${code.text.length > 60 ? `${code.text.slice(0, 60)}...` : code.text}` };
+  }
+}
+
+// "🇩🇪 Germany (DE / DEU) · +49 · EUR"
+export function formatCountry(country: Country): string {
+  return `${country.flag} ${country.name} (${country.code} / ${country.code3}) · ${country.calling_code} · ${country.currency}`;
+}
+
+// Country of the system locale ("de-AT" -> "AT"), if the locale names one
+export function localeCountry(): string | null {
+  try {
+    return new Intl.Locale(navigator.language).maximize().region ?? null;
+  } catch {
+    return null;
+  }
+}
+
+// "🇩🇪 Next: Oct 31 Reformation Day (regional), Dec 25 Christmas Day" - the upcoming holidays
+// this year, or the first ones of another year
+export function formatHolidays(calendar: HolidayCalendar, limit = 3): string {
+  const now = new Date();
+  const today = `${now.getFullYear()}-${String(now.getMonth() + 1).padStart(2, "0")}-${String(now.getDate()).padStart(2, "0")}`;
+  const upcoming = calendar.year === now.getFullYear() ? calendar.holidays.filter((h) => h.date >= today) : [];
+  const shown = (upcoming.length > 0 ? upcoming : calendar.holidays).slice(0, limit);
+  const items = shown.map((h) => {
+    const date = new Date(`${h.date}T00:00:00`).toLocaleDateString([], { month: "short", day: "numeric" });
+    return `${date} ${h.name}${h.global ? "" : " (regional)"}`;
+  });
+  const label = upcoming.length > 0 ? "Next" : `${calendar.year}`;
+  const offline = calendar.cached ? " (offline copy)" : "";
+  return `${calendar.country.flag} ${label}: ${items.join(", ") || "no holidays"}${offline}`;
+}
+
+// "Berlin, Germany: ☀ 07:41 - 18:12 (10h 31m), golden hour 17:20" - in the place's own time zone
+// when it's known, otherwise the system's
+export function formatSunTimes(report: SunReport): string {
+  const time = (ts: number | null) =>
+    ts === null
+      ? "--:--"
+      : new Date(ts * 1000).toLocaleTimeString([], {
+          hour: "2-digit",
+          minute: "2-digit",
+          timeZone: report.location.timezone ?? undefined,
+        });
+  const name = report.location.name;
+  if (report.polar === "day") return `${name}: the sun doesn't set (midnight sun)`;
+  if (report.polar === "night") {
+    const dawn = report.dawn !== null ? `, twilight ${time(report.dawn)} - ${time(report.dusk)}` : "";
+    return `${name}: the sun doesn't rise (polar night)${dawn}`;
+  }
+  const golden = report.golden_hour_evening ? `, golden hour ${time(report.golden_hour_evening.start)}` : "";
+  return `${name}: ☀ ${time(report.sunrise)} - ${time(report.sunset)} (${formatHoursMinutes(report.day_length)})${golden}`;
+}
+
+// "Good morning → Guten Morgen (German) · Thank you → Danke (German)"
+export function formatTranslationHistory(entries: TranslationHistoryEntry[], limit = 3): string {
+  const clip = (text: string) => (text.length > 30 ? text.substring(0, 27) + "..." : text);
+  return entries
+    .slice(0, limit)
+    .map((e) => `${clip(e.source_text)} → ${clip(e.translated_text)} (${e.target_language})`)
+    .join(" · ");
+}
+
+// Tracked time as "2h 05m" / "45m"
+export function formatHoursMinutes(seconds: number): string {
+  const hrs = Math.floor(seconds / 3600);
+  const mins = Math.floor((seconds % 3600) / 60);
+  return hrs > 0 ? `${hrs}h ${mins.toString().padStart(2, '0')}m` : `${mins}m`;
+}
+
+// Tracked seconds of a stopped entry, without its idle periods
+export function timeEntryDuration(entry: TimeEntry): number {
+  const idle = entry.idle.reduce((sum, p) => sum + (p.end - p.start), 0);
+  return Math.max(0, (entry.end ?? Math.floor(Date.now() / 1000)) - entry.start - idle);
+}
+
+// One-line time report summary: "This week: 6h 30m (Ops 4h 00m, No project 2h 30m)"
+export function formatTimeReport(report: TimeReport): string {
+  const titles: Record<TimeReportRange, string> = {
+    today: "Today",
+    yesterday: "Yesterday",
+    week: "This week",
+    last_week: "Last week",
+  };
+  const projects = report.projects
+    .filter((p) => p.seconds >= 60)
+    .map((p) => `${p.project || "No project"} ${formatHoursMinutes(p.seconds)}`);
+  const summary = `${titles[report.range]}: ${formatHoursMinutes(report.total_seconds)}`;
+  return projects.length > 0 ? `${summary} (${projects.join(", ")})` : summary;
+}
+
 // Estimate output file size based on bitrate, duration, and format
 export function estimateOutputSize(
   duration: number,
@@ -1002,3 +1334,16 @@ export function parseYouTubeUrl(url: string): YouTubeUrlInfo | null {
 
   return null;
 }
+
+// The window whose title best matches what was typed: the whole title, its start, then anywhere in it
+export function findExternalWindow(windows: ExternalWindow[], wanted: string): ExternalWindow | null {
+  const needle = wanted.trim().toLowerCase();
+  if (!needle) return null;
+  const title = (w: ExternalWindow) => w.title.toLowerCase();
+  return (
+    windows.find((w) => title(w) === needle) ??
+    windows.find((w) => title(w).startsWith(needle)) ??
+    windows.find((w) => title(w).includes(needle)) ??
+    null
+  );
+}